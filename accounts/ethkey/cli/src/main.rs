@@ -31,7 +31,7 @@ use std::num::ParseIntError;
 use std::{env, fmt, process, io, sync};
 
 use docopt::Docopt;
-use ethkey::{Brain, BrainPrefix, Prefix, brain_recover};
+use ethkey::{Brain, BrainPrefix, brain_recover, generate_vanity};
 use parity_crypto::publickey::{KeyPair, Random, Error as EthkeyError, Generator, sign, verify_public, verify_address};
 use rustc_hex::{FromHex, FromHexError};
 
@@ -219,25 +219,20 @@ fn execute<S, I>(command: I) -> Result<String, Error> where I: IntoIterator<Item
 			}
 		} else if args.cmd_prefix {
 			let prefix = args.arg_prefix.from_hex()?;
-			let brain = args.flag_brain;
-			in_threads(move || {
-				let iterations = 1024;
-				let prefix = prefix.clone();
-				move || {
+			if args.flag_brain {
+				in_threads(move || {
+					let iterations = 1024;
 					let prefix = prefix.clone();
-					let res = if brain {
-						let mut brain = BrainPrefix::new(prefix, iterations, BRAIN_WORDS);
+					move || {
+						let mut brain = BrainPrefix::new(prefix.clone(), iterations, BRAIN_WORDS);
 						let result = brain.generate();
 						let phrase = format!("recovery phrase: {}", brain.phrase());
-						result.map(|keypair| (keypair, Some(phrase)))
-					} else {
-						let result = Prefix::new(prefix, iterations).generate();
-						result.map(|res| (res, None))
-					};
-
-					Ok(res.map(Some).unwrap_or(None))
-				}
-			})?
+						Ok(result.map(|keypair| Some((keypair, Some(phrase)))).unwrap_or(None))
+					}
+				})?
+			} else {
+				(generate_vanity(&prefix, 0)?, None)
+			}
 		} else {
 			return Ok(format!("{}", USAGE))
 		};
@@ -0,0 +1,164 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::{error, fmt};
+
+use parity_crypto::Keccak256;
+use parity_crypto::publickey::{Address, Error, KeyPair, Public, Secret, public_to_address};
+
+/// Address checksum verification error.
+#[derive(Debug, PartialEq)]
+pub enum ChecksumError {
+	/// Not a hex encoded 20 byte address.
+	InvalidAddress,
+	/// Letter case doesn't match the EIP-55 checksum.
+	InvalidChecksum,
+}
+
+impl fmt::Display for ChecksumError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			ChecksumError::InvalidAddress => write!(f, "Invalid address"),
+			ChecksumError::InvalidChecksum => write!(f, "Invalid address checksum"),
+		}
+	}
+}
+
+impl error::Error for ChecksumError {}
+
+/// Derives the address of a public key.
+pub fn address_from_public(public: &Public) -> Address {
+	public_to_address(public)
+}
+
+/// Derives the address of a secret key.
+pub fn address_from_secret(secret: &Secret) -> Result<Address, Error> {
+	KeyPair::from_secret(secret.clone()).map(|keypair| keypair.address())
+}
+
+/// Formats an address with its EIP-55 mixed-case checksum, `0x` prefixed.
+pub fn to_checksum(address: &Address) -> String {
+	let hex = format!("{:x}", address);
+	let hash = hex.as_bytes().keccak256();
+
+	let mut checksummed = String::with_capacity(42);
+	checksummed.push_str("0x");
+	for (i, c) in hex.chars().enumerate() {
+		let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+		if nibble >= 8 {
+			checksummed.push(c.to_ascii_uppercase());
+		} else {
+			checksummed.push(c);
+		}
+	}
+	checksummed
+}
+
+/// Parses an address, optionally `0x` prefixed, which has to carry a valid EIP-55 checksum.
+pub fn verify_checksum(address: &str) -> Result<Address, ChecksumError> {
+	let hex = if address.starts_with("0x") { &address[2..] } else { address };
+	if hex.len() != 40 {
+		return Err(ChecksumError::InvalidAddress);
+	}
+
+	let parsed: Address = hex.parse().map_err(|_| ChecksumError::InvalidAddress)?;
+	if to_checksum(&parsed)[2..] == *hex {
+		Ok(parsed)
+	} else {
+		Err(ChecksumError::InvalidChecksum)
+	}
+}
+
+/// Parses a direct or basic ICAP encoded address (`XE` followed by check digits and the address in base 36).
+pub fn from_icap(icap: &str) -> Result<Address, ChecksumError> {
+	let icap = icap.to_ascii_uppercase();
+	if (icap.len() != 34 && icap.len() != 35) || !icap.starts_with("XE") || !icap.chars().all(|c| c.is_ascii_alphanumeric()) {
+		return Err(ChecksumError::InvalidAddress);
+	}
+
+	// IBAN check: the number formed by moving the first 4 characters to the end, with letters
+	// replaced by 10 to 35, is 1 modulo 97.
+	let remainder = icap[4..].chars().chain(icap[..4].chars()).fold(0, |remainder, c| {
+		let value = c.to_digit(36).expect("checked to be alphanumeric above; qed");
+		let shift = if value < 10 { 10 } else { 100 };
+		(remainder * shift + value) % 97
+	});
+	if remainder != 1 {
+		return Err(ChecksumError::InvalidChecksum);
+	}
+
+	let mut address = [0u8; 20];
+	for c in icap[4..].chars() {
+		let mut carry = c.to_digit(36).expect("checked to be alphanumeric above; qed");
+		for byte in address.iter_mut().rev() {
+			let value = u32::from(*byte) * 36 + carry;
+			*byte = value as u8;
+			carry = value >> 8;
+		}
+		if carry != 0 {
+			return Err(ChecksumError::InvalidAddress);
+		}
+	}
+	Ok(Address::from(address))
+}
+
+#[cfg(test)]
+mod tests {
+	use parity_crypto::publickey::{Generator, Random};
+	use super::{ChecksumError, address_from_public, address_from_secret, from_icap, to_checksum, verify_checksum};
+
+	const CHECKSUMMED: [&str; 4] = [
+		"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+		"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+		"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+		"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+	];
+
+	#[test]
+	fn checksums_addresses() {
+		for address in CHECKSUMMED.iter() {
+			let parsed = address[2..].to_lowercase().parse().unwrap();
+			assert_eq!(to_checksum(&parsed), *address);
+			assert_eq!(verify_checksum(address), Ok(parsed));
+			assert_eq!(verify_checksum(&address[2..]), Ok(parsed));
+		}
+	}
+
+	#[test]
+	fn rejects_invalid_checksums() {
+		assert_eq!(verify_checksum("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"), Err(ChecksumError::InvalidChecksum));
+		assert_eq!(verify_checksum("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"), Err(ChecksumError::InvalidChecksum));
+		assert_eq!(verify_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe"), Err(ChecksumError::InvalidAddress));
+		assert_eq!(verify_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"), Err(ChecksumError::InvalidAddress));
+	}
+
+	#[test]
+	fn parses_icap() {
+		let address = "00c5496aee77c1ba1f0854206a26dda82a81d6d8".parse().unwrap();
+		assert_eq!(from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS"), Ok(address));
+		assert_eq!(from_icap("xe7338o073kygtwwzn0f2wz0r8px5zppzs"), Ok(address));
+		assert_eq!(from_icap("XE7438O073KYGTWWZN0F2WZ0R8PX5ZPPZS"), Err(ChecksumError::InvalidChecksum));
+		assert_eq!(from_icap("XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZ"), Err(ChecksumError::InvalidAddress));
+		assert_eq!(from_icap("0x00c5496aee77c1ba1f0854206a26dda82a81d6d8"), Err(ChecksumError::InvalidAddress));
+	}
+
+	#[test]
+	fn derives_addresses() {
+		let keypair = Random.generate().unwrap();
+		assert_eq!(address_from_public(keypair.public()), keypair.address());
+		assert_eq!(address_from_secret(keypair.secret()).unwrap(), keypair.address());
+	}
+}
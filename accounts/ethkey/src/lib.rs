@@ -17,6 +17,7 @@
 // #![warn(missing_docs)]
 
 extern crate edit_distance;
+extern crate num_cpus;
 extern crate parity_crypto;
 extern crate parity_wordlist;
 extern crate serde;
@@ -26,15 +27,19 @@ extern crate log;
 #[macro_use]
 extern crate serde_derive;
 
+mod address;
 mod brain;
 mod brain_prefix;
 mod password;
 mod prefix;
+mod vanity;
 
 pub mod brain_recover;
 
 pub use self::parity_wordlist::Error as WordlistError;
+pub use self::address::{ChecksumError, address_from_public, address_from_secret, from_icap, to_checksum, verify_checksum};
 pub use self::brain::Brain;
 pub use self::brain_prefix::BrainPrefix;
 pub use self::password::Password;
-pub use self::prefix::Prefix;
\ No newline at end of file
+pub use self::prefix::Prefix;
+pub use self::vanity::generate_vanity;
@@ -0,0 +1,94 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, mpsc};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use num_cpus;
+use parity_crypto::publickey::{Random, Generator, KeyPair, Error};
+
+/// Generates a keypair whose address starts with `prefix` ("vanity address"), searching in `threads`
+/// threads at once, or one per CPU if `threads` is 0. Blocks until a keypair is found.
+pub fn generate_vanity(prefix: &[u8], threads: usize) -> Result<KeyPair, Error> {
+	if prefix.len() > 20 {
+		return Err(Error::Custom("Prefix is longer than an address".into()));
+	}
+
+	let threads = if threads == 0 { num_cpus::get() } else { threads };
+	let found = Arc::new(AtomicBool::new(false));
+	let (tx, rx) = mpsc::channel();
+
+	let mut handles = Vec::with_capacity(threads);
+	for _ in 0..threads {
+		let prefix = prefix.to_vec();
+		let found = found.clone();
+		let tx = tx.clone();
+		let handle = thread::Builder::new()
+			.name("vanity".into())
+			.spawn(move || {
+				if let Some(result) = search(&prefix, &found).transpose() {
+					found.store(true, Ordering::SeqCst);
+					// Only the first result is of interest.
+					let _ = tx.send(result);
+				}
+			})
+			.map_err(|e| Error::Custom(format!("Can't start vanity search: {}", e)));
+
+		match handle {
+			Ok(handle) => handles.push(handle),
+			Err(err) => {
+				found.store(true, Ordering::SeqCst);
+				return Err(err);
+			}
+		}
+	}
+	drop(tx);
+
+	let result = rx.recv().map_err(|_| Error::Custom("Vanity search stopped without a result".into()));
+	found.store(true, Ordering::SeqCst);
+	for handle in handles {
+		let _ = handle.join();
+	}
+	result?
+}
+
+fn search(prefix: &[u8], found: &AtomicBool) -> Result<Option<KeyPair>, Error> {
+	while !found.load(Ordering::Relaxed) {
+		let keypair = Random.generate()?;
+		if keypair.address().as_bytes().starts_with(prefix) {
+			return Ok(Some(keypair));
+		}
+	}
+	Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+	use generate_vanity;
+
+	#[test]
+	fn vanity_generator() {
+		let prefix = vec![0xab];
+		let keypair = generate_vanity(&prefix, 2).unwrap();
+		assert!(keypair.address().as_bytes().starts_with(&prefix));
+	}
+
+	#[test]
+	fn rejects_prefix_longer_than_address() {
+		assert!(generate_vanity(&[0u8; 21], 1).is_err());
+	}
+}
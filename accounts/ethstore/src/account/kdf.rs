@@ -14,7 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
-use json;
+use std::time::{Duration, Instant};
+use {json, crypto};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Prf {
@@ -44,6 +45,42 @@ pub enum Kdf {
 	Scrypt(Scrypt),
 }
 
+/// Key derivation settings used when encrypting new key files.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum KdfParams {
+	/// PBKDF2 with HMAC-SHA256 and the given number of iterations.
+	Pbkdf2 {
+		/// Number of iterations.
+		iterations: u32,
+	},
+	/// Scrypt with the given work factors.
+	Scrypt {
+		/// CPU/memory cost, must be a power of two.
+		n: u32,
+		/// Block size.
+		r: u32,
+		/// Parallelization.
+		p: u32,
+	},
+}
+
+impl KdfParams {
+	/// Derives the encryption and MAC halves of the key for `password`.
+	pub fn derive(&self, password: &[u8], salt: &[u8]) -> Result<(Vec<u8>, Vec<u8>), crypto::Error> {
+		match *self {
+			KdfParams::Pbkdf2 { iterations } => Ok(crypto::derive_key_iterations(password, salt, iterations)),
+			KdfParams::Scrypt { n, r, p } => Ok(crypto::scrypt::derive_key(password, salt, n, p, r)?),
+		}
+	}
+
+	/// Measures how long a single key derivation, and so unlocking an account, takes on this machine.
+	pub fn estimate_unlock_time(&self) -> Result<Duration, crypto::Error> {
+		let start = Instant::now();
+		self.derive(b"unlock time estimate", &[0u8; 32])?;
+		Ok(start.elapsed())
+	}
+}
+
 impl From<json::Prf> for Prf {
 	fn from(json: json::Prf) -> Self {
 		match json {
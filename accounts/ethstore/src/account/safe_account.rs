@@ -18,7 +18,7 @@ use crypto::publickey::{KeyPair, sign, Address, Signature, Message, Public, Secr
 use ethkey::Password;
 use crypto::publickey::ecdh::agree;
 use {json, Error};
-use account::Version;
+use account::{KdfParams, Version};
 use crypto;
 use super::crypto::Crypto;
 
@@ -63,11 +63,23 @@ impl SafeAccount {
 		iterations: u32,
 		name: String,
 		meta: String
+	) -> Result<Self, crypto::Error> {
+		SafeAccount::create_with_kdf(keypair, id, password, &KdfParams::Pbkdf2 { iterations }, name, meta)
+	}
+
+	/// Create a new account using given key derivation settings
+	pub fn create_with_kdf(
+		keypair: &KeyPair,
+		id: [u8; 16],
+		password: &Password,
+		kdf: &KdfParams,
+		name: String,
+		meta: String
 	) -> Result<Self, crypto::Error> {
 		Ok(SafeAccount {
 			id: id,
 			version: Version::V3,
-			crypto: Crypto::with_secret(keypair.secret(), password, iterations)?,
+			crypto: Crypto::with_secret_kdf(keypair.secret(), password, kdf)?,
 			address: keypair.address(),
 			filename: None,
 			name: name,
@@ -179,11 +191,16 @@ impl SafeAccount {
 
 	/// Change account's password.
 	pub fn change_password(&self, old_password: &Password, new_password: &Password, iterations: u32) -> Result<Self, Error> {
+		self.change_password_with_kdf(old_password, new_password, &KdfParams::Pbkdf2 { iterations })
+	}
+
+	/// Change account's password, re-encrypting the secret with given key derivation settings.
+	pub fn change_password_with_kdf(&self, old_password: &Password, new_password: &Password, kdf: &KdfParams) -> Result<Self, Error> {
 		let secret = self.crypto.secret(old_password)?;
 		let result = SafeAccount {
 			id: self.id.clone(),
 			version: self.version.clone(),
-			crypto: Crypto::with_secret(&secret, new_password, iterations)?,
+			crypto: Crypto::with_secret_kdf(&secret, new_password, kdf)?,
 			address: self.address.clone(),
 			filename: self.filename.clone(),
 			name: self.name.clone(),
@@ -79,23 +79,79 @@ pub fn create_new_file_with_permissions_to_owner(file_path: &Path) -> io::Result
 		.open(file_path)
 }
 
-/// Create a new file and restrict permissions to owner only. It replaces the existing file if it already exists.
-#[cfg(unix)]
-pub fn replace_file_with_permissions_to_owner(file_path: &Path) -> io::Result<fs::File> {
-	use std::os::unix::fs::PermissionsExt;
+/// Write a file through a hidden temporary file in the same directory, moved to `file_path`
+/// once fully written and synced. A crash never leaves a truncated file behind. An existing
+/// file at `file_path` is replaced if `replace` is set, otherwise the write fails.
+pub fn write_atomically<F>(file_path: &Path, replace: bool, write: F) -> Result<(), Error> where
+	F: FnOnce(&mut fs::File) -> Result<(), Error>,
+{
+	let file_name = file_path.file_name().and_then(|name| name.to_str())
+		.ok_or_else(|| Error::Custom(format!("Invalid file path: {}", file_path.display())))?;
+	let tmp_path = file_path.with_file_name(format!(".{}.tmp", file_name));
+
+	// left over by a crash during a previous write
+	match fs::remove_file(&tmp_path) {
+		Err(ref e) if e.kind() != io::ErrorKind::NotFound => warn!("Couldn't remove {:?}: {}", tmp_path, e),
+		_ => {},
+	}
+
+	let result = create_new_file_with_permissions_to_owner(&tmp_path)
+		.map_err(Error::from)
+		.and_then(|mut file| {
+			write(&mut file)?;
+			file.flush()?;
+			file.sync_all()?;
+			Ok(())
+		})
+		.and_then(|_| if replace {
+			fs::rename(&tmp_path, file_path).map_err(Error::from)
+		} else {
+			// unlike renaming, linking fails if the file exists
+			fs::hard_link(&tmp_path, file_path).map_err(Error::from)
+		});
+
+	if result.is_err() || !replace {
+		let _ = fs::remove_file(&tmp_path);
+	}
+	result
+}
 
-	let file = fs::File::create(file_path)?;
-	let mut permissions = file.metadata()?.permissions();
-	permissions.set_mode((libc::S_IWUSR | libc::S_IRUSR) as u32);
-	file.set_permissions(permissions)?;
+/// Name of the lock file in a locked keys directory.
+const LOCK_FILE_NAME: &str = ".lock";
 
-	Ok(file)
+/// Advisory lock on a keys directory, released when dropped or when the process exits.
+pub struct DirectoryLock {
+	_file: fs::File,
 }
 
-/// Create a new file and restrict permissions to owner only. It replaces the existing file if it already exists.
-#[cfg(not(unix))]
-pub fn replace_file_with_permissions_to_owner(file_path: &Path) -> io::Result<fs::File> {
-	fs::File::create(file_path)
+impl DirectoryLock {
+	/// Locks given directory, failing if another process holds the lock.
+	pub fn acquire(path: &Path) -> Result<Self, Error> {
+		let file = fs::OpenOptions::new()
+			.create(true)
+			.write(true)
+			.open(path.join(LOCK_FILE_NAME))?;
+		Self::lock(&file).map_err(|e| match e.kind() {
+			io::ErrorKind::WouldBlock => Error::DirectoryLocked(path.display().to_string()),
+			_ => e.into(),
+		})?;
+		Ok(DirectoryLock { _file: file })
+	}
+
+	#[cfg(unix)]
+	fn lock(file: &fs::File) -> io::Result<()> {
+		use std::os::unix::io::AsRawFd;
+
+		match unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } {
+			0 => Ok(()),
+			_ => Err(io::Error::last_os_error()),
+		}
+	}
+
+	#[cfg(not(unix))]
+	fn lock(_file: &fs::File) -> io::Result<()> {
+		Ok(())
+	}
 }
 
 /// Root keys directory implementation
@@ -114,6 +170,7 @@ pub trait KeyFileManager: Send + Sync {
 pub struct DiskDirectory<T> where T: KeyFileManager {
 	path: PathBuf,
 	key_manager: T,
+	_lock: Option<DirectoryLock>,
 }
 
 /// Keys file manager for root keys directory
@@ -128,6 +185,14 @@ impl RootDiskDirectory {
 		Ok(Self::at(path))
 	}
 
+	/// Creates the directory if needed and locks it for as long as the returned value lives,
+	/// so that a second process can't write to the same keys concurrently.
+	pub fn create_locked<P>(path: P) -> Result<Self, Error> where P: AsRef<Path> {
+		let mut dir = Self::create(&path)?;
+		dir._lock = Some(DirectoryLock::acquire(path.as_ref())?);
+		Ok(dir)
+	}
+
 	/// allows to read keyfiles with given password (needed for keyfiles w/o address)
 	pub fn with_password(&self, password: Option<Password>) -> Self {
 		DiskDirectory::new(&self.path, DiskKeyFileManager { password })
@@ -144,6 +209,7 @@ impl<T> DiskDirectory<T> where T: KeyFileManager {
 		DiskDirectory {
 			path: path.as_ref().to_path_buf(),
 			key_manager: key_manager,
+			_lock: None,
 		}
 	}
 
@@ -224,20 +290,10 @@ impl<T> DiskDirectory<T> where T: KeyFileManager {
 		let mut account = account;
 		account.filename = Some(filename);
 
-		{
-			// save the file
-			let mut file = if dedup {
-				create_new_file_with_permissions_to_owner(&keyfile_path)?
-			} else {
-				replace_file_with_permissions_to_owner(&keyfile_path)?
-			};
-
-			// write key content
-			self.key_manager.write(original_account, &mut file).map_err(|e| Error::Custom(format!("{:?}", e)))?;
-
-			file.flush()?;
-			file.sync_all()?;
-		}
+		// write key content, a deduplicated name taken meanwhile is never overwritten
+		write_atomically(&keyfile_path, !dedup, |file| {
+			self.key_manager.write(original_account, file).map_err(|e| Error::Custom(format!("{:?}", e)))
+		})?;
 
 		Ok(account)
 	}
@@ -354,8 +410,10 @@ mod test {
 	extern crate tempdir;
 
 	use std::{env, fs};
-	use super::{KeyDirectory, RootDiskDirectory, VaultKey};
+	use std::io::Write;
+	use super::{KeyDirectory, RootDiskDirectory, VaultKey, write_atomically};
 	use account::SafeAccount;
+	use Error;
 	use crypto::publickey::{Random, Generator};
 	use self::tempdir::TempDir;
 
@@ -412,6 +470,61 @@ mod test {
 		let _ = fs::remove_dir_all(dir);
 	}
 
+	#[test]
+	fn should_replace_key_files_atomically() {
+		// given
+		let temp_path = TempDir::new("").unwrap();
+		let directory = RootDiskDirectory::create(&temp_path).unwrap();
+		let keypair = Random.generate().unwrap();
+		let password = "hello world".into();
+		let account = SafeAccount::create(&keypair, [0u8; 16], &password, 1024, "Test".to_owned(), "{}".to_owned()).unwrap();
+
+		// when
+		let mut account = directory.insert(account).unwrap();
+		account.name = "Renamed".to_owned();
+		directory.update(account).unwrap();
+
+		// then
+		let files: Vec<_> = fs::read_dir(&temp_path).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+		assert_eq!(files.len(), 1, "No temporary file is left behind: {:?}", files);
+		assert_eq!(directory.load().unwrap()[0].name, "Renamed");
+	}
+
+	#[test]
+	fn should_not_overwrite_existing_file_unless_replacing() {
+		// given
+		let temp_path = TempDir::new("").unwrap();
+		let path = temp_path.path().join("key");
+		fs::write(&path, b"first").unwrap();
+
+		// when
+		let created = write_atomically(&path, false, |file| file.write_all(b"second").map_err(Error::from));
+		let replaced = write_atomically(&path, true, |file| file.write_all(b"third").map_err(Error::from));
+
+		// then
+		assert!(created.is_err());
+		assert!(replaced.is_ok());
+		assert_eq!(fs::read(&path).unwrap(), b"third");
+		assert_eq!(fs::read_dir(&temp_path).unwrap().count(), 1);
+	}
+
+	#[test]
+	#[cfg(unix)]
+	fn should_refuse_to_lock_directory_twice() {
+		let temp_path = TempDir::new("").unwrap();
+		let directory = RootDiskDirectory::create_locked(&temp_path).unwrap();
+
+		match RootDiskDirectory::create_locked(&temp_path) {
+			Err(Error::DirectoryLocked(_)) => {},
+			_ => panic!("Second lock of the keys directory should fail."),
+		}
+		// the lock file is not a key file
+		assert!(directory.load().unwrap().is_empty());
+
+		drop(directory);
+		assert!(RootDiskDirectory::create_locked(&temp_path).is_ok());
+	}
+
 	#[test]
 	fn should_manage_vaults() {
 		// given
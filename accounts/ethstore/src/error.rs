@@ -46,6 +46,8 @@ pub enum Error {
 	VaultNotFound,
 	/// Account creation failed.
 	CreationFailed,
+	/// Keys directory is locked by another process.
+	DirectoryLocked(String),
 	/// `EthCrypto` error
 	EthCrypto(EthCryptoError),
 	/// `EthPublicKeyCryptoError` error
@@ -71,6 +73,7 @@ impl fmt::Display for Error {
 			Error::InvalidVaultName => "Invalid vault name".into(),
 			Error::VaultNotFound => "Vault not found".into(),
 			Error::CreationFailed => "Account creation failed".into(),
+			Error::DirectoryLocked(ref path) => format!("Keys directory {} is in use by another process", path),
 			Error::EthCrypto(ref err) => err.to_string(),
 			Error::EthPublicKeyCrypto(ref err) => err.to_string(),
 			Error::Derivation(ref err) => format!("Derivation error: {:?}", err),
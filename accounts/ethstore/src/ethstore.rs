@@ -25,7 +25,7 @@ use random::Random;
 use crypto::publickey::{Signature, Address, Message, Secret, Public, KeyPair, ExtendedKeyPair};
 use ethkey::Password;
 use accounts_dir::{KeyDirectory, VaultKeyDirectory, VaultKey, SetKeyError};
-use account::SafeAccount;
+use account::{KdfParams, SafeAccount};
 use presale::PresaleWallet;
 use json::{self, Uuid, OpaqueKeyFile};
 use {import, Error, SimpleSecretStore, SecretStore, SecretVaultRef, StoreAccountRef, Derivation, OpaqueSecret};
@@ -156,10 +156,22 @@ impl SimpleSecretStore for EthStore {
 }
 
 impl SecretStore for EthStore {
+	fn kdf(&self) -> KdfParams {
+		self.store.kdf()
+	}
+
+	fn set_kdf(&self, kdf: KdfParams) {
+		self.store.set_kdf(kdf)
+	}
+
 	fn raw_secret(&self, account: &StoreAccountRef, password: &Password) -> Result<OpaqueSecret, Error> {
 		Ok(OpaqueSecret(self.get(account)?.crypto.secret(password)?))
 	}
 
+	fn export_secret(&self, account: &StoreAccountRef, password: &Password) -> Result<Secret, Error> {
+		Ok(self.get(account)?.crypto.secret(password)?)
+	}
+
 	fn import_presale(&self, vault: SecretVaultRef, json: &[u8], password: &Password) -> Result<StoreAccountRef, Error> {
 		let json_wallet = json::PresaleWallet::load(json).map_err(|_| Error::InvalidKeyFile("Invalid JSON format".to_owned()))?;
 		let wallet = PresaleWallet::from(json_wallet);
@@ -259,6 +271,8 @@ impl SecretStore for EthStore {
 pub struct EthMultiStore {
 	dir: Box<dyn KeyDirectory>,
 	iterations: u32,
+	// key derivation used for new and re-encrypted account keys
+	kdf: RwLock<KdfParams>,
 	// order lock: cache, then vaults
 	cache: RwLock<BTreeMap<StoreAccountRef, Vec<SafeAccount>>>,
 	vaults: Mutex<HashMap<String, Box<dyn VaultKeyDirectory>>>,
@@ -283,6 +297,7 @@ impl EthMultiStore {
 			dir: directory,
 			vaults: Mutex::new(HashMap::new()),
 			iterations: iterations,
+			kdf: RwLock::new(KdfParams::Pbkdf2 { iterations }),
 			cache: Default::default(),
 			timestamp: Mutex::new(Timestamp {
 				dir_hash: None,
@@ -295,6 +310,17 @@ impl EthMultiStore {
 		Ok(store)
 	}
 
+	/// Key derivation settings used for new account keys.
+	pub fn kdf(&self) -> KdfParams {
+		*self.kdf.read()
+	}
+
+	/// Changes key derivation settings used for new account keys and password changes.
+	/// Existing key files keep their parameters until re-encrypted. Vaults always use PBKDF2.
+	pub fn set_kdf(&self, kdf: KdfParams) {
+		*self.kdf.write() = kdf;
+	}
+
 	/// Modify account refresh timeout - how often they are re-read from `KeyDirectory`.
 	///
 	/// Setting this to low values (or 0) will cause new accounts to be picked up quickly,
@@ -459,7 +485,7 @@ impl SimpleSecretStore for EthMultiStore {
 	fn insert_account(&self, vault: SecretVaultRef, secret: Secret, password: &Password) -> Result<StoreAccountRef, Error> {
 		let keypair = KeyPair::from_secret(secret).map_err(|_| Error::CreationFailed)?;
 		let id: [u8; 16] = Random::random();
-		let account = SafeAccount::create(&keypair, id, password, self.iterations, "".to_owned(), "{}".to_owned())?;
+		let account = SafeAccount::create_with_kdf(&keypair, id, password, &self.kdf(), "".to_owned(), "{}".to_owned())?;
 		self.import(vault, account)
 	}
 
@@ -538,7 +564,7 @@ impl SimpleSecretStore for EthMultiStore {
 
 		for account in accounts {
 			// Change password
-			let new_account = account.change_password(old_password, new_password, self.iterations)?;
+			let new_account = account.change_password_with_kdf(old_password, new_password, &self.kdf())?;
 			self.update(account_ref, account, new_account)?;
 		}
 
@@ -23,6 +23,7 @@ use Error;
 use json::{Uuid, OpaqueKeyFile};
 use ethereum_types::H256;
 use OpaqueSecret;
+use account::KdfParams;
 
 /// Key directory reference
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -105,10 +106,18 @@ pub trait SimpleSecretStore: Send + Sync {
 
 /// Secret Store API
 pub trait SecretStore: SimpleSecretStore {
+	/// Returns key derivation settings used for new account keys.
+	fn kdf(&self) -> KdfParams;
+
+	/// Sets key derivation settings used for new account keys.
+	fn set_kdf(&self, kdf: KdfParams);
 
 	/// Returns a raw opaque Secret that can be later used to sign a message.
 	fn raw_secret(&self, account: &StoreAccountRef, password: &Password) -> Result<OpaqueSecret, Error>;
 
+	/// Returns the plain secret of an account. Only meant for exports explicitly requested by the user.
+	fn export_secret(&self, account: &StoreAccountRef, password: &Password) -> Result<Secret, Error>;
+
 	/// Signs a message with raw secret.
 	fn sign_with_secret(&self, secret: &OpaqueSecret, message: &Message) -> Result<Signature, Error> {
 		Ok(crypto::publickey::sign(&secret.0, message)?)
@@ -23,10 +23,10 @@ mod error;
 mod stores;
 
 use self::account_data::{Unlock, AccountData};
-use self::stores::AddressBook;
+use self::stores::{AddressBook, SecretExportLog};
 
 use std::collections::HashMap;
-use std::time::{Instant, Duration};
+use std::time::{Instant, Duration, SystemTime};
 
 use ethkey::Password;
 use parity_crypto::publickey::{Address, Message, Public, Secret, Random, Generator, Signature};
@@ -37,8 +37,9 @@ use ethstore::{
 };
 use log::warn;
 use parking_lot::RwLock;
+use serde_derive::{Serialize, Deserialize};
 
-pub use ethstore::{Derivation, IndexDerivation, KeyFile, Error};
+pub use ethstore::{Derivation, IndexDerivation, KeyFile, KdfParams, Error};
 
 pub use self::account_data::AccountMeta;
 pub use self::error::SignError;
@@ -54,6 +55,15 @@ pub struct AccountProviderSettings {
 	pub blacklisted_accounts: Vec<Address>,
 }
 
+/// Audit record of a raw secret export.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecretExport {
+	/// Account whose secret was exported.
+	pub address: Address,
+	/// When the export happened.
+	pub time: SystemTime,
+}
+
 /// Account management.
 /// Responsible for unlocking accounts.
 pub struct AccountProvider {
@@ -72,6 +82,8 @@ pub struct AccountProvider {
 	unlock_keep_secret: bool,
 	/// Disallowed accounts.
 	blacklisted_accounts: Vec<Address>,
+	/// Raw secret exports performed through `export_secret`.
+	secret_exports: RwLock<SecretExportLog>,
 }
 
 fn transient_sstore() -> EthMultiStore {
@@ -93,6 +105,7 @@ impl AccountProvider {
 		for addr in &settings.blacklisted_accounts {
 			address_book.remove(*addr);
 		}
+		let secret_exports = SecretExportLog::new(&sstore.local_path());
 
 		AccountProvider {
 			unlocked_secrets: RwLock::new(HashMap::new()),
@@ -102,6 +115,7 @@ impl AccountProvider {
 			transient_sstore: transient_sstore(),
 			unlock_keep_secret: settings.unlock_keep_secret,
 			blacklisted_accounts: settings.blacklisted_accounts,
+			secret_exports: RwLock::new(secret_exports),
 		}
 	}
 
@@ -115,6 +129,7 @@ impl AccountProvider {
 			transient_sstore: transient_sstore(),
 			unlock_keep_secret: false,
 			blacklisted_accounts: vec![],
+			secret_exports: RwLock::new(SecretExportLog::transient()),
 		}
 	}
 
@@ -214,6 +229,26 @@ impl AccountProvider {
 		self.address_book.write().remove(addr)
 	}
 
+	/// Returns the address book as a JSON object mapping addresses to their metadata.
+	pub fn export_address_book(&self) -> String {
+		serde_json::to_string(&self.addresses_info())
+			.expect("map of addresses to plain structs always serializes; qed")
+	}
+
+	/// Merges address book entries exported with `export_address_book`.
+	/// Returns the imported addresses.
+	pub fn import_address_book(&self, json: &str) -> Result<Vec<Address>, serde_json::Error> {
+		let entries = AccountMeta::read(json.as_bytes())?;
+		let addresses = entries.keys().cloned().collect();
+		self.address_book.write().import(entries);
+		Ok(addresses)
+	}
+
+	/// Removes all addresses from the address book.
+	pub fn clear_address_book(&self) {
+		self.address_book.write().clear()
+	}
+
 	/// Returns each account along with name and meta.
 	pub fn accounts_info(&self) -> Result<HashMap<Address, AccountMeta>, Error> {
 		let r = self.sstore.accounts()?
@@ -273,6 +308,45 @@ impl AccountProvider {
 		self.sstore.export_account(&self.sstore.account_ref(address)?, &password)
 	}
 
+	/// Returns key derivation settings used for newly created and imported keys.
+	pub fn key_kdf(&self) -> KdfParams {
+		self.sstore.kdf()
+	}
+
+	/// Changes key derivation settings used for newly created and imported keys and password changes.
+	///
+	/// Stronger settings make brute-forcing key files harder at the cost of slower unlocking,
+	/// see `estimate_unlock_time`.
+	pub fn set_key_kdf(&self, kdf: KdfParams) {
+		self.sstore.set_kdf(kdf)
+	}
+
+	/// Measures how long unlocking an account encrypted with `kdf` takes on this machine.
+	pub fn estimate_unlock_time(kdf: &KdfParams) -> Result<Duration, Error> {
+		Ok(kdf.estimate_unlock_time()?)
+	}
+
+	/// Exports the raw secret of an account so it can be backed up.
+	///
+	/// Requires the account password. Every export is recorded in an audit log kept next to
+	/// the key files, and the secret isn't returned if the record can't be written.
+	/// Must only be reachable through the Trusted Signer, never through public RPC.
+	pub fn export_secret(&self, address: &Address, password: &Password) -> Result<Secret, Error> {
+		let account = self.sstore.account_ref(address)?;
+		let secret = self.sstore.export_secret(&account, password)?;
+		self.secret_exports.write().append(SecretExport {
+			address: *address,
+			time: SystemTime::now(),
+		})?;
+		warn!("Raw secret of account {:?} has been exported", address);
+		Ok(secret)
+	}
+
+	/// Returns the audit log of raw secret exports, oldest first.
+	pub fn secret_exports(&self) -> Vec<SecretExport> {
+		self.secret_exports.read().entries().to_vec()
+	}
+
 	/// Helper method used for unlocking accounts.
 	fn unlock_account(&self, address: Address, password: Password, unlock: Unlock) -> Result<(), Error> {
 		let account = self.sstore.account_ref(&address)?;
@@ -362,6 +436,25 @@ impl AccountProvider {
 		}
 	}
 
+	/// Signs several messages, decrypting the account key only once.
+	/// If password is not provided the account must be unlocked.
+	pub fn sign_many(&self, address: Address, password: Option<Password>, messages: &[Message]) -> Result<Vec<Signature>, SignError> {
+		let account = self.sstore.account_ref(&address)?;
+		let unlocked_secrets = self.unlocked_secrets.read();
+		let decrypted;
+		let secret = match unlocked_secrets.get(&account) {
+			Some(secret) => secret,
+			None => {
+				let password = password.map(Ok).unwrap_or_else(|| self.password(&account))?;
+				decrypted = self.sstore.raw_secret(&account, &password)?;
+				&decrypted
+			}
+		};
+		messages.iter()
+			.map(|message| Ok(self.sstore.sign_with_secret(secret, message)?))
+			.collect()
+	}
+
 	/// Signs message using the derived secret. If password is not provided the account must be unlocked.
 	pub fn sign_derived(&self, address: &Address, password: Option<Password>, derivation: Derivation, message: Message)
 		-> Result<Signature, SignError>
@@ -501,9 +594,9 @@ impl AccountProvider {
 
 #[cfg(test)]
 mod tests {
-	use super::{AccountProvider, Unlock};
+	use super::{AccountProvider, KdfParams, Unlock};
 	use std::time::{Duration, Instant};
-	use parity_crypto::publickey::{Generator, Random, Address};
+	use parity_crypto::publickey::{Generator, Random, Address, Message};
 	use ethstore::{StoreAccountRef, Derivation};
 	use ethereum_types::H256;
 
@@ -518,6 +611,68 @@ mod tests {
 		assert!(ap.sign(kp.address(), None, Default::default()).is_err());
 	}
 
+	#[test]
+	fn sign_many_uses_single_unlock() {
+		let kp = Random.generate().unwrap();
+		let ap = AccountProvider::transient_provider();
+		assert!(ap.insert_account(kp.secret().clone(), &"test".into()).is_ok());
+		let messages = [Message::from_low_u64_be(1), Message::from_low_u64_be(2)];
+		assert!(ap.sign_many(kp.address(), Some("test1".into()), &messages).is_err());
+
+		assert!(ap.unlock_account_temporarily(kp.address(), "test".into()).is_ok());
+		let signatures = ap.sign_many(kp.address(), None, &messages).unwrap();
+		assert_eq!(signatures.len(), 2);
+		assert_eq!(signatures[1], ap.sign(kp.address(), Some("test".into()), messages[1]).unwrap());
+		assert!(ap.sign_many(kp.address(), None, &messages).is_err());
+	}
+
+	#[test]
+	fn export_secret_requires_password_and_is_audited() {
+		let kp = Random.generate().unwrap();
+		let ap = AccountProvider::transient_provider();
+		assert!(ap.insert_account(kp.secret().clone(), &"test".into()).is_ok());
+		assert!(ap.export_secret(&kp.address(), &"wrong".into()).is_err());
+		assert!(ap.secret_exports().is_empty());
+
+		assert_eq!(&ap.export_secret(&kp.address(), &"test".into()).unwrap(), kp.secret());
+		let exports = ap.secret_exports();
+		assert_eq!(exports.len(), 1);
+		assert_eq!(exports[0].address, kp.address());
+	}
+
+	#[test]
+	fn address_book_roundtrips_through_json() {
+		let ap = AccountProvider::transient_provider();
+		ap.set_address_name(Address::from_low_u64_be(1), "One".into());
+		ap.set_address_meta(Address::from_low_u64_be(2), "{\"a\":1}".into());
+		let exported = ap.export_address_book();
+
+		let other = AccountProvider::transient_provider();
+		other.set_address_name(Address::from_low_u64_be(3), "Three".into());
+		let mut imported = other.import_address_book(&exported).unwrap();
+		imported.sort();
+		assert_eq!(imported, vec![Address::from_low_u64_be(1), Address::from_low_u64_be(2)]);
+		assert_eq!(other.addresses_info().len(), 3);
+		assert_eq!(other.addresses_info()[&Address::from_low_u64_be(2)], ap.addresses_info()[&Address::from_low_u64_be(2)]);
+
+		assert!(other.import_address_book("not json").is_err());
+		other.clear_address_book();
+		assert!(other.addresses_info().is_empty());
+	}
+
+	#[test]
+	fn new_accounts_use_configured_kdf() {
+		let ap = AccountProvider::transient_provider();
+		let scrypt = KdfParams::Scrypt { n: 1024, r: 8, p: 1 };
+		assert!(AccountProvider::estimate_unlock_time(&scrypt).is_ok());
+		ap.set_key_kdf(scrypt);
+		assert_eq!(ap.key_kdf(), scrypt);
+
+		let address = ap.new_account(&"test".into()).unwrap();
+		assert!(ap.sign(address, Some("test".into()), Default::default()).is_ok());
+		assert!(ap.sign(address, Some("wrong".into()), Default::default()).is_err());
+	}
+
 	#[test]
 	fn derived_account_nosave() {
 		let kp = Random.generate().unwrap();
@@ -14,16 +14,17 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
-//! Address Book Store
+//! Address Book and Secret Export Stores
 
-use std::{fs, fmt, hash, ops};
+use std::{fs, fmt, hash, io, ops};
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use parity_crypto::publickey::Address;
 use log::{trace, warn};
 
-use crate::AccountMeta;
+use crate::{AccountMeta, SecretExport};
 
 /// Disk-backed map from Address to String. Uses JSON.
 pub struct AddressBook {
@@ -81,6 +82,74 @@ impl AddressBook {
 		self.cache.remove(&a);
 		self.save();
 	}
+
+	/// Merges given entries into the address book, replacing existing entries for the same address.
+	/// Address book entries are not backed by a key file, so any `uuid` is dropped.
+	pub fn import(&mut self, entries: HashMap<Address, AccountMeta>) {
+		self.cache.extend(entries.into_iter().map(|(a, m)| (a, AccountMeta { uuid: None, ..m })));
+		self.save();
+	}
+
+	/// Removes all entries
+	pub fn clear(&mut self) {
+		self.cache.clear();
+		self.save();
+	}
+}
+
+/// Append-only log of raw secret exports. Uses JSON, one entry per line.
+pub struct SecretExportLog {
+	path: Option<PathBuf>,
+	entries: Vec<SecretExport>,
+}
+
+impl SecretExportLog {
+	/// Opens the log in given directory, reading the entries recorded so far.
+	pub fn new(path: &Path) -> Self {
+		let path = path.join("secret_exports.log");
+		let entries = match fs::read_to_string(&path) {
+			Ok(log) => log.lines()
+				.filter_map(|line| serde_json::from_str(line)
+					.map_err(|e| warn!("Couldn't read secret export record at: {:?} {}", path, e))
+					.ok()
+				)
+				.collect(),
+			Err(e) => {
+				trace!("Couldn't open secret export log: {}", e);
+				Vec::new()
+			},
+		};
+		SecretExportLog {
+			path: Some(path),
+			entries,
+		}
+	}
+
+	/// Creates transient log (entries are not saved to disk).
+	pub fn transient() -> Self {
+		SecretExportLog {
+			path: None,
+			entries: Vec::new(),
+		}
+	}
+
+	/// Returns the recorded entries, oldest first.
+	pub fn entries(&self) -> &[SecretExport] {
+		&self.entries
+	}
+
+	/// Records an entry, returning once it is on disk.
+	pub fn append(&mut self, entry: SecretExport) -> io::Result<()> {
+		if let Some(ref path) = self.path {
+			let mut line = serde_json::to_vec(&entry)?;
+			line.push(b'\n');
+			let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+			file.write_all(&line)?;
+			file.sync_all()?;
+		}
+		self.entries.push(entry);
+		Ok(())
+	}
 }
 
 /// Disk-serializable HashMap
@@ -143,20 +212,47 @@ impl<K: hash::Hash + Eq, V> DiskMap<K, V> {
 	{
 		if self.transient { return; }
 		trace!(target: "diskmap", "save {:?}", self.path);
-		let _ = fs::File::create(self.path.clone())
-			.map_err(|e| warn!(target: "diskmap", "Couldn't open disk map for writing at: {:?} {}", self.path, e))
+		// write to a hidden temporary file first, so that a crash never leaves a truncated map behind.
+		let file_name = self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+		let tmp_path = self.path.with_file_name(format!(".{}.tmp", file_name));
+		let _ = fs::File::create(&tmp_path)
+			.map_err(|e| warn!(target: "diskmap", "Couldn't open disk map for writing at: {:?} {}", tmp_path, e))
 			.and_then(|mut f| {
-				write(&self.cache, &mut f).map_err(|e| warn!(target: "diskmap", "Couldn't write to disk map at: {:?} {}", self.path, e))
+				write(&self.cache, &mut f).map_err(|e| warn!(target: "diskmap", "Couldn't write to disk map at: {:?} {}", tmp_path, e))?;
+				f.sync_all().map_err(|e| warn!(target: "diskmap", "Couldn't sync disk map at: {:?} {}", tmp_path, e))
+			})
+			.and_then(|_| {
+				fs::rename(&tmp_path, &self.path).map_err(|e| warn!(target: "diskmap", "Couldn't replace disk map at: {:?} {}", self.path, e))
 			});
 	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::{AddressBook, Address};
+	use super::{AddressBook, Address, SecretExportLog};
 	use std::collections::HashMap;
+	use std::fs;
+	use std::time::{Duration, UNIX_EPOCH};
 	use tempdir::TempDir;
 	use crate::account_data::AccountMeta;
+	use crate::SecretExport;
+
+	#[test]
+	fn should_save_and_reload_secret_exports() {
+		let tempdir = TempDir::new("").unwrap();
+		let export = |n| SecretExport {
+			address: Address::from_low_u64_be(n),
+			time: UNIX_EPOCH + Duration::from_secs(n),
+		};
+		let mut log = SecretExportLog::new(tempdir.path());
+		log.append(export(1)).unwrap();
+		log.append(export(2)).unwrap();
+
+		let mut log = SecretExportLog::new(tempdir.path());
+		assert_eq!(log.entries(), &[export(1), export(2)][..]);
+		log.append(export(3)).unwrap();
+		assert_eq!(SecretExportLog::new(tempdir.path()).entries(), &[export(1), export(2), export(3)][..]);
+	}
 
 	#[test]
 	fn should_save_and_reload_address_book() {
@@ -170,6 +266,18 @@ mod tests {
 		].into_iter().collect::<HashMap<_, _>>());
 	}
 
+	#[test]
+	fn should_replace_address_book_file() {
+		let tempdir = TempDir::new("").unwrap();
+		let mut b = AddressBook::new(tempdir.path());
+		b.set_name(Address::from_low_u64_be(1), "One".to_owned());
+		b.set_name(Address::from_low_u64_be(1), "Uno".to_owned());
+
+		let files: Vec<_> = fs::read_dir(tempdir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+		assert_eq!(files, vec!["address_book.json"]);
+		assert_eq!(AddressBook::new(tempdir.path()).get()[&Address::from_low_u64_be(1)].name, "Uno");
+	}
+
 	#[test]
 	fn should_remove_address() {
 		let tempdir = TempDir::new("").unwrap();
@@ -186,4 +294,28 @@ mod tests {
 			(Address::from_low_u64_be(3), AccountMeta{name: "Three".to_owned(), meta: "{}".to_owned(), uuid: None}),
 		].into_iter().collect::<HashMap<_, _>>());
 	}
+
+	#[test]
+	fn should_import_and_clear_entries() {
+		let tempdir = TempDir::new("").unwrap();
+		let mut b = AddressBook::new(tempdir.path());
+
+		b.set_name(Address::from_low_u64_be(1), "One".to_owned());
+		b.set_name(Address::from_low_u64_be(2), "Two".to_owned());
+		b.import(vec![
+			(Address::from_low_u64_be(2), AccountMeta{name: "Deux".to_owned(), meta: "{}".to_owned(), uuid: Some("uuid".to_owned())}),
+			(Address::from_low_u64_be(3), AccountMeta{name: "Trois".to_owned(), meta: "{}".to_owned(), uuid: None}),
+		].into_iter().collect());
+
+		let mut b = AddressBook::new(tempdir.path());
+		assert_eq!(b.get(), vec![
+			(Address::from_low_u64_be(1), AccountMeta{name: "One".to_owned(), meta: "{}".to_owned(), uuid: None}),
+			(Address::from_low_u64_be(2), AccountMeta{name: "Deux".to_owned(), meta: "{}".to_owned(), uuid: None}),
+			(Address::from_low_u64_be(3), AccountMeta{name: "Trois".to_owned(), meta: "{}".to_owned(), uuid: None}),
+		].into_iter().collect::<HashMap<_, _>>());
+
+		b.clear();
+		let b = AddressBook::new(tempdir.path());
+		assert!(b.get().is_empty());
+	}
 }
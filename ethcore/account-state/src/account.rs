@@ -28,7 +28,7 @@ use log::{trace, warn};
 use lru_cache::LruCache;
 use parity_bytes::{Bytes, ToPretty};
 use rlp::{DecoderError, encode};
-use trie_db::{Recorder, Trie};
+use trie_db::{Recorder, Trie, TrieError};
 use common_types::basic_account::BasicAccount;
 use ethtrie::{Result as TrieResult, SecTrieDB, TrieDB, TrieFactory};
 use keccak_hasher::KeccakHasher;
@@ -274,8 +274,8 @@ impl Account {
 
 	fn get_and_cache_storage(storage_root: &H256, storage_cache: &mut LruCache<H256, H256>, db: &dyn HashDB<KeccakHasher, DBValue>, key: &H256) -> TrieResult<H256> {
 		let db = SecTrieDB::new(&db, storage_root)?;
-		let panicky_decoder = |bytes:&[u8]| ::rlp::decode(&bytes).expect("decoding db value failed");
-		let item: U256 = db.get_with(key.as_bytes(), panicky_decoder)?.unwrap_or_else(U256::zero);
+		let item = db.get_with(key.as_bytes(), |bytes: &[u8]| ::rlp::decode::<U256>(bytes))?;
+		let item = decoded(*key, item)?.unwrap_or_else(U256::zero);
 		let value: H256 = BigEndianHash::from_uint(&item);
 		storage_cache.insert(key.clone(), value.clone());
 		Ok(value)
@@ -492,6 +492,16 @@ impl Account {
 		self.nonce = self.nonce.saturating_add(U256::from(1u8));
 	}
 
+	/// Set the nonce of the account.
+	pub fn set_nonce(&mut self, nonce: U256) {
+		self.nonce = nonce;
+	}
+
+	/// Set the balance of the account.
+	pub fn set_balance(&mut self, balance: U256) {
+		self.balance = balance;
+	}
+
 	/// Increase account balance.
 	pub fn add_balance(&mut self, x: &U256) {
 		self.balance = self.balance.saturating_add(*x);
@@ -610,6 +620,12 @@ impl Account {
 	}
 }
 
+/// Report a value found in a trie under `key` which could not be decoded as a trie error,
+/// so that a corrupt database fails the lookup rather than aborting the node.
+pub(crate) fn decoded<T>(key: H256, value: Option<Result<T, DecoderError>>) -> TrieResult<Option<T>> {
+	value.transpose().map_err(|e| Box::new(TrieError::DecoderError(key, e)))
+}
+
 // light client storage proof.
 impl Account {
 	/// Prove a storage key's existence or nonexistence in the account's storage
@@ -620,10 +636,10 @@ impl Account {
 		let mut recorder = Recorder::new();
 
 		let trie = TrieDB::new(&db, &self.storage_root)?;
-		let item: U256 = {
-			let panicky_decoder = |bytes:&[u8]| ::rlp::decode(bytes).expect("decoding db value failed");
-			let query = (&mut recorder, panicky_decoder);
-			trie.get_with(storage_key.as_bytes(), query)?.unwrap_or_else(U256::zero)
+		let item = {
+			let query = (&mut recorder, |bytes: &[u8]| ::rlp::decode::<U256>(bytes));
+			let item = trie.get_with(storage_key.as_bytes(), query)?;
+			decoded(storage_key, item)?.unwrap_or_else(U256::zero)
 		};
 
 		Ok((recorder.drain().into_iter().map(|r| r.data).collect(), BigEndianHash::from_uint(&item)))
@@ -649,7 +665,9 @@ mod tests {
 	use parity_bytes::Bytes;
 
 	use account_db::*;
+	use ethtrie::SecTrieDBMut;
 	use journaldb::new_memory_db;
+	use trie_db::TrieMut;
 	use rlp_compress::{compress, decompress, snapshot_swapper};
 
 	use super::*;
@@ -682,6 +700,27 @@ mod tests {
 		assert_eq!(a.storage_at(&db.immutable(), &H256::from_low_u64_be(0x01)).unwrap(), H256::zero());
 	}
 
+	#[test]
+	fn storage_at_reports_undecodable_value() {
+		let mut db = new_memory_db();
+		let mut db = AccountDBMut::from_hash(&mut db, keccak(&Address::zero()));
+		let mut root = H256::zero();
+		{
+			let mut trie = SecTrieDBMut::new(&mut db, &mut root);
+			// a list where a storage value is expected
+			trie.insert(H256::zero().as_bytes(), &[0xc0]).unwrap();
+		}
+
+		let a = Account::from(BasicAccount {
+			nonce: 0.into(),
+			balance: 0.into(),
+			storage_root: root,
+			code_hash: KECCAK_EMPTY,
+			code_version: 0.into(),
+		});
+		assert!(a.storage_at(&db.immutable(), &H256::zero()).is_err());
+	}
+
 	#[test]
 	fn note_code() {
 		let mut db = new_memory_db();
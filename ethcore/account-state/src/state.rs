@@ -47,7 +47,7 @@ use pod::{self, PodAccount, PodState};
 use trie_db::{Trie, TrieError, Recorder};
 
 use crate::{
-	account::Account,
+	account::{Account, decoded},
 	backend::Backend,
 };
 
@@ -250,9 +250,6 @@ impl<B: Backend> StateInfo for State<B> {
 	fn code(&self, address: &Address) -> TrieResult<Option<Arc<Bytes>>> { State::code(self, address) }
 }
 
-const SEC_TRIE_DB_UNWRAP_STR: &'static str = "A state can only be created with valid root. Creating a SecTrieDB with a valid root will not fail. \
-			 Therefore creating a SecTrieDB with this state's root will not fail.";
-
 impl<B: Backend> State<B> {
 	/// Creates new state with empty state root
 	/// Used for tests.
@@ -296,6 +293,11 @@ impl<B: Backend> State<B> {
 		self.factories.vm.clone()
 	}
 
+	/// Replace the VM factory used to execute on this state.
+	pub fn set_vm_factory(&mut self, vm: VmFactory) {
+		self.factories.vm = vm;
+	}
+
 	/// Create a recoverable checkpoint of this state. Return the checkpoint index.
 	pub fn checkpoint(&mut self) -> usize {
 		let checkpoints = self.checkpoints.get_mut();
@@ -584,9 +586,8 @@ impl<B: Backend> State<B> {
 
 		// account is not found in the global cache, get from the DB and insert into local
 		let db = &self.db.as_hash_db();
-		let db = self.factories.trie.readonly(db, &self.root).expect(SEC_TRIE_DB_UNWRAP_STR);
-		let from_rlp = |b: &[u8]| Account::from_rlp(b).expect("decoding db value failed");
-		let maybe_acc = db.get_with(address.as_bytes(), from_rlp)?;
+		let db = self.factories.trie.readonly(db, &self.root)?;
+		let maybe_acc = decoded(H256::from(*address), db.get_with(address.as_bytes(), Account::from_rlp)?)?;
 		let r = maybe_acc.as_ref().map_or(Ok(H256::zero()), |a| {
 			let account_db = self.factories.accountdb.readonly(self.db.as_hash_db(), a.address_hash(address));
 			f_at(a, account_db.as_hash_db(), key)
@@ -678,6 +679,17 @@ impl<B: Backend> State<B> {
 		self.require(a, false).map(|mut x| x.inc_nonce())
 	}
 
+	/// Set the nonce of account `a` to `nonce`.
+	pub fn set_nonce(&mut self, a: &Address, nonce: U256) -> TrieResult<()> {
+		self.require(a, false).map(|mut x| x.set_nonce(nonce))
+	}
+
+	/// Set the balance of account `a` to `balance`.
+	pub fn set_balance(&mut self, a: &Address, balance: U256) -> TrieResult<()> {
+		trace!(target: "state", "set_balance({}, {}): {}", a, balance, self.balance(a)?);
+		self.require(a, false).map(|mut x| x.set_balance(balance))
+	}
+
 	/// Mutate storage of account `a` so that it is `value` for `key`.
 	pub fn set_storage(&mut self, a: &Address, key: H256, value: H256) -> TrieResult<()> {
 		trace!(target: "state", "set_storage({}:{:x} to {:x})", a, key, value);
@@ -848,12 +860,10 @@ impl<B: Backend> State<B> {
 		let trie = self.factories.trie.readonly(accountdb, &root)?;
 		for o_kv in trie.iter()? {
 			if let Ok((key, val)) = o_kv {
-				pod_storage.insert(
-					H256::from_slice(&key[..]),
-					BigEndianHash::from_uint(
-						&rlp::decode::<U256>(&val[..]).expect("Decoded from trie which was encoded from the same type; qed")
-					),
-				);
+				let key = H256::from_slice(&key[..]);
+				let val = rlp::decode::<U256>(&val[..])
+					.map_err(|e| Box::new(TrieError::DecoderError(key, e)))?;
+				pod_storage.insert(key, BigEndianHash::from_uint(&val));
 			}
 		}
 
@@ -925,6 +935,86 @@ impl<B: Backend> State<B> {
 		Ok(pod::state::diff_pod(&pod_state_pre, &pod_state_post))
 	}
 
+	/// Returns a `StateDiff` describing the changes made since the last checkpoint,
+	/// which is then discarded.
+	/// Unlike `diff_from` only the accounts modified after the checkpoint are compared,
+	/// so the cost doesn't grow with the number of accounts already in the cache.
+	pub fn diff_from_checkpoint(&mut self) -> TrieResult<StateDiff> {
+		let checkpoint = match self.checkpoints.get_mut().pop() {
+			Some(checkpoint) => checkpoint,
+			None => return Ok(StateDiff { raw: BTreeMap::new() }),
+		};
+
+		let diff = self.diff_checkpoint_entries(&checkpoint);
+		self.checkpoints.get_mut().push(checkpoint);
+		self.discard_checkpoint();
+		diff
+	}
+
+	fn diff_checkpoint_entries(&self, checkpoint: &HashMap<Address, Option<AccountEntry>>) -> TrieResult<StateDiff> {
+		let mut pre = BTreeMap::new();
+		let mut post = BTreeMap::new();
+
+		for (address, entry) in checkpoint {
+			let original = match *entry {
+				Some(ref entry) => entry.account.as_ref().map(Account::clone_dirty),
+				// The account wasn't cached when the checkpoint was created.
+				None => self.original_account(address)?,
+			};
+			let current = self.ensure_cached(address, RequireCache::Code, true, |acc| {
+				acc.map(|acc| (*acc.balance(), *acc.nonce(), acc.storage_changes().keys().cloned().collect::<Vec<_>>(), acc.code().map(|x| x.to_vec()), *acc.code_version()))
+			})?;
+
+			let mut keys = original.as_ref()
+				.map_or_else(BTreeSet::new, |acc| acc.storage_changes().keys().cloned().collect::<BTreeSet<_>>());
+			if let Some((_, _, ref current_keys, _, _)) = current {
+				keys.extend(current_keys.iter().cloned());
+			}
+
+			if let Some(mut acc) = original {
+				let accountdb = self.factories.accountdb.readonly(self.db.as_hash_db(), acc.address_hash(address));
+				if !Self::update_account_cache(RequireCache::Code, &mut acc, &self.db, accountdb.as_hash_db()) {
+					return Err(Box::new(TrieError::IncompleteDatabase(H256::from(*address))));
+				}
+				let mut storage = BTreeMap::new();
+				for key in &keys {
+					storage.insert(*key, acc.storage_at(accountdb.as_hash_db(), key)?);
+				}
+				pre.insert(*address, PodAccount {
+					balance: *acc.balance(),
+					nonce: *acc.nonce(),
+					storage,
+					code: acc.code().map(|x| x.to_vec()),
+					version: *acc.code_version(),
+				});
+			}
+
+			// Storage must be fetched after ensure_cached to avoid borrow problem.
+			if let Some((balance, nonce, _, code, version)) = current {
+				let mut storage = BTreeMap::new();
+				for key in &keys {
+					storage.insert(*key, self.storage_at(address, key)?);
+				}
+				post.insert(*address, PodAccount { balance, nonce, storage, code, version });
+			}
+		}
+
+		Ok(pod::state::diff_pod(&PodState::from(pre), &PodState::from(post)))
+	}
+
+	/// Load account `a` as it is stored under the current root, bypassing the local cache.
+	fn original_account(&self, a: &Address) -> TrieResult<Option<Account>> {
+		if let Some(account) = self.db.get_cached_account(a) {
+			return Ok(account);
+		}
+		if self.db.is_known_null(a) {
+			return Ok(None);
+		}
+		let db = &self.db.as_hash_db();
+		let db = self.factories.trie.readonly(db, &self.root)?;
+		decoded(H256::from(*a), db.get_with(a.as_bytes(), Account::from_rlp)?)
+	}
+
 	/// Load required account data from the databases. Returns whether the cache succeeds.
 	#[must_use]
 	fn update_account_cache(require: RequireCache, account: &mut Account, state_db: &B, db: &dyn HashDB<KeccakHasher, DBValue>) -> bool {
@@ -998,8 +1088,7 @@ impl<B: Backend> State<B> {
 				// not found in the global cache, get from the DB and insert into local
 				let db = &self.db.as_hash_db();
 				let db = self.factories.trie.readonly(db, &self.root)?;
-				let from_rlp = |b: &[u8]| Account::from_rlp(b).expect("decoding db value failed");
-				let mut maybe_acc = db.get_with(a.as_bytes(), from_rlp)?;
+				let mut maybe_acc = decoded(H256::from(*a), db.get_with(a.as_bytes(), Account::from_rlp)?)?;
 				if let Some(ref mut account) = maybe_acc.as_mut() {
 					let accountdb = self.factories.accountdb.readonly(self.db.as_hash_db(), account.address_hash(a));
 					if !Self::update_account_cache(require, account, &self.db, accountdb.as_hash_db()) {
@@ -1031,8 +1120,7 @@ impl<B: Backend> State<B> {
 					let maybe_acc = if !self.db.is_known_null(a) {
 						let db = &self.db.as_hash_db();
 						let db = self.factories.trie.readonly(db, &self.root)?;
-						let from_rlp = |b:&[u8]| { Account::from_rlp(b).expect("decoding db value failed") };
-						AccountEntry::new_clean(db.get_with(a.as_bytes(), from_rlp)?)
+						AccountEntry::new_clean(decoded(H256::from(*a), db.get_with(a.as_bytes(), Account::from_rlp)?)?)
 					} else {
 						AccountEntry::new_clean(None)
 					};
@@ -1113,8 +1201,7 @@ impl<B: Backend> State<B> {
 		// address, not keccak(address).
 		let db = &self.db.as_hash_db();
 		let trie = TrieDB::new(db, &self.root)?;
-		let from_rlp = |b: &[u8]| Account::from_rlp(b).expect("decoding db value failed");
-		let acc = match trie.get_with(account_key.as_bytes(), from_rlp)? {
+		let acc = match decoded(account_key, trie.get_with(account_key.as_bytes(), Account::from_rlp)?)? {
 			Some(acc) => acc,
 			None => return Ok((Vec::new(), H256::zero())),
 		};
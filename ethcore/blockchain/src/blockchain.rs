@@ -16,10 +16,11 @@
 
 //! Blockchain database.
 
-use std::collections::{HashMap, HashSet};
-use std::{mem, io};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::{cmp, mem, io, str};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use ansi_term::Colour;
 use blooms_db;
@@ -27,21 +28,25 @@ use common_types::{
 	BlockNumber,
 	blockchain_info::BlockChainInfo,
 	block::{BlockInfo, BlockLocation, BranchBecomingCanonChainData},
+	disk_usage::DiskUsage,
 	encoded,
 	engines::ForkChoice,
 	engines::epoch::{Transition as EpochTransition, PendingTransition as PendingEpochTransition},
 	header::{Header, ExtendedHeader},
 	import_route::ImportRoute,
-	log_entry::{LogEntry, LocalizedLogEntry},
+	filter::Filter,
+	ids::BlockId,
+	log_entry::LocalizedLogEntry,
 	receipt::Receipt,
+	reorg::Reorg,
 	transaction::LocalizedTransaction,
 	tree_route::TreeRoute,
 	view,
 	views::{BlockView, HeaderView},
 };
 use ethcore_db::cache_manager::CacheManager;
-use ethcore_db::keys::{BlockReceipts, BlockDetails, TransactionAddress, EPOCH_KEY_PREFIX, EpochTransitions};
-use ethcore_db::{self as db, Writable, Readable, CacheUpdatePolicy};
+use ethcore_db::keys::{BlockReceipts, BlockDetails, BlockOutputs, BlockSkip, TransactionAddress, EPOCH_KEY_PREFIX, REORG_KEY_PREFIX, EpochTransitions};
+use ethcore_db::{self as db, Writable, Readable, CacheUpdatePolicy, Key};
 use ethereum_types::{H256, Bloom, BloomRef, U256};
 use util_mem::{MallocSizeOf, allocators::new_malloc_size_ops};
 use itertools::Itertools;
@@ -51,7 +56,7 @@ use parity_bytes::Bytes;
 use parking_lot::{Mutex, RwLock};
 use rayon::prelude::*;
 use rlp::RlpStream;
-use rlp_compress::{compress, decompress, blocks_swapper};
+use rlp_compress::{compress, decompress, blocks_swapper, receipts_swapper};
 
 use crate::best_block::{BestBlock, BestAncientBlock};
 use crate::update::{ExtrasUpdate, ExtrasInsert};
@@ -68,6 +73,15 @@ pub trait BlockChainDB: Send + Sync {
 	/// Trace blooms database.
 	fn trace_blooms(&self) -> &blooms_db::Database;
 
+	/// Size on disk of the databases as of the last `refresh_disk_usage`, or `None` if they
+	/// are not stored on disk.
+	fn disk_usage(&self) -> Option<DiskUsage> {
+		None
+	}
+
+	/// Measure the size on disk of the databases again.
+	fn refresh_disk_usage(&self) {}
+
 	/// Restore the DB from the given path
 	fn restore(&self, new_db: &str) -> Result<(), io::Error> {
 		// First, close the Blooms databases
@@ -193,9 +207,9 @@ pub trait BlockProvider {
 		I: Iterator<Item = B>,
 		Self: Sized;
 
-	/// Returns logs matching given filter.
-	fn logs<F>(&self, blocks: Vec<H256>, matches: F, limit: Option<usize>) -> Vec<LocalizedLogEntry>
-		where F: Fn(&LogEntry) -> bool + Send + Sync, Self: Sized;
+	/// Returns logs matching given filter, oldest first. Logs of blocks which are not on the canon chain are
+	/// marked as removed. Fails with the id of a block of the range which could not be found.
+	fn logs(&self, filter: &Filter) -> Result<Vec<LocalizedLogEntry>, BlockId>;
 }
 
 /// Interface for querying blocks with pending db transaction by hash and by number.
@@ -214,6 +228,21 @@ enum CacheId {
 	BlockReceipts(H256),
 }
 
+/// Number of blooms buffered during bulk insertion before they are written out.
+const BULK_BLOOMS_CHUNK: usize = 1024;
+
+/// Number of blocks whose receipts and bodies are fetched at once when collecting logs.
+const LOGS_CHUNK: usize = 128;
+
+/// Number of blocks visited at most to find the target of a new block's skip pointer.
+const MAX_SKIP_POINTER_STEPS: usize = 1024;
+
+/// Blooms of consecutive blocks, starting at block `from`, waiting to be written to the blooms database.
+struct PendingBlooms {
+	from: BlockNumber,
+	blooms: Vec<Bloom>,
+}
+
 /// Structure providing fast access to blockchain data.
 ///
 /// **Does not do input data verification.**
@@ -247,6 +276,17 @@ pub struct BlockChain {
 	pending_block_hashes: RwLock<HashMap<BlockNumber, H256>>,
 	pending_block_details: RwLock<HashMap<H256, BlockDetails>>,
 	pending_transaction_addresses: RwLock<HashMap<H256, Option<TransactionAddress>>>,
+	pending_next_reorg_index: RwLock<Option<u64>>,
+
+	// Number of reorgs kept in the database and index under which the next one is stored.
+	reorg_history: u64,
+	next_reorg_index: Mutex<u64>,
+
+	// Whether new block receipts are written compressed.
+	compress_receipts: bool,
+
+	// Blooms buffered since `begin_bulk_insert`, `None` outside of bulk insertion.
+	bulk_blooms: Mutex<Option<PendingBlooms>>,
 }
 
 impl BlockProvider for BlockChain {
@@ -358,7 +398,20 @@ impl BlockProvider for BlockChain {
 
 	/// Get receipts of block with given hash.
 	fn block_receipts(&self, hash: &H256) -> Option<BlockReceipts> {
-		let result = self.db.key_value().read_with_cache(db::COL_EXTRA, &self.block_receipts, hash)?;
+		{
+			let read = self.block_receipts.read();
+			if let Some(v) = read.get(hash) {
+				return Some(v.clone());
+			}
+		}
+
+		// receipts may have been written compressed; decompressing plain receipts leaves them unchanged.
+		let raw = self.db.key_value().get(db::COL_EXTRA, Key::<BlockReceipts>::key(hash).as_bytes())
+			.expect("Low level database error when fetching block receipts. Some issue with disk?")?;
+		let result: BlockReceipts = rlp::decode(&decompress(&raw, receipts_swapper()))
+			.expect("decode db value failed");
+
+		self.block_receipts.write().insert(*hash, result.clone());
 		self.cache_man.lock().note_used(CacheId::BlockReceipts(*hash));
 		Some(result)
 	}
@@ -369,68 +422,59 @@ impl BlockProvider for BlockChain {
 		BloomRef<'a>: From<B>,
 		II: IntoIterator<Item = B, IntoIter = I> + Copy,
 		I: Iterator<Item = B> {
-		self.db.blooms()
+		// hold the lock so that buffered blooms aren't flushed in between the two reads
+		let bulk_blooms = self.bulk_blooms.lock();
+		let mut numbers = self.db.blooms()
 			.filter(from_block, to_block, blooms)
-			.expect("Low level database error when searching blooms. Some issue with disk?")
+			.expect("Low level database error when searching blooms. Some issue with disk?");
+
+		// blooms buffered during bulk insertion supersede the ones on disk
+		if let Some(ref pending) = *bulk_blooms {
+			let end = pending.from + pending.blooms.len() as BlockNumber;
+			numbers.retain(|number| *number < pending.from || *number >= end);
+			numbers.extend(pending.blooms.iter()
+				.zip(pending.from..end)
+				.filter(|&(_, number)| number >= from_block && number <= to_block)
+				.filter(|&(bloom, _)| blooms.into_iter().any(|b| bloom.contains_bloom(b)))
+				.map(|(_, number)| number));
+			numbers.sort_unstable();
+		}
+
+		numbers
 	}
 
-	/// Returns logs matching given filter. The order of logs returned will be the same as the order of the blocks
-	/// provided. And it's the callers responsibility to sort blocks provided in advance.
-	fn logs<F>(&self, mut blocks: Vec<H256>, matches: F, limit: Option<usize>) -> Vec<LocalizedLogEntry>
-		where F: Fn(&LogEntry) -> bool + Send + Sync, Self: Sized {
-		// sort in reverse order
-		blocks.reverse();
+	/// Returns logs matching given filter. Blocks are read newest first, a chunk at a time, fetching the receipts
+	/// and body of every block of a chunk in parallel. Reading stops as soon as the limit is reached.
+	fn logs(&self, filter: &Filter) -> Result<Vec<LocalizedLogEntry>, BlockId> {
+		let (blocks, check_canon) = self.filter_blocks(filter)?;
+		let offset = filter.offset.unwrap_or(0);
+		let limit = filter.limit.unwrap_or(usize::max_value());
+
+		let mut logs = Vec::new();
+		let mut skipped = 0;
+		'chunks: for chunk in blocks.rchunks(LOGS_CHUNK) {
+			if logs.len() >= limit {
+				break;
+			}
+
+			let chunk_logs = chunk.par_iter()
+				.map(|hash| self.block_logs(hash, filter, check_canon))
+				.collect::<Vec<_>>();
+
+			for log in chunk_logs.into_iter().rev().flat_map(|block_logs| block_logs.into_iter().rev()) {
+				if skipped < offset {
+					skipped += 1;
+					continue;
+				}
+				if logs.len() >= limit {
+					break 'chunks;
+				}
+				logs.push(log);
+			}
+		}
 
-		let mut logs = blocks
-			.chunks(128)
-			.flat_map(move |blocks_chunk| {
-				blocks_chunk.into_par_iter()
-					.filter_map(|hash| self.block_number(&hash).map(|r| (r, hash)))
-					.filter_map(|(number, hash)| self.block_receipts(&hash).map(|r| (number, hash, r.receipts)))
-					.filter_map(|(number, hash, receipts)| self.block_body(&hash).map(|ref b| (number, hash, receipts, b.transaction_hashes())))
-					.flat_map(|(number, hash, mut receipts, mut hashes)| {
-						if receipts.len() != hashes.len() {
-							warn!("Block {} ({}) has different number of receipts ({}) to transactions ({}). Database corrupt?", number, hash, receipts.len(), hashes.len());
-							assert!(false);
-						}
-						let mut log_index = receipts.iter().fold(0, |sum, receipt| sum + receipt.logs.len());
-
-						let receipts_len = receipts.len();
-						hashes.reverse();
-						receipts.reverse();
-						receipts.into_iter()
-							.map(|receipt| receipt.logs)
-							.zip(hashes)
-							.enumerate()
-							.flat_map(move |(index, (mut logs, tx_hash))| {
-								let current_log_index = log_index;
-								let no_of_logs = logs.len();
-								log_index -= no_of_logs;
-
-								logs.reverse();
-								logs.into_iter()
-									.enumerate()
-									.map(move |(i, log)| LocalizedLogEntry {
-										entry: log,
-										block_hash: *hash,
-										block_number: number,
-										transaction_hash: tx_hash,
-										// iterating in reverse order
-										transaction_index: receipts_len - index - 1,
-										transaction_log_index: no_of_logs - i - 1,
-										log_index: current_log_index - i - 1,
-									})
-							})
-							.filter(|log_entry| matches(&log_entry.entry))
-							.take(limit.unwrap_or(::std::usize::MAX))
-							.collect::<Vec<_>>()
-					})
-					.collect::<Vec<_>>()
-			})
-			.take(limit.unwrap_or(::std::usize::MAX))
-			.collect::<Vec<LocalizedLogEntry>>();
 		logs.reverse();
-		logs
+		Ok(logs)
 	}
 }
 
@@ -570,8 +614,28 @@ impl BlockChain {
 			pending_block_hashes: RwLock::new(HashMap::new()),
 			pending_block_details: RwLock::new(HashMap::new()),
 			pending_transaction_addresses: RwLock::new(HashMap::new()),
+			pending_next_reorg_index: RwLock::new(None),
+			reorg_history: config.reorg_history,
+			next_reorg_index: Mutex::new(0),
+			compress_receipts: config.compress_receipts,
+			bulk_blooms: Mutex::new(None),
 		};
 
+		// continue numbering reorgs after the last recorded one
+		let reorg_indices: Vec<u64> = bc.reorg_records().map(|(index, _)| index).collect();
+		let next_reorg_index = reorg_indices.last().map_or(0, |index| index + 1);
+		*bc.next_reorg_index.get_mut() = next_reorg_index;
+
+		// drop the records beyond the configured history, which may have been lowered since
+		let first_kept = next_reorg_index.saturating_sub(bc.reorg_history);
+		if reorg_indices.first().map_or(false, |index| *index < first_kept) {
+			let mut batch = DBTransaction::new();
+			for index in reorg_indices.into_iter().take_while(|index| *index < first_kept) {
+				Writable::delete::<Reorg, _>(&mut batch, db::COL_EXTRA, &index);
+			}
+			bc.db.key_value().write(batch).expect("Low level database error when pruning reorg history. Some issue with disk?");
+		}
+
 		// load best block
 		let best_block_hash = match bc.db.key_value().get(db::COL_EXTRA, b"best")
 			.expect("Low-level database error when fetching 'best' block. Some issue with disk?")
@@ -786,6 +850,80 @@ impl BlockChain {
 		})
 	}
 
+	/// Returns the hash of the ancestor of `hash` at block `number`.
+	///
+	/// Follows skip pointers where available, so this takes O(log n) database reads
+	/// rather than one per block in between. Returns `None` if `number` is above the
+	/// block or the chain verges into pruned or unknown blocks.
+	pub fn ancestor(&self, hash: H256, number: BlockNumber) -> Option<H256> {
+		self.ancestor_within(hash, number, usize::max_value())
+	}
+
+	/// Like `ancestor`, but gives up with `None` after `max_steps` blocks were visited.
+	fn ancestor_within(&self, hash: H256, number: BlockNumber, max_steps: usize) -> Option<H256> {
+		let mut current = hash;
+		let mut details = self.block_details(&current)?;
+		if number > details.number {
+			return None;
+		}
+
+		for _ in 0..max_steps {
+			if details.number == number {
+				return Some(current);
+			}
+			let skip = skip_number(details.number);
+			let skip_prev = skip_number(details.number - 1);
+			// take the skip only if it doesn't overshoot, and the parent's skip isn't a better fit
+			let use_skip = skip == number || (skip > number && !(skip_prev + 2 < skip && skip_prev >= number));
+			current = match use_skip {
+				true => self.db.key_value().read(db::COL_EXTRA, &current).map(|s: BlockSkip| s.0),
+				false => None,
+			}.unwrap_or(details.parent);
+			details = self.block_details(&current)?;
+		}
+
+		if details.number == number { Some(current) } else { None }
+	}
+
+	/// Returns the hash of the latest common ancestor of `a` and `b`.
+	///
+	/// Unlike `tree_route` this doesn't visit every block on the way, so it stays cheap
+	/// for deep forks.
+	pub fn common_ancestor(&self, a: H256, b: H256) -> Option<H256> {
+		let number = cmp::min(self.block_details(&a)?.number, self.block_details(&b)?.number);
+		let a = self.ancestor(a, number)?;
+		let b = self.ancestor(b, number)?;
+		if a == b {
+			return Some(a);
+		}
+
+		// gallop down until the branches meet, then bisect the last step
+		let mut high = number;
+		let mut step = 1;
+		let mut low = loop {
+			let candidate = number.saturating_sub(step);
+			if self.ancestor(a, candidate)? == self.ancestor(b, candidate)? {
+				break candidate;
+			}
+			if candidate == 0 {
+				return None;
+			}
+			high = candidate;
+			step *= 2;
+		};
+
+		while high - low > 1 {
+			let mid = low + (high - low) / 2;
+			if self.ancestor(a, mid)? == self.ancestor(b, mid)? {
+				low = mid;
+			} else {
+				high = mid;
+			}
+		}
+
+		self.ancestor(a, low)
+	}
+
 	/// Inserts a verified, known block from the canonical chain.
 	///
 	/// Can be performed out-of-order, but care must be taken that the final chain is in a correct state.
@@ -1013,6 +1151,62 @@ impl BlockChain {
 		}
 	}
 
+	/// Record a reorganization making `best_hash` the new best block, dropping the oldest
+	/// record once more than the configured number are kept.
+	fn insert_reorg(&self, batch: &mut DBTransaction, data: &BranchBecomingCanonChainData, best_hash: H256) {
+		if self.reorg_history == 0 {
+			return;
+		}
+
+		let mut enacted = data.enacted.clone();
+		enacted.push(best_hash);
+		let reorg = Reorg {
+			ancestor: data.ancestor,
+			retracted: data.retracted.clone(),
+			enacted,
+			timestamp: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+		};
+
+		// the index only advances on `commit`, together with the rest of the pending state
+		let mut pending_index = self.pending_next_reorg_index.write();
+		let index = pending_index.unwrap_or_else(|| *self.next_reorg_index.lock());
+		batch.write(db::COL_EXTRA, &index, &reorg);
+		if index >= self.reorg_history {
+			Writable::delete::<Reorg, _>(batch, db::COL_EXTRA, &(index - self.reorg_history));
+		}
+		*pending_index = Some(index + 1);
+	}
+
+	/// Iterate over all recorded reorganizations, oldest first, together with their index.
+	fn reorg_records<'a>(&'a self) -> impl Iterator<Item = (u64, Reorg)> + 'a {
+		self.db.key_value().iter_from_prefix(db::COL_EXTRA, &REORG_KEY_PREFIX[..])
+			.take_while(|(key, _)| key.starts_with(&REORG_KEY_PREFIX[..]))
+			.map(|(key, val)| {
+				let index = str::from_utf8(&key[REORG_KEY_PREFIX.len()..]).ok()
+					.and_then(|index| u64::from_str_radix(index, 16).ok())
+					.expect("reorg keys are written as hex encoded indices; qed");
+				let reorg = ::rlp::decode(&val[..]).expect("decode error: the db is corrupted or the data structure has changed");
+				(index, reorg)
+			})
+	}
+
+	/// Returns up to `count` most recent reorganizations of the canonical chain, newest first.
+	pub fn reorg_history(&self, count: usize) -> Vec<Reorg> {
+		// no more than the configured number of records are kept
+		let count = cmp::min(count as u64, self.reorg_history) as usize;
+		let mut reorgs = VecDeque::with_capacity(count);
+		for (_, reorg) in self.reorg_records() {
+			if count == 0 {
+				break;
+			}
+			if reorgs.len() == count {
+				reorgs.pop_front();
+			}
+			reorgs.push_back(reorg);
+		}
+		reorgs.into_iter().rev().collect()
+	}
+
 	/// Get a specific epoch transition by block number and provided block hash.
 	pub fn epoch_transition(&self, block_num: u64, block_hash: H256) -> Option<EpochTransition> {
 		trace!(target: "blockchain", "Loading epoch transition at block {}, {}",
@@ -1080,6 +1274,22 @@ impl BlockChain {
 		self.db.key_value().read(db::COL_EXTRA, &hash)
 	}
 
+	/// Write the return data of a block's transactions.
+	pub fn insert_block_outputs(&self, batch: &mut DBTransaction, hash: H256, outputs: BlockOutputs) {
+		batch.write(db::COL_EXTRA, &hash, &outputs);
+	}
+
+	/// Remove the return data of a block's transactions.
+	pub fn remove_block_outputs(&self, batch: &mut DBTransaction, hash: &H256) {
+		Writable::delete::<BlockOutputs, _>(batch, db::COL_EXTRA, hash);
+	}
+
+	/// Get the transaction return data recorded for a block, if it was imported with
+	/// output recording enabled.
+	pub fn block_outputs(&self, hash: &H256) -> Option<BlockOutputs> {
+		self.db.key_value().read(db::COL_EXTRA, hash)
+	}
+
 	/// Add a child to a given block. Assumes that the block hash is in
 	/// the chain and the child's parent is this block.
 	///
@@ -1131,6 +1341,15 @@ impl BlockChain {
 		batch.put(db::COL_HEADERS, hash.as_bytes(), &compressed_header);
 		batch.put(db::COL_BODIES, hash.as_bytes(), &compressed_body);
 
+		let number = block.header_view().number();
+		if number > 1 {
+			// blocks stored before skip pointers were introduced have none, so reaching the
+			// target may take a read per block. Lookups fall back to parent links without it.
+			if let Some(skip) = self.ancestor_within(parent_hash, skip_number(number), MAX_SKIP_POINTER_STEPS) {
+				batch.write(db::COL_EXTRA, &hash, &BlockSkip(skip));
+			}
+		}
+
 		let info = self.block_info(&block.header_view(), route, &extras);
 
 		if let BlockLocation::BranchBecomingCanonChain(ref d) = info.location {
@@ -1140,6 +1359,8 @@ impl BlockChain {
 				Colour::White.paint(format!("#{} {}", self.block_details(&d.ancestor).expect("`ancestor` is in the route; qed").number, d.ancestor)),
 				Colour::Green.paint(d.enacted.iter().join(" "))
 			);
+
+			self.insert_reorg(batch, d, info.hash);
 		}
 
 		self.prepare_update(batch, ExtrasUpdate {
@@ -1214,13 +1435,19 @@ impl BlockChain {
 
 		{
 			let mut write_receipts = self.block_receipts.write();
-			batch.extend_with_cache(db::COL_EXTRA, &mut *write_receipts, update.block_receipts, CacheUpdatePolicy::Remove);
+			if self.compress_receipts {
+				for (hash, receipts) in update.block_receipts {
+					let compressed = compress(&rlp::encode(&receipts), receipts_swapper());
+					batch.put(db::COL_EXTRA, Key::<BlockReceipts>::key(&hash).as_bytes(), &compressed);
+					write_receipts.remove(&hash);
+				}
+			} else {
+				batch.extend_with_cache(db::COL_EXTRA, &mut *write_receipts, update.block_receipts, CacheUpdatePolicy::Remove);
+			}
 		}
 
 		if let Some((block, blooms)) = update.blocks_blooms {
-			self.db.blooms()
-				.insert_blooms(block, blooms.iter())
-				.expect("Low level database error when updating blooms. Some issue with disk?");
+			self.insert_blooms(block, blooms);
 		}
 
 		// These cached values must be updated last with all four locks taken to avoid
@@ -1246,6 +1473,57 @@ impl BlockChain {
 		}
 	}
 
+	/// Writes the blooms of blocks starting at `from`, or buffers them during bulk insertion.
+	fn insert_blooms(&self, from: BlockNumber, blooms: Vec<Bloom>) {
+		let mut bulk_blooms = self.bulk_blooms.lock();
+		let pending = match *bulk_blooms {
+			Some(ref mut pending) => pending,
+			None => return self.write_blooms(from, &blooms),
+		};
+
+		// only blooms continuing the buffered run, or replacing part of it on a reorg, are buffered
+		let end = pending.from + pending.blooms.len() as BlockNumber;
+		if pending.blooms.is_empty() || from < pending.from || from > end {
+			self.flush_blooms(pending);
+			pending.from = from;
+		}
+
+		pending.blooms.truncate((from - pending.from) as usize);
+		pending.blooms.extend(blooms);
+		if pending.blooms.len() >= BULK_BLOOMS_CHUNK {
+			self.flush_blooms(pending);
+		}
+	}
+
+	fn flush_blooms(&self, pending: &mut PendingBlooms) {
+		if !pending.blooms.is_empty() {
+			self.write_blooms(pending.from, &pending.blooms);
+			pending.blooms.clear();
+		}
+	}
+
+	fn write_blooms(&self, from: BlockNumber, blooms: &[Bloom]) {
+		self.db.blooms()
+			.insert_blooms(from, blooms.iter())
+			.expect("Low level database error when updating blooms. Some issue with disk?");
+	}
+
+	/// Starts bulk insertion: bloom updates of inserted blocks are buffered and those of consecutive
+	/// blocks written out together, in chunks. Log queries read the buffered blooms as well.
+	pub fn begin_bulk_insert(&self) {
+		let mut bulk_blooms = self.bulk_blooms.lock();
+		if bulk_blooms.is_none() {
+			*bulk_blooms = Some(PendingBlooms { from: 0, blooms: Vec::new() });
+		}
+	}
+
+	/// Writes out the bloom updates buffered since `begin_bulk_insert` and ends bulk insertion.
+	pub fn end_bulk_insert(&self) {
+		if let Some(mut pending) = self.bulk_blooms.lock().take() {
+			self.flush_blooms(&mut pending);
+		}
+	}
+
 	/// Apply pending insertion updates
 	pub fn commit(&self) {
 		let mut pending_best_ancient_block = self.pending_best_ancient_block.write();
@@ -1253,6 +1531,7 @@ impl BlockChain {
 		let mut pending_write_hashes = self.pending_block_hashes.write();
 		let mut pending_block_details = self.pending_block_details.write();
 		let mut pending_write_txs = self.pending_transaction_addresses.write();
+		let mut pending_next_reorg_index = self.pending_next_reorg_index.write();
 
 		let mut best_block = self.best_block.write();
 		let mut best_ancient_block = self.best_ancient_block.write();
@@ -1267,6 +1546,9 @@ impl BlockChain {
 		if let Some(block) = pending_best_block.take() {
 			*best_block = block;
 		}
+		if let Some(index) = pending_next_reorg_index.take() {
+			*self.next_reorg_index.lock() = index;
+		}
 
 		let pending_txs = mem::replace(&mut *pending_write_txs, HashMap::new());
 		let (retracted_txs, enacted_txs) = pending_txs.into_iter().partition::<HashMap<_, _>, _>(|&(_, ref value)| value.is_none());
@@ -1358,6 +1640,114 @@ impl BlockChain {
 		Some(ret)
 	}
 
+	/// Returns the hashes of the blocks in the range of `filter` which may contain matching logs, oldest first,
+	/// and whether some of them may not be on the canon chain.
+	fn filter_blocks(&self, filter: &Filter) -> Result<(Vec<H256>, bool), BlockId> {
+		let best_block_number = self.best_block_number();
+		let block_number = |id: &BlockId| match *id {
+			BlockId::Number(number) => Some(number),
+			BlockId::Hash(ref hash) => self.block_number(hash),
+			BlockId::Earliest => Some(0),
+			BlockId::Latest => Some(best_block_number),
+		};
+		let block_hash = |id: &BlockId| match *id {
+			BlockId::Hash(hash) => Some(hash),
+			BlockId::Number(number) => self.block_hash(number),
+			BlockId::Earliest => self.block_hash(0),
+			BlockId::Latest => Some(self.best_block_hash()),
+		};
+		// Blocks referred by number are always on the canon chain.
+		let is_canon = |id: &BlockId| match *id {
+			BlockId::Hash(ref hash) => self.is_canon(hash),
+			BlockId::Earliest | BlockId::Latest | BlockId::Number(_) => true,
+		};
+
+		if is_canon(&filter.from_block) && is_canon(&filter.to_block) {
+			// On the canon chain the blooms database tells which blocks to read. Pending blocks are handled by
+			// the callers, so anything above the best block doesn't exist.
+			let from = match block_number(&filter.from_block) {
+				Some(number) if number <= best_block_number => number,
+				_ => return Err(filter.from_block),
+			};
+			let to = match block_number(&filter.to_block) {
+				Some(number) if number <= best_block_number => number,
+				_ => return Err(filter.to_block),
+			};
+			if from > to {
+				return Err(filter.to_block);
+			}
+
+			let blocks = self.blocks_with_bloom(&filter.bloom_possibilities(), from, to)
+				.into_iter()
+				.filter_map(|number| self.block_hash(number))
+				.collect();
+			return Ok((blocks, false));
+		}
+
+		// Otherwise walk back from `to_block` until `from_block` is reached, checking the header blooms.
+		let from_hash = block_hash(&filter.from_block).ok_or(filter.from_block)?;
+		let from_number = self.block_number(&from_hash).ok_or(BlockId::Hash(from_hash))?;
+		let to_hash = block_hash(&filter.to_block).ok_or(filter.to_block)?;
+
+		let blooms = filter.bloom_possibilities();
+		let mut blocks = Vec::new();
+		let mut current_hash = to_hash;
+		loop {
+			let header = self.block_header_data(&current_hash).ok_or(BlockId::Hash(current_hash))?;
+			if blooms.iter().any(|bloom| header.log_bloom().contains_bloom(bloom)) {
+				blocks.push(current_hash);
+			}
+			if header.number() <= from_number {
+				break;
+			}
+			current_hash = header.parent_hash();
+		}
+
+		// `from_block` has to be an ancestor of `to_block`.
+		if current_hash != from_hash || blocks.is_empty() {
+			return Err(BlockId::Hash(from_hash));
+		}
+
+		blocks.reverse();
+		Ok((blocks, true))
+	}
+
+	/// Returns the logs of a block matching `filter`, reading its receipts and body once.
+	fn block_logs(&self, hash: &H256, filter: &Filter, check_canon: bool) -> Vec<LocalizedLogEntry> {
+		let (number, receipts, body) = match (self.block_number(hash), self.block_receipts(hash), self.block_body(hash)) {
+			(Some(number), Some(receipts), Some(body)) => (number, receipts.receipts, body),
+			_ => return Vec::new(),
+		};
+		let transaction_hashes = body.transaction_hashes();
+		// receipts of system calls may follow those of the transactions, their logs aren't reported
+		if receipts.len() < transaction_hashes.len() {
+			warn!("Block {} ({}) has fewer receipts ({}) than transactions ({}), skipping its logs. Database corrupt?", number, hash, receipts.len(), transaction_hashes.len());
+			return Vec::new();
+		}
+		let removed = check_canon && !self.is_canon(hash);
+
+		let mut logs = Vec::new();
+		let mut log_index = 0;
+		for (transaction_index, (receipt, transaction_hash)) in receipts.into_iter().zip(transaction_hashes).enumerate() {
+			for (transaction_log_index, entry) in receipt.logs.into_iter().enumerate() {
+				if filter.matches(&entry) {
+					logs.push(LocalizedLogEntry {
+						entry,
+						block_hash: *hash,
+						block_number: number,
+						transaction_hash,
+						transaction_index,
+						transaction_log_index,
+						log_index,
+						removed,
+					});
+				}
+				log_index += 1;
+			}
+		}
+		logs
+	}
+
 	/// This function returns modified block hashes.
 	fn prepare_block_hashes_update(&self, info: &BlockInfo) -> HashMap<BlockNumber, H256> {
 		let mut block_hashes = HashMap::new();
@@ -1617,12 +2007,32 @@ impl BlockChain {
 	}
 }
 
+/// Number of the ancestor a block at `number` keeps a skip pointer to.
+///
+/// Same choice as Bitcoin Core's `GetSkipHeight`: pointers of nearby blocks jump by
+/// very different distances, which keeps `BlockChain::ancestor` at O(log n) steps.
+fn skip_number(number: BlockNumber) -> BlockNumber {
+	fn invert_lowest_one(n: BlockNumber) -> BlockNumber {
+		n & n.wrapping_sub(1)
+	}
+
+	if number < 2 {
+		return 0;
+	}
+
+	match number & 1 {
+		1 => invert_lowest_one(invert_lowest_one(number - 1)) + 1,
+		_ => invert_lowest_one(number),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
 	use std::iter;
 
+	use common_types::log_entry::LogEntry;
 	use common_types::receipt::{Receipt, TransactionOutcome};
 	use common_types::transaction::{Transaction, Action};
 	use crate::generator::{BlockGenerator, BlockBuilder, BlockOptions};
@@ -1785,6 +2195,54 @@ mod tests {
 		assert_eq!(block_hashes.len(), 11);
 	}
 
+	#[test]
+	fn skip_numbers_point_backwards() {
+		assert_eq!(skip_number(0), 0);
+		assert_eq!(skip_number(1), 0);
+		assert_eq!(skip_number(2), 0);
+		assert_eq!(skip_number(12), 8);
+		assert_eq!(skip_number(13), 1);
+		assert_eq!(skip_number(15), 9);
+		for number in 2..1000 {
+			assert!(skip_number(number) < number);
+		}
+	}
+
+	#[test]
+	fn finds_ancestors_and_common_ancestor() {
+		let genesis = BlockBuilder::genesis();
+		let common = genesis.add_blocks(40);
+		let canon = common.add_blocks(60);
+		let fork_start = common.add_block_with_difficulty(9);
+		let fork = fork_start.add_blocks(20);
+
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		for block in BlockGenerator::new(vec![common.clone(), canon.clone(), fork_start, fork.clone()]) {
+			insert_block(&db, &bc, block.encoded(), vec![]);
+		}
+
+		let canon_hash = canon.last().hash();
+		let fork_hash = fork.last().hash();
+		let common_hash = common.last().hash();
+		assert_eq!(bc.best_block_hash(), canon_hash);
+
+		for number in 0..=100 {
+			assert_eq!(bc.ancestor(canon_hash, number), bc.block_hash(number));
+		}
+		assert_eq!(bc.ancestor(canon_hash, 101), None);
+		assert_eq!(bc.ancestor(fork_hash, 40), Some(common_hash));
+		assert_eq!(bc.ancestor(fork_hash, 61), Some(fork_hash));
+		assert_eq!(bc.ancestor_within(canon_hash, 100, 0), Some(canon_hash));
+		assert_eq!(bc.ancestor_within(canon_hash, 99, 1), bc.block_hash(99));
+		assert_eq!(bc.ancestor_within(canon_hash, 0, 1), None);
+
+		assert_eq!(bc.common_ancestor(canon_hash, fork_hash), Some(common_hash));
+		assert_eq!(bc.common_ancestor(fork_hash, canon_hash), Some(common_hash));
+		assert_eq!(bc.common_ancestor(canon_hash, common_hash), Some(common_hash));
+		assert_eq!(bc.common_ancestor(canon_hash, canon_hash), Some(canon_hash));
+	}
+
 	#[test]
 	fn test_find_uncles() {
 		let genesis = BlockBuilder::genesis();
@@ -1874,6 +2332,89 @@ mod tests {
 		assert_eq!(bc.transaction_address(&t1_hash), None);
 	}
 
+	#[test]
+	fn records_reorgs() {
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block();
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2 = b1b.add_block();
+
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		insert_block(&db, &bc, b1a.last().encoded(), vec![]);
+		insert_block(&db, &bc, b1b.last().encoded(), vec![]);
+		assert!(bc.reorg_history(10).is_empty());
+
+		insert_block_commit(&db, &bc, b2.last().encoded(), vec![], false);
+		assert_eq!(*bc.next_reorg_index.lock(), 0);
+		bc.commit();
+		assert_eq!(*bc.next_reorg_index.lock(), 1);
+		let reorgs = bc.reorg_history(10);
+		assert_eq!(reorgs.len(), 1);
+		assert_eq!(reorgs[0].ancestor, genesis.last().hash());
+		assert_eq!(reorgs[0].retracted, vec![b1a.last().hash()]);
+		assert_eq!(reorgs[0].enacted, vec![b1b.last().hash(), b2.last().hash()]);
+
+		// numbering continues after reopening the chain.
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		assert_eq!(*bc.next_reorg_index.lock(), 1);
+		assert_eq!(bc.reorg_history(10), reorgs);
+		assert!(bc.reorg_history(0).is_empty());
+		assert_eq!(bc.reorg_history(usize::max_value()), reorgs);
+	}
+
+	#[test]
+	fn prunes_reorgs_when_history_is_lowered() {
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block();
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2b = b1b.add_block();
+		let b2a = b1a.add_block_with_difficulty(20);
+
+		// b2b makes the b1b branch canonical, and b2a switches back to b1a
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		insert_block(&db, &bc, b1a.last().encoded(), vec![]);
+		insert_block(&db, &bc, b1b.last().encoded(), vec![]);
+		insert_block(&db, &bc, b2b.last().encoded(), vec![]);
+		insert_block(&db, &bc, b2a.last().encoded(), vec![]);
+		assert_eq!(bc.reorg_history(10).len(), 2);
+
+		let config = Config { reorg_history: 1, ..Default::default() };
+		let bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone());
+		let reorgs = bc.reorg_history(10);
+		assert_eq!(reorgs.len(), 1);
+		assert_eq!(reorgs[0].enacted, vec![b1a.last().hash(), b2a.last().hash()]);
+		assert_eq!(bc.reorg_records().count(), 1);
+		assert_eq!(*bc.next_reorg_index.lock(), 2);
+	}
+
+	#[test]
+	fn reads_compressed_receipts() {
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block();
+		let b1_hash = b1.last().hash();
+		let receipts = vec![Receipt {
+			outcome: TransactionOutcome::StatusCode(1),
+			gas_used: 21_000.into(),
+			log_bloom: Default::default(),
+			logs: vec![],
+		}];
+
+		let db = new_db();
+		let config = Config { compress_receipts: true, ..Default::default() };
+		let bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone());
+		insert_block(&db, &bc, b1.last().encoded(), receipts.clone());
+
+		let raw = db.key_value().get(db::COL_EXTRA, Key::<BlockReceipts>::key(&b1_hash).as_bytes()).unwrap().unwrap();
+		assert!(raw.len() < rlp::encode(&BlockReceipts::new(receipts.clone())).len());
+		assert_eq!(bc.block_receipts(&b1_hash).unwrap().receipts, receipts);
+
+		// existing entries stay readable once compression is turned off.
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+		assert_eq!(bc.block_receipts(&b1_hash).unwrap().receipts, receipts);
+	}
+
 	#[test]
 	fn test_overwriting_transaction_addresses() {
 		let t1 = Transaction {
@@ -2225,10 +2766,20 @@ mod tests {
 			}
 		]);
 
+		let filter = |from_block, to_block, limit, offset| Filter {
+			from_block,
+			to_block,
+			address: None,
+			topics: vec![None, None, None, None],
+			limit,
+			offset,
+		};
+
 		// when
-		let logs1 = bc.logs(vec![b1_hash, b2_hash], |_| true, None);
-		let logs2 = bc.logs(vec![b1_hash, b2_hash], |_| true, Some(1));
-		let logs3 = bc.logs(vec![b3_hash], |_| true, None);
+		let logs1 = bc.logs(&filter(BlockId::Number(1), BlockId::Latest, None, None)).unwrap();
+		let logs2 = bc.logs(&filter(BlockId::Number(1), BlockId::Latest, Some(1), None)).unwrap();
+		let logs3 = bc.logs(&filter(BlockId::Hash(b3_hash), BlockId::Hash(b3_hash), None, None)).unwrap();
+		let logs4 = bc.logs(&filter(BlockId::Earliest, BlockId::Latest, Some(2), Some(1))).unwrap();
 
 		// then
 		assert_eq!(logs1, vec![
@@ -2240,6 +2791,7 @@ mod tests {
 				transaction_index: 0,
 				transaction_log_index: 0,
 				log_index: 0,
+				removed: false,
 			},
 			LocalizedLogEntry {
 				entry: LogEntry { address: Default::default(), topics: vec![], data: vec![2] },
@@ -2249,6 +2801,7 @@ mod tests {
 				transaction_index: 0,
 				transaction_log_index: 1,
 				log_index: 1,
+				removed: false,
 			},
 			LocalizedLogEntry {
 				entry: LogEntry { address: Default::default(), topics: vec![], data: vec![3] },
@@ -2258,6 +2811,7 @@ mod tests {
 				transaction_index: 1,
 				transaction_log_index: 0,
 				log_index: 2,
+				removed: false,
 			},
 			LocalizedLogEntry {
 				entry: LogEntry { address: Default::default(), topics: vec![], data: vec![4] },
@@ -2267,6 +2821,7 @@ mod tests {
 				transaction_index: 0,
 				transaction_log_index: 0,
 				log_index: 0,
+				removed: false,
 			}
 		]);
 		assert_eq!(logs2, vec![
@@ -2278,6 +2833,7 @@ mod tests {
 				transaction_index: 0,
 				transaction_log_index: 0,
 				log_index: 0,
+				removed: false,
 			}
 		]);
 		assert_eq!(logs3, vec![
@@ -2289,6 +2845,29 @@ mod tests {
 				transaction_index: 0,
 				transaction_log_index: 0,
 				log_index: 0,
+				removed: true,
+			}
+		]);
+		assert_eq!(logs4, vec![
+			LocalizedLogEntry {
+				entry: LogEntry { address: Default::default(), topics: vec![], data: vec![2] },
+				block_hash: b1_hash,
+				block_number: b1_number,
+				transaction_hash: tx_hash1,
+				transaction_index: 0,
+				transaction_log_index: 1,
+				log_index: 1,
+				removed: false,
+			},
+			LocalizedLogEntry {
+				entry: LogEntry { address: Default::default(), topics: vec![], data: vec![3] },
+				block_hash: b1_hash,
+				block_number: b1_number,
+				transaction_hash: tx_hash2,
+				transaction_index: 1,
+				transaction_log_index: 0,
+				log_index: 2,
+				removed: false,
 			}
 		]);
 	}
@@ -2361,6 +2940,68 @@ mod tests {
 		assert_eq!(blocks_ba, vec![3]);
 	}
 
+	#[test]
+	fn test_bulk_insert_blooms() {
+		let bloom_b1 = Bloom::from_low_u64_be(1);
+		let bloom_b3 = Bloom::from_low_u64_be(3);
+		let bloom_ba = Bloom::from_low_u64_be(0xa);
+
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with(|| BlockOptions {
+			bloom: bloom_b1.clone(),
+			difficulty: 9.into(),
+			..Default::default()
+		});
+		// b2 has an empty bloom
+		let b2 = b1.add_block();
+		let b3 = b2.add_block_with_bloom(bloom_b3);
+		let b1a = genesis.add_block_with_bloom(bloom_ba);
+		let b2a = b1a.add_block_with_bloom(bloom_ba);
+
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+
+		bc.begin_bulk_insert();
+		insert_block(&db, &bc, b1.last().encoded(), vec![]);
+		insert_block(&db, &bc, b2.last().encoded(), vec![]);
+		insert_block(&db, &bc, b1a.last().encoded(), vec![]);
+		// reorg to the fork and back
+		insert_block(&db, &bc, b2a.last().encoded(), vec![]);
+		insert_block(&db, &bc, b3.last().encoded(), vec![]);
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_b1), 0, 5), vec![1]);
+		assert!(bc.blocks_with_bloom(Some(&bloom_ba), 0, 5).is_empty());
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_b3), 0, 5), vec![3]);
+
+		bc.end_bulk_insert();
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_b1), 0, 5), vec![1]);
+		assert!(bc.blocks_with_bloom(Some(&bloom_ba), 0, 5).is_empty());
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_b3), 0, 5), vec![3]);
+	}
+
+	#[test]
+	fn test_bulk_insert_blooms_keeps_blooms_outside_the_run() {
+		let bloom_1 = Bloom::from_low_u64_be(1);
+		let bloom_3 = Bloom::from_low_u64_be(3);
+		let bloom_5 = Bloom::from_low_u64_be(5);
+
+		let genesis = BlockBuilder::genesis();
+		let db = new_db();
+		let bc = new_chain(genesis.last().encoded(), db.clone());
+
+		bc.insert_blooms(3, vec![bloom_3]);
+
+		// the run of block 1 doesn't reach block 5, the bloom of block 3 in between must stay
+		bc.begin_bulk_insert();
+		bc.insert_blooms(1, vec![bloom_1]);
+		bc.insert_blooms(5, vec![bloom_5]);
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_3), 0, 5), vec![3]);
+		bc.end_bulk_insert();
+
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_1), 0, 5), vec![1]);
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_3), 0, 5), vec![3]);
+		assert_eq!(bc.blocks_with_bloom(Some(&bloom_5), 0, 5), vec![5]);
+	}
+
 	#[test]
 	fn test_insert_unordered() {
 		let bloom_b1 = Bloom::from_str("00000020000000000000000000000000000000000000000002000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000040000000000000010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008000400000000000000000000002000").unwrap();
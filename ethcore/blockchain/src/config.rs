@@ -23,6 +23,10 @@ pub struct Config {
 	pub pref_cache_size: usize,
 	/// Maximum cache size in bytes.
 	pub max_cache_size: usize,
+	/// Number of most recent chain reorganizations kept in the database; 0 disables recording.
+	pub reorg_history: u64,
+	/// Whether to compress block receipts before writing them to the database.
+	pub compress_receipts: bool,
 }
 
 impl Default for Config {
@@ -30,6 +34,8 @@ impl Default for Config {
 		Config {
 			pref_cache_size: 1 << 14,
 			max_cache_size: 1 << 20,
+			reorg_history: 256,
+			compress_receipts: false,
 		}
 	}
 }
@@ -35,6 +35,6 @@ pub use crate::{
 	config::Config,
 	update::ExtrasInsert,
 };
-pub use ethcore_db::keys::{BlockReceipts, BlockDetails, TransactionAddress, BlockNumberKey};
+pub use ethcore_db::keys::{BlockReceipts, BlockDetails, BlockOutputs, TransactionAddress, BlockNumberKey};
 pub use common_types::tree_route::TreeRoute;
 
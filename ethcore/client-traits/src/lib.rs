@@ -32,15 +32,17 @@ use common_types::{
 	call_analytics::CallAnalytics,
 	chain_notify::{NewBlocks, ChainMessageType},
 	client_types::Mode,
+	disk_usage::DiskUsage,
 	encoded,
 	engines::{epoch::Transition as EpochTransition, machine::Executed},
 	errors::{EthcoreError, EthcoreResult},
 	filter::Filter,
-	header::Header,
+	header::{Header, LocalizedUncle},
 	ids::{BlockId, TransactionId, TraceId, UncleId},
 	log_entry::LocalizedLogEntry,
 	pruning_info::PruningInfo,
 	receipt::LocalizedReceipt,
+	reorg::Reorg,
 	trace_filter::Filter as TraceFilter,
 	transaction::{self, Action, LocalizedTransaction, CallError, SignedTransaction, UnverifiedTransaction},
 	tree_route::TreeRoute,
@@ -187,6 +189,12 @@ pub trait ImportBlock {
 	/// Import a block into the blockchain.
 	fn import_block(&self, block: Unverified) -> EthcoreResult<H256>;
 
+	/// Import a block received from the given peer into the blockchain. Only a few blocks from
+	/// each peer are held back when their timestamp is too far in the future.
+	fn import_block_from_peer(&self, block: Unverified, _peer_id: usize) -> EthcoreResult<H256> {
+		self.import_block(block)
+	}
+
 	/// Triggered by a message from a block queue when the block is ready for insertion.
 	/// Returns the number of blocks imported.
 	fn import_verified_blocks(&self) -> usize;
@@ -216,6 +224,9 @@ impl Tick for () {}
 pub trait BadBlocks {
 	/// Returns a list of blocks that were recently not imported because they were invalid.
 	fn bad_blocks(&self) -> Vec<(Unverified, String)>;
+
+	/// Forget all blocks known to be bad, allowing them to be imported again.
+	fn clear_bad_blocks(&self);
 }
 
 
@@ -284,12 +295,32 @@ pub trait BlockChainClient:
 	/// If `after` is set the list starts with the following item.
 	fn list_storage(&self, id: BlockId, account: &Address, after: Option<&H256>, count: Option<u64>) -> Option<Vec<H256>>;
 
+	/// Get up to `count` accounts of the state at block `id` in trie order, along with their trie keys:
+	/// addresses if fat DB is in operation, otherwise address hashes. `None` if the state is not available.
+	/// If `after` is set the list starts with the following key.
+	fn list_account_entries(&self, id: BlockId, after: Option<&[u8]>, count: u64) -> Option<Vec<(Bytes, BasicAccount)>>;
+
+	/// Keep the state of block `number` and later blocks from being pruned until `unpin_state` is called,
+	/// e.g. while it is read in chunks. Pins are counted.
+	fn pin_state(&self, number: BlockNumber);
+
+	/// Release a pin previously taken with `pin_state`.
+	fn unpin_state(&self, number: BlockNumber);
+
 	/// Get transaction with given hash.
 	fn transaction(&self, id: TransactionId) -> Option<LocalizedTransaction>;
 
 	/// Get uncle with given id.
 	fn uncle(&self, id: UncleId) -> Option<encoded::Header>;
 
+	/// Get uncle with given id along with the block including it.
+	fn localized_uncle(&self, id: UncleId) -> Option<LocalizedUncle> {
+		let block_hash = self.block_hash(id.block)?;
+		let block_number = self.block_number(BlockId::Hash(block_hash))?;
+		let header = self.uncle(UncleId { block: BlockId::Hash(block_hash), position: id.position })?;
+		Some(LocalizedUncle { header, block_hash, block_number, position: id.position })
+	}
+
 	/// Get transaction receipt with given hash.
 	fn transaction_receipt(&self, id: TransactionId) -> Option<LocalizedReceipt>;
 
@@ -326,6 +357,10 @@ pub trait BlockChainClient:
 	/// Replays all the transactions in a given block for inspection.
 	fn replay_block_transactions(&self, block: BlockId, analytics: CallAnalytics) -> Result<Box<dyn Iterator<Item = (H256, Executed<FlatTrace, VMTrace>)>>, CallError>;
 
+	/// Re-executes a whole block on top of its parent's state, rewards included, and
+	/// reports the state root obtained next to the one recorded in its header.
+	fn replay_block(&self, block: BlockId, analytics: CallAnalytics) -> Result<ReplayedBlock, CallError>;
+
 	/// Returns traces matching given filter.
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>>;
 
@@ -376,6 +411,10 @@ pub trait BlockChainClient:
 	/// Set the mode.
 	fn set_mode(&self, mode: Mode);
 
+	/// Notes the total difficulty of the network, as agreed on by connected peers. In passive mode,
+	/// an awake client stays awake while the network is notably ahead of it.
+	fn note_network_difficulty(&self, _total_difficulty: U256) {}
+
 	/// Get the chain spec name.
 	fn spec_name(&self) -> String;
 
@@ -395,6 +434,15 @@ pub trait BlockChainClient:
 	/// Returns information about pruning/data availability.
 	fn pruning_info(&self) -> PruningInfo;
 
+	/// Returns the name of the state pruning algorithm in use.
+	fn pruning_mode(&self) -> &'static str;
+
+	/// Returns the size on disk of the client databases, if known.
+	fn disk_usage(&self) -> Option<DiskUsage>;
+
+	/// Returns up to `count` most recent reorganizations of the canonical chain, newest first.
+	fn reorg_history(&self, count: usize) -> Vec<Reorg>;
+
 	/// Returns a transaction signed with the key configured in the engine signer.
 	fn create_transaction(&self, tx_request: TransactionRequest) -> Result<SignedTransaction, transaction::Error>;
 
@@ -403,6 +451,23 @@ pub trait BlockChainClient:
 	fn transact(&self, tx_request: TransactionRequest) -> Result<(), transaction::Error>;
 }
 
+/// Outcome of re-executing a block with `BlockChainClient::replay_block`.
+pub struct ReplayedBlock {
+	/// Hash and execution result of each transaction, in block order.
+	pub transactions: Vec<(H256, Executed<FlatTrace, VMTrace>)>,
+	/// State root obtained by re-executing the block.
+	pub state_root: H256,
+	/// State root recorded in the block header.
+	pub expected_state_root: H256,
+}
+
+impl ReplayedBlock {
+	/// Whether re-execution reproduced the state root recorded in the header.
+	pub fn state_root_matches(&self) -> bool {
+		self.state_root == self.expected_state_root
+	}
+}
+
 /// The data required for a `Client` to create a transaction.
 ///
 /// Gas limit, gas price, or nonce can be set explicitly, e.g. to create service
@@ -22,9 +22,11 @@ use std::convert::AsRef;
 use common_types::BlockNumber;
 use common_types::engines::epoch::Transition as EpochTransition;
 use common_types::receipt::Receipt;
+use common_types::reorg::Reorg;
 use ethereum_types::{H256, H264, U256};
 use parity_util_mem::MallocSizeOf;
 use kvdb::PREFIX_LEN as DB_PREFIX_LEN;
+use parity_bytes::Bytes;
 use rlp;
 use rlp_derive::{RlpEncodableWrapper, RlpDecodableWrapper, RlpEncodable, RlpDecodable};
 
@@ -45,6 +47,12 @@ pub enum ExtrasIndex {
 	EpochTransitions = 5,
 	/// Pending epoch transition data index.
 	PendingEpochTransition = 6,
+	/// Chain reorganizations index.
+	Reorgs = 7,
+	/// Block skip pointer index.
+	BlockSkip = 8,
+	/// Transaction outputs index.
+	BlockOutputs = 9,
 }
 
 fn with_index(hash: &H256, i: ExtrasIndex) -> H264 {
@@ -85,6 +93,14 @@ impl Key<BlockDetails> for H256 {
 	}
 }
 
+impl Key<BlockSkip> for H256 {
+	type Target = H264;
+
+	fn key(&self) -> H264 {
+		with_index(self, ExtrasIndex::BlockSkip)
+	}
+}
+
 impl Key<TransactionAddress> for H256 {
 	type Target = H264;
 
@@ -101,6 +117,14 @@ impl Key<BlockReceipts> for H256 {
 	}
 }
 
+impl Key<BlockOutputs> for H256 {
+	type Target = H264;
+
+	fn key(&self) -> H264 {
+		with_index(self, ExtrasIndex::BlockOutputs)
+	}
+}
+
 impl Key<common_types::engines::epoch::PendingTransition> for H256 {
 	type Target = H264;
 
@@ -139,6 +163,36 @@ impl Key<EpochTransitions> for u64 {
 	}
 }
 
+/// length of reorg keys.
+pub const REORG_KEY_LEN: usize = DB_PREFIX_LEN + 16;
+
+/// reorg key prefix.
+/// used to iterate over all recorded reorgs in the order they happened.
+pub const REORG_KEY_PREFIX: &'static [u8; DB_PREFIX_LEN] = &[
+	ExtrasIndex::Reorgs as u8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Reorg record key
+pub struct ReorgKey([u8; REORG_KEY_LEN]);
+
+impl AsRef<[u8]> for ReorgKey {
+	fn as_ref(&self) -> &[u8] { &self.0[..] }
+}
+
+impl Key<Reorg> for u64 {
+	type Target = ReorgKey;
+
+	fn key(&self) -> Self::Target {
+		let mut arr = [0u8; REORG_KEY_LEN];
+		arr[..DB_PREFIX_LEN].copy_from_slice(&REORG_KEY_PREFIX[..]);
+
+		write!(&mut arr[DB_PREFIX_LEN..], "{:016x}", self)
+			.expect("format arg is valid; no more than 16 chars will be written; qed");
+
+		ReorgKey(arr)
+	}
+}
+
 /// Familial details concerning a block
 #[derive(Debug, Clone, MallocSizeOf)]
 pub struct BlockDetails {
@@ -195,6 +249,10 @@ impl rlp::Decodable for BlockDetails {
 	}
 }
 
+/// Hash of a distant ancestor of a block, used to find ancestors without walking every parent.
+#[derive(Debug, Clone, Copy, PartialEq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct BlockSkip(pub H256);
+
 /// Represents address of certain transaction within block
 #[derive(Debug, PartialEq, Clone, RlpEncodable, RlpDecodable, MallocSizeOf)]
 pub struct TransactionAddress {
@@ -218,6 +276,20 @@ impl BlockReceipts {
 	}
 }
 
+/// Return data of every transaction in a block, recorded when the block was imported.
+#[derive(Debug, Clone, PartialEq, RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct BlockOutputs {
+	/// Transaction outputs, in block order.
+	pub outputs: Vec<Bytes>,
+}
+
+impl BlockOutputs {
+	/// Create new block outputs wrapper.
+	pub fn new(outputs: Vec<Bytes>) -> Self {
+		BlockOutputs { outputs }
+	}
+}
+
 /// Candidate transitions to an epoch with specific number.
 #[derive(Clone, RlpEncodable, RlpDecodable)]
 pub struct EpochTransitions {
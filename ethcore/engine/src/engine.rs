@@ -182,6 +182,19 @@ pub trait Engine: Sync + Send {
 		Ok(())
 	}
 
+	/// System calls to make once `on_close_block` has been applied, as `(contract, data)` pairs.
+	///
+	/// Each call is made from the system address with unlimited gas and, unlike calls made from
+	/// `on_close_block`, gets a receipt appended to the block after those of its transactions.
+	/// The logs of the calls are thus part of the block's receipts root and log bloom.
+	fn system_calls_on_close_block(
+		&self,
+		_block: &ExecutedBlock,
+		_parent_header: &Header,
+	) -> Result<Vec<(Address, Vec<u8>)>, Error> {
+		Ok(Vec::new())
+	}
+
 	/// Allow mutating the header during seal generation. Currently only used by Clique.
 	fn on_seal_block(&self, _block: &mut ExecutedBlock) -> Result<(), Error> { Ok(()) }
 
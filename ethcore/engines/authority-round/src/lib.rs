@@ -32,6 +32,7 @@
 //!   software bug), e.g. if they proposed multiple blocks with the same step number.
 
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::TryFrom;
 use std::{cmp, fmt};
 use std::iter::{self, FromIterator};
 use std::ops::Deref;
@@ -135,27 +136,32 @@ const U16_MAX: usize = ::std::u16::MAX as usize;
 /// The number of recent block hashes for which the gas limit override is memoized.
 const GAS_LIMIT_OVERRIDE_CACHE_CAPACITY: usize = 10;
 
-impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
-	fn from(p: ethjson::spec::AuthorityRoundParams) -> Self {
+impl TryFrom<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
+	type Error = Error;
+
+	fn try_from(p: ethjson::spec::AuthorityRoundParams) -> Result<Self, Self::Error> {
+		let custom = |msg: &str| Error::Engine(EngineError::Custom(msg.into()));
 		let map_step_duration = |u: ethjson::uint::Uint| {
 			let mut step_duration_usize: usize = u.into();
 			if step_duration_usize == 0 {
-				panic!("AuthorityRoundParams: step duration cannot be 0");
+				return Err(custom("stepDuration cannot be 0"));
 			}
 			if step_duration_usize > U16_MAX {
 				warn!(target: "engine", "step duration is too high ({}), setting it to {}", step_duration_usize, U16_MAX);
 				step_duration_usize = U16_MAX;
 			}
-			step_duration_usize as u64
+			Ok(step_duration_usize as u64)
 		};
 		let step_durations: BTreeMap<_, _> = match p.step_duration {
 			ethjson::spec::StepDuration::Single(u) =>
-				iter::once((0,  map_step_duration(u))).collect(),
+				iter::once(map_step_duration(u).map(|d| (0, d))).collect::<Result<_, _>>()?,
 			ethjson::spec::StepDuration::Transitions(tr) => {
 				if tr.is_empty() {
-					panic!("AuthorityRoundParams: step duration transitions cannot be empty");
+					return Err(custom("stepDuration transitions cannot be empty"));
 				}
-				tr.into_iter().map(|(timestamp, u)| (timestamp.into(), map_step_duration(u))).collect()
+				tr.into_iter()
+					.map(|(timestamp, u)| map_step_duration(u).map(|d| (timestamp.into(), d)))
+					.collect::<Result<_, _>>()?
 			}
 		};
 		let transition_block_num = p.block_reward_contract_transition.map_or(0, Into::into);
@@ -169,7 +175,7 @@ impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
 			br_transitions.keys().next().map_or(false, |&block_num| block_num <= transition_block_num)
 		{
 			let s = "blockRewardContractTransition";
-			panic!("{} should be less than any of the keys in {}s", s, s);
+			return Err(custom(&format!("{} should be less than any of the keys in {}s", s, s)));
 		}
 		if let Some(code) = p.block_reward_contract_code {
 			br_transitions.insert(
@@ -193,7 +199,7 @@ impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
 			.into_iter()
 			.map(|(block_num, address)| (block_num.into(), address.into()))
 			.collect();
-		AuthorityRoundParams {
+		Ok(AuthorityRoundParams {
 			step_durations,
 			validators: new_validator_set(p.validators),
 			start_step: p.start_step.map(Into::into),
@@ -210,7 +216,7 @@ impl From<ethjson::spec::AuthorityRoundParams> for AuthorityRoundParams {
 			strict_empty_steps_transition: p.strict_empty_steps_transition.map_or(0, Into::into),
 			randomness_contract_address,
 			block_gas_limit_contract_transitions,
-		}
+		})
 	}
 }
 
@@ -1906,6 +1912,7 @@ fn next_step_time_duration(info: StepDurationInfo, time: u64) -> Option<(u64, u6
 #[cfg(test)]
 mod tests {
 	use std::collections::BTreeMap;
+	use std::convert::TryFrom;
 	use std::str::FromStr;
 	use std::sync::Arc;
 	use std::sync::atomic::{AtomicUsize, AtomicU64, Ordering as AtomicOrdering};
@@ -2919,7 +2926,7 @@ mod tests {
 			}
 		}"#;
 		let deserialized: ethjson::spec::AuthorityRound = serde_json::from_str(config).unwrap();
-		let params = AuthorityRoundParams::from(deserialized.params);
+		let params = AuthorityRoundParams::try_from(deserialized.params).unwrap();
 		for ((block_num1, address1), (block_num2, address2)) in
 			params.block_reward_contract_transitions.iter().zip(
 				[(0u64, BlockRewardContract::new_from_address(Address::from_str("2000000000000000000000000000000000000002").unwrap())),
@@ -2933,7 +2940,6 @@ mod tests {
 	}
 
 	#[test]
-	#[should_panic(expected="blockRewardContractTransition should be less than any of the keys in blockRewardContractTransitions")]
 	fn should_reject_out_of_order_block_reward_transition() {
 		let config = r#"{
 			"params": {
@@ -2950,6 +2956,12 @@ mod tests {
 			}
 		}"#;
 		let deserialized: ethjson::spec::AuthorityRound = serde_json::from_str(config).unwrap();
-		AuthorityRoundParams::from(deserialized.params);
+		match AuthorityRoundParams::try_from(deserialized.params) {
+			Err(Error::Engine(EngineError::Custom(msg))) => assert_eq!(
+				msg,
+				"blockRewardContractTransition should be less than any of the keys in blockRewardContractTransitions"
+			),
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
 	}
 }
@@ -81,6 +81,13 @@ pub struct EthashParams {
 	pub ecip1017_era_rounds: u64,
 	/// Block reward in base units.
 	pub block_reward: BTreeMap<BlockNumber, U256>,
+	/// Divisor of the uncle reward: an uncle `k` generations behind the including block
+	/// receives `block_reward * (uncle_reward_divisor - k) / uncle_reward_divisor`. Within
+	/// ECIP-1017 eras after the first, every uncle receives `block_reward / (uncle_reward_divisor * 4)`.
+	pub uncle_reward_divisor: u64,
+	/// Divisor of the nephew reward: the block author receives `block_reward / nephew_reward_divisor`
+	/// for every uncle included.
+	pub nephew_reward_divisor: u64,
 	/// EXPIP-2 block height
 	pub expip2_transition: u64,
 	/// EXPIP-2 duration limit
@@ -131,6 +138,8 @@ impl From<ethjson::spec::EthashParams> for EthashParams {
 						},
 					}
 				}),
+			uncle_reward_divisor: p.uncle_reward_divisor.map_or(8, Into::into),
+			nephew_reward_divisor: p.nephew_reward_divisor.map_or(32, Into::into),
 			expip2_transition: p.expip2_transition.map_or(u64::max_value(), Into::into),
 			expip2_duration_limit: p.expip2_duration_limit.map_or(30, Into::into),
 			progpow_transition: p.progpow_transition.map_or(u64::max_value(), Into::into),
@@ -248,8 +257,6 @@ impl Engine for Ethash {
 	/// Apply the block reward on finalisation of the block.
 	/// This assumes that all uncles are valid uncles (i.e. of at least one generation before the current).
 	fn on_close_block(&self, block: &mut ExecutedBlock, _parent_header: &Header) -> Result<(), Error> {
-		use std::ops::Shr;
-
 		let author = *block.header.author();
 		let number = block.header.number();
 
@@ -285,17 +292,21 @@ impl Engine for Ethash {
 				let n_uncles = block.uncles.len();
 
 				// Bestow block rewards.
-				let result_block_reward = reward + reward.shr(5) * U256::from(n_uncles);
+				let nephew_reward = reward / U256::from(self.ethash_params.nephew_reward_divisor);
+				let result_block_reward = reward + nephew_reward * U256::from(n_uncles);
 
 				rewards.push((author, RewardKind::Author, result_block_reward));
 
 				// Bestow uncle rewards.
+				let divisor = self.ethash_params.uncle_reward_divisor;
 				for u in &block.uncles {
 					let uncle_author = u.author();
 					let result_uncle_reward = if eras == 0 {
-						(reward * U256::from(8 + u.number() - number)).shr(3)
+						let share = (divisor + u.number()).saturating_sub(number);
+						reward * U256::from(share) / U256::from(divisor)
 					} else {
-						reward.shr(5)
+						// ECIP-1017 eras pay a flat 1/32 with the default divisor of 8.
+						reward / U256::from(divisor * 4)
 					};
 
 					rewards.push((*uncle_author, RewardKind::uncle(number, u.number()), result_uncle_reward));
@@ -535,6 +546,8 @@ mod tests {
 			ecip1010_pause_transition: u64::max_value(),
 			ecip1010_continue_transition: u64::max_value(),
 			ecip1017_era_rounds: u64::max_value(),
+			uncle_reward_divisor: 8,
+			nephew_reward_divisor: 32,
 			expip2_transition: u64::max_value(),
 			expip2_duration_limit: 30,
 			block_reward_contract: None,
@@ -611,6 +624,71 @@ mod tests {
 		assert_eq!(b.state.balance(&uncle_author).unwrap(), "3cb71f51fc558000".into());
 	}
 
+	#[test]
+	fn on_close_block_with_custom_uncle_rewards() {
+		let spec = test_spec();
+		let machine = new_homestead_test_machine();
+		let ethparams = EthashParams {
+			block_reward: {
+				let mut ret = BTreeMap::new();
+				ret.insert(0, 0x1000.into());
+				ret
+			},
+			uncle_reward_divisor: 4,
+			nephew_reward_divisor: 16,
+			..get_default_ethash_params()
+		};
+		let tempdir = TempDir::new("").unwrap();
+		let ethash = Ethash::new(tempdir.path(), ethparams, machine, None);
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(&ethash, Default::default(), false, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author = Address::from_str("ef2d6d194084c2de36e0dabfce45d046b37d1106").unwrap();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close().unwrap();
+		// 0x1000 block reward plus 0x1000 / 16 for the included uncle.
+		assert_eq!(b.state.balance(&Address::zero()).unwrap(), 0x1100.into());
+		// Uncle one generation behind: 0x1000 * (4 - 1) / 4.
+		assert_eq!(b.state.balance(&uncle_author).unwrap(), 0xc00.into());
+	}
+
+	#[test]
+	fn on_close_block_with_custom_uncle_rewards_in_ecip1017_era() {
+		let spec = test_spec();
+		let machine = new_homestead_test_machine();
+		let ethparams = EthashParams {
+			block_reward: {
+				let mut ret = BTreeMap::new();
+				ret.insert(0, 0x1000.into());
+				ret
+			},
+			ecip1017_era_rounds: 2,
+			uncle_reward_divisor: 4,
+			nephew_reward_divisor: 16,
+			..get_default_ethash_params()
+		};
+		let tempdir = TempDir::new("").unwrap();
+		let ethash = Ethash::new(tempdir.path(), ethparams, machine, None);
+		// block 3 is in the second era
+		let mut parent_header = spec.genesis_header();
+		parent_header.set_number(2);
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![parent_header.hash()]);
+		let mut b = OpenBlock::new(&ethash, Default::default(), false, db, &parent_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		let mut uncle = Header::new();
+		let uncle_author = Address::from_str("ef2d6d194084c2de36e0dabfce45d046b37d1106").unwrap();
+		uncle.set_author(uncle_author);
+		b.push_uncle(uncle).unwrap();
+
+		let b = b.close().unwrap();
+		// Era reward 0x1000 * 4 / 5 = 0xccc, of which uncles get 1 / (4 * 4).
+		assert_eq!(b.state.balance(&uncle_author).unwrap(), 0xcc.into());
+	}
+
 	#[test]
 	fn has_valid_mcip3_era_block_rewards() {
 		let spec = new_mcip3_test();
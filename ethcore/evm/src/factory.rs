@@ -20,12 +20,13 @@ use std::sync::Arc;
 use vm::{Exec, Schedule};
 use ethereum_types::U256;
 use super::vm::ActionParams;
-use super::interpreter::SharedCache;
+use super::interpreter::{SharedCache, Interpreter, InstructionObserver};
 
 /// Evm factory. Creates appropriate Evm.
 #[derive(Clone)]
 pub struct Factory {
 	evm_cache: Arc<SharedCache>,
+	observer: Option<Arc<dyn InstructionObserver>>,
 }
 
 impl Factory {
@@ -33,9 +34,9 @@ impl Factory {
 	/// Might choose implementation depending on supplied gas.
 	pub fn create(&self, params: ActionParams, schedule: &Schedule, depth: usize) -> Box<dyn Exec> {
 		if Self::can_fit_in_usize(&params.gas) {
-			Box::new(super::interpreter::Interpreter::<usize>::new(params, self.evm_cache.clone(), schedule, depth))
+			Box::new(Interpreter::<usize>::new(params, self.evm_cache.clone(), schedule, depth).with_observer(self.observer.clone()))
 		} else {
-			Box::new(super::interpreter::Interpreter::<U256>::new(params, self.evm_cache.clone(), schedule, depth))
+			Box::new(Interpreter::<U256>::new(params, self.evm_cache.clone(), schedule, depth).with_observer(self.observer.clone()))
 		}
 	}
 
@@ -44,9 +45,17 @@ impl Factory {
 	pub fn new(cache_size: usize) -> Self {
 		Factory {
 			evm_cache: Arc::new(SharedCache::new(cache_size)),
+			observer: None,
 		}
 	}
 
+	/// Install an observer on every VM created by this factory.
+	/// See `interpreter::DebugSession` for a step debugger built on top of it.
+	pub fn with_observer(mut self, observer: Arc<dyn InstructionObserver>) -> Self {
+		self.observer = Some(observer);
+		self
+	}
+
 	fn can_fit_in_usize(gas: &U256) -> bool {
 		gas == &U256::from(gas.low_u64() as usize)
 	}
@@ -57,6 +66,7 @@ impl Default for Factory {
 	fn default() -> Factory {
 		Factory {
 			evm_cache: Arc::new(SharedCache::default()),
+			observer: None,
 		}
 	}
 }
@@ -22,6 +22,7 @@ mod gasometer;
 mod stack;
 mod memory;
 mod shared_cache;
+mod observer;
 
 use std::marker::PhantomData;
 use std::{cmp, mem};
@@ -45,6 +46,7 @@ use self::gasometer::Gasometer;
 use self::stack::{Stack, VecStack};
 use self::memory::Memory;
 pub use self::shared_cache::SharedCache;
+pub use self::observer::{InstructionObserver, Step, Breakpoints, DebugSession, PausedStep};
 
 use bit_set::BitSet;
 
@@ -185,6 +187,7 @@ pub struct Interpreter<Cost: CostType> {
 	resume_output_range: Option<(U256, U256)>,
 	resume_result: Option<InstructionResult<Cost>>,
 	last_stack_ret_len: usize,
+	observer: Option<Arc<dyn InstructionObserver>>,
 	_type: PhantomData<Cost>,
 }
 
@@ -287,10 +290,17 @@ impl<Cost: CostType> Interpreter<Cost> {
 			last_stack_ret_len: 0,
 			resume_output_range: None,
 			resume_result: None,
+			observer: None,
 			_type: PhantomData,
 		}
 	}
 
+	/// Install an observer consulted before every instruction.
+	pub fn with_observer(mut self, observer: Option<Arc<dyn InstructionObserver>>) -> Self {
+		self.observer = observer;
+		self
+	}
+
 	/// Execute a single step on the VM.
 	#[inline(always)]
 	pub fn step(&mut self, ext: &mut dyn vm::Ext) -> InterpreterResult {
@@ -346,6 +356,21 @@ impl<Cost: CostType> Interpreter<Cost> {
 					return InterpreterResult::Done(Err(e));
 				};
 
+				if let Some(ref observer) = self.observer {
+					let pc = self.reader.position - 1;
+					if observer.should_break(pc, instruction) {
+						observer.on_break(Step {
+							depth: ext.depth(),
+							address: &self.params.address,
+							pc,
+							instruction,
+							gas: self.gasometer.as_ref().expect(GASOMETER_PROOF).current_gas.as_u256(),
+							stack: self.stack.peek_top(self.stack.size()),
+							memory: &self.mem,
+						});
+					}
+				}
+
 				// Calculate gas cost
 				let requirements = match self.gasometer.as_mut().expect(GASOMETER_PROOF).requirements(ext, instruction, info, &self.stack, self.mem.size()) {
 					Ok(t) => t,
@@ -547,8 +572,7 @@ impl<Cost: CostType> Interpreter<Cost> {
 				// clear return data buffer before creating new call frame.
 				self.return_data = ReturnData::empty();
 
-				let can_create = ext.balance(&self.params.address)? >= endowment && ext.depth() < ext.schedule().max_depth;
-				if !can_create {
+				if ext.check_nested_call(&self.params.address, &endowment)?.is_some() {
 					self.stack.push(U256::zero());
 					return Ok(InstructionResult::UnusedGas(create_gas));
 				}
@@ -609,29 +633,26 @@ impl<Cost: CostType> Interpreter<Cost> {
 					true => Cost::from(0),
 				})).0;
 
-				// Get sender & receive addresses, check if we have balance
-				let (sender_address, receive_address, has_balance, call_type) = match instruction {
+				// Get sender & receive addresses
+				let (sender_address, receive_address, call_type) = match instruction {
 					instructions::CALL => {
 						if ext.is_static() && value.map_or(false, |v| !v.is_zero()) {
 							return Err(vm::Error::MutableCallInStaticContext);
 						}
-						let has_balance = ext.balance(&self.params.address)? >= value.expect("value set for all but delegate call; qed");
-						(&self.params.address, &code_address, has_balance, ActionType::Call)
-					},
-					instructions::CALLCODE => {
-						let has_balance = ext.balance(&self.params.address)? >= value.expect("value set for all but delegate call; qed");
-						(&self.params.address, &self.params.address, has_balance, ActionType::CallCode)
+						(&self.params.address, &code_address, ActionType::Call)
 					},
-					instructions::DELEGATECALL => (&self.params.sender, &self.params.address, true, ActionType::DelegateCall),
-					instructions::STATICCALL => (&self.params.address, &code_address, true, ActionType::StaticCall),
+					instructions::CALLCODE => (&self.params.address, &self.params.address, ActionType::CallCode),
+					instructions::DELEGATECALL => (&self.params.sender, &self.params.address, ActionType::DelegateCall),
+					instructions::STATICCALL => (&self.params.address, &code_address, ActionType::StaticCall),
 					_ => panic!(format!("Unexpected instruction {:?} in CALL branch.", instruction))
 				};
 
 				// clear return data buffer before creating new call frame.
 				self.return_data = ReturnData::empty();
 
-				let can_call = has_balance && ext.depth() < ext.schedule().max_depth;
-				if !can_call {
+				// Value is only transferred (and has to be covered) by CALL|CALLCODE; a refused call
+				// returns all of `call_gas`, stipend included.
+				if ext.check_nested_call(&self.params.address, &value.unwrap_or_default())?.is_some() {
 					self.stack.push(U256::zero());
 					return Ok(InstructionResult::UnusedGas(call_gas));
 				}
@@ -0,0 +1,333 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Instruction-level hooks for debuggers.
+//!
+//! An `InstructionObserver` installed on the `Factory` is consulted before every
+//! instruction. When it asks to break, the interpreter hands it a `Step` with the
+//! current stack and memory and does not continue until `on_break` returns, so a
+//! blocking observer effectively pauses execution.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+
+use ethereum_types::{U256, Address};
+use parking_lot::{Mutex, Condvar, RwLock};
+
+use instructions::Instruction;
+
+/// Machine state right before an instruction is executed.
+pub struct Step<'a> {
+	/// Call depth of the executing frame.
+	pub depth: usize,
+	/// Address of the executing code.
+	pub address: &'a Address,
+	/// Program counter of the instruction.
+	pub pc: usize,
+	/// Instruction about to be executed.
+	pub instruction: Instruction,
+	/// Gas left before the instruction is charged.
+	pub gas: U256,
+	/// Stack, bottom first.
+	pub stack: &'a [U256],
+	/// Memory.
+	pub memory: &'a [u8],
+}
+
+/// Hook into the interpreter loop.
+pub trait InstructionObserver: Send + Sync {
+	/// Whether execution should break before `instruction` at `pc`.
+	/// Called for every instruction, so it should be cheap.
+	fn should_break(&self, pc: usize, instruction: Instruction) -> bool;
+
+	/// Called when `should_break` returned `true`. Execution resumes when this returns.
+	fn on_break(&self, step: Step);
+}
+
+/// Set of program counters and opcodes to break at.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Breakpoints {
+	pcs: HashSet<usize>,
+	opcodes: HashSet<u8>,
+}
+
+impl Breakpoints {
+	/// Break before the instruction at `pc`.
+	pub fn add_pc(&mut self, pc: usize) {
+		self.pcs.insert(pc);
+	}
+
+	/// Break before every occurrence of `instruction`.
+	pub fn add_instruction(&mut self, instruction: Instruction) {
+		self.opcodes.insert(instruction as u8);
+	}
+
+	/// Remove a program counter breakpoint. Returns `false` if it was not set.
+	pub fn remove_pc(&mut self, pc: usize) -> bool {
+		self.pcs.remove(&pc)
+	}
+
+	/// Remove an opcode breakpoint. Returns `false` if it was not set.
+	pub fn remove_instruction(&mut self, instruction: Instruction) -> bool {
+		self.opcodes.remove(&(instruction as u8))
+	}
+
+	/// Remove all breakpoints.
+	pub fn clear(&mut self) {
+		self.pcs.clear();
+		self.opcodes.clear();
+	}
+
+	/// Whether any breakpoint matches.
+	pub fn matches(&self, pc: usize, instruction: Instruction) -> bool {
+		self.pcs.contains(&pc) || self.opcodes.contains(&(instruction as u8))
+	}
+}
+
+/// Owned copy of a `Step`, handed out to the debugger while execution is paused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PausedStep {
+	/// Call depth of the executing frame.
+	pub depth: usize,
+	/// Address of the executing code.
+	pub address: Address,
+	/// Program counter of the instruction.
+	pub pc: usize,
+	/// Instruction about to be executed.
+	pub instruction: Instruction,
+	/// Gas left before the instruction is charged.
+	pub gas: U256,
+	/// Stack, bottom first.
+	pub stack: Vec<U256>,
+	/// Memory.
+	pub memory: Vec<u8>,
+}
+
+impl<'a> From<Step<'a>> for PausedStep {
+	fn from(step: Step<'a>) -> Self {
+		PausedStep {
+			depth: step.depth,
+			address: *step.address,
+			pc: step.pc,
+			instruction: step.instruction,
+			gas: step.gas,
+			stack: step.stack.to_vec(),
+			memory: step.memory.to_vec(),
+		}
+	}
+}
+
+#[derive(Default)]
+struct SessionState {
+	paused: Option<PausedStep>,
+	single_step: bool,
+	detached: bool,
+}
+
+/// Blocking observer for step debuggers.
+///
+/// Execution runs on its own thread and blocks at every breakpoint until the
+/// debugger calls `resume` or `step`. Dropping interest in the session must be
+/// signalled with `detach`, which releases any paused execution and disables
+/// all further breaks.
+#[derive(Default)]
+pub struct DebugSession {
+	breakpoints: RwLock<Breakpoints>,
+	state: Mutex<SessionState>,
+	cond: Condvar,
+}
+
+impl DebugSession {
+	/// New session without any breakpoints.
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// New session breaking before the first instruction.
+	pub fn paused_at_start() -> Self {
+		let session = Self::new();
+		session.state.lock().single_step = true;
+		session
+	}
+
+	/// Current breakpoints.
+	pub fn breakpoints(&self) -> Breakpoints {
+		self.breakpoints.read().clone()
+	}
+
+	/// Replace the breakpoints.
+	pub fn set_breakpoints(&self, breakpoints: Breakpoints) {
+		*self.breakpoints.write() = breakpoints;
+	}
+
+	/// Step at which execution is currently paused, if any.
+	pub fn paused(&self) -> Option<PausedStep> {
+		self.state.lock().paused.clone()
+	}
+
+	/// Wait up to `timeout` for execution to pause.
+	pub fn wait_paused(&self, timeout: Duration) -> Option<PausedStep> {
+		let deadline = Instant::now() + timeout;
+		let mut state = self.state.lock();
+		// the condition is also notified on resume and step, and may wake up spuriously
+		while state.paused.is_none() && !state.detached {
+			if self.cond.wait_until(&mut state, deadline).timed_out() {
+				break;
+			}
+		}
+		state.paused.clone()
+	}
+
+	/// Continue until the next breakpoint.
+	pub fn resume(&self) {
+		let mut state = self.state.lock();
+		state.paused = None;
+		self.cond.notify_all();
+	}
+
+	/// Execute the current instruction and pause before the next one.
+	pub fn step(&self) {
+		let mut state = self.state.lock();
+		state.single_step = true;
+		state.paused = None;
+		self.cond.notify_all();
+	}
+
+	/// Release paused execution and stop breaking. Irreversible.
+	pub fn detach(&self) {
+		let mut state = self.state.lock();
+		state.detached = true;
+		state.paused = None;
+		self.cond.notify_all();
+	}
+}
+
+impl InstructionObserver for DebugSession {
+	fn should_break(&self, pc: usize, instruction: Instruction) -> bool {
+		{
+			let state = self.state.lock();
+			if state.detached {
+				return false;
+			}
+			if state.single_step {
+				return true;
+			}
+		}
+		self.breakpoints.read().matches(pc, instruction)
+	}
+
+	fn on_break(&self, step: Step) {
+		let mut state = self.state.lock();
+		if state.detached {
+			return;
+		}
+		state.single_step = false;
+		state.paused = Some(step.into());
+		self.cond.notify_all();
+		while state.paused.is_some() {
+			self.cond.wait(&mut state);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::thread;
+	use std::time::Duration;
+	use ethereum_types::U256;
+	use parking_lot::Mutex;
+	use vm::{ActionParams, Ext};
+	use vm::tests::{FakeExt, test_finalize};
+	use hex_literal::hex;
+	use factory::Factory;
+	use instructions::Instruction;
+	use super::*;
+
+	// 60 01    PUSH1 1
+	// 60 02    PUSH1 2
+	// 01       ADD
+	// 60 00    PUSH1 0
+	// 55       SSTORE
+	fn params() -> ActionParams {
+		let mut params = ActionParams::default();
+		params.gas = U256::from(100_000);
+		params.code = Some(Arc::new(hex!("6001600201600055").to_vec()));
+		params
+	}
+
+	#[derive(Default)]
+	struct Recorder {
+		steps: Mutex<Vec<PausedStep>>,
+	}
+
+	impl InstructionObserver for Recorder {
+		fn should_break(&self, _pc: usize, instruction: Instruction) -> bool {
+			instruction == Instruction::ADD
+		}
+
+		fn on_break(&self, step: Step) {
+			self.steps.lock().push(step.into());
+		}
+	}
+
+	#[test]
+	fn should_observe_matching_instructions() {
+		let recorder = Arc::new(Recorder::default());
+		let factory = Factory::default().with_observer(recorder.clone());
+		let mut ext = FakeExt::new();
+		let vm = factory.create(params(), ext.schedule(), ext.depth());
+		test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap();
+
+		let steps = recorder.steps.lock();
+		assert_eq!(steps.len(), 1);
+		assert_eq!(steps[0].pc, 4);
+		assert_eq!(steps[0].instruction, Instruction::ADD);
+		assert_eq!(steps[0].stack, vec![U256::from(1), U256::from(2)]);
+		assert_eq!(steps[0].gas, U256::from(100_000 - 6));
+	}
+
+	#[test]
+	fn should_pause_and_resume_execution() {
+		let session = Arc::new(DebugSession::paused_at_start());
+		let mut breakpoints = Breakpoints::default();
+		breakpoints.add_instruction(Instruction::SSTORE);
+		session.set_breakpoints(breakpoints);
+
+		let factory = Factory::default().with_observer(session.clone());
+		let handle = thread::spawn(move || {
+			let mut ext = FakeExt::new();
+			let vm = factory.create(params(), ext.schedule(), ext.depth());
+			test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap();
+			ext.store
+		});
+
+		let timeout = Duration::from_secs(5);
+		assert_eq!(session.wait_paused(timeout).unwrap().pc, 0);
+		session.step();
+		let paused = session.wait_paused(timeout).unwrap();
+		assert_eq!(paused.pc, 2);
+		assert_eq!(paused.stack, vec![U256::from(1)]);
+		session.resume();
+		let paused = session.wait_paused(timeout).unwrap();
+		assert_eq!(paused.instruction, Instruction::SSTORE);
+		assert_eq!(paused.stack, vec![U256::from(3), U256::zero()]);
+		session.detach();
+
+		let store = handle.join().unwrap();
+		assert_eq!(store.len(), 1);
+	}
+}
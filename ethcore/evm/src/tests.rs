@@ -832,6 +832,43 @@ fn test_calls(factory: super::Factory) {
 	assert_eq!(ext.calls.len(), 2);
 }
 
+evm_test!{test_refused_calls: test_refused_calls_int}
+fn test_refused_calls(factory: super::Factory) {
+	let code = hex!("600054602d57600160005560006000600060006050610998610100f160006000600060006050610998610100f25b").to_vec();
+
+	let address = Address::from_low_u64_be(0x155);
+	let mut params = ActionParams::default();
+	params.gas = U256::from(150_000);
+	params.code = Some(Arc::new(code));
+	params.address = address.clone();
+
+	// at the depth limit, with enough balance
+	let mut ext = FakeExt::new();
+	ext.depth = ext.schedule().max_depth;
+	ext.balances.insert(address.clone(), params.gas);
+	assert_eq!(ext.check_nested_call(&address, &U256::from(0x50)).unwrap(), Some(vm::CallRefusal::DepthLimit));
+
+	let gas_left = {
+		let vm = factory.create(params.clone(), ext.schedule(), ext.depth());
+		test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+	};
+	assert_eq!(ext.calls.len(), 0);
+	// all gas handed to the refused calls, stipend included, is returned
+	assert_eq!(gas_left, U256::from(91_405));
+
+	// without balance to cover the value
+	let mut ext = FakeExt::new();
+	assert_eq!(ext.check_nested_call(&address, &U256::from(0x50)).unwrap(), Some(vm::CallRefusal::InsufficientBalance));
+	assert_eq!(ext.check_nested_call(&address, &U256::zero()).unwrap(), None);
+
+	let gas_left = {
+		let vm = factory.create(params, ext.schedule(), ext.depth());
+		test_finalize(vm.exec(&mut ext).ok().unwrap()).unwrap()
+	};
+	assert_eq!(ext.calls.len(), 0);
+	assert_eq!(gas_left, U256::from(91_405));
+}
+
 evm_test!{test_create_in_staticcall: test_create_in_staticcall_int}
 fn test_create_in_staticcall(factory: super::Factory) {
 	let code = hex!("600060006064f000").to_vec();
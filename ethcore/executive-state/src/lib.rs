@@ -165,6 +165,21 @@ pub trait ExecutiveState {
 		where
 			T: trace::Tracer,
 			V: trace::VMTracer;
+
+	/// Execute a given transaction like `apply_with_tracing`, returning the receipt along with
+	/// the full execution result. The result carries the state diff of the transaction if
+	/// `state_diff` is set.
+	fn apply_with_analytics<V, T>(
+		&mut self,
+		env_info: &EnvInfo,
+		machine: &Machine,
+		t: &SignedTransaction,
+		options: TransactOptions<T, V>,
+		state_diff: bool,
+	) -> Result<(Receipt, RawExecuted<T::Output, V::Output>), Error>
+		where
+			T: trace::Tracer,
+			V: trace::VMTracer;
 }
 
 impl<B: Backend> ExecutiveState for State<B> {
@@ -202,23 +217,7 @@ impl<B: Backend> ExecutiveState for State<B> {
 	{
 		let options = TransactOptions::new(tracer, vm_tracer);
 		let e = execute(self, env_info, machine, t, options, false)?;
-		let params = machine.params();
-
-		let eip658 = env_info.number >= params.eip658_transition;
-		let no_intermediate_commits =
-			eip658 ||
-				(env_info.number >= params.eip98_transition && env_info.number >= params.validate_receipts_transition);
-
-		let outcome = if no_intermediate_commits {
-			if eip658 {
-				TransactionOutcome::StatusCode(if e.exception.is_some() { 0 } else { 1 })
-			} else {
-				TransactionOutcome::Unknown
-			}
-		} else {
-			self.commit()?;
-			TransactionOutcome::StateRoot(self.root().clone())
-		};
+		let outcome = transaction_outcome(self, env_info, machine, e.exception.is_some())?;
 
 		let output = e.output;
 		let receipt = Receipt::new(outcome, e.cumulative_gas_used, e.logs);
@@ -231,6 +230,67 @@ impl<B: Backend> ExecutiveState for State<B> {
 			vm_trace: e.vm_trace,
 		})
 	}
+
+	fn apply_with_analytics<V, T>(
+		&mut self,
+		env_info: &EnvInfo,
+		machine: &Machine,
+		t: &SignedTransaction,
+		options: TransactOptions<T, V>,
+		state_diff: bool,
+	) -> Result<(Receipt, RawExecuted<T::Output, V::Output>), Error>
+		where
+			T: trace::Tracer,
+			V: trace::VMTracer,
+	{
+		if state_diff {
+			self.checkpoint();
+		}
+		let mut e = match execute(self, env_info, machine, t, options, false) {
+			Ok(e) => e,
+			Err(err) => {
+				if state_diff {
+					self.discard_checkpoint();
+				}
+				return Err(err.into());
+			}
+		};
+		if state_diff {
+			e.state_diff = Some(self.diff_from_checkpoint()?);
+		}
+		let outcome = transaction_outcome(self, env_info, machine, e.exception.is_some())?;
+
+		let receipt = Receipt::new(outcome, e.cumulative_gas_used, e.logs.clone());
+		trace!(target: "state", "Transaction receipt: {:?}", receipt);
+
+		Ok((receipt, e))
+	}
+}
+
+// Outcome recorded in the receipt of a transaction just executed on `state`.
+fn transaction_outcome<B: Backend>(
+	state: &mut State<B>,
+	env_info: &EnvInfo,
+	machine: &Machine,
+	failed: bool,
+) -> Result<TransactionOutcome, Error> {
+	let params = machine.params();
+
+	let eip658 = env_info.number >= params.eip658_transition;
+	let no_intermediate_commits =
+		eip658 ||
+			(env_info.number >= params.eip98_transition && env_info.number >= params.validate_receipts_transition);
+
+	Ok(if no_intermediate_commits {
+		if eip658 {
+			TransactionOutcome::StatusCode(if failed { 0 } else { 1 })
+		} else {
+			TransactionOutcome::Unknown
+		}
+	} else {
+		state.commit()?;
+		TransactionOutcome::StateRoot(state.root().clone())
+	})
 }
 
 // Execute a given transaction without committing changes.
@@ -1281,6 +1341,20 @@ mod tests {
 		assert_eq!(state.nonce(&a).unwrap(), U256::from(3u64));
 	}
 
+	#[test]
+	fn set_balance_and_nonce() {
+		let mut state = get_temp_state();
+		let a = Address::zero();
+		state.add_balance(&a, &U256::from(69u64), CleanupMode::NoEmpty).unwrap();
+		state.set_balance(&a, U256::from(42u64)).unwrap();
+		state.set_nonce(&a, U256::from(7u64)).unwrap();
+		assert_eq!(state.balance(&a).unwrap(), U256::from(42u64));
+		assert_eq!(state.nonce(&a).unwrap(), U256::from(7u64));
+		state.commit().unwrap();
+		assert_eq!(state.balance(&a).unwrap(), U256::from(42u64));
+		assert_eq!(state.nonce(&a).unwrap(), U256::from(7u64));
+	}
+
 	#[test]
 	fn balance_nonce() {
 		let mut state = get_temp_state();
@@ -1662,6 +1736,56 @@ mod tests {
 					})).as_ref());
 	}
 
+	#[test]
+	fn should_diff_only_changes_since_checkpoint() {
+		let a = Address::from_low_u64_be(10);
+		let b = Address::from_low_u64_be(20);
+		let key = BigEndianHash::from_uint(&U256::from(1u64));
+		let db = get_temp_state_db();
+
+		let (root, db) = {
+			let mut state = State::new(db, U256::from(0), Default::default());
+			state.add_balance(&a, &100.into(), CleanupMode::ForceCreate).unwrap();
+			state.set_storage(&b, key, BigEndianHash::from_uint(&U256::from(20u64))).unwrap();
+			state.commit().unwrap();
+			state.drop()
+		};
+
+		let mut state = State::from_existing(db, root, U256::from(0u8), Default::default()).unwrap();
+		// changed before the checkpoint, so not part of the diff.
+		state.add_balance(&a, &1.into(), CleanupMode::NoEmpty).unwrap();
+		state.checkpoint();
+		state.set_storage(&b, key, BigEndianHash::from_uint(&U256::from(100u64))).unwrap();
+
+		let diff = state.diff_from_checkpoint().unwrap();
+		let diff_map = diff.raw;
+		assert_eq!(diff_map.len(), 1);
+		assert_eq!(diff_map.get(&b),
+			pod::account::diff_pod(
+				Some(&PodAccount {
+					balance: U256::zero(),
+					nonce: U256::zero(),
+					code: Some(Default::default()),
+					storage: vec![(key, BigEndianHash::from_uint(&U256::from(20u64)))].into_iter().collect(),
+					version: U256::zero(),
+				}),
+				Some(&PodAccount {
+					balance: U256::zero(),
+					nonce: U256::zero(),
+					code: Some(Default::default()),
+					storage: vec![(key, BigEndianHash::from_uint(&U256::from(100u64)))].into_iter().collect(),
+					version: U256::zero(),
+				})).as_ref());
+
+		// the checkpoint is discarded, the changes are kept.
+		assert_eq!(state.storage_at(&b, &key).unwrap(), BigEndianHash::from_uint(&U256::from(100u64)));
+		state.checkpoint();
+		state.kill_account(&a);
+		let diff = state.diff_from_checkpoint().unwrap();
+		assert_eq!(diff.raw.len(), 1);
+		assert!(diff.raw.get(&a).is_some());
+	}
+
 	#[test]
 	fn should_get_full_pod_storage_values() {
 		let a = Address::from_low_u64_be(10);
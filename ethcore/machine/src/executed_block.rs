@@ -19,11 +19,13 @@
 //! "open", "closed", "locked", "sealed". They all embed an `ExecutedBlock`.
 
 use std::{
+	borrow::Cow,
 	collections::HashSet,
 	sync::Arc,
 };
 
 use ethereum_types::{H256, U256};
+use parity_bytes::Bytes;
 
 use account_state::State;
 use common_types::{
@@ -46,6 +48,11 @@ pub struct ExecutedBlock {
 	pub uncles: Vec<Header>,
 	/// Transaction receipts.
 	pub receipts: Vec<Receipt>,
+	/// Receipts of system calls made when closing the block, kept apart from the
+	/// transaction receipts so that those stay indexed by transaction.
+	pub system_receipts: Vec<Receipt>,
+	/// Return data of executed transactions, if it is recorded.
+	pub outputs: Option<Vec<Bytes>>,
 	/// Hashes of already executed transactions.
 	pub transactions_set: HashSet<H256>,
 	/// Underlying state.
@@ -64,6 +71,8 @@ impl ExecutedBlock {
 			transactions: Default::default(),
 			uncles: Default::default(),
 			receipts: Default::default(),
+			system_receipts: Default::default(),
+			outputs: None,
 			transactions_set: Default::default(),
 			state,
 			traces: if tracing {
@@ -89,6 +98,15 @@ impl ExecutedBlock {
 		}
 	}
 
+	/// All receipts the block's receipts root commits to: those of the transactions,
+	/// followed by those of the system calls.
+	pub fn block_receipts(&self) -> Cow<[Receipt]> {
+		match self.system_receipts.is_empty() {
+			true => Cow::Borrowed(&self.receipts),
+			false => Cow::Owned(self.receipts.iter().chain(&self.system_receipts).cloned().collect()),
+		}
+	}
+
 	/// Get mutable access to a state.
 	pub fn state_mut(&mut self) -> &mut State<StateDB> {
 		&mut self.state
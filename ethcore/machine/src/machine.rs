@@ -32,6 +32,7 @@ use common_types::{
 		params::CommonParams,
 	},
 	errors::{EngineError, EthcoreError as Error},
+	receipt::{Receipt, TransactionOutcome},
 	transaction::{self, SYSTEM_ADDRESS, UNSIGNED_SENDER, UnverifiedTransaction, SignedTransaction},
 };
 use vm::{ActionType, ActionParams, ActionValue, ParamsType};
@@ -129,6 +130,46 @@ impl Machine {
 		)
 	}
 
+	/// Same as `execute_as_system`, but the call is recorded on the block: a receipt with the logs of
+	/// the call is added to the block's system receipts, which the receipts root and the log bloom of
+	/// the block cover after the transaction receipts. The receipt doesn't add to the gas used by the block.
+	pub fn execute_as_system_with_receipt(
+		&self,
+		block: &mut ExecutedBlock,
+		contract_address: Address,
+		gas: U256,
+		data: Option<Vec<u8>>,
+	) -> Result<Vec<u8>, Error> {
+		let (code, code_hash) = {
+			let state = &block.state;
+
+			(state.code(&contract_address)?,
+			 state.code_hash(&contract_address)?)
+		};
+
+		let (output, substate, applied) = self.execute_system_call(
+			block,
+			Some(contract_address),
+			code,
+			code_hash,
+			None,
+			gas,
+			data,
+			None,
+		)?;
+
+		let outcome = if block.header.number() >= self.params().eip658_transition {
+			TransactionOutcome::StatusCode(applied as u8)
+		} else {
+			block.state.commit()?;
+			TransactionOutcome::StateRoot(*block.state.root())
+		};
+		let gas_used = block.receipts.last().map_or_else(U256::zero, |r| r.gas_used);
+		block.system_receipts.push(Receipt::new(outcome, gas_used, substate.logs));
+
+		Ok(output)
+	}
+
 	/// Same as execute_as_system, but execute code directly. If contract address is None, use the null sender
 	/// address. If code is None, then this function has no effect. The call is executed without finalization, and does
 	/// not form a transaction.
@@ -143,6 +184,22 @@ impl Machine {
 		data: Option<Vec<u8>>,
 		action_type: Option<ActionType>,
 	) -> Result<Vec<u8>, Error> {
+		self.execute_system_call(block, contract_address, code, code_hash, value, gas, data, action_type)
+			.map(|(output, _, _)| output)
+	}
+
+	/// Executes a system call, returning its output, its substate and whether its state changes were applied.
+	fn execute_system_call(
+		&self,
+		block: &mut ExecutedBlock,
+		contract_address: Option<Address>,
+		code: Option<Arc<Vec<u8>>>,
+		code_hash: Option<H256>,
+		value: Option<ActionValue>,
+		gas: U256,
+		data: Option<Vec<u8>>,
+		action_type: Option<ActionType>,
+	) -> Result<(Vec<u8>, Substate, bool), Error> {
 		let env_info = {
 			let mut env_info = block.env_info();
 			env_info.gas_limit = env_info.gas_used.saturating_add(gas);
@@ -173,7 +230,7 @@ impl Machine {
 		let res = ex.call(params, &mut substate, &mut NoopTracer, &mut NoopVMTracer).map_err(|e| EngineError::FailedSystemCall(format!("{}", e)))?;
 		let output = res.return_data.to_vec();
 
-		Ok(output)
+		Ok((output, substate, res.apply_state))
 	}
 
 	/// Push last known block hash to the state.
@@ -289,6 +346,19 @@ impl Machine {
 		schedule
 	}
 
+	/// Blocks at which the EVM schedule changes, with the names of the spec parameters activating
+	/// each change, ordered by block.
+	pub fn schedule_transitions(&self) -> Vec<(BlockNumber, &'static str)> {
+		let mut transitions = self.params.schedule_transitions();
+		if let Some(ref ext) = self.ethash_extensions {
+			if ext.homestead_transition != BlockNumber::max_value() {
+				transitions.push((ext.homestead_transition, "homesteadTransition"));
+				transitions.sort();
+			}
+		}
+		transitions
+	}
+
 	/// Builtin-contracts for the chain..
 	pub fn builtins(&self) -> &BTreeMap<Address, Builtin> {
 		&*self.builtins
@@ -409,6 +479,7 @@ mod tests {
 	use common_types::header::Header;
 	use super::*;
 	use spec;
+	use tempdir::TempDir;
 
 	fn get_default_ethash_extensions() -> EthashExtensions {
 		EthashExtensions {
@@ -419,6 +490,37 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn should_select_schedule_by_block() {
+		let tempdir = TempDir::new("").unwrap();
+		let spec = spec::new_foundation(&tempdir.path());
+		let machine = Machine::with_ethash_extensions(
+			spec.params().clone(),
+			Default::default(),
+			get_default_ethash_extensions(),
+		);
+
+		let transitions = machine.schedule_transitions();
+		assert!(transitions.windows(2).all(|pair| pair[0] <= pair[1]));
+		assert!(transitions.contains(&(1_150_000, "homesteadTransition")));
+		assert!(transitions.contains(&(4_370_000, "eip140Transition")));
+		assert!(transitions.contains(&(9_069_000, "eip1344Transition")));
+		assert!(transitions.iter().all(|&(block, _)| block != BlockNumber::max_value()));
+
+		assert_eq!(machine.schedule(1_149_999).tx_create_gas, 21000);
+		assert_eq!(machine.schedule(1_150_000).tx_create_gas, 53000);
+		assert!(!machine.schedule(4_369_999).have_revert);
+		assert!(machine.schedule(4_370_000).have_revert);
+		assert!(!machine.schedule(9_068_999).have_chain_id);
+
+		let istanbul = machine.schedule(9_069_000);
+		let named = Schedule::from_name("istanbul").unwrap();
+		assert!(istanbul.have_chain_id);
+		assert_eq!(istanbul.sload_gas, named.sload_gas);
+		assert_eq!(istanbul.balance_gas, named.balance_gas);
+		assert_eq!(istanbul.tx_data_non_zero_gas, named.tx_data_non_zero_gas);
+	}
+
 	#[test]
 	fn should_disallow_unsigned_transactions() {
 		let rlp = "ea80843b9aca0083015f90948921ebb5f79e9e3920abe571004d0b1d5119c154865af3107a400080038080";
@@ -25,6 +25,7 @@ use parity_util_mem::MallocSizeOfExt;
 use crypto::publickey::Signature;
 use messages::PrivateTransaction;
 use parking_lot::RwLock;
+use rlp::Encodable;
 use types::transaction::{UnverifiedTransaction, SignedTransaction};
 use txpool;
 use txpool::{VerifiedTransaction, Verifier};
@@ -81,6 +82,16 @@ impl pool::ScoredTransaction for VerifiedPrivateTransaction {
 	fn nonce(&self) -> U256 {
 		self.transaction.nonce
 	}
+
+	/// Gets transaction gas limit.
+	fn gas(&self) -> &U256 {
+		&self.transaction.gas
+	}
+
+	/// Gets the size of RLP-encoded transaction in bytes.
+	fn size(&self) -> usize {
+		self.transaction.rlp_bytes().len()
+	}
 }
 
 /// Checks readiness of transactions by looking if the transaction from sender already exists.
@@ -25,7 +25,7 @@ macro_rules! bundle_release_spec {
 				crate::spec::Spec::load(
 					params,
 					include_bytes!(concat!("../../res/", $path, ".json")) as &[u8]
-				).expect(concat!("Chain spec ", $path, " is invalid."))
+				).unwrap_or_else(|e| panic!("Chain spec {} is invalid: {}", $path, e))
 			}
 		)*
 	}
@@ -39,7 +39,7 @@ macro_rules! bundle_test_spec {
 				crate::spec::Spec::load(
 					&::std::env::temp_dir(),
 					include_bytes!(concat!("../../res/", $path, ".json")) as &[u8]
-				).expect(concat!("Chain spec ", $path, " is invalid."))
+				).unwrap_or_else(|e| panic!("Chain spec {} is invalid: {}", $path, e))
 			}
 		)*
 	}
@@ -52,7 +52,7 @@ macro_rules! bundle_test_machine {
 			pub fn $name() -> machine::Machine {
 				crate::spec::Spec::load_machine(
 					include_bytes!(concat!("../../res/", $path, ".json")) as &[u8]
-				).expect(concat!("Chain spec ", $path, " is invalid."))
+				).unwrap_or_else(|e| panic!("Chain spec {} is invalid: {}", $path, e))
 			}
 		)*
 	}
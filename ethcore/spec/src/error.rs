@@ -0,0 +1,80 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Errors raised while loading a chain specification.
+
+use std::{error, fmt};
+
+use common_types::errors::EthcoreError;
+use ethereum_types::{Address, H256};
+
+/// Reasons for rejecting a chain specification.
+#[derive(Debug)]
+pub enum SpecError {
+	/// Not valid JSON, or a field is missing or malformed (e.g. bad hex).
+	Json(serde_json::Error),
+	/// A builtin contract definition is invalid.
+	Builtin(Address, EthcoreError),
+	/// The genesis seal can't be used with the consensus engine.
+	InconsistentSeal {
+		/// Name of the engine.
+		engine: &'static str,
+		/// Name of the seal found in `genesis.seal`.
+		seal: &'static str,
+	},
+	/// The genesis `stateRoot` doesn't match the state described by `accounts`.
+	StateRootMismatch {
+		/// Root given in the spec.
+		expected: H256,
+		/// Root computed from the accounts.
+		found: H256,
+	},
+	/// The consensus engine rejected its parameters.
+	Engine(&'static str, EthcoreError),
+	/// Running the genesis account constructors failed.
+	Constructor(EthcoreError),
+}
+
+impl fmt::Display for SpecError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			SpecError::Json(ref e) => write!(f, "Invalid chain spec: {}", e),
+			SpecError::Builtin(ref address, ref e) =>
+				write!(f, "Invalid builtin at accounts.{:#x}: {}", address, e),
+			SpecError::InconsistentSeal { engine, seal } =>
+				write!(f, "Invalid genesis.seal: a `{}` seal can't be used with the `{}` engine", seal, engine),
+			SpecError::StateRootMismatch { ref expected, ref found } =>
+				write!(f, "Invalid genesis.stateRoot: expected {:#x}, but the accounts produce {:#x}", expected, found),
+			SpecError::Engine(engine, ref e) => write!(f, "Invalid engine.{}.params: {}", engine, e),
+			SpecError::Constructor(ref e) => write!(f, "Failed to run genesis account constructors: {}", e),
+		}
+	}
+}
+
+impl error::Error for SpecError {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match *self {
+			SpecError::Json(ref e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<serde_json::Error> for SpecError {
+	fn from(e: serde_json::Error) -> Self {
+		SpecError::Json(e)
+	}
+}
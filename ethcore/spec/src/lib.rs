@@ -17,10 +17,12 @@
 //! Blockchain params.
 
 mod chain;
+mod error;
 mod genesis;
 mod seal;
 mod spec;
 
 pub use self::chain::*;
+pub use self::error::SpecError;
 pub use self::genesis::Genesis;
-pub use self::spec::{Spec, SpecHardcodedSync, SpecParams};
+pub use self::spec::{Spec, SpecBuilder, SpecEngine, SpecHardcodedSync, SpecParams};
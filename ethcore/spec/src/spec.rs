@@ -34,7 +34,7 @@ use common_types::{
 	transaction::{Action, Transaction},
 };
 use account_state::{Backend, State, backend::Basic as BasicBackend};
-use authority_round::AuthorityRound;
+use authority_round::{AuthorityRound, AuthorityRoundParams};
 use basic_authority::BasicAuthority;
 use bytes::Bytes;
 use builtin::Builtin;
@@ -50,12 +50,14 @@ use machine::{executive::Executive, Machine, substate::Substate};
 use null_engine::NullEngine;
 use pod::PodState;
 use rlp::{Rlp, RlpStream};
+use serde_json::{json, Map, Value};
 use trace::{NoopTracer, NoopVMTracer};
 use trie_vm_factories::Factories;
 use vm::{EnvInfo, ActionType, ActionValue, ActionParams, ParamsType};
 
 use crate::{
 	Genesis,
+	SpecError,
 	seal::Generic as GenericSeal,
 };
 
@@ -226,6 +228,172 @@ pub struct Spec {
 	pub genesis_state: PodState,
 }
 
+/// Consensus engine of a chain assembled with `SpecBuilder`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecEngine {
+	/// `NullEngine`, no sealing and no block rewards.
+	Null,
+	/// `InstantSeal`, seals each block as soon as it is produced.
+	InstantSeal,
+}
+
+/// Programmatic construction of a `Spec`, so tests don't need handcrafted JSON files.
+///
+/// The builder assembles the same JSON a spec file would contain and loads it, so the
+/// result is validated exactly like a bundled chain.
+pub struct SpecBuilder {
+	name: String,
+	engine: SpecEngine,
+	author: Address,
+	difficulty: U256,
+	gas_limit: U256,
+	timestamp: u64,
+	params: Map<String, Value>,
+	accounts: BTreeMap<Address, Map<String, Value>>,
+}
+
+impl SpecBuilder {
+	/// Starts a spec for a `NullEngine` chain with sensible test parameters.
+	pub fn new(name: &str) -> Self {
+		let mut params = Map::new();
+		params.insert("gasLimitBoundDivisor".into(), json!("0x0400"));
+		params.insert("accountStartNonce".into(), json!("0x0"));
+		params.insert("maximumExtraDataSize".into(), json!("0x20"));
+		params.insert("minGasLimit".into(), json!("0x1388"));
+		params.insert("networkID".into(), json!("0x2"));
+
+		SpecBuilder {
+			name: name.into(),
+			engine: SpecEngine::Null,
+			author: Address::zero(),
+			difficulty: 0x20000.into(),
+			gas_limit: 0x2fefd8.into(),
+			timestamp: 0,
+			params,
+			accounts: BTreeMap::new(),
+		}
+	}
+
+	/// Sets the consensus engine.
+	pub fn engine(mut self, engine: SpecEngine) -> Self {
+		self.engine = engine;
+		self
+	}
+
+	/// Sets the genesis author.
+	pub fn author(mut self, author: Address) -> Self {
+		self.author = author;
+		self
+	}
+
+	/// Sets the genesis difficulty.
+	pub fn difficulty(mut self, difficulty: U256) -> Self {
+		self.difficulty = difficulty;
+		self
+	}
+
+	/// Sets the genesis gas limit.
+	pub fn gas_limit(mut self, gas_limit: U256) -> Self {
+		self.gas_limit = gas_limit;
+		self
+	}
+
+	/// Sets the genesis timestamp.
+	pub fn timestamp(mut self, timestamp: u64) -> Self {
+		self.timestamp = timestamp;
+		self
+	}
+
+	/// Sets the network id.
+	pub fn network_id(self, network_id: u64) -> Self {
+		self.param("networkID", json!(format!("{:#x}", network_id)))
+	}
+
+	/// Sets a raw chain parameter using its spec file name, e.g. `("eip155Transition", json!("0x0"))`.
+	pub fn param(mut self, name: &str, value: Value) -> Self {
+		self.params.insert(name.into(), value);
+		self
+	}
+
+	/// Adds a genesis account with the given balance.
+	pub fn account(mut self, address: Address, balance: U256) -> Self {
+		self.account_entry(address).insert("balance".into(), json!(format!("{:#x}", balance)));
+		self
+	}
+
+	/// Adds genesis code and storage to an account.
+	pub fn code(mut self, address: Address, code: Bytes, storage: BTreeMap<H256, H256>) -> Self {
+		let storage: Map<String, Value> = storage.into_iter()
+			.map(|(key, value)| (format!("{:#x}", key), json!(format!("{:#x}", value))))
+			.collect();
+		let account = self.account_entry(address);
+		account.insert("code".into(), json!(to_hex(&code)));
+		account.insert("storage".into(), Value::Object(storage));
+		self
+	}
+
+	/// Adds a builtin contract with linear pricing, active from genesis.
+	pub fn builtin(mut self, address: Address, name: &str, base: u64, word: u64) -> Self {
+		let account = self.account_entry(address);
+		account.entry("balance").or_insert_with(|| json!("0x1"));
+		account.insert("builtin".into(), json!({
+			"name": name,
+			"pricing": { "linear": { "base": base, "word": word } },
+		}));
+		self
+	}
+
+	/// Adds the `ecrecover`, `sha256`, `ripemd160` and `identity` builtins at their usual addresses.
+	pub fn standard_builtins(self) -> Self {
+		self.builtin(Address::from_low_u64_be(1), "ecrecover", 3000, 0)
+			.builtin(Address::from_low_u64_be(2), "sha256", 60, 12)
+			.builtin(Address::from_low_u64_be(3), "ripemd160", 600, 120)
+			.builtin(Address::from_low_u64_be(4), "identity", 15, 3)
+	}
+
+	/// Assembles and loads the spec.
+	pub fn build(self) -> Result<Spec, SpecError> {
+		let engine = match self.engine {
+			SpecEngine::Null => json!({ "null": { "params": {} } }),
+			SpecEngine::InstantSeal => json!({ "instantSeal": { "params": {} } }),
+		};
+		let accounts: Map<String, Value> = self.accounts.into_iter()
+			.map(|(address, account)| (format!("{:#x}", address), Value::Object(account)))
+			.collect();
+		let spec = json!({
+			"name": self.name,
+			"engine": engine,
+			"params": Value::Object(self.params),
+			"genesis": {
+				"seal": {
+					"ethereum": {
+						"nonce": "0x0000000000000000",
+						"mixHash": format!("{:#x}", H256::zero()),
+					}
+				},
+				"difficulty": format!("{:#x}", self.difficulty),
+				"author": format!("{:#x}", self.author),
+				"timestamp": format!("{:#x}", self.timestamp),
+				"parentHash": format!("{:#x}", H256::zero()),
+				"extraData": "0x",
+				"gasLimit": format!("{:#x}", self.gas_limit),
+			},
+			"accounts": Value::Object(accounts),
+		});
+		let json = serde_json::to_vec(&spec).expect("serializing a JSON value never fails; qed");
+		Spec::load(&::std::env::temp_dir(), &json[..])
+	}
+
+	fn account_entry(&mut self, address: Address) -> &mut Map<String, Value> {
+		self.accounts.entry(address).or_insert_with(Map::new)
+	}
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+	let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+	format!("0x{}", hex)
+}
+
 /// Part of `Spec`. Describes the hardcoded synchronization parameters.
 pub struct SpecHardcodedSync {
 	/// Header of the block to jump to for hardcoded sync, and total difficulty.
@@ -260,27 +428,49 @@ impl fmt::Display for SpecHardcodedSync {
 
 fn convert_json_to_spec(
 	(address, builtin): (ethjson::hash::Address, ethjson::spec::builtin::Builtin),
-) -> Result<(Address, Builtin), Error> {
-	let builtin = Builtin::try_from(builtin)?;
-	Ok((address.into(), builtin))
+) -> Result<(Address, Builtin), SpecError> {
+	let address = address.into();
+	let builtin = Builtin::try_from(builtin).map_err(|e| SpecError::Builtin(address, e))?;
+	Ok((address, builtin))
+}
+
+/// Checks that the genesis seal is one the engine is able to verify.
+fn check_seal(engine: &ethjson::spec::Engine, seal: &ethjson::spec::Seal) -> Result<(), SpecError> {
+	use ethjson::spec::{Engine, Seal};
+
+	let engine = match *engine {
+		Engine::Ethash(_) => "Ethash",
+		Engine::AuthorityRound(_) => "authorityRound",
+		_ => return Ok(()),
+	};
+	let seal = match *seal {
+		Seal::Generic(_) => return Ok(()),
+		Seal::Ethereum(_) if engine == "Ethash" => return Ok(()),
+		Seal::AuthorityRound(_) if engine == "authorityRound" => return Ok(()),
+		Seal::Ethereum(_) => "ethereum",
+		Seal::AuthorityRound(_) => "authorityRound",
+		Seal::Tendermint(_) => "tendermint",
+	};
+
+	Err(SpecError::InconsistentSeal { engine, seal })
 }
 
 /// Load from JSON object.
-fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec) -> Result<Spec, Error> {
-	let builtins: Result<BTreeMap<Address, Builtin>, _> = s
+fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec) -> Result<Spec, SpecError> {
+	check_seal(&s.engine, &s.genesis.seal)?;
+	let builtins = s
 		.accounts
 		.builtins()
 		.into_iter()
 		.map(convert_json_to_spec)
-		.collect();
-	let builtins = builtins?;
+		.collect::<Result<BTreeMap<Address, Builtin>, _>>()?;
 	let g = Genesis::from(s.genesis);
 	let GenericSeal(seal_rlp) = g.seal.into();
 	let params = CommonParams::from(s.params);
 
 	let hardcoded_sync = s.hardcoded_sync.map(Into::into);
 
-	let engine = Spec::engine(spec_params, s.engine, params, builtins);
+	let engine = Spec::engine(spec_params, s.engine, params, builtins)?;
 	let author = g.author;
 	let timestamp = g.timestamp;
 	let difficulty = g.difficulty;
@@ -300,7 +490,13 @@ fn load_from(spec_params: SpecParams, s: ethjson::spec::Spec) -> Result<Spec, Er
 		difficulty,
 		&Default::default(),
 		BasicBackend(journaldb::new_memory_db()),
-	)?;
+	).map_err(SpecError::Constructor)?;
+
+	if let Some(expected) = g.state_root {
+		if expected != state_root {
+			return Err(SpecError::StateRootMismatch { expected, found: state_root });
+		}
+	}
 
 	let s = Spec {
 		engine,
@@ -347,20 +543,23 @@ impl Spec {
 		engine_spec: ethjson::spec::Engine,
 		params: CommonParams,
 		builtins: BTreeMap<Address, Builtin>,
-	) -> Arc<dyn Engine> {
+	) -> Result<Arc<dyn Engine>, SpecError> {
 		let machine = Self::machine(&engine_spec, params, builtins);
 
-		match engine_spec {
+		Ok(match engine_spec {
 			ethjson::spec::Engine::Null(null) => Arc::new(NullEngine::new(null.params.into(), machine)),
 			ethjson::spec::Engine::Ethash(ethash) => Arc::new(Ethash::new(spec_params.cache_dir, ethash.params.into(), machine, spec_params.optimization_setting)),
 			ethjson::spec::Engine::InstantSeal(Some(instant_seal)) => Arc::new(InstantSeal::new(instant_seal.params.into(), machine)),
 			ethjson::spec::Engine::InstantSeal(None) => Arc::new(InstantSeal::new(InstantSealParams::default(), machine)),
 			ethjson::spec::Engine::BasicAuthority(basic_authority) => Arc::new(BasicAuthority::new(basic_authority.params.into(), machine)),
 			ethjson::spec::Engine::Clique(clique) => Clique::new(clique.params.into(), machine)
-								.expect("Failed to start Clique consensus engine."),
-			ethjson::spec::Engine::AuthorityRound(authority_round) => AuthorityRound::new(authority_round.params.into(), machine)
-				.expect("Failed to start AuthorityRound consensus engine."),
-		}
+				.map_err(|e| SpecError::Engine("clique", e))?,
+			ethjson::spec::Engine::AuthorityRound(authority_round) => {
+				let params = AuthorityRoundParams::try_from(authority_round.params)
+					.map_err(|e| SpecError::Engine("authorityRound", e))?;
+				AuthorityRound::new(params, machine).map_err(|e| SpecError::Engine("authorityRound", e))?
+			},
+		})
 	}
 
 	/// Get common blockchain parameters.
@@ -423,6 +622,37 @@ impl Spec {
 		ret.out()
 	}
 
+	/// Header of an empty child block of `parent`, filled in the way the engine expects.
+	///
+	/// The state root is copied from `parent`, which is only correct for engines that do not
+	/// pay block rewards, such as those produced by `SpecBuilder`.
+	pub fn child_header(&self, parent: &Header) -> Header {
+		let mut header = Header::default();
+		header.set_parent_hash(parent.hash());
+		header.set_number(parent.number() + 1);
+		header.set_timestamp(parent.timestamp() + 1);
+		header.set_author(parent.author().clone());
+		header.set_transactions_root(KECCAK_NULL_RLP);
+		header.set_uncles_hash(keccak(RlpStream::new_list(0).out()));
+		header.set_receipts_root(KECCAK_NULL_RLP);
+		header.set_state_root(*parent.state_root());
+		header.set_log_bloom(Bloom::default());
+		self.engine.machine().populate_from_parent(&mut header, parent, *parent.gas_limit(), *parent.gas_limit());
+		self.engine.populate_from_parent(&mut header, parent);
+		header
+	}
+
+	/// Compose an empty child block of `parent`, see `child_header`.
+	pub fn child_block(&self, parent: &Header) -> Bytes {
+		let empty_list = RlpStream::new_list(0).out();
+		let header = self.child_header(parent);
+		let mut ret = RlpStream::new_list(3);
+		ret.append(&header);
+		ret.append_raw(&empty_list, 1);
+		ret.append_raw(&empty_list, 1);
+		ret.out()
+	}
+
 	/// Overwrite the genesis components.
 	pub fn overwrite_genesis_params(&mut self, g: Genesis) {
 		let GenericSeal(seal_rlp) = g.seal.into();
@@ -480,28 +710,25 @@ impl Spec {
 	}
 
 	/// Loads just the state machine from a json file.
-	pub fn load_machine<R: Read>(reader: R) -> Result<Machine, Error> {
-		ethjson::spec::Spec::load(reader)
-			.map_err(|e| Error::Msg(e.to_string()))
-			.and_then(|s| {
-				let builtins: Result<BTreeMap<Address, Builtin>, _> = s
-					.accounts
-					.builtins()
-					.into_iter()
-					.map(convert_json_to_spec)
-					.collect();
-				let builtins = builtins?;
-				let params = CommonParams::from(s.params);
-				Ok(Spec::machine(&s.engine, params, builtins))
-			})
+	pub fn load_machine<R: Read>(reader: R) -> Result<Machine, SpecError> {
+		let s = ethjson::spec::Spec::load(reader)?;
+		let builtins = s
+			.accounts
+			.builtins()
+			.into_iter()
+			.map(convert_json_to_spec)
+			.collect::<Result<BTreeMap<Address, Builtin>, _>>()?;
+		let params = CommonParams::from(s.params);
+		Ok(Spec::machine(&s.engine, params, builtins))
 	}
 
 	/// Loads spec from json file. Provide factories for executing contracts and ensuring
 	/// storage goes to the right place.
-	pub fn load<'a, T: Into<SpecParams<'a>>, R: Read>(params: T, reader: R) -> Result<Self, Error> {
-		ethjson::spec::Spec::load(reader)
-			.map_err(|e| Error::Msg(e.to_string()))
-			.and_then(|x| load_from(params.into(), x))
+	///
+	/// Malformed or inconsistent specs are reported as a `SpecError` naming the offending field.
+	pub fn load<'a, T: Into<SpecParams<'a>>, R: Read>(params: T, reader: R) -> Result<Self, SpecError> {
+		let spec = ethjson::spec::Spec::load(reader)?;
+		load_from(params.into(), spec)
 	}
 
 	/// initialize genesis epoch data, using in-memory database for
@@ -565,7 +792,30 @@ mod tests {
 	use ethcore::test_helpers::get_temp_state_db;
 	use tempdir::TempDir;
 
-	use super::Spec;
+	use crate::SpecError;
+	use super::{Spec, SpecBuilder, SpecEngine};
+
+	const ETHEREUM_SEAL: &str = r#"{ "ethereum": { "nonce": "0x0000000000000000", "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000" } }"#;
+	const AURA: &str = r#"{ "authorityRound": { "params": { "stepDuration": 5, "validators": { "list": ["0x0000000000000000000000000000000000000001"] } } } }"#;
+
+	fn load_spec(engine: &str, seal: &str, genesis: &str) -> Result<Spec, SpecError> {
+		let json = format!(r#"{{
+			"name": "Test",
+			"engine": {},
+			"params": {{
+				"gasLimitBoundDivisor": "0x0400",
+				"maximumExtraDataSize": "0x20",
+				"minGasLimit": "0x1388",
+				"networkID": "0x2"
+			}},
+			"genesis": {{
+				"seal": {},
+				"difficulty": "0x20000"{}
+			}},
+			"accounts": {{}}
+		}}"#, engine, seal, genesis);
+		Spec::load(&::std::env::temp_dir(), json.as_bytes())
+	}
 
 	#[test]
 	fn test_load_empty() {
@@ -573,6 +823,49 @@ mod tests {
 		assert!(Spec::load(&tempdir.path(), &[] as &[u8]).is_err());
 	}
 
+	#[test]
+	fn reports_malformed_fields() {
+		let null = r#"{ "null": { "params": {} } }"#;
+		assert!(load_spec(null, ETHEREUM_SEAL, r#", "gasLimit": "0x2fefd8""#).is_ok());
+
+		match load_spec(null, ETHEREUM_SEAL, "") {
+			Err(e @ SpecError::Json(_)) => assert!(e.to_string().contains("missing field `gasLimit`"), "{}", e),
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
+		match load_spec(null, ETHEREUM_SEAL, r#", "gasLimit": "0x2fefzz""#) {
+			Err(e @ SpecError::Json(_)) => assert!(e.to_string().contains("Invalid hex value 0x2fefzz"), "{}", e),
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
+	}
+
+	#[test]
+	fn rejects_inconsistent_genesis() {
+		let gas_limit = r#", "gasLimit": "0x2fefd8""#;
+		match load_spec(AURA, ETHEREUM_SEAL, gas_limit) {
+			Err(SpecError::InconsistentSeal { engine, seal }) => {
+				assert_eq!(engine, "authorityRound");
+				assert_eq!(seal, "ethereum");
+			},
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
+
+		let state_root = r#", "gasLimit": "0x2fefd8", "stateRoot": "0x0000000000000000000000000000000000000000000000000000000000000001""#;
+		match load_spec(r#"{ "null": { "params": {} } }"#, ETHEREUM_SEAL, state_root) {
+			Err(SpecError::StateRootMismatch { expected, .. }) => assert_eq!(expected, H256::from_low_u64_be(1)),
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
+	}
+
+	#[test]
+	fn reports_invalid_engine_params() {
+		let aura = AURA.replace(r#""stepDuration": 5"#, r#""stepDuration": 0"#);
+		match load_spec(&aura, r#"{ "generic": "0xc180" }"#, r#", "gasLimit": "0x2fefd8""#) {
+			Err(e @ SpecError::Engine("authorityRound", _)) =>
+				assert!(e.to_string().contains("stepDuration cannot be 0"), "{}", e),
+			other => panic!("unexpected result: {:?}", other.map(|_| ())),
+		}
+	}
+
 	#[test]
 	fn test_chain() {
 		let test_spec = crate::new_test();
@@ -588,6 +881,40 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn spec_builder() {
+		let rich = Address::from_low_u64_be(0x1337);
+		let spec = SpecBuilder::new("Builder")
+			.engine(SpecEngine::InstantSeal)
+			.gas_limit(0x1000000.into())
+			.standard_builtins()
+			.account(rich, 1_000_000.into())
+			.build()
+			.unwrap();
+
+		assert_eq!(spec.name, "Builder");
+		assert_eq!(spec.engine.name(), "InstantSeal");
+		assert_eq!(spec.gas_limit, 0x1000000.into());
+		assert!(spec.engine.builtin(&Address::from_low_u64_be(1), 0).is_some());
+
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let state = State::from_existing(
+			db.boxed_clone(),
+			spec.state_root,
+			spec.engine.account_start_nonce(0),
+			Default::default(),
+		).unwrap();
+		assert_eq!(state.balance(&rich).unwrap(), 1_000_000.into());
+
+		let genesis = spec.genesis_header();
+		let child = spec.child_header(&genesis);
+		assert_eq!(child.number(), 1);
+		assert_eq!(child.parent_hash(), &genesis.hash());
+		assert_eq!(child.gas_limit(), genesis.gas_limit());
+		assert!(spec.engine.verify_block_basic(&child).is_ok());
+		assert!(spec.engine.verify_block_family(&child, &genesis).is_ok());
+	}
+
 	#[test]
 	fn genesis_constructor() {
 		let _ = ::env_logger::try_init();
@@ -50,13 +50,18 @@ use hash::keccak;
 use rlp::{RlpStream, Encodable, encode_list};
 use types::{
 	block::PreverifiedBlock,
+	call_analytics::CallAnalytics,
 	errors::{EthcoreError as Error, BlockError},
 	transaction::{SignedTransaction, Error as TransactionError},
 	header::Header,
 	receipt::{Receipt, TransactionOutcome},
 };
 use executive_state::ExecutiveState;
-use machine::ExecutedBlock;
+use machine::{
+	ExecutedBlock,
+	executed::Executed,
+	executive::TransactOptions,
+};
 
 /// Block that is ready for transactions to be added.
 ///
@@ -141,6 +146,11 @@ impl<'x> OpenBlock<'x> {
 		self.block.header.set_timestamp(timestamp);
 	}
 
+	/// Record the return data of the transactions pushed from now on.
+	pub fn record_outputs(&mut self) {
+		self.block.outputs.get_or_insert_with(Vec::new);
+	}
+
 	/// Removes block gas limit.
 	pub fn remove_gas_limit(&mut self) {
 		self.block.header.set_gas_limit(U256::max_value());
@@ -182,9 +192,42 @@ impl<'x> OpenBlock<'x> {
 			traces.push(outcome.trace.into());
 		}
 		self.block.receipts.push(outcome.receipt);
+		if let Some(ref mut outputs) = self.block.outputs {
+			outputs.push(outcome.output);
+		}
 		Ok(self.block.receipts.last().expect("receipt just pushed; qed"))
 	}
 
+	/// Push a transaction into the block, executing it with the given analytics.
+	///
+	/// Like `push_transaction`, but returns the full execution result instead of the receipt.
+	/// Traces collected this way are not kept in the block, which must be opened without tracing.
+	pub fn push_transaction_with_analytics(&mut self, t: SignedTransaction, analytics: CallAnalytics) -> Result<Executed, Error> {
+		if self.block.transactions_set.contains(&t.hash()) {
+			return Err(TransactionError::AlreadyImported.into());
+		}
+		debug_assert!(!self.block.traces.is_enabled(), "block traces are not collected with analytics; qed");
+
+		let env_info = self.block.env_info();
+		let machine = self.engine.machine();
+		let state = &mut self.block.state;
+		let state_diff = analytics.state_diffing;
+		let (receipt, executed) = match (analytics.transaction_tracing, analytics.vm_tracing) {
+			(true, true) => state.apply_with_analytics(&env_info, machine, &t, TransactOptions::with_tracing_and_vm_tracing(), state_diff),
+			(true, false) => state.apply_with_analytics(&env_info, machine, &t, TransactOptions::with_tracing(), state_diff),
+			(false, true) => state.apply_with_analytics(&env_info, machine, &t, TransactOptions::with_vm_tracing(), state_diff),
+			(false, false) => state.apply_with_analytics(&env_info, machine, &t, TransactOptions::with_no_tracing(), state_diff),
+		}?;
+
+		self.block.transactions_set.insert(t.hash());
+		self.block.transactions.push(t.into());
+		self.block.receipts.push(receipt);
+		if let Some(ref mut outputs) = self.block.outputs {
+			outputs.push(executed.output.clone());
+		}
+		Ok(executed)
+	}
+
 	/// Push transactions onto the block.
 	#[cfg(not(feature = "slow-blocks"))]
 	fn push_transactions(&mut self, transactions: Vec<SignedTransaction>) -> Result<(), Error> {
@@ -247,14 +290,18 @@ impl<'x> OpenBlock<'x> {
 	pub fn close_and_lock(self) -> Result<LockedBlock, Error> {
 		let mut s = self;
 		s.engine.on_close_block(&mut s.block, &s.parent)?;
+		for (contract, data) in s.engine.system_calls_on_close_block(&s.block, &s.parent)? {
+			s.engine.machine().execute_as_system_with_receipt(&mut s.block, contract, U256::max_value(), Some(data))?;
+		}
 		s.block.state.commit()?;
 
 		s.block.header.set_transactions_root(ordered_trie_root(s.block.transactions.iter().map(|e| e.rlp_bytes())));
 		let uncle_bytes = encode_list(&s.block.uncles);
 		s.block.header.set_uncles_hash(keccak(&uncle_bytes));
 		s.block.header.set_state_root(s.block.state.root().clone());
-		s.block.header.set_receipts_root(ordered_trie_root(s.block.receipts.iter().map(|r| r.rlp_bytes())));
-		s.block.header.set_log_bloom(s.block.receipts.iter().fold(Bloom::zero(), |mut b, r| {
+		let receipts = s.block.receipts.iter().chain(&s.block.system_receipts);
+		s.block.header.set_receipts_root(ordered_trie_root(receipts.clone().map(|r| r.rlp_bytes())));
+		s.block.header.set_log_bloom(receipts.fold(Bloom::zero(), |mut b, r| {
 			b.accrue_bloom(&r.log_bloom);
 			b
 		}));
@@ -312,9 +359,10 @@ impl ClosedBlock {
 
 	/// Given an engine reference, reopen the `ClosedBlock` into an `OpenBlock`.
 	pub fn reopen(self, engine: &dyn Engine) -> OpenBlock {
-		// revert rewards (i.e. set state back at last transaction's state).
+		// revert rewards and system calls (i.e. set state back at last transaction's state).
 		let mut block = self.block;
 		block.state = self.unclosed_state;
+		block.system_receipts.clear();
 		let parent = self.parent;
 		OpenBlock { block, engine, parent }
 	}
@@ -328,11 +376,11 @@ impl LockedBlock {
 	/// is set to non-zero value, so the check only happens if we detect
 	/// unmatching root first and then fall back to striped receipts.
 	pub fn strip_receipts_outcomes(&mut self) {
-		for receipt in &mut self.block.receipts {
+		for receipt in self.block.receipts.iter_mut().chain(&mut self.block.system_receipts) {
 			receipt.outcome = TransactionOutcome::Unknown;
 		}
 		self.block.header.set_receipts_root(
-			ordered_trie_root(self.block.receipts.iter().map(|r| r.rlp_bytes()))
+			ordered_trie_root(self.block.block_receipts().iter().map(|r| r.rlp_bytes()))
 		);
 	}
 
@@ -402,19 +450,17 @@ impl Drain for SealedBlock {
 	}
 }
 
-/// Enact the block given by block header, transactions and uncles
-pub(crate) fn enact(
-	header: Header,
-	transactions: Vec<SignedTransaction>,
-	uncles: Vec<Header>,
-	engine: &dyn Engine,
+// Open a block to enact the block with given header in.
+fn open_for_enact<'x>(
+	header: &Header,
+	engine: &'x dyn Engine,
 	tracing: bool,
 	db: StateDB,
 	parent: &Header,
 	last_hashes: Arc<LastHashes>,
 	factories: Factories,
 	is_epoch_begin: bool,
-) -> Result<LockedBlock, Error> {
+) -> Result<OpenBlock<'x>, Error> {
 	// For trace log
 	let trace_state = if log_enabled!(target: "enact", ::log::Level::Trace) {
 		Some(State::from_existing(db.boxed_clone(), parent.state_root().clone(), engine.account_start_nonce(parent.number() + 1), factories.clone())?)
@@ -431,7 +477,7 @@ pub(crate) fn enact(
 		last_hashes,
 		// Engine such as Clique will calculate author from extra_data.
 		// this is only important for executing contracts as the 'executive_author'.
-		engine.executive_author(&header)?,
+		engine.executive_author(header)?,
 		(3141562.into(), 31415620.into()),
 		vec![],
 		is_epoch_begin,
@@ -445,7 +491,28 @@ pub(crate) fn enact(
 				b.block.header.number(), root, env.author, author_balance);
 	}
 
-	b.populate_from(&header);
+	b.populate_from(header);
+	Ok(b)
+}
+
+/// Enact the block given by block header, transactions and uncles
+pub(crate) fn enact(
+	header: Header,
+	transactions: Vec<SignedTransaction>,
+	uncles: Vec<Header>,
+	engine: &dyn Engine,
+	tracing: bool,
+	store_outputs: bool,
+	db: StateDB,
+	parent: &Header,
+	last_hashes: Arc<LastHashes>,
+	factories: Factories,
+	is_epoch_begin: bool,
+) -> Result<LockedBlock, Error> {
+	let mut b = open_for_enact(&header, engine, tracing, db, parent, last_hashes, factories, is_epoch_begin)?;
+	if store_outputs {
+		b.record_outputs();
+	}
 	b.push_transactions(transactions)?;
 
 	for u in uncles {
@@ -460,6 +527,7 @@ pub fn enact_verified(
 	block: PreverifiedBlock,
 	engine: &dyn Engine,
 	tracing: bool,
+	store_outputs: bool,
 	db: StateDB,
 	parent: &Header,
 	last_hashes: Arc<LastHashes>,
@@ -473,6 +541,7 @@ pub fn enact_verified(
 		block.uncles,
 		engine,
 		tracing,
+		store_outputs,
 		db,
 		parent,
 		last_hashes,
@@ -481,11 +550,37 @@ pub fn enact_verified(
 	)
 }
 
+/// Enact the verified block like `enact_verified`, executing its transactions with the given
+/// analytics. Returns the execution result of each transaction along with the locked block.
+pub fn enact_verified_with_analytics(
+	block: PreverifiedBlock,
+	engine: &dyn Engine,
+	db: StateDB,
+	parent: &Header,
+	last_hashes: Arc<LastHashes>,
+	factories: Factories,
+	is_epoch_begin: bool,
+	analytics: CallAnalytics,
+) -> Result<(LockedBlock, Vec<Executed>), Error> {
+	let mut b = open_for_enact(&block.header, engine, false, db, parent, last_hashes, factories, is_epoch_begin)?;
+	let executed = block.transactions.into_iter()
+		.map(|t| b.push_transaction_with_analytics(t, analytics))
+		.collect::<Result<Vec<_>, _>>()?;
+
+	for u in block.uncles {
+		b.push_uncle(u)?;
+	}
+
+	Ok((b.close_and_lock()?, executed))
+}
+
 #[cfg(test)]
 mod tests {
 	use test_helpers::get_temp_state_db;
 	use super::*;
 	use engine::Engine;
+	use machine::Machine;
+	use types::engines::params::CommonParams;
 	use vm::LastHashes;
 	use trie_vm_factories::Factories;
 	use state_db::StateDB;
@@ -604,6 +699,46 @@ mod tests {
 			!= db.journal_db().get(k.0, EMPTY_PREFIX)).next() == None);
 	}
 
+	struct SystemCallingEngine {
+		inner: Arc<dyn Engine>,
+		contract: Address,
+	}
+
+	impl Engine for SystemCallingEngine {
+		fn name(&self) -> &str { "SystemCallingEngine" }
+		fn machine(&self) -> &Machine { self.inner.machine() }
+		fn verify_local_seal(&self, header: &Header) -> Result<(), Error> { self.inner.verify_local_seal(header) }
+		fn params(&self) -> &CommonParams { self.inner.params() }
+
+		fn system_calls_on_close_block(&self, _block: &ExecutedBlock, _parent: &Header) -> Result<Vec<(Address, Vec<u8>)>, Error> {
+			Ok(vec![(self.contract, vec![])])
+		}
+	}
+
+	#[test]
+	fn records_receipts_of_system_calls() {
+		let spec = spec::new_test();
+		let engine = SystemCallingEngine { inner: spec.engine.clone(), contract: Address::from_low_u64_be(0x1000) };
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(&engine, Default::default(), false, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false).unwrap();
+		// PUSH1 0 PUSH1 0 LOG0 STOP
+		b.block_mut().state_mut().init_code(&engine.contract, vec![0x60, 0x00, 0x60, 0x00, 0xa0, 0x00]).unwrap();
+
+		let b = b.close_and_lock().unwrap();
+		assert!(b.transactions.is_empty());
+		assert!(b.receipts.is_empty());
+		assert_eq!(b.system_receipts.len(), 1);
+		assert_eq!(b.system_receipts[0].logs.len(), 1);
+		assert_eq!(b.system_receipts[0].logs[0].address, engine.contract);
+		assert_eq!(b.system_receipts[0].gas_used, U256::zero());
+		assert_eq!(*b.header.gas_used(), U256::zero());
+		assert_eq!(*b.header.receipts_root(), ordered_trie_root(b.block_receipts().iter().map(|r| r.rlp_bytes())));
+		assert!(b.header.log_bloom().contains_bloom(&b.system_receipts[0].log_bloom));
+		assert!(!b.header.log_bloom().is_zero());
+	}
+
 	#[test]
 	fn enact_block_with_uncle() {
 		let spec = spec::new_test();
@@ -66,6 +66,11 @@ impl BadBlocks {
 		}
 	}
 
+	/// Forgets all recently detected bad blocks.
+	pub fn clear(&self) {
+		self.last_blocks.write().clear();
+	}
+
 	/// Returns a list of recently detected bad blocks with error descriptions.
 	pub fn bad_blocks(&self) -> Vec<(Unverified, String)> {
 		self.last_blocks.read()
@@ -20,7 +20,7 @@ use std::convert::TryFrom;
 use std::io::{BufRead, BufReader};
 use std::str::from_utf8;
 use std::sync::{Arc, Weak};
-use std::sync::atomic::{AtomicBool, AtomicI64, Ordering as AtomicOrdering, Ordering, AtomicU64};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering as AtomicOrdering};
 use std::time::{Duration, Instant};
 
 use ansi_term::Colour;
@@ -39,11 +39,12 @@ use trie::{Trie, TrieFactory, TrieSpec};
 
 use account_state::State;
 use account_state::state::StateInfo;
-use block::{ClosedBlock, Drain, enact_verified, LockedBlock, OpenBlock, SealedBlock};
+use block::{ClosedBlock, Drain, enact_verified, enact_verified_with_analytics, LockedBlock, OpenBlock, SealedBlock};
 use blockchain::{
 	BlockChain,
 	BlockChainDB,
 	BlockNumberKey,
+	BlockOutputs,
 	BlockProvider,
 	BlockReceipts,
 	CacheSize as BlockChainCacheSize,
@@ -58,6 +59,7 @@ use client::{
 	ReopenBlock, SealedBlockImporter,
 };
 use client::ancient_import::AncientVerifier;
+use client::notify_batch::NotifyBatcher;
 use client_traits::{
 	AccountData,
 	BadBlocks,
@@ -74,6 +76,7 @@ use client_traits::{
 	IoClient,
 	Nonce,
 	ProvingBlockChainClient,
+	ReplayedBlock,
 	ScheduleInfo,
 	StateClient,
 	StateOrBlock,
@@ -87,6 +90,7 @@ use engine::Engine;
 use ethcore_miner::pool::VerifiedTransaction;
 use ethtrie::Layout;
 use evm::Schedule;
+use evm::interpreter::InstructionObserver;
 use executive_state;
 use io::IoChannel;
 use journaldb;
@@ -104,6 +108,7 @@ use trace::{self, Database as TraceDatabase, ImportRequest as TraceImportRequest
 use trie_vm_factories::{Factories, VmFactory};
 use types::{
 	ancestry_action::AncestryAction,
+	basic_account::BasicAccount,
 	block::PreverifiedBlock,
 	block_status::BlockStatus,
 	blockchain_info::BlockChainInfo,
@@ -111,6 +116,7 @@ use types::{
 	call_analytics::CallAnalytics,
 	chain_notify::{ChainMessageType, ChainRoute, NewBlocks},
 	client_types::{ClientReport, Mode, StateResult},
+	disk_usage::DiskUsage,
 	encoded,
 	engines::{
 		epoch::{PendingTransition, Transition as EpochTransition},
@@ -128,7 +134,10 @@ use types::{
 	log_entry::LocalizedLogEntry,
 	pruning_info::PruningInfo,
 	receipt::{LocalizedReceipt, Receipt},
+	reorg::Reorg,
 	snapshot::{Progress, Snapshotting},
+	state_diff::StateDiff,
+	state_override::StateOverride,
 	trace_filter::Filter as TraceFilter,
 	transaction::{self, Action, CallError, LocalizedTransaction, SignedTransaction, UnverifiedTransaction},
 	verification::{Unverified, VerificationQueueInfo as BlockQueueInfo},
@@ -143,6 +152,10 @@ const MAX_ANCIENT_BLOCKS_QUEUE_SIZE: usize = 4096;
 const MAX_ANCIENT_BLOCKS_TO_IMPORT: usize = 4;
 const MAX_QUEUE_SIZE_TO_SLEEP_ON: usize = 2;
 const MIN_HISTORY_SIZE: u64 = 8;
+// Number of blocks the network has to be ahead by to keep a client in passive mode awake.
+const MIN_PEER_BLOCKS_AHEAD_TO_STAY_AWAKE: u64 = 4;
+// Interval between two measurements of the size on disk of the databases.
+const DISK_USAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(10 * 60);
 
 struct SleepState {
 	last_activity: Option<Instant>,
@@ -202,9 +215,6 @@ pub struct Client {
 	/// Database pruning strategy to use for StateDB
 	pruning: journaldb::Algorithm,
 
-	/// Don't prune the state we're currently snapshotting
-	snapshotting_at: AtomicU64,
-
 	/// Client uses this to store blocks, traces, etc.
 	db: RwLock<Arc<dyn BlockChainDB>>,
 
@@ -222,6 +232,9 @@ pub struct Client {
 	/// List of actors to be notified on certain chain events
 	notify: RwLock<Vec<Weak<dyn ChainNotify>>>,
 
+	/// Coalesces `new_blocks` notifications during bulk import.
+	notify_batcher: NotifyBatcher,
+
 	/// Queued transactions from IO
 	queue_transactions: IoChannelQueue,
 	/// Ancient blocks import queue
@@ -235,7 +248,13 @@ pub struct Client {
 	/// Consensus messages import queue
 	queue_consensus_message: IoChannelQueue,
 
-	last_hashes: RwLock<VecDeque<H256>>,
+	/// Hashes of the 256 most recent blocks up to and including the one at the front,
+	/// following the best chain. Shared with every `EnvInfo` built on top of it.
+	last_hashes: RwLock<Option<Arc<LastHashes>>>,
+	/// Gas price corpus computed for the given best block hash and sample size.
+	gas_price_corpus: RwLock<Option<(H256, usize, stats::Corpus<U256>)>>,
+	/// Time the size on disk of the databases was last measured.
+	disk_usage_refreshed: Mutex<Instant>,
 	factories: Factories,
 
 	/// Number of eras kept in a journal before they are pruned
@@ -287,6 +306,7 @@ impl Importer {
 		let (imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, duration, has_more_blocks_to_import) = {
 			let mut imported_blocks = Vec::with_capacity(max_blocks_to_import);
 			let mut invalid_blocks = HashSet::new();
+			let mut failed_blocks = HashSet::new();
 			let proposed_blocks = Vec::with_capacity(max_blocks_to_import);
 			let mut import_results = Vec::with_capacity(max_blocks_to_import);
 
@@ -298,6 +318,12 @@ impl Importer {
 			trace_time!("import_verified_blocks");
 			let start = Instant::now();
 
+			// write the blooms of a run of blocks at once rather than block by block
+			let bulk_insert = blocks.len() > 1;
+			if bulk_insert {
+				client.chain.read().begin_bulk_insert();
+			}
+
 			for block in blocks {
 				let header = block.header.clone();
 				let bytes = block.bytes.clone();
@@ -309,6 +335,11 @@ impl Importer {
 					continue;
 				}
 
+				if failed_blocks.contains(header.parent_hash()) {
+					failed_blocks.insert(hash);
+					continue;
+				}
+
 				match self.check_and_lock_block(&bytes, block, client) {
 					Ok((closed_block, pending)) => {
 						imported_blocks.push(hash);
@@ -317,6 +348,10 @@ impl Importer {
 						import_results.push(route);
 						client.report.write().accrue_block(&header, transactions_len);
 					},
+					Err(ref err) if is_local_failure(err) => {
+						warn!(target: "client", "Block import of #{} ({}) failed locally: {}", header.number(), hash, err);
+						failed_blocks.insert(hash);
+					},
 					Err(err) => {
 						self.bad_blocks.report(bytes, format!("{:?}", err));
 						invalid_blocks.insert(hash);
@@ -324,12 +359,19 @@ impl Importer {
 				}
 			}
 
+			if bulk_insert {
+				client.chain.read().end_bulk_insert();
+			}
+
 			let imported = imported_blocks.len();
 			let invalid_blocks = invalid_blocks.into_iter().collect::<Vec<H256>>();
 
 			if !invalid_blocks.is_empty() {
 				self.block_queue.mark_as_bad(&invalid_blocks);
 			}
+			if !failed_blocks.is_empty() {
+				self.block_queue.mark_as_failed(&failed_blocks.into_iter().collect::<Vec<H256>>());
+			}
 			let has_more_blocks_to_import = !self.block_queue.mark_as_good(&imported_blocks);
 			(imported_blocks, import_results, invalid_blocks, imported, proposed_blocks, start.elapsed(), has_more_blocks_to_import)
 		};
@@ -340,21 +382,26 @@ impl Importer {
 
 				if !has_more_blocks_to_import {
 					self.miner.chain_new_blocks(client, &imported_blocks, &invalid_blocks, route.enacted(), route.retracted(), false);
+					if !route.enacted().is_empty() {
+						client.refresh_gas_price_corpus();
+					}
 				}
 
-				client.notify(|notify| {
-					notify.new_blocks(
-						NewBlocks::new(
-							imported_blocks.clone(),
-							invalid_blocks.clone(),
-							route.clone(),
-							Vec::new(),
-							proposed_blocks.clone(),
-							duration,
-							has_more_blocks_to_import,
-						)
-					);
-				});
+				let new_blocks = NewBlocks::new(
+					imported_blocks,
+					invalid_blocks,
+					route,
+					Vec::new(),
+					proposed_blocks,
+					duration,
+					has_more_blocks_to_import,
+				);
+				if let Some(new_blocks) = client.notify_batcher.push(new_blocks, Instant::now()) {
+					client.notify(|notify| notify.new_blocks(new_blocks.clone()));
+				}
+			} else if !has_more_blocks_to_import {
+				// the rest of the queue failed, deliver what's been held back
+				client.flush_new_blocks(true);
 			}
 		}
 
@@ -417,6 +464,7 @@ impl Importer {
 			block,
 			engine,
 			client.tracedb.read().tracing_enabled(),
+			client.config.store_outputs,
 			db,
 			&parent,
 			last_hashes,
@@ -450,7 +498,7 @@ impl Importer {
 		let pending = self.check_epoch_end_signal(
 			&header,
 			bytes,
-			&locked_block.receipts,
+			&locked_block.block_receipts(),
 			locked_block.state.db(),
 			client
 		)?;
@@ -512,7 +560,10 @@ impl Importer {
 
 		let ancestry_actions = self.engine.ancestry_actions(&header, &mut chain.ancestry_with_metadata_iter(*parent));
 
-		let receipts = block.receipts;
+		// stored along with the system receipts, so that peers can check them against the header
+		let mut receipts = block.receipts;
+		receipts.extend(block.system_receipts);
+		let outputs = block.outputs;
 		let traces = block.traces.drain();
 		let best_hash = chain.best_block_hash();
 
@@ -549,6 +600,10 @@ impl Importer {
 
 		state.journal_under(&mut batch, number, hash).expect("DB commit failed");
 
+		if let Some(outputs) = outputs {
+			chain.insert_block_outputs(&mut batch, *hash, BlockOutputs::new(outputs));
+		}
+
 		let finalized: Vec<_> = ancestry_actions.into_iter().map(|ancestry_action| {
 			let AncestryAction::MarkFinalized(a) = ancestry_action;
 
@@ -583,7 +638,7 @@ impl Importer {
 
 		self.check_epoch_end(&header, &finalized, &chain, client);
 
-		client.update_last_hashes(&parent, hash);
+		client.update_last_hashes(&chain, &route, is_canon);
 
 		if let Err(e) = client.prune_ancient(state, &chain) {
 			warn!("Failed to prune ancient state data: {}", e);
@@ -778,18 +833,21 @@ impl Client {
 			tracedb,
 			engine,
 			pruning: config.pruning,
-			snapshotting_at: AtomicU64::new(0),
 			db: RwLock::new(db.clone()),
 			state_db: RwLock::new(state_db),
 			report: RwLock::new(Default::default()),
 			io_channel: RwLock::new(message_channel),
 			notify: RwLock::new(Vec::new()),
+			notify_batcher: NotifyBatcher::new(config.new_blocks_notify_interval),
 			queue_transactions: IoChannelQueue::new(config.transaction_verification_queue_size),
 			queue_ancient_blocks: IoChannelQueue::new(MAX_ANCIENT_BLOCKS_QUEUE_SIZE),
 			queued_ancient_blocks: Default::default(),
 			ancient_blocks_import_lock: Default::default(),
 			queue_consensus_message: IoChannelQueue::new(usize::max_value()),
-			last_hashes: RwLock::new(VecDeque::new()),
+			last_hashes: RwLock::new(None),
+			gas_price_corpus: RwLock::new(None),
+			// the databases are measured when they are opened
+			disk_usage_refreshed: Mutex::new(Instant::now()),
 			factories,
 			history,
 			on_user_defaults_change: Mutex::new(None),
@@ -909,12 +967,9 @@ impl Client {
 	}
 
 	fn build_last_hashes(&self, parent_hash: H256) -> Arc<LastHashes> {
-		{
-			let hashes = self.last_hashes.read();
-			if hashes.front().map_or(false, |h| h == &parent_hash) {
-				let mut res = Vec::from(hashes.clone());
-				res.resize(256, H256::zero());
-				return Arc::new(res);
+		if let Some(ref hashes) = *self.last_hashes.read() {
+			if hashes.first() == Some(&parent_hash) {
+				return hashes.clone();
 			}
 		}
 		let mut last_hashes = LastHashes::new();
@@ -929,9 +984,12 @@ impl Client {
 				None => break,
 			}
 		}
-		let mut cached_hashes = self.last_hashes.write();
-		*cached_hashes = VecDeque::from(last_hashes.clone());
-		Arc::new(last_hashes)
+		let last_hashes = Arc::new(last_hashes);
+		// only the best block is worth caching, calls against older state shouldn't evict it
+		if parent_hash == chain.best_block_hash() {
+			*self.last_hashes.write() = Some(last_hashes.clone());
+		}
+		last_hashes
 	}
 
 	// use a state-proving closure for the given block.
@@ -974,14 +1032,16 @@ impl Client {
 
 			match state_db.journal_db().earliest_era() {
 				Some(earliest_era) if earliest_era + self.history <= latest_era => {
-					let freeze_at = self.snapshotting_at.load(Ordering::SeqCst);
-					if freeze_at > 0 && freeze_at == earliest_era {
-						// Note: journal_db().mem_used() can be used for a more accurate memory
-						// consumption measurement but it can be expensive so sticking with the
-						// faster `journal_size()` instead.
-						trace!(target: "pruning", "Pruning is paused at era {} (snapshot under way); earliest era={}, latest era={}, journal_size={} – Not pruning.",
-						       freeze_at, earliest_era, latest_era, state_db.journal_db().journal_size());
-						break;
+					match state_db.journal_db().earliest_pinned_era() {
+						Some(pinned) if pinned <= earliest_era => {
+							// Note: journal_db().mem_used() can be used for a more accurate memory
+							// consumption measurement but it can be expensive so sticking with the
+							// faster `journal_size()` instead.
+							trace!(target: "pruning", "Pruning is paused at era {} (pinned, e.g. snapshot under way); earliest era={}, latest era={}, journal_size={} – Not pruning.",
+							       pinned, earliest_era, latest_era, state_db.journal_db().journal_size());
+							break;
+						}
+						_ => {}
 					}
 					trace!(target: "pruning", "Pruning state for ancient era #{}; latest era={}, journal_size={}",
 					       earliest_era, latest_era, state_db.journal_db().journal_size());
@@ -989,6 +1049,9 @@ impl Client {
 						Some(ancient_hash) => {
 							let mut batch = DBTransaction::new();
 							state_db.mark_canonical(&mut batch, earliest_era, &ancient_hash)?;
+							if self.config.store_outputs {
+								chain.remove_block_outputs(&mut batch, &ancient_hash);
+							}
 							self.db.read().key_value().write_buffered(batch);
 							state_db.journal_db().flush();
 						}
@@ -1003,14 +1066,53 @@ impl Client {
 		Ok(())
 	}
 
-	fn update_last_hashes(&self, parent: &H256, hash: &H256) {
-		let mut hashes = self.last_hashes.write();
-		if hashes.front().map_or(false, |h| h == parent) {
-			if hashes.len() > 255 {
-				hashes.pop_back();
+	/// Moves the cached last hashes along the best chain after a block import.
+	///
+	/// Retracted blocks are dropped from the front and enacted ones pushed, so a reorg
+	/// only touches the blocks it replaced. If the cache doesn't follow the previous best
+	/// block it is dropped and rebuilt lazily by `build_last_hashes`.
+	fn update_last_hashes(&self, chain: &BlockChain, route: &ImportRoute, is_canon: bool) {
+		if !is_canon {
+			return;
+		}
+
+		let mut cached = self.last_hashes.write();
+		let mut hashes = match cached.take() {
+			Some(hashes) => hashes,
+			None => return,
+		};
+
+		{
+			let hashes = Arc::make_mut(&mut hashes);
+			for retracted in &route.retracted {
+				if hashes.first() != Some(retracted) {
+					return;
+				}
+				hashes.remove(0);
+			}
+
+			let ancestor = route.enacted.first()
+				.and_then(|hash| chain.block_details(hash))
+				.map(|details| details.parent);
+			if ancestor.is_none() || hashes.first() != ancestor.as_ref() {
+				return;
+			}
+
+			for enacted in &route.enacted {
+				hashes.insert(0, *enacted);
+			}
+			hashes.truncate(256);
+
+			// a reorg onto a shorter branch leaves a gap at the back
+			while hashes.len() < 256 {
+				let next = hashes.last()
+					.and_then(|hash| chain.block_details(hash))
+					.map_or_else(H256::zero, |details| details.parent);
+				hashes.push(next);
 			}
-			hashes.push_front(hash.clone());
 		}
+
+		*cached = Some(hashes);
 	}
 
 	/// Get shared miner reference.
@@ -1091,6 +1193,55 @@ impl Client {
 		}
 	}
 
+	/// Re-execute the given block on top of its parent's state and return the aggregate
+	/// account and storage changes it made, including block and uncle rewards.
+	///
+	/// Fails for the genesis block and for blocks whose parent state was pruned.
+	pub fn block_state_diff(&self, id: BlockId) -> Result<StateDiff, CallError> {
+		let (parent, locked, _) = self.reenact_block(id, CallAnalytics::default())?;
+		let original = self.state_at(BlockId::Hash(parent.hash())).ok_or(CallError::StatePruned)?;
+
+		locked.drain().state.diff_from(original).map_err(|_| CallError::StateCorrupt)
+	}
+
+	/// Re-execute the given block, rewards included, on top of its parent's state.
+	/// Returns the parent header along with the enacted block and the execution result
+	/// of each of its transactions, analysed as requested.
+	fn reenact_block(&self, id: BlockId, analytics: CallAnalytics) -> Result<(Header, LockedBlock, Vec<Executed>), CallError> {
+		let block = self.block(id).ok_or(CallError::StatePruned)?;
+		let header = block.decode_header();
+		let parent = self.block_header_decoded(BlockId::Hash(*header.parent_hash())).ok_or(CallError::StatePruned)?;
+
+		let transactions = block.transactions()
+			.into_iter()
+			.map(SignedTransaction::new)
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|_| CallError::StateCorrupt)?;
+		let preverified = PreverifiedBlock {
+			header,
+			transactions,
+			uncles: block.uncles(),
+			bytes: block.into_inner(),
+		};
+
+		let is_epoch_begin = self.chain.read().epoch_transition(parent.number(), parent.hash()).is_some();
+		let (locked, executed) = enact_verified_with_analytics(
+			preverified,
+			&*self.engine,
+			self.state_db.read().boxed_clone_canon(&parent.hash()),
+			&parent,
+			self.build_last_hashes(parent.hash()),
+			self.factories.clone(),
+			is_epoch_begin,
+			analytics,
+		).map_err(|e| {
+			warn!(target: "client", "Failed to re-execute block {:?}: {}", id, e);
+			CallError::StateCorrupt
+		})?;
+
+		Ok((parent, locked, executed))
+	}
+
 	/// Get a copy of the best block's state.
 	pub fn state(&self) -> impl StateInfo {
 		let (state, _) = self.latest_state_and_header();
@@ -1109,10 +1260,37 @@ impl Client {
 		report
 	}
 
+	/// Recompute the cached gas price corpus for the new best block, so `eth_gasPrice` doesn't
+	/// walk the recent blocks itself. Nothing is computed until the corpus has been asked for.
+	fn refresh_gas_price_corpus(&self) {
+		let sample_size = match *self.gas_price_corpus.read() {
+			Some((_, sample_size, _)) => sample_size,
+			None => return,
+		};
+		self.gas_price_corpus(sample_size);
+	}
+
+	/// Deliver any `new_blocks` notification held back by the batcher once it's due,
+	/// or straight away if the block queue has been drained.
+	fn flush_new_blocks(&self, queue_drained: bool) {
+		if let Some(new_blocks) = self.notify_batcher.flush(Instant::now(), queue_drained) {
+			self.notify(|notify| notify.new_blocks(new_blocks.clone()));
+		}
+	}
+
 	fn check_garbage(&self) {
 		self.chain.read().collect_garbage();
 		self.importer.block_queue.collect_garbage();
 		self.tracedb.read().collect_garbage();
+		self.retry_quarantined_blocks();
+	}
+
+	fn check_disk_usage(&self) {
+		let mut refreshed = self.disk_usage_refreshed.lock();
+		if refreshed.elapsed() >= DISK_USAGE_REFRESH_INTERVAL {
+			self.db.read().refresh_disk_usage();
+			*refreshed = Instant::now();
+		}
 	}
 
 	fn check_snooze(&self) {
@@ -1203,6 +1381,24 @@ impl Client {
 		}.fake_sign(from)
 	}
 
+	fn apply_state_override(state: &mut State<StateDB>, overrides: &StateOverride) -> ethtrie::Result<()> {
+		for (address, account) in overrides {
+			if let Some(balance) = account.balance {
+				state.set_balance(address, balance)?;
+			}
+			if let Some(nonce) = account.nonce {
+				state.set_nonce(address, nonce)?;
+			}
+			if let Some(ref code) = account.code {
+				state.reset_code(address, code.clone())?;
+			}
+			for (key, value) in &account.storage {
+				state.set_storage(address, *key, *value)?;
+			}
+		}
+		Ok(())
+	}
+
 	fn do_virtual_call(
 		machine: &::machine::Machine,
 		env_info: &EnvInfo,
@@ -1225,13 +1421,24 @@ impl Client {
 			let options = options
 				.dont_check_nonce()
 				.save_output_from_contract();
-			let original_state = if state_diff { Some(state.clone()) } else { None };
+			if state_diff {
+				state.checkpoint();
+			}
 			let schedule = machine.schedule(env_info.number);
 
-			let mut ret = Executive::new(state, env_info, &machine, &schedule).transact_virtual(transaction, options)?;
+			let result = Executive::new(state, env_info, &machine, &schedule).transact_virtual(transaction, options);
+			let mut ret = match result {
+				Ok(ret) => ret,
+				Err(e) => {
+					if state_diff {
+						state.discard_checkpoint();
+					}
+					return Err(e.into());
+				}
+			};
 
-			if let Some(original) = original_state {
-				ret.state_diff = Some(state.diff_from(original).map_err(ExecutionError::from)?);
+			if state_diff {
+				ret.state_diff = Some(state.diff_from_checkpoint().map_err(ExecutionError::from)?);
 			}
 			Ok(ret)
 		}
@@ -1430,7 +1637,7 @@ impl CallContract for Client {
 
 		let transaction = self.contract_call_tx(block_id, address, data);
 
-		self.call(&transaction, Default::default(), state, &header)
+		self.call(&transaction, Default::default(), state, &header, None)
 			.map_err(|e| format!("{:?}", e))
 			.map(|executed| executed.output)
 	}
@@ -1444,6 +1651,21 @@ impl RegistrarClient for Client {
 
 impl ImportBlock for Client {
 	fn import_block(&self, unverified: Unverified) -> EthcoreResult<H256> {
+		self.import_block_from(unverified, None)
+	}
+
+	fn import_block_from_peer(&self, unverified: Unverified, peer_id: usize) -> EthcoreResult<H256> {
+		self.import_block_from(unverified, Some(peer_id))
+	}
+
+	/// Triggered by a message from a block queue when the block is ready for insertion
+	fn import_verified_blocks(&self) -> usize {
+		self.importer.import_verified_blocks(self)
+	}
+}
+
+impl Client {
+	fn import_block_from(&self, unverified: Unverified, peer: Option<usize>) -> EthcoreResult<H256> {
 		if self.chain.read().is_known(&unverified.hash()) {
 			return Err(EthcoreError::Import(ImportError::AlreadyInChain));
 		}
@@ -1459,13 +1681,15 @@ impl ImportBlock for Client {
 			None
 		};
 
-		match self.importer.block_queue.import(unverified) {
+		match self.importer.block_queue.import_from(unverified, peer) {
 			Ok(hash) => {
 				if let Some((bytes, difficulty)) = raw {
 					self.notify(move |n| n.block_pre_import(&bytes, &hash, &difficulty));
 				}
 				Ok(hash)
 			},
+			// future blocks are quarantined by the queue, they are not bad
+			Err((EthcoreError::Block(e @ BlockError::TemporarilyInvalid(_)), _)) => Err(EthcoreError::Block(e)),
 			// we only care about block errors (not import errors)
 			Err((EthcoreError::Block(e), Some(input))) => {
 				self.importer.bad_blocks.report(input.bytes, e.to_string());
@@ -1479,9 +1703,16 @@ impl ImportBlock for Client {
 		}
 	}
 
-	/// Triggered by a message from a block queue when the block is ready for insertion
-	fn import_verified_blocks(&self) -> usize {
-		self.importer.import_verified_blocks(self)
+	/// Import the blocks held back for being ahead of the clock again, through the same checks
+	/// as new blocks. Those still too far in the future are held back again.
+	fn retry_quarantined_blocks(&self) {
+		for (block, peer) in self.importer.block_queue.take_quarantined() {
+			let hash = block.hash();
+			match self.import_block_from(block, peer) {
+				Ok(_) => trace!(target: "client", "Quarantined block {} queued", hash),
+				Err(e) => trace!(target: "client", "Quarantined block {} not queued: {}", hash, e),
+			}
+		}
 	}
 }
 
@@ -1500,7 +1731,11 @@ impl StateClient for Client {
 impl Call for Client {
 	type State = State<::state_db::StateDB>;
 
-	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header) -> Result<Executed, CallError> {
+	fn call(&self, transaction: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header, state_override: Option<&StateOverride>) -> Result<Executed, CallError> {
+		if let Some(overrides) = state_override {
+			Self::apply_state_override(state, overrides).map_err(|_| CallError::StateCorrupt)?;
+		}
+
 		let env_info = EnvInfo {
 			number: header.number(),
 			author: *header.author(),
@@ -1515,6 +1750,12 @@ impl Call for Client {
 		Self::do_virtual_call(&machine, &env_info, state, transaction, analytics)
 	}
 
+	fn call_observed(&self, transaction: &SignedTransaction, state: &mut Self::State, header: &Header, observer: Arc<dyn InstructionObserver>) -> Result<Executed, CallError> {
+		let vm_factory = state.vm_factory().with_observer(observer);
+		state.set_vm_factory(vm_factory);
+		self.call(transaction, Default::default(), state, header, None)
+	}
+
 	fn call_many(&self, transactions: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
 		let mut env_info = EnvInfo {
 			number: header.number(),
@@ -1633,6 +1874,11 @@ impl BadBlocks for Client {
 	fn bad_blocks(&self) -> Vec<(Unverified, String)> {
 		self.importer.bad_blocks.bad_blocks()
 	}
+
+	fn clear_bad_blocks(&self) {
+		self.importer.block_queue.clear_bad();
+		self.importer.bad_blocks.clear();
+	}
 }
 
 impl BlockChainClient for Client {
@@ -1665,6 +1911,17 @@ impl BlockChainClient for Client {
 			})))
 	}
 
+	fn replay_block(&self, block: BlockId, analytics: CallAnalytics) -> Result<ReplayedBlock, CallError> {
+		let (_, locked, executed) = self.reenact_block(block, analytics)?;
+		let expected = self.block_header_decoded(block).ok_or(CallError::StatePruned)?;
+
+		Ok(ReplayedBlock {
+			transactions: locked.transactions.iter().map(|t| t.hash()).zip(executed).collect(),
+			state_root: *locked.header.state_root(),
+			expected_state_root: *expected.state_root(),
+		})
+	}
+
 	fn mode(&self) -> Mode {
 		self.mode.lock().clone()
 	}
@@ -1700,6 +1957,29 @@ impl BlockChainClient for Client {
 		}
 	}
 
+	fn note_network_difficulty(&self, total_difficulty: U256) {
+		let passive = match *self.mode.lock() {
+			Mode::Passive(..) => true,
+			_ => false,
+		};
+		// A sleeping client has no peers, waking it up is left to the passive mode timer.
+		if !passive || !self.liveness.load(AtomicOrdering::Relaxed) {
+			return;
+		}
+
+		let (our_difficulty, margin) = {
+			let chain = self.chain.read();
+			let block_difficulty = *chain.best_block_header().difficulty();
+			(chain.best_block_total_difficulty(), block_difficulty.saturating_mul(MIN_PEER_BLOCKS_AHEAD_TO_STAY_AWAKE.into()))
+		};
+		if total_difficulty > our_difficulty.saturating_add(margin) {
+			trace!(target: "mode", "Network is ahead (td {} vs {}), staying awake.", total_difficulty, our_difficulty);
+			let mut ss = self.sleep_state.lock();
+			ss.last_activity = Some(Instant::now());
+			ss.last_autosleep = None;
+		}
+	}
+
 	fn spec_name(&self) -> String {
 		self.config.spec_name.clone()
 	}
@@ -1870,6 +2150,44 @@ impl BlockChainClient for Client {
 		Some(keys)
 	}
 
+	fn list_account_entries(&self, id: BlockId, after: Option<&[u8]>, count: u64) -> Option<Vec<(Bytes, BasicAccount)>> {
+		let state = self.state_at(id)?;
+		let (root, db) = state.drop();
+		let db = &db.as_hash_db();
+		let trie = match self.factories.trie.readonly(db, &root) {
+			Ok(trie) => trie,
+			Err(e) => {
+				trace!(target: "client", "list_account_entries: Couldn't open the DB: {:?}", e);
+				return None;
+			}
+		};
+
+		let mut iter = trie.iter().ok()?;
+		if let Some(after) = after {
+			if let Err(e) = iter.seek(after) {
+				trace!(target: "client", "list_account_entries: Couldn't seek the DB: {:?}", e);
+			} else {
+				// Position the iterator after the `after` element
+				iter.next();
+			}
+		}
+
+		let entries = iter.filter_map(|item| {
+			let (key, value) = item.ok()?;
+			rlp::decode::<BasicAccount>(&value).ok().map(|account| (key, account))
+		}).take(count as usize).collect();
+
+		Some(entries)
+	}
+
+	fn pin_state(&self, number: BlockNumber) {
+		self.state_db.read().journal_db().pin_era(number);
+	}
+
+	fn unpin_state(&self, number: BlockNumber) {
+		self.state_db.read().journal_db().unpin_era(number);
+	}
+
 	fn transaction(&self, id: TransactionId) -> Option<LocalizedTransaction> {
 		self.transaction_address(id).and_then(|address| self.chain.read().transaction(&address))
 	}
@@ -1889,13 +2207,15 @@ impl BlockChainClient for Client {
 		let body = chain.block_body(&hash)?;
 		let mut receipts = chain.block_receipts(&hash)?.receipts;
 		receipts.truncate(address.index + 1);
+		let output = chain.block_outputs(&hash)
+			.and_then(|outputs| outputs.outputs.into_iter().nth(address.index));
 
 		let transaction = body.view().localized_transaction_at(&hash, number, address.index)?;
 		let receipt = receipts.pop()?;
 		let gas_used = receipts.last().map_or_else(|| 0.into(), |r| r.gas_used);
 		let no_of_logs = receipts.into_iter().map(|receipt| receipt.logs.len()).sum::<usize>();
 
-		let receipt = transaction_receipt(transaction, receipt, gas_used, no_of_logs);
+		let receipt = transaction_receipt(transaction, receipt, output, gas_used, no_of_logs);
 		Some(receipt)
 	}
 
@@ -1906,6 +2226,7 @@ impl BlockChainClient for Client {
 		let receipts = chain.block_receipts(&hash)?;
 		let number = chain.block_number(&hash)?;
 		let body = chain.block_body(&hash)?;
+		let mut outputs = chain.block_outputs(&hash).map(|outputs| outputs.outputs.into_iter());
 
 		let mut gas_used = 0.into();
 		let mut no_of_logs = 0;
@@ -1916,7 +2237,8 @@ impl BlockChainClient for Client {
 			.into_iter()
 			.zip(receipts.receipts)
 			.map(move |(transaction, receipt)| {
-				let result = transaction_receipt(transaction, receipt, gas_used, no_of_logs);
+				let output = outputs.as_mut().and_then(Iterator::next);
+				let result = transaction_receipt(transaction, receipt, output, gas_used, no_of_logs);
 				gas_used = result.cumulative_gas_used;
 				no_of_logs += result.logs.len();
 				result
@@ -1954,99 +2276,7 @@ impl BlockChainClient for Client {
 	}
 
 	fn logs(&self, filter: Filter) -> Result<Vec<LocalizedLogEntry>, BlockId> {
-		let chain = self.chain.read();
-
-		// First, check whether `filter.from_block` and `filter.to_block` is on the canon chain. If so, we can use the
-		// optimized version.
-		let is_canon = |id| {
-			match id {
-				// If it is referred by number, then it is always on the canon chain.
-				&BlockId::Earliest | &BlockId::Latest | &BlockId::Number(_) => true,
-				// If it is referred by hash, we see whether a hash -> number -> hash conversion gives us the same
-				// result.
-				&BlockId::Hash(ref hash) => chain.is_canon(hash),
-			}
-		};
-
-		let blocks = if is_canon(&filter.from_block) && is_canon(&filter.to_block) {
-			// If we are on the canon chain, use bloom filter to fetch required hashes.
-			//
-			// If we are sure the block does not exist (where val > best_block_number), then return error. Note that we
-			// don't need to care about pending blocks here because RPC query sets pending back to latest (or handled
-			// pending logs themselves).
-			let from = match self.block_number_ref(&filter.from_block) {
-				Some(val) if val <= chain.best_block_number() => val,
-				_ => return Err(filter.from_block),
-			};
-			let to = match self.block_number_ref(&filter.to_block) {
-				Some(val) if val <= chain.best_block_number() => val,
-				_ => return Err(filter.to_block),
-			};
-
-			// If from is greater than to, then the current bloom filter behavior is to just return empty
-			// result. There's no point to continue here.
-			if from > to {
-				return Err(filter.to_block);
-			}
-
-			chain.blocks_with_bloom(&filter.bloom_possibilities(), from, to)
-				.into_iter()
-				.filter_map(|n| chain.block_hash(n))
-				.collect::<Vec<H256>>()
-		} else {
-			// Otherwise, we use a slower version that finds a link between from_block and to_block.
-			let from_hash = match Self::block_hash(&chain, filter.from_block) {
-				Some(val) => val,
-				None => return Err(filter.from_block),
-			};
-			let from_number = match chain.block_number(&from_hash) {
-				Some(val) => val,
-				None => return Err(BlockId::Hash(from_hash)),
-			};
-			let to_hash = match Self::block_hash(&chain, filter.to_block) {
-				Some(val) => val,
-				None => return Err(filter.to_block),
-			};
-
-			let blooms = filter.bloom_possibilities();
-			let bloom_match = |header: &encoded::Header| {
-				blooms.iter().any(|bloom| header.log_bloom().contains_bloom(bloom))
-			};
-
-			let (blocks, last_hash) = {
-				let mut blocks = Vec::new();
-				let mut current_hash = to_hash;
-
-				loop {
-					let header = match chain.block_header_data(&current_hash) {
-						Some(val) => val,
-						None => return Err(BlockId::Hash(current_hash)),
-					};
-					if bloom_match(&header) {
-						blocks.push(current_hash);
-					}
-
-					// Stop if `from` block is reached.
-					if header.number() <= from_number {
-						break;
-					}
-					current_hash = header.parent_hash();
-				}
-
-				blocks.reverse();
-				(blocks, current_hash)
-			};
-
-			// Check if we've actually reached the expected `from` block.
-			if last_hash != from_hash || blocks.is_empty() {
-				// In this case, from_hash is the cause (for not matching last_hash).
-				return Err(BlockId::Hash(from_hash));
-			}
-
-			blocks
-		};
-
-		Ok(chain.logs(blocks, |entry| filter.matches(entry), filter.limit))
+		self.chain.read().logs(&filter)
 	}
 
 	fn filter_traces(&self, filter: TraceFilter) -> Option<Vec<LocalizedTrace>> {
@@ -2064,11 +2294,7 @@ impl BlockChainClient for Client {
 		};
 
 		let traces = self.tracedb.read()
-			.filter(&db_filter)
-			.into_iter()
-			.skip(filter.after.unwrap_or(0))
-			.take(filter.count.unwrap_or(usize::max_value()))
-			.collect();
+			.filter_page(&db_filter, filter.after.unwrap_or(0), filter.count.unwrap_or(usize::max_value()));
 		Some(traces)
 	}
 
@@ -2132,6 +2358,34 @@ impl BlockChainClient for Client {
 		self.importer.miner.ready_transactions(self, max_len, PendingOrdering::Priority)
 	}
 
+	fn gas_price_corpus(&self, sample_size: usize) -> stats::Corpus<U256> {
+		let best_block_hash = self.chain.read().best_block_hash();
+		if let Some((ref hash, size, ref corpus)) = *self.gas_price_corpus.read() {
+			if *hash == best_block_hash && size == sample_size {
+				return corpus.clone();
+			}
+		}
+
+		// walk back at least `sample_size` blocks, continuing until some transactions are found.
+		let mut h = best_block_hash;
+		let mut sampled = 0;
+		let mut corpus = Vec::new();
+		while sampled < sample_size || corpus.is_empty() {
+			let block = match self.block(BlockId::Hash(h)) {
+				Some(ref block) if block.number() == 0 => break,
+				Some(block) => block,
+				None => break,
+			};
+			corpus.extend(block.transaction_views().iter().map(|t| t.gas_price()));
+			h = block.parent_hash();
+			sampled += 1;
+		}
+
+		let corpus: stats::Corpus<U256> = corpus.into();
+		*self.gas_price_corpus.write() = Some((best_block_hash, sample_size, corpus.clone()));
+		corpus
+	}
+
 	fn signing_chain_id(&self) -> Option<u64> {
 		self.engine.signing_chain_id(&self.latest_env_info())
 	}
@@ -2157,6 +2411,18 @@ impl BlockChainClient for Client {
 		}
 	}
 
+	fn pruning_mode(&self) -> &'static str {
+		self.pruning.as_str()
+	}
+
+	fn disk_usage(&self) -> Option<DiskUsage> {
+		self.db.read().disk_usage()
+	}
+
+	fn reorg_history(&self, count: usize) -> Vec<Reorg> {
+		self.chain.read().reorg_history(count)
+	}
+
 	fn create_transaction(&self, TransactionRequest { action, data, gas, gas_price, nonce }: TransactionRequest)
 		-> Result<SignedTransaction, transaction::Error>
 	{
@@ -2292,6 +2558,10 @@ impl Tick for Client {
 	// TODO: manage by real events.
 	fn tick(&self, prevent_sleep: bool) {
 		self.check_garbage();
+		self.check_disk_usage();
+		self.flush_new_blocks(self.queue_info().is_empty());
+		// picks up queue changes made while the pseudo-pending block was rebuilt too recently
+		self.importer.miner.update_pseudo_pending(self);
 		if !prevent_sleep {
 			self.check_snooze();
 		}
@@ -2348,6 +2618,9 @@ impl PrepareOpenBlock for Client {
 			extra_data,
 			is_epoch_begin,
 		)?;
+		if self.config.store_outputs {
+			open_block.record_outputs();
+		}
 
 		// Add uncles
 		chain
@@ -2404,7 +2677,7 @@ impl ImportSealedBlock for Client {
 			let pending = self.importer.check_epoch_end_signal(
 				&header,
 				&block_bytes,
-				&block.receipts,
+				&block.block_receipts(),
 				block.state.db(),
 				self
 			)?;
@@ -2428,19 +2701,19 @@ impl ImportSealedBlock for Client {
 			route.retracted(),
 			self.engine.sealing_state() != SealingState::External,
 		);
-		self.notify(|notify| {
-			notify.new_blocks(
-				NewBlocks::new(
-					vec![hash],
-					vec![],
-					route.clone(),
-					vec![hash],
-					vec![],
-					start.elapsed(),
-					false
-				)
-			);
-		});
+		let new_blocks = NewBlocks::new(
+			vec![hash],
+			vec![],
+			route,
+			vec![hash],
+			vec![],
+			start.elapsed(),
+			false
+		);
+		// never held back, but flushes anything batched before it
+		if let Some(new_blocks) = self.notify_batcher.push(new_blocks, Instant::now()) {
+			self.notify(|notify| notify.new_blocks(new_blocks.clone()));
+		}
 		self.db.read().key_value().flush().expect("DB flush failed.");
 		Ok(hash)
 	}
@@ -2587,11 +2860,11 @@ impl SnapshotClient for Client {
 			at, actual_block_nr, block_hash, self.pruning_info().earliest_chain, earliest_era, processing_threads,
 		);
 		// Stop pruning from happening while the snapshot is under way.
-		self.snapshotting_at.store(actual_block_nr, Ordering::SeqCst);
+		db.pin_era(actual_block_nr);
 		{
 			scopeguard::defer! {{
 				trace!(target: "snapshot", "Re-enabling pruning.");
-				self.snapshotting_at.store(0, Ordering::SeqCst)
+				db.unpin_era(actual_block_nr)
 			}};
 			let chunker = snapshot::chunker(self.engine.snapshot_mode()).ok_or_else(|| SnapshotError::SnapshotsUnsupported)?;
 			// Spawn threads and take snapshot
@@ -2744,11 +3017,20 @@ impl ImportExportBlocks for Client {
 	}
 }
 
+/// Whether the error was caused by the local node (e.g. database failure) rather than the block itself.
+fn is_local_failure(err: &EthcoreError) -> bool {
+	match *err {
+		EthcoreError::Io(_) | EthcoreError::StdIo(_) | EthcoreError::Trie(_) | EthcoreError::Snappy(_) => true,
+		_ => false,
+	}
+}
+
 /// Returns `LocalizedReceipt` given `LocalizedTransaction`
 /// and a vector of receipts from given block up to transaction index.
 fn transaction_receipt(
 	mut tx: LocalizedTransaction,
 	receipt: Receipt,
+	output: Option<Bytes>,
 	prior_gas_used: U256,
 	prior_no_of_logs: usize,
 ) -> LocalizedReceipt {
@@ -2782,9 +3064,11 @@ fn transaction_receipt(
 			transaction_index,
 			transaction_log_index: i,
 			log_index: prior_no_of_logs + i,
+			removed: false,
 		}).collect(),
 		log_bloom: receipt.log_bloom,
 		outcome: receipt.outcome,
+		output,
 	}
 }
 
@@ -2836,7 +3120,7 @@ mod tests {
 	use std::thread;
 	use std::time::Duration;
 
-	use ethereum_types::{Address, H256};
+	use ethereum_types::{Address, H256, U256};
 	use hash::keccak;
 	use kvdb::DBTransaction;
 
@@ -2881,6 +3165,28 @@ mod tests {
 		assert!(client.tree_route(&genesis, &new_hash).is_none());
 	}
 
+	#[test]
+	fn should_reuse_gas_price_corpus_until_best_block_changes() {
+		let client = generate_dummy_client_with_data(3, 1, &[1.into(), 2.into(), 3.into()]);
+		let best_hash = client.chain_info().best_block_hash;
+		assert_eq!(client.gas_price_corpus(3).len(), 3);
+
+		// the cached corpus is returned while the best block and sample size are the same
+		*client.gas_price_corpus.write() = Some((best_hash, 3, vec![U256::from(42)].into()));
+		assert_eq!(client.gas_price_corpus(3).median(), Some(&U256::from(42)));
+		assert_eq!(client.gas_price_corpus(1).len(), 1);
+
+		// and computed again once the best block changed
+		*client.gas_price_corpus.write() = Some((H256::zero(), 1, vec![U256::from(42)].into()));
+		assert_eq!(client.gas_price_corpus(1).median(), Some(&U256::from(3)));
+
+		// an import refreshes the corpus for the sample size in use
+		*client.gas_price_corpus.write() = Some((H256::zero(), 2, vec![U256::from(42)].into()));
+		client.refresh_gas_price_corpus();
+		let cached = client.gas_price_corpus.read().clone().map(|(hash, size, corpus)| (hash, size, corpus.len()));
+		assert_eq!(cached, Some((best_hash, 2, 2)));
+	}
+
 	#[test]
 	fn should_return_block_receipts() {
 		let client = generate_dummy_client_with_data(2, 2, &[1.into(), 1.into()]);
@@ -2947,7 +3253,7 @@ mod tests {
 		};
 
 		// when
-		let receipt = transaction_receipt(transaction, receipt, 5.into(), 1);
+		let receipt = transaction_receipt(transaction, receipt, None, 5.into(), 1);
 
 		// then
 		assert_eq!(receipt, LocalizedReceipt {
@@ -2971,6 +3277,7 @@ mod tests {
 				transaction_index: 1,
 				transaction_log_index: 0,
 				log_index: 1,
+				removed: false,
 			}, LocalizedLogEntry {
 				entry: logs[1].clone(),
 				block_hash: block_hash,
@@ -2979,9 +3286,11 @@ mod tests {
 				transaction_index: 1,
 				transaction_log_index: 1,
 				log_index: 2,
+				removed: false,
 			}],
 			log_bloom: Default::default(),
 			outcome: TransactionOutcome::StateRoot(state_root),
+			output: None,
 		});
 	}
 
@@ -15,6 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::str::FromStr;
+use std::time::Duration;
 
 use blockchain::Config as BlockChainConfig;
 use journaldb;
@@ -94,6 +95,11 @@ pub struct ClientConfig {
 	pub max_round_blocks_to_import: usize,
 	/// Snapshot configuration
 	pub snapshot: SnapshotConfiguration,
+	/// Minimal interval between `new_blocks` notifications while there are still blocks to import.
+	/// `None` delivers a notification for every import round.
+	pub new_blocks_notify_interval: Option<Duration>,
+	/// Record the return data of every transaction on import, so receipts can include it.
+	pub store_outputs: bool,
 }
 
 impl Default for ClientConfig {
@@ -119,6 +125,8 @@ impl Default for ClientConfig {
 			transaction_verification_queue_size: 8192,
 			max_round_blocks_to_import: 12,
 			snapshot: Default::default(),
+			new_blocks_notify_interval: None,
+			store_outputs: false,
 		}
 	}
 }
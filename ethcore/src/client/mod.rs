@@ -20,6 +20,7 @@ mod ancient_import;
 mod bad_blocks;
 mod client;
 mod config;
+mod notify_batch;
 mod traits;
 
 pub use self::client::Client;
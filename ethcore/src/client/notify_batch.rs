@@ -0,0 +1,205 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Coalescing of `new_blocks` notifications during bulk import.
+//!
+//! While the block queue still has blocks to import, notifications are delivered at most
+//! once per interval, with everything imported in between folded into a single `NewBlocks`.
+//! A notification that leaves the queue empty is always delivered straight away, together
+//! with anything still held back. Batches left behind when imports stall are flushed from
+//! the client tick, or as soon as the queue drains.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use types::chain_notify::NewBlocks;
+
+struct State {
+	pending: Option<NewBlocks>,
+	last_delivery: Option<Instant>,
+}
+
+/// Batches `NewBlocks` notifications. Without an interval every notification is delivered as is.
+pub struct NotifyBatcher {
+	interval: Option<Duration>,
+	state: Mutex<State>,
+}
+
+impl NotifyBatcher {
+	/// Create a new batcher delivering at most once per `interval` while importing.
+	pub fn new(interval: Option<Duration>) -> Self {
+		NotifyBatcher {
+			interval,
+			state: Mutex::new(State {
+				pending: None,
+				last_delivery: None,
+			}),
+		}
+	}
+
+	/// Queue a notification. Returns what should be delivered now, if anything.
+	pub fn push(&self, new_blocks: NewBlocks, now: Instant) -> Option<NewBlocks> {
+		let interval = match self.interval {
+			Some(interval) => interval,
+			None => return Some(new_blocks),
+		};
+
+		let mut state = self.state.lock();
+		let batch = match state.pending.take() {
+			Some(mut pending) => {
+				pending.append(new_blocks);
+				pending
+			},
+			None => new_blocks,
+		};
+
+		let due = state.last_delivery.map_or(true, |last| now >= last + interval);
+		if batch.has_more_blocks_to_import && !due {
+			state.pending = Some(batch);
+			return None;
+		}
+
+		state.last_delivery = Some(now);
+		Some(batch)
+	}
+
+	/// Take the held back notification if its interval has elapsed, or unconditionally
+	/// once the queue has been drained.
+	pub fn flush(&self, now: Instant, queue_drained: bool) -> Option<NewBlocks> {
+		let interval = self.interval?;
+		let mut state = self.state.lock();
+		let due = state.last_delivery.map_or(true, |last| now >= last + interval);
+		if !queue_drained && !due {
+			return None;
+		}
+
+		let mut batch = state.pending.take()?;
+		if queue_drained {
+			batch.has_more_blocks_to_import = false;
+		}
+		state.last_delivery = Some(now);
+		Some(batch)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::{Duration, Instant};
+
+	use ethereum_types::H256;
+	use types::chain_notify::{ChainRoute, ChainRouteType, NewBlocks};
+
+	use super::NotifyBatcher;
+
+	fn new_blocks(hash: H256, has_more_blocks_to_import: bool) -> NewBlocks {
+		NewBlocks::new(
+			vec![hash],
+			vec![],
+			ChainRoute::new(vec![(hash, ChainRouteType::Enacted)]),
+			vec![],
+			vec![],
+			Duration::from_millis(1),
+			has_more_blocks_to_import,
+		)
+	}
+
+	#[test]
+	fn delivers_everything_without_interval() {
+		let batcher = NotifyBatcher::new(None);
+		let now = Instant::now();
+		assert!(batcher.push(new_blocks(H256::from_low_u64_be(1), true), now).is_some());
+		assert!(batcher.push(new_blocks(H256::from_low_u64_be(2), true), now).is_some());
+	}
+
+	#[test]
+	fn coalesces_while_importing() {
+		let batcher = NotifyBatcher::new(Some(Duration::from_millis(100)));
+		let now = Instant::now();
+		let (a, b, c) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2), H256::from_low_u64_be(3));
+
+		// first notification goes out straight away
+		assert_eq!(batcher.push(new_blocks(a, true), now).unwrap().imported, vec![a]);
+		// then we hold back until the interval elapses
+		assert!(batcher.push(new_blocks(b, true), now + Duration::from_millis(10)).is_none());
+		let batch = batcher.push(new_blocks(c, true), now + Duration::from_millis(100)).unwrap();
+		assert_eq!(batch.imported, vec![b, c]);
+		assert_eq!(batch.route.enacted().len(), 2);
+		assert_eq!(batch.duration, Duration::from_millis(2));
+	}
+
+	#[test]
+	fn flushes_when_queue_drained() {
+		let batcher = NotifyBatcher::new(Some(Duration::from_secs(60)));
+		let now = Instant::now();
+		let (a, b, c) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2), H256::from_low_u64_be(3));
+
+		assert!(batcher.push(new_blocks(a, true), now).is_some());
+		assert!(batcher.push(new_blocks(b, true), now).is_none());
+		let batch = batcher.push(new_blocks(c, false), now).unwrap();
+		assert_eq!(batch.imported, vec![b, c]);
+		assert!(!batch.has_more_blocks_to_import);
+	}
+
+	#[test]
+	fn flushes_held_back_batch() {
+		let batcher = NotifyBatcher::new(Some(Duration::from_millis(100)));
+		let now = Instant::now();
+		let (a, b) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2));
+
+		assert!(batcher.push(new_blocks(a, true), now).is_some());
+		assert!(batcher.push(new_blocks(b, true), now).is_none());
+		// not due yet while the queue keeps importing
+		assert!(batcher.flush(now + Duration::from_millis(10), false).is_none());
+		// once due, the tick delivers it
+		assert_eq!(batcher.flush(now + Duration::from_millis(100), false).unwrap().imported, vec![b]);
+		assert!(batcher.flush(now + Duration::from_millis(200), true).is_none());
+
+		// and a drained queue delivers it straight away
+		assert!(batcher.push(new_blocks(a, true), now + Duration::from_millis(210)).is_none());
+		let batch = batcher.flush(now + Duration::from_millis(210), true).unwrap();
+		assert_eq!(batch.imported, vec![a]);
+		assert!(!batch.has_more_blocks_to_import);
+	}
+
+	#[test]
+	fn nets_out_reverted_route_changes() {
+		let batcher = NotifyBatcher::new(Some(Duration::from_secs(60)));
+		let now = Instant::now();
+		let (a, b, c) = (H256::from_low_u64_be(1), H256::from_low_u64_be(2), H256::from_low_u64_be(3));
+
+		assert!(batcher.push(new_blocks(a, true), now).is_some());
+		assert!(batcher.push(new_blocks(b, true), now).is_none());
+		// `c` replaces `b`, then `a` is retracted and enacted again by a reorg
+		let reorg = NewBlocks::new(
+			vec![c],
+			vec![],
+			ChainRoute::new(vec![
+				(b, ChainRouteType::Retracted),
+				(a, ChainRouteType::Retracted),
+				(a, ChainRouteType::Enacted),
+				(c, ChainRouteType::Enacted),
+			]),
+			vec![],
+			vec![],
+			Duration::from_millis(1),
+			false,
+		);
+		let batch = batcher.push(reorg, now).unwrap();
+		assert_eq!(batch.route.enacted(), &[c]);
+		assert!(batch.route.retracted().is_empty());
+		assert!(batch.route.route().iter().all(|&(hash, _)| hash == c));
+	}
+}
@@ -14,14 +14,18 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::sync::Arc;
+
 use bytes::Bytes;
 use ethereum_types::{H256, U256, Address};
+use evm::interpreter::InstructionObserver;
 use types::{
 	transaction::{SignedTransaction, CallError},
 	call_analytics::CallAnalytics,
 	errors::EthcoreError as Error,
 	errors::EthcoreResult,
 	header::Header,
+	state_override::StateOverride,
 };
 
 use block::{OpenBlock, SealedBlock, ClosedBlock};
@@ -35,7 +39,12 @@ pub trait Call {
 	type State: StateInfo;
 
 	/// Makes a non-persistent transaction call.
-	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header) -> Result<Executed, CallError>;
+	/// If `state_override` is given, the accounts are modified accordingly before the call is made.
+	fn call(&self, tx: &SignedTransaction, analytics: CallAnalytics, state: &mut Self::State, header: &Header, state_override: Option<&StateOverride>) -> Result<Executed, CallError>;
+
+	/// Makes a non-persistent transaction call, consulting `observer` before every EVM instruction.
+	/// A blocking observer pauses the call, see `evm::interpreter::DebugSession`.
+	fn call_observed(&self, tx: &SignedTransaction, state: &mut Self::State, header: &Header, observer: Arc<dyn InstructionObserver>) -> Result<Executed, CallError>;
 
 	/// Makes multiple non-persistent but dependent transaction calls.
 	/// Returns a vector of successes or a failure if any of the transaction fails.
@@ -26,7 +26,7 @@ use test_helpers::{self, EvmTestClient};
 use types::verification::Unverified;
 use verification::{VerifierType, queue::kind::BlockLike};
 use super::SKIP_TESTS;
-use super::HookType;
+use super::{HookType, TestFilter};
 
 #[allow(dead_code)]
 fn skip_test(name: &String) -> bool {
@@ -36,14 +36,30 @@ fn skip_test(name: &String) -> bool {
 		.any(|block_test|block_test.subtests.contains(name))
 }
 
+/// Run the blockchain tests under the given path (file or directory) that match `filter`,
+/// returning the names of failed tests.
+pub fn run_test_path<H: FnMut(&str, HookType)>(p: &Path, skip: &[&'static str], filter: &TestFilter, h: &mut H) -> Vec<String> {
+	::json_tests::test_common::collect_test_path(p, skip, &|path: &Path, json_data: &[u8], h: &mut H| {
+		json_chain_test_filtered(path, json_data, filter, h)
+	}, h)
+}
+
 #[allow(dead_code)]
 pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8], start_stop_hook: &mut H) -> Vec<String> {
+	json_chain_test_filtered(path, json_data, &TestFilter::default(), start_stop_hook)
+}
+
+fn json_chain_test_filtered<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8], filter: &TestFilter, start_stop_hook: &mut H) -> Vec<String> {
 	let _ = ::env_logger::try_init();
 	let tests = blockchain::Test::load(json_data)
 		.expect(&format!("Could not parse JSON chain test data from {}", path.display()));
 	let mut failed = Vec::new();
 
 	for (name, blockchain) in tests.into_iter() {
+		if !filter.matches_name(&name) || !filter.matches_fork(&blockchain.network) {
+			continue;
+		}
+
 		if skip_test(&name) {
 			println!("   - {} | {:?}: SKIPPED", name, blockchain.network);
 			continue;
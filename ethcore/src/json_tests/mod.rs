@@ -29,8 +29,10 @@ mod skip;
 #[cfg(test)]
 mod difficulty;
 
-pub use self::test_common::HookType;
+pub use self::test_common::{HookType, TestFilter};
 pub use self::executive::run_test_path as run_executive_test_path;
 pub use self::executive::run_test_file as run_executive_test_file;
+pub use self::state::run_test_path as run_state_test_path;
+pub use self::chain::run_test_path as run_blockchain_test_path;
 
 use self::skip::SKIP_TESTS;
@@ -23,7 +23,7 @@ use test_helpers::{EvmTestClient, EvmTestError, TransactErr, TransactSuccess};
 use types::transaction::SignedTransaction;
 use vm::EnvInfo;
 use super::SKIP_TESTS;
-use super::HookType;
+use super::{HookType, TestFilter};
 
 #[allow(dead_code)]
 fn skip_test(subname: &str, chain: &String, number: usize) -> bool {
@@ -42,14 +42,26 @@ fn skip_test(subname: &str, chain: &String, number: usize) -> bool {
 	})
 }
 
+/// Run the state tests under the given path (file or directory) that match `filter`, returning
+/// the names of failed tests.
+pub fn run_test_path<H: FnMut(&str, HookType)>(p: &Path, skip: &[&'static str], filter: &TestFilter, h: &mut H) -> Vec<String> {
+	::json_tests::test_common::collect_test_path(p, skip, &|path: &Path, json_data: &[u8], h: &mut H| {
+		json_chain_test_filtered(path, json_data, filter, h)
+	}, h)
+}
+
 #[allow(dead_code)]
 pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8], start_stop_hook: &mut H) -> Vec<String> {
+	json_chain_test_filtered(path, json_data, &TestFilter::default(), start_stop_hook)
+}
+
+fn json_chain_test_filtered<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8], filter: &TestFilter, start_stop_hook: &mut H) -> Vec<String> {
 	let _ = ::env_logger::try_init();
 	let tests = ethjson::test_helpers::state::Test::load(json_data)
 		.expect(&format!("Could not parse JSON state test data from {}", path.display()));
 	let mut failed = Vec::new();
 
-	for (name, test) in tests.into_iter() {
+	for (name, test) in tests.into_iter().filter(|(name, _)| filter.matches_name(name)) {
 		start_stop_hook(&name, HookType::OnStart);
 
 		{
@@ -57,7 +69,7 @@ pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8],
 			let env: EnvInfo = test.env.into();
 			let pre: PodState = test.pre_state.into();
 
-			for (spec_name, states) in test.post_states {
+			for (spec_name, states) in test.post_states.into_iter().filter(|(spec_name, _)| filter.matches_fork(spec_name)) {
 				let total = states.len();
 				let spec = match EvmTestClient::fork_spec_from_json(&spec_name) {
 					Some(spec) => spec,
@@ -122,13 +134,27 @@ pub fn json_chain_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8],
 mod state_tests {
 	use std::path::Path;
 
-	use super::json_chain_test;
-	use json_tests::HookType;
+	use super::{json_chain_test, run_test_path};
+	use json_tests::{HookType, TestFilter};
 
 	fn do_json_test<H: FnMut(&str, HookType)>(path: &Path, json_data: &[u8], h: &mut H) -> Vec<String> {
 		json_chain_test(path, json_data, h)
 	}
 
+	#[test]
+	fn filters_tests_by_name_and_fork() {
+		let filter = TestFilter { name: Some("ADD11".into()), fork: Some("istanbul".into()) };
+		let mut started = Vec::new();
+		let failed = run_test_path(
+			Path::new("res/ethereum/tests/GeneralStateTests/stExample/"),
+			&[],
+			&filter,
+			&mut |name: &str, hook| if hook == HookType::OnStart { started.push(name.to_owned()) },
+		);
+		assert!(failed.is_empty());
+		assert_eq!(started, vec!["add11".to_owned()]);
+	}
+
 	declare_test!{GeneralStateTest_stArgsZeroOneBalance, "GeneralStateTests/stArgsZeroOneBalance/"}
 	declare_test!{GeneralStateTest_stAttackTest, "GeneralStateTests/stAttackTest/"}
 	declare_test!{GeneralStateTest_stBadOpcodeTest, "GeneralStateTests/stBadOpcode/"}
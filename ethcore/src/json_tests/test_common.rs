@@ -20,6 +20,7 @@ use std::fs::{File, read_dir};
 use std::path::Path;
 use std::ffi::OsString;
 pub use ethereum_types::{H256, U256, Address};
+use ethjson::spec::ForkSpec;
 
 /// Indicate when to run the hook passed to test functions.
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -30,6 +31,27 @@ pub enum HookType {
 	OnStop
 }
 
+/// Selects the tests of a suite to run, by test name and fork. Both are matched case insensitively.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TestFilter {
+	/// Only run the test with this name.
+	pub name: Option<String>,
+	/// Only run the test against this fork, e.g. `Istanbul` or `EIP158`.
+	pub fork: Option<String>,
+}
+
+impl TestFilter {
+	/// Whether the test named `name` should run.
+	pub fn matches_name(&self, name: &str) -> bool {
+		self.name.as_ref().map_or(true, |only| only.eq_ignore_ascii_case(name))
+	}
+
+	/// Whether tests should run against `fork`.
+	pub fn matches_fork(&self, fork: &ForkSpec) -> bool {
+		self.fork.as_ref().map_or(true, |only| only.eq_ignore_ascii_case(&format!("{:?}", fork)))
+	}
+}
+
 /// Run all tests under the given path (except for the test files named in the skip list) using the
 /// provided runner function.
 pub fn run_test_path<H: FnMut(&str, HookType)>(
@@ -38,6 +60,21 @@ pub fn run_test_path<H: FnMut(&str, HookType)>(
 	runner: fn(path: &Path, json_data: &[u8], start_stop_hook: &mut H) -> Vec<String>,
 	start_stop_hook: &mut H
 ) {
+	let errors = collect_test_path(path, skip, &runner, start_stop_hook);
+	let empty: [String; 0] = [];
+	assert_eq!(errors, empty, "\nThere were {} tests in '{}' that failed.", errors.len(), path.display());
+}
+
+/// Like `run_test_path`, but returns the names of failed tests instead of panicking.
+pub fn collect_test_path<H, R>(
+	path: &Path,
+	skip: &[&'static str],
+	runner: &R,
+	start_stop_hook: &mut H
+) -> Vec<String> where
+	H: FnMut(&str, HookType),
+	R: Fn(&Path, &[u8], &mut H) -> Vec<String>,
+{
 	if !skip.is_empty() {
 		// todo[dvdplm] it's really annoying to have to use flushln here. Should be `info!(target:
 		// "json-tests", …)`. Issue https://github.com/paritytech/parity-ethereum/issues/11084
@@ -45,17 +82,19 @@ pub fn run_test_path<H: FnMut(&str, HookType)>(
 	}
 	let mut errors = Vec::new();
 	run_test_path_inner(path, skip, runner, start_stop_hook, &mut errors);
-	let empty: [String; 0] = [];
-	assert_eq!(errors, empty, "\nThere were {} tests in '{}' that failed.", errors.len(), path.display());
+	errors
 }
 
-fn run_test_path_inner<H: FnMut(&str, HookType)>(
+fn run_test_path_inner<H, R>(
 	p: &Path,
 	skip: &[&'static str],
-	runner: fn(path: &Path, json_data: &[u8], start_stop_hook: &mut H) -> Vec<String>,
+	runner: &R,
 	start_stop_hook: &mut H,
 	errors: &mut Vec<String>
-) {
+) where
+	H: FnMut(&str, HookType),
+	R: Fn(&Path, &[u8], &mut H) -> Vec<String>,
+{
 	let path = Path::new(p);
 	let extension = path.extension().and_then(|s| s.to_str());
 	let skip_list: HashSet<OsString> = skip.iter().map(|s| {
@@ -91,12 +130,15 @@ fn run_test_path_inner<H: FnMut(&str, HookType)>(
 	}
 }
 
-fn run_test_file_append<H: FnMut(&str, HookType)>(
+fn run_test_file_append<H, R>(
 	path: &Path,
-	runner: fn(path: &Path, json_data: &[u8], start_stop_hook: &mut H) -> Vec<String>,
+	runner: &R,
 	start_stop_hook: &mut H,
 	errors: &mut Vec<String>
-) {
+) where
+	H: FnMut(&str, HookType),
+	R: Fn(&Path, &[u8], &mut H) -> Vec<String>,
+{
 	let mut data = Vec::new();
 	let mut file = match File::open(&path) {
 		Ok(file) => file,
@@ -25,6 +25,7 @@ use call_contract::CallContract;
 use ethcore_miner::gas_pricer::GasPricer;
 use ethcore_miner::local_accounts::LocalAccounts;
 use ethcore_miner::pool::{self, TransactionQueue, VerifiedTransaction, QueueStatus, PrioritizationStrategy, TxStatus};
+use ethcore_miner::pool::client::{AllowAll, TransactionAdmissionPolicy};
 use ethcore_miner::service_transaction_checker::ServiceTransactionChecker;
 #[cfg(feature = "work-notify")]
 use ethcore_miner::work_notify::NotifyWork;
@@ -152,12 +153,25 @@ pub struct MinerOptions {
 	pub tx_queue_penalization: Penalization,
 	/// Do we want to mark transactions received locally (e.g. RPC) as local if we don't have the sending account?
 	pub tx_queue_no_unfamiliar_locals: bool,
+	/// Maximal total RLP size of non-local transactions a single sender may have in the queue.
+	pub tx_queue_max_size_per_sender: Option<usize>,
 	/// Do we refuse to accept service transactions even if sender is certified.
 	pub refuse_service_transactions: bool,
 	/// Transaction pool limits.
 	pub pool_limits: pool::Options,
 	/// Initial transaction verification options.
 	pub pool_verification_options: pool::verifier::Options,
+
+	/// Minimal gas price of transactions included in authored blocks, local ones included.
+	pub block_min_gas_price: Option<U256>,
+	/// Maximal number of transactions in authored blocks.
+	pub block_max_transactions: Option<usize>,
+	/// Maximal share of the block gas limit, in percent, a single transaction may use.
+	pub tx_max_gas_percent: Option<u8>,
+	/// Answer `Pending` queries from a block of the queued transactions executed on top of the
+	/// best block while not sealing. The block is rebuilt when the best block or the queue changes,
+	/// at most once per `reseal_min_period`.
+	pub pseudo_pending_block: bool,
 }
 
 impl Default for MinerOptions {
@@ -176,6 +190,7 @@ impl Default for MinerOptions {
 			tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
 			tx_queue_penalization: Penalization::Disabled,
 			tx_queue_no_unfamiliar_locals: false,
+			tx_queue_max_size_per_sender: None,
 			refuse_service_transactions: false,
 			pool_limits: pool::Options {
 				max_count: 8_192,
@@ -188,10 +203,55 @@ impl Default for MinerOptions {
 				tx_gas_limit: U256::max_value(),
 				no_early_reject: false,
 			},
+			block_min_gas_price: None,
+			block_max_transactions: None,
+			tx_max_gas_percent: None,
+			pseudo_pending_block: false,
 		}
 	}
 }
 
+/// Number of transactions left out of authored blocks, by reason.
+///
+/// A transaction left out of consecutively prepared blocks is counted once.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MinerStats {
+	/// Gas price below `block_min_gas_price`.
+	pub low_gas_price: u64,
+	/// Block already had `block_max_transactions`.
+	pub too_many_transactions: u64,
+	/// Gas above `tx_max_gas_percent` of the block gas limit.
+	pub gas_share_exceeded: u64,
+	/// Not enough gas left in the block.
+	pub block_gas_limit_reached: u64,
+	/// Nonce not matching the sender's.
+	pub invalid_nonce: u64,
+	/// Sender not allowed to transact.
+	pub not_allowed: u64,
+	/// Failed verification or execution.
+	pub invalid: u64,
+}
+
+#[derive(Default)]
+struct SkipStats {
+	totals: MinerStats,
+	// transactions left out of the last prepared block
+	last_skipped: HashSet<H256>,
+}
+
+impl SkipStats {
+	fn note(&mut self, skipped: Vec<(H256, fn(&mut MinerStats) -> &mut u64)>) {
+		let mut now_skipped = HashSet::with_capacity(skipped.len());
+		for (hash, counter) in skipped {
+			if !self.last_skipped.contains(&hash) {
+				*counter(&mut self.totals) += 1;
+			}
+			now_skipped.insert(hash);
+		}
+		self.last_skipped = now_skipped;
+	}
+}
+
 /// Configurable parameters of block authoring.
 #[derive(Debug, Default, Clone)]
 pub struct AuthoringParams {
@@ -201,6 +261,21 @@ pub struct AuthoringParams {
 	pub author: Address,
 	/// Block extra data
 	pub extra_data: Bytes,
+	/// Block authors rotated through by block number; `author` is used when empty
+	pub author_rotation: Vec<Address>,
+	/// Whether blocks are sealed by an engine signer, whose address is always the author
+	pub sealer: bool,
+}
+
+impl AuthoringParams {
+	/// Returns the author to use for a block with given number.
+	pub fn author_for(&self, number: BlockNumber) -> Address {
+		if self.sealer || self.author_rotation.is_empty() {
+			self.author
+		} else {
+			self.author_rotation[(number % self.author_rotation.len() as u64) as usize]
+		}
+	}
 }
 
 /// Block sealing mechanism
@@ -241,6 +316,19 @@ impl SealingWork {
 	}
 }
 
+/// Pending block of a node that isn't sealing, see `MinerOptions::pseudo_pending_block`.
+#[derive(Default)]
+struct PseudoPending {
+	/// The last block built, with the version it contains. Dropped when the best block changes.
+	block: Option<(u64, Arc<ClosedBlock>)>,
+	/// When the last block was built.
+	built_at: Option<Instant>,
+	/// Bumped whenever the transaction queue or the best block changes.
+	version: u64,
+	/// Bumped whenever the best block changes.
+	head: u64,
+}
+
 /// Keeps track of transactions using priority queue and holds currently mined block.
 /// Handles preparing work for "work sealing" or seals "internally" if Engine does not require work.
 pub struct Miner {
@@ -258,6 +346,9 @@ pub struct Miner {
 	accounts: Arc<dyn LocalAccounts>,
 	io_channel: RwLock<Option<IoChannel<ClientIoMessage<Client>>>>,
 	service_transaction_checker: Option<ServiceTransactionChecker>,
+	admission_policy: RwLock<Arc<dyn TransactionAdmissionPolicy>>,
+	stats: Mutex<SkipStats>,
+	pseudo_pending: Mutex<PseudoPending>,
 }
 
 impl Miner {
@@ -292,6 +383,7 @@ impl Miner {
 		let limits = options.pool_limits.clone();
 		let verifier_options = options.pool_verification_options.clone();
 		let tx_queue_strategy = options.tx_queue_strategy;
+		let max_size_per_sender = options.tx_queue_max_size_per_sender;
 		let nonce_cache_size = cmp::max(4096, limits.max_count / 4);
 		let refuse_service_transactions = options.refuse_service_transactions;
 		let engine = spec.engine.clone();
@@ -311,7 +403,10 @@ impl Miner {
 			gas_pricer: Mutex::new(gas_pricer),
 			nonce_cache: NonceCache::new(nonce_cache_size),
 			options,
-			transaction_queue: Arc::new(TransactionQueue::new(limits, verifier_options, tx_queue_strategy)),
+			transaction_queue: Arc::new(
+				TransactionQueue::new(limits, verifier_options, tx_queue_strategy)
+					.with_max_size_per_sender(max_size_per_sender)
+			),
 			accounts: Arc::new(accounts),
 			engine,
 			io_channel: RwLock::new(None),
@@ -320,6 +415,9 @@ impl Miner {
 			} else {
 				Some(ServiceTransactionChecker::default())
 			},
+			admission_policy: RwLock::new(Arc::new(AllowAll)),
+			stats: Mutex::new(SkipStats::default()),
+			pseudo_pending: Mutex::new(PseudoPending::default()),
 		}
 	}
 
@@ -369,6 +467,7 @@ impl Miner {
 	/// Clear all pending block states
 	pub fn clear(&self) {
 		self.sealing.lock().queue.reset();
+		self.invalidate_pseudo_pending(true);
 	}
 
 	/// Updates transaction queue verification limits.
@@ -391,23 +490,100 @@ impl Miner {
 		self.service_transaction_checker.clone()
 	}
 
+	/// Set the policy deciding which transactions are admitted to the queue.
+	/// Transactions already in the queue are not re-checked.
+	pub fn set_transaction_admission_policy(&self, policy: Arc<dyn TransactionAdmissionPolicy>) {
+		*self.admission_policy.write() = policy;
+	}
+
 	/// Retrieves an existing pending block iff it's not older than given block number.
+	/// Falls back to the pseudo-pending block if enabled and nothing is being sealed.
 	///
-	/// NOTE: This will not prepare a new pending block if it's not existing.
+	/// NOTE: This will not prepare a new pending block for sealing if it's not existing.
 	fn map_existing_pending_block<F, T>(&self, f: F, latest_block_number: BlockNumber) -> Option<T> where
 		F: FnOnce(&ClosedBlock) -> T,
 	{
-		self.sealing.lock().queue
-			.peek_last_ref()
-			.and_then(|b| {
-				// to prevent a data race between block import and updating pending block
-				// we allow the number to be equal.
-				if b.header.number() >= latest_block_number {
-					Some(f(b))
-				} else {
-					None
-				}
-			})
+		{
+			let sealing = self.sealing.lock();
+			// to prevent a data race between block import and updating pending block
+			// we allow the number to be equal.
+			if let Some(b) = sealing.queue.peek_last_ref().filter(|b| b.header.number() >= latest_block_number) {
+				return Some(f(b));
+			}
+		}
+
+		let block = self.pseudo_pending.lock().block.as_ref().map(|&(_, ref block)| block.clone());
+		block.filter(|b| b.header.number() >= latest_block_number).map(|b| f(&b))
+	}
+
+	/// Rebuilds the pseudo-pending block on top of the best block of `chain` if the best block or
+	/// the transaction queue changed since it was built, and it wasn't built within the last
+	/// `reseal_min_period`. See `MinerOptions::pseudo_pending_block`.
+	pub fn update_pseudo_pending<C>(&self, chain: &C) where
+		C: BlockChain + CallContract + BlockProducer + Nonce + Sync,
+	{
+		if !self.options.pseudo_pending_block || self.sealing.lock().enabled {
+			return;
+		}
+
+		let (version, head) = {
+			let pseudo_pending = self.pseudo_pending.lock();
+			let up_to_date = pseudo_pending.block.as_ref().map_or(false, |&(version, _)| version == pseudo_pending.version);
+			let recent = pseudo_pending.built_at.map_or(false, |built_at| built_at.elapsed() < self.options.reseal_min_period);
+			if up_to_date || recent {
+				return;
+			}
+			(pseudo_pending.version, pseudo_pending.head)
+		};
+
+		trace_time!("pseudo_pending_block");
+		let chain_info = chain.chain_info();
+		let min_tx_gas: U256 = self.engine.schedule(chain_info.best_block_number).tx_gas.into();
+		let gas_limit = *chain.best_block_header().gas_limit();
+		let max_len = if min_tx_gas.is_zero() || self.options.infinite_pending_block {
+			usize::max_value()
+		} else {
+			cmp::min(gas_limit / min_tx_gas, u64::max_value().into()).as_u64() as usize
+		};
+		let transactions = self.transaction_queue.pending(
+			self.pool_client(chain),
+			pool::PendingSettings {
+				block_number: chain_info.best_block_number,
+				current_timestamp: chain_info.best_block_timestamp,
+				nonce_cap: None,
+				max_len,
+				ordering: miner::PendingOrdering::Priority,
+			}
+		).into_iter().map(|tx| tx.signed().clone()).collect();
+
+		let author = self.params.read().author_for(chain_info.best_block_number + 1);
+		let block = match self.block_template(chain, author, transactions) {
+			Ok((block, _)) => Arc::new(block),
+			Err(e) => {
+				warn!(target: "miner", "Preparing the pseudo-pending block failed: {:?}", e);
+				return;
+			}
+		};
+
+		let mut pseudo_pending = self.pseudo_pending.lock();
+		pseudo_pending.built_at = Some(Instant::now());
+		// a block built on top of a replaced best block is useless, one missing queue changes isn't
+		if pseudo_pending.head == head {
+			pseudo_pending.block = Some((version, block));
+		}
+	}
+
+	/// Marks the pseudo-pending block as out of date with the transaction queue, or drops it if the
+	/// best block changed.
+	fn invalidate_pseudo_pending(&self, new_best_block: bool) {
+		if self.options.pseudo_pending_block {
+			let mut pseudo_pending = self.pseudo_pending.lock();
+			pseudo_pending.version += 1;
+			if new_best_block {
+				pseudo_pending.head += 1;
+				pseudo_pending.block = None;
+			}
+		}
 	}
 
 	fn pool_client<'a, C: 'a>(&'a self, chain: &'a C) -> PoolClient<'a, C> where
@@ -419,7 +595,7 @@ impl Miner {
 			&*self.engine,
 			&*self.accounts,
 			self.service_transaction_checker.as_ref(),
-		)
+		).with_admission_policy(self.admission_policy.read().clone())
 	}
 
 	/// Prepares new block for sealing including top transactions from queue.
@@ -454,7 +630,7 @@ impl Miner {
 					let params = self.params.read().clone();
 
 					let block = match chain.prepare_open_block(
-						params.author,
+						params.author_for(chain_info.best_block_number + 1),
 						params.gas_range_target,
 						params.extra_data,
 					) {
@@ -491,6 +667,7 @@ impl Miner {
 
 		let mut tx_count = 0usize;
 		let mut skipped_transactions = 0usize;
+		let mut skipped: Vec<(H256, fn(&mut MinerStats) -> &mut u64)> = Vec::new();
 
 		let client = self.pool_client(chain);
 		let engine_params = self.engine.params();
@@ -523,14 +700,38 @@ impl Miner {
 		};
 
 		let block_start = Instant::now();
-		debug!(target: "miner", "Attempting to push {} transactions.", engine_txs.len() + queue_txs.len());
+		let engine_txs_count = engine_txs.len();
+		debug!(target: "miner", "Attempting to push {} transactions.", engine_txs_count + queue_txs.len());
 
-		for transaction in engine_txs.into_iter().chain(queue_txs.into_iter().map(|tx| tx.signed().clone())) {
+		let all_txs = engine_txs.into_iter().chain(queue_txs.into_iter().map(|tx| tx.signed().clone()));
+		for (index, transaction) in all_txs.enumerate() {
 			let start = Instant::now();
 
 			let hash = transaction.hash();
 			let sender = transaction.sender();
 
+			// Limits apply to transactions from the queue only.
+			if index >= engine_txs_count {
+				if self.options.block_min_gas_price.map_or(false, |min| transaction.gas_price < min) {
+					debug!(target: "miner", "Skipping transaction {:?} with gas price below the block minimum", hash);
+					skipped.push((hash, |stats| &mut stats.low_gas_price));
+					continue;
+				}
+				if self.options.block_max_transactions.map_or(false, |max| tx_count >= max) {
+					debug!(target: "miner", "Skipping transaction {:?}, block transactions limit reached", hash);
+					skipped.push((hash, |stats| &mut stats.too_many_transactions));
+					continue;
+				}
+				if let Some(percent) = self.options.tx_max_gas_percent {
+					let max_gas = *open_block.header.gas_limit() / 100 * U256::from(percent);
+					if transaction.gas > max_gas {
+						debug!(target: "miner", "Skipping transaction {:?} using more than {}% of the block gas limit", hash, percent);
+						skipped.push((hash, |stats| &mut stats.gas_share_exceeded));
+						continue;
+					}
+				}
+			}
+
 			// Re-verify transaction again vs current state.
 			let result = client.verify_for_pending_block(&transaction, &open_block.header)
 				.map_err(|e| e.into())
@@ -553,6 +754,7 @@ impl Miner {
 			match result {
 				Err(Error::Execution(ExecutionError::BlockGasLimitReached { gas_limit, gas_used, gas })) => {
 					debug!(target: "miner", "Skipping adding transaction to block because of gas limit: {:?} (limit: {:?}, used: {:?}, gas: {:?})", hash, gas_limit, gas_used, gas);
+					skipped.push((hash, |stats| &mut stats.block_gas_limit_reached));
 
 					// Penalize transaction if it's above current gas limit
 					if gas > gas_limit {
@@ -578,11 +780,13 @@ impl Miner {
 				// If there is erroneous state of transaction queue it will be fixed when next block is imported.
 				Err(Error::Execution(ExecutionError::InvalidNonce { expected, got })) => {
 					debug!(target: "miner", "Skipping adding transaction to block because of invalid nonce: {:?} (expected: {:?}, got: {:?})", hash, expected, got);
+					skipped.push((hash, |stats| &mut stats.invalid_nonce));
 				},
 				// already have transaction - ignore
 				Err(Error::Transaction(transaction::Error::AlreadyImported)) => {},
 				Err(Error::Transaction(transaction::Error::NotAllowed)) => {
 					not_allowed_transactions.insert(hash);
+					skipped.push((hash, |stats| &mut stats.not_allowed));
 					debug!(target: "miner", "Skipping non-allowed transaction for sender {:?}", hash);
 				},
 				Err(e) => {
@@ -591,6 +795,7 @@ impl Miner {
 						target: "miner", "Error adding transaction to block: number={}. transaction_hash={:?}, Error: {:?}", block_number, hash, e
 					);
 					invalid_transactions.insert(hash);
+					skipped.push((hash, |stats| &mut stats.invalid));
 				},
 				// imported ok
 				_ => tx_count += 1,
@@ -598,6 +803,7 @@ impl Miner {
 		}
 		let elapsed = block_start.elapsed();
 		debug!(target: "miner", "Pushed {} transactions in {} ms", tx_count, took_ms(&elapsed));
+		self.stats.lock().note(skipped);
 
 		let block = match open_block.close() {
 			Ok(block) => block,
@@ -616,6 +822,43 @@ impl Miner {
 		Some((block, original_work_hash))
 	}
 
+	/// Produces a closed, unsealed block on top of the current best block containing exactly
+	/// the given transactions (after any the engine adds itself), authored by `author`.
+	///
+	/// Neither the pending block nor the transaction queue are touched, so this can be called
+	/// concurrently with `update_sealing`. Transactions that fail verification or execution are
+	/// left out and returned alongside the block together with the reason.
+	pub fn block_template<C>(&self, chain: &C, author: Address, transactions: Vec<SignedTransaction>)
+		-> Result<(ClosedBlock, Vec<(H256, Error)>), Error>
+		where C: BlockChain + CallContract + BlockProducer + Nonce + Sync,
+	{
+		let params = self.params.read().clone();
+		let mut open_block = chain.prepare_open_block(author, params.gas_range_target, params.extra_data)?;
+		let engine_txs = self.engine.generate_engine_transactions(&open_block)?;
+
+		if self.options.infinite_pending_block {
+			open_block.remove_gas_limit();
+		}
+
+		let client = self.pool_client(chain);
+		let mut rejected = Vec::new();
+		for transaction in engine_txs.into_iter().chain(transactions) {
+			let hash = transaction.hash();
+			let result = client.verify_for_pending_block(&transaction, &open_block.header)
+				.map_err(|e| e.into())
+				.and_then(|_| {
+					open_block.push_transaction(transaction, None)
+				});
+
+			if let Err(e) = result {
+				debug!(target: "miner", "Leaving transaction {:?} out of block template: {:?}", hash, e);
+				rejected.push((hash, e));
+			}
+		}
+
+		Ok((open_block.close()?, rejected))
+	}
+
 	/// Returns `true` if we should create pending block even if some other conditions are not met.
 	///
 	/// In general we always seal iff:
@@ -912,9 +1155,29 @@ impl miner::MinerService for Miner {
 		self.params.write().extra_data = extra_data;
 	}
 
+	fn set_author_rotation(&self, authors: Vec<Address>) -> Result<(), Error> {
+		let mut params = self.params.write();
+		if params.sealer && !authors.is_empty() {
+			return Err(Error::Msg("Author rotation is not available while blocks are sealed by an engine signer.".into()));
+		}
+		params.author_rotation = authors;
+		Ok(())
+	}
+
 	fn set_author<T: Into<Option<Author>>>(&self, author: T) {
 		let author_opt = author.into();
-		self.params.write().author = author_opt.as_ref().map(Author::address).unwrap_or_default();
+		{
+			let mut params = self.params.write();
+			params.author = author_opt.as_ref().map(Author::address).unwrap_or_default();
+			params.sealer = match author_opt {
+				Some(Author::Sealer(_)) => true,
+				_ => false,
+			};
+			if params.sealer && !params.author_rotation.is_empty() {
+				warn!("Author rotation is disabled while blocks are sealed by an engine signer.");
+				params.author_rotation.clear();
+			}
+		}
 
 		match author_opt {
 			Some(Author::Sealer(signer)) => {
@@ -994,6 +1257,10 @@ impl miner::MinerService for Miner {
 			client,
 			transactions.into_iter().map(pool::verifier::Transaction::Unverified),
 		);
+		if results.iter().any(Result::is_ok) {
+			self.invalidate_pseudo_pending(false);
+			self.update_pseudo_pending(chain);
+		}
 
 		// --------------------------------------------------------------------------
 		// | NOTE Code below requires sealing locks.                                |
@@ -1020,6 +1287,10 @@ impl miner::MinerService for Miner {
 			client,
 			Some(pool::verifier::Transaction::Local(pending))
 		).pop().expect("one result returned per added transaction; one added => one result; qed");
+		if imported.is_ok() {
+			self.invalidate_pseudo_pending(false);
+			self.update_pseudo_pending(chain);
+		}
 
 		// --------------------------------------------------------------------------
 		// | NOTE Code below requires sealing locks.                                |
@@ -1067,6 +1338,10 @@ impl miner::MinerService for Miner {
 		self.transaction_queue.all_transaction_hashes()
 	}
 
+	fn queued_transactions_from_sender(&self, address: &Address) -> Vec<Arc<VerifiedTransaction>> {
+		self.transaction_queue.transactions_from_sender(address)
+	}
+
 	fn pending_transaction_hashes<C>(&self, chain: &C) -> BTreeSet<H256> where
 		C: ChainInfo + Sync,
 	{
@@ -1186,15 +1461,23 @@ impl miner::MinerService for Miner {
 	}
 
 	fn remove_transaction(&self, hash: &H256) -> Option<Arc<VerifiedTransaction>> {
-		self.transaction_queue.remove(::std::iter::once(hash), false)
+		let removed = self.transaction_queue.remove(::std::iter::once(hash), false)
 			.pop()
-			.expect("remove() returns one result per hash; one hash passed; qed")
+			.expect("remove() returns one result per hash; one hash passed; qed");
+		if removed.is_some() {
+			self.invalidate_pseudo_pending(false);
+		}
+		removed
 	}
 
 	fn queue_status(&self) -> QueueStatus {
 		self.transaction_queue.status()
 	}
 
+	fn stats(&self) -> MinerStats {
+		self.stats.lock().totals.clone()
+	}
+
 	fn pending_receipts(&self, best_block: BlockNumber) -> Option<Vec<RichReceipt>> {
 		self.map_existing_pending_block(|pending| {
 			let receipts = &pending.receipts;
@@ -1360,6 +1643,7 @@ impl miner::MinerService for Miner {
 		if has_new_best_block {
 			// Clear nonce cache
 			self.nonce_cache.clear();
+			self.invalidate_pseudo_pending(true);
 		}
 
 		// First update gas limit in transaction queue and minimal gas price.
@@ -1400,6 +1684,8 @@ impl miner::MinerService for Miner {
 		}
 
 		if has_new_best_block {
+			self.update_pseudo_pending(chain);
+
 			// Make sure to cull transactions after we update sealing.
 			// Not culling won't lead to old transactions being added to the block
 			// (thanks to Ready), but culling can take significant amount of time,
@@ -1457,6 +1743,10 @@ impl miner::MinerService for Miner {
 		self.map_existing_pending_block(|b| b.state.clone(), latest_block_number)
 	}
 
+	fn pending_state_and_header(&self, latest_block_number: BlockNumber) -> Option<(Self::State, Header)> {
+		self.map_existing_pending_block(|b| (b.state.clone(), b.header.clone()), latest_block_number)
+	}
+
 	fn pending_block_header(&self, latest_block_number: BlockNumber) -> Option<Header> {
 		self.map_existing_pending_block(|b| b.header.clone(), latest_block_number)
 	}
@@ -1489,6 +1779,7 @@ mod tests {
 	use client_traits::ChainInfo;
 	use client::ImportSealedBlock;
 	use miner::{MinerService, PendingOrdering, filter_options::FilterOperator};
+	use ethcore_miner::pool::client::Admission;
 	use test_helpers::{
 		generate_dummy_client, generate_dummy_client_with_spec, TestBlockChainClient, EachBlockWith
 	};
@@ -1547,6 +1838,7 @@ mod tests {
 				tx_queue_penalization: Penalization::Disabled,
 				tx_queue_strategy: PrioritizationStrategy::GasPriceOnly,
 				tx_queue_no_unfamiliar_locals: false,
+				tx_queue_max_size_per_sender: None,
 				refuse_service_transactions: false,
 				pool_limits: Default::default(),
 				pool_verification_options: pool::verifier::Options {
@@ -1555,6 +1847,10 @@ mod tests {
 					tx_gas_limit: U256::max_value(),
 					no_early_reject: false,
 				},
+				block_min_gas_price: None,
+				block_max_transactions: None,
+				tx_max_gas_percent: None,
+				pseudo_pending_block: false,
 			},
 			GasPricer::new_fixed(0u64.into()),
 			&spec::new_test(),
@@ -1580,6 +1876,28 @@ mod tests {
 		}.sign(keypair.secret(), Some(chain_id))
 	}
 
+	#[test]
+	fn should_build_block_template_without_touching_pending_block() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let author = Address::from_low_u64_be(42);
+		let good = transaction();
+		let bad = transaction_with_chain_id(TEST_CHAIN_ID + 1);
+
+		// when
+		let (block, rejected) = miner.block_template(&client, author, vec![good.clone(), bad.clone()]).unwrap();
+
+		// then
+		assert_eq!(block.header.author(), &author);
+		assert_eq!(block.transactions.len(), 1);
+		assert_eq!(block.transactions[0].hash(), good.hash());
+		assert_eq!(rejected.len(), 1);
+		assert_eq!(rejected[0].0, bad.hash());
+		assert!(miner.pending_block(0).is_none());
+		assert_eq!(miner.queued_transactions().len(), 0);
+	}
+
 	#[test]
 	fn should_make_pending_block_when_importing_own_transaction() {
 		// given
@@ -1599,6 +1917,99 @@ mod tests {
 		assert_eq!(miner.prepare_pending_block(&client), BlockPreparationStatus::NotPrepared);
 	}
 
+	#[test]
+	fn should_apply_queued_local_transactions_to_pending_account() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		let keypair = Random.generate().unwrap();
+		let sender = keypair.address();
+		client.set_balance(sender, 10.into());
+		for nonce in &[0u64, 1, 3] {
+			let transaction = Transaction {
+				action: Action::Call(Address::from_low_u64_be(5)),
+				value: U256::from(2),
+				data: vec![],
+				gas: U256::from(21_000),
+				gas_price: U256::zero(),
+				nonce: U256::from(*nonce),
+			}.sign(keypair.secret(), Some(TEST_CHAIN_ID));
+			miner.import_own_transaction(&client, PendingTransaction::new(transaction, None)).unwrap();
+		}
+
+		// then
+		assert_eq!(miner.local_pending_account(&sender, 0.into(), 10.into()), (2.into(), 6.into()));
+		assert_eq!(miner.local_pending_account(&sender, 1.into(), 8.into()), (2.into(), 6.into()));
+		assert_eq!(miner.local_pending_account(&sender, 0.into(), 3.into()), (1.into(), 1.into()));
+		assert_eq!(miner.local_pending_account(&Address::from_low_u64_be(5), 7.into(), 1.into()), (7.into(), 1.into()));
+	}
+
+	#[test]
+	fn should_leave_out_transactions_below_block_min_gas_price() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = Miner::new(
+			MinerOptions {
+				block_min_gas_price: Some(1.into()),
+				..miner().options
+			},
+			GasPricer::new_fixed(0u64.into()),
+			&spec::new_test(),
+			::std::collections::HashSet::new(),
+		);
+
+		// when
+		let res = miner.import_own_transaction(&client, PendingTransaction::new(transaction(), None));
+
+		// then
+		assert_eq!(res.unwrap(), ());
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+		assert_eq!(miner.ready_transactions(&client, 10, PendingOrdering::Priority).len(), 1);
+		assert_eq!(miner.stats(), MinerStats { low_gas_price: 1, ..Default::default() });
+
+		// left out again of the next block, but counted once
+		assert!(miner.prepare_block(&client).is_some());
+		assert_eq!(miner.stats(), MinerStats { low_gas_price: 1, ..Default::default() });
+	}
+
+	#[test]
+	fn should_limit_gas_share_of_single_transaction() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = Miner::new(
+			MinerOptions {
+				tx_max_gas_percent: Some(1),
+				..miner().options
+			},
+			GasPricer::new_fixed(0u64.into()),
+			&spec::new_test(),
+			::std::collections::HashSet::new(),
+		);
+
+		// when
+		miner.import_own_transaction(&client, PendingTransaction::new(transaction(), None)).unwrap();
+
+		// then
+		assert_eq!(miner.pending_transactions(0).unwrap().len(), 0);
+		assert_eq!(miner.stats().gas_share_exceeded, 1);
+	}
+
+	#[test]
+	fn should_return_pending_state_and_header_of_the_same_block() {
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		assert!(miner.pending_state_and_header(0).is_none());
+
+		// when
+		miner.import_own_transaction(&client, PendingTransaction::new(transaction(), None)).unwrap();
+
+		// then
+		let (state, header) = miner.pending_state_and_header(0).unwrap();
+		assert_eq!(state.root(), header.state_root());
+		assert_eq!(Some(header), miner.pending_block_header(0));
+	}
+
 	#[test]
 	fn should_not_return_stale_work_packages() {
 		// given
@@ -1626,6 +2037,75 @@ mod tests {
 		assert_eq!(miner.prepare_pending_block(&*client), BlockPreparationStatus::NotPrepared);
 	}
 
+	#[test]
+	fn should_answer_pending_queries_from_pseudo_pending_block() {
+		// given
+		let spec = spec::new_test();
+		let miner = Miner::new(
+			MinerOptions {
+				pseudo_pending_block: true,
+				reseal_min_period: Duration::from_secs(0),
+				..miner().options
+			},
+			GasPricer::new_fixed(0u64.into()),
+			&spec,
+			::std::collections::HashSet::new(),
+		);
+		let client = generate_dummy_client(2);
+		assert!(miner.pending_transactions(2).is_none());
+		miner.update_pseudo_pending(&*client);
+		assert_eq!(miner.pending_transactions(2).unwrap().len(), 0);
+
+		// when
+		let transaction = transaction_with_chain_id(spec.chain_id());
+		miner.import_external_transactions(&*client, vec![transaction.clone().into()]).pop().unwrap().unwrap();
+
+		// then
+		let header = miner.pending_block_header(2).unwrap();
+		assert_eq!(header.number(), 3);
+		assert_eq!(miner.pending_transactions(2).unwrap()[0].hash(), transaction.hash());
+		assert_eq!(miner.pending_state(2).unwrap().nonce(&transaction.sender()).unwrap(), 1.into());
+	}
+
+	#[test]
+	fn should_answer_pending_queries_only_when_pseudo_pending_is_enabled() {
+		// given
+		let spec = spec::new_test();
+		let miner = Miner::new(miner().options, GasPricer::new_fixed(0u64.into()), &spec, ::std::collections::HashSet::new());
+		let client = generate_dummy_client(2);
+
+		// when
+		miner.import_external_transactions(&*client, vec![transaction_with_chain_id(spec.chain_id()).into()]).pop().unwrap().unwrap();
+		miner.update_pseudo_pending(&*client);
+
+		// then
+		assert!(miner.pending_state(2).is_none());
+	}
+
+	#[test]
+	fn should_rebuild_pseudo_pending_block_at_most_once_per_reseal_min_period() {
+		// given
+		let spec = spec::new_test();
+		let miner = Miner::new(
+			MinerOptions {
+				pseudo_pending_block: true,
+				reseal_min_period: Duration::from_secs(3600),
+				..miner().options
+			},
+			GasPricer::new_fixed(0u64.into()),
+			&spec,
+			::std::collections::HashSet::new(),
+		);
+		let client = generate_dummy_client(2);
+		miner.update_pseudo_pending(&*client);
+
+		// when
+		miner.import_external_transactions(&*client, vec![transaction_with_chain_id(spec.chain_id()).into()]).pop().unwrap().unwrap();
+
+		// then
+		assert_eq!(miner.pending_transactions(2).unwrap().len(), 0);
+	}
+
 	#[test]
 	fn should_not_use_pending_block_if_best_block_is_higher() {
 		// given
@@ -1666,6 +2146,31 @@ mod tests {
 		assert_eq!(miner.ready_transactions(&client, 10, PendingOrdering::Priority).len(), 1);
 	}
 
+	#[test]
+	fn should_apply_transaction_admission_policy() {
+		struct DenyCreate;
+		impl TransactionAdmissionPolicy for DenyCreate {
+			fn admit(&self, tx: &SignedTransaction) -> Admission {
+				match tx.action {
+					Action::Create => Admission::Reject(transaction::Error::CodeBanned),
+					Action::Call(_) => Admission::Accept,
+				}
+			}
+		}
+
+		// given
+		let client = TestBlockChainClient::default();
+		let miner = miner();
+		miner.set_transaction_admission_policy(Arc::new(DenyCreate));
+
+		// when
+		let res = miner.import_external_transactions(&client, vec![transaction().into()]).pop().unwrap();
+
+		// then
+		assert_eq!(res, Err(transaction::Error::CodeBanned));
+		assert_eq!(miner.queue_status().status.transaction_count, 0);
+	}
+
 	#[test]
 	fn should_treat_unfamiliar_locals_selectively() {
 		// given
@@ -1822,6 +2327,26 @@ mod tests {
 		assert!(client.engine().sign(msg).is_ok());
 	}
 
+	#[test]
+	fn should_not_rotate_authors_of_sealed_blocks() {
+		let spec = spec::new_test_round;
+		let tap = Arc::new(AccountProvider::transient_provider());
+		let addr = tap.insert_account(keccak("1").into(), &"".into()).unwrap();
+		let client = generate_dummy_client_with_spec(spec);
+		let miner = client.miner();
+		miner.set_author_rotation(vec![Address::from_low_u64_be(1), Address::from_low_u64_be(2)]).unwrap();
+		assert_eq!(miner.authoring_params().author_for(1), Address::from_low_u64_be(2));
+
+		// setting an engine signer drops the rotation
+		miner.set_author(Author::Sealer(Box::new((tap.clone(), addr, "".into()))));
+		assert_eq!(miner.authoring_params().author_for(1), addr);
+		assert!(miner.authoring_params().author_rotation.is_empty());
+
+		// and it can't be set again while sealing
+		assert!(miner.set_author_rotation(vec![Address::from_low_u64_be(1)]).is_err());
+		assert_eq!(miner.authoring_params().author_for(1), addr);
+	}
+
 	#[test]
 	fn should_mine_if_internal_sealing_is_enabled() {
 		let spec = spec::new_instant();
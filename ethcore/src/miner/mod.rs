@@ -25,7 +25,7 @@ pub mod pool_client;
 #[cfg(feature = "stratum")]
 pub mod stratum;
 
-pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams, Author};
+pub use self::miner::{Miner, MinerOptions, MinerStats, Penalization, PendingSet, AuthoringParams, Author};
 pub use self::filter_options::FilterOptions;
 pub use ethcore_miner::local_accounts::LocalAccounts;
 pub use ethcore_miner::pool::PendingOrdering;
@@ -112,6 +112,13 @@ pub trait MinerService : Send + Sync {
 	/// Get `Some` `clone()` of the current pending block's state or `None` if we're not sealing.
 	fn pending_state(&self, latest_block_number: BlockNumber) -> Option<Self::State>;
 
+	/// Get `Some` `clone()` of the current pending block's state and header or `None` if we're not sealing.
+	///
+	/// Both are taken from the same pending block, so unlike separate calls to `pending_state` and
+	/// `pending_block_header` the result can't straddle a sealing update. The returned state is a
+	/// copy-on-write snapshot and is not affected by later updates of the pending block.
+	fn pending_state_and_header(&self, latest_block_number: BlockNumber) -> Option<(Self::State, Header)>;
+
 	/// Get `Some` `clone()` of the current pending block header or `None` if we're not sealing.
 	fn pending_block_header(&self, latest_block_number: BlockNumber) -> Option<Header>;
 
@@ -137,6 +144,12 @@ pub trait MinerService : Send + Sync {
 	/// On chains where sealing is done externally (e.g. PoW) we provide only reward beneficiary.
 	fn set_author<T: Into<Option<Author>>>(&self, author: T);
 
+	/// Set a list of block authors to rotate through, one per block.
+	///
+	/// An empty list disables rotation and the author set by `set_author` is used.
+	/// Fails if blocks are sealed by an engine signer, since the signer has to author them.
+	fn set_author_rotation(&self, authors: Vec<Address>) -> Result<(), Error>;
+
 	// Transaction Pool
 
 	/// Imports transactions to transaction queue.
@@ -202,6 +215,39 @@ pub trait MinerService : Send + Sync {
 	/// Get a list of all transaction hashes in the pool (some of them might not be ready for inclusion yet).
 	fn queued_transaction_hashes(&self) -> Vec<H256>;
 
+	/// Get a list of all transactions in the pool from given sender, in nonce order.
+	fn queued_transactions_from_sender(&self, address: &Address) -> Vec<Arc<VerifiedTransaction>> {
+		let mut transactions = self.queued_transactions()
+			.into_iter()
+			.filter(|tx| tx.signed().sender() == *address)
+			.collect::<Vec<_>>();
+		transactions.sort_by_key(|tx| tx.signed().nonce);
+		transactions
+	}
+
+	/// Returns `nonce` and `balance` of `address` after its local transactions waiting in the pool are
+	/// applied on top of them, in nonce order.
+	///
+	/// Stops at the first nonce gap or at the first transaction whose maximal cost
+	/// (`value + gas * gas_price`) exceeds the remaining balance.
+	fn local_pending_account(&self, address: &Address, nonce: U256, balance: U256) -> (U256, U256) {
+		let local = self.queued_transactions_from_sender(address)
+			.into_iter()
+			.filter(|tx| tx.is_local() && tx.signed().nonce >= nonce);
+
+		let (mut nonce, mut balance) = (nonce, balance);
+		for tx in local {
+			let tx = tx.signed();
+			let cost = tx.value.saturating_add(tx.gas.saturating_mul(tx.gas_price));
+			if tx.nonce != nonce || cost > balance {
+				break;
+			}
+			nonce += U256::one();
+			balance -= cost;
+		}
+		(nonce, balance)
+	}
+
 	/// Get a list of local transactions with statuses.
 	fn local_transactions(&self) -> BTreeMap<H256, local_transactions::Status>;
 
@@ -210,6 +256,9 @@ pub trait MinerService : Send + Sync {
 	/// Status includes verification thresholds and current pool utilization and limits.
 	fn queue_status(&self) -> QueueStatus;
 
+	/// Get the number of transactions left out of authored blocks, by reason.
+	fn stats(&self) -> MinerStats;
+
 	// Misc
 
 	/// Suggested gas price.
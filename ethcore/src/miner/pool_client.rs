@@ -25,7 +25,7 @@ use std::{
 use ethereum_types::{H256, U256, Address};
 use ethcore_miner::local_accounts::LocalAccounts;
 use ethcore_miner::pool;
-use ethcore_miner::pool::client::NonceClient;
+use ethcore_miner::pool::client::{Admission, NonceClient, TransactionAdmissionPolicy};
 use ethcore_miner::service_transaction_checker::ServiceTransactionChecker;
 use types::transaction::{
 	self,
@@ -79,6 +79,7 @@ pub struct PoolClient<'a, C: 'a> {
 	accounts: &'a dyn LocalAccounts,
 	best_block_header: Header,
 	service_transaction_checker: Option<&'a ServiceTransactionChecker>,
+	admission_policy: Option<Arc<dyn TransactionAdmissionPolicy>>,
 }
 
 impl<'a, C: 'a> Clone for PoolClient<'a, C> {
@@ -90,6 +91,7 @@ impl<'a, C: 'a> Clone for PoolClient<'a, C> {
 			accounts: self.accounts.clone(),
 			best_block_header: self.best_block_header.clone(),
 			service_transaction_checker: self.service_transaction_checker.clone(),
+			admission_policy: self.admission_policy.clone(),
 		}
 	}
 }
@@ -113,9 +115,16 @@ impl<'a, C: 'a> PoolClient<'a, C> where
 			accounts,
 			best_block_header,
 			service_transaction_checker,
+			admission_policy: None,
 		}
 	}
 
+	/// Check transactions against given admission policy. All transactions are admitted by default.
+	pub fn with_admission_policy(mut self, policy: Arc<dyn TransactionAdmissionPolicy>) -> Self {
+		self.admission_policy = Some(policy);
+		self
+	}
+
 	/// Verifies transaction against its block (before its import into this block)
 	/// Also Verifies if signed transaction is executable.
 	///
@@ -178,6 +187,13 @@ impl<'a, C: 'a> pool::client::Client for PoolClient<'a, C> where
 		}
 	}
 
+	fn admission(&self, tx: &SignedTransaction) -> Admission {
+		match self.admission_policy {
+			Some(ref policy) => policy.admit(tx),
+			None => Admission::Accept,
+		}
+	}
+
 	fn decode_transaction(&self, transaction: &[u8]) -> Result<UnverifiedTransaction, transaction::Error> {
 		self.engine.decode_transaction(transaction)
 	}
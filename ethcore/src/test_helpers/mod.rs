@@ -24,7 +24,7 @@ pub use evm::CreateContractAddress;
 /// Re-export for tests only
 pub use trie::TrieSpec;
 /// Re-export for tests only
-pub use self::test_client::{TestBlockChainClient, EachBlockWith, TestState};
+pub use self::test_client::{TestBlockChainClient, EachBlockWith, ChainStep, TestState};
 /// Re-export for tests only
 pub use self::evm_test_client::{EvmTestClient, EvmTestError, TransactErr, TransactSuccess};
 
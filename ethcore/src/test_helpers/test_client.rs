@@ -51,7 +51,10 @@ use types::{
 	header::Header,
 	log_entry::LocalizedLogEntry,
 	pruning_info::PruningInfo,
+	disk_usage::DiskUsage,
 	receipt::{Receipt, LocalizedReceipt, TransactionOutcome},
+	reorg::Reorg,
+	state_override::StateOverride,
 	view,
 	views::BlockView,
 	verification::Unverified,
@@ -61,6 +64,7 @@ use types::{
 	verification::VerificationQueueInfo as BlockQueueInfo,
 };
 use vm::{Schedule, LastHashes};
+use evm::interpreter::InstructionObserver;
 
 use block::{OpenBlock, SealedBlock, ClosedBlock};
 use call_contract::CallContract;
@@ -72,7 +76,7 @@ use client::{
 use client_traits::{
 	BlockInfo, Nonce, Balance, ChainInfo, TransactionInfo, BlockChainClient, ImportBlock,
 	AccountData, BlockChain, IoClient, BadBlocks, ScheduleInfo, StateClient, ProvingBlockChainClient,
-	StateOrBlock, ForceUpdateSealing, TransactionRequest
+	StateOrBlock, ForceUpdateSealing, TransactionRequest, ReplayedBlock
 };
 use engine::Engine;
 use machine::executed::Executed;
@@ -129,8 +133,14 @@ pub struct TestBlockChainClient {
 	pub traces: RwLock<Option<Vec<LocalizedTrace>>>,
 	/// Pruning history size to report.
 	pub history: RwLock<Option<u64>>,
+	/// Database sizes to report.
+	pub disk_usage: RwLock<Option<DiskUsage>>,
+	/// Reorgs to report, newest first.
+	pub reorgs: RwLock<Vec<Reorg>>,
 	/// Is disabled
 	pub disabled: AtomicBool,
+	/// Total difficulty of the network, as last noted by sync.
+	pub network_difficulty: RwLock<Option<U256>>,
 }
 
 /// Used for generating test client blocks.
@@ -148,6 +158,17 @@ pub enum EachBlockWith {
 	UncleAndTransaction
 }
 
+/// Step of a chain script, see `TestBlockChainClient::run_script`.
+#[derive(Clone, Copy)]
+pub enum ChainStep {
+	/// Extend the best chain by a number of blocks.
+	Extend(usize, EachBlockWith),
+	/// Force a reorganization of the given depth, see `TestBlockChainClient::reorg`.
+	Reorg(usize, EachBlockWith),
+	/// Mine the transactions queued in the miner, see `TestBlockChainClient::mine_block`.
+	Mine,
+}
+
 impl Default for TestBlockChainClient {
 	fn default() -> Self {
 		TestBlockChainClient::new()
@@ -198,8 +219,11 @@ impl TestBlockChainClient {
 			first_block: RwLock::new(None),
 			traces: RwLock::new(None),
 			history: RwLock::new(None),
+			disk_usage: RwLock::new(None),
+			reorgs: RwLock::new(Vec::new()),
 			disabled: AtomicBool::new(false),
 			error_on_logs: RwLock::new(None),
+			network_difficulty: RwLock::new(None),
 		};
 
 		// insert genesis hash.
@@ -264,23 +288,23 @@ impl TestBlockChainClient {
 	pub fn add_block<F>(&self, with: EachBlockWith, hook: F)
 		where F: Fn(Header) -> Header
 	{
+		let parent_hash = self.last_hash.read().clone();
 		let n = self.numbers.read().len();
+		self.add_block_on(parent_hash, n, with, hook);
+	}
 
-		let mut header = Header::new();
-		header.set_difficulty(From::from(n));
-		header.set_parent_hash(self.last_hash.read().clone());
-		header.set_number(n as BlockNumber);
-		header.set_gas_limit(U256::from(1_000_000));
-		header.set_extra_data(self.extra_data.clone());
-
-		header = hook(header);
+	/// Add a block with given parent and number. Only becomes the best block if it extends the chain.
+	fn add_block_on<F>(&self, parent_hash: H256, n: usize, with: EachBlockWith, hook: F) -> H256
+		where F: Fn(Header) -> Header
+	{
+		let mut header = hook(self.new_header(parent_hash, n));
 
 		let uncles = match with {
 			EachBlockWith::Uncle | EachBlockWith::UncleAndTransaction => {
 				let mut uncles = RlpStream::new_list(1);
 				let mut uncle_header = Header::new();
 				uncle_header.set_difficulty(From::from(n));
-				uncle_header.set_parent_hash(self.last_hash.read().clone());
+				uncle_header.set_parent_hash(parent_hash);
 				uncle_header.set_number(n as BlockNumber);
 				uncles.append(&uncle_header);
 				header.set_uncles_hash(keccak(uncles.as_raw()));
@@ -319,12 +343,64 @@ impl TestBlockChainClient {
 			_ => ::rlp::EMPTY_LIST_RLP.to_vec()
 		};
 
+		self.insert_block(&header, &txs, uncles.as_raw())
+	}
+
+	fn new_header(&self, parent_hash: H256, n: usize) -> Header {
+		let mut header = Header::new();
+		header.set_difficulty(From::from(n));
+		header.set_parent_hash(parent_hash);
+		header.set_number(n as BlockNumber);
+		header.set_gas_limit(U256::from(1_000_000));
+		header.set_extra_data(self.extra_data.clone());
+		header
+	}
+
+	fn insert_block(&self, header: &Header, txs: &[u8], uncles: &[u8]) -> H256 {
 		let mut rlp = RlpStream::new_list(3);
-		rlp.append(&header);
-		rlp.append_raw(&txs, 1);
-		rlp.append_raw(uncles.as_raw(), 1);
+		rlp.append(header);
+		rlp.append_raw(txs, 1);
+		rlp.append_raw(uncles, 1);
 		let unverified = Unverified::from_rlp(rlp.out()).unwrap();
-		self.import_block(unverified).unwrap();
+		self.import_block(unverified).unwrap()
+	}
+
+	/// Mine a new best block out of the transactions ready in the miner's queue, taken in
+	/// priority order. The block only depends on the queue, so the same transactions always
+	/// give the same block. Mined transactions leave the queue and their senders' nonces are
+	/// bumped. Returns the hash of the new block.
+	pub fn mine_block(&self) -> H256 {
+		let transactions = self.miner.ready_transactions(self, usize::max_value(), miner::PendingOrdering::Priority);
+		let mut txs = RlpStream::new_list(transactions.len());
+		for tx in &transactions {
+			let tx = tx.signed();
+			txs.append(tx);
+			self.nonces.write().insert(tx.sender(), tx.nonce + 1);
+			self.miner.remove_transaction(&tx.hash());
+		}
+
+		let parent_hash = *self.last_hash.read();
+		let n = self.numbers.read().len();
+		self.insert_block(&self.new_header(parent_hash, n), &txs.out(), &::rlp::EMPTY_LIST_RLP)
+	}
+
+	/// Build the chain by running the steps of `script` in order. Returns hashes of the
+	/// blocks that were added, in order.
+	pub fn run_script(&self, script: &[ChainStep]) -> Vec<H256> {
+		let mut added = Vec::new();
+		for step in script {
+			match *step {
+				ChainStep::Extend(count, with) => {
+					for _ in 0..count {
+						self.add_block(with, |header| header);
+						added.push(*self.last_hash.read());
+					}
+				},
+				ChainStep::Reorg(depth, with) => added.extend(self.reorg(depth, with)),
+				ChainStep::Mine => added.push(self.mine_block()),
+			}
+		}
+		added
 	}
 
 	/// Add a sequence of blocks to test client.
@@ -334,6 +410,24 @@ impl TestBlockChainClient {
 		}
 	}
 
+	/// Force a reorganization: replace the last `depth` blocks by `depth + 1` new ones,
+	/// forking off the block `depth` below the best one. Returns hashes of the enacted blocks.
+	pub fn reorg(&self, depth: usize, with: EachBlockWith) -> Vec<H256> {
+		let best = self.numbers.read().len() - 1;
+		assert!(depth <= best, "can't reorg past the genesis block");
+		let mut parent_hash = self.numbers.read()[&(best - depth)];
+		let mut enacted = Vec::new();
+		for n in (best - depth + 1)..=(best + 1) {
+			// different extra data so that replaced blocks get new hashes
+			parent_hash = self.add_block_on(parent_hash, n, with, |mut header| {
+				header.set_extra_data(b"reorg".to_vec());
+				header
+			});
+			enacted.push(parent_hash);
+		}
+		enacted
+	}
+
 	/// Make a bad block by setting invalid parent hash.
 	pub fn corrupt_block_parent(&self, n: BlockNumber) {
 		let hash = self.block_hash(BlockId::Number(n)).unwrap();
@@ -488,7 +582,7 @@ impl AccountData for TestBlockChainClient {}
 
 impl ChainInfo for TestBlockChainClient {
 	fn chain_info(&self) -> BlockChainInfo {
-		let number = self.blocks.read().len() as BlockNumber - 1;
+		let number = self.numbers.read().len() as BlockNumber - 1;
 		BlockChainInfo {
 			total_difficulty: *self.difficulty.read(),
 			pending_total_difficulty: *self.difficulty.read(),
@@ -612,14 +706,18 @@ impl Call for TestBlockChainClient {
 	// State will not be used by test client anyway, since all methods that accept state are mocked
 	type State = TestState;
 
-	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _state: &mut Self::State, _header: &Header) -> Result<Executed, CallError> {
+	fn call(&self, _t: &SignedTransaction, _analytics: CallAnalytics, _state: &mut Self::State, _header: &Header, _state_override: Option<&StateOverride>) -> Result<Executed, CallError> {
 		self.execution_result.read().clone().unwrap()
 	}
 
+	fn call_observed(&self, t: &SignedTransaction, state: &mut Self::State, header: &Header, _observer: Arc<dyn InstructionObserver>) -> Result<Executed, CallError> {
+		self.call(t, Default::default(), state, header, None)
+	}
+
 	fn call_many(&self, txs: &[(SignedTransaction, CallAnalytics)], state: &mut Self::State, header: &Header) -> Result<Vec<Executed>, CallError> {
 		let mut res = Vec::with_capacity(txs.len());
 		for &(ref tx, analytics) in txs {
-			res.push(self.call(tx, analytics, state, header)?);
+			res.push(self.call(tx, analytics, state, header, None)?);
 		}
 		Ok(res)
 	}
@@ -670,6 +768,8 @@ impl BadBlocks for TestBlockChainClient {
 			}, "Invalid block".into())
 		]
 	}
+
+	fn clear_bad_blocks(&self) {}
 }
 
 impl BlockChainClient for TestBlockChainClient {
@@ -700,6 +800,15 @@ impl BlockChainClient for TestBlockChainClient {
 		))
 	}
 
+	fn replay_block(&self, block: BlockId, analytics: CallAnalytics) -> Result<ReplayedBlock, CallError> {
+		let state_root = self.block_header(block).ok_or(CallError::StatePruned)?.state_root();
+		Ok(ReplayedBlock {
+			transactions: self.replay_block_transactions(block, analytics)?.collect(),
+			state_root,
+			expected_state_root: state_root,
+		})
+	}
+
 	fn block_total_difficulty(&self, _id: BlockId) -> Option<U256> {
 		Some(U256::zero())
 	}
@@ -738,16 +847,29 @@ impl BlockChainClient for TestBlockChainClient {
 	fn list_storage(&self, _id: BlockId, _account: &Address, _after: Option<&H256>, _count: Option<u64>) -> Option<Vec<H256>> {
 		None
 	}
+
+	fn list_account_entries(&self, _id: BlockId, _after: Option<&[u8]>, _count: u64) -> Option<Vec<(Bytes, BasicAccount)>> {
+		None
+	}
+
+	fn pin_state(&self, _number: BlockNumber) {}
+
+	fn unpin_state(&self, _number: BlockNumber) {}
+
 	fn transaction(&self, _id: TransactionId) -> Option<LocalizedTransaction> {
 		None	// Simple default.
 	}
 
-	fn uncle(&self, _id: UncleId) -> Option<encoded::Header> {
-		None	// Simple default.
+	fn uncle(&self, id: UncleId) -> Option<encoded::Header> {
+		self.block_body(id.block)
+			.and_then(|body| body.view().uncle_rlp_at(id.position))
+			.map(encoded::Header::new)
 	}
 
-	fn uncle_extra_info(&self, _id: UncleId) -> Option<BTreeMap<String, String>> {
-		None
+	fn uncle_extra_info(&self, id: UncleId) -> Option<BTreeMap<String, String>> {
+		self.uncle(id)
+			.and_then(|header| header.decode().ok())
+			.map(|header| self.spec.engine.extra_info(&header))
 	}
 
 	fn transaction_receipt(&self, id: TransactionId) -> Option<LocalizedReceipt> {
@@ -765,7 +887,8 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 
 		let mut logs = self.logs.read().clone();
-		let len = logs.len();
+		let len = logs.len().saturating_sub(filter.offset.unwrap_or(0));
+		logs.truncate(len);
 		Ok(match filter.limit {
 			Some(limit) if limit <= len => logs.split_off(len - limit),
 			_ => logs,
@@ -804,7 +927,7 @@ impl BlockChainClient for TestBlockChainClient {
 
 	fn block_status(&self, id: BlockId) -> BlockStatus {
 		match id {
-			BlockId::Number(number) if (number as usize) < self.blocks.read().len() => BlockStatus::InChain,
+			BlockId::Number(number) if (number as usize) < self.numbers.read().len() => BlockStatus::InChain,
 			BlockId::Hash(ref hash) if self.blocks.read().get(hash).is_some() => BlockStatus::InChain,
 			BlockId::Latest | BlockId::Earliest => BlockStatus::InChain,
 			_ => BlockStatus::Unknown,
@@ -898,6 +1021,10 @@ impl BlockChainClient for TestBlockChainClient {
 
 	fn set_mode(&self, _: Mode) { unimplemented!(); }
 
+	fn note_network_difficulty(&self, total_difficulty: U256) {
+		*self.network_difficulty.write() = Some(total_difficulty);
+	}
+
 	fn spec_name(&self) -> String { "foundation".into() }
 
 	fn set_spec_name(&self, _: String) -> Result<(), ()> { unimplemented!(); }
@@ -912,6 +1039,18 @@ impl BlockChainClient for TestBlockChainClient {
 		}
 	}
 
+	fn pruning_mode(&self) -> &'static str {
+		if self.history.read().is_some() { "fast" } else { "archive" }
+	}
+
+	fn disk_usage(&self) -> Option<DiskUsage> {
+		self.disk_usage.read().clone()
+	}
+
+	fn reorg_history(&self, count: usize) -> Vec<Reorg> {
+		self.reorgs.read().iter().take(count).cloned().collect()
+	}
+
 	fn create_transaction(&self, TransactionRequest { action, data, gas, gas_price, nonce }: TransactionRequest)
 		-> Result<SignedTransaction, transaction::Error>
 	{
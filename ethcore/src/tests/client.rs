@@ -18,22 +18,25 @@ use std::str::{FromStr, from_utf8};
 use std::sync::Arc;
 
 use account_state::state::StateInfo;
-use ethereum_types::{U256, Address};
+use ethereum_types::{U256, H256, Address};
 use parity_crypto::publickey::KeyPair;
 use hash::keccak;
 use io::IoChannel;
 use tempdir::TempDir;
 use types::{
+	account_diff::Diff,
+	call_analytics::CallAnalytics,
 	data_format::DataFormat,
-	ids::BlockId,
+	ids::{BlockId, TransactionId},
 	transaction::{PendingTransaction, Transaction, Action, Condition},
 	filter::Filter,
+	state_override::{AccountOverride, StateOverride},
 	verification::Unverified,
 	view,
 	views::BlockView,
 };
 
-use client::{Client, ClientConfig, PrepareOpenBlock, ImportSealedBlock};
+use client::{Call, Client, ClientConfig, PrepareOpenBlock, ImportSealedBlock};
 use client_traits::{
 	BlockInfo, BlockChainClient, BlockChainReset, ChainInfo,
 	ImportExportBlocks, Tick, ImportBlock
@@ -48,7 +51,7 @@ use test_helpers::{
 	generate_dummy_client, push_blocks_to_client, get_test_client_with_blocks, get_good_dummy_block_seq,
 	generate_dummy_client_with_data, get_good_dummy_block, get_bad_state_dummy_block
 };
-use rustc_hex::ToHex;
+use rustc_hex::{FromHex, ToHex};
 use registrar::RegistrarClient;
 
 #[test]
@@ -157,6 +160,7 @@ fn returns_logs() {
 		address: None,
 		topics: vec![],
 		limit: None,
+		offset: None,
 	}).unwrap();
 	assert_eq!(logs.len(), 0);
 }
@@ -171,6 +175,7 @@ fn returns_logs_with_limit() {
 		address: None,
 		topics: vec![],
 		limit: None,
+		offset: None,
 	}).unwrap();
 	assert_eq!(logs.len(), 0);
 }
@@ -211,6 +216,29 @@ fn can_generate_gas_price_median() {
 	assert_eq!(Some(&U256::from(3)), client.gas_price_corpus(3).median());
 }
 
+#[test]
+fn computes_block_state_diff() {
+	let client = generate_dummy_client_with_data(2, 1, slice_into![1]);
+	let sender = KeyPair::from_secret_slice(keccak("").as_bytes()).unwrap().address();
+
+	let diff = client.block_state_diff(BlockId::Number(2)).unwrap();
+	assert_eq!(diff.raw[&sender].nonce, Diff::Changed(1.into(), 2.into()));
+	assert!(client.block_state_diff(BlockId::Number(0)).is_err());
+}
+
+#[test]
+fn replays_block_to_recorded_state_root() {
+	let client = generate_dummy_client_with_data(2, 2, slice_into![1, 1]);
+	let analytics = CallAnalytics { transaction_tracing: true, vm_tracing: false, state_diffing: false };
+
+	let replayed = client.replay_block(BlockId::Number(2), analytics).unwrap();
+	assert_eq!(replayed.transactions.len(), 2);
+	assert!(replayed.transactions.iter().all(|(_, executed)| !executed.trace.is_empty()));
+	assert!(replayed.state_root_matches());
+	assert_eq!(replayed.expected_state_root, client.block_header(BlockId::Number(2)).unwrap().state_root());
+	assert!(client.replay_block(BlockId::Number(0), analytics).is_err());
+}
+
 #[test]
 fn can_generate_gas_price_histogram() {
 	let client = generate_dummy_client_with_data(20, 1, slice_into![6354,8593,6065,4842,7845,7002,689,4958,4250,6098,5804,4320,643,8895,2296,8589,7145,2000,2512,1408]);
@@ -252,6 +280,28 @@ fn can_handle_long_fork() {
 	assert_eq!(2000, client.chain_info().best_block_number);
 }
 
+#[test]
+fn last_hashes_follow_reorg() {
+	let client = generate_dummy_client(300);
+	assert_eq!(300, client.chain_info().best_block_number);
+	// warm up the cache
+	client.last_hashes();
+
+	push_blocks_to_client(&client, 45, 301, 10);
+	push_blocks_to_client(&client, 49, 301, 20);
+	for _ in 0..10 {
+		client.import_verified_blocks();
+	}
+
+	let best = client.chain_info().best_block_number;
+	assert_eq!(320, best);
+	let last_hashes = client.last_hashes();
+	assert_eq!(last_hashes.len(), 256);
+	for (i, hash) in last_hashes.iter().enumerate() {
+		assert_eq!(Some(*hash), client.block_hash(BlockId::Number(best - i as u64)));
+	}
+}
+
 #[test]
 fn can_mine() {
 	let dummy_blocks = get_good_dummy_block_seq(2);
@@ -331,6 +381,37 @@ fn does_not_propagate_delayed_transactions() {
 	assert_eq!(2, client.miner().ready_transactions(&*client, 10, PendingOrdering::Priority).len());
 }
 
+#[test]
+fn call_applies_state_override() {
+	let client = generate_dummy_client(0);
+	let contract = Address::from_low_u64_be(0x42);
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Call(contract),
+		value: 0.into(),
+		data: Vec::new(),
+	}.fake_sign(Address::zero());
+
+	// returns the value of storage slot 0
+	let account = AccountOverride {
+		code: Some("60005460005260206000f3".from_hex().unwrap()),
+		storage: vec![(H256::zero(), H256::from_low_u64_be(0x2a))].into_iter().collect(),
+		..Default::default()
+	};
+	let overrides: StateOverride = vec![(contract, account)].into_iter().collect();
+
+	let (mut state, header) = client.latest_state_and_header();
+	let executed = client.call(&transaction, Default::default(), &mut state, &header, Some(&overrides)).unwrap();
+	assert_eq!(executed.output, H256::from_low_u64_be(0x2a).as_bytes().to_vec());
+
+	// the override is not persisted
+	let (mut state, header) = client.latest_state_and_header();
+	let executed = client.call(&transaction, Default::default(), &mut state, &header, None).unwrap();
+	assert!(executed.output.is_empty());
+}
+
 #[test]
 fn transaction_proof() {
 	use client_traits::ProvingBlockChainClient;
@@ -373,6 +454,42 @@ fn transaction_proof() {
 	assert_eq!(state.balance(&address).unwrap(), 95.into());
 }
 
+#[test]
+fn stores_transaction_outputs() {
+	let test_spec = spec::new_test();
+	let mut config = ClientConfig::default();
+	config.store_outputs = true;
+	let client = Client::new(
+		config,
+		&test_spec,
+		test_helpers::new_db(),
+		Arc::new(Miner::new_for_tests(&test_spec, None)),
+		IoChannel::disconnected()
+	).unwrap();
+
+	let key = KeyPair::from_secret_slice(keccak("test").as_bytes()).unwrap();
+	// init code returning the single byte 0x2a
+	let transaction = Transaction {
+		nonce: 0.into(),
+		gas_price: 0.into(),
+		gas: 100_000.into(),
+		action: Action::Create,
+		value: 0.into(),
+		data: "602a60005360016000f3".from_hex().unwrap(),
+	}.sign(key.secret(), None);
+	let hash = transaction.hash();
+
+	let mut b = client.prepare_open_block(Address::zero(), (3141562.into(), 31415620.into()), vec![]).unwrap();
+	b.push_transaction(transaction, None).unwrap();
+	let b = b.close_and_lock().unwrap().seal(&*test_spec.engine, vec![]).unwrap();
+	client.import_sealed_block(b).unwrap();
+
+	let receipt = client.transaction_receipt(TransactionId::Hash(hash)).unwrap();
+	assert_eq!(receipt.output, Some(vec![0x2a]));
+	let receipts = client.localized_block_receipts(BlockId::Latest).unwrap();
+	assert_eq!(receipts[0].output, Some(vec![0x2a]));
+}
+
 #[test]
 fn reset_blockchain() {
 	let client = get_test_client_with_blocks(get_good_dummy_block_seq(19));
@@ -18,7 +18,7 @@ use std::sync::{Arc, mpsc, atomic};
 use std::collections::{HashMap, BTreeMap};
 use std::io;
 use std::ops::RangeInclusive;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::net::{SocketAddr, AddrParseError};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -28,8 +28,8 @@ use crate::light_sync::{self, SyncInfo};
 use crate::private_tx::PrivateTxHandler;
 use crate::chain::{
 	sync_packet::SyncPacket::{PrivateTransactionPacket, SignedPrivateTransactionPacket},
-	ChainSyncApi, SyncState, SyncStatus as EthSyncStatus, ETH_PROTOCOL_VERSION_62,
-	ETH_PROTOCOL_VERSION_63, PAR_PROTOCOL_VERSION_1, PAR_PROTOCOL_VERSION_2,
+	Bandwidth, BandwidthLimits, ChainSyncApi, EthVersion, SyncProgress, SyncState, SyncStatus as EthSyncStatus,
+	PAR_PROTOCOL_VERSION_1, PAR_PROTOCOL_VERSION_2,
 	PAR_PROTOCOL_VERSION_3, PAR_PROTOCOL_VERSION_4,
 };
 
@@ -129,6 +129,10 @@ pub struct SyncConfig {
 	pub warp_sync: WarpSync,
 	/// Enable light client server.
 	pub serve_light: bool,
+	/// Bandwidth caps and per-peer request rate limits.
+	pub bandwidth: BandwidthLimits,
+	/// Propagation of local transactions.
+	pub local_transactions_relay: LocalTransactionsRelay,
 }
 
 impl Default for SyncConfig {
@@ -142,6 +146,8 @@ impl Default for SyncConfig {
 			fork_block: None,
 			warp_sync: WarpSync::Disabled,
 			serve_light: false,
+			bandwidth: BandwidthLimits::default(),
+			local_transactions_relay: LocalTransactionsRelay::Immediate,
 		}
 	}
 }
@@ -192,6 +198,8 @@ pub struct PeerInfo {
 	pub remote_address: String,
 	/// Local endpoint address
 	pub local_address: String,
+	/// Average round-trip time of the recent session pings
+	pub ping: Option<Duration>,
 	/// Eth protocol info.
 	pub eth_info: Option<EthProtocolInfo>,
 	/// Light protocol info.
@@ -207,6 +215,14 @@ pub struct EthProtocolInfo {
 	pub head: H256,
 	/// Peer total difficulty if known
 	pub difficulty: Option<U256>,
+	/// Peer best block number if announced
+	pub head_number: Option<BlockNumber>,
+	/// Average time the peer takes to answer our requests
+	pub latency: Option<Duration>,
+	/// Type of data currently being requested from the peer
+	pub asking: Option<String>,
+	/// Number of blocks currently being requested from the peer
+	pub asking_blocks: usize,
 }
 
 /// PIP protocol info.
@@ -385,6 +401,7 @@ impl EthSync {
 		let sync = Arc::new(EthSync {
 			network: service,
 			eth_handler: Arc::new(SyncProtocolHandler {
+				bandwidth: sync.bandwidth(),
 				sync,
 				chain: params.chain,
 				snapshot_service: params.snapshot_service,
@@ -405,6 +422,11 @@ impl EthSync {
 	pub fn priority_tasks(&self) -> mpsc::Sender<PriorityTask> {
 		self.priority_tasks.lock().clone()
 	}
+
+	/// Gets block download progress notifications, sent every sync maintenance round.
+	pub fn sync_progress_notification(&self) -> Notification<SyncProgress> {
+		self.eth_handler.sync.write().progress_notifications()
+	}
 }
 
 impl SyncProvider for EthSync {
@@ -432,6 +454,7 @@ impl SyncProvider for EthSync {
 					capabilities: session_info.peer_capabilities.into_iter().map(|c| c.to_string()).collect(),
 					remote_address: session_info.remote_address,
 					local_address: session_info.local_address,
+					ping: session_info.ping_average,
 					eth_info: peer_info,
 					pip_info: light_proto.as_ref().and_then(|lp| lp.peer_status(peer_id)).map(Into::into),
 				})
@@ -475,6 +498,8 @@ struct SyncProtocolHandler {
 	overlay: RwLock<HashMap<BlockNumber, Bytes>>,
 	/// Private state db
 	private_state: Option<Arc<PrivateStateDB>>,
+	/// Traffic counters and rate limits, shared with `sync`.
+	bandwidth: Arc<Bandwidth>,
 }
 
 impl NetworkProtocolHandler for SyncProtocolHandler {
@@ -490,10 +515,12 @@ impl NetworkProtocolHandler for SyncProtocolHandler {
 	}
 
 	fn read(&self, io: &dyn NetworkContext, peer: &PeerId, packet_id: u8, data: &[u8]) {
+		self.bandwidth.note_received(data.len(), Instant::now());
 		self.sync.dispatch_packet(&mut NetSyncIo::new(io,
 			&*self.chain,
 			&*self.snapshot_service,
 			&self.overlay,
+			&self.bandwidth,
 			self.private_state.clone()),
 			*peer, packet_id, data);
 	}
@@ -508,6 +535,7 @@ impl NetworkProtocolHandler for SyncProtocolHandler {
 			&*self.chain,
 			&*self.snapshot_service,
 			&self.overlay,
+			&self.bandwidth,
 			self.private_state.clone()),
 			*peer);
 		}
@@ -520,6 +548,7 @@ impl NetworkProtocolHandler for SyncProtocolHandler {
 				&*self.chain,
 				&*self.snapshot_service,
 				&self.overlay,
+				&self.bandwidth,
 				self.private_state.clone()),
 				*peer);
 		}
@@ -527,10 +556,13 @@ impl NetworkProtocolHandler for SyncProtocolHandler {
 
 	fn timeout(&self, io: &dyn NetworkContext, timer: TimerToken) {
 		trace_time!("sync::timeout");
-		let mut io = NetSyncIo::new(io, &*self.chain, &*self.snapshot_service, &self.overlay, self.private_state.clone());
+		let mut io = NetSyncIo::new(io, &*self.chain, &*self.snapshot_service, &self.overlay, &self.bandwidth, self.private_state.clone());
 		match timer {
 			PEERS_TIMER => self.sync.write().maintain_peers(&mut io),
-			MAINTAIN_SYNC_TIMER => self.sync.write().maintain_sync(&mut io),
+			MAINTAIN_SYNC_TIMER => {
+				self.sync.write().maintain_sync(&mut io);
+				self.sync.refresh_status();
+			},
 			CONTINUE_SYNC_TIMER => self.sync.write().continue_sync(&mut io),
 			TX_TIMER => self.sync.write().propagate_new_transactions(&mut io),
 			PRIORITY_TIMER => self.sync.process_priority_queue(&mut io),
@@ -562,6 +594,7 @@ impl ChainNotify for EthSync {
 				&*self.eth_handler.chain,
 				&*self.eth_handler.snapshot_service,
 				&self.eth_handler.overlay,
+				&self.eth_handler.bandwidth,
 				self.eth_handler.private_state.clone());
 			self.eth_handler.sync.write().chain_new_blocks(
 				&mut sync_io,
@@ -606,7 +639,7 @@ impl ChainNotify for EthSync {
 			_ => {},
 		}
 
-		self.network.register_protocol(self.eth_handler.clone(), self.subprotocol_name, &[ETH_PROTOCOL_VERSION_62, ETH_PROTOCOL_VERSION_63])
+		self.network.register_protocol(self.eth_handler.clone(), self.subprotocol_name, &EthVersion::capabilities())
 			.unwrap_or_else(|e| warn!("Error registering ethereum protocol: {:?}", e));
 		// register the warp sync subprotocol
 		self.network.register_protocol(self.eth_handler.clone(), WARP_SYNC_PROTOCOL_ID, &[PAR_PROTOCOL_VERSION_1, PAR_PROTOCOL_VERSION_2, PAR_PROTOCOL_VERSION_3, PAR_PROTOCOL_VERSION_4])
@@ -630,6 +663,7 @@ impl ChainNotify for EthSync {
 				&*self.eth_handler.chain,
 				&*self.eth_handler.snapshot_service,
 				&self.eth_handler.overlay,
+				&self.eth_handler.bandwidth,
 				self.eth_handler.private_state.clone());
 			match message_type {
 				ChainMessageType::Consensus(message) => self.eth_handler.sync.write().propagate_consensus_packet(&mut sync_io, message),
@@ -670,6 +704,10 @@ pub trait ManageNetwork: Send + Sync {
 	fn remove_reserved_peer(&self, peer: String) -> Result<(), String>;
 	/// Add reserved peer
 	fn add_reserved_peer(&self, peer: String) -> Result<(), String>;
+	/// Blacklist a node id or IP/CIDR range
+	fn add_to_blacklist(&self, entry: String) -> Result<(), String>;
+	/// Remove a blacklist entry
+	fn remove_from_blacklist(&self, entry: String) -> Result<(), String>;
 	/// Start network
 	fn start_network(&self);
 	/// Stop network
@@ -697,6 +735,14 @@ impl ManageNetwork for EthSync {
 		self.network.add_reserved_peer(&peer).map_err(|e| format!("{:?}", e))
 	}
 
+	fn add_to_blacklist(&self, entry: String) -> Result<(), String> {
+		self.network.add_to_blacklist(&entry).map_err(|e| format!("{:?}", e))
+	}
+
+	fn remove_from_blacklist(&self, entry: String) -> Result<(), String> {
+		self.network.remove_from_blacklist(&entry).map_err(|e| format!("{:?}", e))
+	}
+
 	fn start_network(&self) {
 		self.start();
 	}
@@ -707,6 +753,7 @@ impl ManageNetwork for EthSync {
 				&*self.eth_handler.chain,
 				&*self.eth_handler.snapshot_service,
 				&self.eth_handler.overlay,
+				&self.eth_handler.bandwidth,
 				self.eth_handler.private_state.clone());
 			self.eth_handler.sync.write().abort(&mut sync_io);
 		});
@@ -754,6 +801,12 @@ pub struct NetworkConfiguration {
 	pub max_peers: u32,
 	/// Min number of connected peers to maintain
 	pub min_peers: u32,
+	/// Max number of peers we connect to
+	pub max_outbound_peers: Option<u32>,
+	/// Max number of peers connecting to us
+	pub max_inbound_peers: Option<u32>,
+	/// Share the listening port with other processes
+	pub reuse_port: bool,
 	/// Max pending peers.
 	pub max_pending_peers: u32,
 	/// Reserved snapshot sync peers.
@@ -794,6 +847,9 @@ impl NetworkConfiguration {
 			use_secret: self.use_secret,
 			max_peers: self.max_peers,
 			min_peers: self.min_peers,
+			max_outbound_peers: self.max_outbound_peers,
+			max_inbound_peers: self.max_inbound_peers,
+			reuse_port: self.reuse_port,
 			max_handshakes: self.max_pending_peers,
 			reserved_protocols: hash_map![WARP_SYNC_PROTOCOL_ID => self.snapshot_peers],
 			reserved_nodes: self.reserved_nodes,
@@ -819,6 +875,9 @@ impl From<BasicNetworkConfiguration> for NetworkConfiguration {
 			use_secret: other.use_secret,
 			max_peers: other.max_peers,
 			min_peers: other.min_peers,
+			max_outbound_peers: other.max_outbound_peers,
+			max_inbound_peers: other.max_inbound_peers,
+			reuse_port: other.reuse_port,
 			max_pending_peers: other.max_handshakes,
 			snapshot_peers: *other.reserved_protocols.get(&WARP_SYNC_PROTOCOL_ID).unwrap_or(&0),
 			reserved_nodes: other.reserved_nodes,
@@ -829,6 +888,21 @@ impl From<BasicNetworkConfiguration> for NetworkConfiguration {
 	}
 }
 
+/// How transactions submitted to this node are announced to the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalTransactionsRelay {
+	/// As soon as possible, like any other transaction.
+	Immediate,
+	/// After a delay and to a few random peers only, making it harder to tell
+	/// the transactions originated from this node.
+	Delayed {
+		/// Time to hold a transaction back, and to wait before announcing it again.
+		delay: Duration,
+		/// Number of peers to announce a transaction to.
+		peers: usize,
+	},
+}
+
 /// Configuration for IPC service.
 #[derive(Debug, Clone)]
 pub struct ServiceConfiguration {
@@ -990,6 +1064,14 @@ impl ManageNetwork for LightSync {
 		self.network.add_reserved_peer(&peer).map_err(|e| format!("{:?}", e))
 	}
 
+	fn add_to_blacklist(&self, entry: String) -> Result<(), String> {
+		self.network.add_to_blacklist(&entry).map_err(|e| format!("{:?}", e))
+	}
+
+	fn remove_from_blacklist(&self, entry: String) -> Result<(), String> {
+		self.network.remove_from_blacklist(&entry).map_err(|e| format!("{:?}", e))
+	}
+
 	fn start_network(&self) {
 		match self.network.start() {
 			Err((err, listen_address)) => {
@@ -1052,6 +1134,7 @@ impl LightSyncProvider for LightSync {
 					capabilities: session_info.peer_capabilities.into_iter().map(|c| c.to_string()).collect(),
 					remote_address: session_info.remote_address,
 					local_address: session_info.local_address,
+					ping: session_info.ping_average,
 					eth_info: None,
 					pip_info: self.proto.peer_status(peer_id).map(Into::into),
 				})
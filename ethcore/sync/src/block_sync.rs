@@ -42,6 +42,7 @@ use common_types::{
 const MAX_HEADERS_TO_REQUEST: usize = 128;
 const MAX_BODIES_TO_REQUEST_LARGE: usize = 128;
 const MAX_BODIES_TO_REQUEST_SMALL: usize = 32; // Size request for parity clients prior to 2.4.0
+const MIN_BODIES_TO_REQUEST: usize = 8;
 const MAX_RECEPITS_TO_REQUEST: usize = 256;
 const SUBCHAIN_SIZE: u64 = 256;
 const MAX_ROUND_PARENTS: usize = 16;
@@ -236,6 +237,15 @@ impl BlockDownloader {
 		self.last_imported_block
 	}
 
+	/// Returns the block numbers headers and bodies have been downloaded up to,
+	/// counting the blocks held in memory on top of the last imported one.
+	pub fn downloaded_block_numbers(&self) -> (BlockNumber, BlockNumber) {
+		(
+			self.last_imported_block + self.blocks.headers_len() as BlockNumber,
+			self.last_imported_block + self.blocks.bodies_len() as BlockNumber,
+		)
+	}
+
 	/// Add new block headers.
 	pub fn import_headers(&mut self, io: &mut dyn SyncIo, r: &Rlp, expected_hash: H256) -> Result<DownloadAction, BlockDownloaderImportError> {
 		let item_count = r.item_count().unwrap_or(0);
@@ -470,12 +480,15 @@ impl BlockDownloader {
 	}
 
 	/// Find some headers or blocks to download for a peer.
-	pub fn request_blocks(&mut self, peer_id: PeerId, io: &mut dyn SyncIo, num_active_peers: usize) -> Option<BlockRequest> {
+	///
+	/// `bodies_weight` scales the number of block bodies asked for, in `(0, 1]`, so that slower
+	/// peers get smaller chunks and don't hold up the import of the ones fetched from faster peers.
+	pub fn request_blocks(&mut self, peer_id: PeerId, io: &mut dyn SyncIo, num_active_peers: usize, bodies_weight: f64) -> Option<BlockRequest> {
 		match self.state {
 			State::Idle => {
 				self.start_sync_round(io);
 				if self.state == State::ChainHead {
-					return self.request_blocks(peer_id, io, num_active_peers);
+					return self.request_blocks(peer_id, io, num_active_peers, bodies_weight);
 				}
 			},
 			State::ChainHead => {
@@ -495,11 +508,15 @@ impl BlockDownloader {
 				// check to see if we need to download any block bodies first
 				let client_version = io.peer_version(peer_id);
 
-				let number_of_bodies_to_request = if client_version.can_handle_large_requests() {
+				let max_bodies_to_request = if client_version.can_handle_large_requests() {
 					MAX_BODIES_TO_REQUEST_LARGE
 				} else {
 					MAX_BODIES_TO_REQUEST_SMALL
 				};
+				let number_of_bodies_to_request = cmp::max(
+					MIN_BODIES_TO_REQUEST,
+					(max_bodies_to_request as f64 * bodies_weight).ceil() as usize,
+				);
 
 				let needed_bodies = self.blocks.needed_bodies(number_of_bodies_to_request, false);
 				if !needed_bodies.is_empty() {
@@ -591,6 +608,10 @@ impl BlockDownloader {
 					debug_sync!(self, "Block temporarily invalid: {:?}, restarting sync", h);
 					break;
 				},
+				Err(EthcoreError::Import(ImportError::ForkTooWide)) => {
+					debug_sync!(self, "Too many siblings of {:?} queued, restarting sync", h);
+					break;
+				},
 				Err(EthcoreError::FullQueue(limit)) => {
 					debug_sync!(self, "Block import queue full ({}), restarting sync", limit);
 					download_action = DownloadAction::Reset;
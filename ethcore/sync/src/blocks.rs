@@ -372,6 +372,16 @@ impl BlockCollection {
 		self.heads.len()
 	}
 
+	/// Number of downloaded headers.
+	pub fn headers_len(&self) -> usize {
+		self.blocks.len()
+	}
+
+	/// Number of downloaded headers that also have their body.
+	pub fn bodies_len(&self) -> usize {
+		self.blocks.values().filter(|b| b.body.is_some()).count()
+	}
+
 	/// Check if given block hash is marked as being downloaded.
 	pub fn is_downloading(&self, hash: &H256) -> bool {
 		self.downloading_headers.contains(hash) || self.downloading_bodies.contains(hash)
@@ -0,0 +1,196 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Traffic accounting and rate limits for the sync protocol.
+//!
+//! All limits are enforced over one second windows. Going over the upload cap or a
+//! peer's request rate makes us ignore further requests until the window rolls over;
+//! going over the download cap stops us from issuing new requests. Requests that were
+//! already in flight are not affected, so the caps may be exceeded by at most one
+//! round of responses.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use network::PeerId;
+use parking_lot::Mutex;
+
+use super::supplier::TrafficWindow;
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Bandwidth caps and request rate limits. `None` means unlimited.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BandwidthLimits {
+	/// Maximum bytes sent per second.
+	pub upload: Option<usize>,
+	/// Maximum bytes received per second.
+	pub download: Option<usize>,
+	/// Maximum requests served to a single peer per second.
+	pub peer_requests: Option<usize>,
+}
+
+struct Windows {
+	upload: TrafficWindow,
+	download: TrafficWindow,
+	peer_requests: HashMap<PeerId, TrafficWindow>,
+}
+
+/// Sync traffic meter, shared between the network IO and `ChainSync`.
+pub struct Bandwidth {
+	limits: BandwidthLimits,
+	windows: Mutex<Windows>,
+	bytes_sent: AtomicU64,
+	bytes_received: AtomicU64,
+}
+
+impl Bandwidth {
+	/// Create a new meter enforcing `limits`.
+	pub fn new(limits: BandwidthLimits) -> Self {
+		let now = Instant::now();
+		Bandwidth {
+			limits,
+			windows: Mutex::new(Windows {
+				upload: TrafficWindow::new(WINDOW, now),
+				download: TrafficWindow::new(WINDOW, now),
+				peer_requests: HashMap::new(),
+			}),
+			bytes_sent: AtomicU64::new(0),
+			bytes_received: AtomicU64::new(0),
+		}
+	}
+
+	/// Records `bytes` sent to any peer.
+	pub fn note_sent(&self, bytes: usize, now: Instant) {
+		self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+		if self.limits.upload.is_some() {
+			self.windows.lock().upload.add(bytes, now);
+		}
+	}
+
+	/// Records `bytes` received from any peer.
+	pub fn note_received(&self, bytes: usize, now: Instant) {
+		self.bytes_received.fetch_add(bytes as u64, Ordering::Relaxed);
+		if self.limits.download.is_some() {
+			self.windows.lock().download.add(bytes, now);
+		}
+	}
+
+	/// Records a request from `peer` and returns whether it should be served.
+	pub fn allow_request(&self, peer: PeerId, now: Instant) -> bool {
+		if self.limits.upload.is_none() && self.limits.peer_requests.is_none() {
+			return true;
+		}
+		let mut windows = self.windows.lock();
+		if self.limits.upload.map_or(false, |max| windows.upload.used(now) >= max) {
+			return false;
+		}
+		match self.limits.peer_requests {
+			Some(max) => {
+				let requests = windows.peer_requests.entry(peer).or_insert_with(|| TrafficWindow::new(WINDOW, now));
+				if requests.used(now) >= max {
+					return false;
+				}
+				requests.add(1, now);
+				true
+			},
+			None => true,
+		}
+	}
+
+	/// Whether new requests may be sent without going over the download cap.
+	pub fn can_download(&self, now: Instant) -> bool {
+		match self.limits.download {
+			Some(max) => self.windows.lock().download.used(now) < max,
+			None => true,
+		}
+	}
+
+	/// Forgets a disconnected peer.
+	pub fn remove_peer(&self, peer: PeerId) {
+		self.windows.lock().peer_requests.remove(&peer);
+	}
+
+	/// Total bytes sent.
+	pub fn bytes_sent(&self) -> u64 {
+		self.bytes_sent.load(Ordering::Relaxed)
+	}
+
+	/// Total bytes received.
+	pub fn bytes_received(&self) -> u64 {
+		self.bytes_received.load(Ordering::Relaxed)
+	}
+}
+
+impl Default for Bandwidth {
+	fn default() -> Self {
+		Bandwidth::new(BandwidthLimits::default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn counts_traffic_without_limits() {
+		let bandwidth = Bandwidth::default();
+		let now = Instant::now();
+		bandwidth.note_sent(100, now);
+		bandwidth.note_received(50, now);
+		bandwidth.note_received(50, now);
+		assert_eq!(bandwidth.bytes_sent(), 100);
+		assert_eq!(bandwidth.bytes_received(), 100);
+		assert!(bandwidth.allow_request(0, now));
+		assert!(bandwidth.can_download(now));
+	}
+
+	#[test]
+	fn enforces_caps_within_window() {
+		let bandwidth = Bandwidth::new(BandwidthLimits {
+			upload: Some(1000),
+			download: Some(1000),
+			peer_requests: None,
+		});
+		let now = Instant::now();
+		bandwidth.note_sent(1000, now);
+		bandwidth.note_received(999, now);
+		assert!(!bandwidth.allow_request(0, now));
+		assert!(bandwidth.can_download(now));
+		bandwidth.note_received(1, now);
+		assert!(!bandwidth.can_download(now));
+
+		let later = now + WINDOW;
+		assert!(bandwidth.allow_request(0, later));
+		assert!(bandwidth.can_download(later));
+	}
+
+	#[test]
+	fn limits_requests_per_peer() {
+		let bandwidth = Bandwidth::new(BandwidthLimits {
+			upload: None,
+			download: None,
+			peer_requests: Some(2),
+		});
+		let now = Instant::now();
+		assert!(bandwidth.allow_request(0, now));
+		assert!(bandwidth.allow_request(0, now));
+		assert!(!bandwidth.allow_request(0, now));
+		assert!(bandwidth.allow_request(1, now));
+		assert!(bandwidth.allow_request(0, now + WINDOW));
+	}
+}
@@ -0,0 +1,117 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Negotiated `eth` subprotocol versions.
+//!
+//! The p2p layer picks the highest `eth` version offered by both sides
+//! during the RLPx hello. Each peer's version is recorded when its status
+//! packet arrives, and every `eth` packet sent to or received from that
+//! peer is checked against it. A packet is only encoded or decoded if it
+//! exists in the negotiated version. New versions need a variant here and
+//! an entry in `EthVersion::supports`.
+
+use crate::api::ETH_PROTOCOL;
+
+use super::sync_packet::{PacketInfo, SyncPacket};
+use super::sync_packet::SyncPacket::{
+	GetNodeDataPacket,
+	NodeDataPacket,
+	GetReceiptsPacket,
+	ReceiptsPacket,
+};
+use super::{ETH_PROTOCOL_VERSION_62, ETH_PROTOCOL_VERSION_63};
+
+/// Version of the `eth` subprotocol spoken with a peer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EthVersion {
+	/// eth/62: headers, bodies and block propagation.
+	Eth62,
+	/// eth/63: adds state (`GetNodeData`) and receipts (`GetReceipts`) retrieval.
+	Eth63,
+}
+
+impl EthVersion {
+	/// All supported versions, in ascending order.
+	pub const ALL: [EthVersion; 2] = [EthVersion::Eth62, EthVersion::Eth63];
+
+	/// The highest version we support.
+	pub const LATEST: EthVersion = EthVersion::Eth63;
+
+	/// Maps a version number read from the wire or reported by the network layer.
+	pub fn from_u8(version: u8) -> Option<Self> {
+		EthVersion::ALL.iter().cloned().find(|v| v.number() == version)
+	}
+
+	/// Protocol version number.
+	pub fn number(&self) -> u8 {
+		self.info().0
+	}
+
+	/// Number of packet ids reserved by this version.
+	pub fn packet_count(&self) -> u8 {
+		self.info().1
+	}
+
+	/// `(version, packet count)` pairs to offer when registering the protocol.
+	pub fn capabilities() -> Vec<(u8, u8)> {
+		EthVersion::ALL.iter().map(|v| v.info()).collect()
+	}
+
+	/// Whether `packet` is part of this version. Packets of other subprotocols are
+	/// not affected by the `eth` version and always pass.
+	pub fn supports(&self, packet: SyncPacket) -> bool {
+		if packet.protocol() != ETH_PROTOCOL {
+			return true;
+		}
+		match packet {
+			GetNodeDataPacket | NodeDataPacket | GetReceiptsPacket | ReceiptsPacket => *self >= EthVersion::Eth63,
+			_ => true,
+		}
+	}
+
+	fn info(&self) -> (u8, u8) {
+		match *self {
+			EthVersion::Eth62 => ETH_PROTOCOL_VERSION_62,
+			EthVersion::Eth63 => ETH_PROTOCOL_VERSION_63,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::sync_packet::SyncPacket::{GetBlockHeadersPacket, GetSnapshotDataPacket};
+
+	#[test]
+	fn maps_version_numbers() {
+		assert_eq!(EthVersion::from_u8(62), Some(EthVersion::Eth62));
+		assert_eq!(EthVersion::from_u8(63), Some(EthVersion::Eth63));
+		assert_eq!(EthVersion::from_u8(61), None);
+		assert_eq!(EthVersion::from_u8(64), None);
+		assert_eq!(EthVersion::LATEST.number(), 63);
+		assert_eq!(EthVersion::capabilities(), vec![ETH_PROTOCOL_VERSION_62, ETH_PROTOCOL_VERSION_63]);
+	}
+
+	#[test]
+	fn eth62_has_no_state_or_receipts() {
+		assert!(EthVersion::Eth62.supports(GetBlockHeadersPacket));
+		assert!(!EthVersion::Eth62.supports(GetReceiptsPacket));
+		assert!(!EthVersion::Eth62.supports(NodeDataPacket));
+		assert!(EthVersion::Eth62.supports(GetSnapshotDataPacket));
+		assert!(EthVersion::Eth63.supports(GetReceiptsPacket));
+		assert!(EthVersion::Eth63.supports(NodeDataPacket));
+	}
+}
@@ -23,6 +23,7 @@ use crate::{
 	api::WARP_SYNC_PROTOCOL_ID,
 	block_sync::{BlockDownloaderImportError as DownloaderImportError, DownloadAction},
 	chain::{
+		message::Status,
 		sync_packet::{
 			PacketInfo,
 			SyncPacket::{
@@ -31,8 +32,8 @@ use crate::{
 				SnapshotDataPacket, SnapshotManifestPacket, StatusPacket,
 			}
 		},
-		BlockSet, ChainSync, ForkConfirmation, PacketDecodeError, PeerAsking, PeerInfo, SyncRequester,
-		SyncState, ETH_PROTOCOL_VERSION_62, ETH_PROTOCOL_VERSION_63, MAX_NEW_BLOCK_AGE, MAX_NEW_HASHES,
+		BlockSet, ChainSync, EthVersion, ForkConfirmation, PacketDecodeError, PeerAsking, PeerInfo, SyncRequester,
+		SyncState, MAX_NEW_BLOCK_AGE, MAX_NEW_HASHES,
 		PAR_PROTOCOL_VERSION_1, PAR_PROTOCOL_VERSION_3, PAR_PROTOCOL_VERSION_4,
 	}
 };
@@ -110,6 +111,8 @@ impl SyncHandler {
 	pub fn on_peer_aborting(sync: &mut ChainSync, io: &mut dyn SyncIo, peer_id: PeerId) {
 		trace!(target: "sync", "== Disconnecting {}: {}", peer_id, io.peer_version(peer_id));
 		sync.handshaking_peers.remove(&peer_id);
+		sync.served_data.lock().remove(peer_id);
+		sync.bandwidth.remove_peer(peer_id);
 		if sync.peers.contains_key(&peer_id) {
 			debug!(target: "sync", "Disconnected {}", peer_id);
 			sync.clear_peer_download(peer_id);
@@ -165,7 +168,7 @@ impl SyncHandler {
 		let mut unknown = false;
 
 		if let Some(ref mut peer) = sync.peers.get_mut(&peer_id) {
-			peer.latest_hash = hash;
+			peer.note_head(hash, Some(number));
 		}
 
 		let last_imported_number = sync.new_blocks.last_imported_block_number();
@@ -173,13 +176,16 @@ impl SyncHandler {
 			trace!(target: "sync", "Ignored ancient new block {:?}", hash);
 			return Err(DownloaderImportError::Invalid);
 		}
-		match io.chain().import_block(block) {
+		match io.chain().import_block_from_peer(block, peer_id) {
 			Err(EthcoreError::Import(ImportError::AlreadyInChain)) => {
 				trace!(target: "sync", "New block already in chain {:?}", hash);
 			},
 			Err(EthcoreError::Import(ImportError::AlreadyQueued)) => {
 				trace!(target: "sync", "New block already queued {:?}", hash);
 			},
+			Err(EthcoreError::Import(ImportError::ForkTooWide)) => {
+				trace!(target: "sync", "New block dropped, too many siblings queued {:?}", hash);
+			},
 			Ok(_) => {
 				// abort current download of the same block
 				sync.complete_sync(io);
@@ -217,8 +223,8 @@ impl SyncHandler {
 		if let Some(ref mut peer) = sync.peers.get_mut(&peer_id) {
 			// Peer has new blocks with unknown difficulty
 			peer.difficulty = None;
-			if let Some(&(Ok(ref h), _)) = hashes.last() {
-				peer.latest_hash = h.clone();
+			if let Some(&(Ok(ref h), ref n)) = hashes.last() {
+				peer.note_head(h.clone(), n.as_ref().ok().cloned());
 			}
 		}
 		if sync.state != SyncState::Idle {
@@ -258,7 +264,7 @@ impl SyncHandler {
 					if number > max_height {
 						trace!(target: "sync", "New unknown block hash {:?}", hash);
 						if let Some(ref mut peer) = sync.peers.get_mut(&peer_id) {
-							peer.latest_hash = hash.clone();
+							peer.note_head(hash.clone(), Some(number));
 						}
 						max_height = number;
 					}
@@ -285,6 +291,7 @@ impl SyncHandler {
 			.and_then(|p| p.block_set)
 			.unwrap_or(BlockSet::NewBlocks);
 		let allowed = sync.peers.get(&peer_id).map(|p| p.is_allowed()).unwrap_or(false);
+		let elapsed = sync.peers.get(&peer_id).map(|p| p.ask_time.elapsed());
 
 		if !sync.reset_peer_asking(peer_id, PeerAsking::BlockBodies) || !allowed {
 			trace!(target: "sync", "{}: Ignored unexpected bodies", peer_id);
@@ -305,6 +312,9 @@ impl SyncHandler {
 			trace!(target: "sync", "Ignored block bodies while waiting");
 			Ok(())
 		} else {
+			if let (Some(peer), Some(elapsed)) = (sync.peers.get_mut(&peer_id), elapsed) {
+				peer.note_bodies_received(item_count, elapsed);
+			}
 			{
 				let downloader = match block_set {
 					BlockSet::NewBlocks => &mut sync.new_blocks,
@@ -563,16 +573,25 @@ impl SyncHandler {
 	/// Called by peer to report status
 	fn on_peer_status(sync: &mut ChainSync, io: &mut dyn SyncIo, peer_id: PeerId, r: &Rlp) -> Result<(), DownloaderImportError> {
 		sync.handshaking_peers.remove(&peer_id);
-		let protocol_version: u8 = r.val_at(0)?;
 		let warp_protocol_version = io.protocol_version(&WARP_SYNC_PROTOCOL_ID, peer_id);
 		let warp_protocol = warp_protocol_version != 0;
+		let status = Status::decode(r, warp_protocol)?;
+		let protocol_version = status.protocol_version;
 		let private_tx_protocol = warp_protocol_version >= PAR_PROTOCOL_VERSION_3.0;
+		let eth_version = match EthVersion::from_u8(io.eth_protocol_version(peer_id)) {
+			Some(version) => version,
+			// The warp protocol carries the full eth/63 packet set.
+			None if warp_protocol => EthVersion::Eth63,
+			None => EthVersion::from_u8(protocol_version).unwrap_or(EthVersion::Eth62),
+		};
 		let peer = PeerInfo {
 			protocol_version,
-			network_id: r.val_at(1)?,
-			difficulty: Some(r.val_at(2)?),
-			latest_hash: r.val_at(3)?,
-			genesis: r.val_at(4)?,
+			eth_version,
+			network_id: status.network_id,
+			difficulty: Some(status.total_difficulty),
+			latest_hash: status.best_hash,
+			latest_number: None,
+			genesis: status.genesis_hash,
 			asking: PeerAsking::Nothing,
 			asking_blocks: Vec::new(),
 			asking_hash: None,
@@ -583,15 +602,20 @@ impl SyncHandler {
 			expired: false,
 			confirmation: if sync.fork_block.is_none() { ForkConfirmation::Confirmed } else { ForkConfirmation::Unconfirmed },
 			asking_snapshot_data: None,
-			snapshot_hash: if warp_protocol { Some(r.val_at(5)?) } else { None },
-			snapshot_number: if warp_protocol { Some(r.val_at(6)?) } else { None },
+			snapshot_hash: status.snapshot.map(|(hash, _)| hash),
+			snapshot_number: status.snapshot.map(|(_, number)| number),
 			block_set: None,
-			private_tx_enabled: if private_tx_protocol { r.val_at(7).unwrap_or(false) } else { false },
+			private_tx_enabled: private_tx_protocol && status.private_tx_enabled.unwrap_or(false),
+			bodies_throughput: None,
+			latency: None,
+			timeouts: 0,
+			ping: io.peer_session_info(peer_id).and_then(|info| info.ping_average),
 			client_version: ClientVersion::from(io.peer_version(peer_id)),
 		};
 
 		trace!(target: "sync", "New peer {} (\
 			protocol: {}, \
+			eth: {}, \
 			network: {:?}, \
 			difficulty: {:?}, \
 			latest:{}, \
@@ -601,6 +625,7 @@ impl SyncHandler {
 			client_version: {})",
 			peer_id,
 			peer.protocol_version,
+			peer.eth_version.number(),
 			peer.network_id,
 			peer.difficulty,
 			peer.latest_hash,
@@ -630,7 +655,7 @@ impl SyncHandler {
 
 		if false
 			|| (warp_protocol && (peer.protocol_version < PAR_PROTOCOL_VERSION_1.0 || peer.protocol_version > PAR_PROTOCOL_VERSION_4.0))
-			|| (!warp_protocol && (peer.protocol_version < ETH_PROTOCOL_VERSION_62.0 || peer.protocol_version > ETH_PROTOCOL_VERSION_63.0))
+			|| (!warp_protocol && EthVersion::from_u8(peer.protocol_version).is_none())
 		{
 			trace!(target: "sync", "Peer {} unsupported eth protocol ({})", peer_id, peer.protocol_version);
 			return Err(DownloaderImportError::Invalid);
@@ -0,0 +1,226 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Typed payloads of `eth` and `par` subprotocol packets, with their RLP codecs.
+//! Keeping the wire layout in one place lets it be tested apart from the sync state machine.
+
+use common_types::BlockNumber;
+use ethereum_types::{H256, U256};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+/// `Status` packet, the first packet exchanged with a peer.
+///
+/// Layout: `[ protocolVersion, networkId, totalDifficulty, bestHash, genesisHash,
+/// (snapshotHash, snapshotNumber, (privateTxEnabled)) ]`, the trailing fields being only sent
+/// over the warp subprotocol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Status {
+	/// Negotiated protocol version.
+	pub protocol_version: u8,
+	/// Network id.
+	pub network_id: u64,
+	/// Total difficulty of the best block.
+	pub total_difficulty: U256,
+	/// Hash of the best block.
+	pub best_hash: H256,
+	/// Hash of the genesis block.
+	pub genesis_hash: H256,
+	/// Hash and block number of the served snapshot manifest (warp only).
+	pub snapshot: Option<(H256, BlockNumber)>,
+	/// Whether private transactions are handled (warp version 3 and above).
+	pub private_tx_enabled: Option<bool>,
+}
+
+impl Encodable for Status {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_unbounded_list();
+		s.append(&(self.protocol_version as u32));
+		s.append(&self.network_id);
+		s.append(&self.total_difficulty);
+		s.append(&self.best_hash);
+		s.append(&self.genesis_hash);
+		if let Some((ref hash, ref number)) = self.snapshot {
+			s.append(hash);
+			s.append(number);
+			if let Some(ref private_tx_enabled) = self.private_tx_enabled {
+				s.append(private_tx_enabled);
+			}
+		}
+		s.finalize_unbounded_list();
+	}
+}
+
+impl Status {
+	/// Decodes a status packet. Trailing fields are only interpreted when the packet was
+	/// received over the warp subprotocol, other `eth` versions may append their own.
+	pub fn decode(r: &Rlp, warp: bool) -> Result<Self, DecoderError> {
+		let (snapshot, private_tx_enabled) = if warp {
+			let private_tx_enabled = if r.item_count()? > 7 {
+				// older peers send garbage here, treat it as disabled.
+				Some(r.val_at(7).unwrap_or(false))
+			} else {
+				None
+			};
+			(Some((r.val_at(5)?, r.val_at(6)?)), private_tx_enabled)
+		} else {
+			(None, None)
+		};
+
+		Ok(Status {
+			protocol_version: r.val_at(0)?,
+			network_id: r.val_at(1)?,
+			total_difficulty: r.val_at(2)?,
+			best_hash: r.val_at(3)?,
+			genesis_hash: r.val_at(4)?,
+			snapshot,
+			private_tx_enabled,
+		})
+	}
+}
+
+/// Block a `GetBlockHeaders` request starts from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashOrNumber {
+	/// Block hash.
+	Hash(H256),
+	/// Block number.
+	Number(BlockNumber),
+}
+
+impl Encodable for HashOrNumber {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		match *self {
+			HashOrNumber::Hash(ref hash) => s.append(hash),
+			HashOrNumber::Number(ref number) => s.append(number),
+		};
+	}
+}
+
+impl Decodable for HashOrNumber {
+	fn decode(r: &Rlp) -> Result<Self, DecoderError> {
+		if r.size() == 32 {
+			r.as_val().map(HashOrNumber::Hash)
+		} else {
+			r.as_val().map(HashOrNumber::Number)
+		}
+	}
+}
+
+/// `GetBlockHeaders` packet.
+///
+/// Layout: `[ block: { P , B_32 }, maxHeaders: P, skip: P, reverse: P in { 0 , 1 } ]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GetBlockHeaders {
+	/// First block to return.
+	pub start: HashOrNumber,
+	/// Maximum number of headers to return.
+	pub max_headers: u64,
+	/// Number of blocks to skip between returned headers.
+	pub skip: u64,
+	/// Whether to walk towards the genesis.
+	pub reverse: bool,
+}
+
+impl Encodable for GetBlockHeaders {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(4);
+		s.append(&self.start);
+		s.append(&self.max_headers);
+		s.append(&self.skip);
+		s.append(&self.reverse);
+	}
+}
+
+impl Decodable for GetBlockHeaders {
+	fn decode(r: &Rlp) -> Result<Self, DecoderError> {
+		Ok(GetBlockHeaders {
+			start: r.val_at(0)?,
+			max_headers: r.val_at(1)?,
+			skip: r.val_at(2)?,
+			reverse: r.val_at(3)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_block_headers_matches_wire_layout() {
+		let hash = H256::from_low_u64_be(42);
+		let mut raw = RlpStream::new_list(4);
+		raw.append(&hash);
+		raw.append(&192u64);
+		raw.append(&0u64);
+		raw.append(&1u32);
+		let raw = raw.out();
+
+		let request = GetBlockHeaders {
+			start: HashOrNumber::Hash(hash),
+			max_headers: 192,
+			skip: 0,
+			reverse: true,
+		};
+		assert_eq!(rlp::encode(&request), raw);
+		assert_eq!(rlp::decode::<GetBlockHeaders>(&raw).unwrap(), request);
+
+		let request = GetBlockHeaders { start: HashOrNumber::Number(1_000_000), ..request };
+		assert_eq!(rlp::decode::<GetBlockHeaders>(&rlp::encode(&request)).unwrap(), request);
+	}
+
+	#[test]
+	fn status_roundtrips_with_optional_fields() {
+		let eth = Status {
+			protocol_version: 63,
+			network_id: 1,
+			total_difficulty: 1_000.into(),
+			best_hash: H256::from_low_u64_be(1),
+			genesis_hash: H256::from_low_u64_be(2),
+			snapshot: None,
+			private_tx_enabled: None,
+		};
+		assert_eq!(Rlp::new(&rlp::encode(&eth)).item_count().unwrap(), 5);
+		assert_eq!(Status::decode(&Rlp::new(&rlp::encode(&eth)), false).unwrap(), eth);
+		assert!(Status::decode(&Rlp::new(&rlp::encode(&eth)), true).is_err());
+
+		let warp = Status {
+			protocol_version: 3,
+			snapshot: Some((H256::from_low_u64_be(3), 500)),
+			private_tx_enabled: Some(true),
+			..eth
+		};
+		assert_eq!(Rlp::new(&rlp::encode(&warp)).item_count().unwrap(), 8);
+		assert_eq!(Status::decode(&Rlp::new(&rlp::encode(&warp)), true).unwrap(), warp);
+	}
+
+	#[test]
+	fn status_ignores_unknown_eth_fields() {
+		let mut raw = RlpStream::new_list(6);
+		raw.append(&64u32);
+		raw.append(&1u64);
+		raw.append(&U256::from(1_000));
+		raw.append(&H256::from_low_u64_be(1));
+		raw.append(&H256::from_low_u64_be(2));
+		// eth/64 fork id
+		raw.begin_list(2).append(&vec![0xfcu8, 0x64, 0xec, 0x04]).append(&1_150_000u64);
+		let raw = raw.out();
+
+		let status = Status::decode(&Rlp::new(&raw), false).unwrap();
+		assert_eq!(status.protocol_version, 64);
+		assert_eq!(status.snapshot, None);
+	}
+}
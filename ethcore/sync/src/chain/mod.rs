@@ -87,7 +87,10 @@
 //!
 //! All other messages are ignored.
 
+mod bandwidth;
+mod capability;
 mod handler;
+mod message;
 mod propagator;
 mod requester;
 mod supplier;
@@ -101,7 +104,7 @@ use std::time::{Duration, Instant};
 
 use crate::{
 	EthProtocolInfo as PeerInfoDigest, PriorityTask, SyncConfig, WarpSync, WARP_SYNC_PROTOCOL_ID,
-	api::{Notification, PRIORITY_TIMER_INTERVAL},
+	api::{LocalTransactionsRelay, Notification, PRIORITY_TIMER_INTERVAL},
 	block_sync::{BlockDownloader, DownloadAction},
 	sync_io::SyncIo,
 	snapshot_sync::Snapshot,
@@ -121,7 +124,7 @@ use network::{self, PeerId, PacketId};
 use parity_util_mem::{MallocSizeOfExt, malloc_size_of_is_0};
 use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
 use rand::{Rng, seq::SliceRandom};
-use rlp::{RlpStream, DecoderError};
+use rlp::{Rlp, RlpStream, DecoderError};
 use common_types::{
 	BlockNumber,
 	ids::BlockId,
@@ -130,18 +133,24 @@ use common_types::{
 	blockchain_info::BlockChainInfo,
 	block_status::BlockStatus,
 	snapshot::RestorationStatus,
+	view,
+	views::BlockView,
 };
 
 use self::handler::SyncHandler;
+use self::message::Status;
 use self::sync_packet::{PacketInfo, SyncPacket};
 use self::sync_packet::SyncPacket::{
+	GetReceiptsPacket,
 	NewBlockPacket,
 	StatusPacket,
 };
 
 use self::propagator::SyncPropagator;
 use self::requester::SyncRequester;
-pub(crate) use self::supplier::SyncSupplier;
+pub(crate) use self::supplier::{SyncSupplier, ServedData};
+pub(crate) use self::capability::EthVersion;
+pub use self::bandwidth::{Bandwidth, BandwidthLimits};
 
 malloc_size_of_is_0!(PeerInfo);
 
@@ -169,6 +178,14 @@ const MAX_NODE_DATA_TOTAL_DURATION: Duration = Duration::from_secs(2);
 /// Maximum allowed duration for serving a single GetNodeData request.
 const MAX_NODE_DATA_SINGLE_DURATION: Duration = Duration::from_millis(100);
 pub const MAX_RECEIPTS_HEADERS_TO_SEND: usize = 256;
+/// Maximum amount of node data and receipts served to a single peer within `SERVED_DATA_WINDOW`.
+const MAX_SERVED_BYTES_PER_WINDOW: usize = 32 * 1024 * 1024;
+/// Accounting window for `MAX_SERVED_BYTES_PER_WINDOW`.
+const SERVED_DATA_WINDOW: Duration = Duration::from_secs(10);
+/// Weight of the latest sample in the moving average of a peer's block bodies throughput.
+const BODIES_THROUGHPUT_SMOOTHING: f64 = 0.3;
+/// Weight of the latest sample in the moving average of a peer's response latency.
+const LATENCY_SMOOTHING: f64 = 0.3;
 const MIN_PEERS_PROPAGATION: usize = 4;
 const MAX_PEERS_PROPAGATION: usize = 128;
 const MAX_PEER_LAG_PROPAGATION: BlockNumber = 20;
@@ -183,6 +200,9 @@ const SNAPSHOT_RESTORE_THRESHOLD: BlockNumber = 30000;
 /// snapshot available from `SNAPSHOT_MIN_PEERS` peers within `WAIT_PEERS_TIMEOUT`, then we make do
 /// with a single peer to sync from.
 const SNAPSHOT_MIN_PEERS: usize = 3;
+/// Number of peers which have to report their total difficulty for the network's one to be noted.
+/// The median is noted, so that a single peer can't make us look behind.
+const NETWORK_DIFFICULTY_MIN_PEERS: usize = 3;
 /// To keep memory from growing uncontrollably we restore chunks as we download them and write them
 /// to disk only after we have processed them; we also want to avoid pausing the chunk download too
 /// often, so we allow a little bit of leeway here and let the downloading be
@@ -203,6 +223,8 @@ const FORK_HEADER_TIMEOUT: Duration = Duration::from_secs(3);
 const SNAPSHOT_MANIFEST_TIMEOUT: Duration = Duration::from_secs(5);
 const SNAPSHOT_DATA_TIMEOUT: Duration = Duration::from_secs(120);
 const PRIVATE_STATE_TIMEOUT: Duration = Duration::from_secs(120);
+/// Number of block download requests in a row a peer may fail to answer in time before it's disconnected.
+const MAX_REQUEST_TIMEOUTS: usize = 2;
 
 /// Defines how much time we have to complete priority transaction or block propagation.
 /// after the deadline is reached the task is considered finished
@@ -261,6 +283,12 @@ pub struct SyncStatus {
 	pub snapshot_chunks_done: usize,
 	/// Last fully downloaded and imported ancient block number (if any).
 	pub last_imported_old_block_number: Option<BlockNumber>,
+	/// Total sync protocol bytes received.
+	pub bytes_received: u64,
+	/// Total sync protocol bytes sent.
+	pub bytes_sent: u64,
+	/// Number of blocks currently requested from peers.
+	pub num_blocks_in_flight: usize,
 }
 
 impl SyncStatus {
@@ -291,6 +319,31 @@ impl SyncStatus {
 	}
 }
 
+/// Progress of the block download, sent to subscribers every sync maintenance round.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SyncProgress {
+	/// State
+	pub state: SyncState,
+	/// Percentage of headers downloaded between the sync start and the highest known block.
+	pub headers_percent: f64,
+	/// Percentage of bodies downloaded between the sync start and the highest known block.
+	pub bodies_percent: f64,
+	/// Number of blocks waiting in the verification queue.
+	pub verification_backlog: usize,
+	/// Blocks imported per second since the previous event.
+	pub import_rate: f64,
+}
+
+/// Share of the `start..target` range covered up to `done`, in percent.
+/// Unknown while no peer has reported its best block.
+fn download_percent(start: BlockNumber, done: BlockNumber, target: Option<BlockNumber>) -> f64 {
+	match target {
+		Some(target) if target > start => (done.saturating_sub(start) as f64 * 100.0 / (target - start) as f64).min(100.0),
+		Some(_) => 100.0,
+		None => 0.0,
+	}
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 /// Peer data type requested from a peer by us.
 pub enum PeerAsking {
@@ -304,6 +357,35 @@ pub enum PeerAsking {
 	PrivateState,
 }
 
+impl PeerAsking {
+	/// Time to wait for the response to this kind of request before giving up on the peer.
+	fn timeout(&self) -> Option<Duration> {
+		match *self {
+			PeerAsking::Nothing => None,
+			PeerAsking::ForkHeader => Some(FORK_HEADER_TIMEOUT),
+			PeerAsking::BlockHeaders => Some(HEADERS_TIMEOUT),
+			PeerAsking::BlockBodies => Some(BODIES_TIMEOUT),
+			PeerAsking::BlockReceipts => Some(RECEIPTS_TIMEOUT),
+			PeerAsking::SnapshotManifest => Some(SNAPSHOT_MANIFEST_TIMEOUT),
+			PeerAsking::SnapshotData => Some(SNAPSHOT_DATA_TIMEOUT),
+			PeerAsking::PrivateState => Some(PRIVATE_STATE_TIMEOUT),
+		}
+	}
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+/// A request outstanding to a peer.
+pub struct PendingRequest {
+	/// Peer the request was sent to.
+	pub peer_id: PeerId,
+	/// Type of data requested.
+	pub asking: PeerAsking,
+	/// Number of blocks covered by a bodies or receipts request.
+	pub blocks: usize,
+	/// Time after which the request is considered lost.
+	pub deadline: Instant,
+}
+
 #[derive(PartialEq, Eq, Debug, Clone, Copy, MallocSizeOf)]
 /// Block downloader channel.
 pub enum BlockSet {
@@ -327,12 +409,16 @@ pub enum ForkConfirmation {
 pub struct PeerInfo {
 	/// eth protocol version
 	protocol_version: u8,
+	/// `eth` subprotocol version negotiated with the peer
+	eth_version: EthVersion,
 	/// Peer chain genesis hash
 	genesis: H256,
 	/// Peer network id
 	network_id: u64,
 	/// Peer best block hash
 	latest_hash: H256,
+	/// Peer best block number, if announced
+	latest_number: Option<BlockNumber>,
 	/// Peer total difficulty if known
 	difficulty: Option<U256>,
 	/// Type of data currently being requested by us from a peer.
@@ -363,6 +449,14 @@ pub struct PeerInfo {
 	snapshot_number: Option<BlockNumber>,
 	/// Block set requested
 	block_set: Option<BlockSet>,
+	/// Measured block bodies per second delivered by the peer, if any were requested yet
+	bodies_throughput: Option<f64>,
+	/// Moving average of the time the peer takes to answer our requests, if any were answered yet
+	latency: Option<Duration>,
+	/// Number of block download requests in a row the peer failed to answer in time
+	timeouts: usize,
+	/// Rolling average of the session's RLPx ping round-trip time, if measured yet
+	ping: Option<Duration>,
 	/// Version of the software the peer is running
 	client_version: ClientVersion,
 }
@@ -376,6 +470,40 @@ impl PeerInfo {
 		self.confirmation != ForkConfirmation::Unconfirmed && !self.expired
 	}
 
+	fn note_bodies_received(&mut self, count: usize, elapsed: Duration) {
+		let secs = elapsed.as_secs_f64().max(0.001);
+		let sample = count as f64 / secs;
+		self.bodies_throughput = Some(match self.bodies_throughput {
+			Some(current) => current + BODIES_THROUGHPUT_SMOOTHING * (sample - current),
+			None => sample,
+		});
+	}
+
+	fn note_response(&mut self) {
+		let sample = self.ask_time.elapsed().as_secs_f64();
+		let smoothed = match self.latency {
+			Some(current) => current.as_secs_f64() + LATENCY_SMOOTHING * (sample - current.as_secs_f64()),
+			None => sample,
+		};
+		self.latency = Some(Duration::from_secs_f64(smoothed));
+		self.timeouts = 0;
+	}
+
+	fn note_head(&mut self, hash: H256, number: Option<BlockNumber>) {
+		self.latest_hash = hash;
+		self.latest_number = number;
+	}
+
+	fn pending_request(&self, peer_id: PeerId) -> Option<PendingRequest> {
+		let timeout = self.asking.timeout()?;
+		Some(PendingRequest {
+			peer_id,
+			asking: self.asking.clone(),
+			blocks: self.asking_blocks.len(),
+			deadline: self.ask_time + timeout,
+		})
+	}
+
 	fn reset_asking(&mut self) {
 		self.asking_blocks.clear();
 		self.asking_hash = None;
@@ -420,6 +548,8 @@ pub struct ChainSyncApi {
 	priority_tasks: Mutex<mpsc::Receiver<PriorityTask>>,
 	/// The rest of sync data
 	sync: RwLock<ChainSync>,
+	/// Last known synchronization status, served when `sync` is busy
+	status: RwLock<SyncStatus>,
 }
 
 impl ChainSyncApi {
@@ -430,8 +560,11 @@ impl ChainSyncApi {
 		private_tx_handler: Option<Arc<dyn PrivateTxHandler>>,
 		priority_tasks: mpsc::Receiver<PriorityTask>,
 	) -> Self {
+		let sync = ChainSync::new(config, chain, private_tx_handler);
+		let status = sync.status();
 		ChainSyncApi {
-			sync: RwLock::new(ChainSync::new(config, chain, private_tx_handler)),
+			sync: RwLock::new(sync),
+			status: RwLock::new(status),
 			priority_tasks: Mutex::new(priority_tasks),
 		}
 	}
@@ -447,9 +580,30 @@ impl ChainSyncApi {
 		ids.iter().map(|id| sync.peer_info(id)).collect()
 	}
 
-	/// Returns synchonization status
+	/// Returns synchonization status.
+	///
+	/// Never waits for the sync lock: if it is currently held by packet processing
+	/// the last snapshot is returned instead.
 	pub fn status(&self) -> SyncStatus {
-		self.sync.read().status()
+		match self.sync.try_read() {
+			Some(sync) => {
+				let status = sync.status();
+				*self.status.write() = status;
+				status
+			},
+			None => *self.status.read(),
+		}
+	}
+
+	/// Refreshes the status snapshot returned by `status` while the sync lock is busy.
+	pub fn refresh_status(&self) {
+		let status = self.sync.read().status();
+		*self.status.write() = status;
+	}
+
+	/// Returns the traffic meter shared with the network IO.
+	pub fn bandwidth(&self) -> Arc<Bandwidth> {
+		self.sync.read().bandwidth.clone()
 	}
 
 	/// Returns transactions propagation statistics
@@ -510,12 +664,13 @@ impl ChainSyncApi {
 					let chain_info = io.chain().chain_info();
 					let total_difficulty = chain_info.total_difficulty + difficulty;
 					let rlp = ChainSync::create_block_rlp(&block, total_difficulty);
+					let number = view!(BlockView, &block).header_view().number();
 					for peers in sync.get_peers(&chain_info, PeerState::SameBlock).chunks(10) {
 						check_deadline(deadline)?;
 						for peer in peers {
 							SyncPropagator::send_packet(io, *peer, NewBlockPacket, rlp.clone());
 							if let Some(ref mut peer) = sync.peers.get_mut(peer) {
-								peer.latest_hash = hash;
+								peer.note_head(hash, Some(number));
 							}
 						}
 					}
@@ -671,6 +826,11 @@ pub struct ChainSync {
 	sync_start_time: Option<Instant>,
 	/// Transactions propagation statistics
 	transactions_stats: TransactionsStats,
+	/// Propagation of local transactions.
+	#[ignore_malloc_size_of = "plain configuration"]
+	local_transactions_relay: LocalTransactionsRelay,
+	/// Held back local transactions and when they may be announced next.
+	local_transactions_due: HashMap<H256, Instant>,
 	/// Enable ancient block downloading
 	download_old_blocks: bool,
 	/// Shared private tx service.
@@ -678,9 +838,19 @@ pub struct ChainSync {
 	private_tx_handler: Option<Arc<dyn PrivateTxHandler>>,
 	/// Enable warp sync.
 	warp_sync: WarpSync,
+	/// Node data and receipts served to each peer.
+	#[ignore_malloc_size_of = "per-peer counters only"]
+	served_data: Mutex<ServedData>,
+	/// Traffic counters and rate limits.
+	#[ignore_malloc_size_of = "counters only"]
+	bandwidth: Arc<Bandwidth>,
 
 	#[ignore_malloc_size_of = "mpsc unmettered, ignoring"]
-	status_sinks: Vec<futures_mpsc::UnboundedSender<SyncState>>
+	status_sinks: Vec<futures_mpsc::UnboundedSender<SyncState>>,
+	#[ignore_malloc_size_of = "mpsc unmettered, ignoring"]
+	progress_sinks: Vec<futures_mpsc::UnboundedSender<SyncProgress>>,
+	/// Best block number and time of the last progress event.
+	last_progress: Option<(BlockNumber, Instant)>,
 }
 
 impl ChainSync {
@@ -710,9 +880,15 @@ impl ChainSync {
 			snapshot: Snapshot::new(),
 			sync_start_time: None,
 			transactions_stats: TransactionsStats::default(),
+			local_transactions_relay: config.local_transactions_relay,
+			local_transactions_due: HashMap::new(),
 			private_tx_handler,
 			warp_sync: config.warp_sync,
-			status_sinks: Vec::new()
+			served_data: Mutex::new(ServedData::default()),
+			bandwidth: Arc::new(Bandwidth::new(config.bandwidth)),
+			status_sinks: Vec::new(),
+			progress_sinks: Vec::new(),
+			last_progress: None,
 		};
 		sync.update_targets(chain);
 		sync
@@ -723,7 +899,7 @@ impl ChainSync {
 		let last_imported_number = self.new_blocks.last_imported_block_number();
 		SyncStatus {
 			state: self.state.clone(),
-			protocol_version: ETH_PROTOCOL_VERSION_63.0,
+			protocol_version: EthVersion::LATEST.number(),
 			network_id: self.network_id,
 			start_block_number: self.starting_block,
 			last_imported_block_number: Some(last_imported_number),
@@ -736,9 +912,20 @@ impl ChainSync {
 			num_snapshot_chunks: self.snapshot.total_chunks(),
 			snapshot_chunks_done: self.snapshot.done_chunks(),
 			mem_used: self.malloc_size_of(),
+			bytes_received: self.bandwidth.bytes_received(),
+			bytes_sent: self.bandwidth.bytes_sent(),
+			num_blocks_in_flight: self.peers.values().filter(|p| p.asking != PeerAsking::Nothing).map(|p| p.asking_blocks.len()).sum(),
 		}
 	}
 
+	/// Returns the `eth` version negotiated with a peer. Falls back to the network layer
+	/// for peers whose status packet has not been processed yet.
+	fn peer_eth_version(&self, io: &dyn SyncIo, peer_id: PeerId) -> Option<EthVersion> {
+		self.peers.get(&peer_id)
+			.map(|peer| peer.eth_version)
+			.or_else(|| EthVersion::from_u8(io.eth_protocol_version(peer_id)))
+	}
+
 	/// Returns information on peers connections
 	pub fn peer_info(&self, peer_id: &PeerId) -> Option<PeerInfoDigest> {
 		self.peers.get(peer_id).map(|peer_data| {
@@ -746,6 +933,16 @@ impl ChainSync {
 				version: peer_data.protocol_version as u32,
 				difficulty: peer_data.difficulty,
 				head: peer_data.latest_hash,
+				head_number: peer_data.latest_number,
+				latency: peer_data.latency,
+				asking: match peer_data.asking {
+					PeerAsking::Nothing => None,
+					ref asking => Some(format!("{:?}", asking)),
+				},
+				asking_blocks: match peer_data.asking {
+					PeerAsking::Nothing => 0,
+					_ => peer_data.asking_blocks.len(),
+				},
 			}
 		})
 	}
@@ -784,6 +981,43 @@ impl ChainSync {
 		});
 	}
 
+	/// returns the receiving end of a future::mpsc channel that is sent
+	/// the download progress every sync maintenance round.
+	pub fn progress_notifications(&mut self) -> Notification<SyncProgress> {
+		let (sender, receiver) = futures_mpsc::unbounded();
+		self.progress_sinks.push(sender);
+		receiver
+	}
+
+	/// Notify all subscribers of the current download progress
+	fn notify_progress(&mut self, io: &dyn SyncIo) {
+		if self.progress_sinks.is_empty() {
+			return;
+		}
+
+		let now = Instant::now();
+		let best_block = io.chain().chain_info().best_block_number;
+		let import_rate = match self.last_progress {
+			Some((number, at)) if now > at => best_block.saturating_sub(number) as f64 / (now - at).as_secs_f64(),
+			_ => 0.0,
+		};
+		self.last_progress = Some((best_block, now));
+
+		let (headers, bodies) = self.new_blocks.downloaded_block_numbers();
+		let progress = SyncProgress {
+			state: self.state,
+			headers_percent: download_percent(self.starting_block, headers, self.highest_block),
+			bodies_percent: download_percent(self.starting_block, bodies, self.highest_block),
+			verification_backlog: io.chain().queue_info().total_queue_size(),
+			import_rate,
+		};
+
+		// remove any sender whose receiving end has been dropped
+		self.progress_sinks.retain(|sender| {
+			sender.unbounded_send(progress).is_ok()
+		});
+	}
+
 	/// sets a new SyncState
 	fn set_state(&mut self, state: SyncState) {
 		self.notify_sync_state(state);
@@ -1009,8 +1243,17 @@ impl ChainSync {
 				);
 
 				peers.shuffle(&mut random::new()); // TODO (#646): sort by rating
-				// prefer peers with higher protocol version
-				peers.sort_by(|&(_, ref v1), &(_, ref v2)| v1.cmp(v2));
+				if self.state == SyncState::Idle || self.state == SyncState::NewBlocks {
+					// new blocks at the head of the chain are requested from the closest peers first,
+					// then by protocol version
+					peers.sort_by_key(|&(peer_id, version)| (self.peer_ping_rank(peer_id), version));
+				} else {
+					// prefer peers with higher protocol version
+					peers.sort_by(|&(_, ref v1), &(_, ref v2)| v1.cmp(v2));
+				}
+				// peers that recently failed to answer in time are asked last, so that the blocks
+				// they dropped go to other peers first
+				peers.sort_by_key(|&(peer_id, _)| self.peers.get(&peer_id).map_or(0, |peer| peer.timeouts));
 
 				for (peer_id, _) in peers {
 					self.sync_peer(io, peer_id, false);
@@ -1054,9 +1297,14 @@ impl ChainSync {
 				return;
 			}
 		};
+		if !self.bandwidth.can_download(Instant::now()) {
+			trace!(target: "sync", "Download rate limit reached, not requesting from {}", peer_id);
+			return;
+		}
 		let chain_info = io.chain().chain_info();
 		let syncing_difficulty = chain_info.pending_total_difficulty;
 		let num_active_peers = self.peers.values().filter(|p| p.asking != PeerAsking::Nothing).count();
+		let bodies_weight = self.bodies_request_weight(peer_id);
 
 		let higher_difficulty = peer_difficulty.map_or(true, |pd| pd > syncing_difficulty);
 		if force || higher_difficulty || self.old_blocks.is_some() {
@@ -1083,7 +1331,7 @@ impl ChainSync {
 					if !have_latest && (higher_difficulty || force || self.state == SyncState::NewBlocks) {
 						// check if got new blocks to download
 						trace!(target: "sync", "Syncing with peer {}, force={}, td={:?}, our td={}, state={:?}", peer_id, force, peer_difficulty, syncing_difficulty, self.state);
-						if let Some(request) = self.new_blocks.request_blocks(peer_id, io, num_active_peers) {
+						if let Some(request) = self.new_blocks.request_blocks(peer_id, io, num_active_peers, bodies_weight) {
 							SyncRequester::request_blocks(self, io, peer_id, request, BlockSet::NewBlocks);
 							if self.state == SyncState::Idle {
 								self.set_state(SyncState::Blocks);
@@ -1095,8 +1343,13 @@ impl ChainSync {
 					// Only ask for old blocks if the peer has an equal or higher difficulty
 					let equal_or_higher_difficulty = peer_difficulty.map_or(true, |pd| pd >= syncing_difficulty);
 
-					if force || equal_or_higher_difficulty {
-						if let Some(request) = self.old_blocks.as_mut().and_then(|d| d.request_blocks(peer_id, io, num_active_peers)) {
+					// Old blocks are downloaded together with their receipts
+					let serves_receipts = self.peers.get(&peer_id).map_or(false, |p| p.eth_version.supports(GetReceiptsPacket));
+
+					if (force || equal_or_higher_difficulty) && !serves_receipts {
+						trace!(target: "sync", "peer {:?} does not serve receipts, not requesting old blocks", peer_id);
+					} else if force || equal_or_higher_difficulty {
+						if let Some(request) = self.old_blocks.as_mut().and_then(|d| d.request_blocks(peer_id, io, num_active_peers, bodies_weight)) {
 							SyncRequester::request_blocks(self, io, peer_id, request, BlockSet::OldBlocks);
 							return;
 						}
@@ -1231,6 +1484,7 @@ impl ChainSync {
 				peer.asking = PeerAsking::Nothing;
 				return false;
 			} else {
+				peer.note_response();
 				peer.asking = PeerAsking::Nothing;
 				return true;
 			}
@@ -1243,66 +1497,112 @@ impl ChainSync {
 		let warp_protocol_version = io.protocol_version(&WARP_SYNC_PROTOCOL_ID, peer);
 		let warp_protocol = warp_protocol_version != 0;
 		let private_tx_protocol = warp_protocol_version >= PAR_PROTOCOL_VERSION_3.0;
-		let protocol = if warp_protocol { warp_protocol_version } else { ETH_PROTOCOL_VERSION_63.0 };
+		let protocol = if warp_protocol {
+			warp_protocol_version
+		} else {
+			EthVersion::from_u8(io.eth_protocol_version(peer)).unwrap_or(EthVersion::LATEST).number()
+		};
 		trace!(target: "sync", "Sending status to {}, protocol version {}", peer, protocol);
-		let mut packet = RlpStream::new();
-		packet.begin_unbounded_list();
 		let chain = io.chain().chain_info();
-		packet.append(&(protocol as u32));
-		packet.append(&self.network_id);
-		packet.append(&chain.total_difficulty);
-		packet.append(&chain.best_block_hash);
-		packet.append(&chain.genesis_hash);
-		if warp_protocol {
+		let snapshot = if warp_protocol {
 			let manifest = io.snapshot_service().manifest();
 			let block_number = manifest.as_ref().map_or(0, |m| m.block_number);
 			let manifest_hash = manifest.map_or(H256::zero(), |m| keccak(m.into_rlp()));
-			packet.append(&manifest_hash);
-			packet.append(&block_number);
-			if private_tx_protocol {
-				packet.append(&self.private_tx_handler.is_some());
-			}
-		}
-		packet.finalize_unbounded_list();
-		io.respond(StatusPacket.id(), packet.out())
+			Some((manifest_hash, block_number))
+		} else {
+			None
+		};
+		let status = Status {
+			protocol_version: protocol,
+			network_id: self.network_id,
+			total_difficulty: chain.total_difficulty,
+			best_hash: chain.best_block_hash,
+			genesis_hash: chain.genesis_hash,
+			snapshot,
+			private_tx_enabled: if private_tx_protocol { Some(self.private_tx_handler.is_some()) } else { None },
+		};
+		io.respond(StatusPacket.id(), rlp::encode(&status))
 	}
 
-	/// Check if any tasks we have on-going with a peer is taking too long (if so, disconnect them).
+	/// Check if any tasks we have on-going with a peer is taking too long. Timed out block downloads
+	/// are handed over to other peers; peers timing out repeatedly or on other requests are disconnected.
 	/// Also checks handshaking peers.
 	/// Called every `PEERS_TIMER` (0.7sec).
 	pub fn maintain_peers(&mut self, io: &mut dyn SyncIo) {
 		let tick = Instant::now();
+		let mut reassigned = false;
 		let mut aborting = Vec::new();
-		for (peer_id, peer) in &self.peers {
-			let elapsed = tick - peer.ask_time;
-			let timeout = match peer.asking {
-				PeerAsking::BlockHeaders => elapsed > HEADERS_TIMEOUT,
-				PeerAsking::BlockBodies => elapsed > BODIES_TIMEOUT,
-				PeerAsking::BlockReceipts => elapsed > RECEIPTS_TIMEOUT,
-				PeerAsking::Nothing => false,
-				PeerAsking::ForkHeader => elapsed > FORK_HEADER_TIMEOUT,
-				PeerAsking::SnapshotManifest => elapsed > SNAPSHOT_MANIFEST_TIMEOUT,
-				PeerAsking::SnapshotData => elapsed > SNAPSHOT_DATA_TIMEOUT,
-				PeerAsking::PrivateState => elapsed > PRIVATE_STATE_TIMEOUT,
+		for request in self.pending_requests().into_iter().filter(|request| tick > request.deadline) {
+			let retry = match request.asking {
+				PeerAsking::BlockHeaders | PeerAsking::BlockBodies | PeerAsking::BlockReceipts =>
+					self.peers.get(&request.peer_id).map_or(false, |peer| peer.timeouts + 1 < MAX_REQUEST_TIMEOUTS),
+				_ => false,
 			};
-			if timeout {
-				debug!(target:"sync", "Peer {} timeout while we were asking them for {:?}; disconnecting.", peer_id, peer.asking);
-				io.disconnect_peer(*peer_id);
-				aborting.push(*peer_id);
+			if retry {
+				debug!(target:"sync", "Peer {} timeout while we were asking them for {:?} ({} blocks); reassigning.",
+					request.peer_id, request.asking, request.blocks);
+				self.clear_peer_download(request.peer_id);
+				if let Some(peer) = self.peers.get_mut(&request.peer_id) {
+					// a late response is ignored, since we're no longer asking for anything. With nothing
+					// asked `reset_asking` doesn't mark the peer expired, so it can be asked again right away.
+					peer.asking = PeerAsking::Nothing;
+					peer.reset_asking();
+					peer.block_set = None;
+					peer.timeouts += 1;
+				}
+				reassigned = true;
+			} else {
+				debug!(target:"sync", "Peer {} timeout while we were asking them for {:?} ({} blocks); disconnecting.",
+					request.peer_id, request.asking, request.blocks);
+				io.disconnect_peer(request.peer_id);
+				aborting.push(request.peer_id);
 			}
 		}
+		// `on_peer_aborting` releases the blocks or chunks the peer was asked for and continues sync.
 		for p in aborting {
 			SyncHandler::on_peer_aborting(self, io, p);
 		}
+		if reassigned {
+			self.continue_sync(io);
+		}
 
 		// Check for handshake timeouts
 		for (peer, &ask_time) in &self.handshaking_peers {
-			let elapsed = (tick - ask_time) / 1_000_000_000;
-			if elapsed > STATUS_TIMEOUT {
+			if tick - ask_time > STATUS_TIMEOUT {
 				trace!(target:"sync", "Status timeout {}", peer);
 				io.disconnect_peer(*peer);
 			}
 		}
+
+		// Pick up the latest ping measurements of the network sessions
+		for (peer_id, peer) in self.peers.iter_mut() {
+			if let Some(ping) = io.peer_session_info(*peer_id).and_then(|info| info.ping_average) {
+				peer.ping = Some(ping);
+			}
+		}
+	}
+
+	/// Sort key ordering peers by their session ping, peers not measured yet last.
+	fn peer_ping_rank(&self, peer_id: PeerId) -> (bool, Duration) {
+		match self.peers.get(&peer_id).and_then(|p| p.ping) {
+			Some(ping) => (false, ping),
+			None => (true, Duration::from_secs(0)),
+		}
+	}
+
+	/// Share of the largest block bodies request the peer should be sent, in proportion to its
+	/// measured throughput relative to the fastest peer. Peers not measured yet get the full size.
+	fn bodies_request_weight(&self, peer_id: PeerId) -> f64 {
+		let fastest = self.peers.values().filter_map(|p| p.bodies_throughput).fold(0.0, f64::max);
+		match self.peers.get(&peer_id).and_then(|p| p.bodies_throughput) {
+			Some(throughput) if fastest > 0.0 => throughput / fastest,
+			_ => 1.0,
+		}
+	}
+
+	/// Returns the requests currently outstanding to peers.
+	pub fn pending_requests(&self) -> Vec<PendingRequest> {
+		self.peers.iter().filter_map(|(id, peer)| peer.pending_request(*id)).collect()
 	}
 
 	fn check_resume(&mut self, io: &mut dyn SyncIo) {
@@ -1407,6 +1707,17 @@ impl ChainSync {
 	pub fn maintain_sync(&mut self, io: &mut dyn SyncIo) {
 		self.maybe_start_snapshot_sync(io);
 		self.check_resume(io);
+		self.notify_progress(io);
+		self.note_network_difficulty(io);
+	}
+
+	/// Let the client know how far ahead the network is, so that it doesn't go to sleep while behind.
+	fn note_network_difficulty(&self, io: &mut dyn SyncIo) {
+		let mut difficulties = self.peers.values().filter_map(|p| p.difficulty).collect::<Vec<_>>();
+		if difficulties.len() >= NETWORK_DIFFICULTY_MIN_PEERS {
+			difficulties.sort();
+			io.chain().note_network_difficulty(difficulties[difficulties.len() / 2]);
+		}
 	}
 
 	/// called when block is imported to chain - propagates the blocks and updates transactions sent to peers
@@ -1490,15 +1801,17 @@ impl ChainSync {
 
 #[cfg(test)]
 pub mod tests {
-	use std::{collections::VecDeque, time::Instant};
+	use std::{collections::VecDeque, time::{Duration, Instant}};
 
 	use super::{
-		BlockId, BlockQueueInfo, ChainSync, ClientVersion, PeerInfo, PeerAsking,
-		SyncHandler, SyncState, SyncStatus, SyncPropagator, UnverifiedTransaction
+		BlockId, BlockQueueInfo, BlockSet, ChainSync, ClientVersion, EthVersion, PeerInfo, PeerAsking, PendingRequest,
+		SyncHandler, SyncRequester, SyncState, SyncStatus, SyncPropagator, UnverifiedTransaction, BODIES_TIMEOUT,
+		download_percent,
 	};
 
 	use crate::{
 		api::SyncConfig,
+		block_sync::BlockRequest,
 		tests::{helpers::TestIo, snapshot::TestSnapshotService},
 	};
 
@@ -1507,6 +1820,7 @@ pub mod tests {
 	use ethcore::test_helpers::{EachBlockWith, TestBlockChainClient};
 	use ethcore::miner::{MinerService, PendingOrdering};
 	use ethereum_types::{H256, U256, Address};
+	use futures::Stream;
 	use network::PeerId;
 	use parking_lot::RwLock;
 	use rlp::{Rlp, RlpStream};
@@ -1578,6 +1892,9 @@ pub mod tests {
 			num_snapshot_chunks: 0,
 			snapshot_chunks_done: 0,
 			last_imported_old_block_number: None,
+			bytes_received: 0,
+			bytes_sent: 0,
+			num_blocks_in_flight: 0,
 		}
 	}
 
@@ -1604,9 +1921,11 @@ pub mod tests {
 		sync.peers.insert(peer_id,
 			PeerInfo {
 				protocol_version: 0,
+				eth_version: EthVersion::LATEST,
 				genesis: H256::zero(),
 				network_id: 0,
 				latest_hash: peer_latest_hash,
+				latest_number: None,
 				difficulty: None,
 				asking: PeerAsking::Nothing,
 				asking_blocks: Vec::new(),
@@ -1622,11 +1941,174 @@ pub mod tests {
 				snapshot_hash: None,
 				asking_snapshot_data: None,
 				block_set: None,
+				bodies_throughput: None,
+				latency: None,
+				timeouts: 0,
+				ping: None,
 				client_version: ClientVersion::from(""),
 			});
 
 	}
 
+	#[test]
+	fn tracks_pending_requests() {
+		let client = TestBlockChainClient::new();
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		assert!(sync.pending_requests().is_empty());
+
+		let ask_time = Instant::now();
+		{
+			let peer = sync.peers.get_mut(&0).unwrap();
+			peer.asking = PeerAsking::BlockBodies;
+			peer.asking_blocks = vec![H256::zero(), H256::from_low_u64_be(1)];
+			peer.ask_time = ask_time;
+		}
+
+		assert_eq!(sync.pending_requests(), vec![PendingRequest {
+			peer_id: 0,
+			asking: PeerAsking::BlockBodies,
+			blocks: 2,
+			deadline: ask_time + BODIES_TIMEOUT,
+		}]);
+	}
+
+	#[test]
+	fn weights_bodies_requests_by_throughput() {
+		let client = TestBlockChainClient::new();
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		insert_dummy_peer(&mut sync, 1, H256::zero());
+		insert_dummy_peer(&mut sync, 2, H256::zero());
+
+		sync.peers.get_mut(&0).unwrap().note_bodies_received(100, Duration::from_secs(1));
+		sync.peers.get_mut(&1).unwrap().note_bodies_received(25, Duration::from_secs(1));
+
+		assert_eq!(sync.bodies_request_weight(0), 1.0);
+		assert_eq!(sync.bodies_request_weight(1), 0.25);
+		assert_eq!(sync.bodies_request_weight(2), 1.0);
+	}
+
+	#[test]
+	fn reports_peer_requests_and_latency() {
+		let client = TestBlockChainClient::new();
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		{
+			let peer = sync.peers.get_mut(&0).unwrap();
+			peer.note_head(H256::from_low_u64_be(5), Some(5));
+			peer.asking = PeerAsking::BlockBodies;
+			peer.asking_blocks = vec![H256::zero(), H256::zero()];
+			peer.ask_time = Instant::now() - Duration::from_millis(100);
+		}
+
+		let info = sync.peer_info(&0).unwrap();
+		assert_eq!(info.head, H256::from_low_u64_be(5));
+		assert_eq!(info.head_number, Some(5));
+		assert_eq!(info.asking, Some("BlockBodies".to_owned()));
+		assert_eq!(info.asking_blocks, 2);
+		assert_eq!(info.latency, None);
+		assert_eq!(sync.status().num_blocks_in_flight, 2);
+
+		assert!(sync.reset_peer_asking(0, PeerAsking::BlockBodies));
+		let info = sync.peer_info(&0).unwrap();
+		assert_eq!(info.asking, None);
+		assert!(info.latency.unwrap() >= Duration::from_millis(100));
+	}
+
+	#[test]
+	fn ranks_peers_by_session_ping() {
+		let client = TestBlockChainClient::new();
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		insert_dummy_peer(&mut sync, 1, H256::zero());
+		insert_dummy_peer(&mut sync, 2, H256::zero());
+
+		sync.peers.get_mut(&0).unwrap().ping = Some(Duration::from_millis(300));
+		sync.peers.get_mut(&2).unwrap().ping = Some(Duration::from_millis(40));
+
+		let mut peers: Vec<PeerId> = vec![0, 1, 2];
+		peers.sort_by_key(|&peer_id| sync.peer_ping_rank(peer_id));
+		assert_eq!(peers, vec![2, 0, 1]);
+	}
+
+	#[test]
+	fn reassigns_timed_out_downloads() {
+		let mut client = TestBlockChainClient::new();
+		let queue = RwLock::new(VecDeque::new());
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		let ss = TestSnapshotService::new();
+		let timed_out = |sync: &mut ChainSync| {
+			let peer = sync.peers.get_mut(&0).unwrap();
+			peer.asking = PeerAsking::BlockBodies;
+			peer.asking_blocks = vec![H256::from_low_u64_be(1)];
+			peer.ask_time = Instant::now() - BODIES_TIMEOUT - Duration::from_secs(1);
+		};
+
+		// the first timeout releases the blocks but keeps the peer
+		timed_out(&mut sync);
+		{
+			let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+			sync.maintain_peers(&mut io);
+			assert!(io.to_disconnect.is_empty());
+		}
+		assert_eq!(sync.peers[&0].timeouts, 1);
+		assert!(sync.peers[&0].asking_blocks.is_empty());
+		assert!(sync.peers[&0].can_sync());
+		assert!(sync.pending_requests().is_empty());
+
+		// another one in a row disconnects it
+		timed_out(&mut sync);
+		let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+		sync.maintain_peers(&mut io);
+		assert!(io.to_disconnect.contains(&0));
+		assert!(!sync.peers.contains_key(&0));
+	}
+
+	#[test]
+	fn notes_median_network_difficulty() {
+		let mut client = TestBlockChainClient::new();
+		let queue = RwLock::new(VecDeque::new());
+		let mut sync = dummy_sync_with_peer(H256::zero(), &client);
+		insert_dummy_peer(&mut sync, 1, H256::zero());
+		insert_dummy_peer(&mut sync, 2, H256::zero());
+		sync.peers.get_mut(&0).unwrap().difficulty = Some(300.into());
+		sync.peers.get_mut(&1).unwrap().difficulty = Some(U256::max_value());
+		let ss = TestSnapshotService::new();
+
+		// too few peers to agree on the difficulty of the network
+		sync.maintain_sync(&mut TestIo::new(&mut client, &ss, &queue, None, None));
+		assert_eq!(*client.network_difficulty.read(), None);
+
+		// a single peer claiming a huge difficulty is outvoted
+		sync.peers.get_mut(&2).unwrap().difficulty = Some(500.into());
+		sync.maintain_sync(&mut TestIo::new(&mut client, &ss, &queue, None, None));
+		assert_eq!(*client.network_difficulty.read(), Some(500.into()));
+	}
+
+	#[test]
+	fn notifies_download_progress() {
+		assert_eq!(download_percent(10, 15, Some(20)), 50.0);
+		assert_eq!(download_percent(10, 25, Some(20)), 100.0);
+		assert_eq!(download_percent(10, 10, Some(5)), 100.0);
+		assert_eq!(download_percent(10, 10, None), 0.0);
+
+		let mut client = TestBlockChainClient::new();
+		client.add_blocks(10, EachBlockWith::Nothing);
+		let queue = RwLock::new(VecDeque::new());
+		let mut sync = dummy_sync_with_peer(client.block_hash_delta_minus(1), &client);
+		let progress = sync.progress_notifications();
+		sync.highest_block = Some(30);
+		let ss = TestSnapshotService::new();
+		let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+
+		sync.maintain_sync(&mut io);
+		drop(sync);
+
+		let events = progress.wait().collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(events.len(), 1);
+		assert_eq!(events[0].headers_percent, 0.0);
+		assert_eq!(events[0].bodies_percent, 0.0);
+		assert_eq!(events[0].verification_backlog, 0);
+		assert_eq!(events[0].import_rate, 0.0);
+	}
+
 	#[test]
 	fn finds_lagging_peers() {
 		let mut client = TestBlockChainClient::new();
@@ -1695,6 +2177,29 @@ pub mod tests {
 		assert!(result.is_ok());
 	}
 
+	#[test]
+	fn does_not_request_receipts_from_eth62_peers() {
+		let mut client = TestBlockChainClient::new();
+		client.add_blocks(10, EachBlockWith::Nothing);
+		let queue = RwLock::new(VecDeque::new());
+		let mut sync = dummy_sync_with_peer(client.block_hash_delta_minus(1), &client);
+		let hashes = vec![client.block_hash_delta_minus(2)];
+		let ss = TestSnapshotService::new();
+		let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+
+		sync.peers.get_mut(&0).unwrap().eth_version = EthVersion::Eth62;
+		SyncRequester::request_blocks(&mut sync, &mut io, 0, BlockRequest::Receipts { hashes: hashes.clone() }, BlockSet::OldBlocks);
+		assert!(io.packets.is_empty());
+		assert_eq!(sync.peers[&0].asking, PeerAsking::Nothing);
+		assert!(sync.peers[&0].asking_blocks.is_empty());
+		assert_eq!(sync.peers[&0].block_set, None);
+
+		sync.peers.get_mut(&0).unwrap().eth_version = EthVersion::Eth63;
+		SyncRequester::request_blocks(&mut sync, &mut io, 0, BlockRequest::Receipts { hashes }, BlockSet::OldBlocks);
+		assert_eq!(io.packets.len(), 1);
+		assert_eq!(sync.peers[&0].asking, PeerAsking::BlockReceipts);
+	}
+
 	#[test]
 	fn should_add_transactions_to_queue() {
 		fn sender(tx: &UnverifiedTransaction) -> Address {
@@ -16,8 +16,9 @@
 
 use std::cmp;
 use std::collections::HashSet;
+use std::time::Instant;
 
-use crate::{sync_io::SyncIo, chain::sync_packet::SyncPacket};
+use crate::{api::LocalTransactionsRelay, sync_io::SyncIo, chain::sync_packet::SyncPacket};
 
 use bytes::Bytes;
 use ethereum_types::H256;
@@ -25,7 +26,7 @@ use fastmap::H256FastSet;
 use log::{debug, error, trace};
 use network::client_version::ClientCapabilities;
 use network::PeerId;
-use rand::RngCore;
+use rand::{RngCore, seq::SliceRandom};
 use rlp::{Encodable, RlpStream};
 use common_types::{blockchain_info::BlockChainInfo, transaction::SignedTransaction, BlockNumber};
 
@@ -58,7 +59,7 @@ impl SyncPropagator {
 				SyncPropagator::send_packet(io, *peer_id, NewBlockPacket, rlp.clone());
 
 				if let Some(ref mut peer) = sync.peers.get_mut(peer_id) {
-					peer.latest_hash = chain_info.best_block_hash.clone();
+					peer.note_head(chain_info.best_block_hash.clone(), Some(chain_info.best_block_number));
 				}
 			}
 		};
@@ -89,7 +90,7 @@ impl SyncPropagator {
 		let sent = peers.len();
 		for peer_id in peers {
 			if let Some(ref mut peer) = sync.peers.get_mut(peer_id) {
-				peer.latest_hash = best_block_hash;
+				peer.note_head(best_block_hash, Some(chain_info.best_block_number));
 			}
 			SyncPropagator::send_packet(io, *peer_id, NewBlockHashesPacket, rlp.clone());
 		}
@@ -105,6 +106,7 @@ impl SyncPropagator {
 
 		let transactions = io.chain().transactions_to_propagate();
 		if transactions.is_empty() {
+			sync.local_transactions_due.clear();
 			return 0;
 		}
 
@@ -112,17 +114,23 @@ impl SyncPropagator {
 			return 0;
 		}
 
-		let (transactions, service_transactions): (Vec<_>, Vec<_>) = transactions.iter()
+		let delay_local = sync.local_transactions_relay != LocalTransactionsRelay::Immediate;
+		let (local_transactions, transactions): (Vec<_>, Vec<_>) = transactions.iter()
+			.partition(|tx| delay_local && tx.is_local());
+		let mut affected_peers = SyncPropagator::relay_local_transactions(
+			sync, io, local_transactions.into_iter().map(|tx| tx.signed()).collect(),
+		);
+
+		let (transactions, service_transactions): (Vec<_>, Vec<_>) = transactions.into_iter()
 			.map(|tx| tx.signed())
 			.partition(|tx| !tx.gas_price.is_zero());
 
 		// usual transactions could be propagated to all peers
-		let mut affected_peers = HashSet::new();
 		if !transactions.is_empty() {
 			let peers = SyncPropagator::select_peers_for_transactions(sync, |_| true);
-			affected_peers = SyncPropagator::propagate_transactions_to_peers(
+			affected_peers.extend(&SyncPropagator::propagate_transactions_to_peers(
 				sync, io, peers, transactions, &mut should_continue,
-			);
+			));
 		}
 
 		// most of times service_transactions will be empty
@@ -138,6 +146,53 @@ impl SyncPropagator {
 		affected_peers.len()
 	}
 
+	/// Announces each due local transaction to a few random peers, holding back the others.
+	fn relay_local_transactions(
+		sync: &mut ChainSync,
+		io: &mut dyn SyncIo,
+		transactions: Vec<&SignedTransaction>,
+	) -> HashSet<PeerId> {
+		let (delay, peers_count) = match sync.local_transactions_relay {
+			LocalTransactionsRelay::Delayed { delay, peers } => (delay, peers),
+			LocalTransactionsRelay::Immediate => return HashSet::new(),
+		};
+
+		let pending = transactions.iter().map(|tx| tx.hash()).collect::<H256FastSet>();
+		sync.local_transactions_due.retain(|hash, _| pending.contains(hash));
+
+		let now = Instant::now();
+		let all_peers = sync.peers.keys().cloned().collect::<Vec<_>>();
+		let mut sent_to_peers = HashSet::new();
+		for tx in transactions {
+			let hash = tx.hash();
+			let due = sync.local_transactions_due.entry(hash).or_insert(now + delay);
+			if *due > now {
+				continue;
+			}
+			// announce again after another delay if it's still not mined by then
+			*due = now + delay;
+
+			let mut peers = all_peers.iter()
+				.cloned()
+				.filter(|peer_id| !tx.gas_price.is_zero() || io.peer_version(*peer_id).accepts_service_transaction())
+				.collect::<Vec<_>>();
+			peers.shuffle(&mut random::new());
+			peers.truncate(peers_count);
+
+			let rlp = {
+				let mut packet = RlpStream::new_list(1);
+				packet.append(tx);
+				packet.out()
+			};
+			for peer_id in peers {
+				SyncPropagator::send_packet(io, peer_id, TransactionsPacket, rlp.clone());
+				trace!(target: "sync", "{:02} <- Local transaction {:?}", peer_id, hash);
+				sent_to_peers.insert(peer_id);
+			}
+		}
+		sent_to_peers
+	}
+
 	fn propagate_transactions_to_peers<F: FnMut() -> bool>(
 		sync: &mut ChainSync,
 		io: &mut dyn SyncIo,
@@ -334,11 +389,11 @@ impl SyncPropagator {
 
 #[cfg(test)]
 mod tests {
-	use std::{collections::VecDeque, time::Instant};
+	use std::{collections::VecDeque, time::{Duration, Instant}};
 
 	use crate::{
-		api::SyncConfig,
-		chain::{ChainSync, ForkConfirmation, PeerAsking, PeerInfo},
+		api::{LocalTransactionsRelay, SyncConfig},
+		chain::{ChainSync, EthVersion, ForkConfirmation, PeerAsking, PeerInfo},
 		tests::{helpers::TestIo, snapshot::TestSnapshotService},
 	};
 
@@ -348,12 +403,13 @@ mod tests {
 	};
 
 	use client_traits::{BlockChainClient, BlockInfo, ChainInfo};
-	use ethcore::test_helpers::{EachBlockWith, TestBlockChainClient};
+	use ethcore::{miner::MinerService, test_helpers::{EachBlockWith, TestBlockChainClient}};
 	use ethereum_types::{H256, U256};
 	use network::client_version::ClientVersion;
+	use parity_crypto::publickey::{Generator, Random};
 	use parking_lot::RwLock;
 	use rlp::Rlp;
-	use common_types::{ids::BlockId, transaction::UnverifiedTransaction};
+	use common_types::{ids::BlockId, transaction::{Action, Transaction, UnverifiedTransaction}};
 
 	#[test]
 	fn sends_new_hashes_to_lagging_peer() {
@@ -428,9 +484,11 @@ mod tests {
 			PeerInfo {
 				// Messaging protocol
 				protocol_version: 2,
+				eth_version: EthVersion::LATEST,
 				genesis: H256::zero(),
 				network_id: 0,
 				latest_hash: client.block_hash_delta_minus(1),
+				latest_number: None,
 				difficulty: None,
 				asking: PeerAsking::Nothing,
 				asking_blocks: Vec::new(),
@@ -446,6 +504,10 @@ mod tests {
 				snapshot_hash: None,
 				asking_snapshot_data: None,
 				block_set: None,
+				bodies_throughput: None,
+				latency: None,
+				timeouts: 0,
+				ping: None,
 				client_version: ClientVersion::from(""),
 			});
 		let ss = TestSnapshotService::new();
@@ -528,6 +590,41 @@ mod tests {
 		assert_eq!(0, peer_count2);
 	}
 
+	#[test]
+	fn holds_back_local_transactions() {
+		let mut client = TestBlockChainClient::new();
+		client.add_blocks(100, EachBlockWith::Uncle);
+		let keypair = Random.generate().unwrap();
+		let tx = Transaction {
+			action: Action::Create,
+			value: U256::from(100),
+			data: Vec::new(),
+			gas: U256::from(100_000),
+			gas_price: U256::from(20_000_000_000u64),
+			nonce: U256::zero(),
+		}.sign(keypair.secret(), None);
+		let hash = tx.hash();
+		client.set_balance(tx.sender(), 10_000_000_000_000_000_000u64.into());
+		client.miner.import_own_transaction(&client, tx.into()).unwrap();
+		let mut sync = dummy_sync_with_peer(client.block_hash_delta_minus(1), &client);
+		sync.local_transactions_relay = LocalTransactionsRelay::Delayed { delay: Duration::from_secs(60), peers: 1 };
+		let queue = RwLock::new(VecDeque::new());
+		let ss = TestSnapshotService::new();
+		let mut io = TestIo::new(&mut client, &ss, &queue, None, None);
+
+		// held back until due
+		assert_eq!(0, SyncPropagator::propagate_new_transactions(&mut sync, &mut io, || true));
+		assert_eq!(0, io.packets.len());
+
+		sync.local_transactions_due.insert(hash, Instant::now() - Duration::from_secs(1));
+		assert_eq!(1, SyncPropagator::propagate_new_transactions(&mut sync, &mut io, || true));
+		assert_eq!(1, io.packets.len());
+
+		// and then not again before another delay
+		assert_eq!(0, SyncPropagator::propagate_new_transactions(&mut sync, &mut io, || true));
+		assert_eq!(1, io.packets.len());
+	}
+
 	#[test]
 	fn propagates_transactions_without_alternating() {
 		let mut client = TestBlockChainClient::new();
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::mem;
 use std::time::Instant;
 
 use crate::{
@@ -28,6 +29,7 @@ use network::{PeerId};
 use rlp::RlpStream;
 use common_types::BlockNumber;
 
+use super::message::{GetBlockHeaders, HashOrNumber};
 use super::sync_packet::SyncPacket;
 use super::sync_packet::SyncPacket::{
 	GetBlockHeadersPacket,
@@ -70,21 +72,22 @@ impl SyncRequester {
 		for h in &hashes {
 			rlp.append(&h.clone());
 		}
-		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockBodies, GetBlockBodiesPacket, rlp.out());
 		let peer = sync.peers.get_mut(&peer_id).expect("peer_id may originate either from on_packet, where it is already validated or from enumerating self.peers. qed");
 		peer.asking_blocks = hashes;
 		peer.block_set = Some(set);
+		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockBodies, GetBlockBodiesPacket, rlp.out());
 	}
 
 	/// Request headers from a peer by block number
 	pub fn request_fork_header(sync: &mut ChainSync, io: &mut dyn SyncIo, peer_id: PeerId, n: BlockNumber) {
 		trace!(target: "sync", "{} <- GetForkHeader: at {}", peer_id, n);
-		let mut rlp = RlpStream::new_list(4);
-		rlp.append(&n);
-		rlp.append(&1u32);
-		rlp.append(&0u32);
-		rlp.append(&0u32);
-		SyncRequester::send_request(sync, io, peer_id, PeerAsking::ForkHeader, GetBlockHeadersPacket, rlp.out());
+		let request = GetBlockHeaders {
+			start: HashOrNumber::Number(n),
+			max_headers: 1,
+			skip: 0,
+			reverse: false,
+		};
+		SyncRequester::send_request(sync, io, peer_id, PeerAsking::ForkHeader, GetBlockHeadersPacket, rlp::encode(&request));
 	}
 
 	/// Find some headers or blocks to download from a peer.
@@ -116,15 +119,16 @@ impl SyncRequester {
 	/// Request headers from a peer by block hash
 	fn request_headers_by_hash(sync: &mut ChainSync, io: &mut dyn SyncIo, peer_id: PeerId, h: &H256, count: u64, skip: u64, reverse: bool, set: BlockSet) {
 		trace!(target: "sync", "{} <- GetBlockHeaders: {} entries starting from {}, set = {:?}", peer_id, count, h, set);
-		let mut rlp = RlpStream::new_list(4);
-		rlp.append(h);
-		rlp.append(&count);
-		rlp.append(&skip);
-		rlp.append(&if reverse {1u32} else {0u32});
-		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockHeaders, GetBlockHeadersPacket, rlp.out());
+		let request = GetBlockHeaders {
+			start: HashOrNumber::Hash(*h),
+			max_headers: count,
+			skip,
+			reverse,
+		};
 		let peer = sync.peers.get_mut(&peer_id).expect("peer_id may originate either from on_packet, where it is already validated or from enumerating self.peers. qed");
 		peer.asking_hash = Some(h.clone());
 		peer.block_set = Some(set);
+		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockHeaders, GetBlockHeadersPacket, rlp::encode(&request));
 	}
 
 	/// Request block receipts from a peer
@@ -134,10 +138,10 @@ impl SyncRequester {
 		for h in &hashes {
 			rlp.append(&h.clone());
 		}
-		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockReceipts, GetReceiptsPacket, rlp.out());
 		let peer = sync.peers.get_mut(&peer_id).expect("peer_id may originate either from on_packet, where it is already validated or from enumerating self.peers. qed");
 		peer.asking_blocks = hashes;
 		peer.block_set = Some(set);
+		SyncRequester::send_request(sync, io, peer_id, PeerAsking::BlockReceipts, GetReceiptsPacket, rlp.out());
 	}
 
 	/// Request snapshot chunk from a peer.
@@ -150,6 +154,15 @@ impl SyncRequester {
 
 	/// Generic request sender
 	fn send_request(sync: &mut ChainSync, io: &mut dyn SyncIo, peer_id: PeerId, asking: PeerAsking, packet_id: SyncPacket, packet: Bytes) {
+		let eth_version = match sync.peers.get(&peer_id) {
+			Some(peer) => peer.eth_version,
+			None => return,
+		};
+		if !eth_version.supports(packet_id) {
+			debug!(target:"sync", "{} <- Not sending {:?}, unsupported by eth/{}", peer_id, packet_id, eth_version.number());
+			SyncRequester::release_request(sync, peer_id, asking);
+			return;
+		}
 		if let Some(ref mut peer) = sync.peers.get_mut(&peer_id) {
 			if peer.asking != PeerAsking::Nothing {
 				warn!(target:"sync", "Asking {:?} while requesting {:?}", peer.asking, asking);
@@ -165,4 +178,21 @@ impl SyncRequester {
 			}
 		}
 	}
+
+	/// Unmark the blocks or snapshot chunk reserved for a request that wasn't sent, so that
+	/// they can be requested from other peers.
+	fn release_request(sync: &mut ChainSync, peer_id: PeerId, asking: PeerAsking) {
+		let previous = match sync.peers.get_mut(&peer_id) {
+			Some(peer) => mem::replace(&mut peer.asking, asking),
+			None => return,
+		};
+		sync.clear_peer_download(peer_id);
+		if let Some(peer) = sync.peers.get_mut(&peer_id) {
+			peer.asking = previous;
+			peer.asking_blocks.clear();
+			peer.asking_hash = None;
+			peer.asking_snapshot_data = None;
+			peer.block_set = None;
+		}
+	}
 }
@@ -15,6 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::cmp;
+use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
 use crate::sync_io::SyncIo;
@@ -28,6 +29,7 @@ use parking_lot::RwLock;
 use rlp::{Rlp, RlpStream};
 use common_types::{ids::BlockId, BlockNumber};
 
+use super::message::{GetBlockHeaders, HashOrNumber};
 use super::sync_packet::{PacketInfo, SyncPacket};
 use super::sync_packet::SyncPacket::{
 	StatusPacket,
@@ -60,8 +62,77 @@ use super::{
 	MAX_NODE_DATA_TOTAL_DURATION,
 	MAX_NODE_DATA_SINGLE_DURATION,
 	MAX_RECEIPTS_HEADERS_TO_SEND,
+	MAX_SERVED_BYTES_PER_WINDOW,
+	SERVED_DATA_WINDOW,
 };
 
+/// Amount of traffic accounted over a fixed-length window, which restarts once it's over.
+pub(crate) struct TrafficWindow {
+	length: Duration,
+	start: Instant,
+	amount: usize,
+}
+
+impl TrafficWindow {
+	/// Create an empty window of given length starting at `now`.
+	pub fn new(length: Duration, now: Instant) -> Self {
+		TrafficWindow { length, start: now, amount: 0 }
+	}
+
+	/// Amount accounted in the window that is current at `now`.
+	pub fn used(&self, now: Instant) -> usize {
+		if now >= self.start + self.length { 0 } else { self.amount }
+	}
+
+	/// Accounts `amount` at `now`, starting a new window if the previous one is over.
+	pub fn add(&mut self, amount: usize, now: Instant) {
+		if now >= self.start + self.length {
+			self.start = now;
+			self.amount = 0;
+		}
+		self.amount += amount;
+	}
+}
+
+/// Per-peer accounting of the state and receipts data we serve, used to keep a single
+/// fast-syncing peer from monopolising the state database.
+#[derive(Default)]
+pub struct ServedData {
+	peers: HashMap<PeerId, PeerServedData>,
+}
+
+struct PeerServedData {
+	window: TrafficWindow,
+	total_bytes: u64,
+}
+
+impl ServedData {
+	/// Returns true if the peer has used up its allowance for the current window.
+	pub fn is_throttled(&self, peer: PeerId, now: Instant) -> bool {
+		self.peers.get(&peer).map_or(false, |served| served.window.used(now) >= MAX_SERVED_BYTES_PER_WINDOW)
+	}
+
+	/// Records `bytes` sent to the peer.
+	pub fn note_served(&mut self, peer: PeerId, bytes: usize, now: Instant) {
+		let served = self.peers.entry(peer).or_insert_with(|| PeerServedData {
+			window: TrafficWindow::new(SERVED_DATA_WINDOW, now),
+			total_bytes: 0,
+		});
+		served.window.add(bytes, now);
+		served.total_bytes += bytes as u64;
+	}
+
+	/// Total number of bytes served to the peer since it connected.
+	pub fn total_served(&self, peer: PeerId) -> u64 {
+		self.peers.get(&peer).map_or(0, |served| served.total_bytes)
+	}
+
+	/// Forgets a disconnected peer.
+	pub fn remove(&mut self, peer: PeerId) {
+		self.peers.remove(&peer);
+	}
+}
+
 /// The Chain Sync Supplier: answers requests from peers with available data
 pub struct SyncSupplier;
 
@@ -73,6 +144,23 @@ impl SyncSupplier {
 		let rlp = Rlp::new(data);
 
 		if let Some(id) = SyncPacket::from_u8(packet_id) {
+			if let Some(version) = sync.read().peer_eth_version(io, peer) {
+				if !version.supports(id) {
+					debug!(target: "sync", "{} -> Packet {:?} is not part of eth/{}", peer, id, version.number());
+					return;
+				}
+			}
+
+			let is_request = match id {
+				GetBlockBodiesPacket | GetBlockHeadersPacket | GetReceiptsPacket | GetNodeDataPacket |
+				GetSnapshotManifestPacket | GetSnapshotDataPacket | GetPrivateStatePacket => true,
+				_ => false,
+			};
+			if is_request && !sync.read().bandwidth.allow_request(peer, Instant::now()) {
+				trace!(target: "sync", "{} -> Rate limit reached, ignoring {:?}", peer, id);
+				return;
+			}
+
 			let result = match id {
 				GetBlockBodiesPacket => SyncSupplier::return_rlp(
 					io, &rlp, peer,
@@ -84,13 +172,13 @@ impl SyncSupplier {
 					SyncSupplier::return_block_headers,
 					|e| format!("Error sending block headers: {:?}", e)),
 
-				GetReceiptsPacket => SyncSupplier::return_rlp(
-					io, &rlp, peer,
+				GetReceiptsPacket => SyncSupplier::return_throttled_rlp(
+					sync, io, &rlp, peer, ReceiptsPacket,
 					SyncSupplier::return_receipts,
 					|e| format!("Error sending receipts: {:?}", e)),
 
-				GetNodeDataPacket => SyncSupplier::return_rlp(
-					io, &rlp, peer,
+				GetNodeDataPacket => SyncSupplier::return_throttled_rlp(
+					sync, io, &rlp, peer, NodeDataPacket,
 					SyncSupplier::return_node_data,
 					|e| format!("Error sending nodes: {:?}", e)),
 
@@ -154,37 +242,36 @@ impl SyncSupplier {
 	/// Respond to GetBlockHeaders request
 	fn return_block_headers(io: &dyn SyncIo, r: &Rlp, peer_id: PeerId) -> RlpResponseResult {
 		let payload_soft_limit = io.payload_soft_limit();
-		// Packet layout:
-		// [ block: { P , B_32 }, maxHeaders: P, skip: P, reverse: P in { 0 , 1 } ]
-		let max_headers: usize = r.val_at(1)?;
-		let skip: usize = r.val_at(2)?;
-		let reverse: bool = r.val_at(3)?;
+		let request: GetBlockHeaders = r.as_val()?;
+		let max_headers = request.max_headers as usize;
+		let skip = request.skip as usize;
+		let reverse = request.reverse;
 		let last = io.chain().chain_info().best_block_number;
-		let number = if r.at(0)?.size() == 32 {
-			// id is a hash
-			let hash: H256 = r.val_at(0)?;
-			trace!(target: "sync", "{} -> GetBlockHeaders (hash: {}, max: {}, skip: {}, reverse:{})", peer_id, hash, max_headers, skip, reverse);
-			match io.chain().block_header(BlockId::Hash(hash)) {
-				Some(hdr) => {
-					let number = hdr.number().into();
-					debug_assert_eq!(hdr.hash(), hash);
-
-					if max_headers == 1 || io.chain().block_hash(BlockId::Number(number)) != Some(hash) {
-						// Non canonical header or single header requested
-						// TODO: handle single-step reverse hashchains of non-canon hashes
-						trace!(target:"sync", "Returning single header: {:?}", hash);
-						let mut rlp = RlpStream::new_list(1);
-						rlp.append_raw(&hdr.into_inner(), 1);
-						return Ok(Some((BlockHeadersPacket.id(), rlp)));
+		let number = match request.start {
+			HashOrNumber::Hash(hash) => {
+				trace!(target: "sync", "{} -> GetBlockHeaders (hash: {}, max: {}, skip: {}, reverse:{})", peer_id, hash, max_headers, skip, reverse);
+				match io.chain().block_header(BlockId::Hash(hash)) {
+					Some(hdr) => {
+						let number = hdr.number().into();
+						debug_assert_eq!(hdr.hash(), hash);
+
+						if max_headers == 1 || io.chain().block_hash(BlockId::Number(number)) != Some(hash) {
+							// Non canonical header or single header requested
+							// TODO: handle single-step reverse hashchains of non-canon hashes
+							trace!(target:"sync", "Returning single header: {:?}", hash);
+							let mut rlp = RlpStream::new_list(1);
+							rlp.append_raw(&hdr.into_inner(), 1);
+							return Ok(Some((BlockHeadersPacket.id(), rlp)));
+						}
+						number
 					}
-					number
+					None => return Ok(Some((BlockHeadersPacket.id(), RlpStream::new_list(0)))) //no such header, return nothing
 				}
-				None => return Ok(Some((BlockHeadersPacket.id(), RlpStream::new_list(0)))) //no such header, return nothing
-			}
-		} else {
-			let number = r.val_at::<BlockNumber>(0)?;
-			trace!(target: "sync", "{} -> GetBlockHeaders (number: {}, max: {}, skip: {}, reverse:{})", peer_id, number, max_headers, skip, reverse);
-			number
+			},
+			HashOrNumber::Number(number) => {
+				trace!(target: "sync", "{} -> GetBlockHeaders (number: {}, max: {}, skip: {}, reverse:{})", peer_id, number, max_headers, skip, reverse);
+				number
+			},
 		};
 
 		let mut number = if reverse {
@@ -405,11 +492,42 @@ impl SyncSupplier {
 			_ => Ok(())
 		}
 	}
+
+	/// Like `return_rlp`, but accounts the response against the peer's serving allowance
+	/// and answers with an empty `empty_response` once the allowance is used up.
+	fn return_throttled_rlp<FRlp, FError>(
+		sync: &RwLock<ChainSync>,
+		io: &mut dyn SyncIo,
+		rlp: &Rlp,
+		peer: PeerId,
+		empty_response: SyncPacket,
+		rlp_func: FRlp,
+		error_func: FError,
+	) -> Result<(), PacketDecodeError>
+		where FRlp : Fn(&dyn SyncIo, &Rlp, PeerId) -> RlpResponseResult,
+			FError : FnOnce(network::Error) -> String
+	{
+		let now = Instant::now();
+		let response = if sync.read().served_data.lock().is_throttled(peer, now) {
+			trace!(target: "sync", "{} -> Serving allowance used up, sending empty {:?}", peer, empty_response);
+			Some((empty_response.id(), RlpStream::new_list(0)))
+		} else {
+			rlp_func(io, rlp, peer)?
+		};
+
+		if let Some((packet_id, rlp_stream)) = response {
+			let data = rlp_stream.out();
+			sync.read().served_data.lock().note_served(peer, data.len(), now);
+			io.respond(packet_id, data).unwrap_or_else(
+				|e| debug!(target: "sync", "{:?}", error_func(e)));
+		}
+		Ok(())
+	}
 }
 
 #[cfg(test)]
 mod test {
-	use std::{collections::VecDeque, str::FromStr};
+	use std::{collections::VecDeque, str::FromStr, time::Instant};
 
 	use crate::{
 		blocks::SyncHeader,
@@ -419,7 +537,8 @@ mod test {
 
 	use super::{
 		SyncPacket::{GetReceiptsPacket, GetNodeDataPacket},
-		BlockNumber, BlockId, SyncSupplier, PacketInfo
+		BlockNumber, BlockId, SyncSupplier, PacketInfo, ServedData,
+		MAX_SERVED_BYTES_PER_WINDOW, SERVED_DATA_WINDOW,
 	};
 
 	use super::super::tests::dummy_sync_with_peer;
@@ -606,4 +725,24 @@ mod test {
 		SyncSupplier::dispatch_packet(&RwLock::new(sync), &mut io, 0usize, GetReceiptsPacket.id(), &receipts_request);
 		assert_eq!(1, io.packets.len());
 	}
+
+	#[test]
+	fn served_data_throttles_within_window() {
+		let mut served = ServedData::default();
+		let now = Instant::now();
+
+		assert!(!served.is_throttled(0, now));
+		served.note_served(0, MAX_SERVED_BYTES_PER_WINDOW, now);
+		assert!(served.is_throttled(0, now));
+		assert!(!served.is_throttled(1, now));
+
+		let later = now + SERVED_DATA_WINDOW;
+		assert!(!served.is_throttled(0, later));
+		served.note_served(0, 10, later);
+		assert!(!served.is_throttled(0, later));
+		assert_eq!(served.total_served(0), MAX_SERVED_BYTES_PER_WINDOW as u64 + 10);
+
+		served.remove(0);
+		assert_eq!(served.total_served(0), 0);
+	}
 }
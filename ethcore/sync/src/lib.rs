@@ -35,11 +35,19 @@ mod transactions_stats;
 
 pub mod light_sync;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "test-helpers"))]
 mod tests;
 
+/// Peers running `ChainSync` over an in-memory network, for integration tests of crates
+/// built on top of sync.
+#[cfg(feature = "test-helpers")]
+pub mod test_helpers {
+	pub use crate::tests::helpers::*;
+	pub use crate::tests::snapshot::TestSnapshotService;
+}
+
 pub use api::*;
-pub use chain::{SyncStatus, SyncState};
+pub use chain::{SyncStatus, SyncState, SyncProgress, BandwidthLimits};
 pub use devp2p::validate_node_url;
 pub use network::{NonReservedPeerMode, Error, ConnectionFilter, ConnectionDirection};
 pub use private_tx::{PrivateTxHandler, NoopPrivateTxHandler, SimplePrivateTxHandler};
@@ -16,7 +16,9 @@
 
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Instant;
 
+use crate::chain::Bandwidth;
 use crate::chain::sync_packet::{PacketInfo, SyncPacket};
 
 use bytes::Bytes;
@@ -76,6 +78,7 @@ pub struct NetSyncIo<'s> {
 	chain: &'s dyn BlockChainClient,
 	snapshot_service: &'s dyn SnapshotService,
 	chain_overlay: &'s RwLock<HashMap<BlockNumber, Bytes>>,
+	bandwidth: &'s Bandwidth,
 	private_state: Option<Arc<PrivateStateDB>>,
 }
 
@@ -85,12 +88,14 @@ impl<'s> NetSyncIo<'s> {
 		chain: &'s dyn BlockChainClient,
 		snapshot_service: &'s dyn SnapshotService,
 		chain_overlay: &'s RwLock<HashMap<BlockNumber, Bytes>>,
+		bandwidth: &'s Bandwidth,
 		private_state: Option<Arc<PrivateStateDB>>) -> NetSyncIo<'s> {
 		NetSyncIo {
 			network,
 			chain,
 			snapshot_service,
 			chain_overlay,
+			bandwidth,
 			private_state,
 		}
 	}
@@ -106,10 +111,12 @@ impl<'s> SyncIo for NetSyncIo<'s> {
 	}
 
 	fn respond(&mut self, packet_id: PacketId, data: Vec<u8>) -> Result<(), Error>{
+		self.bandwidth.note_sent(data.len(), Instant::now());
 		self.network.respond(packet_id, data)
 	}
 
 	fn send(&mut self, peer_id: PeerId, packet_id: SyncPacket, data: Vec<u8>) -> Result<(), Error>{
+		self.bandwidth.note_sent(data.len(), Instant::now());
 		self.network.send_protocol(packet_id.protocol(), peer_id, packet_id.id(), data)
 	}
 
@@ -24,7 +24,7 @@ use crate::{
 
 use client_traits::{BlockChainClient, BlockInfo, ChainInfo};
 use common_types::ids::BlockId;
-use ethcore::test_helpers::{TestBlockChainClient, EachBlockWith};
+use ethcore::test_helpers::{TestBlockChainClient, EachBlockWith, ChainStep};
 
 #[test]
 fn two_peers() {
@@ -102,6 +102,41 @@ fn forked() {
 	assert_eq!(&*net.peer(2).chain.numbers.read(), &peer1_chain);
 }
 
+#[test]
+fn follows_reorg() {
+	env_logger::try_init().ok();
+	let mut net = TestNet::new(2);
+	net.peer(0).chain.add_blocks(20, EachBlockWith::Uncle);
+	net.sync();
+	assert_eq!(&*net.peer(1).chain.numbers.read(), &*net.peer(0).chain.numbers.read());
+
+	let enacted = net.peer(0).chain.reorg(5, EachBlockWith::Nothing);
+	assert_eq!(enacted.len(), 6);
+	assert_eq!(net.peer(0).chain.chain_info().best_block_number, 21);
+	assert_eq!(net.peer(0).chain.chain_info().best_block_hash, enacted[5]);
+	net.sync();
+	assert_eq!(&*net.peer(1).chain.numbers.read(), &*net.peer(0).chain.numbers.read());
+}
+
+#[test]
+fn follows_scripted_chain() {
+	env_logger::try_init().ok();
+	let mut net = TestNet::new(2);
+	let tx = net.peer(0).chain.insert_transaction_to_queue();
+	let added = net.peer(0).chain.run_script(&[
+		ChainStep::Extend(10, EachBlockWith::Uncle),
+		ChainStep::Reorg(3, EachBlockWith::Nothing),
+		ChainStep::Mine,
+		ChainStep::Extend(2, EachBlockWith::Transaction),
+	]);
+	assert_eq!(added.len(), 17);
+	assert_eq!(net.peer(0).chain.chain_info().best_block_number, 14);
+	assert_eq!(net.peer(0).chain.chain_info().best_block_hash, added[16]);
+	net.sync();
+	assert_eq!(&*net.peer(1).chain.numbers.read(), &*net.peer(0).chain.numbers.read());
+	assert_eq!(net.peer(1).chain.block(BlockId::Hash(added[14])).unwrap().transaction_hashes(), vec![tx]);
+}
+
 #[test]
 fn forked_with_misbehaving_peer() {
 	env_logger::try_init().ok();
@@ -16,8 +16,11 @@
 
 pub mod helpers;
 pub mod snapshot;
+#[cfg(test)]
 mod chain;
+#[cfg(test)]
 mod consensus;
+#[cfg(test)]
 mod private;
 
 #[cfg(feature = "ipc")]
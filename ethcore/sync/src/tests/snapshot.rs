@@ -15,14 +15,17 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::collections::HashMap;
+#[cfg(test)]
 use std::sync::Arc;
 
+#[cfg(test)]
 use crate::{
 	api::{SyncConfig, WarpSync},
 	tests::helpers::TestNet
 };
 
 use bytes::Bytes;
+#[cfg(test)]
 use ethcore::test_helpers::EachBlockWith;
 use ethereum_types::H256;
 use keccak_hash::keccak;
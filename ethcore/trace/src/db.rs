@@ -347,11 +347,16 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 	}
 
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace> {
+		self.filter_page(filter, 0, usize::max_value())
+	}
+
+	fn filter_page(&self, filter: &Filter, after: usize, count: usize) -> Vec<LocalizedTrace> {
 		let possibilities = filter.bloom_possibilities();
 		let numbers = self.db.trace_blooms()
 			.filter(filter.range.start as u64, filter.range.end as u64, &possibilities)
 			.expect("Low level database error. Some issue with disk?");
 
+		// Blocks are only read until the page is full.
 		numbers.into_iter()
 			.flat_map(|n| {
 				let number = n as BlockNumber;
@@ -361,6 +366,8 @@ impl<T> TraceDatabase for TraceDB<T> where T: DatabaseExtras {
 					.expect("Expected to find a trace. Db is probably corrupted.");
 				self.matching_block_traces(filter, traces, hash, number)
 			})
+			.skip(after)
+			.take(count)
 			.collect()
 	}
 }
@@ -597,6 +604,11 @@ mod tests {
 		assert_eq!(traces[0], create_simple_localized_trace(1, block_1.clone(), tx_1.clone()));
 		assert_eq!(traces[1], create_simple_localized_trace(2, block_2.clone(), tx_2.clone()));
 
+		let traces = tracedb.filter_page(&filter, 1, 10);
+		assert_eq!(traces, vec![create_simple_localized_trace(2, block_2.clone(), tx_2.clone())]);
+		let traces = tracedb.filter_page(&filter, 0, 1);
+		assert_eq!(traces, vec![create_simple_localized_trace(1, block_1.clone(), tx_1.clone())]);
+
 		assert!(tracedb.block_traces(0).is_some(), "Genesis trace should be always present.");
 
 		let traces = tracedb.block_traces(1).unwrap();
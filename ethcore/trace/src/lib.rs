@@ -129,4 +129,9 @@ pub trait Database {
 
 	/// Filter traces matching given filter.
 	fn filter(&self, filter: &Filter) -> Vec<LocalizedTrace>;
+
+	/// Filter traces matching given filter, skipping the first `after` and returning at most `count` of them.
+	fn filter_page(&self, filter: &Filter, after: usize, count: usize) -> Vec<LocalizedTrace> {
+		self.filter(filter).into_iter().skip(after).take(count).collect()
+	}
 }
@@ -17,7 +17,9 @@
 use trie_db::TrieFactory;
 use ethtrie::Layout;
 use account_db::Factory as AccountFactory;
-use evm::{Factory as EvmFactory};
+use std::sync::Arc;
+
+use evm::{Factory as EvmFactory, interpreter::InstructionObserver};
 use vm::{Exec, ActionParams, VersionedSchedule, Schedule};
 use wasm::WasmInterpreter;
 
@@ -52,6 +54,11 @@ impl VmFactory {
 	pub fn new(cache_size: usize) -> Self {
 		VmFactory { evm: EvmFactory::new(cache_size) }
 	}
+
+	/// Install an observer on every EVM created by this factory.
+	pub fn with_observer(self, observer: Arc<dyn InstructionObserver>) -> Self {
+		VmFactory { evm: self.evm.with_observer(observer) }
+	}
 }
 
 impl From<EvmFactory> for VmFactory {
@@ -23,7 +23,7 @@ use crate::{
 	import_route::ImportRoute,
 };
 use std::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Messages to broadcast via chain
 pub enum ChainMessageType {
@@ -118,10 +118,36 @@ impl ChainRoute {
 	pub fn route(&self) -> &[(H256, ChainRouteType)] {
 		&self.route
 	}
+
+	/// Drop blocks that end up where they started, like a block enacted and then retracted
+	/// again. Listeners only ever see the route as a whole, so such blocks didn't change for them.
+	fn net_out(self) -> Self {
+		let mut first = HashMap::new();
+		let mut last = HashMap::new();
+		for &(hash, ref route_type) in &self.route {
+			let enacted = match *route_type {
+				ChainRouteType::Enacted => true,
+				ChainRouteType::Retracted => false,
+			};
+			first.entry(hash).or_insert(enacted);
+			last.insert(hash, enacted);
+		}
+		let changed: HashSet<H256> = last.into_iter()
+			.filter(|(hash, enacted)| first[hash] == *enacted)
+			.map(|(hash, _)| hash)
+			.collect();
+
+		ChainRoute {
+			route: self.route.into_iter().filter(|(hash, _)| changed.contains(hash)).collect(),
+			enacted: self.enacted.into_iter().filter(|hash| changed.contains(hash)).collect(),
+			retracted: self.retracted.into_iter().filter(|hash| changed.contains(hash)).collect(),
+		}
+	}
 }
 
 /// Used by `ChainNotify` `new_blocks()` and contains information about new blocks imported to the
 /// chain.
+#[derive(Clone)]
 pub struct NewBlocks {
 	/// Imported blocks
 	pub imported: Vec<H256>,
@@ -160,4 +186,16 @@ impl NewBlocks {
 			has_more_blocks_to_import,
 		}
 	}
+
+	/// Fold a later notification into this one, as if both imports had happened in a single round.
+	pub fn append(&mut self, next: NewBlocks) {
+		let route = self.route.route().iter().chain(next.route.route()).cloned().collect();
+		self.route = ChainRoute::new(route).net_out();
+		self.imported.extend(next.imported);
+		self.invalid.extend(next.invalid);
+		self.sealed.extend(next.sealed);
+		self.proposed.extend(next.proposed);
+		self.duration += next.duration;
+		self.has_more_blocks_to_import = next.has_more_blocks_to_import;
+	}
 }
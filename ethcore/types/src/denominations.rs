@@ -0,0 +1,219 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Ether denominations and conversions of amounts between them.
+//!
+//! Amounts are always held in wei as `U256`, conversions never go through floating point.
+
+use std::fmt;
+use std::str::FromStr;
+
+use ethereum_types::U256;
+
+/// A denomination of ether.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+	/// 1 wei.
+	Wei,
+	/// 10^3 wei.
+	Kwei,
+	/// 10^6 wei.
+	Mwei,
+	/// 10^9 wei, also known as shannon.
+	Gwei,
+	/// 10^12 wei.
+	Szabo,
+	/// 10^15 wei.
+	Finney,
+	/// 10^18 wei.
+	Ether,
+}
+
+impl Unit {
+	/// Number of decimal places of wei in one unit.
+	pub fn decimals(&self) -> usize {
+		match *self {
+			Unit::Wei => 0,
+			Unit::Kwei => 3,
+			Unit::Mwei => 6,
+			Unit::Gwei => 9,
+			Unit::Szabo => 12,
+			Unit::Finney => 15,
+			Unit::Ether => 18,
+		}
+	}
+
+	/// Value of one unit in wei.
+	pub fn wei(&self) -> U256 {
+		U256::exp10(self.decimals())
+	}
+
+	/// Converts an amount of whole units to wei, `None` on overflow.
+	pub fn to_wei(&self, amount: U256) -> Option<U256> {
+		amount.checked_mul(self.wei())
+	}
+}
+
+impl fmt::Display for Unit {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = match *self {
+			Unit::Wei => "wei",
+			Unit::Kwei => "kwei",
+			Unit::Mwei => "mwei",
+			Unit::Gwei => "gwei",
+			Unit::Szabo => "szabo",
+			Unit::Finney => "finney",
+			Unit::Ether => "ether",
+		};
+		f.write_str(name)
+	}
+}
+
+impl FromStr for Unit {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"wei" => Ok(Unit::Wei),
+			"kwei" | "babbage" => Ok(Unit::Kwei),
+			"mwei" | "lovelace" => Ok(Unit::Mwei),
+			"gwei" | "shannon" => Ok(Unit::Gwei),
+			"szabo" | "microether" => Ok(Unit::Szabo),
+			"finney" | "milliether" => Ok(Unit::Finney),
+			"ether" | "eth" => Ok(Unit::Ether),
+			_ => Err(Error::UnknownUnit(s.into())),
+		}
+	}
+}
+
+/// Errors parsing an amount of ether.
+#[derive(Debug, Display, PartialEq, Clone, Eq)]
+pub enum Error {
+	/// The amount is not a decimal number.
+	#[display(fmt = "Invalid amount: {}", _0)]
+	InvalidAmount(String),
+	/// The unit is not a known denomination.
+	#[display(fmt = "Unknown unit: {}", _0)]
+	UnknownUnit(String),
+	/// The amount is not a whole number of wei.
+	#[display(fmt = "Amount has more than {} decimal places for {}", _0, _1)]
+	TooPrecise(usize, Unit),
+	/// The amount doesn't fit in 256 bits of wei.
+	#[display(fmt = "Amount too large")]
+	Overflow,
+}
+
+impl std::error::Error for Error {}
+
+/// Parses a decimal amount, optionally followed by a unit, to wei: `"1.5 ether"`, `"20gwei"`.
+/// Amounts without a unit are in wei.
+pub fn parse(s: &str) -> Result<U256, Error> {
+	let s = s.trim();
+	let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or_else(|| s.len());
+	let (amount, unit) = s.split_at(split);
+	let unit = match unit.trim() {
+		"" => Unit::Wei,
+		unit => unit.parse()?,
+	};
+	parse_in(amount, unit)
+}
+
+/// Parses a decimal amount of `unit` to wei.
+pub fn parse_in(amount: &str, unit: Unit) -> Result<U256, Error> {
+	let invalid = || Error::InvalidAmount(amount.into());
+	let mut parts = amount.splitn(2, '.');
+	let whole = parts.next().unwrap_or_default();
+	let fraction = parts.next().unwrap_or_default().trim_end_matches('0');
+	if whole.is_empty() && fraction.is_empty() {
+		return Err(invalid());
+	}
+	if !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+		return Err(invalid());
+	}
+	if fraction.len() > unit.decimals() {
+		return Err(Error::TooPrecise(unit.decimals(), unit));
+	}
+
+	let whole = match whole {
+		"" => U256::zero(),
+		whole => U256::from_dec_str(whole).map_err(|_| Error::Overflow)?,
+	};
+	let fraction = match fraction {
+		"" => U256::zero(),
+		fraction => U256::from_dec_str(fraction).map_err(|_| Error::Overflow)? * U256::exp10(unit.decimals() - fraction.len()),
+	};
+	unit.to_wei(whole)
+		.and_then(|wei| wei.checked_add(fraction))
+		.ok_or(Error::Overflow)
+}
+
+/// Formats an amount of wei in given unit, without trailing zeros: `1.5`.
+pub fn format(wei: U256, unit: Unit) -> String {
+	let (whole, fraction) = wei.div_mod(unit.wei());
+	if fraction.is_zero() {
+		return whole.to_string();
+	}
+	let fraction = format!("{:0>width$}", fraction.to_string(), width = unit.decimals());
+	format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// Formats an amount of wei in ether, without trailing zeros.
+pub fn format_ether(wei: U256) -> String {
+	format(wei, Unit::Ether)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_amounts_with_units() {
+		assert_eq!(parse("1.5 ether"), Ok(U256::from(1_500_000_000_000_000_000u64)));
+		assert_eq!(parse("20gwei"), Ok(U256::from(20_000_000_000u64)));
+		assert_eq!(parse("20 Shannon"), Ok(U256::from(20_000_000_000u64)));
+		assert_eq!(parse(".25 finney"), Ok(U256::from(250_000_000_000u64)));
+		assert_eq!(parse("1000"), Ok(U256::from(1000)));
+		assert_eq!(parse("1.000 wei"), Ok(U256::from(1)));
+	}
+
+	#[test]
+	fn rejects_invalid_amounts() {
+		assert_eq!(parse("ether"), Err(Error::InvalidAmount("".into())));
+		assert_eq!(parse("1.2.3 ether"), Err(Error::InvalidAmount("1.2.3".into())));
+		assert_eq!(parse("1 bitcoin"), Err(Error::UnknownUnit("bitcoin".into())));
+		assert_eq!(parse("1.5 wei"), Err(Error::TooPrecise(0, Unit::Wei)));
+		assert_eq!(parse("0.0000000001 gwei"), Err(Error::TooPrecise(9, Unit::Gwei)));
+		assert_eq!(parse("1000000000000000000000000000000000000000000000000000000000000 ether"), Err(Error::Overflow));
+	}
+
+	#[test]
+	fn formats_amounts() {
+		assert_eq!(format_ether(U256::from(1_500_000_000_000_000_000u64)), "1.5");
+		assert_eq!(format_ether(U256::from(10_000_000_000_000_000_000u64)), "10");
+		assert_eq!(format_ether(U256::from(1)), "0.000000000000000001");
+		assert_eq!(format_ether(U256::zero()), "0");
+		assert_eq!(format(U256::from(20_000_000_000u64), Unit::Gwei), "20");
+		assert_eq!(format(U256::from(12_345), Unit::Kwei), "12.345");
+	}
+
+	#[test]
+	fn roundtrips() {
+		let wei = U256::from(123_456_789_000_000u64);
+		for unit in &[Unit::Wei, Unit::Gwei, Unit::Finney, Unit::Ether] {
+			assert_eq!(parse(&format!("{} {}", format(wei, *unit), unit)), Ok(wei));
+		}
+	}
+}
@@ -0,0 +1,36 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Size on disk of the client databases.
+
+/// Size in bytes of each of the databases backing the client.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DiskUsage {
+	/// Key-value database holding the state, headers, bodies, extras and traces.
+	pub key_value: u64,
+	/// Estimated share of the key-value database taken by the state.
+	pub state: u64,
+	/// Estimated share of the key-value database taken by block headers and bodies.
+	pub blocks: u64,
+	/// Estimated share of the key-value database taken by block extras.
+	pub extras: u64,
+	/// Estimated share of the key-value database taken by traces.
+	pub traces: u64,
+	/// Header blooms database.
+	pub blooms: u64,
+	/// Trace blooms database.
+	pub trace_blooms: u64,
+}
@@ -154,6 +154,40 @@ impl CommonParams {
 		}
 	}
 
+	/// Blocks at which the EVM schedule changes, with the names of the spec parameters activating
+	/// each change, ordered by block. Transitions which never activate are left out.
+	pub fn schedule_transitions(&self) -> Vec<(BlockNumber, &'static str)> {
+		let mut transitions: Vec<_> = vec![
+			(self.eip150_transition, "eip150Transition"),
+			(self.eip160_transition, "eip160Transition"),
+			(self.eip161abc_transition, "eip161abcTransition"),
+			(self.eip161d_transition, "eip161dTransition"),
+			(self.max_code_size_transition, "maxCodeSizeTransition"),
+			(self.eip140_transition, "eip140Transition"),
+			(self.eip211_transition, "eip211Transition"),
+			(self.eip214_transition, "eip214Transition"),
+			(self.eip145_transition, "eip145Transition"),
+			(self.eip1014_transition, "eip1014Transition"),
+			(self.eip1052_transition, "eip1052Transition"),
+			(self.eip1283_transition, "eip1283Transition"),
+			(self.eip1283_disable_transition, "eip1283DisableTransition"),
+			(self.eip1283_reenable_transition, "eip1283ReenableTransition"),
+			(self.eip1344_transition, "eip1344Transition"),
+			(self.eip1706_transition, "eip1706Transition"),
+			(self.eip1884_transition, "eip1884Transition"),
+			(self.eip2028_transition, "eip2028Transition"),
+			(self.eip2200_advance_transition, "eip2200AdvanceTransition"),
+			(self.eip210_transition, "eip210Transition"),
+			(self.dust_protection_transition, "dustProtectionTransition"),
+			(self.wasm_activation_transition, "wasmActivationTransition"),
+			(self.kip4_transition, "kip4Transition"),
+			(self.kip6_transition, "kip6Transition"),
+		].into_iter().filter(|&(block, _)| block != BlockNumber::max_value()).collect();
+
+		transitions.sort();
+		transitions
+	}
+
 	/// Returns max code size at given block.
 	pub fn max_code_size(&self, block_number: u64) -> u64 {
 		if block_number >= self.max_code_size_transition {
@@ -151,6 +151,9 @@ pub enum ImportError {
 	/// Already marked as bad from a previous import (could mean parent is bad)
 	#[display(fmt = "block known to be bad")]
 	KnownBad,
+	/// Too many blocks with the same parent already in the block queue
+	#[display(fmt = "too many siblings in the block queue")]
+	ForkTooWide,
 }
 
 impl error::Error for ImportError {}
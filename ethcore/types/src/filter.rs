@@ -46,6 +46,12 @@ pub struct Filter {
 	/// If None, return all logs
 	/// If specified, should only return *last* `n` logs.
 	pub limit: Option<usize>,
+
+	/// Logs offset
+	///
+	/// If None, skip no logs
+	/// If specified, the *last* `n` logs are skipped before applying the limit.
+	pub offset: Option<usize>,
 }
 
 impl Clone for Filter {
@@ -66,6 +72,7 @@ impl Clone for Filter {
 			address: self.address.clone(),
 			topics: topics[..].to_vec(),
 			limit: self.limit,
+			offset: self.offset,
 		}
 	}
 }
@@ -123,6 +130,7 @@ mod tests {
 			address: None,
 			topics: vec![None, None, None, None],
 			limit: None,
+			offset: None,
 		};
 
 		let possibilities = none_filter.bloom_possibilities();
@@ -144,6 +152,7 @@ mod tests {
 				None,
 			],
 			limit: None,
+			offset: None,
 		};
 
 		let possibilities = filter.bloom_possibilities();
@@ -163,6 +172,7 @@ mod tests {
 				None,
 			],
 			limit: None,
+			offset: None,
 		};
 
 		let possibilities = filter.bloom_possibilities();
@@ -191,6 +201,7 @@ mod tests {
 				None
 			],
 			limit: None,
+			offset: None,
 		};
 
 		// number of possibilites should be equal 2 * 2 * 2 * 1 = 8
@@ -212,6 +223,7 @@ mod tests {
 				None,
 			],
 			limit: None,
+			offset: None,
 		};
 
 		let entry0 = LogEntry {
@@ -0,0 +1,69 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Entry points for fuzzing the decoding of untrusted blocks and transactions.
+//!
+//! Both functions go through the same code paths as data received from the network
+//! and must never panic: any input is either decoded or rejected with an error.
+
+use rlp::DecoderError;
+
+use crate::{
+	transaction::{self, SignedTransaction, UnverifiedTransaction},
+	verification::Unverified,
+};
+
+/// Decode a block as received from a peer, including all of its transactions and uncles.
+pub fn fuzz_decode_block(bytes: &[u8]) -> Result<Unverified, DecoderError> {
+	Unverified::from_rlp(bytes.to_vec())
+}
+
+/// Decode a transaction as received from a peer and recover its sender.
+pub fn fuzz_decode_transaction(bytes: &[u8]) -> Result<SignedTransaction, transaction::Error> {
+	let transaction: UnverifiedTransaction = rlp::decode(bytes)?;
+	Ok(SignedTransaction::new(transaction)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{fuzz_decode_block, fuzz_decode_transaction};
+	use rustc_hex::FromHex;
+
+	#[test]
+	fn rejects_malformed_input() {
+		let inputs: Vec<Vec<u8>> = vec![
+			vec![],
+			vec![0xc0],
+			vec![0xf8],
+			vec![0xff; 64],
+			vec![0xc3, 0xc0, 0xc0, 0xc0],
+			// list claiming more bytes than available
+			vec![0xf9, 0xff, 0xff, 0x80],
+		];
+		for input in inputs {
+			assert!(fuzz_decode_block(&input).is_err());
+			assert!(fuzz_decode_transaction(&input).is_err());
+		}
+	}
+
+	#[test]
+	fn decodes_signed_transaction() {
+		let bytes: Vec<u8> = FromHex::from_hex("f85f800182520894095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba048b55bfa915ac795c431978d8a6a992b628d557da5ff759b307d495a36649353a0efffd310ac743f371de3b9f7f9cb56c0b28ad43601b4ab949f53faa07bd2c804").unwrap();
+		assert!(fuzz_decode_transaction(&bytes).is_ok());
+		// truncated
+		assert!(fuzz_decode_transaction(&bytes[..bytes.len() - 1]).is_err());
+	}
+}
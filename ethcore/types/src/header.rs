@@ -21,6 +21,7 @@ use parity_util_mem::MallocSizeOf;
 use ethereum_types::{H256, U256, Address, Bloom};
 use bytes::Bytes;
 use rlp::{Rlp, RlpStream, Encodable, DecoderError, Decodable};
+use encoded;
 use BlockNumber;
 
 /// Semantic boolean for when a seal/signature is included.
@@ -43,6 +44,19 @@ pub struct ExtendedHeader {
 	pub parent_total_difficulty: U256,
 }
 
+/// Uncle header with the position of its inclusion in the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalizedUncle {
+	/// The uncle header.
+	pub header: encoded::Header,
+	/// Hash of the block including the uncle.
+	pub block_hash: H256,
+	/// Number of the block including the uncle.
+	pub block_number: BlockNumber,
+	/// Position of the uncle in the including block.
+	pub position: usize,
+}
+
 /// A block header.
 ///
 /// Reflects the specific RLP fields of a block in the chain with additional room for the seal
@@ -66,10 +66,13 @@ pub mod blockchain_info;
 pub mod call_analytics;
 pub mod chain_notify;
 pub mod client_types;
+pub mod denominations;
+pub mod disk_usage;
 pub mod encoded;
 pub mod engines;
 pub mod errors;
 pub mod filter;
+pub mod fuzz;
 pub mod header;
 pub mod ids;
 pub mod io_message;
@@ -77,9 +80,11 @@ pub mod import_route;
 pub mod log_entry;
 pub mod pruning_info;
 pub mod receipt;
+pub mod reorg;
 pub mod security_level;
 pub mod snapshot;
 pub mod state_diff;
+pub mod state_override;
 pub mod trace_filter;
 pub mod transaction;
 pub mod tree_route;
@@ -72,6 +72,8 @@ pub struct LocalizedLogEntry {
 	pub log_index: usize,
 	/// Log position in the transaction.
 	pub transaction_log_index: usize,
+	/// Whether the block of this log is no longer part of the canonical chain.
+	pub removed: bool,
 }
 
 impl Deref for LocalizedLogEntry {
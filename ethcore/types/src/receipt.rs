@@ -16,6 +16,7 @@
 
 //! Receipt
 
+use bytes::Bytes;
 use ethereum_types::{H160, H256, U256, Address, Bloom};
 use parity_util_mem::MallocSizeOf;
 use rlp::{Rlp, RlpStream, Encodable, Decodable, DecoderError};
@@ -161,6 +162,9 @@ pub struct LocalizedReceipt {
 	pub log_bloom: Bloom,
 	/// Transaction outcome.
 	pub outcome: TransactionOutcome,
+	/// Return data, or the revert reason if the transaction failed.
+	/// NOTE: Only available if the block was imported with output recording enabled
+	pub output: Option<Bytes>,
 	/// Receiver address
 	/// NOTE: It is an Option because only `Action::Call` transactions has a receiver address
 	pub to: Option<H160>,
@@ -0,0 +1,32 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Record of a chain reorganization.
+
+use ethereum_types::H256;
+
+/// A reorganization of the canonical chain.
+#[derive(Debug, Clone, PartialEq, RlpEncodable, RlpDecodable)]
+pub struct Reorg {
+	/// Hash of the newest common ancestor of the old and new canonical chains.
+	pub ancestor: H256,
+	/// Hashes of the blocks removed from the canonical chain.
+	pub retracted: Vec<H256>,
+	/// Hashes of the blocks added to the canonical chain, ending with the new best block.
+	pub enacted: Vec<H256>,
+	/// Unix timestamp, in seconds, at which the reorganization was imported.
+	pub timestamp: u64,
+}
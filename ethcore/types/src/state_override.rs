@@ -0,0 +1,38 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Account state overrides applied before executing a call.
+
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use ethereum_types::{Address, H256, U256};
+
+/// Values replacing those of an account in the state a call is executed against.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AccountOverride {
+	/// Balance to set.
+	pub balance: Option<U256>,
+	/// Nonce to set.
+	pub nonce: Option<U256>,
+	/// Code to set.
+	pub code: Option<Bytes>,
+	/// Storage slots to set; slots not listed keep their value.
+	pub storage: BTreeMap<H256, H256>,
+}
+
+/// Overrides for a set of accounts.
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
@@ -55,6 +55,8 @@ fn ethash_params() -> EthashParams {
 			m.insert(7280000, 2000000000000000000u64.into());
 			m
 		},
+		uncle_reward_divisor: 8,
+		nephew_reward_divisor: 32,
 		expip2_transition: u64::max_value(),
 		expip2_duration_limit: 30,
 		block_reward_contract_transition: 0,
@@ -100,7 +102,8 @@ fn block_verification(c: &mut Criterion) {
 			assert!(verification::verify_block_basic(
 				&block,
 				&ethash,
-				true
+				true,
+				&verification::TimestampPolicy::default(),
 			).is_ok());
 		})
 	});
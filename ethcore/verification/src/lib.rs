@@ -27,7 +27,9 @@ pub mod queue;
 #[cfg(any(test, feature = "bench" ))]
 pub mod test_helpers;
 
-pub use self::verification::{FullFamilyParams, verify_block_family, verify_block_final};
+pub use self::verification::{
+	Clock, FullFamilyParams, SystemClock, TimestampPolicy, verify_block_family, verify_block_final,
+};
 pub use self::queue::{BlockQueue, Config as QueueConfig};
 
 /// Verifier type.
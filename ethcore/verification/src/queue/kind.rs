@@ -23,6 +23,8 @@ use ethereum_types::{H256, U256};
 
 use common_types::errors::EthcoreError as Error;
 
+use crate::verification::TimestampPolicy;
+
 pub use self::blocks::Blocks;
 pub use self::headers::Headers;
 
@@ -53,7 +55,7 @@ pub trait BlockLike {
 /// consistent.
 pub trait Kind: 'static + Sized + Send + Sync {
 	/// The first stage: completely unverified.
-	type Input: Sized + Send + BlockLike + MallocSizeOf;
+	type Input: Sized + Send + Clone + BlockLike + MallocSizeOf;
 
 	/// The second stage: partially verified.
 	type Unverified: Sized + Send + BlockLike + MallocSizeOf;
@@ -68,7 +70,8 @@ pub trait Kind: 'static + Sized + Send + Sync {
 	fn create(
 		input: Self::Input,
 		engine: &dyn Engine,
-		check_seal: bool
+		check_seal: bool,
+		timestamps: &TimestampPolicy,
 	) -> Result<Self::Unverified, (Error, Option<Self::Input>)>;
 
 	/// Attempt to verify the `Unverified` item using the given engine.
@@ -86,7 +89,7 @@ pub mod blocks {
 		verification::Unverified,
 	};
 	use log::{debug, warn};
-	use crate::verification::{verify_block_basic, verify_block_unordered};
+	use crate::verification::{verify_block_basic, verify_block_unordered, TimestampPolicy};
 
 	use ethereum_types::{H256, U256};
 
@@ -101,9 +104,10 @@ pub mod blocks {
 		fn create(
 			input: Self::Input,
 			engine: &dyn Engine,
-			check_seal: bool
+			check_seal: bool,
+			timestamps: &TimestampPolicy,
 		) -> Result<Self::Unverified, (Error, Option<Self::Input>)> {
-			match verify_block_basic(&input, engine, check_seal) {
+			match verify_block_basic(&input, engine, check_seal, timestamps) {
 				Ok(()) => Ok(input),
 				Err(Error::Block(BlockError::TemporarilyInvalid(oob))) => {
 					debug!(target: "client", "Block received too early {}: {:?}", input.hash(), oob);
@@ -174,7 +178,7 @@ pub mod headers {
 		header::Header,
 		errors::EthcoreError as Error,
 	};
-	use crate::verification::{verify_header_params, verify_header_time};
+	use crate::verification::{verify_header_params, verify_header_time, TimestampPolicy};
 
 	use ethereum_types::{H256, U256};
 
@@ -196,10 +200,13 @@ pub mod headers {
 		fn create(
 			input: Self::Input,
 			engine: &dyn Engine,
-			check_seal: bool
+			check_seal: bool,
+			timestamps: &TimestampPolicy,
 		) -> Result<Self::Unverified, (Error, Option<Self::Input>)> {
+			// the timestamp is checked last, so only otherwise valid headers are quarantined
 			let res = verify_header_params(&input, engine, check_seal)
-				.and_then(|_| verify_header_time(&input));
+				.and_then(|_| if check_seal { engine.verify_block_basic(&input) } else { Ok(()) })
+				.and_then(|_| verify_header_time(&input, timestamps));
 
 			match res {
 				Ok(_) => Ok(input),
@@ -21,7 +21,9 @@ use std::thread::{self, JoinHandle};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::Arc;
 use std::cmp;
-use std::collections::{VecDeque, HashSet, HashMap};
+use std::collections::{VecDeque, HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::time::{Duration, Instant};
 use common_types::{
 	block_status::BlockStatus,
 	io_message::ClientIoMessage,
@@ -37,11 +39,22 @@ use parity_util_mem::{MallocSizeOf, MallocSizeOfExt};
 use parking_lot::{Condvar, Mutex, RwLock};
 
 use self::kind::{BlockLike, Kind};
+use crate::verification::{Clock, TimestampPolicy};
 
 pub mod kind;
 
 const MIN_MEM_LIMIT: usize = 16384;
 const MIN_QUEUE_LIMIT: usize = 512;
+const MAX_QUARANTINED: usize = 1024;
+const MAX_QUARANTINED_MEM: usize = 8 * 1024 * 1024;
+const MAX_QUARANTINED_PER_PEER: usize = 16;
+const MAX_BAD_ITEMS: usize = 65536;
+const INVALID_ITEM_LIFETIME: Duration = Duration::from_secs(60 * 60);
+const FAILED_ITEM_LIFETIME: Duration = Duration::from_secs(60);
+// expected time to drain the unverified queue above which another verifier is woken up.
+const SCALE_UP_BACKLOG: Duration = Duration::from_secs(1);
+// expected time to drain the unverified queue below which a verifier is put to sleep.
+const SCALE_DOWN_BACKLOG: Duration = Duration::from_millis(100);
 
 /// Type alias for block queue convenience.
 pub type BlockQueue<C> = VerificationQueue<self::kind::Blocks, C>;
@@ -60,6 +73,12 @@ pub struct Config {
 	pub max_mem_use: usize,
 	/// Settings for the number of verifiers and adaptation strategy.
 	pub verifier_settings: VerifierSettings,
+	/// Maximum distance of a header timestamp into the future.
+	/// Items further ahead are quarantined until the clock catches up.
+	pub max_timestamp_drift: Duration,
+	/// Maximum number of queued items with the same parent.
+	/// Further siblings are dropped on import.
+	pub max_fork_width: usize,
 }
 
 impl Default for Config {
@@ -68,6 +87,8 @@ impl Default for Config {
 			max_queue_size: 30000,
 			max_mem_use: 50 * 1024 * 1024,
 			verifier_settings: VerifierSettings::default(),
+			max_timestamp_drift: Duration::from_secs(15),
+			max_fork_width: 16,
 		}
 	}
 }
@@ -80,6 +101,10 @@ pub struct VerifierSettings {
 	pub scale_verifiers: bool,
 	/// Beginning amount of verifiers.
 	pub num_verifiers: usize,
+	/// Lowest amount of verifiers kept awake when scaling.
+	pub min_verifiers: usize,
+	/// Highest amount of verifier threads to spawn.
+	pub max_verifiers: usize,
 }
 
 impl Default for VerifierSettings {
@@ -87,10 +112,37 @@ impl Default for VerifierSettings {
 		VerifierSettings {
 			scale_verifiers: false,
 			num_verifiers: num_cpus::get(),
+			min_verifiers: 1,
+			max_verifiers: num_cpus::get(),
 		}
 	}
 }
 
+// time spent verifying items since the last readjustment.
+#[derive(Default)]
+struct Latency {
+	total_micros: AtomicUsize,
+	items: AtomicUsize,
+}
+
+impl Latency {
+	fn record(&self, elapsed: Duration) {
+		let micros = elapsed.as_secs() as usize * 1_000_000 + elapsed.subsec_micros() as usize;
+		self.total_micros.fetch_add(micros, AtomicOrdering::SeqCst);
+		self.items.fetch_add(1, AtomicOrdering::SeqCst);
+	}
+
+	// average verification time of a single item, resetting the measurement.
+	fn take_average(&self) -> Option<Duration> {
+		let items = self.items.swap(0, AtomicOrdering::SeqCst);
+		let total_micros = self.total_micros.swap(0, AtomicOrdering::SeqCst);
+		if items == 0 {
+			return None;
+		}
+		Some(Duration::from_micros((total_micros / items) as u64))
+	}
+}
+
 // pool states
 enum State {
 	// all threads with id < inner value are to work.
@@ -125,6 +177,179 @@ impl Into<BlockStatus> for Status {
 	}
 }
 
+/// Why an item is considered bad.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum BadReason {
+	/// The item breaks consensus rules.
+	Invalid,
+	/// The item could not be processed because of a local failure, e.g. a database error.
+	/// Such items are forgotten sooner so they can be retried.
+	Failed,
+}
+
+impl BadReason {
+	fn lifetime(&self) -> Duration {
+		match *self {
+			BadReason::Invalid => INVALID_ITEM_LIFETIME,
+			BadReason::Failed => FAILED_ITEM_LIFETIME,
+		}
+	}
+}
+
+// items known to be bad, forgotten after a while.
+#[derive(Default)]
+struct BadItems {
+	items: HashMap<H256, (Instant, BadReason)>,
+}
+
+impl BadItems {
+	fn get(&self, hash: &H256) -> Option<BadReason> {
+		match self.items.get(hash) {
+			Some(&(expires, reason)) if expires > Instant::now() => Some(reason),
+			_ => None,
+		}
+	}
+
+	fn contains(&self, hash: &H256) -> bool {
+		self.get(hash).is_some()
+	}
+
+	fn insert(&mut self, hash: H256, reason: BadReason) {
+		self.items.insert(hash, (Instant::now() + reason.lifetime(), reason));
+	}
+
+	fn reserve(&mut self, additional: usize) {
+		self.items.reserve(additional);
+	}
+
+	fn clear(&mut self) {
+		self.items.clear();
+	}
+
+	// drop expired items and, if still over the limit, the ones expiring soonest.
+	fn prune(&mut self) {
+		let now = Instant::now();
+		self.items.retain(|_, &mut (expires, _)| expires > now);
+
+		if self.items.len() > MAX_BAD_ITEMS {
+			let mut by_expiry: Vec<_> = self.items.iter().map(|(hash, &(expires, _))| (expires, *hash)).collect();
+			by_expiry.sort();
+			let excess = self.items.len() - MAX_BAD_ITEMS;
+			for (_, hash) in by_expiry.into_iter().take(excess) {
+				self.items.remove(&hash);
+			}
+		}
+		self.items.shrink_to_fit();
+	}
+}
+
+// items in the queue, with the number of them sharing each parent.
+#[derive(Default)]
+struct Processing {
+	// hash to difficulty and parent hash.
+	items: HashMap<H256, (U256, H256)>,
+	children: HashMap<H256, usize>,
+}
+
+impl Processing {
+	fn contains(&self, hash: &H256) -> bool {
+		self.items.contains_key(hash)
+	}
+
+	fn children(&self, parent: &H256) -> usize {
+		self.children.get(parent).cloned().unwrap_or(0)
+	}
+
+	// returns false if the item is already there.
+	fn insert(&mut self, hash: H256, parent: H256, difficulty: U256) -> bool {
+		if self.items.contains_key(&hash) {
+			return false;
+		}
+		self.items.insert(hash, (difficulty, parent));
+		*self.children.entry(parent).or_insert(0) += 1;
+		true
+	}
+
+	// returns the difficulty of the removed item.
+	fn remove(&mut self, hash: &H256) -> Option<U256> {
+		let (difficulty, parent) = self.items.remove(hash)?;
+		if let Entry::Occupied(mut children) = self.children.entry(parent) {
+			*children.get_mut() -= 1;
+			if *children.get() == 0 {
+				children.remove();
+			}
+		}
+		Some(difficulty)
+	}
+
+	fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	fn clear(&mut self) {
+		self.items.clear();
+		self.children.clear();
+	}
+
+	fn shrink_to_fit(&mut self) {
+		self.items.shrink_to_fit();
+		self.children.shrink_to_fit();
+	}
+}
+
+// unverified items waiting for their parent to be verified.
+struct Waiting<U> {
+	// waiting items, by parent hash.
+	children: HashMap<H256, Vec<U>>,
+	// hashes of the waiting items.
+	parked: HashSet<H256>,
+	// hashes of the items being verified.
+	verifying: HashSet<H256>,
+}
+
+impl<U> Default for Waiting<U> {
+	fn default() -> Self {
+		Waiting {
+			children: HashMap::new(),
+			parked: HashSet::new(),
+			verifying: HashSet::new(),
+		}
+	}
+}
+
+impl<U> Waiting<U> {
+	// whether an item with given parent, which has `siblings` items in the queue including it,
+	// waits for the parent. Fork siblings wait for a parent being verified, so that a bad parent
+	// doesn't have all of them verified in vain, while the only child is verified alongside it.
+	// Children of waiting items wait as well, so that items are verified in order.
+	fn must_wait(&self, parent: &H256, siblings: usize) -> bool {
+		self.parked.contains(parent) || (siblings > 1 && self.verifying.contains(parent))
+	}
+
+	fn len(&self) -> usize {
+		self.parked.len()
+	}
+
+	fn is_empty(&self) -> bool {
+		self.parked.is_empty()
+	}
+
+	// removes the items waiting for given parent.
+	fn take_children(&mut self, parent: &H256) -> Vec<U> where U: BlockLike {
+		let children = self.children.remove(parent).unwrap_or_default();
+		for child in &children {
+			self.parked.remove(&child.hash());
+		}
+		children
+	}
+
+	fn shrink_to_fit(&mut self) {
+		self.children.shrink_to_fit();
+		self.parked.shrink_to_fit();
+		self.verifying.shrink_to_fit();
+	}
+}
+
 // the internal queue sizes.
 struct Sizes {
 	unverified: AtomicUsize,
@@ -141,14 +366,24 @@ pub struct VerificationQueue<K: Kind, C: 'static> {
 	deleting: Arc<AtomicBool>,
 	ready_signal: Arc<QueueSignal<C>>,
 	empty: Arc<Condvar>,
-	processing: RwLock<HashMap<H256, U256>>, // hash to difficulty
 	ticks_since_adjustment: AtomicUsize,
 	max_queue_size: usize,
 	max_mem_use: usize,
+	max_fork_width: usize,
 	scale_verifiers: bool,
+	min_verifiers: usize,
 	verifier_handles: Vec<JoinHandle<()>>,
 	state: Arc<(Mutex<State>, Condvar)>,
-	total_difficulty: RwLock<U256>,
+	timestamps: TimestampPolicy,
+	quarantined: Mutex<HashMap<H256, Quarantined<K::Input>>>,
+}
+
+/// An item held back until its timestamp is no longer too far in the future.
+struct Quarantined<I> {
+	input: I,
+	/// Peer the item was received from, `None` for other sources.
+	peer: Option<usize>,
+	mem: usize,
 }
 
 struct QueueSignal<C: 'static> {
@@ -196,11 +431,33 @@ struct Verification<K: Kind> {
 	unverified: LenCachingMutex<VecDeque<K::Unverified>>,
 	verifying: LenCachingMutex<VecDeque<Verifying<K>>>,
 	verified: LenCachingMutex<VecDeque<K::Verified>>,
-	bad: Mutex<HashSet<H256>>,
+	bad: Mutex<BadItems>,
+	// items in the queue, until imported or found bad.
+	processing: RwLock<Processing>,
+	total_difficulty: RwLock<U256>,
+	// unverified items waiting for their parent to be verified.
+	// Only ever locked briefly, without acquiring any other lock in the meantime.
+	waiting: Mutex<Waiting<K::Unverified>>,
 	sizes: Sizes,
+	latency: Latency,
 	check_seal: bool,
 }
 
+impl<K: Kind> Verification<K> {
+	/// Number of items waiting for their parent to be verified.
+	fn waiting_len(&self) -> usize {
+		self.waiting.lock().len()
+	}
+
+	/// Stops counting an item found bad during verification as queued.
+	fn forget(&self, hash: &H256) {
+		if let Some(difficulty) = self.processing.write().remove(hash) {
+			let mut td = self.total_difficulty.write();
+			*td = *td - difficulty;
+		}
+	}
+}
+
 impl<K: Kind, C> VerificationQueue<K, C> {
 	/// Creates a new queue instance.
 	pub fn new(config: Config, engine: Arc<dyn Engine>, message_channel: IoChannel<ClientIoMessage<C>>, check_seal: bool) -> Self {
@@ -208,12 +465,16 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 			unverified: LenCachingMutex::new(VecDeque::new()),
 			verifying: LenCachingMutex::new(VecDeque::new()),
 			verified: LenCachingMutex::new(VecDeque::new()),
-			bad: Mutex::new(HashSet::new()),
+			bad: Mutex::new(BadItems::default()),
+			processing: RwLock::new(Processing::default()),
+			total_difficulty: RwLock::new(0.into()),
+			waiting: Mutex::new(Waiting::default()),
 			sizes: Sizes {
 				unverified: AtomicUsize::new(0),
 				verifying: AtomicUsize::new(0),
 				verified: AtomicUsize::new(0),
 			},
+			latency: Latency::default(),
 			check_seal,
 		});
 		let more_to_verify = Arc::new(Condvar::new());
@@ -226,8 +487,9 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		let empty = Arc::new(Condvar::new());
 		let scale_verifiers = config.verifier_settings.scale_verifiers;
 
-		let max_verifiers = ::num_cpus::get();
-		let default_amount = cmp::max(1, cmp::min(max_verifiers, config.verifier_settings.num_verifiers));
+		let max_verifiers = cmp::max(1, config.verifier_settings.max_verifiers);
+		let min_verifiers = cmp::max(1, cmp::min(max_verifiers, config.verifier_settings.min_verifiers));
+		let default_amount = cmp::max(min_verifiers, cmp::min(max_verifiers, config.verifier_settings.num_verifiers));
 
 		// if `auto-scaling` is enabled spawn up extra threads as they might be needed
 		// otherwise just spawn the number of threads specified by the config
@@ -276,18 +538,26 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 			more_to_verify,
 			verification,
 			deleting,
-			processing: RwLock::new(HashMap::new()),
 			empty,
 			ticks_since_adjustment: AtomicUsize::new(0),
 			max_queue_size: cmp::max(config.max_queue_size, MIN_QUEUE_LIMIT),
 			max_mem_use: cmp::max(config.max_mem_use, MIN_MEM_LIMIT),
+			max_fork_width: cmp::max(config.max_fork_width, 1),
 			scale_verifiers,
+			min_verifiers,
 			verifier_handles,
 			state,
-			total_difficulty: RwLock::new(0.into()),
+			timestamps: TimestampPolicy::new(config.max_timestamp_drift),
+			quarantined: Mutex::new(HashMap::new()),
 		}
 	}
 
+	/// Use the given clock when checking header timestamps.
+	pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+		self.timestamps.clock = clock;
+		self
+	}
+
 	fn verify(
 		verification: Arc<Verification<K>>,
 		engine: Arc<dyn Engine>,
@@ -350,15 +620,46 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 					None => continue,
 				};
 
+				let parent = item.parent_hash();
+				let siblings = verification.processing.read().children(&parent);
+				{
+					let mut waiting = verification.waiting.lock();
+					if waiting.must_wait(&parent, siblings) {
+						trace!(target: "verification", "{} waiting for its parent {} to be verified", item.hash(), parent);
+						waiting.parked.insert(item.hash());
+						waiting.children.entry(parent).or_insert_with(Vec::new).push(item);
+						continue;
+					}
+					waiting.verifying.insert(item.hash());
+				}
+
 				verification.sizes.unverified.fetch_sub(item.malloc_size_of(), AtomicOrdering::SeqCst);
 				verifying.push_back(Verifying { hash: item.hash(), output: None });
 				item
 			};
 
 			let hash = item.hash();
-			let is_ready = match K::verify(item, &*engine, verification.check_seal) {
+			let started = Instant::now();
+			let result = K::verify(item, &*engine, verification.check_seal);
+			verification.latency.record(started.elapsed());
+			let is_ready = match result {
 				Ok(verified) => {
+					let mut unverified = verification.unverified.lock();
 					let mut verifying = verification.verifying.lock();
+
+					// the children waiting for the item are verified next.
+					let children = {
+						let mut waiting = verification.waiting.lock();
+						waiting.verifying.remove(&hash);
+						waiting.take_children(&hash)
+					};
+					if !children.is_empty() {
+						for child in children.into_iter().rev() {
+							unverified.push_front(child);
+						}
+						wait.notify_all();
+					}
+
 					let mut idx = None;
 					for (i, e) in verifying.iter_mut().enumerate() {
 						if e.hash == hash {
@@ -374,7 +675,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 						// we're next!
 						let mut verified = verification.verified.lock();
 						let mut bad = verification.bad.lock();
-						VerificationQueue::<_, C>::drain_verifying(&mut verifying, &mut verified, &mut bad, &verification.sizes);
+						VerificationQueue::<_, C>::drain_verifying(&mut verifying, &mut verified, &mut bad, &verification);
 						true
 					} else {
 						false
@@ -385,11 +686,25 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 					let mut verified = verification.verified.lock();
 					let mut bad = verification.bad.lock();
 
-					bad.insert(hash.clone());
+					bad.insert(hash.clone(), BadReason::Invalid);
+					verification.forget(&hash);
 					verifying.retain(|e| e.hash != hash);
+					verification.waiting.lock().verifying.remove(&hash);
+
+					// the children waiting for the item, and theirs, are bad as well.
+					let mut parents = vec![hash];
+					while let Some(parent) = parents.pop() {
+						let children = verification.waiting.lock().take_children(&parent);
+						for child in children {
+							verification.sizes.unverified.fetch_sub(child.malloc_size_of(), AtomicOrdering::SeqCst);
+							bad.insert(child.hash(), BadReason::Invalid);
+							verification.forget(&child.hash());
+							parents.push(child.hash());
+						}
+					}
 
 					if verifying.front().map_or(false, |x| x.output.is_some()) {
-						VerificationQueue::<_, C>::drain_verifying(&mut verifying, &mut verified, &mut bad, &verification.sizes);
+						VerificationQueue::<_, C>::drain_verifying(&mut verifying, &mut verified, &mut bad, &verification);
 						true
 					} else {
 						false
@@ -406,8 +721,8 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 	fn drain_verifying(
 		verifying: &mut VecDeque<Verifying<K>>,
 		verified: &mut VecDeque<K::Verified>,
-		bad: &mut HashSet<H256>,
-		sizes: &Sizes,
+		bad: &mut BadItems,
+		verification: &Verification<K>,
 	) {
 		let mut removed_size = 0;
 		let mut inserted_size = 0;
@@ -417,16 +732,17 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 			let size = output.malloc_size_of();
 			removed_size += size;
 
-			if bad.contains(&output.parent_hash()) {
-				bad.insert(output.hash());
+			if let Some(reason) = bad.get(&output.parent_hash()) {
+				bad.insert(output.hash(), reason);
+				verification.forget(&output.hash());
 			} else {
 				inserted_size += size;
 				verified.push_back(output);
 			}
 		}
 
-		sizes.verifying.fetch_sub(removed_size, AtomicOrdering::SeqCst);
-		sizes.verified.fetch_add(inserted_size, AtomicOrdering::SeqCst);
+		verification.sizes.verifying.fetch_sub(removed_size, AtomicOrdering::SeqCst);
+		verification.sizes.verified.fetch_add(inserted_size, AtomicOrdering::SeqCst);
 	}
 
 	/// Clear the queue and stop verification activity.
@@ -437,14 +753,16 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		unverified.clear();
 		verifying.clear();
 		verified.clear();
+		*self.verification.waiting.lock() = Waiting::default();
 
 		let sizes = &self.verification.sizes;
 		sizes.unverified.store(0, AtomicOrdering::Release);
 		sizes.verifying.store(0, AtomicOrdering::Release);
 		sizes.verified.store(0, AtomicOrdering::Release);
-		*self.total_difficulty.write() = 0.into();
+		*self.verification.total_difficulty.write() = 0.into();
 
-		self.processing.write().clear();
+		self.verification.processing.write().clear();
+		self.quarantined.lock().clear();
 	}
 
 	/// Wait for unverified queue to be empty
@@ -457,7 +775,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 
 	/// Check if the item is currently in the queue
 	pub fn status(&self, hash: &H256) -> Status {
-		if self.processing.read().contains_key(hash) {
+		if self.verification.processing.read().contains(hash) {
 			return Status::Queued;
 		}
 		if self.verification.bad.lock().contains(hash) {
@@ -467,35 +785,50 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 	}
 
 	/// Add a block to the queue.
+	pub fn import(&self, input: K::Input) -> Result<H256, (Error, Option<K::Input>)> {
+		self.import_from(input, None)
+	}
+
+	/// Add a block received from `peer` to the queue. Only a few items from each peer are
+	/// quarantined when they are too far in the future.
 	//
 	// TODO: #11403 - rework `EthcoreError::Block` to include raw bytes of the error cause
-	pub fn import(&self, input: K::Input) -> Result<H256, (Error, Option<K::Input>)> {
+	pub fn import_from(&self, input: K::Input, peer: Option<usize>) -> Result<H256, (Error, Option<K::Input>)> {
 		let hash = input.hash();
 		let raw_hash = input.raw_hash();
 		{
-			if self.processing.read().contains_key(&hash) {
+			let (queued, siblings) = {
+				let processing = self.verification.processing.read();
+				(processing.contains(&hash), processing.children(&input.parent_hash()))
+			};
+			if queued {
 				return Err((Error::Import(ImportError::AlreadyQueued), Some(input)));
 			}
 
+			if siblings >= self.max_fork_width {
+				debug!(target: "verification", "Dropping {}, {} siblings already queued", hash, siblings);
+				return Err((Error::Import(ImportError::ForkTooWide), Some(input)));
+			}
+
 			let mut bad = self.verification.bad.lock();
 			if bad.contains(&hash) || bad.contains(&raw_hash)  {
 				return Err((Error::Import(ImportError::KnownBad), Some(input)));
 			}
 
-			if bad.contains(&input.parent_hash()) {
-				bad.insert(hash);
+			if let Some(reason) = bad.get(&input.parent_hash()) {
+				bad.insert(hash, reason);
 				return Err((Error::Import(ImportError::KnownBad), Some(input)));
 			}
 		}
 
-		match K::create(input, &*self.engine, self.verification.check_seal) {
+		match K::create(input, &*self.engine, self.verification.check_seal, &self.timestamps) {
 			Ok(item) => {
-				if self.processing.write().insert(hash, item.difficulty()).is_some() {
+				if !self.verification.processing.write().insert(hash, item.parent_hash(), item.difficulty()) {
 					return Err((Error::Import(ImportError::AlreadyQueued), None));
 				}
 				self.verification.sizes.unverified.fetch_add(item.malloc_size_of(), AtomicOrdering::SeqCst);
 				{
-					let mut td = self.total_difficulty.write();
+					let mut td = self.verification.total_difficulty.write();
 					*td = *td + item.difficulty();
 				}
 				self.verification.unverified.lock().push_back(item);
@@ -504,8 +837,12 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 			},
 			Err((err, input)) => {
 				match err {
-					// Don't mark future blocks as bad.
-					Error::Block(BlockError::TemporarilyInvalid(_)) => {},
+					// Don't mark future blocks as bad, keep them around until they become valid.
+					Error::Block(BlockError::TemporarilyInvalid(_)) => {
+						if let Some(ref input) = input {
+							self.quarantine(input.clone(), peer);
+						}
+					},
 					// If the transaction root or uncles hash is invalid, it doesn't necessarily mean
 					// that the header is invalid. We might have just received a malformed block body,
 					// so we shouldn't put the header hash to `bad`.
@@ -514,10 +851,10 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 					// the items that are malformed.
 					Error::Block(BlockError::InvalidTransactionsRoot(_)) |
 					Error::Block(BlockError::InvalidUnclesHash(_)) => {
-						self.verification.bad.lock().insert(raw_hash);
+						self.verification.bad.lock().insert(raw_hash, BadReason::Invalid);
 					},
 					_ => {
-						self.verification.bad.lock().insert(hash);
+						self.verification.bad.lock().insert(hash, BadReason::Invalid);
 					}
 				}
 				Err((err, input))
@@ -525,21 +862,57 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		}
 	}
 
+	/// Keep an item that passed every check but the timestamp until the clock catches up,
+	/// within limits on the number of items, on their size and on the items from one peer.
+	fn quarantine(&self, input: K::Input, peer: Option<usize>) {
+		let mem = input.malloc_size_of();
+		let mut quarantined = self.quarantined.lock();
+		let (from_peer, total_mem) = quarantined.values()
+			.fold((0, 0), |(from_peer, total_mem), item| (from_peer + (item.peer == peer) as usize, total_mem + item.mem));
+
+		if quarantined.len() >= MAX_QUARANTINED || total_mem + mem > MAX_QUARANTINED_MEM || from_peer >= MAX_QUARANTINED_PER_PEER {
+			debug!(target: "verification", "Quarantine full, dropping {}", input.hash());
+			return;
+		}
+		quarantined.insert(input.hash(), Quarantined { input, peer, mem });
+	}
+
+	/// Take all quarantined items, with the peer they came from. They should be imported again
+	/// the same way as new items, those still too far in the future are quarantined again.
+	pub fn take_quarantined(&self) -> Vec<(K::Input, Option<usize>)> {
+		self.quarantined.lock().drain().map(|(_, item)| (item.input, item.peer)).collect()
+	}
+
+	/// Number of items held back because their timestamp is too far in the future.
+	pub fn quarantined_count(&self) -> usize {
+		self.quarantined.lock().len()
+	}
+
 	/// Mark given item and all its children as bad. pauses verification
 	/// until complete.
 	pub fn mark_as_bad(&self, hashes: &[H256]) {
+		self.mark(hashes, BadReason::Invalid)
+	}
+
+	/// Mark given item and all its children as failed due to a local error.
+	/// Unlike bad items they are forgotten quickly, so that the import can be retried.
+	pub fn mark_as_failed(&self, hashes: &[H256]) {
+		self.mark(hashes, BadReason::Failed)
+	}
+
+	fn mark(&self, hashes: &[H256], reason: BadReason) {
 		if hashes.is_empty() {
 			return;
 		}
 		let mut verified_lock = self.verification.verified.lock();
 		let verified = &mut *verified_lock;
 		let mut bad = self.verification.bad.lock();
-		let mut processing = self.processing.write();
+		let mut processing = self.verification.processing.write();
 		bad.reserve(hashes.len());
 		for hash in hashes {
-			bad.insert(hash.clone());
+			bad.insert(hash.clone(), reason);
 			if let Some(difficulty) = processing.remove(hash) {
-				let mut td = self.total_difficulty.write();
+				let mut td = self.verification.total_difficulty.write();
 				*td = *td - difficulty;
 			}
 		}
@@ -549,9 +922,9 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		for output in verified.drain(..) {
 			if bad.contains(&output.parent_hash()) {
 				removed_size += output.malloc_size_of();
-				bad.insert(output.hash());
+				bad.insert(output.hash(), reason);
 				if let Some(difficulty) = processing.remove(&output.hash()) {
-					let mut td = self.total_difficulty.write();
+					let mut td = self.verification.total_difficulty.write();
 					*td = *td - difficulty;
 				}
 			} else {
@@ -563,16 +936,21 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		*verified = new_verified;
 	}
 
+	/// Forget all items known to be bad.
+	pub fn clear_bad(&self) {
+		self.verification.bad.lock().clear();
+	}
+
 	/// Mark given item as processed.
 	/// Returns true if the queue becomes empty.
 	pub fn mark_as_good(&self, hashes: &[H256]) -> bool {
 		if hashes.is_empty() {
-			return self.processing.read().is_empty();
+			return self.verification.processing.read().is_empty();
 		}
-		let mut processing = self.processing.write();
+		let mut processing = self.verification.processing.write();
 		for hash in hashes {
 			if let Some(difficulty) = processing.remove(hash) {
-				let mut td = self.total_difficulty.write();
+				let mut td = self.verification.total_difficulty.write();
 				*td = *td - difficulty;
 			}
 		}
@@ -602,6 +980,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		v.unverified.load_len() == 0
 			&& v.verifying.load_len() == 0
 			&& v.verified.load_len() == 0
+			&& v.waiting.lock().is_empty()
 	}
 
 	/// Get queue status.
@@ -609,7 +988,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		use std::mem::size_of;
 
 		let (unverified_len, unverified_bytes) = {
-			let len = self.verification.unverified.load_len();
+			let len = self.verification.unverified.load_len() + self.verification.waiting_len();
 			let size = self.verification.sizes.unverified.load(AtomicOrdering::Acquire);
 
 			(len, size + len * size_of::<K::Unverified>())
@@ -639,7 +1018,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 
 	/// Get the total difficulty of all the blocks in the queue.
 	pub fn total_difficulty(&self) -> U256 {
-		*self.total_difficulty.read()
+		*self.verification.total_difficulty.read()
 	}
 
 	/// Get the current number of working verifiers.
@@ -668,6 +1047,7 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 				q.len()
 			};
 			self.verification.verifying.lock().shrink_to_fit();
+			self.verification.waiting.lock().shrink_to_fit();
 
 			let v_len = {
 				let mut q = self.verification.verified.lock();
@@ -678,7 +1058,8 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 			(u_len as isize, v_len as isize)
 		};
 
-		self.processing.write().shrink_to_fit();
+		self.verification.processing.write().shrink_to_fit();
+		self.verification.bad.lock().prune();
 
 		if !self.scale_verifiers { return }
 
@@ -689,30 +1070,18 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 		}
 
 		let current = self.num_verifiers();
+		let latency = self.verification.latency.take_average();
 
-		let diff = (v_len - u_len).abs();
-		let total = v_len + u_len;
-
-		self.scale_verifiers(
-			if u_len < 20 {
-				1
-			} else if diff <= total / 10 {
-				current
-			} else if v_len > u_len {
-				current - 1
-			} else {
-				current + 1
-			}
-		);
+		self.scale_verifiers(target_verifiers(current, u_len as usize, v_len as usize, latency));
 	}
 
 	// wake up or sleep verifiers to get as close to the target as
 	// possible, never going over the amount of initially allocated threads
-	// or below 1.
+	// or below the configured minimum.
 	fn scale_verifiers(&self, target: usize) {
 		let current = self.num_verifiers();
 		let target = cmp::min(self.verifier_handles.len(), target);
-		let target = cmp::max(1, target);
+		let target = cmp::max(self.min_verifiers, target);
 
 		debug!(target: "verification", "Scaling from {} to {} verifiers", current, target);
 
@@ -721,6 +1090,31 @@ impl<K: Kind, C> VerificationQueue<K, C> {
 	}
 }
 
+// number of verifiers needed to drain the unverified queue in reasonable time,
+// given the average time it took to verify a single item.
+fn target_verifiers(current: usize, unverified: usize, verified: usize, latency: Option<Duration>) -> usize {
+	if unverified == 0 {
+		return 1;
+	}
+	// import can't keep up, more verifiers would only grow the verified queue.
+	if verified > unverified {
+		return current.saturating_sub(1);
+	}
+	let latency = match latency {
+		Some(latency) => latency,
+		None => return current,
+	};
+
+	let backlog = latency * unverified as u32 / cmp::max(1, current) as u32;
+	if backlog > SCALE_UP_BACKLOG {
+		current + 1
+	} else if backlog < SCALE_DOWN_BACKLOG {
+		current.saturating_sub(1)
+	} else {
+		current
+	}
+}
+
 impl<K: Kind, C> Drop for VerificationQueue<K, C> {
 	fn drop(&mut self) {
 		trace!(target: "shutdown", "[VerificationQueue] Closing...");
@@ -750,12 +1144,17 @@ impl<K: Kind, C> Drop for VerificationQueue<K, C> {
 #[cfg(test)]
 mod tests {
 	use ethcore_io::*;
-	use super::{BlockQueue, Config, State};
-	use ethcore::test_helpers::{get_good_dummy_block_seq, get_good_dummy_block};
+	use std::time::Duration;
+	use super::{BlockQueue, Config, State, Status, Waiting, target_verifiers, MAX_QUARANTINED_PER_PEER};
+	use ethereum_types::{H256, U256};
+	use ethcore::test_helpers::{get_good_dummy_block_seq, get_good_dummy_block_fork_seq, get_good_dummy_block};
 	use ethcore::client::Client;
 	use parity_bytes::Bytes;
+	use rlp::RlpStream;
+	use triehash::ordered_trie_root;
 	use common_types::{
 		errors::{EthcoreError, ImportError},
+		header::Header,
 		verification::Unverified,
 		view,
 		views::BlockView,
@@ -784,6 +1183,32 @@ mod tests {
 		Unverified::from_rlp(bytes).expect("Should be valid rlp")
 	}
 
+	// a block which passes the checks on import, but not verification: the sender of its
+	// transaction can't be recovered.
+	fn get_unrecoverable_sender_block(number: u64, parent_hash: &H256) -> Bytes {
+		let mut transaction = RlpStream::new_list(9);
+		transaction.append(&0u8).append(&0u8).append(&21000u32).append_empty_data().append(&0u8).append_empty_data();
+		// there's no point on the curve with x = 5
+		transaction.append(&27u8).append(&5u8).append(&1u8);
+		let transaction = transaction.out();
+
+		let spec = spec::new_test();
+		let mut header = Header::new();
+		header.set_gas_limit(*spec.genesis_header().gas_limit());
+		header.set_difficulty(U256::from(0x20000));
+		header.set_timestamp(number * 10);
+		header.set_number(number);
+		header.set_parent_hash(*parent_hash);
+		header.set_state_root(*spec.genesis_header().state_root());
+		header.set_transactions_root(ordered_trie_root(Some(&transaction)));
+
+		let mut block = RlpStream::new_list(3);
+		block.append(&header);
+		block.begin_list(1).append_raw(&transaction, 1);
+		block.append_raw(&rlp::EMPTY_LIST_RLP, 1);
+		block.out()
+	}
+
 	#[test]
 	fn can_be_created() {
 		// TODO better test
@@ -863,6 +1288,177 @@ mod tests {
 		assert!(queue.queue_info().is_empty());
 	}
 
+	#[test]
+	fn bad_items_can_be_cleared() {
+		let queue = get_test_queue(false);
+		let block = get_good_dummy_block();
+		let hash = view!(BlockView, &block).header().hash().clone();
+		queue.import(new_unverified(block)).expect("valid by definition");
+		queue.flush();
+		queue.drain(10);
+
+		queue.mark_as_failed(&[hash]);
+		assert!(match queue.status(&hash) { Status::Bad => true, _ => false });
+		match queue.import(new_unverified(get_good_dummy_block())) {
+			Err((EthcoreError::Import(ImportError::KnownBad), _)) => {},
+			_ => panic!("expected KnownBad"),
+		}
+
+		queue.clear_bad();
+		assert!(match queue.status(&hash) { Status::Unknown => true, _ => false });
+		queue.import(new_unverified(get_good_dummy_block())).expect("bad items were cleared");
+	}
+
+	#[test]
+	fn drops_siblings_beyond_fork_width() {
+		let spec = spec::new_test();
+		let genesis = spec.genesis_header().hash();
+		let mut config = Config::default();
+		config.max_fork_width = 2;
+		let queue = BlockQueue::<Client>::new(config, spec.engine, IoChannel::disconnected(), true);
+
+		let siblings: Vec<_> = (1..4).map(|number| get_good_dummy_block_fork_seq(number, 0, &genesis).remove(0)).collect();
+		let first = queue.import(new_unverified(siblings[0].clone())).expect("valid by definition");
+		queue.import(new_unverified(siblings[1].clone())).expect("valid by definition");
+		match queue.import(new_unverified(siblings[2].clone())) {
+			Err((EthcoreError::Import(ImportError::ForkTooWide), _)) => {},
+			other => panic!("expected ForkTooWide, got {:?}", other.map_err(|(e, _)| e)),
+		}
+
+		queue.flush();
+		assert_eq!(queue.drain(10).len(), 2);
+		queue.mark_as_good(&[first]);
+		queue.import(new_unverified(siblings[2].clone())).expect("a sibling was imported");
+	}
+
+	#[test]
+	fn forgets_children_of_invalid_items() {
+		let spec = spec::new_test();
+		let genesis = spec.genesis_header().hash();
+		let mut config = Config::default();
+		config.max_fork_width = 1;
+		let queue = BlockQueue::<Client>::new(config, spec.engine, IoChannel::disconnected(), true);
+
+		let invalid = get_unrecoverable_sender_block(1, &genesis);
+		let invalid_hash = view!(BlockView, &invalid).header().hash();
+		let child = get_good_dummy_block_fork_seq(2, 0, &invalid_hash).remove(0);
+		let child_hash = view!(BlockView, &child).header().hash();
+		queue.import(new_unverified(invalid)).expect("passes the checks on import");
+		queue.import(new_unverified(child.clone())).expect("valid by definition");
+		queue.flush();
+
+		assert!(queue.drain(10).is_empty());
+		assert_eq!(queue.total_difficulty(), 0.into());
+		assert!(match queue.status(&child_hash) { Status::Bad => true, _ => false });
+		match queue.import(new_unverified(child)) {
+			Err((EthcoreError::Import(ImportError::KnownBad), _)) => {},
+			other => panic!("expected KnownBad, got {:?}", other.map_err(|(e, _)| e)),
+		}
+
+		// the invalid item doesn't count as a sibling anymore
+		queue.import(new_unverified(get_good_dummy_block())).expect("the invalid sibling is gone");
+	}
+
+	#[test]
+	fn verifies_children_after_their_parent() {
+		let queue = get_test_queue(false);
+		let blocks = get_good_dummy_block_seq(3);
+		for block in &blocks {
+			queue.import(new_unverified(block.clone())).expect("valid by definition");
+		}
+		queue.flush();
+
+		let hashes: Vec<_> = blocks.iter().map(|block| view!(BlockView, block).header().hash()).collect();
+		let drained: Vec<_> = queue.drain(10).iter().map(|block| block.header.hash()).collect();
+		assert_eq!(drained, hashes);
+		assert!(queue.verification.waiting.lock().is_empty());
+	}
+
+	#[test]
+	fn verifies_consecutive_blocks_in_parallel() {
+		let mut waiting = Waiting::<Unverified>::default();
+		let parent = H256::from_low_u64_be(1);
+		waiting.verifying.insert(parent);
+
+		// the only child of an item being verified doesn't wait for it
+		assert!(!waiting.must_wait(&parent, 1));
+		// fork siblings do
+		assert!(waiting.must_wait(&parent, 2));
+
+		// children of a waiting item wait as well, whatever their number
+		waiting.verifying.remove(&parent);
+		assert!(!waiting.must_wait(&parent, 2));
+		waiting.parked.insert(parent);
+		assert!(waiting.must_wait(&parent, 1));
+	}
+
+	#[test]
+	fn quarantines_future_blocks() {
+		use std::sync::Arc;
+		use std::time::{Duration, SystemTime, UNIX_EPOCH};
+		use parking_lot::Mutex;
+		use common_types::errors::BlockError;
+		use crate::verification::Clock;
+
+		struct TestClock(Mutex<SystemTime>);
+
+		impl Clock for TestClock {
+			fn now(&self) -> SystemTime { *self.0.lock() }
+		}
+
+		// the dummy block has timestamp 40
+		let clock = Arc::new(TestClock(Mutex::new(UNIX_EPOCH + Duration::from_secs(20))));
+		let spec = spec::new_test();
+		let queue = BlockQueue::<Client>::new(Config::default(), spec.engine, IoChannel::disconnected(), true)
+			.with_clock(clock.clone());
+
+		match queue.import(new_unverified(get_good_dummy_block())) {
+			Err((EthcoreError::Block(BlockError::TemporarilyInvalid(_)), _)) => {},
+			other => panic!("expected TemporarilyInvalid, got {:?}", other.map_err(|(e, _)| e)),
+		}
+		assert_eq!(queue.quarantined_count(), 1);
+
+		let retry = |queue: &BlockQueue<Client>| for (input, peer) in queue.take_quarantined() {
+			let _ = queue.import_from(input, peer);
+		};
+		retry(&queue);
+		assert_eq!(queue.quarantined_count(), 1);
+
+		*clock.0.lock() = UNIX_EPOCH + Duration::from_secs(40);
+		retry(&queue);
+		assert_eq!(queue.quarantined_count(), 0);
+		queue.flush();
+		assert_eq!(queue.drain(10).len(), 1);
+	}
+
+	#[test]
+	fn bounds_quarantined_blocks_per_peer() {
+		use std::sync::Arc;
+		use std::time::{SystemTime, UNIX_EPOCH};
+		use crate::verification::Clock;
+
+		struct TestClock;
+
+		impl Clock for TestClock {
+			fn now(&self) -> SystemTime { UNIX_EPOCH + Duration::from_secs(20) }
+		}
+
+		let spec = spec::new_test();
+		let queue = BlockQueue::<Client>::new(Config::default(), spec.engine, IoChannel::disconnected(), true)
+			.with_clock(Arc::new(TestClock));
+
+		// blocks with timestamp 40 on different parents
+		let future_block = |parent: u64| new_unverified(get_good_dummy_block_fork_seq(4, 0, &H256::from_low_u64_be(parent)).remove(0));
+		for parent in 0..=MAX_QUARANTINED_PER_PEER as u64 {
+			assert!(queue.import_from(future_block(parent), Some(1)).is_err());
+		}
+		assert_eq!(queue.quarantined_count(), MAX_QUARANTINED_PER_PEER);
+
+		// other peers aren't affected
+		assert!(queue.import_from(future_block(MAX_QUARANTINED_PER_PEER as u64 + 1), Some(2)).is_err());
+		assert_eq!(queue.quarantined_count(), MAX_QUARANTINED_PER_PEER + 1);
+	}
+
 	#[test]
 	fn test_mem_limit() {
 		let spec = spec::new_test();
@@ -891,6 +1487,23 @@ mod tests {
 		assert!(queue.num_verifiers() == 1);
 	}
 
+	#[test]
+	fn target_verifiers_follows_backlog() {
+		let latency = Some(Duration::from_millis(10));
+		// nothing to do.
+		assert_eq!(target_verifiers(4, 0, 0, latency), 1);
+		// import is the bottleneck.
+		assert_eq!(target_verifiers(4, 100, 200, latency), 3);
+		// 1000 items at 10ms on 4 threads take 2.5s.
+		assert_eq!(target_verifiers(4, 1000, 0, latency), 5);
+		// 20 items at 10ms on 4 threads take 50ms.
+		assert_eq!(target_verifiers(4, 20, 0, latency), 3);
+		// 200 items at 10ms on 4 threads take 500ms.
+		assert_eq!(target_verifiers(4, 200, 0, latency), 4);
+		// no measurement.
+		assert_eq!(target_verifiers(4, 1000, 0, None), 4);
+	}
+
 	#[test]
 	fn readjust_verifiers() {
 		let queue = get_test_queue(true);
@@ -23,7 +23,9 @@ use common_types::{
 	BlockNumber,
 	encoded,
 	verification::Unverified,
-	log_entry::{LogEntry, LocalizedLogEntry},
+	filter::Filter,
+	ids::BlockId,
+	log_entry::LocalizedLogEntry,
 };
 use ethereum_types::{BloomRef, H256};
 use parity_bytes::Bytes;
@@ -107,8 +109,7 @@ impl BlockProvider for TestBlockChain {
 		unimplemented!()
 	}
 
-	fn logs<F>(&self, _blocks: Vec<H256>, _matches: F, _limit: Option<usize>) -> Vec<LocalizedLogEntry>
-		where F: Fn(&LogEntry) -> bool, Self: Sized {
+	fn logs(&self, _filter: &Filter) -> Result<Vec<LocalizedLogEntry>, BlockId> {
 		unimplemented!()
 	}
 }
@@ -22,6 +22,7 @@
 //! 3. Final verification against the blockchain done before enactment.
 
 use std::collections::HashSet;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use keccak_hash::keccak;
@@ -44,10 +45,61 @@ use common_types::{
 
 use time_utils::CheckedSystemTime;
 
+/// Source of the current time used when checking header timestamps.
+pub trait Clock: Send + Sync {
+	/// Returns the current time.
+	fn now(&self) -> SystemTime;
+}
+
+/// A `Clock` reading the system time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+	fn now(&self) -> SystemTime {
+		SystemTime::now()
+	}
+}
+
+/// Bounds on how far into the future a header timestamp may be.
+///
+/// Headers more than `max_drift` ahead of the clock are temporarily invalid and may be
+/// retried later; headers more than ten times `max_drift` ahead are invalid.
+#[derive(Clone)]
+pub struct TimestampPolicy {
+	/// Maximum accepted distance of a header timestamp into the future.
+	pub max_drift: Duration,
+	/// Clock the timestamps are checked against.
+	pub clock: Arc<dyn Clock>,
+}
+
+impl TimestampPolicy {
+	/// Create a policy checking against the system clock.
+	pub fn new(max_drift: Duration) -> Self {
+		TimestampPolicy {
+			max_drift,
+			clock: Arc::new(SystemClock),
+		}
+	}
+}
+
+impl Default for TimestampPolicy {
+	fn default() -> Self {
+		TimestampPolicy::new(Duration::from_secs(15))
+	}
+}
+
 /// Phase 1 quick block verification. Only does checks that are cheap. Operates on a single block
-pub fn verify_block_basic(block: &Unverified, engine: &dyn Engine, check_seal: bool) -> Result<(), Error> {
+///
+/// The timestamp is checked last, so a block reported as `TemporarilyInvalid` passed all the
+/// other checks and is worth keeping until the clock catches up.
+pub fn verify_block_basic(
+	block: &Unverified,
+	engine: &dyn Engine,
+	check_seal: bool,
+	timestamps: &TimestampPolicy,
+) -> Result<(), Error> {
 	verify_header_params(&block.header, engine, check_seal)?;
-	verify_header_time(&block.header)?;
 	verify_block_integrity(block)?;
 
 	if check_seal {
@@ -73,7 +125,7 @@ pub fn verify_block_basic(block: &Unverified, engine: &dyn Engine, check_seal: b
 		engine.verify_transaction_basic(t, &block.header)?;
 	}
 
-	Ok(())
+	verify_header_time(&block.header, timestamps)
 }
 
 /// Phase 2 verification. Perform costly checks such as transaction signatures and block nonce for ethash.
@@ -89,7 +141,7 @@ pub fn verify_block_unordered(block: Unverified, engine: &dyn Engine, check_seal
 	}
 	// Verify transactions.
 	let nonce_cap = if header.number() >= engine.params().dust_protection_transition {
-		Some((engine.params().nonce_cap_increment * header.number()).into())
+		Some((engine.params().nonce_cap_increment.saturating_mul(header.number())).into())
 	} else {
 		None
 	};
@@ -336,11 +388,10 @@ pub(crate) fn verify_header_params(header: &Header, engine: &dyn Engine, check_s
 }
 
 /// A header verification step that should be done for new block headers, but not for uncles.
-pub(crate) fn verify_header_time(header: &Header) -> Result<(), Error> {
-	const ACCEPTABLE_DRIFT: Duration = Duration::from_secs(15);
+pub(crate) fn verify_header_time(header: &Header, timestamps: &TimestampPolicy) -> Result<(), Error> {
 	// this will resist overflow until `year 2037`
-	let max_time = SystemTime::now() + ACCEPTABLE_DRIFT;
-	let invalid_threshold = max_time + ACCEPTABLE_DRIFT * 9;
+	let max_time = timestamps.clock.now() + timestamps.max_drift;
+	let invalid_threshold = max_time + timestamps.max_drift * 9;
 	let timestamp = CheckedSystemTime::checked_add(UNIX_EPOCH, Duration::from_secs(header.timestamp()))
 		.ok_or(BlockError::TimestampOverflow)?;
 
@@ -480,7 +531,7 @@ mod tests {
 
 	fn basic_test(bytes: &[u8], engine: &dyn Engine) -> Result<(), Error> {
 		let unverified = Unverified::from_rlp(bytes.to_vec())?;
-		verify_block_basic(&unverified, engine, true)
+		verify_block_basic(&unverified, engine, true, &TimestampPolicy::default())
 	}
 
 	fn family_test<BC>(bytes: &[u8], engine: &dyn Engine, bc: &BC) -> Result<(), Error> where BC: BlockProvider {
@@ -711,15 +762,21 @@ mod tests {
 		header.set_timestamp(10);
 		check_fail_timestamp(family_test(&create_test_block_with_data(&header, &good_transactions, &good_uncles), engine, &bc), false);
 
+		// the timestamp is only checked once the rest of the block is valid
 		header = good.clone();
+		header.set_transactions_root(good_transactions_root.clone());
+		header.set_uncles_hash(good_uncles_hash.clone());
 		// will return `BlockError::TimestampOverflow` when timestamp > `i32::max_value()`
 		header.set_timestamp(i32::max_value() as u64);
 		check_fail_timestamp(basic_test(&create_test_block_with_data(&header, &good_transactions, &good_uncles), engine), false);
 
-		header = good.clone();
 		header.set_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 20);
 		check_fail_timestamp(basic_test(&create_test_block_with_data(&header, &good_transactions, &good_uncles), engine), true);
 
+		header.set_uncles_hash(H256::random());
+		check_fail(basic_test(&create_test_block_with_data(&header, &good_transactions, &good_uncles), engine),
+			InvalidUnclesHash(Mismatch { expected: good_uncles_hash.clone(), found: header.uncles_hash().clone() }));
+
 		header = good.clone();
 		header.set_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 10);
 		header.set_uncles_hash(good_uncles_hash.clone());
@@ -780,4 +837,28 @@ mod tests {
 		check_fail(unordered_test(&create_test_block_with_data(&header, &bad_transactions, &[]), &engine), TooManyTransactions(keypair.address()));
 		unordered_test(&create_test_block_with_data(&header, &good_transactions, &[]), &engine).unwrap();
 	}
+
+	#[test]
+	fn header_time_respects_policy() {
+		struct FixedClock(SystemTime);
+
+		impl Clock for FixedClock {
+			fn now(&self) -> SystemTime { self.0 }
+		}
+
+		let policy = TimestampPolicy {
+			max_drift: Duration::from_secs(5),
+			clock: Arc::new(FixedClock(UNIX_EPOCH + Duration::from_secs(1000))),
+		};
+
+		let mut header = Header::default();
+		header.set_timestamp(1005);
+		check_ok(verify_header_time(&header, &policy));
+
+		header.set_timestamp(1006);
+		check_fail_timestamp(verify_header_time(&header, &policy), true);
+
+		header.set_timestamp(1051);
+		check_fail_timestamp(verify_header_time(&header, &policy), false);
+	}
 }
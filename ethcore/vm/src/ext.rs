@@ -52,6 +52,17 @@ pub enum MessageCallResult {
 	Reverted(U256, ReturnData),
 }
 
+/// Reason a nested call or create is refused before any frame is entered.
+///
+/// A refused call costs nothing but the instruction itself: all gas handed to it is returned.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CallRefusal {
+	/// Call stack is already `Schedule::max_depth` frames deep.
+	DepthLimit,
+	/// Caller can't afford the transferred value.
+	InsufficientBalance,
+}
+
 /// Specifies how an address is calculated for a new contract.
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum CreateContractAddress {
@@ -154,6 +165,20 @@ pub trait Ext {
 	/// then A depth is 0, B is 1, C is 2 and so on.
 	fn depth(&self) -> usize;
 
+	/// Checks whether `sender` may enter a nested call or create transferring `value`.
+	///
+	/// Depth is checked first, so a call at the depth limit is refused with `DepthLimit`
+	/// regardless of balance. Zero `value` skips the balance lookup.
+	fn check_nested_call(&self, sender: &Address, value: &U256) -> Result<Option<CallRefusal>> {
+		if self.depth() >= self.schedule().max_depth {
+			return Ok(Some(CallRefusal::DepthLimit));
+		}
+		if !value.is_zero() && self.balance(sender)? < *value {
+			return Ok(Some(CallRefusal::InsufficientBalance));
+		}
+		Ok(None)
+	}
+
 	/// Increments sstore refunds counter.
 	fn add_sstore_refund(&mut self, value: usize);
 
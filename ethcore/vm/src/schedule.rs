@@ -315,6 +315,18 @@ impl Schedule {
 		schedule
 	}
 
+	/// Gas table of the Ethereum main net fork with the given name, if there is one.
+	pub fn from_name(name: &str) -> Option<Schedule> {
+		match name {
+			"frontier" => Some(Self::new_frontier()),
+			"homestead" => Some(Self::new_homestead()),
+			"byzantium" => Some(Self::new_byzantium()),
+			"constantinople" => Some(Self::new_constantinople()),
+			"istanbul" => Some(Self::new_istanbul()),
+			_ => None,
+		}
+	}
+
 	fn new(efcd: bool, hdc: bool, tcg: usize) -> Schedule {
 		Schedule {
 			exceptional_failed_code_deposit: efcd,
@@ -403,3 +415,12 @@ fn schedule_evm_assumptions() {
 	assert_eq!(s1.quad_coeff_div, 512);
 	assert_eq!(s2.quad_coeff_div, 512);
 }
+
+#[test]
+#[cfg(test)]
+fn schedule_from_name() {
+	assert_eq!(Schedule::from_name("homestead").unwrap().tx_create_gas, 53000);
+	assert!(Schedule::from_name("istanbul").unwrap().have_chain_id);
+	assert!(!Schedule::from_name("constantinople").unwrap().have_chain_id);
+	assert!(Schedule::from_name("olympic").is_none());
+}
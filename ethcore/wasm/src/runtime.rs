@@ -413,14 +413,11 @@ impl<'a> Runtime<'a> {
 		let result_alloc_len: u32 = args.nth_checked(5 + vofs)?;
 		trace!(target: "wasm", "    result_len: {:?}", result_alloc_len);
 
-		if let Some(ref val) = val {
-			let address_balance = self.ext.balance(&self.context.address)
-				.map_err(|_| Error::BalanceQueryError)?;
-
-			if &address_balance < val {
-				trace!(target: "wasm", "runtime: call failed due to balance check");
-				return Ok((-1i32).into());
-			}
+		if let Some(refusal) = self.ext.check_nested_call(&self.context.address, &val.unwrap_or_default())
+			.map_err(|_| Error::BalanceQueryError)?
+		{
+			trace!(target: "wasm", "runtime: call refused ({:?})", refusal);
+			return Ok((-1i32).into());
 		}
 
 		self.adjusted_charge(|schedule| schedule.call_gas as u64)?;
@@ -530,6 +527,13 @@ impl<'a> Runtime<'a> {
 			* U256::from(self.ext.schedule().wasm().opcodes_mul)
 			/ U256::from(self.ext.schedule().wasm().opcodes_div);
 
+		if let Some(refusal) = self.ext.check_nested_call(&self.context.address, &endowment)
+			.map_err(|_| Error::BalanceQueryError)?
+		{
+			trace!(target: "wasm", "runtime: create contract refused ({:?})", refusal);
+			return Ok((-1i32).into());
+		}
+
 		match self.ext.create(&gas_left, &endowment, &code, &self.context.code_version, scheme, false).ok().expect("Trap is false; trap error will not happen; qed") {
 			vm::ContractCreateResult::Created(address, gas_left) => {
 				self.memory.set(result_ptr, address.as_bytes())?;
@@ -59,6 +59,12 @@ pub struct EthashParams {
 	pub homestead_transition: Option<Uint>,
 	/// Reward per block in wei.
 	pub block_reward: Option<BlockReward>,
+	/// See main EthashParams docs.
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub uncle_reward_divisor: Option<Uint>,
+	/// See main EthashParams docs.
+	#[serde(default, deserialize_with="uint::validate_optional_non_zero")]
+	pub nephew_reward_divisor: Option<Uint>,
 	/// Block at which the block reward contract should start being used.
 	pub block_reward_contract_transition: Option<Uint>,
 	/// Block reward contract address (setting the block reward contract
@@ -128,6 +134,7 @@ mod tests {
 				"durationLimit": "0x0d",
 				"homesteadTransition": "0x42",
 				"blockReward": "0x100",
+				"uncleRewardDivisor": "0x10",
 				"daoHardforkTransition": "0x08",
 				"daoHardforkBeneficiary": "0xabcabcabcabcabcabcabcabcabcabcabcabcabca",
 				"daoHardforkAccounts": [
@@ -170,6 +177,8 @@ mod tests {
 				duration_limit: Some(Uint(U256::from(0x0d))),
 				homestead_transition: Some(Uint(U256::from(0x42))),
 				block_reward: Some(BlockReward::Single(Uint(U256::from(0x100)))),
+				uncle_reward_divisor: Some(Uint(U256::from(0x10))),
+				nephew_reward_divisor: None,
 				block_reward_contract_address: None,
 				block_reward_contract_code: None,
 				block_reward_contract_transition: None,
@@ -231,6 +240,8 @@ mod tests {
 				duration_limit: None,
 				homestead_transition: None,
 				block_reward: None,
+				uncle_reward_divisor: None,
+				nephew_reward_divisor: None,
 				block_reward_contract_address: None,
 				block_reward_contract_code: None,
 				block_reward_contract_transition: None,
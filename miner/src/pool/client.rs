@@ -20,6 +20,7 @@
 //! It includes any Ethereum state parts required for checking the transaction and
 //! any consensus-required structure of the transaction.
 
+use std::collections::HashSet;
 use std::fmt;
 
 use ethereum_types::{U256, H256, H160 as Address};
@@ -45,6 +46,64 @@ pub enum TransactionType {
 	Service,
 }
 
+/// Outcome of checking a transaction against a `TransactionAdmissionPolicy`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Admission {
+	/// Admit the transaction.
+	Accept,
+	/// Admit the transaction and treat it as a service transaction,
+	/// so it's accepted even below the minimal gas price.
+	AcceptAsService,
+	/// Refuse the transaction with given error.
+	Reject(transaction::Error),
+}
+
+/// Node-level rules deciding which transactions may enter the pool,
+/// e.g. based on sender, recipient, calldata or gas price.
+pub trait TransactionAdmissionPolicy: Send + Sync {
+	/// Decide whether given transaction should be admitted.
+	fn admit(&self, tx: &transaction::SignedTransaction) -> Admission;
+}
+
+/// Admission policy that accepts every transaction.
+#[derive(Debug, Default, Clone)]
+pub struct AllowAll;
+
+impl TransactionAdmissionPolicy for AllowAll {
+	fn admit(&self, _tx: &transaction::SignedTransaction) -> Admission {
+		Admission::Accept
+	}
+}
+
+/// Admission policy restricting who may send transactions and where to.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AdmissionRules {
+	/// Senders whose transactions are admitted; all senders are if empty.
+	pub allowed_senders: HashSet<Address>,
+	/// Addresses transactions may not be sent to.
+	pub denied_recipients: HashSet<Address>,
+}
+
+impl AdmissionRules {
+	/// Returns true if the rules admit every transaction.
+	pub fn is_empty(&self) -> bool {
+		self.allowed_senders.is_empty() && self.denied_recipients.is_empty()
+	}
+}
+
+impl TransactionAdmissionPolicy for AdmissionRules {
+	fn admit(&self, tx: &transaction::SignedTransaction) -> Admission {
+		if !self.allowed_senders.is_empty() && !self.allowed_senders.contains(&tx.sender()) {
+			return Admission::Reject(transaction::Error::NotAllowed);
+		}
+		match tx.action {
+			transaction::Action::Call(ref to) if self.denied_recipients.contains(to) =>
+				Admission::Reject(transaction::Error::NotAllowed),
+			_ => Admission::Accept,
+		}
+	}
+}
+
 /// Verification client.
 pub trait Client: fmt::Debug + Sync {
 	/// Is transaction with given hash already in the blockchain?
@@ -72,6 +131,9 @@ pub trait Client: fmt::Debug + Sync {
 	/// Classify transaction (check if transaction is filtered by some contracts).
 	fn transaction_type(&self, tx: &transaction::SignedTransaction) -> TransactionType;
 
+	/// Check transaction against node's admission policy.
+	fn admission(&self, tx: &transaction::SignedTransaction) -> Admission;
+
 	/// Performs pre-validation of RLP decoded transaction
 	fn decode_transaction(&self, transaction: &[u8])
 		-> Result<transaction::UnverifiedTransaction, transaction::Error>;
@@ -18,6 +18,7 @@
 
 use ethereum_types::{U256, H256, Address};
 use parity_util_mem::MallocSizeOfExt;
+use rlp::Encodable;
 use types::transaction;
 use txpool;
 
@@ -119,6 +120,12 @@ pub trait ScoredTransaction {
 
 	/// Gets transaction nonce.
 	fn nonce(&self) -> U256;
+
+	/// Gets transaction gas limit.
+	fn gas(&self) -> &U256;
+
+	/// Gets the size of RLP-encoded transaction in bytes.
+	fn size(&self) -> usize;
 }
 
 /// Verified transaction stored in the pool.
@@ -130,6 +137,7 @@ pub struct VerifiedTransaction {
 	sender: Address,
 	priority: Priority,
 	insertion_id: usize,
+	rlp_size: usize,
 }
 
 impl VerifiedTransaction {
@@ -138,15 +146,18 @@ impl VerifiedTransaction {
 	/// This method should be used only:
 	/// 1. for tests
 	/// 2. In case we are converting pending block transactions that are already in the queue to match the function signature.
-	pub fn from_pending_block_transaction(tx: transaction::SignedTransaction) -> Self {
+	pub fn from_pending_block_transaction<T: Into<transaction::PendingTransaction>>(tx: T) -> Self {
+		let tx = tx.into();
 		let hash = tx.hash();
 		let sender = tx.sender();
+		let rlp_size = tx.rlp_bytes().len();
 		VerifiedTransaction {
-			transaction: tx.into(),
+			transaction: tx,
 			hash,
 			sender,
 			priority: Priority::Retracted,
 			insertion_id: 0,
+			rlp_size,
 		}
 	}
 
@@ -165,6 +176,11 @@ impl VerifiedTransaction {
 		&self.transaction
 	}
 
+	/// Returns `true` if the transaction was submitted to this node.
+	pub fn is_local(&self) -> bool {
+		self.priority.is_local()
+	}
+
 }
 
 impl txpool::VerifiedTransaction for VerifiedTransaction {
@@ -198,6 +214,16 @@ impl ScoredTransaction for VerifiedTransaction {
 	fn nonce(&self) -> U256 {
 		self.transaction.nonce
 	}
+
+	/// Gets transaction gas limit.
+	fn gas(&self) -> &U256 {
+		&self.transaction.gas
+	}
+
+	/// Gets the size of RLP-encoded transaction in bytes.
+	fn size(&self) -> usize {
+		self.rlp_size
+	}
 }
 
 /// Pool transactions status
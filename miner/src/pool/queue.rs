@@ -29,7 +29,7 @@ use types::transaction;
 
 use pool::{
 	self, replace, scoring, verifier, client, ready, listener,
-	PrioritizationStrategy, PendingOrdering, PendingSettings, TxStatus
+	PrioritizationStrategy, PendingOrdering, PendingSettings, ScoredTransaction, TxStatus
 };
 use pool::local_transactions::LocalTransactionsList;
 
@@ -204,6 +204,7 @@ pub struct TransactionQueue {
 	options: RwLock<verifier::Options>,
 	cached_pending: RwLock<CachedPending>,
 	recently_rejected: RecentlyRejected,
+	max_size_per_sender: Option<usize>,
 }
 
 impl TransactionQueue {
@@ -220,9 +221,16 @@ impl TransactionQueue {
 			options: RwLock::new(verification_options),
 			cached_pending: RwLock::new(CachedPending::none()),
 			recently_rejected: RecentlyRejected::new(cmp::max(MIN_REJECTED_CACHE_SIZE, max_count / 4)),
+			max_size_per_sender: None,
 		}
 	}
 
+	/// Limits the total RLP size of non-local transactions pooled from a single sender.
+	pub fn with_max_size_per_sender(mut self, max_size_per_sender: Option<usize>) -> Self {
+		self.max_size_per_sender = max_size_per_sender;
+		self
+	}
+
 	/// Update verification options
 	///
 	/// Some parameters of verification may vary in time (like block gas limit or minimal gas price).
@@ -288,6 +296,7 @@ impl TransactionQueue {
 
 				let imported = verifier
 					.verify_transaction(transaction)
+					.and_then(|verified| self.check_size_per_sender(&verified).map(|_| verified))
 					.and_then(|verified| {
 						self.pool.write().import(verified, &mut replace).map_err(convert_error)
 					});
@@ -312,12 +321,46 @@ impl TransactionQueue {
 		results
 	}
 
+	/// Rejects the transaction if it would take its sender over `max_size_per_sender`.
+	///
+	/// Transactions with the same nonce are not counted, since they would be replaced.
+	fn check_size_per_sender(&self, tx: &pool::VerifiedTransaction) -> Result<(), transaction::Error> {
+		let limit = match self.max_size_per_sender {
+			Some(limit) if !tx.is_local() => limit,
+			_ => return Ok(()),
+		};
+
+		let all = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
+		let pooled: usize = self.pool.read().pending_from_sender(all, &tx.sender)
+			.filter(|pooled| pooled.nonce() != tx.nonce())
+			.map(|pooled| pooled.size())
+			.sum();
+
+		if pooled + tx.size() > limit {
+			trace!(
+				target: "txqueue",
+				"[{:?}] Rejected tx, sender would exceed size limit: {} > {}",
+				tx.hash,
+				pooled + tx.size(),
+				limit,
+			);
+			return Err(transaction::Error::LimitReached);
+		}
+		Ok(())
+	}
+
 	/// Returns all transactions in the queue without explicit ordering.
 	pub fn all_transactions(&self) -> Vec<Arc<pool::VerifiedTransaction>> {
 		let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
 		self.pool.read().unordered_pending(ready).collect()
 	}
 
+	/// Returns all transactions in the queue from given sender, in nonce order.
+	pub fn transactions_from_sender(&self, address: &Address) -> Vec<Arc<pool::VerifiedTransaction>> {
+		let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
+		self.pool.read().pending_from_sender(ready, address).collect()
+	}
+
 	/// Returns all transaction hashes in the queue without explicit ordering.
 	pub fn all_transaction_hashes(&self) -> Vec<H256> {
 		let ready = |_tx: &pool::VerifiedTransaction| txpool::Readiness::Ready;
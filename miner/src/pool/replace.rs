@@ -20,8 +20,8 @@
 //! in the pool. The decision whether to reject, replace or retain both is
 //! delegated to an implementation of `ShouldReplace`.
 //!
-//! Here we decide based on the sender, the nonce and gas price, and finally
-//! on the `Readiness` of the transactions when comparing them
+//! Here we decide based on the sender, the nonce and gas price, the fees paid
+//! per byte and finally on the `Readiness` of the transactions when comparing them
 
 use std::cmp;
 
@@ -85,6 +85,11 @@ where
 					}
 				}
 
+				if new.priority() == old.priority() && pays_less_per_byte(old, new) {
+					// prevent a large transaction from evicting smaller ones that pay more for the space they use
+					return Choice::RejectNew
+				}
+
 				let state = &self.client;
 				// calculate readiness based on state nonce + pooled txs from same sender
 				let is_ready = |replace: &ReplaceTransaction<T>| {
@@ -114,6 +119,16 @@ where
 	}
 }
 
+/// Returns `true` if `new` is larger than `old` but pays less in fees per byte of its RLP.
+fn pays_less_per_byte<T: ScoredTransaction>(old: &ReplaceTransaction<T>, new: &ReplaceTransaction<T>) -> bool {
+	if new.size() <= old.size() {
+		return false;
+	}
+	let old_fee = old.gas_price().saturating_mul(*old.gas());
+	let new_fee = new.gas_price().saturating_mul(*new.gas());
+	new_fee.full_mul(U256::from(old.size())) < old_fee.full_mul(U256::from(new.size()))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -290,6 +305,20 @@ mod tests {
 		assert_eq!(should_replace(&replace, tx_local_high_gas.clone(), tx_regular_low_gas.clone()), RejectNew);
 	}
 
+	#[test]
+	fn should_not_replace_smaller_transaction_paying_more_per_byte() {
+		let scoring = NonceAndGasPrice(PrioritizationStrategy::GasPriceOnly);
+		let client = TestClient::new().with_nonce(123);
+		let replace = ReplaceByScoreAndReadiness::new(scoring, client);
+
+		let tx_small = Tx::gas_price(1).signed().verified();
+		let tx_big_cheap = Tx::gas_price(2).big_one().verified();
+		let tx_big_expensive = Tx::gas_price(100_000).big_one().verified();
+
+		assert_eq!(should_replace(&replace, tx_small.clone(), tx_big_cheap), RejectNew);
+		assert_eq!(should_replace(&replace, tx_small, tx_big_expensive), ReplaceOld);
+	}
+
 	#[test]
 	fn should_not_replace_ready_transaction_with_future_transaction() {
 		let scoring = NonceAndGasPrice(PrioritizationStrategy::GasPriceOnly);
@@ -21,7 +21,7 @@ use rlp::Rlp;
 use types::transaction::{self, Transaction, SignedTransaction, UnverifiedTransaction};
 
 use pool;
-use pool::client::AccountDetails;
+use pool::client::{AccountDetails, Admission};
 
 const MAX_TRANSACTION_SIZE: usize = 15 * 1024;
 
@@ -31,6 +31,7 @@ pub struct TestClient {
 	account_details: AccountDetails,
 	gas_required: U256,
 	is_service_transaction: bool,
+	admission: Admission,
 	local_address: Address,
 	max_transaction_size: usize,
 }
@@ -46,6 +47,7 @@ impl Default for TestClient {
 			},
 			gas_required: 21_000.into(),
 			is_service_transaction: false,
+			admission: Admission::Accept,
 			local_address: Default::default(),
 			max_transaction_size: MAX_TRANSACTION_SIZE,
 		}
@@ -82,6 +84,11 @@ impl TestClient {
 		self
 	}
 
+	pub fn with_admission(mut self, admission: Admission) -> Self {
+		self.admission = admission;
+		self
+	}
+
 	pub fn verify<T: Into<transaction::PendingTransaction>>(&self, tx: T) -> pool::VerifiedTransaction {
 		let tx = tx.into();
 		pool::VerifiedTransaction {
@@ -137,6 +144,10 @@ impl pool::client::Client for TestClient {
 		}
 	}
 
+	fn admission(&self, _tx: &SignedTransaction) -> Admission {
+		self.admission.clone()
+	}
+
 	fn decode_transaction(&self, transaction: &[u8]) -> Result<UnverifiedTransaction, transaction::Error> {
 		let rlp = Rlp::new(&transaction);
 		if rlp.as_raw().len() > self.max_transaction_size {
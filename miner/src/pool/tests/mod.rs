@@ -14,11 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
-use ethereum_types::U256;
+use ethereum_types::{Address, U256};
+use parity_crypto::publickey::{Random, Generator};
+use rlp::Encodable;
 use types::transaction::{self, PendingTransaction};
 use txpool;
 
 use pool::{verifier, TransactionQueue, PrioritizationStrategy, PendingSettings, PendingOrdering};
+use pool::client::{Admission, AdmissionRules, TransactionAdmissionPolicy};
 
 pub mod tx;
 pub mod client;
@@ -775,6 +778,51 @@ fn should_not_accept_external_service_transaction_if_sender_not_certified() {
 	assert_eq!(res, vec![Ok(())]);
 }
 
+#[test]
+fn should_apply_admission_policy() {
+	// given
+	let txq = new_queue();
+	let (tx1, tx2) = Tx::default().signed_pair();
+	let (tx1, tx2) = (tx1.unverified(), tx2.local());
+	let tx3 = Tx::gas_price(0).signed().unverified();
+
+	// when
+	let reject = TestClient::new().with_admission(Admission::Reject(transaction::Error::SenderBanned));
+	let res = txq.import(reject, vec![tx1, tx2]);
+	assert_eq!(res, vec![
+		Err(transaction::Error::SenderBanned),
+		Err(transaction::Error::SenderBanned),
+	]);
+
+	// then
+	let res = txq.import(TestClient::new().with_admission(Admission::AcceptAsService), vec![tx3]);
+	assert_eq!(res, vec![Ok(())]);
+	assert_eq!(txq.status().status.transaction_count, 1);
+}
+
+#[test]
+fn should_check_admission_rules() {
+	// given
+	let tx1 = Tx::default().signed();
+	let tx2 = Tx::default().signed();
+	let recipient = Address::from_low_u64_be(5);
+	let mut call = Tx::default().unsigned();
+	call.action = transaction::Action::Call(recipient);
+	let tx3 = call.sign(Random.generate().unwrap().secret(), None);
+
+	// when
+	let mut rules = AdmissionRules::default();
+	assert!(rules.is_empty());
+	rules.allowed_senders.insert(tx1.sender());
+	rules.allowed_senders.insert(tx3.sender());
+	rules.denied_recipients.insert(recipient);
+
+	// then
+	assert_eq!(rules.admit(&tx1), Admission::Accept);
+	assert_eq!(rules.admit(&tx2), Admission::Reject(transaction::Error::NotAllowed));
+	assert_eq!(rules.admit(&tx3), Admission::Reject(transaction::Error::NotAllowed));
+}
+
 #[test]
 fn should_not_return_transactions_over_nonce_cap() {
 	// given
@@ -889,6 +937,23 @@ fn should_reject_big_transaction() {
 	assert_eq!(res, vec![Err(transaction::Error::TooBig)]);
 }
 
+#[test]
+fn should_limit_total_size_of_transactions_per_sender() {
+	// given
+	let (tx1, tx2, tx3) = Tx::default().signed_triple();
+	let limit = tx1.rlp_bytes().len() + tx2.rlp_bytes().len();
+	let txq = new_queue().with_max_size_per_sender(Some(limit));
+	let res = txq.import(TestClient::new(), vec![tx1, tx2].unverified());
+	assert_eq!(res, vec![Ok(()), Ok(())]);
+
+	// when
+	let res = txq.import(TestClient::new(), vec![tx3.unverified()]);
+
+	// then
+	assert_eq!(res, vec![Err(transaction::Error::LimitReached)]);
+	assert_eq!(txq.status().status.transaction_count, 2);
+}
+
 #[test]
 fn should_include_local_transaction_to_a_full_pool() {
 	// given
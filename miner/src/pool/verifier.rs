@@ -31,7 +31,7 @@ use rlp::Encodable;
 use txpool;
 use types::transaction;
 
-use super::client::{Client, TransactionType};
+use super::client::{Admission, Client, TransactionType};
 use super::VerifiedTransaction;
 
 /// Verification options.
@@ -260,17 +260,25 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 		};
 
 		// Verify RLP payload
-		if let Err(err) = self.client.decode_transaction(&transaction.rlp_bytes()) {
+		let rlp = transaction.rlp_bytes();
+		if let Err(err) = self.client.decode_transaction(&rlp) {
 			debug!(target: "txqueue", "[{:?}] Rejected transaction's rlp payload", err);
 			return Err(err)
 		}
 
 		let sender = transaction.sender();
+		let admission = self.client.admission(&transaction);
+		if let Admission::Reject(err) = admission {
+			debug!(target: "txqueue", "[{:?}] Rejected tx by admission policy: {:?}", hash, err);
+			return Err(err)
+		}
+
 		let account_details = self.client.account_details(&sender);
 
 		if transaction.gas_price < self.options.minimal_gas_price {
-			let transaction_type = self.client.transaction_type(&transaction);
-			if let TransactionType::Service = transaction_type {
+			let is_service = admission == Admission::AcceptAsService
+				|| self.client.transaction_type(&transaction) == TransactionType::Service;
+			if is_service {
 				debug!(target: "txqueue", "Service tx {:?} below minimal gas price accepted", hash);
 			} else if is_own || account_details.is_local {
 				info!(target: "own_tx", "Local tx {:?} below minimal gas price accepted", hash);
@@ -338,6 +346,7 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 			hash,
 			sender,
 			insertion_id: self.id.fetch_add(1, atomic::Ordering::AcqRel),
+			rlp_size: rlp.len(),
 		})
 	}
 }
@@ -74,10 +74,7 @@ impl NotifyWork for WorkPoster {
 		let target = ethash::difficulty_to_boundary(&difficulty);
 		let seed_hash = &self.seed_compute.lock().hash_block_number(number);
 		let seed_hash = H256::from_slice(&seed_hash[..]);
-		let body = format!(
-			r#"{{ "result": ["0x{:x}","0x{:x}","0x{:x}","0x{:x}"] }}"#,
-			pow_hash, seed_hash, target, number
-		);
+		let body = work_package_json(&pow_hash, &seed_hash, &target, number);
 
 		for u in &self.urls {
 			let u = u.clone();
@@ -86,8 +83,37 @@ impl NotifyWork for WorkPoster {
 					.with_header(header::CONTENT_TYPE, HeaderValue::from_static("application/json"))
 					.with_body(body.clone()), Default::default()
 			).map_err(move |e| {
-				warn!("Error sending HTTP notification to {} : {}, retrying", u, e);
+				warn!("Error sending HTTP notification to {} : {}", u, e);
 			}).map(|_| ()));
 		}
 	}
 }
+
+/// Formats a work package the same way `eth_getWork` returns it:
+/// pow-hash, seed-hash, boundary and block number.
+fn work_package_json(pow_hash: &H256, seed_hash: &H256, target: &H256, number: u64) -> String {
+	format!(
+		r#"{{ "result": ["0x{:x}","0x{:x}","0x{:x}","0x{:x}"] }}"#,
+		pow_hash, seed_hash, target, number
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn should_format_work_package() {
+		let body = work_package_json(
+			&H256::from_low_u64_be(1),
+			&H256::from_low_u64_be(2),
+			&H256::from_low_u64_be(3),
+			0x10,
+		);
+
+		assert_eq!(body, format!(
+			r#"{{ "result": ["0x{zeros}1","0x{zeros}2","0x{zeros}3","0x10"] }}"#,
+			zeros = "0".repeat(63)
+		));
+	}
+}
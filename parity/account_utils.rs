@@ -55,6 +55,11 @@ mod accounts {
 	pub fn accounts_list(_account_provider: Arc<AccountProvider>) -> Arc<Fn() -> Vec<Address> + Send + Sync> {
 		Arc::new(|| vec![])
 	}
+
+	pub fn address_book_names(_account_provider: Arc<AccountProvider>) -> Option<Box<dyn ::parity_rpc::name_resolution::NameResolver>> {
+		warn!("Note: Names can't be resolved through the address book without account support.");
+		None
+	}
 }
 
 #[cfg(feature = "accounts")]
@@ -77,7 +82,8 @@ mod accounts {
 
 		let path = dirs.keys_path(data_dir);
 		upgrade_key_location(&dirs.legacy_keys_path(cfg.testnet), &path);
-		let dir = Box::new(RootDiskDirectory::create(&path).map_err(|e| format!("Could not open keys directory: {}", e))?);
+		// the lock is held for as long as the account provider lives, refusing a second instance.
+		let dir = Box::new(RootDiskDirectory::create_locked(&path).map_err(|e| format!("Could not open keys directory: {}", e))?);
 		let account_settings = AccountProviderSettings {
 			unlock_keep_secret: cfg.enable_fast_unlock,
 			blacklisted_accounts: 	match *spec {
@@ -210,6 +216,10 @@ mod accounts {
 		Arc::new(move || account_provider.accounts().unwrap_or_default())
 	}
 
+	pub fn address_book_names(account_provider: Arc<AccountProvider>) -> Option<Box<dyn ::parity_rpc::name_resolution::NameResolver>> {
+		Some(Box::new(::parity_rpc::name_resolution::AddressBook(account_provider)))
+	}
+
 	fn insert_dev_account(account_provider: &AccountProvider) {
 		let secret = parity_crypto::publickey::Secret::from_str("4d5db4107d237df6a3d58ee5f70ae63d73d7658d4026f2eefd2f204c81682cb7".into()).expect("Valid account;qed");
 		let dev_account = parity_crypto::publickey::KeyPair::from_secret(secret.clone()).expect("Valid secret produces valid key;qed");
@@ -240,4 +250,5 @@ pub use self::accounts::{
 	miner_author,
 	private_tx_signer,
 	accounts_list,
+	address_book_names,
 };
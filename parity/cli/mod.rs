@@ -19,6 +19,7 @@ mod usage;
 mod presets;
 
 use std::collections::HashSet;
+use parity_rpc::DEFAULT_HEAVY_CALLS;
 use super::helpers;
 
 usage! {
@@ -213,6 +214,25 @@ usage! {
 			}
 		}
 
+		CMD cmd_node_key
+		{
+			"Manage the key identifying this node on the network",
+
+			CMD cmd_node_key_export
+			{
+				"Print the stored node key",
+			}
+
+			CMD cmd_node_key_import
+			{
+				"Store the given node key, replacing the current one",
+
+				ARG arg_node_key_import_secret: (Option<String>) = None,
+				"<SECRET>",
+				"Hex-encoded secret or a phrase to derive it from",
+			}
+		}
+
 		CMD cmd_db
 		{
 			"Manage the database representing the state of the blockchain on this system",
@@ -389,6 +409,10 @@ usage! {
 			"--private-passwords=[FILE]...",
 			"Provide a file containing passwords for unlocking accounts (signer, private account, validators).",
 
+			ARG arg_private_tx_relay: (Option<String>) = None, or |c: &Config| c.private_tx.as_ref()?.relay.clone(),
+			"--private-tx-relay=[URL]",
+			"Send transactions submitted with parity_sendPrivateTransaction to this HTTP endpoint, as eth_sendRawTransaction, instead of the local queue. They are never broadcast to peers.",
+
 		["UI Options"]
 			ARG arg_ui_path: (String) = "$BASE/signer", or |c: &Config| c.ui.as_ref()?.path.clone(),
 			"--ui-path=[PATH]",
@@ -407,6 +431,10 @@ usage! {
 			"--reserved-only",
 			"Connect only to reserved nodes.",
 
+			FLAG flag_reuse_port: (bool) = false, or |c: &Config| c.network.as_ref()?.reuse_port.clone(),
+			"--reuse-port",
+			"Allow other processes to listen on the same TCP port, e.g. several nodes behind a load balancer.",
+
 			FLAG flag_no_ancient_blocks: (bool) = false, or |_| None,
 			"--no-ancient-blocks",
 			"Disable downloading old blocks after snapshot restoration or warp sync. Not recommended.",
@@ -435,6 +463,14 @@ usage! {
 			"--max-peers=[NUM]",
 			"Allow up to NUM peers.",
 
+			ARG arg_max_inbound_peers: (Option<u16>) = None, or |c: &Config| c.network.as_ref()?.max_inbound_peers.clone(),
+			"--max-inbound-peers=[NUM]",
+			"Accept up to NUM peers connecting to us. Defaults to the slots left by --min-peers in --max-peers, but at least half of --min-peers.",
+
+			ARG arg_max_outbound_peers: (Option<u16>) = None, or |c: &Config| c.network.as_ref()?.max_outbound_peers.clone(),
+			"--max-outbound-peers=[NUM]",
+			"Connect to up to NUM peers ourselves. Defaults to --min-peers.",
+
 			ARG arg_snapshot_peers: (u16) = 0u16, or |c: &Config| c.network.as_ref()?.snapshot_peers.clone(),
 			"--snapshot-peers=[NUM]",
 			"Allow additional NUM peers for a snapshot sync.",
@@ -443,6 +479,10 @@ usage! {
 			"--nat=[METHOD]",
 			"Specify method to use for determining public address. Must be one of: any, none, upnp, extip:<IP>.",
 
+			ARG arg_public_address: (Option<String>) = None, or |c: &Config| c.network.as_ref()?.public_address.clone(),
+			"--public-address=[IP:PORT]",
+			"Advertise IP:PORT to other peers instead of the address determined by --nat. Useful when the node is reachable on a different port, e.g. behind a load balancer.",
+
 			ARG arg_allow_ips: (String) = "all", or |c: &Config| c.network.as_ref()?.allow_ips.clone(),
 			"--allow-ips=[FILTER]",
 			"Filter outbound connections. Must be one of: private - connect to private network IP addresses only; public - connect to public network IP addresses only; all - connect to any IP address.",
@@ -451,13 +491,33 @@ usage! {
 			"--max-pending-peers=[NUM]",
 			"Allow up to NUM pending connections.",
 
+			ARG arg_max_upload_rate: (Option<u32>) = None, or |c: &Config| c.network.as_ref()?.max_upload_rate.clone(),
+			"--max-upload-rate=[KB/S]",
+			"Limit sync upload bandwidth to KB/S kilobytes per second. Requests from peers are ignored while over the limit.",
+
+			ARG arg_max_download_rate: (Option<u32>) = None, or |c: &Config| c.network.as_ref()?.max_download_rate.clone(),
+			"--max-download-rate=[KB/S]",
+			"Limit sync download bandwidth to KB/S kilobytes per second. No new requests are sent while over the limit.",
+
+			ARG arg_max_peer_requests: (Option<u32>) = None, or |c: &Config| c.network.as_ref()?.max_peer_requests.clone(),
+			"--max-peer-requests=[NUM]",
+			"Serve at most NUM sync requests per second to a single peer.",
+
+			ARG arg_local_tx_delay: (Option<u64>) = None, or |c: &Config| c.network.as_ref()?.local_tx_delay.clone(),
+			"--local-tx-delay=[SECS]",
+			"Hold back transactions submitted to this node for SECS seconds and then announce each to a few random peers only, to make it harder to link them to this node. Announcements are repeated every SECS seconds until the transaction is mined.",
+
+			ARG arg_local_tx_peers: (usize) = 2usize, or |c: &Config| c.network.as_ref()?.local_tx_peers.clone(),
+			"--local-tx-peers=[NUM]",
+			"Number of random peers held back local transactions are announced to. Only used with --local-tx-delay.",
+
 			ARG arg_network_id: (Option<u64>) = None, or |c: &Config| c.network.as_ref()?.id.clone(),
 			"--network-id=[INDEX]",
 			"Override the network identifier from the chain we are on.",
 
 			ARG arg_bootnodes: (Option<String>) = None, or |c: &Config| c.network.as_ref()?.bootnodes.as_ref().map(|vec| vec.join(",")),
 			"--bootnodes=[NODES]",
-			"Override the bootnodes from our chain. NODES should be comma-delimited enodes.",
+			"Override the bootnodes from our chain. NODES should be comma-delimited enodes, or dns://DOMAIN for the enodes listed in the TXT records of DOMAIN.",
 
 			ARG arg_node_key: (Option<String>) = None, or |c: &Config| c.network.as_ref()?.node_key.clone(),
 			"--node-key=[KEY]",
@@ -518,6 +578,30 @@ usage! {
 			"--jsonrpc-server-threads=[NUM]",
 			"Enables multiple threads handling incoming connections for HTTP JSON-RPC server.",
 
+			ARG arg_jsonrpc_heavy_threads: (usize) = 2usize, or |c: &Config| c.rpc.as_ref()?.heavy_threads,
+			"--jsonrpc-heavy-threads=[NUM]",
+			"Maximum number of heavy HTTP JSON-RPC calls executing at the same time, further ones are queued, up to 32 per thread. Heavy calls run on their own worker pool so they can't occupy all of the server threads. 0 runs them on the server threads.",
+
+			ARG arg_jsonrpc_heavy_calls: (String) = DEFAULT_HEAVY_CALLS, or |c: &Config| c.rpc.as_ref()?.heavy_calls.as_ref().map(|vec| vec.join(",")),
+			"--jsonrpc-heavy-calls=[METHODS]",
+			"Comma-delimited list of heavy HTTP JSON-RPC methods with their timeouts in seconds, as METHOD:SECONDS. A heavy call not answered within its timeout returns an error asking to narrow the query.",
+
+			ARG arg_jsonrpc_abi_dir: (Option<String>) = None, or |c: &Config| c.rpc.as_ref()?.abi_dir.clone(),
+			"--jsonrpc-abi-dir=[PATH]",
+			"Register the contract ABIs found in PATH at startup, one <ADDRESS>.json file per contract. Logs of registered contracts are decoded in eth_getLogs and receipt responses. ABIs can also be registered with parity_registerAbi.",
+
+			ARG arg_jsonrpc_call_cache_size: (usize) = 0usize, or |c: &Config| c.rpc.as_ref()?.call_cache_size.clone(),
+			"--jsonrpc-call-cache-size=[ENTRIES]",
+			"Number of eth_call results to cache until the next block is imported. Calls on the pending block or with state overrides are never cached. Setting this to 0 disables the cache.",
+
+			ARG arg_jsonrpc_conflict_search_depth: (u64) = 256u64, or |c: &Config| c.rpc.as_ref()?.conflict_search_depth.clone(),
+			"--jsonrpc-conflict-search-depth=[BLOCKS]",
+			"Number of recent blocks parity_checkTransactionIncluded reads to find a mined transaction conflicting with the checked one. Each block body in range is read on every such call. Setting this to 0 disables the search.",
+
+			ARG arg_jsonrpc_max_traces: (usize) = 10000usize, or |c: &Config| c.rpc.as_ref()?.max_traces.clone(),
+			"--jsonrpc-max-traces=[TRACES]",
+			"Maximum number of traces trace_filter returns at once. Requests matching more are rejected and have to be paged through with after and count. Setting this to 0 removes the limit.",
+
 			ARG arg_jsonrpc_cors: (String) = "none", or |c: &Config| c.rpc.as_ref()?.cors.as_ref().map(|vec| vec.join(",")),
 			"--jsonrpc-cors=[URL]",
 			"Specify CORS header for HTTP JSON-RPC API responses. Special options: \"all\", \"none\".",
@@ -724,6 +808,10 @@ usage! {
 			"--infinite-pending-block",
 			"Pending block will be created with maximal possible gas limit and will execute all transactions in the queue. Note that such block is invalid and should never be attempted to be mined.",
 
+			FLAG flag_pseudo_pending_block: (bool) = false, or |c: &Config| c.mining.as_ref()?.pseudo_pending_block.clone(),
+			"--pseudo-pending-block",
+			"When not sealing, answer queries for the pending block from the queued transactions executed on top of the latest block. The block is rebuilt when the latest block or the queue changes, at most once per --reseal-min-period, and no work is generated for it.",
+
 			FLAG flag_no_persistent_txqueue: (bool) = false, or |c: &Config| c.parity.as_ref()?.no_persistent_txqueue,
 			"--no-persistent-txqueue",
 			"Don't save pending local transactions to disk to be restored whenever the node restarts.",
@@ -786,10 +874,22 @@ usage! {
 			"--tx-queue-per-sender=[LIMIT]",
 			"Maximum number of transactions per sender in the queue. By default it's 1% of the entire queue, but not less than 16.",
 
+			ARG arg_tx_queue_per_sender_size: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.tx_queue_per_sender_size.clone(),
+			"--tx-queue-per-sender-size=[KB]",
+			"Maximum total size of transactions per sender in the queue. Transactions from local accounts are not limited.",
+
 			ARG arg_tx_queue_locals: (Option<String>) = None, or |c: &Config| helpers::join_set(c.mining.as_ref()?.tx_queue_locals.as_ref()),
 			"--tx-queue-locals=[ACCOUNTS]",
 			"Specify local accounts for which transactions are prioritized in the queue. ACCOUNTS is a comma-delimited list of addresses.",
 
+			ARG arg_tx_queue_allowed_senders: (Option<String>) = None, or |c: &Config| helpers::join_set(c.mining.as_ref()?.tx_queue_allowed_senders.as_ref()),
+			"--tx-queue-allowed-senders=[ACCOUNTS]",
+			"Only admit transactions sent by these accounts to the queue. ACCOUNTS is a comma-delimited list of addresses. All senders are admitted by default.",
+
+			ARG arg_tx_queue_denied_recipients: (Option<String>) = None, or |c: &Config| helpers::join_set(c.mining.as_ref()?.tx_queue_denied_recipients.as_ref()),
+			"--tx-queue-denied-recipients=[ACCOUNTS]",
+			"Refuse transactions sent to these accounts. ACCOUNTS is a comma-delimited list of addresses.",
+
 			ARG arg_tx_queue_strategy: (String) = "gas_price", or |c: &Config| c.mining.as_ref()?.tx_queue_strategy.clone(),
 			"--tx-queue-strategy=[S]",
 			"Prioritization strategy used to order transactions in the queue. S may be: gas_price - Prioritize txs with high gas price",
@@ -808,7 +908,11 @@ usage! {
 
 			ARG arg_gas_price_percentile: (usize) = 50usize, or |c: &Config| c.mining.as_ref()?.gas_price_percentile,
 			"--gas-price-percentile=[PCT]",
-			"Set PCT percentile gas price value from last 100 blocks as default gas price when sending transactions.",
+			"Set PCT percentile gas price value from last --gas-price-blocks blocks as default gas price when sending transactions.",
+
+			ARG arg_gas_price_blocks: (usize) = 100usize, or |c: &Config| c.mining.as_ref()?.gas_price_blocks,
+			"--gas-price-blocks=[N]",
+			"Sample the gas prices of transactions in the last N blocks for the default gas price and parity_gasPriceHistogram.",
 
 			ARG arg_author: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.author.clone(),
 			"--author=[ADDRESS]",
@@ -826,9 +930,21 @@ usage! {
 			"--tx-time-limit=[MS]",
 			"Maximal time for processing single transaction. If enabled senders of transactions offending the limit will get other transactions penalized.",
 
+			ARG arg_block_min_gas_price: (Option<u64>) = None, or |c: &Config| c.mining.as_ref()?.block_min_gas_price.clone(),
+			"--block-min-gas-price=[WEI]",
+			"Minimum amount of Wei per GAS a transaction must pay to be included in authored blocks, even if it was accepted into the queue as a local transaction.",
+
+			ARG arg_block_max_txs: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.block_max_txs.clone(),
+			"--block-max-txs=[NUM]",
+			"Maximum number of transactions in authored blocks.",
+
+			ARG arg_tx_max_gas_percent: (Option<u8>) = None, or |c: &Config| c.mining.as_ref()?.tx_max_gas_percent.clone(),
+			"--tx-max-gas-percent=[PCT]",
+			"Maximum share of the block gas limit, in percent, a single transaction may use to be included in authored blocks.",
+
 			ARG arg_extra_data: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.extra_data.clone(),
 			"--extra-data=[STRING]",
-			"Specify a custom extra-data for authored blocks, no more than 32 characters.",
+			"Specify a custom extra-data for authored blocks, no more than 32 characters. Any occurrence of {version} is replaced with the client version.",
 
 			ARG arg_notify_work: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.notify_work.as_ref().map(|vec| vec.join(",")),
 			"--notify-work=[URLS]",
@@ -842,6 +958,10 @@ usage! {
 			"--max-round-blocks-to-import=[S]",
 			"Maximal number of blocks to import for each import round.",
 
+			ARG arg_new_blocks_notify_interval: (u64) = 0u64, or |c: &Config| c.mining.as_ref()?.new_blocks_notify_interval.clone(),
+			"--new-blocks-notify-interval=[MS]",
+			"Minimal interval between new block notifications while blocks are still being imported. Blocks imported in between are reported together. Notifications are never delayed once the import queue is empty. 0 disables batching.",
+
 		["Internal Options"]
 			FLAG flag_can_restart: (bool) = false, or |_| None,
 			"--can-restart",
@@ -868,6 +988,26 @@ usage! {
 			"--log-file=[FILENAME]",
 			"Specify a filename into which logging should be appended.",
 
+			FLAG flag_rpc_access_log: (bool) = false, or |c: &Config| c.misc.as_ref()?.rpc_access_log.clone(),
+			"--rpc-access-log",
+			"Log the methods, params size, duration and outcome of every request served over HTTP, WebSockets and IPC.",
+
+			ARG arg_rpc_slow_threshold: (u64) = 0u64, or |c: &Config| c.misc.as_ref()?.rpc_slow_threshold.clone(),
+			"--rpc-slow-threshold=[MS]",
+			"Log the full params of RPC requests taking longer than given number of milliseconds. Params of methods that may take passwords or secrets are redacted. 0 disables the slow request log.",
+
+			ARG arg_rpc_name_resolution: (String) = "none", or |c: &Config| c.misc.as_ref()?.rpc_name_resolution.as_ref().map(|vec| vec.join(",")),
+			"--rpc-name-resolution=[SOURCES]",
+			"Accept names in place of addresses in RPC parameters, e.g. the account of eth_getBalance or the recipient of eth_call, resolved through the given comma-delimited sources in order. Sources: \"addressbook\" (names of local address book entries), \"registry\" (the on-chain registry). Resolutions are cached until the next block. \"none\" disables it.",
+
+			FLAG flag_rpc_strict_address_checksum: (bool) = false, or |c: &Config| c.misc.as_ref()?.rpc_strict_address_checksum.clone(),
+			"--rpc-strict-address-checksum",
+			"Reject RPC requests with mixed-case addresses failing their EIP-55 checksum, instead of accepting addresses in any case. All-lowercase and all-uppercase addresses are still accepted.",
+
+			ARG arg_export_events_to: (Option<String>) = None, or |c: &Config| c.misc.as_ref()?.export_events_to.clone(),
+			"--export-events-to=[URL]",
+			"Post new block and reorg events as JSON arrays to the given HTTP endpoint, e.g. http://127.0.0.1:8080/events. Events are kept on disk until the endpoint accepts them with a 2xx status, including across restarts. Full nodes only.",
+
 		["Footprint Options"]
 			FLAG flag_scale_verifiers: (bool) = false, or |c: &Config| c.footprint.as_ref()?.scale_verifiers.clone(),
 			"--scale-verifiers",
@@ -889,6 +1029,10 @@ usage! {
 			"--pruning-memory=[MB]",
 			"The ideal amount of memory in megabytes to use to store recent states. As many states as possible will be kept within this limit, and at least --pruning-history states will always be kept.",
 
+			ARG arg_reorg_history: (u64) = 256u64, or |c: &Config| c.footprint.as_ref()?.reorg_history.clone(),
+			"--reorg-history=[NUM]",
+			"Number of most recent chain reorganizations to keep a record of in the database, available through parity_reorgHistory. 0 disables recording.",
+
 			ARG arg_cache_size_db: (u32) = 128u32, or |c: &Config| c.footprint.as_ref()?.cache_size_db.clone(),
 			"--cache-size-db=[MB]",
 			"Override database cache size.",
@@ -913,6 +1057,14 @@ usage! {
 			"--fat-db=[BOOL]",
 			"Build appropriate information to allow enumeration of all accounts and storage keys. Doubles the size of the state database. BOOL may be one of on, off or auto.",
 
+			FLAG flag_store_tx_outputs: (bool) = false, or |c: &Config| c.footprint.as_ref()?.store_tx_outputs.clone(),
+			"--store-tx-outputs",
+			"Keep the return data of every imported transaction, so receipts can report it without re-executing the block. Only applies to blocks imported while enabled. Outputs are pruned along with the state of old blocks, unless --pruning=archive.",
+
+			FLAG flag_db_compress_receipts: (bool) = false, or |c: &Config| c.footprint.as_ref()?.db_compress_receipts.clone(),
+			"--db-compress-receipts",
+			"Compress block receipts before writing them to the database. Receipts written before enabling it remain readable, and so do compressed ones after disabling it.",
+
 			ARG arg_cache_size: (Option<u32>) = None, or |c: &Config| c.footprint.as_ref()?.cache_size.clone(),
 			"--cache-size=[MB]",
 			"Set total amount of discretionary memory to use for the entire system, overrides other cache and queue options.",
@@ -921,6 +1073,22 @@ usage! {
 			"--num-verifiers=[INT]",
 			"Amount of verifier threads to use or to begin with, if verifier auto-scaling is enabled.",
 
+			ARG arg_min_verifiers: (Option<usize>) = None, or |c: &Config| c.footprint.as_ref()?.min_verifiers.clone(),
+			"--min-verifiers=[INT]",
+			"Lowest amount of verifier threads kept active when verifier auto-scaling is enabled.",
+
+			ARG arg_max_verifiers: (Option<usize>) = None, or |c: &Config| c.footprint.as_ref()?.max_verifiers.clone(),
+			"--max-verifiers=[INT]",
+			"Highest amount of verifier threads to use. Defaults to the number of CPUs.",
+
+			ARG arg_max_fork_width: (usize) = 16usize, or |c: &Config| c.footprint.as_ref()?.max_fork_width.clone(),
+			"--max-fork-width=[INT]",
+			"Maximum number of blocks with the same parent kept in the verification queue. Further siblings are dropped.",
+
+			ARG arg_max_timestamp_drift: (u64) = 15u64, or |c: &Config| c.footprint.as_ref()?.max_timestamp_drift.clone(),
+			"--max-timestamp-drift=[SECS]",
+			"Maximum number of seconds a block timestamp may be ahead of the local clock. Blocks further ahead are held back until the clock catches up.",
+
 		["Import/export Options"]
 			FLAG flag_no_seal_check: (bool) = false, or |_| None,
 			"--no-seal-check",
@@ -1084,7 +1252,7 @@ usage! {
 
 			ARG arg_gasprice: (Option<String>) = None, or |_| None,
 			"--gasprice=[WEI]",
-			"Equivalent to --min-gas-price WEI.",
+			"Equivalent to --min-gas-price WEI. Units may be given, e.g. 20gwei.",
 
 			ARG arg_cache: (Option<u32>) = None, or |_| None,
 			"--cache=[MB]",
@@ -1220,6 +1388,7 @@ struct PrivateTransactions {
 	passwords: Option<String>,
 	sstore_url: Option<String>,
 	sstore_threshold: Option<u32>,
+	relay: Option<String>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1248,9 +1417,17 @@ struct Network {
 	interface: Option<String>,
 	min_peers: Option<u16>,
 	max_peers: Option<u16>,
+	max_inbound_peers: Option<u16>,
+	max_outbound_peers: Option<u16>,
 	snapshot_peers: Option<u16>,
 	max_pending_peers: Option<u16>,
+	max_upload_rate: Option<u32>,
+	max_download_rate: Option<u32>,
+	max_peer_requests: Option<u32>,
+	local_tx_delay: Option<u64>,
+	local_tx_peers: Option<usize>,
 	nat: Option<String>,
+	public_address: Option<String>,
 	allow_ips: Option<String>,
 	id: Option<u64>,
 	bootnodes: Option<Vec<String>>,
@@ -1258,6 +1435,7 @@ struct Network {
 	node_key: Option<String>,
 	reserved_peers: Option<String>,
 	reserved_only: Option<bool>,
+	reuse_port: Option<bool>,
 	no_serve_light: Option<bool>,
 }
 
@@ -1271,6 +1449,12 @@ struct Rpc {
 	apis: Option<Vec<String>>,
 	hosts: Option<Vec<String>>,
 	server_threads: Option<usize>,
+	heavy_threads: Option<usize>,
+	heavy_calls: Option<Vec<String>>,
+	abi_dir: Option<String>,
+	call_cache_size: Option<usize>,
+	conflict_search_depth: Option<u64>,
+	max_traces: Option<usize>,
 	max_payload: Option<usize>,
 	keep_alive: Option<bool>,
 	experimental_rpcs: Option<bool>,
@@ -1367,9 +1551,13 @@ struct Mining {
 	work_queue_size: Option<usize>,
 	tx_gas_limit: Option<String>,
 	tx_time_limit: Option<u64>,
+	block_min_gas_price: Option<u64>,
+	block_max_txs: Option<usize>,
+	tx_max_gas_percent: Option<u8>,
 	relay_set: Option<String>,
 	min_gas_price: Option<u64>,
 	gas_price_percentile: Option<usize>,
+	gas_price_blocks: Option<usize>,
 	usd_per_tx: Option<String>,
 	usd_per_eth: Option<String>,
 	price_update_period: Option<String>,
@@ -1378,8 +1566,11 @@ struct Mining {
 	extra_data: Option<String>,
 	tx_queue_size: Option<usize>,
 	tx_queue_per_sender: Option<usize>,
+	tx_queue_per_sender_size: Option<usize>,
 	tx_queue_mem_limit: Option<u32>,
 	tx_queue_locals: Option<HashSet<String>>,
+	tx_queue_allowed_senders: Option<HashSet<String>>,
+	tx_queue_denied_recipients: Option<HashSet<String>>,
 	tx_queue_strategy: Option<String>,
 	tx_queue_ban_count: Option<u16>,
 	tx_queue_ban_time: Option<u16>,
@@ -1389,7 +1580,9 @@ struct Mining {
 	notify_work: Option<Vec<String>>,
 	refuse_service_transactions: Option<bool>,
 	infinite_pending_block: Option<bool>,
+	pseudo_pending_block: Option<bool>,
 	max_round_blocks_to_import: Option<usize>,
+	new_blocks_notify_interval: Option<u64>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1408,6 +1601,7 @@ struct Footprint {
 	pruning: Option<String>,
 	pruning_history: Option<u64>,
 	pruning_memory: Option<usize>,
+	reorg_history: Option<u64>,
 	fast_and_loose: Option<bool>,
 	cache_size: Option<u32>,
 	cache_size_db: Option<u32>,
@@ -1416,8 +1610,14 @@ struct Footprint {
 	cache_size_state: Option<u32>,
 	db_compaction: Option<String>,
 	fat_db: Option<String>,
+	store_tx_outputs: Option<bool>,
+	db_compress_receipts: Option<bool>,
 	scale_verifiers: Option<bool>,
 	num_verifiers: Option<usize>,
+	min_verifiers: Option<usize>,
+	max_verifiers: Option<usize>,
+	max_fork_width: Option<usize>,
+	max_timestamp_drift: Option<u64>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1432,6 +1632,11 @@ struct Snapshots {
 struct Misc {
 	logging: Option<String>,
 	log_file: Option<String>,
+	rpc_access_log: Option<bool>,
+	rpc_slow_threshold: Option<u64>,
+	rpc_name_resolution: Option<Vec<String>>,
+	rpc_strict_address_checksum: Option<bool>,
+	export_events_to: Option<String>,
 	color: Option<bool>,
 	ports_shift: Option<u16>,
 	unsafe_expose: Option<bool>,
@@ -1459,7 +1664,7 @@ mod tests {
 	use super::{
 		Args, ArgsError,
 		Config, Operating, Account, Ui, Network, Ws, Rpc, Ipc, Dapps, Ipfs, Mining, Footprint,
-		Snapshots, Misc, Whisper, SecretStore, Light,
+		Snapshots, Misc, Whisper, SecretStore, Light, DEFAULT_HEAVY_CALLS,
 	};
 	use toml;
 	use clap::{ErrorKind as ClapErrorKind};
@@ -1721,6 +1926,9 @@ mod tests {
 			cmd_restore: false,
 			cmd_tools: false,
 			cmd_tools_hash: false,
+			cmd_node_key: false,
+			cmd_node_key_export: false,
+			cmd_node_key_import: false,
 			cmd_db: false,
 			cmd_db_kill: false,
 			cmd_db_reset: false,
@@ -1737,6 +1945,7 @@ mod tests {
 			arg_snapshot_file: None,
 			arg_restore_file: None,
 			arg_tools_hash_file: None,
+			arg_node_key_import_secret: None,
 
 			arg_enable_signing_queue: false,
 			arg_signer_sign_id: None,
@@ -1788,6 +1997,7 @@ mod tests {
 			arg_private_account: Some("0xdeadbeefcafe0000000000000000000000000000".into()),
 			arg_private_sstore_url: Some("http://localhost:8082".into()),
 			arg_private_sstore_threshold: Some(0),
+			arg_private_tx_relay: None,
 
 			flag_force_ui: false,
 			flag_no_ui: false,
@@ -1803,16 +2013,25 @@ mod tests {
 			arg_interface: "all".into(),
 			arg_min_peers: Some(25u16),
 			arg_max_peers: Some(50u16),
+			arg_max_inbound_peers: None,
+			arg_max_outbound_peers: None,
 			arg_max_pending_peers: 64u16,
+			arg_max_upload_rate: None,
+			arg_max_download_rate: None,
+			arg_max_peer_requests: None,
+			arg_local_tx_delay: None,
+			arg_local_tx_peers: 2usize,
 			arg_snapshot_peers: 0u16,
 			arg_allow_ips: "all".into(),
 			arg_nat: "any".into(),
+			arg_public_address: None,
 			arg_network_id: Some(1),
 			arg_bootnodes: Some("".into()),
 			flag_no_discovery: false,
 			arg_node_key: None,
 			arg_reserved_peers: Some("./path_to_file".into()),
 			flag_reserved_only: false,
+			flag_reuse_port: false,
 			flag_no_ancient_blocks: false,
 			flag_no_serve_light: false,
 
@@ -1827,6 +2046,12 @@ mod tests {
 			arg_jsonrpc_apis: "web3,eth,net,parity,traces,rpc,secretstore".into(),
 			arg_jsonrpc_hosts: "none".into(),
 			arg_jsonrpc_server_threads: Some(4),
+			arg_jsonrpc_heavy_threads: 2,
+			arg_jsonrpc_heavy_calls: DEFAULT_HEAVY_CALLS.into(),
+			arg_jsonrpc_abi_dir: None,
+			arg_jsonrpc_call_cache_size: 0usize,
+			arg_jsonrpc_conflict_search_depth: 256u64,
+			arg_jsonrpc_max_traces: 10000usize,
 			arg_jsonrpc_threads: None, // DEPRECATED, does nothing
 			arg_jsonrpc_max_payload: None,
 			arg_poll_lifetime: 60u32,
@@ -1889,10 +2114,14 @@ mod tests {
 			arg_work_queue_size: 20usize,
 			arg_tx_gas_limit: Some("10000000".into()),
 			arg_tx_time_limit: Some(100u64),
+			arg_block_min_gas_price: None,
+			arg_block_max_txs: None,
+			arg_tx_max_gas_percent: None,
 			arg_relay_set: "cheap".into(),
 			arg_min_gas_price: Some(0u64),
 			arg_usd_per_tx: "0.0001".into(),
 			arg_gas_price_percentile: 50usize,
+			arg_gas_price_blocks: 100usize,
 			arg_usd_per_eth: "auto".into(),
 			arg_price_update_period: "hourly".into(),
 			arg_gas_floor_target: "8000000".into(),
@@ -1902,8 +2131,11 @@ mod tests {
 			flag_tx_queue_no_early_reject: false,
 			arg_tx_queue_size: 8192usize,
 			arg_tx_queue_per_sender: None,
+			arg_tx_queue_per_sender_size: None,
 			arg_tx_queue_mem_limit: 4u32,
 			arg_tx_queue_locals: Some("0xdeadbeefcafe0000000000000000000000000000".into()),
+			arg_tx_queue_allowed_senders: None,
+			arg_tx_queue_denied_recipients: None,
 			arg_tx_queue_strategy: "gas_factor".into(),
 			arg_tx_queue_ban_count: Some(1u16),
 			arg_tx_queue_ban_time: Some(180u16),
@@ -1911,7 +2143,9 @@ mod tests {
 			arg_notify_work: Some("http://localhost:3001".into()),
 			flag_refuse_service_transactions: false,
 			flag_infinite_pending_block: false,
+			flag_pseudo_pending_block: false,
 			arg_max_round_blocks_to_import: 12usize,
+			arg_new_blocks_notify_interval: 0u64,
 
 			flag_stratum: false,
 			arg_stratum_interface: "local".to_owned(),
@@ -1923,6 +2157,7 @@ mod tests {
 			arg_pruning: "auto".into(),
 			arg_pruning_history: 64u64,
 			arg_pruning_memory: 500usize,
+			arg_reorg_history: 256u64,
 			arg_cache_size_db: 64u32,
 			arg_cache_size_blocks: 8u32,
 			arg_cache_size_queue: 50u32,
@@ -1931,8 +2166,14 @@ mod tests {
 			flag_fast_and_loose: false,
 			arg_db_compaction: "ssd".into(),
 			arg_fat_db: "auto".into(),
+			flag_store_tx_outputs: false,
+			flag_db_compress_receipts: false,
 			flag_scale_verifiers: true,
 			arg_num_verifiers: Some(6),
+			arg_min_verifiers: None,
+			arg_max_verifiers: None,
+			arg_max_fork_width: 16usize,
+			arg_max_timestamp_drift: 15u64,
 
 			// -- Import/Export Options
 			arg_export_blocks_from: "1".into(),
@@ -2005,6 +2246,11 @@ mod tests {
 			flag_version: false,
 			arg_logging: Some("own_tx=trace".into()),
 			arg_log_file: Some("/var/log/parity.log".into()),
+			flag_rpc_access_log: false,
+			arg_rpc_slow_threshold: 0u64,
+			arg_rpc_name_resolution: "none".into(),
+			flag_rpc_strict_address_checksum: false,
+			arg_export_events_to: None,
 			flag_no_color: false,
 			flag_no_config: false,
 		});
@@ -2078,16 +2324,25 @@ mod tests {
 				interface: None,
 				min_peers: Some(10),
 				max_peers: Some(20),
+				max_inbound_peers: None,
+				max_outbound_peers: None,
 				max_pending_peers: Some(30),
+				max_upload_rate: None,
+				max_download_rate: None,
+				max_peer_requests: None,
+				local_tx_delay: None,
+				local_tx_peers: None,
 				snapshot_peers: Some(40),
 				allow_ips: Some("public".into()),
 				nat: Some("any".into()),
+				public_address: None,
 				id: None,
 				bootnodes: None,
 				discovery: Some(true),
 				node_key: None,
 				reserved_peers: Some("./path/to/reserved_peers".into()),
 				reserved_only: Some(true),
+				reuse_port: None,
 				no_serve_light: None,
 			}),
 			websockets: Some(Ws {
@@ -2107,6 +2362,12 @@ mod tests {
 				apis: None,
 				hosts: None,
 				server_threads: Some(13),
+				heavy_threads: None,
+				heavy_calls: None,
+				abi_dir: None,
+				call_cache_size: None,
+				conflict_search_depth: None,
+				max_traces: None,
 				max_payload: None,
 				keep_alive: None,
 				experimental_rpcs: None,
@@ -2170,6 +2431,7 @@ mod tests {
 				relay_set: None,
 				min_gas_price: None,
 				gas_price_percentile: None,
+				gas_price_blocks: None,
 				usd_per_tx: None,
 				usd_per_eth: None,
 				price_update_period: Some("hourly".into()),
@@ -2177,8 +2439,11 @@ mod tests {
 				gas_cap: None,
 				tx_queue_size: Some(8192),
 				tx_queue_per_sender: None,
+				tx_queue_per_sender_size: None,
 				tx_queue_mem_limit: None,
 				tx_queue_locals: None,
+				tx_queue_allowed_senders: None,
+				tx_queue_denied_recipients: None,
 				tx_queue_strategy: None,
 				tx_queue_ban_count: None,
 				tx_queue_ban_time: None,
@@ -2186,18 +2451,24 @@ mod tests {
 				tx_queue_no_early_reject: None,
 				tx_gas_limit: None,
 				tx_time_limit: None,
+				block_min_gas_price: None,
+				block_max_txs: None,
+				tx_max_gas_percent: None,
 				extra_data: None,
 				remove_solved: None,
 				notify_work: None,
 				refuse_service_transactions: None,
 				infinite_pending_block: None,
+				pseudo_pending_block: None,
 				max_round_blocks_to_import: None,
+				new_blocks_notify_interval: None,
 			}),
 			footprint: Some(Footprint {
 				tracing: Some("on".into()),
 				pruning: Some("fast".into()),
 				pruning_history: Some(64),
 				pruning_memory: None,
+				reorg_history: None,
 				fast_and_loose: None,
 				cache_size: None,
 				cache_size_db: Some(256),
@@ -2206,8 +2477,14 @@ mod tests {
 				cache_size_state: Some(25),
 				db_compaction: Some("ssd".into()),
 				fat_db: Some("off".into()),
+				store_tx_outputs: None,
+				db_compress_receipts: None,
 				scale_verifiers: Some(false),
 				num_verifiers: None,
+				min_verifiers: None,
+				max_verifiers: None,
+				max_fork_width: None,
+				max_timestamp_drift: None,
 			}),
 			light: Some(Light {
 				on_demand_response_time_window: Some(2),
@@ -2223,6 +2500,11 @@ mod tests {
 			misc: Some(Misc {
 				logging: Some("own_tx=trace".into()),
 				log_file: Some("/var/log/parity.log".into()),
+				rpc_access_log: None,
+				rpc_slow_threshold: None,
+				rpc_name_resolution: None,
+				rpc_strict_address_checksum: None,
+				export_events_to: None,
 				color: Some(true),
 				ports_shift: Some(0),
 				unsafe_expose: Some(false),
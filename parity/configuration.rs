@@ -24,20 +24,23 @@ use std::cmp;
 use cli::{Args, ArgsError};
 use hash::keccak;
 use ethereum_types::{U256, H256, Address};
-use parity_version::{version_data, version};
+use parity_version::{version_data, version, with_version};
 use bytes::Bytes;
 use ansi_term::Colour;
 use sync::{NetworkConfiguration, validate_node_url, self};
-use parity_crypto::publickey::{Secret, Public};
+use parity_crypto::publickey::Public;
 use ethcore::miner::{stratum, MinerOptions};
 use snapshot::SnapshotConfiguration;
 use miner::pool;
 use verification::queue::VerifierSettings;
 
 use rpc::{IpcConfiguration, HttpConfiguration, WsConfiguration};
-use parity_rpc::NetworkSettings;
+use rpc_apis::NameSource;
+use event_export::Endpoint as EventExportEndpoint;
+use parity_rpc::{AddressFormat, HeavyCalls, NetworkSettings};
+use parity_rpc::informant::RpcLogging;
 use cache::CacheConfig;
-use helpers::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_price, geth_ipc_path, parity_ipc_path, to_bootnodes, to_addresses, to_address, to_queue_strategy, to_queue_penalization};
+use helpers::{to_duration, to_mode, to_block_id, to_u256, to_wei, to_pending_set, to_price, geth_ipc_path, parity_ipc_path, to_bootnodes, to_addresses, to_address, to_queue_strategy, to_queue_penalization, parse_cors, parse_hosts};
 use dir::helpers::{replace_home, replace_home_and_local};
 use params::{ResealPolicy, AccountsConfig, GasPricerConfig, MinerExtras, SpecType};
 use ethcore_logger::Config as LogConfig;
@@ -51,6 +54,7 @@ use types::data_format::DataFormat;
 use blockchain::{BlockchainCmd, ImportBlockchain, ExportBlockchain, KillBlockchain, ExportState, ResetBlockchain};
 use export_hardcoded_sync::ExportHsyncCmd;
 use presale::ImportWallet;
+use node_key::{NodeKeyCmd, parse_node_key};
 use account::{AccountCmd, NewAccount, ListAccounts, ImportAccounts, ImportFromGethAccounts};
 use snapshot_cmd::{self, SnapshotCommand};
 use network::{IpFilter, NatType};
@@ -84,6 +88,7 @@ pub enum Cmd {
 	},
 	Snapshot(SnapshotCommand),
 	Hash(Option<String>),
+	NodeKey(NodeKeyCmd),
 	ExportHardcodedSync(ExportHsyncCmd),
 }
 
@@ -181,6 +186,15 @@ impl Configuration {
 			}
 		} else if self.args.cmd_tools && self.args.cmd_tools_hash {
 			Cmd::Hash(self.args.arg_tools_hash_file)
+		} else if self.args.cmd_node_key && self.args.cmd_node_key_export {
+			Cmd::NodeKey(NodeKeyCmd::Export {
+				path: self.network_path(),
+			})
+		} else if self.args.cmd_node_key && self.args.cmd_node_key_import {
+			Cmd::NodeKey(NodeKeyCmd::Import {
+				path: self.network_path(),
+				secret: self.args.arg_node_key_import_secret.clone().expect("CLI argument is required; qed"),
+			})
 		} else if self.args.cmd_db && self.args.cmd_db_reset {
 			Cmd::Blockchain(BlockchainCmd::Reset(ResetBlockchain {
 				dirs,
@@ -300,8 +314,8 @@ impl Configuration {
 					at: to_block_id(&self.args.arg_export_state_at)?,
 					storage: !self.args.flag_export_state_no_storage,
 					code: !self.args.flag_export_state_no_code,
-					min_balance: self.args.arg_export_state_min_balance.and_then(|s| to_u256(&s).ok()),
-					max_balance: self.args.arg_export_state_max_balance.and_then(|s| to_u256(&s).ok()),
+					min_balance: self.args.arg_export_state_min_balance.and_then(|s| to_wei(&s).ok()),
+					max_balance: self.args.arg_export_state_max_balance.and_then(|s| to_wei(&s).ok()),
 					max_round_blocks_to_import: self.args.arg_max_round_blocks_to_import,
 				};
 				Cmd::Blockchain(BlockchainCmd::ExportState(export_cmd))
@@ -370,10 +384,14 @@ impl Configuration {
 				pruning,
 				pruning_history,
 				pruning_memory: self.args.arg_pruning_memory,
+				reorg_history: self.args.arg_reorg_history,
+				store_tx_outputs: self.args.flag_store_tx_outputs,
+				compress_receipts: self.args.flag_db_compress_receipts,
 				daemon,
 				logger_config: logger_config.clone(),
 				miner_options: self.miner_options()?,
 				gas_price_percentile: self.args.arg_gas_price_percentile,
+				gas_price_blocks: self.args.arg_gas_price_blocks,
 				poll_lifetime: self.args.arg_poll_lifetime,
 				ws_conf,
 				snapshot_conf,
@@ -393,6 +411,8 @@ impl Configuration {
 				compaction,
 				warp_sync,
 				warp_barrier: self.args.arg_warp_barrier,
+				max_fork_width: self.args.arg_max_fork_width,
+				max_timestamp_drift: Duration::from_secs(self.args.arg_max_timestamp_drift),
 				geth_compatibility,
 				experimental_rpcs,
 				net_settings: self.network_settings()?,
@@ -401,16 +421,31 @@ impl Configuration {
 				private_provider_conf,
 				private_encryptor_conf: private_enc_conf,
 				private_tx_enabled,
+				private_tx_relay: self.args.arg_private_tx_relay,
+				abi_dir: self.args.arg_jsonrpc_abi_dir,
+				call_cache_size: self.args.arg_jsonrpc_call_cache_size,
+				conflict_search_depth: self.args.arg_jsonrpc_conflict_search_depth,
+				max_traces: self.args.arg_jsonrpc_max_traces,
+				rpc_logging: self.rpc_logging(),
+				rpc_name_sources: self.rpc_name_sources()?,
+				rpc_address_format: AddressFormat { strict: self.args.flag_rpc_strict_address_checksum },
+				event_export: self.event_export_endpoint()?,
 				name: self.args.arg_identity,
 				custom_bootnodes: self.args.arg_bootnodes.is_some(),
 				check_seal: !self.args.flag_no_seal_check,
 				download_old_blocks: !self.args.flag_no_ancient_blocks,
+				sync_bandwidth: self.sync_bandwidth(),
+				local_transactions_relay: self.local_transactions_relay(),
 				verifier_settings,
 				serve_light: !self.args.flag_no_serve_light,
 				light: self.args.flag_light,
 				no_persistent_txqueue: self.args.flag_no_persistent_txqueue,
 				no_hardcoded_sync: self.args.flag_no_hardcoded_sync,
 				max_round_blocks_to_import: self.args.arg_max_round_blocks_to_import,
+				new_blocks_notify_interval: match self.args.arg_new_blocks_notify_interval {
+					0 => None,
+					ms => Some(Duration::from_millis(ms)),
+				},
 				on_demand_response_time_window: self.args.arg_on_demand_response_time_window,
 				on_demand_request_backoff_start: self.args.arg_on_demand_request_backoff_start,
 				on_demand_request_backoff_max: self.args.arg_on_demand_request_backoff_max,
@@ -436,6 +471,10 @@ impl Configuration {
 			engine_signer: self.engine_signer()?,
 			work_notify: self.work_notify(),
 			local_accounts: HashSet::from_iter(to_addresses(&self.args.arg_tx_queue_locals)?.into_iter()),
+			admission_rules: pool::client::AdmissionRules {
+				allowed_senders: HashSet::from_iter(to_addresses(&self.args.arg_tx_queue_allowed_senders)?.into_iter()),
+				denied_recipients: HashSet::from_iter(to_addresses(&self.args.arg_tx_queue_denied_recipients)?.into_iter()),
+			},
 		};
 
 		Ok(extras)
@@ -516,6 +555,24 @@ impl Configuration {
 		self.args.arg_max_pending_peers as u32
 	}
 
+	fn sync_bandwidth(&self) -> sync::BandwidthLimits {
+		sync::BandwidthLimits {
+			upload: self.args.arg_max_upload_rate.map(|kb| kb as usize * 1024),
+			download: self.args.arg_max_download_rate.map(|kb| kb as usize * 1024),
+			peer_requests: self.args.arg_max_peer_requests.map(|n| n as usize),
+		}
+	}
+
+	fn local_transactions_relay(&self) -> sync::LocalTransactionsRelay {
+		match self.args.arg_local_tx_delay {
+			Some(secs) => sync::LocalTransactionsRelay::Delayed {
+				delay: Duration::from_secs(secs),
+				peers: self.args.arg_local_tx_peers,
+			},
+			None => sync::LocalTransactionsRelay::Immediate,
+		}
+	}
+
 	fn snapshot_peers(&self) -> u32 {
 		self.args.arg_snapshot_peers as u32
 	}
@@ -568,14 +625,23 @@ impl Configuration {
 			work_queue_size: self.args.arg_work_queue_size,
 			enable_resubmission: !self.args.flag_remove_solved,
 			infinite_pending_block: self.args.flag_infinite_pending_block,
+			pseudo_pending_block: self.args.flag_pseudo_pending_block,
 
 			tx_queue_penalization: to_queue_penalization(self.args.arg_tx_time_limit)?,
 			tx_queue_strategy: to_queue_strategy(&self.args.arg_tx_queue_strategy)?,
 			tx_queue_no_unfamiliar_locals: self.args.flag_tx_queue_no_unfamiliar_locals,
+			tx_queue_max_size_per_sender: self.args.arg_tx_queue_per_sender_size.map(|kb| kb * 1024),
 			refuse_service_transactions: self.args.flag_refuse_service_transactions,
 
 			pool_limits: self.pool_limits()?,
 			pool_verification_options: self.pool_verification_options()?,
+
+			block_min_gas_price: self.args.arg_block_min_gas_price.map(U256::from),
+			block_max_transactions: self.args.arg_block_max_txs,
+			tx_max_gas_percent: match self.args.arg_tx_max_gas_percent {
+				Some(percent) if percent > 100 => return Err(format!("Invalid --tx-max-gas-percent: {} is more than 100", percent)),
+				percent => percent,
+			},
 		};
 
 		Ok(options)
@@ -651,7 +717,7 @@ impl Configuration {
 		}
 
 		if let Some(dec) = self.args.arg_gasprice.as_ref() {
-			return Ok(GasPricerConfig::Fixed(to_u256(dec)?));
+			return Ok(GasPricerConfig::Fixed(to_wei(dec)?));
 		} else if let Some(dec) = self.args.arg_min_gas_price {
 			return Ok(GasPricerConfig::Fixed(U256::from(dec)));
 		} else if self.chain()? != SpecType::Foundation {
@@ -686,8 +752,11 @@ impl Configuration {
 	}
 
 	fn extra_data(&self) -> Result<Bytes, String> {
-		match self.args.arg_extradata.as_ref().or(self.args.arg_extra_data.as_ref()) {
-			Some(x) if x.len() <= 32 => Ok(x.as_bytes().to_owned()),
+		let extra_data = self.args.arg_extradata.as_ref().or(self.args.arg_extra_data.as_ref())
+			.map(|x| with_version(x.as_bytes().to_vec()));
+
+		match extra_data {
+			Some(x) if x.len() <= 32 => Ok(x),
 			None => Ok(version_data()),
 			Some(_) => Err("Extra data must be at most 32 characters".into()),
 		}
@@ -722,7 +791,12 @@ impl Configuration {
 	fn net_addresses(&self) -> Result<(SocketAddr, Option<SocketAddr>), String> {
 		let port = self.args.arg_ports_shift + self.args.arg_port;
 		let listen_address = SocketAddr::new(self.interface(&self.args.arg_interface).parse().unwrap(), port);
-		let public_address = if self.args.arg_nat.starts_with("extip:") {
+		let public_address = if let Some(ref address) = self.args.arg_public_address {
+			match address.to_socket_addrs().map(|mut addr_iter| addr_iter.next()) {
+				Ok(Some(addr)) => Some(addr),
+				_ => return Err(format!("Invalid address given with `--public-address {}`", address)),
+			}
+		} else if self.args.arg_nat.starts_with("extip:") {
 			let host = self.args.arg_nat[6..].split(':').next().expect("split has at least one part; qed");
 			let host = format!("{}:{}", host, port);
 			match host.to_socket_addrs() {
@@ -754,9 +828,7 @@ impl Configuration {
 		let (listen, public) = self.net_addresses()?;
 		ret.listen_address = Some(format!("{}", listen));
 		ret.public_address = public.map(|p| format!("{}", p));
-		ret.use_secret = match self.args.arg_node_key.as_ref()
-			.map(|s| s.parse::<Secret>().or_else(|_| Secret::import_key(keccak(s).as_bytes())).map_err(|e| format!("Invalid key: {:?}", e))
-			) {
+		ret.use_secret = match self.args.arg_node_key.as_ref().map(|s| parse_node_key(s)) {
 			None => None,
 			Some(Ok(key)) => Some(key),
 			Some(Err(err)) => return Err(err),
@@ -764,12 +836,13 @@ impl Configuration {
 		ret.discovery_enabled = !self.args.flag_no_discovery && !self.args.flag_nodiscover;
 		ret.max_peers = self.max_peers();
 		ret.min_peers = self.min_peers();
+		ret.max_inbound_peers = self.args.arg_max_inbound_peers.map(|n| n as u32);
+		ret.max_outbound_peers = self.args.arg_max_outbound_peers.map(|n| n as u32);
+		ret.reuse_port = self.args.flag_reuse_port;
 		ret.snapshot_peers = self.snapshot_peers();
 		ret.ip_filter = self.ip_filter()?;
 		ret.max_pending_peers = self.max_pending_peers();
-		let mut net_path = PathBuf::from(self.directories().base);
-		net_path.push("network");
-		ret.config_path = Some(net_path.to_str().unwrap().to_owned());
+		ret.config_path = Some(self.network_path().to_str().unwrap().to_owned());
 		ret.reserved_nodes = self.init_reserved_nodes()?;
 		ret.allow_non_reserved = !self.args.flag_reserved_only;
 		ret.client_version = {
@@ -802,23 +875,13 @@ impl Configuration {
 		apis.join(",")
 	}
 
-	fn cors(cors: &str) -> Option<Vec<String>> {
-		match cors {
-			"none" => return Some(Vec::new()),
-			"*" | "all" | "any" => return None,
-			_ => {},
-		}
-
-		Some(cors.split(',').map(Into::into).collect())
-	}
-
 	fn rpc_cors(&self) -> Option<Vec<String>> {
 		let cors = self.args.arg_rpccorsdomain.clone().unwrap_or_else(|| self.args.arg_jsonrpc_cors.to_owned());
-		Self::cors(&cors)
+		parse_cors(&cors)
 	}
 
 	fn ipfs_cors(&self) -> Option<Vec<String>> {
-		Self::cors(self.args.arg_ipfs_api_cors.as_ref())
+		parse_cors(self.args.arg_ipfs_api_cors.as_ref())
 	}
 
 	fn hosts(&self, hosts: &str, interface: &str) -> Option<Vec<String>> {
@@ -830,17 +893,7 @@ impl Configuration {
 			return None;
 		}
 
-		Self::parse_hosts(hosts)
-	}
-
-	fn parse_hosts(hosts: &str) -> Option<Vec<String>> {
-		match hosts {
-			"none" => return Some(Vec::new()),
-			"*" | "all" | "any" => return None,
-			_ => {}
-		}
-		let hosts = hosts.split(',').map(Into::into).collect();
-		Some(hosts)
+		parse_hosts(hosts)
 	}
 
 	fn rpc_hosts(&self) -> Option<Vec<String>> {
@@ -856,7 +909,7 @@ impl Configuration {
 			return None;
 		}
 
-		Self::parse_hosts(&self.args.arg_ws_origins)
+		parse_hosts(&self.args.arg_ws_origins)
 	}
 
 	fn ipfs_hosts(&self) -> Option<Vec<String>> {
@@ -894,6 +947,8 @@ impl Configuration {
 		if let Some(threads) = self.args.arg_jsonrpc_server_threads {
 			conf.server_threads = std::cmp::max(1, threads);
 		}
+		conf.heavy_threads = self.args.arg_jsonrpc_heavy_threads;
+		conf.heavy_calls = HeavyCalls::parse_timeouts(&self.args.arg_jsonrpc_heavy_calls)?;
 		if let Some(max_payload) = self.args.arg_jsonrpc_max_payload {
 			conf.max_payload = std::cmp::max(1, max_payload);
 		}
@@ -955,6 +1010,27 @@ impl Configuration {
 		Ok(conf)
 	}
 
+	fn rpc_logging(&self) -> RpcLogging {
+		RpcLogging {
+			access: self.args.flag_rpc_access_log,
+			slow_threshold: match self.args.arg_rpc_slow_threshold {
+				0 => None,
+				ms => Some(Duration::from_millis(ms)),
+			},
+		}
+	}
+
+	fn rpc_name_sources(&self) -> Result<Vec<NameSource>, String> {
+		match self.args.arg_rpc_name_resolution.as_str() {
+			"none" => Ok(Vec::new()),
+			sources => sources.split(',').map(str::parse).collect(),
+		}
+	}
+
+	fn event_export_endpoint(&self) -> Result<Option<EventExportEndpoint>, String> {
+		self.args.arg_export_events_to.as_ref().map(|url| url.parse()).transpose()
+	}
+
 	fn network_settings(&self) -> Result<NetworkSettings, String> {
 		let http_conf = self.http_config()?;
 		let net_addresses = self.net_addresses()?;
@@ -992,6 +1068,12 @@ impl Configuration {
 		})
 	}
 
+	fn network_path(&self) -> PathBuf {
+		let mut net_path = PathBuf::from(self.directories().base);
+		net_path.push("network");
+		net_path
+	}
+
 	fn directories(&self) -> Directories {
 		let local_path = default_local_path();
 		let base_path = self.args.arg_base_path.as_ref().or_else(|| self.args.arg_datadir.as_ref()).map_or_else(|| default_data_path(), |s| s.clone());
@@ -1073,7 +1155,7 @@ impl Configuration {
 	}
 
 	fn secretstore_cors(&self) -> Option<Vec<String>> {
-		Self::cors(self.args.arg_secretstore_http_cors.as_ref())
+		parse_cors(self.args.arg_secretstore_http_cors.as_ref())
 	}
 
 	fn secretstore_self_secret(&self) -> Result<Option<NodeSecretKey>, String> {
@@ -1177,6 +1259,12 @@ impl Configuration {
 		if let Some(num_verifiers) = self.args.arg_num_verifiers {
 			settings.num_verifiers = num_verifiers;
 		}
+		if let Some(min_verifiers) = self.args.arg_min_verifiers {
+			settings.min_verifiers = min_verifiers;
+		}
+		if let Some(max_verifiers) = self.args.arg_max_verifiers {
+			settings.max_verifiers = max_verifiers;
+		}
 
 		settings
 	}
@@ -1230,6 +1318,15 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn should_substitute_version_in_extra_data() {
+		let conf = parse(&["parity", "--extra-data", "pool/{version}"]);
+		assert_eq!(conf.extra_data().unwrap(), format!("pool/{}", ::parity_version::raw_package_info().1).into_bytes());
+
+		let conf = parse(&["parity", "--extra-data", "{version}{version}{version}{version}{version}{version}"]);
+		assert!(conf.extra_data().is_err());
+	}
+
 	#[test]
 	fn test_command_version() {
 		let args = vec!["parity", "--version"];
@@ -1380,6 +1477,18 @@ mod tests {
 		})));
 	}
 
+	#[test]
+	fn test_command_node_key_import() {
+		let args = vec!["parity", "node-key", "import", "my node"];
+		let conf = parse(&args);
+		let mut path = PathBuf::from(Directories::default().base);
+		path.push("network");
+		assert_eq!(conf.into_command().unwrap().cmd, Cmd::NodeKey(NodeKeyCmd::Import {
+			path,
+			secret: "my node".into(),
+		}));
+	}
+
 	#[test]
 	fn test_command_signer_new_token() {
 		let args = vec!["parity", "signer", "new-token"];
@@ -1425,10 +1534,14 @@ mod tests {
 			pruning: Default::default(),
 			pruning_history: 64,
 			pruning_memory: 32,
+			reorg_history: 256,
+			store_tx_outputs: false,
+			compress_receipts: false,
 			daemon: None,
 			logger_config: Default::default(),
 			miner_options: Default::default(),
 			gas_price_percentile: 50,
+			gas_price_blocks: 100,
 			poll_lifetime: 60,
 			ws_conf: Default::default(),
 			http_conf: Default::default(),
@@ -1437,6 +1550,8 @@ mod tests {
 			network_id: None,
 			warp_sync: true,
 			warp_barrier: None,
+			max_fork_width: 16,
+			max_timestamp_drift: Duration::from_secs(15),
 			acc_conf: Default::default(),
 			gas_pricer_conf: Default::default(),
 			miner_extras: Default::default(),
@@ -1461,6 +1576,15 @@ mod tests {
 			private_provider_conf: Default::default(),
 			private_encryptor_conf: Default::default(),
 			private_tx_enabled: false,
+			private_tx_relay: None,
+			abi_dir: None,
+			call_cache_size: 0,
+			conflict_search_depth: 256,
+			max_traces: 10000,
+			rpc_logging: Default::default(),
+			rpc_name_sources: Vec::new(),
+			rpc_address_format: Default::default(),
+			event_export: None,
 			name: "".into(),
 			custom_bootnodes: false,
 			fat_db: Default::default(),
@@ -1468,12 +1592,15 @@ mod tests {
 			stratum: None,
 			check_seal: true,
 			download_old_blocks: true,
+			sync_bandwidth: Default::default(),
+			local_transactions_relay: sync::LocalTransactionsRelay::Immediate,
 			verifier_settings: Default::default(),
 			serve_light: true,
 			light: false,
 			no_hardcoded_sync: false,
 			no_persistent_txqueue: false,
 			max_round_blocks_to_import: 12,
+			new_blocks_notify_interval: None,
 			on_demand_response_time_window: None,
 			on_demand_request_backoff_start: None,
 			on_demand_request_backoff_max: None,
@@ -1614,6 +1741,56 @@ mod tests {
 		let http_conf = conf.http_config().unwrap();
 		assert_eq!(http_conf.server_threads, 4);
 		assert_eq!(http_conf.max_payload, 5);
+		assert_eq!(http_conf.heavy_threads, 2);
+		assert_eq!(http_conf.heavy_calls, HttpConfiguration::default().heavy_calls);
+	}
+
+	#[test]
+	fn should_parse_rpc_logging() {
+		let conf = parse(&["parity"]);
+		assert_eq!(conf.rpc_logging(), RpcLogging::default());
+
+		let conf = parse(&["parity", "--rpc-access-log", "--rpc-slow-threshold=500"]);
+		assert_eq!(conf.rpc_logging(), RpcLogging {
+			access: true,
+			slow_threshold: Some(Duration::from_millis(500)),
+		});
+	}
+
+	#[test]
+	fn should_parse_rpc_name_sources() {
+		let conf = parse(&["parity"]);
+		assert_eq!(conf.rpc_name_sources(), Ok(vec![]));
+
+		let conf = parse(&["parity", "--rpc-name-resolution=addressbook,registry"]);
+		assert_eq!(conf.rpc_name_sources(), Ok(vec![NameSource::AddressBook, NameSource::Registry]));
+
+		let conf = parse(&["parity", "--rpc-name-resolution=dns"]);
+		assert!(conf.rpc_name_sources().is_err());
+	}
+
+	#[test]
+	fn should_parse_event_export_endpoint() {
+		let conf = parse(&["parity"]);
+		assert_eq!(conf.event_export_endpoint(), Ok(None));
+
+		let conf = parse(&["parity", "--export-events-to=http://127.0.0.1:8080/events"]);
+		assert_eq!(conf.event_export_endpoint(), Ok(Some("http://127.0.0.1:8080/events".parse().unwrap())));
+
+		let conf = parse(&["parity", "--export-events-to=127.0.0.1:8080"]);
+		assert!(conf.event_export_endpoint().is_err());
+	}
+
+	#[test]
+	fn should_parse_heavy_jsonrpc_calls() {
+		let conf = parse(&["parity", "--jsonrpc-heavy-threads=0", "--jsonrpc-heavy-calls=eth_getLogs:5,trace_filter:60"]);
+		let http_conf = conf.http_config().unwrap();
+		assert_eq!(http_conf.heavy_threads, 0);
+		assert_eq!(http_conf.heavy_calls.len(), 2);
+		assert_eq!(http_conf.heavy_calls["eth_getLogs"], Duration::from_secs(5));
+
+		let conf = parse(&["parity", "--jsonrpc-heavy-calls=eth_getLogs"]);
+		assert!(conf.http_config().is_err());
 	}
 
 	#[test]
@@ -1870,6 +2047,24 @@ mod tests {
 		assert!(conf.net_addresses().is_err());
 	}
 
+	#[test]
+	fn should_override_public_address() {
+		let conf = parse(&["parity", "--nat", "extip:1.1.1.1", "--public-address", "2.2.2.2:40404"]);
+		assert_eq!(conf.net_addresses().unwrap().1, Some("2.2.2.2:40404".parse().unwrap()));
+
+		let conf = parse(&["parity", "--public-address", "2.2.2.2"]);
+		assert!(conf.net_addresses().is_err());
+	}
+
+	#[test]
+	fn should_split_peer_quotas() {
+		let conf = parse(&["parity", "--max-inbound-peers", "40", "--max-outbound-peers", "10", "--reuse-port"]);
+		let net = conf.net_config().unwrap();
+		assert_eq!(net.max_inbound_peers, Some(40));
+		assert_eq!(net.max_outbound_peers, Some(10));
+		assert!(net.reuse_port);
+	}
+
 	#[test]
 	fn should_expose_all_servers() {
 		// given
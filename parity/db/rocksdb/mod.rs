@@ -23,11 +23,13 @@ extern crate tempdir;
 
 use std::{io, fs};
 use std::sync::Arc;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use blooms_db;
-use ethcore_db::NUM_COLUMNS;
+use ethcore_db::{COL_BODIES, COL_EXTRA, COL_HEADERS, COL_STATE, COL_TRACE, NUM_COLUMNS};
 use ethcore::client::{ClientConfig, DatabaseCompactionProfile};
 use kvdb::KeyValueDB;
+use parking_lot::RwLock;
+use types::disk_usage::DiskUsage;
 use self::ethcore_blockchain::{BlockChainDBHandler, BlockChainDB};
 use self::kvdb_rocksdb::{Database, DatabaseConfig};
 
@@ -39,10 +41,33 @@ mod helpers;
 
 pub use self::migration::migrate;
 
+/// Number of entries read from each column to estimate the average size of its entries.
+const COLUMN_SAMPLE_SIZE: usize = 1024;
+
 struct AppDB {
 	key_value: Arc<dyn KeyValueDB>,
+	rocksdb: Arc<Database>,
 	blooms: blooms_db::Database,
 	trace_blooms: blooms_db::Database,
+	path: PathBuf,
+	disk_usage: RwLock<Option<DiskUsage>>,
+}
+
+impl AppDB {
+	fn measure_disk_usage(&self) -> io::Result<DiskUsage> {
+		// rocksdb keeps all of its files directly in the client path
+		let key_value = dir_size(&self.path, false)?;
+		let columns = column_sizes(&self.rocksdb, key_value)?;
+		Ok(DiskUsage {
+			key_value,
+			state: columns[COL_STATE as usize],
+			blocks: columns[COL_HEADERS as usize] + columns[COL_BODIES as usize],
+			extras: columns[COL_EXTRA as usize],
+			traces: columns[COL_TRACE as usize],
+			blooms: dir_size(&self.path.join("blooms"), true)?,
+			trace_blooms: dir_size(&self.path.join("trace_blooms"), true)?,
+		})
+	}
 }
 
 impl BlockChainDB for AppDB {
@@ -57,6 +82,53 @@ impl BlockChainDB for AppDB {
 	fn trace_blooms(&self) -> &blooms_db::Database {
 		&self.trace_blooms
 	}
+
+	fn disk_usage(&self) -> Option<DiskUsage> {
+		self.disk_usage.read().clone()
+	}
+
+	fn refresh_disk_usage(&self) {
+		match self.measure_disk_usage() {
+			Ok(usage) => *self.disk_usage.write() = Some(usage),
+			Err(e) => warn!("Failed to compute database size: {}", e),
+		}
+	}
+}
+
+/// Splits `total`, the size on disk of the key-value database, between its columns. RocksDB
+/// doesn't attribute its files to columns, so each column gets a share proportional to its
+/// estimated number of keys times the average size of a sample of its entries.
+fn column_sizes(db: &Database, total: u64) -> io::Result<Vec<u64>> {
+	let mut estimates = Vec::with_capacity(NUM_COLUMNS as usize);
+	for col in 0..NUM_COLUMNS {
+		let keys = db.num_keys(col)?;
+		let (entries, bytes) = db.iter(col)
+			.take(COLUMN_SAMPLE_SIZE)
+			.fold((0u128, 0u128), |(entries, bytes), (key, value)| (entries + 1, bytes + (key.len() + value.len()) as u128));
+		estimates.push(if entries == 0 { 0 } else { keys as u128 * bytes / entries });
+	}
+
+	let estimated_total: u128 = estimates.iter().sum();
+	Ok(estimates.into_iter()
+		.map(|estimate| if estimated_total == 0 { 0 } else { (estimate * total as u128 / estimated_total) as u64 })
+		.collect())
+}
+
+/// Total size of the files in `path`, including subdirectories if `recursive` is set.
+fn dir_size(path: &Path, recursive: bool) -> io::Result<u64> {
+	let mut size = 0;
+	for entry in fs::read_dir(path)? {
+		let entry = entry?;
+		let metadata = entry.metadata()?;
+		if metadata.is_dir() {
+			if recursive {
+				size += dir_size(&entry.path(), true)?;
+			}
+		} else {
+			size += metadata.len();
+		}
+	}
+	Ok(size)
 }
 
 /// Create a restoration db handler using the config generated by `client_path` and `client_config`.
@@ -103,11 +175,16 @@ pub fn open_database(client_path: &str, config: &DatabaseConfig) -> io::Result<A
 	fs::create_dir_all(&blooms_path)?;
 	fs::create_dir_all(&trace_blooms_path)?;
 
+	let rocksdb = Arc::new(Database::open(&config, client_path)?);
 	let db = AppDB {
-		key_value: Arc::new(Database::open(&config, client_path)?),
+		key_value: rocksdb.clone(),
+		rocksdb,
 		blooms: blooms_db::Database::open(blooms_path)?,
 		trace_blooms: blooms_db::Database::open(trace_blooms_path)?,
+		path: path.to_path_buf(),
+		disk_usage: RwLock::new(None),
 	};
+	db.refresh_disk_usage();
 
 	Ok(Arc::new(db))
 }
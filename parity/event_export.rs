@@ -0,0 +1,532 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Export of new block and reorg events to an external HTTP endpoint.
+//!
+//! Events are delivered at least once: they are kept in a file until the endpoint accepts them,
+//! so that events not yet delivered when the node stops are sent after it restarts. If the endpoint
+//! falls too far behind, the oldest events are dropped.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use client_traits::{BlockChainClient, ChainNotify};
+use ethereum_types::H256;
+use parking_lot::{Condvar, Mutex};
+use serde_json;
+use types::{
+	BlockNumber,
+	chain_notify::NewBlocks,
+	ids::BlockId,
+};
+
+/// Maximal number of events sent in a single request.
+const BATCH_SIZE: usize = 100;
+/// Time to wait before retrying a failed delivery.
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// Timeout of connecting to, reading from and writing to the endpoint.
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+/// Maximal number of undelivered events kept, the oldest are dropped beyond it.
+const MAX_EVENTS: usize = 100_000;
+/// Size of the delivered events at the start of the file above which they are cut off it.
+const COMPACT_SIZE: u64 = 4 * 1024 * 1024;
+
+/// HTTP endpoint receiving the events, as JSON arrays posted to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Endpoint {
+	host: String,
+	port: u16,
+	path: String,
+}
+
+impl FromStr for Endpoint {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || format!("Invalid event export endpoint {}, expected http://HOST[:PORT][/PATH].", s);
+		let rest = if s.starts_with("http://") { &s[7..] } else { return Err(invalid()) };
+		let (authority, path) = match rest.find('/') {
+			Some(index) => (&rest[..index], &rest[index..]),
+			None => (rest, "/"),
+		};
+		let (host, port) = match authority.rfind(':') {
+			Some(index) => (&authority[..index], authority[index + 1..].parse().map_err(|_| invalid())?),
+			None => (authority, 80),
+		};
+		if host.is_empty() {
+			return Err(invalid());
+		}
+
+		Ok(Endpoint {
+			host: host.into(),
+			port,
+			path: path.into(),
+		})
+	}
+}
+
+impl fmt::Display for Endpoint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "http://{}:{}{}", self.host, self.port, self.path)
+	}
+}
+
+impl Endpoint {
+	/// Connects to the first address of the endpoint that accepts a connection in time.
+	fn connect(&self) -> io::Result<TcpStream> {
+		let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("No address found for {}", self.host));
+		for address in (self.host.as_str(), self.port).to_socket_addrs()? {
+			match TcpStream::connect_timeout(&address, IO_TIMEOUT) {
+				Ok(stream) => return Ok(stream),
+				Err(e) => last_error = e,
+			}
+		}
+		Err(last_error)
+	}
+
+	/// Posts `body` and succeeds if the endpoint responds with a 2xx status.
+	fn post(&self, body: &str) -> io::Result<()> {
+		let mut stream = self.connect()?;
+		stream.set_read_timeout(Some(IO_TIMEOUT))?;
+		stream.set_write_timeout(Some(IO_TIMEOUT))?;
+		write!(
+			stream,
+			"POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+			self.path, self.host, self.port, body.len(), body,
+		)?;
+		stream.flush()?;
+
+		let mut status = String::new();
+		BufReader::new(stream).read_line(&mut status)?;
+		match status.split_whitespace().nth(1) {
+			Some(code) if code.starts_with('2') => Ok(()),
+			_ => Err(io::Error::new(io::ErrorKind::Other, format!("Unexpected response: {}", status.trim()))),
+		}
+	}
+}
+
+/// Chain event, as exported.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Event {
+	/// A block was added to the canonical chain.
+	NewBlock {
+		hash: H256,
+		number: BlockNumber,
+		#[serde(rename = "parentHash")]
+		parent_hash: H256,
+	},
+	/// Blocks were removed from the canonical chain in favour of others.
+	Reorg {
+		retracted: Vec<H256>,
+		enacted: Vec<H256>,
+	},
+}
+
+/// File of the undelivered events, with a line per event in delivery order. Delivered and dropped
+/// events at its start are skipped by an offset kept in a second file, and cut off once nothing is
+/// left to deliver or they take up `COMPACT_SIZE`.
+struct Store {
+	path: PathBuf,
+	file: File,
+	/// Size of the file.
+	len: u64,
+	/// Offset of the first undelivered event in the file.
+	offset: u64,
+}
+
+impl Store {
+	/// Opens the file at `path`, returning the events left in it by a previous run.
+	fn open(path: PathBuf) -> io::Result<(Self, Vec<String>)> {
+		let offset: u64 = match fs::read_to_string(offset_path(&path)) {
+			Ok(offset) => offset.trim().parse()
+				.map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid offset of undelivered events"))?,
+			Err(ref e) if e.kind() == io::ErrorKind::NotFound => 0,
+			Err(e) => return Err(e),
+		};
+		let mut file = OpenOptions::new().read(true).append(true).create(true).open(&path)?;
+		let len = file.metadata()?.len();
+		let offset = offset.min(len);
+
+		file.seek(SeekFrom::Start(offset))?;
+		let events = BufReader::new(&file).lines().collect::<io::Result<_>>()?;
+		Ok((Store { path, file, len, offset }, events))
+	}
+
+	/// Appends events and syncs them to disk. The file is left as it was if that fails.
+	fn append(&mut self, events: &[String]) -> io::Result<()> {
+		let mut lines = String::new();
+		for event in events {
+			lines.push_str(event);
+			lines.push('\n');
+		}
+
+		match self.file.write_all(lines.as_bytes()).and_then(|_| self.file.sync_data()) {
+			Ok(()) => {
+				self.len += lines.len() as u64;
+				Ok(())
+			},
+			Err(e) => {
+				let _ = self.file.set_len(self.len);
+				Err(e)
+			},
+		}
+	}
+
+	/// Skips `size` bytes of events at the start of the file, which were delivered or dropped.
+	fn skip(&mut self, size: u64) -> io::Result<()> {
+		if size == 0 {
+			return Ok(());
+		}
+
+		self.offset += size;
+		if self.offset >= self.len {
+			// Events are delivered at least once, so the offset is reset before the file is cut.
+			self.write_offset(0)?;
+			self.file.set_len(0)?;
+			self.len = 0;
+			self.offset = 0;
+			Ok(())
+		} else if self.offset >= COMPACT_SIZE {
+			self.compact()
+		} else {
+			self.write_offset(self.offset)
+		}
+	}
+
+	/// Moves the undelivered events to the start of a new file.
+	fn compact(&mut self) -> io::Result<()> {
+		let tmp_path = self.path.with_extension("tmp");
+		let mut tmp = File::create(&tmp_path)?;
+		let mut file = File::open(&self.path)?;
+		file.seek(SeekFrom::Start(self.offset))?;
+		io::copy(&mut file, &mut tmp)?;
+		tmp.sync_all()?;
+
+		self.write_offset(0)?;
+		fs::rename(&tmp_path, &self.path)?;
+		self.file = OpenOptions::new().read(true).append(true).open(&self.path)?;
+		self.len -= self.offset;
+		self.offset = 0;
+		Ok(())
+	}
+
+	fn write_offset(&self, offset: u64) -> io::Result<()> {
+		let tmp_path = self.path.with_extension("offset.tmp");
+		let mut file = File::create(&tmp_path)?;
+		write!(file, "{}", offset)?;
+		file.sync_all()?;
+		fs::rename(tmp_path, offset_path(&self.path))
+	}
+}
+
+fn offset_path(path: &Path) -> PathBuf {
+	path.with_extension("offset")
+}
+
+/// Undelivered events, oldest first, along with the size each takes up in the file.
+#[derive(Default)]
+struct Queue {
+	events: VecDeque<(String, u64)>,
+	/// Number of events removed so far.
+	removed: u64,
+}
+
+impl Queue {
+	/// Adds events, dropping the oldest ones beyond `MAX_EVENTS`. Returns the size the dropped
+	/// events take up in the file.
+	fn push(&mut self, events: Vec<(String, u64)>) -> u64 {
+		self.events.extend(events);
+		let excess = self.events.len().saturating_sub(MAX_EVENTS);
+		if excess > 0 {
+			warn!(target: "export", "Too many undelivered events, dropping the {} oldest", excess);
+		}
+		self.remove(excess)
+	}
+
+	/// Removes the events added before the `end`th one which weren't removed yet. Returns the size
+	/// they take up in the file.
+	fn remove_until(&mut self, end: u64) -> u64 {
+		let count = end.saturating_sub(self.removed).min(self.events.len() as u64);
+		self.remove(count as usize)
+	}
+
+	fn remove(&mut self, count: usize) -> u64 {
+		self.removed += count as u64;
+		self.events.drain(..count).map(|(_, size)| size).sum()
+	}
+}
+
+/// Events persisted with their size in the file, or with none if they couldn't be persisted.
+fn sized(events: Vec<String>, persisted: bool) -> Vec<(String, u64)> {
+	events.into_iter()
+		.map(|event| {
+			let size = if persisted { event.len() as u64 + 1 } else { 0 };
+			(event, size)
+		})
+		.collect()
+}
+
+/// State shared with the delivery thread. When both locks are needed, `store` is taken first, so that
+/// the file is updated in the order the queue is.
+struct Shared {
+	store: Mutex<Store>,
+	queue: Mutex<Queue>,
+	wake: Condvar,
+	exit: AtomicBool,
+}
+
+/// Publishes the new block and reorg events of the chain to an endpoint.
+pub struct EventExporter {
+	client: Weak<dyn BlockChainClient>,
+	shared: Arc<Shared>,
+	thread: Option<thread::JoinHandle<()>>,
+}
+
+impl EventExporter {
+	/// Starts delivering events to `endpoint`, beginning with the ones left in the file at
+	/// `buffer_path` by a previous run.
+	pub fn start(endpoint: Endpoint, buffer_path: PathBuf, client: Weak<dyn BlockChainClient>) -> Result<Arc<Self>, String> {
+		let (mut store, events) = Store::open(buffer_path.clone())
+			.map_err(|e| format!("Can't load undelivered events from {}: {}", buffer_path.display(), e))?;
+		if !events.is_empty() {
+			info!("Exporting {} undelivered events to {}", events.len(), endpoint);
+		}
+		let mut queue = Queue::default();
+		let dropped = queue.push(sized(events, true));
+		store.skip(dropped)
+			.map_err(|e| format!("Can't drop undelivered events from {}: {}", buffer_path.display(), e))?;
+
+		let shared = Arc::new(Shared {
+			store: Mutex::new(store),
+			queue: Mutex::new(queue),
+			wake: Condvar::new(),
+			exit: AtomicBool::new(false),
+		});
+		let thread = {
+			let shared = shared.clone();
+			thread::Builder::new()
+				.name("event-export".into())
+				.spawn(move || deliver(&shared, &endpoint))
+				.map_err(|e| format!("Can't start event export: {}", e))?
+		};
+
+		Ok(Arc::new(EventExporter {
+			client,
+			shared,
+			thread: Some(thread),
+		}))
+	}
+
+	fn export(&self, events: Vec<Event>) {
+		let events: Vec<_> = events.iter()
+			.map(|event| serde_json::to_string(event).expect("events serialize to JSON; qed"))
+			.collect();
+
+		// Events are kept in memory even if they can't be persisted.
+		let mut store = self.shared.store.lock();
+		let persisted = match store.append(&events) {
+			Ok(()) => true,
+			Err(e) => {
+				warn!(target: "export", "Failed to persist events: {}", e);
+				false
+			},
+		};
+		let dropped = self.shared.queue.lock().push(sized(events, persisted));
+		self.shared.wake.notify_all();
+		if let Err(e) = store.skip(dropped) {
+			warn!(target: "export", "Failed to persist events: {}", e);
+		}
+	}
+}
+
+impl ChainNotify for EventExporter {
+	fn new_blocks(&self, new_blocks: NewBlocks) {
+		let client = match self.client.upgrade() {
+			Some(client) => client,
+			None => return,
+		};
+
+		let route = &new_blocks.route;
+		let mut events = Vec::with_capacity(route.enacted().len() + 1);
+		if !route.retracted().is_empty() {
+			events.push(Event::Reorg {
+				retracted: route.retracted().to_vec(),
+				enacted: route.enacted().to_vec(),
+			});
+		}
+		events.extend(route.enacted().iter().filter_map(|hash| {
+			client.block_header(BlockId::Hash(*hash)).map(|header| Event::NewBlock {
+				hash: *hash,
+				number: header.number(),
+				parent_hash: header.parent_hash(),
+			})
+		}));
+
+		if !events.is_empty() {
+			self.export(events);
+		}
+	}
+}
+
+impl Drop for EventExporter {
+	fn drop(&mut self) {
+		{
+			let _queue = self.shared.queue.lock();
+			self.shared.exit.store(true, Ordering::SeqCst);
+			self.shared.wake.notify_all();
+		}
+		if let Some(thread) = self.thread.take() {
+			let _ = thread.join();
+		}
+	}
+}
+
+/// Delivers buffered events in batches, oldest first, until the exporter is dropped.
+fn deliver(shared: &Shared, endpoint: &Endpoint) {
+	loop {
+		let (start, batch): (_, Vec<_>) = {
+			let mut queue = shared.queue.lock();
+			while queue.events.is_empty() && !shared.exit.load(Ordering::SeqCst) {
+				shared.wake.wait(&mut queue);
+			}
+			if shared.exit.load(Ordering::SeqCst) {
+				return;
+			}
+			(queue.removed, queue.events.iter().take(BATCH_SIZE).map(|(event, _)| event.clone()).collect())
+		};
+
+		match endpoint.post(&format!("[{}]", batch.join(","))) {
+			Ok(()) => {
+				trace!(target: "export", "Exported {} events to {}", batch.len(), endpoint);
+				// Some of the events may have been dropped meanwhile.
+				let mut store = shared.store.lock();
+				let delivered = shared.queue.lock().remove_until(start + batch.len() as u64);
+				if let Err(e) = store.skip(delivered) {
+					warn!(target: "export", "Failed to persist events: {}", e);
+				}
+			},
+			Err(e) => {
+				warn!(target: "export", "Failed to export events to {}: {}", endpoint, e);
+				let mut queue = shared.queue.lock();
+				if !shared.exit.load(Ordering::SeqCst) {
+					shared.wake.wait_for(&mut queue, RETRY_INTERVAL);
+				}
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::{BufRead, BufReader, Read, Write};
+	use std::net::TcpListener;
+	use std::thread;
+	use tempdir::TempDir;
+	use super::{Endpoint, Queue, Store, MAX_EVENTS, sized};
+
+	#[test]
+	fn should_parse_endpoints() {
+		let endpoint: Endpoint = "http://127.0.0.1:8080/events".parse().unwrap();
+		assert_eq!(endpoint, Endpoint { host: "127.0.0.1".into(), port: 8080, path: "/events".into() });
+
+		let endpoint: Endpoint = "http://indexer".parse().unwrap();
+		assert_eq!(endpoint, Endpoint { host: "indexer".into(), port: 80, path: "/".into() });
+
+		assert!("https://indexer".parse::<Endpoint>().is_err());
+		assert!("http://indexer:port".parse::<Endpoint>().is_err());
+		assert!("http://:8080".parse::<Endpoint>().is_err());
+	}
+
+	#[test]
+	fn should_keep_undelivered_events_across_restarts() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = tempdir.path().join("event_export");
+
+		let (mut store, events) = Store::open(path.clone()).unwrap();
+		assert!(events.is_empty());
+		store.append(&["1".into(), "2".into(), "3".into()]).unwrap();
+		store.skip(2).unwrap();
+		store.append(&["4".into()]).unwrap();
+
+		let (mut store, events) = Store::open(path.clone()).unwrap();
+		assert_eq!(events, vec!["2", "3", "4"]);
+
+		// the file is cut once everything is delivered
+		store.skip(6).unwrap();
+		assert_eq!(store.len, 0);
+		let (_, events) = Store::open(path).unwrap();
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn should_drop_oldest_events_beyond_limit() {
+		let mut queue = Queue::default();
+		let events = (0..MAX_EVENTS).map(|i| i.to_string()).collect();
+		assert_eq!(queue.push(sized(events, true)), 0);
+
+		// a batch of the first three events is in flight while two are dropped
+		let start = queue.removed;
+		assert_eq!(queue.push(sized(vec!["a".into(), "b".into()], true)), 4);
+		assert_eq!(queue.events.len(), MAX_EVENTS);
+		assert_eq!(queue.events[0].0, "2");
+
+		// only the one left of the batch is removed on delivery
+		assert_eq!(queue.remove_until(start + 3), 2);
+		assert_eq!(queue.events[0].0, "3");
+	}
+
+	#[test]
+	fn should_post_events() {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let endpoint: Endpoint = format!("http://{}/events", listener.local_addr().unwrap()).parse().unwrap();
+		let server = thread::spawn(move || {
+			let (stream, _) = listener.accept().unwrap();
+			let mut reader = BufReader::new(stream);
+			let mut request_line = String::new();
+			reader.read_line(&mut request_line).unwrap();
+			let mut content_length = 0;
+			loop {
+				let mut line = String::new();
+				reader.read_line(&mut line).unwrap();
+				if line == "\r\n" {
+					break;
+				}
+				if line.starts_with("Content-Length: ") {
+					content_length = line[16..].trim().parse().unwrap();
+				}
+			}
+			let mut body = vec![0; content_length];
+			reader.read_exact(&mut body).unwrap();
+			reader.into_inner().write_all(b"HTTP/1.1 204 No Content\r\n\r\n").unwrap();
+			(request_line, String::from_utf8(body).unwrap())
+		});
+
+		endpoint.post("[1,2]").unwrap();
+		let (request_line, body) = server.join().unwrap();
+		assert_eq!(request_line, "POST /events HTTP/1.1\r\n");
+		assert_eq!(body, "[1,2]");
+	}
+}
@@ -36,6 +36,7 @@ use ethkey::Password;
 use types::{
 	ids::BlockId,
 	client_types::Mode,
+	denominations,
 };
 
 pub fn to_duration(s: &str) -> Result<Duration, String> {
@@ -93,6 +94,28 @@ pub fn to_block_id(s: &str) -> Result<BlockId, String> {
 	}
 }
 
+/// Parses a list of allowed origins, `None` meaning any origin.
+pub fn parse_cors(cors: &str) -> Option<Vec<String>> {
+	match cors {
+		"none" => return Some(Vec::new()),
+		"*" | "all" | "any" => return None,
+		_ => {},
+	}
+
+	Some(cors.split(',').map(Into::into).collect())
+}
+
+/// Parses a list of allowed hosts, `None` meaning any host.
+pub fn parse_hosts(hosts: &str) -> Option<Vec<String>> {
+	match hosts {
+		"none" => return Some(Vec::new()),
+		"*" | "all" | "any" => return None,
+		_ => {}
+	}
+	let hosts = hosts.split(',').map(Into::into).collect();
+	Some(hosts)
+}
+
 pub fn to_u256(s: &str) -> Result<U256, String> {
 	if let Ok(decimal) = U256::from_dec_str(s) {
 		Ok(decimal)
@@ -103,6 +126,14 @@ pub fn to_u256(s: &str) -> Result<U256, String> {
 	}
 }
 
+/// Parses an amount of wei, either hex or decimal with an optional unit: `20gwei`, `1.5 ether`.
+pub fn to_wei(s: &str) -> Result<U256, String> {
+	if s.starts_with("0x") {
+		return to_u256(s);
+	}
+	denominations::parse(s).map_err(|e| format!("Invalid amount {}: {}", s, e))
+}
+
 pub fn to_pending_set(s: &str) -> Result<PendingSet, String> {
 	match s {
 		"cheap" => Ok(PendingSet::AlwaysQueue),
@@ -218,6 +249,9 @@ pub fn default_network_config() -> ::sync::NetworkConfiguration {
 		use_secret: None,
 		max_peers: 50,
 		min_peers: 25,
+		max_inbound_peers: None,
+		max_outbound_peers: None,
+		reuse_port: false,
 		snapshot_peers: 0,
 		max_pending_peers: 64,
 		ip_filter: IpFilter::default(),
@@ -358,7 +392,7 @@ mod tests {
 		ids::BlockId,
 		client_types::Mode,
 	};
-	use super::{to_duration, to_mode, to_block_id, to_u256, to_pending_set, to_address, to_addresses, to_price, geth_ipc_path, to_bootnodes, join_set, password_from_file};
+	use super::{to_duration, to_mode, to_block_id, to_u256, to_wei, to_pending_set, to_address, to_addresses, to_price, geth_ipc_path, to_bootnodes, join_set, password_from_file};
 
 	#[test]
 	fn test_to_duration() {
@@ -410,6 +444,16 @@ mod tests {
 		assert!(to_u256("u").is_err())
 	}
 
+	#[test]
+	fn test_to_wei() {
+		assert_eq!(to_wei("11").unwrap(), U256::from(11));
+		assert_eq!(to_wei("0x11").unwrap(), U256::from(17));
+		assert_eq!(to_wei("20gwei").unwrap(), U256::from(20_000_000_000u64));
+		assert_eq!(to_wei("1.5 ether").unwrap(), U256::from(1_500_000_000_000_000_000u64));
+		assert!(to_wei("1.5").is_err());
+		assert!(to_wei("1 dogecoin").is_err());
+	}
+
 	#[test]
 	fn test_pending_set() {
 		assert_eq!(to_pending_set("cheap").unwrap(), PendingSet::AlwaysQueue);
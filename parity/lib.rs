@@ -52,6 +52,7 @@ extern crate ethcore_light as light;
 extern crate ethcore_logger;
 extern crate ethcore_miner as miner;
 extern crate ethcore_network as network;
+extern crate ethcore_network_devp2p as devp2p;
 extern crate ethcore_private_tx;
 extern crate ethcore_service;
 extern crate ethcore_sync as sync;
@@ -105,6 +106,7 @@ mod blockchain;
 mod cache;
 mod cli;
 mod configuration;
+mod event_export;
 mod export_hardcoded_sync;
 mod ipfs;
 mod deprecated;
@@ -112,6 +114,7 @@ mod helpers;
 mod informant;
 mod light_helpers;
 mod modules;
+mod node_key;
 mod params;
 mod presale;
 mod rpc;
@@ -216,6 +219,7 @@ fn execute<Cr, Rr>(
 		},
 		Cmd::Version => Ok(ExecutionAction::Instant(Some(Args::print_version()))),
 		Cmd::Hash(maybe_file) => print_hash_of(maybe_file).map(|s| ExecutionAction::Instant(Some(s))),
+		Cmd::NodeKey(node_key_cmd) => node_key::execute(node_key_cmd).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::Account(account_cmd) => account::execute(account_cmd).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::ImportPresaleWallet(presale_cmd) => presale::execute(presale_cmd).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::Blockchain(blockchain_cmd) => blockchain::execute(blockchain_cmd).map(|_| ExecutionAction::Instant(None)),
@@ -0,0 +1,78 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Export and import of the devp2p node key.
+
+use std::path::PathBuf;
+
+use devp2p::{load_node_key, save_node_key};
+use hash::keccak;
+use parity_crypto::publickey::{KeyPair, Secret};
+
+#[derive(Debug, PartialEq)]
+pub enum NodeKeyCmd {
+	Export {
+		path: PathBuf,
+	},
+	Import {
+		path: PathBuf,
+		secret: String,
+	},
+}
+
+/// Parses a node key given either as a hex-encoded secret or as a phrase to be hashed into one.
+pub fn parse_node_key(s: &str) -> Result<Secret, String> {
+	s.parse::<Secret>()
+		.or_else(|_| Secret::import_key(keccak(s).as_bytes()))
+		.map_err(|e| format!("Invalid key: {:?}", e))
+}
+
+pub fn execute(cmd: NodeKeyCmd) -> Result<String, String> {
+	match cmd {
+		NodeKeyCmd::Export { path } => {
+			let secret = load_node_key(&path)
+				.ok_or_else(|| format!("No node key found in {}", path.display()))?;
+			Ok(secret.to_hex())
+		},
+		NodeKeyCmd::Import { path, secret } => {
+			let secret = parse_node_key(&secret)?;
+			let keypair = KeyPair::from_secret(secret.clone()).map_err(|e| format!("Invalid key: {:?}", e))?;
+			save_node_key(&path, &secret).map_err(|e| format!("Error writing node key: {}", e))?;
+			Ok(format!("{:x}", keypair.public()))
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use tempdir::TempDir;
+	use super::{execute, NodeKeyCmd};
+
+	#[test]
+	fn imports_and_exports_node_key() {
+		let tempdir = TempDir::new("").unwrap();
+		let secret = "6f7b0d801bc7b5ce7bbd930b84fd0369b3eb25d09be58d64ba811091046f3aa2";
+
+		let node_id = execute(NodeKeyCmd::Import {
+			path: tempdir.path().to_owned(),
+			secret: secret.into(),
+		}).unwrap();
+		assert_eq!(node_id, "101b3ef5a4ea7a1c7928e24c4c75fd053c235d7b80c22ae5c03d145d0ac7396e2a4ffff9adee3133a7b05044a5cee08115fd65145e5165d646bde371010d803c");
+
+		let exported = execute(NodeKeyCmd::Export { path: tempdir.path().to_owned() }).unwrap();
+		assert_eq!(exported, secret);
+	}
+}
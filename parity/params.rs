@@ -25,6 +25,7 @@ use hash_fetch::fetch::Client as FetchClient;
 use journaldb::Algorithm;
 use miner::gas_pricer::GasPricer;
 use miner::gas_price_calibrator::{GasPriceCalibratorOptions, GasPriceCalibrator};
+use miner::pool::client::AdmissionRules;
 use parity_version::version_data;
 use user_defaults::UserDefaults;
 use types::client_types::Mode;
@@ -155,7 +156,7 @@ impl SpecType {
 			SpecType::Dev => Ok(spec::new_instant()),
 			SpecType::Custom(ref filename) => {
 				let file = fs::File::open(filename).map_err(|e| format!("Could not load specification file at {}: {}", filename, e))?;
-				Spec::load(params, file).map_err(|e| e.to_string())
+				Spec::load(params, file).map_err(|e| format!("Could not load specification file at {}: {}", filename, e))
 			}
 		}
 	}
@@ -309,6 +310,7 @@ pub struct MinerExtras {
 	pub gas_range_target: (U256, U256),
 	pub work_notify: Vec<String>,
 	pub local_accounts: HashSet<Address>,
+	pub admission_rules: AdmissionRules,
 }
 
 impl Default for MinerExtras {
@@ -320,6 +322,7 @@ impl Default for MinerExtras {
 			gas_range_target: (8_000_000.into(), 10_000_000.into()),
 			work_notify: Default::default(),
 			local_accounts: Default::default(),
+			admission_rules: Default::default(),
 		}
 	}
 }
@@ -15,17 +15,19 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::io;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::path::PathBuf;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
 
 use dir::default_data_path;
 use dir::helpers::replace_home;
-use helpers::parity_ipc_path;
+use helpers::{parity_ipc_path, parse_cors, parse_hosts};
 use jsonrpc_core::MetaIoHandler;
 use parity_runtime::Executor;
-use parity_rpc::informant::{RpcStats, Middleware};
-use parity_rpc::{self as rpc, Metadata, DomainsValidation};
+use parity_rpc::informant::{RpcStats, RpcLogging, Middleware};
+use parity_rpc::{self as rpc, AddressFormat, HeavyCalls, Metadata, DomainsValidation, RpcModules, DEFAULT_HEAVY_CALLS};
+use parking_lot::RwLock;
 use rpc_apis::{self, ApiSet};
 
 pub use parity_rpc::{IpcServer, HttpServer, RequestMiddleware};
@@ -49,6 +51,10 @@ pub struct HttpConfiguration {
 	pub hosts: Option<Vec<String>>,
 	/// Number of HTTP server threads to use to handle incoming requests (default is 4).
 	pub server_threads: usize,
+	/// Maximum number of heavy calls executing at the same time, 0 runs them on the server threads (default is 2).
+	pub heavy_threads: usize,
+	/// Heavy methods along with their soft timeouts.
+	pub heavy_calls: HashMap<String, Duration>,
 	/// Sets the maximum size of a request body in megabytes (default is 5 MiB).
 	pub max_payload: usize,
 	/// Use keepalive messages on the underlying socket: SO_KEEPALIVE as well as the TCP_KEEPALIVE
@@ -66,6 +72,8 @@ impl Default for HttpConfiguration {
 			cors: Some(vec![]),
 			hosts: Some(vec![]),
 			server_threads: 4,
+			heavy_threads: 2,
+			heavy_calls: HeavyCalls::parse_timeouts(DEFAULT_HEAVY_CALLS).expect("default heavy calls are valid; qed"),
 			max_payload: 5,
 			keep_alive: true,
 		}
@@ -147,6 +155,8 @@ pub struct Dependencies<D: rpc_apis::Dependencies> {
 	pub apis: Arc<D>,
 	pub executor: Executor,
 	pub stats: Arc<RpcStats>,
+	pub logging: RpcLogging,
+	pub address_format: AddressFormat,
 }
 
 pub fn new_ws<D: rpc_apis::Dependencies>(
@@ -165,7 +175,9 @@ pub fn new_ws<D: rpc_apis::Dependencies>(
 	let handler = {
 		let mut handler = MetaIoHandler::with_middleware((
 			rpc::WsDispatcher::new(full_handler),
-			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier())
+			Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.logging.clone())
+				.with_name_resolution(deps.apis.name_resolution())
+				.with_address_format(deps.address_format)
 		));
 		let apis = conf.apis.list_apis();
 		deps.apis.extend_with_set(&mut handler, &apis);
@@ -206,11 +218,12 @@ pub fn new_ws<D: rpc_apis::Dependencies>(
 	}
 }
 
-pub fn new_http<D: rpc_apis::Dependencies>(
+pub fn new_http<D: rpc_apis::Dependencies + Send + Sync + 'static>(
 	id: &str,
 	options: &str,
 	conf: HttpConfiguration,
 	deps: &Dependencies<D>,
+	modules: &RpcModules,
 ) -> Result<Option<HttpServer>, String> {
 	if !conf.enabled {
 		return Ok(None);
@@ -219,17 +232,47 @@ pub fn new_http<D: rpc_apis::Dependencies>(
 	let domain = DAPPS_DOMAIN;
 	let url = format!("{}:{}", conf.interface, conf.port);
 	let addr = url.parse().map_err(|_| format!("Invalid {} listen host/port given: {}", id, url))?;
-	let handler = setup_apis(conf.apis, deps);
+
+	// the served APIs live behind a lock so that `parity_setRpcModules` can swap them
+	let apis = Arc::new(RwLock::new(Arc::new(setup_apis(conf.apis, deps))));
+	modules.set_reload(reload_apis(Arc::downgrade(&apis), deps));
+	let heavy_calls = match conf.heavy_threads {
+		0 => None,
+		threads => Some(HeavyCalls::new(conf.heavy_calls, threads)),
+	};
+	let handler = MetaIoHandler::with_middleware(rpc::HttpDispatcher::new(apis, heavy_calls));
 
 	let cors_domains = into_domains(conf.cors);
 	let allowed_hosts = into_domains(with_domain(conf.hosts, domain, &Some(url.clone().into())));
 
-	let start_result = rpc::start_http(
+	// origins and hosts are checked by the middleware so that `parity_setRpcCors` and
+	// `parity_setRpcHosts` can replace them, the server itself lets everything through
+	let access = rpc::HttpAccessControl::new(cors_domains, allowed_hosts);
+	modules.set_reload_cors({
+		let access = access.clone();
+		move |cors| {
+			access.set_cors(into_domains(parse_cors(cors)));
+			info!("HTTP JSON-RPC CORS domains changed to {}", cors);
+			Ok(())
+		}
+	});
+	modules.set_reload_hosts({
+		let access = access.clone();
+		let url = url.clone();
+		move |hosts| {
+			access.set_hosts(into_domains(with_domain(parse_hosts(hosts), domain, &Some(url.clone().into()))));
+			info!("HTTP JSON-RPC hosts changed to {}", hosts);
+			Ok(())
+		}
+	});
+
+	let start_result = rpc::start_http_with_middleware(
 		&addr,
-		cors_domains,
-		allowed_hosts,
+		DomainsValidation::Disabled,
+		DomainsValidation::Disabled,
 		handler,
 		rpc::RpcExtractor,
+		access,
 		conf.server_threads,
 		conf.max_payload,
 		conf.keep_alive,
@@ -244,6 +287,30 @@ pub fn new_http<D: rpc_apis::Dependencies>(
 	}
 }
 
+// Weak references only, the handler itself ends up holding the `RpcModules` this is registered with.
+fn reload_apis<D: rpc_apis::Dependencies + Send + Sync + 'static>(
+	handler: Weak<RwLock<Arc<MetaIoHandler<Metadata, Middleware<D::Notifier>>>>>,
+	deps: &Dependencies<D>,
+) -> impl Fn(&str) -> Result<(), String> + Send + Sync + 'static {
+	let apis = Arc::downgrade(&deps.apis);
+	let executor = deps.executor.clone();
+	let stats = deps.stats.clone();
+
+	move |modules| {
+		let api_set = modules.parse::<ApiSet>()?;
+		let (handler, apis) = match (handler.upgrade(), apis.upgrade()) {
+			(Some(handler), Some(apis)) => (handler, apis),
+			_ => return Err("HTTP JSON-RPC server is shutting down".into()),
+		};
+		let deps = Dependencies { apis, executor: executor.clone(), stats: stats.clone() };
+		// built before taking the lock, requests keep being served by the old handler meanwhile
+		let new_handler = Arc::new(setup_apis(api_set, &deps));
+		*handler.write() = new_handler;
+		info!("HTTP JSON-RPC modules changed to {}", modules);
+		Ok(())
+	}
+}
+
 pub fn new_ipc<D: rpc_apis::Dependencies>(
 	conf: IpcConfiguration,
 	dependencies: &Dependencies<D>
@@ -311,7 +378,9 @@ pub fn setup_apis<D>(apis: ApiSet, deps: &Dependencies<D>) -> MetaIoHandler<Meta
 	where D: rpc_apis::Dependencies
 {
 	let mut handler = MetaIoHandler::with_middleware(
-		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier())
+		Middleware::new(deps.stats.clone(), deps.apis.activity_notifier(), deps.logging.clone())
+			.with_name_resolution(deps.apis.name_resolution())
+			.with_address_format(deps.address_format)
 	);
 	let apis = apis.list_apis();
 	deps.apis.extend_with_set(&mut handler, &apis);
@@ -16,6 +16,7 @@
 
 use std::cmp::PartialEq;
 use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::{Arc, Weak};
 
@@ -37,7 +38,7 @@ use light::{Cache as LightDataCache, TransactionQueue as LightTransactionQueue};
 use miner::external::ExternalMiner;
 use parity_rpc::dispatch::{FullDispatcher, LightDispatcher};
 use parity_rpc::informant::{ActivityNotifier, ClientNotifier};
-use parity_rpc::{Host, Metadata, NetworkSettings};
+use parity_rpc::{AbiRegistry, CallCache, Host, Metadata, NameResolution, NetworkSettings, PrivateRelay, RpcModules, name_resolution};
 use parity_rpc::v1::traits::TransactionsPool;
 use parity_runtime::Executor;
 use parking_lot::{Mutex, RwLock};
@@ -112,6 +113,40 @@ impl FromStr for Api {
 	}
 }
 
+/// Source of names accepted in place of addresses in RPC parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NameSource {
+	/// Entries of the local address book.
+	AddressBook,
+	/// `A` records of the on-chain registry.
+	Registry,
+}
+
+impl FromStr for NameSource {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"addressbook" => Ok(NameSource::AddressBook),
+			"registry" => Ok(NameSource::Registry),
+			source => Err(format!("Unknown name source: {}", source)),
+		}
+	}
+}
+
+/// Name resolution of a full node using given sources in order, `None` without sources.
+pub fn full_name_resolution(sources: &[NameSource], client: Arc<Client>, accounts: Arc<AccountProvider>) -> Option<Arc<NameResolution>> {
+	if sources.is_empty() {
+		return None;
+	}
+
+	let resolvers = sources.iter().filter_map(|source| match *source {
+		NameSource::AddressBook => account_utils::address_book_names(accounts.clone()),
+		NameSource::Registry => Some(Box::new(name_resolution::Registry(client.clone())) as Box<dyn name_resolution::NameResolver>),
+	}).collect();
+	Some(Arc::new(NameResolution::new(client, resolvers)))
+}
+
 #[derive(Debug, Clone)]
 pub enum ApiSet {
 	// Unsafe context (like jsonrpc over http)
@@ -225,6 +260,9 @@ pub trait Dependencies {
 	/// Create the activity notifier.
 	fn activity_notifier(&self) -> Self::Notifier;
 
+	/// Resolution of names given in place of addresses, if enabled.
+	fn name_resolution(&self) -> Option<Arc<NameResolution>>;
+
 	/// Extend the given I/O handler with endpoints for each API.
 	fn extend_with_set<S>(&self, handler: &mut MetaIoHandler<Metadata, S>, apis: &HashSet<Api>)
 	where
@@ -240,10 +278,13 @@ pub struct FullDependencies {
 	pub net: Arc<dyn ManageNetwork>,
 	pub accounts: Arc<AccountProvider>,
 	pub private_tx_service: Option<Arc<PrivateTxService>>,
+	pub private_tx_relay: Option<Arc<PrivateRelay>>,
+	pub abi_registry: Arc<AbiRegistry>,
 	pub miner: Arc<Miner>,
 	pub external_miner: Arc<ExternalMiner>,
 	pub logger: Arc<RotatingLogger>,
 	pub settings: Arc<NetworkSettings>,
+	pub rpc_modules: Arc<RpcModules>,
 	pub net_service: Arc<dyn ManageNetwork>,
 	pub updater: Arc<Updater>,
 	pub geth_compatibility: bool,
@@ -252,9 +293,15 @@ pub struct FullDependencies {
 	pub fetch: FetchClient,
 	pub executor: Executor,
 	pub gas_price_percentile: usize,
+	pub gas_price_blocks: usize,
 	pub poll_lifetime: u32,
 	pub allow_missing_blocks: bool,
 	pub no_ancient_blocks: bool,
+	pub call_cache: Arc<CallCache>,
+	pub conflict_search_depth: u64,
+	pub max_traces: usize,
+	pub name_resolution: Option<Arc<NameResolution>>,
+	pub state_dumps_path: PathBuf,
 }
 
 impl FullDependencies {
@@ -276,6 +323,7 @@ impl FullDependencies {
 			self.miner.clone(),
 			nonces.clone(),
 			self.gas_price_percentile,
+			self.gas_price_blocks,
 		);
 		let account_signer = Arc::new(dispatch::Signer::new(self.accounts.clone())) as _;
 		let accounts = account_utils::accounts_list(self.accounts.clone());
@@ -299,14 +347,17 @@ impl FullDependencies {
 						&accounts,
 						&self.miner,
 						&self.external_miner,
+						&self.abi_registry,
+						&self.call_cache,
 						EthClientOptions {
 							pending_nonce_from_queue: self.geth_compatibility,
 							allow_pending_receipt_query: !self.geth_compatibility,
 							send_block_number_in_get_work: !self.geth_compatibility,
 							gas_price_percentile: self.gas_price_percentile,
+							gas_price_blocks: self.gas_price_blocks,
 							allow_missing_blocks: self.allow_missing_blocks,
 							allow_experimental_rpcs: self.experimental_rpcs,
-							no_ancient_blocks: self.no_ancient_blocks
+							no_ancient_blocks: self.no_ancient_blocks,
 						}
 					);
 					handler.extend_with(client.to_delegate());
@@ -392,6 +443,10 @@ impl FullDependencies {
 							signer,
 							self.ws_address.clone(),
 							self.snapshot.clone().into(),
+							self.abi_registry.clone(),
+							self.call_cache.clone(),
+							self.gas_price_blocks,
+							self.conflict_search_depth,
 						).to_delegate(),
 					);
 					#[cfg(feature = "accounts")]
@@ -424,7 +479,11 @@ impl FullDependencies {
 							&self.miner,
 							&self.updater,
 							&self.net_service,
+							&self.rpc_modules,
 							self.fetch.clone(),
+							self.private_tx_relay.clone(),
+							&self.abi_registry,
+							self.state_dumps_path.clone(),
 						).to_delegate(),
 					);
 					#[cfg(feature = "accounts")]
@@ -435,7 +494,7 @@ impl FullDependencies {
 						).to_delegate(),
 					);
 				}
-				Api::Traces => handler.extend_with(TracesClient::new(&self.client).to_delegate()),
+				Api::Traces => handler.extend_with(TracesClient::new(&self.client, self.max_traces).to_delegate()),
 				Api::Rpc => {
 					let modules = to_modules(&apis);
 					handler.extend_with(RpcClient::new(modules).to_delegate());
@@ -465,6 +524,10 @@ impl Dependencies for FullDependencies {
 		}
 	}
 
+	fn name_resolution(&self) -> Option<Arc<NameResolution>> {
+		self.name_resolution.clone()
+	}
+
 	fn extend_with_set<S>(&self, handler: &mut MetaIoHandler<Metadata, S>, apis: &HashSet<Api>)
 	where
 		S: core::Middleware<Metadata>,
@@ -489,6 +552,7 @@ pub struct LightDependencies<T> {
 	pub accounts: Arc<AccountProvider>,
 	pub logger: Arc<RotatingLogger>,
 	pub settings: Arc<NetworkSettings>,
+	pub rpc_modules: Arc<RpcModules>,
 	pub on_demand: Arc<::light::on_demand::OnDemand>,
 	pub cache: Arc<Mutex<LightDataCache>>,
 	pub transaction_queue: Arc<RwLock<LightTransactionQueue>>,
@@ -653,7 +717,7 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 					handler.extend_with(ParityAccounts::to_delegate(ParityAccountsClient::new(&self.accounts)));
 				}
 				Api::ParitySet => handler.extend_with(
-					light::ParitySetClient::new(self.client.clone(), self.sync.clone(), self.fetch.clone())
+					light::ParitySetClient::new(self.client.clone(), self.sync.clone(), self.rpc_modules.clone(), self.fetch.clone())
 						.to_delegate(),
 				),
 				Api::Traces => handler.extend_with(light::TracesClient.to_delegate()),
@@ -684,6 +748,10 @@ impl<T: LightChainClient + 'static> Dependencies for LightDependencies<T> {
 		LightClientNotifier
 	}
 
+	fn name_resolution(&self) -> Option<Arc<NameResolution>> {
+		None
+	}
+
 	fn extend_with_set<S>(&self, handler: &mut MetaIoHandler<Metadata, S>, apis: &HashSet<Api>)
 	where
 		S: core::Middleware<Metadata>,
@@ -15,6 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::any::Any;
+use std::path::Path;
 use std::sync::{Arc, Weak, atomic};
 use std::time::{Duration, Instant};
 use std::thread;
@@ -30,6 +31,7 @@ use ethcore_logger::{Config as LogConfig, RotatingLogger};
 use ethcore_service::ClientService;
 use futures::Stream;
 use hash_fetch::{self, fetch};
+use event_export::{Endpoint as EventExportEndpoint, EventExporter};
 use informant::{Informant, LightNodeInformantData, FullNodeInformantData};
 use journaldb::Algorithm;
 use light::Cache as LightDataCache;
@@ -44,7 +46,7 @@ use types::{
 	snapshot::Snapshotting,
 };
 use parity_rpc::{
-	Origin, Metadata, NetworkSettings, informant, PubSubSession, FutureResult, FutureResponse, FutureOutput
+	AbiRegistry, AddressFormat, CallCache, Origin, Metadata, NetworkSettings, PrivateRelay, RpcModules, informant, PubSubSession, FutureResult, FutureResponse, FutureOutput
 };
 use updater::{UpdatePolicy, Updater};
 use parity_version::version;
@@ -92,11 +94,15 @@ pub struct RunCmd {
 	pub pruning: Pruning,
 	pub pruning_history: u64,
 	pub pruning_memory: usize,
+	pub reorg_history: u64,
+	pub store_tx_outputs: bool,
+	pub compress_receipts: bool,
 	/// Some if execution should be daemonized. Contains pid_file path.
 	pub daemon: Option<String>,
 	pub logger_config: LogConfig,
 	pub miner_options: MinerOptions,
 	pub gas_price_percentile: usize,
+	pub gas_price_blocks: usize,
 	pub poll_lifetime: u32,
 	pub ws_conf: rpc::WsConfiguration,
 	pub http_conf: rpc::HttpConfiguration,
@@ -105,6 +111,8 @@ pub struct RunCmd {
 	pub network_id: Option<u64>,
 	pub warp_sync: bool,
 	pub warp_barrier: Option<u64>,
+	pub max_fork_width: usize,
+	pub max_timestamp_drift: Duration,
 	pub acc_conf: AccountsConfig,
 	pub gas_pricer_conf: GasPricerConfig,
 	pub miner_extras: MinerExtras,
@@ -121,6 +129,15 @@ pub struct RunCmd {
 	pub private_provider_conf: ProviderConfig,
 	pub private_encryptor_conf: EncryptorConfig,
 	pub private_tx_enabled: bool,
+	pub private_tx_relay: Option<String>,
+	pub abi_dir: Option<String>,
+	pub call_cache_size: usize,
+	pub conflict_search_depth: u64,
+	pub max_traces: usize,
+	pub rpc_logging: informant::RpcLogging,
+	pub rpc_name_sources: Vec<rpc_apis::NameSource>,
+	pub rpc_address_format: AddressFormat,
+	pub event_export: Option<EventExportEndpoint>,
 	pub name: String,
 	pub custom_bootnodes: bool,
 	pub stratum: Option<stratum::Options>,
@@ -128,12 +145,15 @@ pub struct RunCmd {
 	pub check_seal: bool,
 	pub allow_missing_blocks: bool,
 	pub download_old_blocks: bool,
+	pub sync_bandwidth: sync::BandwidthLimits,
+	pub local_transactions_relay: sync::LocalTransactionsRelay,
 	pub verifier_settings: VerifierSettings,
 	pub serve_light: bool,
 	pub light: bool,
 	pub no_persistent_txqueue: bool,
 	pub no_hardcoded_sync: bool,
 	pub max_round_blocks_to_import: usize,
+	pub new_blocks_notify_interval: Option<Duration>,
 	pub on_demand_response_time_window: Option<u64>,
 	pub on_demand_request_backoff_start: Option<u64>,
 	pub on_demand_request_backoff_max: Option<u64>,
@@ -217,6 +237,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 
 	config.queue.max_mem_use = cmd.cache_config.queue() as usize * 1024 * 1024;
 	config.queue.verifier_settings = cmd.verifier_settings;
+	config.queue.max_timestamp_drift = cmd.max_timestamp_drift;
 
 	// start on_demand service.
 
@@ -304,6 +325,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 	let signer_service = Arc::new(signer::new_service(&cmd.ws_conf, &cmd.logger_config));
 
 	// start RPCs
+	let rpc_modules = Arc::new(RpcModules::default());
 	let deps_for_rpc_apis = Arc::new(rpc_apis::LightDependencies {
 		signer_service,
 		client: client.clone(),
@@ -312,6 +334,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 		accounts: account_provider,
 		logger,
 		settings: Arc::new(cmd.net_settings),
+		rpc_modules: rpc_modules.clone(),
 		on_demand,
 		cache: cache.clone(),
 		transaction_queue: txq,
@@ -329,12 +352,14 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 		apis: deps_for_rpc_apis.clone(),
 		executor: runtime.executor(),
 		stats: rpc_stats.clone(),
+		logging: cmd.rpc_logging.clone(),
+		address_format: cmd.rpc_address_format,
 	};
 
 	// start rpc servers
 	let rpc_direct = rpc::setup_apis(rpc_apis::ApiSet::All, &dependencies);
 	let ws_server = rpc::new_ws(cmd.ws_conf, &dependencies)?;
-	let http_server = rpc::new_http("HTTP JSON-RPC", "jsonrpc", cmd.http_conf.clone(), &dependencies)?;
+	let http_server = rpc::new_http("HTTP JSON-RPC", "jsonrpc", cmd.http_conf.clone(), &dependencies, &rpc_modules)?;
 	let ipc_server = rpc::new_ipc(cmd.ipc_conf, &dependencies)?;
 
 	// the informant
@@ -477,6 +502,8 @@ fn execute_impl<Cr, Rr>(
 		_ => sync::WarpSync::Disabled,
 	};
 	sync_config.download_old_blocks = cmd.download_old_blocks;
+	sync_config.bandwidth = cmd.sync_bandwidth;
+	sync_config.local_transactions_relay = cmd.local_transactions_relay;
 	sync_config.serve_light = cmd.serve_light;
 
 	let passwords = passwords_from_files(&cmd.acc_conf.password_files)?;
@@ -505,6 +532,9 @@ fn execute_impl<Cr, Rr>(
 	miner.set_author(miner::Author::External(cmd.miner_extras.author));
 	miner.set_gas_range_target(cmd.miner_extras.gas_range_target);
 	miner.set_extra_data(cmd.miner_extras.extra_data);
+	if !cmd.miner_extras.admission_rules.is_empty() {
+		miner.set_transaction_admission_policy(Arc::new(cmd.miner_extras.admission_rules));
+	}
 
 	if !cmd.miner_extras.work_notify.is_empty() {
 		miner.add_work_listener(Box::new(
@@ -541,8 +571,14 @@ fn execute_impl<Cr, Rr>(
 	);
 
 	client_config.queue.verifier_settings = cmd.verifier_settings;
+	client_config.queue.max_fork_width = cmd.max_fork_width;
+	client_config.queue.max_timestamp_drift = cmd.max_timestamp_drift;
 	client_config.transaction_verification_queue_size = ::std::cmp::max(2048, txpool_size / 4);
 	client_config.snapshot = cmd.snapshot_conf.clone();
+	client_config.blockchain.reorg_history = cmd.reorg_history;
+	client_config.blockchain.compress_receipts = cmd.compress_receipts;
+	client_config.store_outputs = cmd.store_tx_outputs;
+	client_config.new_blocks_notify_interval = cmd.new_blocks_notify_interval;
 
 	// set up bootnodes
 	let mut net_conf = cmd.net_conf;
@@ -705,10 +741,34 @@ fn execute_impl<Cr, Rr>(
 	);
 	service.add_notify(updater.clone());
 
+	// export of chain events
+	let event_exporter = match cmd.event_export {
+		Some(endpoint) => {
+			let exporter = EventExporter::start(
+				endpoint,
+				db_dirs.event_export_path(),
+				Arc::downgrade(&(service.client() as Arc<dyn BlockChainClient>)),
+			)?;
+			service.add_notify(exporter.clone());
+			Some(exporter)
+		},
+		None => None,
+	};
+
 	// set up dependencies for rpc servers
 	let rpc_stats = Arc::new(informant::RpcStats::default());
 	let secret_store = account_provider.clone();
 	let signer_service = Arc::new(signer::new_service(&cmd.ws_conf, &cmd.logger_config));
+	let rpc_modules = Arc::new(RpcModules::default());
+
+	let abi_registry = Arc::new(AbiRegistry::default());
+	if let Some(ref dir) = cmd.abi_dir {
+		let loaded = abi_registry.load_dir(Path::new(dir))?;
+		info!("Loaded {} contract ABIs from {}", loaded, dir);
+	}
+
+	let call_cache = Arc::new(CallCache::new(cmd.call_cache_size));
+	let name_resolution = rpc_apis::full_name_resolution(&cmd.rpc_name_sources, client.clone(), secret_store.clone());
 
 	let deps_for_rpc_apis = Arc::new(rpc_apis::FullDependencies {
 		signer_service: signer_service,
@@ -721,6 +781,7 @@ fn execute_impl<Cr, Rr>(
 		external_miner: external_miner.clone(),
 		logger: logger.clone(),
 		settings: Arc::new(cmd.net_settings.clone()),
+		rpc_modules: rpc_modules.clone(),
 		net_service: manage_network.clone(),
 		updater: updater.clone(),
 		geth_compatibility: cmd.geth_compatibility,
@@ -729,23 +790,33 @@ fn execute_impl<Cr, Rr>(
 		fetch: fetch.clone(),
 		executor: runtime.executor(),
 		private_tx_service: Some(private_tx_service.clone()),
+		private_tx_relay: cmd.private_tx_relay.clone().map(|endpoint| Arc::new(PrivateRelay::new(endpoint))),
+		abi_registry,
 		gas_price_percentile: cmd.gas_price_percentile,
+		gas_price_blocks: cmd.gas_price_blocks,
 		poll_lifetime: cmd.poll_lifetime,
 		allow_missing_blocks: cmd.allow_missing_blocks,
 		no_ancient_blocks: !cmd.download_old_blocks,
+		call_cache,
+		conflict_search_depth: cmd.conflict_search_depth,
+		max_traces: cmd.max_traces,
+		name_resolution,
+		state_dumps_path: db_dirs.state_dumps_path(),
 	});
 
 	let dependencies = rpc::Dependencies {
 		apis: deps_for_rpc_apis.clone(),
 		executor: runtime.executor(),
 		stats: rpc_stats.clone(),
+		logging: cmd.rpc_logging.clone(),
+		address_format: cmd.rpc_address_format,
 	};
 
 	// start rpc servers
 	let rpc_direct = rpc::setup_apis(rpc_apis::ApiSet::All, &dependencies);
 	let ws_server = rpc::new_ws(cmd.ws_conf.clone(), &dependencies)?;
 	let ipc_server = rpc::new_ipc(cmd.ipc_conf, &dependencies)?;
-	let http_server = rpc::new_http("HTTP JSON-RPC", "jsonrpc", cmd.http_conf.clone(), &dependencies)?;
+	let http_server = rpc::new_http("HTTP JSON-RPC", "jsonrpc", cmd.http_conf.clone(), &dependencies, &rpc_modules)?;
 
 	// secret store key server
 	let secretstore_deps = secretstore::Dependencies {
@@ -817,7 +888,7 @@ fn execute_impl<Cr, Rr>(
 			informant,
 			client,
 			client_service: Arc::new(service),
-			keep_alive: Box::new((watcher, updater, ws_server, http_server, ipc_server, secretstore_key_server, ipfs_server, runtime)),
+			keep_alive: Box::new((watcher, updater, event_exporter, ws_server, http_server, ipc_server, secretstore_key_server, ipfs_server, runtime)),
 		}
 	})
 }
@@ -215,6 +215,7 @@ impl SecretStoreChain for TrustedClient {
 			address: filter.address,
 			topics: filter.topics,
 			limit: None,
+			offset: None,
 		})
 		.map(|blockchain_logs| {
 			blockchain_logs
@@ -60,9 +60,11 @@ extern crate jsonrpc_derive;
 extern crate jsonrpc_http_server as http;
 extern crate jsonrpc_ipc_server as ipc;
 extern crate jsonrpc_pubsub;
+extern crate jsonrpc_server_utils;
 
 extern crate client_traits;
 extern crate common_types as types;
+extern crate ethabi;
 extern crate ethash;
 extern crate ethcore;
 extern crate fastmap;
@@ -75,6 +77,7 @@ extern crate ethcore_network as network;
 extern crate ethcore_private_tx;
 extern crate ethcore_sync as sync;
 extern crate ethereum_types;
+extern crate evm;
 extern crate ethkey;
 extern crate ethstore;
 extern crate fetch;
@@ -82,6 +85,7 @@ extern crate keccak_hash as hash;
 extern crate parity_runtime;
 extern crate parity_updater as updater;
 extern crate parity_version as version;
+extern crate registrar;
 extern crate eip_712;
 extern crate rlp;
 extern crate account_state;
@@ -155,10 +159,10 @@ pub use http::{
 	AccessControlAllowOrigin, Host, DomainsValidation, cors::AccessControlAllowHeaders
 };
 
-pub use v1::{NetworkSettings, Metadata, Origin, informant, dispatch, signer};
+pub use v1::{AbiRegistry, AddressFormat, CallCache, HeavyCalls, DEFAULT_HEAVY_CALLS, NameResolution, NetworkSettings, PrivateRelay, RpcModules, Metadata, Origin, informant, dispatch, name_resolution, signer};
 pub use v1::block_import::{is_major_importing_or_waiting};
 pub use v1::PubSubSyncStatus;
-pub use v1::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
+pub use v1::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher, HttpAccessControl, HttpDispatcher};
 pub use authcodes::{AuthCodes, TimeProvider};
 pub use http_common::HttpMetaExtractor;
 
@@ -220,6 +224,7 @@ pub fn start_http_with_middleware<M, S, H, T, R>(
 		.threads(threads)
 		.cors(cors_domains)
 		.allowed_hosts(allowed_hosts)
+		.health_api(("/api/health", "parity_nodeStatus"))
 		.cors_allow_headers(AccessControlAllowHeaders::Any)
 		.max_request_body_size(max_payload * 1024 * 1024)
 		.request_middleware(middleware)
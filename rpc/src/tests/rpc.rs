@@ -44,6 +44,22 @@ fn serve(handler: Option<MetaIoHandler<Metadata>>) -> Server<HttpServer> {
 	).unwrap())
 }
 
+fn serve_with_access(access: extractors::HttpAccessControl) -> Server<HttpServer> {
+	let address = "127.0.0.1:0".parse().unwrap();
+
+	Server::new(|_remote| ::start_http_with_middleware(
+		&address,
+		http::DomainsValidation::Disabled,
+		http::DomainsValidation::Disabled,
+		MetaIoHandler::<Metadata>::default(),
+		extractors::RpcExtractor,
+		access,
+		1,
+		5,
+		false,
+	).unwrap())
+}
+
 /// Test a single request to running server
 fn request(server: Server<HttpServer>, request: &str) -> http_client::Response {
 	http_client::request(server.server.address(), request)
@@ -52,7 +68,9 @@ fn request(server: Server<HttpServer>, request: &str) -> http_client::Response {
 #[cfg(test)]
 mod tests {
 	use jsonrpc_core::{MetaIoHandler, Value};
-	use v1::Metadata;
+	use http::DomainsValidation;
+	use tests::http_client;
+	use v1::{HttpAccessControl, Metadata};
 	use super::{request, Server};
 
 	fn serve() -> (Server<::HttpServer>, ::std::net::SocketAddr) {
@@ -143,4 +161,35 @@ mod tests {
 		assert!(res.headers.contains(&expected), "Headers missing in {:?}", res.headers);
 	}
 
+	#[test]
+	fn should_apply_reloaded_origins_and_hosts() {
+		// given
+		let access = HttpAccessControl::new(
+			DomainsValidation::AllowOnly(vec!["http://parity.io".to_owned().into()]),
+			DomainsValidation::Disabled,
+		);
+		let server = super::serve_with_access(access.clone());
+		let address = server.server.address().to_owned();
+		let options = |origin: &str| http_client::request(&address, &format!("\
+			OPTIONS / HTTP/1.1\r\n\
+			Host: {}\r\n\
+			Origin: {}\r\n\
+			Content-Length: 0\r\n\
+			Connection: close\r\n\
+			\r\n\
+		", address, origin));
+
+		// when
+		let rejected = options("http://example.com");
+		access.set_cors(DomainsValidation::AllowOnly(vec!["http://example.com".to_owned().into()]));
+		let allowed = options("http://example.com");
+		access.set_hosts(DomainsValidation::AllowOnly(vec!["parity.io".to_owned().into()]));
+		let wrong_host = options("http://example.com");
+
+		// then
+		rejected.assert_status("HTTP/1.1 403 Forbidden");
+		allowed.assert_status("HTTP/1.1 200 OK");
+		allowed.assert_header("access-control-allow-origin", "http://example.com");
+		wrong_host.assert_status("HTTP/1.1 403 Forbidden");
+	}
 }
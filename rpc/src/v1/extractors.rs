@@ -20,15 +20,20 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use authcodes;
+use http::{self, hyper, AccessControlAllowOrigin, DomainsValidation, Host, RequestMiddlewareAction};
+use http::cors::{self, AllowCors};
 use http_common::HttpMetaExtractor;
 use ipc;
 use jsonrpc_core as core;
-use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::future::{self, Either};
 use jsonrpc_pubsub::Session;
+use jsonrpc_server_utils::hosts;
 use ws;
 use ethereum_types::H256;
+use parking_lot::RwLock;
 
 use v1::{Metadata, Origin};
+use v1::helpers::HeavyCalls;
 use v1::informant::RpcStats;
 
 /// Common HTTP & IPC metadata extractor.
@@ -241,6 +246,128 @@ impl<M: core::Middleware<Metadata>> core::Middleware<Metadata> for WsDispatcher<
 	}
 }
 
+/// HTTP RPC middleware dispatching every request to a handler that can be replaced at runtime.
+/// Requests containing heavy calls are executed on a separate worker pool, if one is configured.
+///
+/// The lock is only held to take a reference to the current handler, so that the handler may
+/// be replaced by one of the calls it's dispatching.
+pub struct HttpDispatcher<M: core::Middleware<Metadata>> {
+	handler: Arc<RwLock<Arc<core::MetaIoHandler<Metadata, M>>>>,
+	heavy_calls: Option<HeavyCalls>,
+}
+
+impl<M: core::Middleware<Metadata>> HttpDispatcher<M> {
+	/// Create new `HttpDispatcher` serving requests with the current contents of `handler`.
+	pub fn new(handler: Arc<RwLock<Arc<core::MetaIoHandler<Metadata, M>>>>, heavy_calls: Option<HeavyCalls>) -> Self {
+		HttpDispatcher {
+			handler,
+			heavy_calls,
+		}
+	}
+}
+
+impl<M: core::Middleware<Metadata>> core::Middleware<Metadata> for HttpDispatcher<M> {
+	type Future = Either<
+		core::FutureRpcResult<M::Future, M::CallFuture>,
+		core::FutureResponse,
+	>;
+	type CallFuture = core::middleware::NoopCallFuture;
+
+	fn on_request<F, X>(&self, request: core::Request, meta: Metadata, _process: F)
+		-> Either<Self::Future, X>
+	where
+		F: FnOnce(core::Request, Metadata) -> X,
+		X: core::futures::Future<Item=Option<core::Response>, Error=()> + Send + 'static,
+	{
+		let handler = self.handler.read().clone();
+		if let Some(ref heavy_calls) = self.heavy_calls {
+			if let Some((method, timeout)) = heavy_calls.heaviest_call(&request) {
+				return Either::A(Either::B(heavy_calls.dispatch(handler, request, meta, method, timeout)));
+			}
+		}
+
+		Either::A(Either::A(handler.handle_rpc_request(request, meta)))
+	}
+}
+
+struct AccessRules {
+	cors: Option<Vec<AccessControlAllowOrigin>>,
+	hosts: Option<Vec<Host>>,
+}
+
+/// HTTP request middleware checking the `Origin` and `Host` headers against whitelists that
+/// can be replaced while the server is running.
+///
+/// The server must be started with its own CORS and host validation disabled, it then
+/// echoes back any origin this middleware lets through.
+#[derive(Clone)]
+pub struct HttpAccessControl {
+	rules: Arc<RwLock<AccessRules>>,
+}
+
+impl HttpAccessControl {
+	/// Create new `HttpAccessControl` with the initial whitelists.
+	pub fn new(cors: DomainsValidation<AccessControlAllowOrigin>, hosts: DomainsValidation<Host>) -> Self {
+		HttpAccessControl {
+			rules: Arc::new(RwLock::new(AccessRules {
+				cors: cors.into(),
+				hosts: hosts.into(),
+			})),
+		}
+	}
+
+	/// Replace the origins allowed to make cross-origin requests.
+	pub fn set_cors(&self, cors: DomainsValidation<AccessControlAllowOrigin>) {
+		self.rules.write().cors = cors.into();
+	}
+
+	/// Replace the hosts requests may be addressed to.
+	pub fn set_hosts(&self, hosts: DomainsValidation<Host>) {
+		self.rules.write().hosts = hosts.into();
+	}
+}
+
+impl http::RequestMiddleware for HttpAccessControl {
+	fn on_request(&self, request: hyper::Request<hyper::Body>) -> RequestMiddlewareAction {
+		let (host_allowed, origin_allowed) = {
+			let header = |name: hyper::header::HeaderName| request.headers().get(name).and_then(|value| value.to_str().ok());
+			let host = header(hyper::header::HOST);
+			let rules = self.rules.read();
+			let origin_allowed = match cors::get_cors_allow_origin(header(hyper::header::ORIGIN), host, &rules.cors) {
+				AllowCors::Invalid => false,
+				_ => true,
+			};
+			(hosts::is_host_valid(host, &rules.hosts), origin_allowed)
+		};
+
+		// same responses as the server's own validation
+		if !host_allowed {
+			return forbidden("Provided Host header is not whitelisted.\n");
+		}
+		if !origin_allowed {
+			return forbidden("Origin of the request is not whitelisted. CORS headers would not be sent and any side-effects were cancelled as well.\n");
+		}
+
+		RequestMiddlewareAction::Proceed {
+			should_continue_on_invalid_cors: false,
+			request,
+		}
+	}
+}
+
+fn forbidden(message: &'static str) -> RequestMiddlewareAction {
+	let response = hyper::Response::builder()
+		.status(hyper::StatusCode::FORBIDDEN)
+		.header(hyper::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+		.body(hyper::Body::from(message))
+		.expect("Status and headers are valid; qed");
+
+	RequestMiddlewareAction::Respond {
+		should_validate_hosts: false,
+		response: Box::new(future::ok::<_, hyper::Error>(response)),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::RpcExtractor;
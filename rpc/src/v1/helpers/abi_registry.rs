@@ -0,0 +1,237 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Contract ABIs registered by users, used to decode logs and transaction inputs.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use ethabi::{self, Contract, ParamType, RawLog, Token};
+use ethereum_types::{H160, H256, U256};
+use hash::keccak;
+use parking_lot::RwLock;
+use rustc_hex::ToHex;
+
+use v1::types::{DecodedCall, DecodedLog, DecodedParam, Log};
+
+/// Contract ABIs by contract address.
+#[derive(Default)]
+pub struct AbiRegistry {
+	contracts: RwLock<HashMap<H160, Contract>>,
+}
+
+impl AbiRegistry {
+	/// Registers the JSON ABI of the contract at `address`, replacing any previous one.
+	pub fn register(&self, address: H160, abi: &str) -> Result<(), ethabi::Error> {
+		let contract = Contract::load(abi.as_bytes())?;
+		self.contracts.write().insert(address, contract);
+		Ok(())
+	}
+
+	/// Removes the ABI of the contract at `address`. Returns `false` if none was registered.
+	pub fn unregister(&self, address: &H160) -> bool {
+		self.contracts.write().remove(address).is_some()
+	}
+
+	/// Registers every `<address>.json` ABI file in given directory, returns the number registered.
+	pub fn load_dir(&self, dir: &Path) -> Result<usize, String> {
+		let entries = fs::read_dir(dir).map_err(|e| format!("Can't read ABI directory {}: {}", dir.display(), e))?;
+		let mut loaded = 0;
+		for entry in entries {
+			let path = entry.map_err(|e| e.to_string())?.path();
+			if path.extension().map_or(true, |ext| ext != "json") {
+				continue;
+			}
+			let address = match path.file_stem().and_then(|stem| stem.to_str()) {
+				Some(stem) => stem.trim_start_matches("0x").parse::<H160>()
+					.map_err(|_| format!("ABI file name {} is not a contract address", path.display()))?,
+				None => continue,
+			};
+			let abi = fs::read_to_string(&path).map_err(|e| format!("Can't read ABI file {}: {}", path.display(), e))?;
+			self.register(address, &abi).map_err(|e| format!("Invalid ABI in {}: {}", path.display(), e))?;
+			loaded += 1;
+		}
+		Ok(loaded)
+	}
+
+	/// Decodes a log with the ABI of the emitting contract, if registered and an event matches.
+	/// Anonymous events are never matched.
+	pub fn decode_log(&self, address: &H160, topics: &[H256], data: &[u8]) -> Option<DecodedLog> {
+		let contracts = self.contracts.read();
+		let contract = contracts.get(address)?;
+		let topic = topics.first()?;
+		contract.events()
+			.filter(|event| !event.anonymous && event.signature() == *topic)
+			.filter_map(|event| {
+				let log = event.parse_log(RawLog { topics: topics.to_vec(), data: data.to_vec() }).ok()?;
+				let params = event.inputs.iter()
+					.zip(log.params)
+					.map(|(input, param)| decoded_param(param.name, &input.kind, &param.value))
+					.collect();
+				Some(DecodedLog {
+					event: signature(&event.name, event.inputs.iter().map(|input| &input.kind)),
+					params,
+				})
+			})
+			.next()
+	}
+
+	/// Decodes the input of a call to `address` with its ABI, if registered and a function matches.
+	pub fn decode_input(&self, address: &H160, data: &[u8]) -> Option<DecodedCall> {
+		if data.len() < 4 {
+			return None;
+		}
+		let contracts = self.contracts.read();
+		let contract = contracts.get(address)?;
+		contract.functions()
+			.filter_map(|function| {
+				let signature = signature(&function.name, function.inputs.iter().map(|input| &input.kind));
+				if keccak(signature.as_bytes())[..4] != data[..4] {
+					return None;
+				}
+				let kinds: Vec<_> = function.inputs.iter().map(|input| input.kind.clone()).collect();
+				let tokens = ethabi::decode(&kinds, &data[4..]).ok()?;
+				let params = function.inputs.iter()
+					.zip(tokens)
+					.map(|(input, token)| decoded_param(input.name.clone(), &input.kind, &token))
+					.collect();
+				Some(DecodedCall { function: signature, params })
+			})
+			.next()
+	}
+
+	/// Fills in the `decoded` field of logs emitted by registered contracts.
+	pub fn decode_logs(&self, logs: &mut [Log]) {
+		for log in logs {
+			log.decoded = self.decode_log(&log.address, &log.topics, &log.data.0);
+		}
+	}
+}
+
+fn signature<'a>(name: &str, kinds: impl Iterator<Item = &'a ParamType>) -> String {
+	let kinds: Vec<_> = kinds.map(ToString::to_string).collect();
+	format!("{}({})", name, kinds.join(","))
+}
+
+fn decoded_param(name: String, kind: &ParamType, value: &Token) -> DecodedParam {
+	DecodedParam {
+		name,
+		kind: kind.to_string(),
+		value: format_token(value),
+	}
+}
+
+fn format_token(token: &Token) -> String {
+	match *token {
+		Token::Address(ref address) => format!("{:?}", address),
+		Token::FixedBytes(ref bytes) | Token::Bytes(ref bytes) => format!("0x{}", bytes.to_hex()),
+		Token::Uint(ref value) => value.to_string(),
+		Token::Int(ref value) if value.bit(255) => format!("-{}", (!*value).overflowing_add(U256::one()).0),
+		Token::Int(ref value) => value.to_string(),
+		Token::Bool(value) => value.to_string(),
+		Token::String(ref value) => value.clone(),
+		Token::FixedArray(ref tokens) | Token::Array(ref tokens) => {
+			let tokens: Vec<_> = tokens.iter().map(format_token).collect();
+			format!("[{}]", tokens.join(","))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{H160, H256, U256};
+	use rustc_hex::FromHex;
+
+	use ethabi::Token;
+	use super::{AbiRegistry, format_token};
+	use v1::types::{DecodedParam, Log};
+
+	const ERC20: &str = r#"[
+		{"type":"function","name":"transfer","constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"outputs":[{"name":"","type":"bool"}]},
+		{"type":"event","name":"Transfer","anonymous":false,"inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"value","type":"uint256","indexed":false}]}
+	]"#;
+
+	fn param(name: &str, kind: &str, value: &str) -> DecodedParam {
+		DecodedParam { name: name.into(), kind: kind.into(), value: value.into() }
+	}
+
+	#[test]
+	fn should_decode_transfer_input() {
+		let registry = AbiRegistry::default();
+		let token = H160::from_low_u64_be(1);
+		registry.register(token, ERC20).unwrap();
+
+		let input = "a9059cbb000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000003e8".from_hex().unwrap();
+		let call = registry.decode_input(&token, &input).unwrap();
+		assert_eq!(call.function, "transfer(address,uint256)");
+		assert_eq!(call.params, vec![
+			param("to", "address", "0x0000000000000000000000000000000000000002"),
+			param("value", "uint256", "1000"),
+		]);
+
+		assert!(registry.decode_input(&H160::from_low_u64_be(2), &input).is_none());
+		assert!(registry.decode_input(&token, &input[..3]).is_none());
+		assert!(registry.unregister(&token));
+		assert!(registry.decode_input(&token, &input).is_none());
+	}
+
+	#[test]
+	fn should_decode_transfer_log() {
+		let registry = AbiRegistry::default();
+		let token = H160::from_low_u64_be(1);
+		registry.register(token, ERC20).unwrap();
+
+		let transfer: H256 = "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().unwrap();
+		let mut log = Log {
+			address: token,
+			topics: vec![transfer, H256::from_low_u64_be(2), H256::from_low_u64_be(3)],
+			data: "00000000000000000000000000000000000000000000000000000000000003e8".from_hex().unwrap().into(),
+			block_hash: None,
+			block_number: None,
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: None,
+			transaction_log_index: None,
+			log_type: "mined".into(),
+			removed: false,
+			decoded: None,
+		};
+		registry.decode_logs(::std::slice::from_mut(&mut log));
+
+		let decoded = log.decoded.unwrap();
+		assert_eq!(decoded.event, "Transfer(address,address,uint256)");
+		assert_eq!(decoded.params, vec![
+			param("from", "address", "0x0000000000000000000000000000000000000002"),
+			param("to", "address", "0x0000000000000000000000000000000000000003"),
+			param("value", "uint256", "1000"),
+		]);
+		assert!(registry.decode_log(&token, &[H256::zero()], &[]).is_none());
+	}
+
+	#[test]
+	fn should_format_signed_integers() {
+		assert_eq!(format_token(&Token::Int(U256::max_value())), "-1");
+		assert_eq!(format_token(&Token::Int(U256::from(5))), "5");
+		assert_eq!(format_token(&Token::Array(vec![Token::Bool(true), Token::Uint(2.into())])), "[true,2]");
+	}
+
+	#[test]
+	fn should_reject_invalid_abi() {
+		let registry = AbiRegistry::default();
+		assert!(registry.register(H160::zero(), "{").is_err());
+	}
+}
@@ -0,0 +1,340 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Checksummed and ICAP encodings of addresses in RPC requests and responses.
+
+use ethereum_types::Address;
+use ethkey::{from_icap, to_checksum, verify_checksum};
+use jsonrpc_core::{self as core, Value};
+use v1::helpers::errors;
+
+/// Methods taking addresses: index of the parameter holding an address and, for object
+/// parameters, the fields holding addresses or lists of addresses. Both ICAP encoded addresses
+/// and names are accepted in these.
+pub const ADDRESS_PARAMS: &[(&str, usize, &[&str])] = &[
+	("eth_getBalance", 0, &[]),
+	("eth_getCode", 0, &[]),
+	("eth_getStorageAt", 0, &[]),
+	("eth_getTransactionCount", 0, &[]),
+	("eth_sign", 0, &[]),
+	("eth_call", 0, &["from", "to"]),
+	("eth_estimateGas", 0, &["from", "to"]),
+	("eth_sendTransaction", 0, &["from", "to"]),
+	("eth_signTransaction", 0, &["from", "to"]),
+	("eth_getLogs", 0, &["address"]),
+	("eth_newFilter", 0, &["address"]),
+	("personal_sendTransaction", 0, &["from", "to"]),
+	("personal_signTransaction", 0, &["from", "to"]),
+	("personal_unlockAccount", 0, &[]),
+	("personal_sign", 1, &[]),
+	("parity_postTransaction", 0, &["from", "to"]),
+	("parity_nextNonce", 0, &[]),
+	("trace_call", 0, &["from", "to"]),
+	("trace_filter", 0, &["fromAddress", "toAddress"]),
+];
+
+/// Methods whose results are raw data and are left as they are.
+const DATA_METHODS: &[&str] = &[
+	"eth_call",
+	"eth_getCode",
+	"eth_getStorageAt",
+	"eth_sign",
+	"personal_sign",
+	"parity_call",
+];
+
+/// Result fields holding raw data, which are left as they are.
+const DATA_FIELDS: &[&str] = &["data", "input", "init", "code", "output", "extraData", "raw"];
+
+/// Accepts ICAP encoded addresses in the parameters of known methods and formats addresses in
+/// results with their EIP-55 checksum.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AddressFormat {
+	/// Reject calls with mixed-case addresses failing their checksum, instead of accepting any case.
+	pub strict: bool,
+}
+
+/// Formatting left to apply to the response of a request.
+pub struct ResponseFormat {
+	rejected: Vec<core::Output>,
+	data_results: Vec<core::Id>,
+}
+
+impl AddressFormat {
+	/// Converts ICAP addresses in the address parameters of the calls in given request to hex.
+	///
+	/// In strict mode, calls with an address failing its checksum are taken out of the request. If no call
+	/// is left, returns the response to send right away.
+	pub fn format_request(&self, request: &mut core::Request) -> Result<ResponseFormat, Option<core::Response>> {
+		let rejected = match *request {
+			core::Request::Single(ref mut call) => match self.format_call(call) {
+				Ok(()) => Vec::new(),
+				Err(failure) => return Err(failure.map(core::Response::Single)),
+			},
+			core::Request::Batch(ref mut calls) => {
+				let mut rejected = Vec::new();
+				let mut accepted = Vec::with_capacity(calls.len());
+				for mut call in calls.drain(..) {
+					match self.format_call(&mut call) {
+						Ok(()) => accepted.push(call),
+						Err(failure) => rejected.extend(failure),
+					}
+				}
+				if accepted.is_empty() {
+					return Err(if rejected.is_empty() { None } else { Some(core::Response::Batch(rejected)) });
+				}
+				*calls = accepted;
+				rejected
+			},
+		};
+
+		let calls = match *request {
+			core::Request::Single(ref call) => vec![call],
+			core::Request::Batch(ref calls) => calls.iter().collect(),
+		};
+		let data_results = calls.into_iter()
+			.filter_map(|call| match *call {
+				core::Call::MethodCall(ref call) if DATA_METHODS.contains(&call.method.as_str()) => Some(call.id.clone()),
+				_ => None,
+			})
+			.collect();
+
+		Ok(ResponseFormat { rejected, data_results })
+	}
+
+	/// Returns the failure to respond with, if any, when the call is rejected.
+	fn format_call(&self, call: &mut core::Call) -> Result<(), Option<core::Output>> {
+		let (method, params, id, jsonrpc) = match *call {
+			core::Call::MethodCall(ref mut call) => (&call.method, &mut call.params, Some(call.id.clone()), call.jsonrpc),
+			core::Call::Notification(ref mut notification) => (&notification.method, &mut notification.params, None, notification.jsonrpc),
+			core::Call::Invalid { .. } => return Ok(()),
+		};
+		let params = match *params {
+			core::Params::Array(ref mut params) => params,
+			_ => return Ok(()),
+		};
+
+		for &(_, index, fields) in ADDRESS_PARAMS.iter().filter(|&&(name, _, _)| name == method.as_str()) {
+			let param = match params.get_mut(index) {
+				Some(param) => param,
+				None => continue,
+			};
+			let mut result = Ok(());
+			if fields.is_empty() {
+				result = self.format_param(param);
+			} else if let Value::Object(ref mut object) = *param {
+				for field in fields {
+					if let Some(value) = object.get_mut(*field) {
+						result = result.and_then(|_| self.format_param(value));
+					}
+				}
+			}
+
+			if let Err(error) = result {
+				return Err(id.map(|id| core::Output::from(Err(error), id, jsonrpc)));
+			}
+		}
+		Ok(())
+	}
+
+	fn format_param(&self, value: &mut Value) -> Result<(), core::Error> {
+		match *value {
+			Value::Array(ref mut values) => values.iter_mut().map(|value| self.format_param(value)).collect(),
+			Value::String(ref address) if address.starts_with("0x") => {
+				let hex = &address[2..];
+				let mixed_case = hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+				if self.strict && mixed_case {
+					verify_checksum(address).map_err(|e| errors::invalid_params(address, e))?;
+				}
+				Ok(())
+			},
+			Value::String(ref mut address) => {
+				if let Ok(icap) = from_icap(address) {
+					*address = format!("{:?}", icap);
+				}
+				Ok(())
+			},
+			_ => Ok(()),
+		}
+	}
+}
+
+impl ResponseFormat {
+	/// Adds the failures of rejected calls to the response and checksums the addresses in its results.
+	pub fn apply(self, response: Option<core::Response>) -> Option<core::Response> {
+		let data_results = self.data_results;
+		let format_output = |output: &mut core::Output| match *output {
+			core::Output::Success(ref mut success) if !data_results.contains(&success.id) => checksum_value(&mut success.result),
+			_ => {},
+		};
+
+		match response {
+			Some(core::Response::Single(mut output)) => {
+				format_output(&mut output);
+				Some(core::Response::Single(output))
+			},
+			Some(core::Response::Batch(mut outputs)) => {
+				outputs.iter_mut().for_each(format_output);
+				outputs.extend(self.rejected);
+				Some(core::Response::Batch(outputs))
+			},
+			None if !self.rejected.is_empty() => Some(core::Response::Batch(self.rejected)),
+			None => None,
+		}
+	}
+}
+
+fn hex_address(value: &str) -> Option<Address> {
+	if value.len() == 42 && value.starts_with("0x") {
+		value[2..].parse().ok()
+	} else {
+		None
+	}
+}
+
+/// Checksums the addresses in the result of given method, unless its result is raw data.
+pub fn checksum_result(method: &str, value: &mut Value) {
+	if !DATA_METHODS.contains(&method) {
+		checksum_value(value);
+	}
+}
+
+/// Checksums the addresses in given value, leaving raw data fields as they are.
+pub fn checksum_value(value: &mut Value) {
+	match *value {
+		Value::String(ref mut string) => if let Some(address) = hex_address(string) {
+			*string = to_checksum(&address);
+		},
+		Value::Array(ref mut values) => values.iter_mut().for_each(checksum_value),
+		Value::Object(ref mut object) => {
+			// Maps keyed by address, e.g. of accounts info.
+			let address_keys = object.keys()
+				.filter_map(|key| hex_address(key).map(|address| (key.clone(), to_checksum(&address))))
+				.collect::<Vec<_>>();
+			for (key, checksummed) in address_keys {
+				if let Some(value) = object.remove(&key) {
+					object.insert(checksummed, value);
+				}
+			}
+
+			for (key, value) in object.iter_mut() {
+				if !DATA_FIELDS.contains(&key.as_str()) {
+					checksum_value(value);
+				}
+			}
+		},
+		_ => {},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use jsonrpc_core as core;
+	use serde_json;
+
+	use super::{AddressFormat, checksum_result};
+
+	fn request(json: &str) -> core::Request {
+		serde_json::from_str(json).unwrap()
+	}
+
+	fn response(json: &str) -> Option<core::Response> {
+		Some(serde_json::from_str(json).unwrap())
+	}
+
+	#[test]
+	fn should_convert_icap_params() {
+		let mut req = request(r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"address":["XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS"]}],"id":2},
+			{"jsonrpc":"2.0","method":"personal_unlockAccount","params":["0x00c5496aee77c1ba1f0854206a26dda82a81d6d8","XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS"],"id":3}
+		]"#);
+
+		assert!(AddressFormat::default().format_request(&mut req).is_ok());
+
+		assert_eq!(req, request(r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x00c5496aee77c1ba1f0854206a26dda82a81d6d8","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"address":["0x00c5496aee77c1ba1f0854206a26dda82a81d6d8"]}],"id":2},
+			{"jsonrpc":"2.0","method":"personal_unlockAccount","params":["0x00c5496aee77c1ba1f0854206a26dda82a81d6d8","XE7338O073KYGTWWZN0F2WZ0R8PX5ZPPZS"],"id":3}
+		]"#));
+	}
+
+	#[test]
+	fn should_reject_bad_checksums_in_strict_mode() {
+		let json = r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","latest"],"id":2},
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","latest"],"id":3}
+		]"#;
+
+		let mut lenient = request(json);
+		assert!(AddressFormat { strict: false }.format_request(&mut lenient).is_ok());
+		assert_eq!(lenient, request(json));
+
+		let mut strict = request(json);
+		let format = AddressFormat { strict: true }.format_request(&mut strict).ok().unwrap();
+		match strict {
+			core::Request::Batch(ref calls) => assert_eq!(calls.len(), 2),
+			_ => panic!("expected a batch"),
+		}
+		match format.apply(None) {
+			Some(core::Response::Batch(ref outputs)) => {
+				assert_eq!(outputs.len(), 1);
+				assert_eq!(outputs[0].id(), &core::Id::Num(2));
+			},
+			_ => panic!("expected a batch response"),
+		}
+	}
+
+	#[test]
+	fn should_respond_right_away_when_nothing_is_left() {
+		let mut req = request(r#"{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","latest"],"id":1}"#);
+		match AddressFormat { strict: true }.format_request(&mut req) {
+			Err(Some(core::Response::Single(core::Output::Failure(_)))) => {},
+			_ => panic!("expected a failure"),
+		}
+	}
+
+	#[test]
+	fn should_checksum_addresses_in_results() {
+		let mut req = request(r#"[
+			{"jsonrpc":"2.0","method":"eth_getTransactionByHash","params":[],"id":1},
+			{"jsonrpc":"2.0","method":"eth_call","params":[],"id":2}
+		]"#);
+		let format = AddressFormat::default().format_request(&mut req).ok().unwrap();
+
+		let formatted = format.apply(response(r#"[
+			{"jsonrpc":"2.0","result":{"from":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","input":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","accounts":{"0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359":["0xdbf03b407c01e7cd3cbea99509d93f8dddc8c6fb"]}},"id":1},
+			{"jsonrpc":"2.0","result":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","id":2}
+		]"#));
+
+		assert_eq!(formatted, response(r#"[
+			{"jsonrpc":"2.0","result":{"from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed","input":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","accounts":{"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359":["0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB"]}},"id":1},
+			{"jsonrpc":"2.0","result":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed","id":2}
+		]"#));
+	}
+
+	#[test]
+	fn should_leave_data_results_of_polled_methods() {
+		let mut balance = serde_json::from_str(r#"{"from":"0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"}"#).unwrap();
+		checksum_result("eth_getTransactionByHash", &mut balance);
+		assert_eq!(balance, serde_json::from_str::<serde_json::Value>(r#"{"from":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"}"#).unwrap());
+
+		let mut data = serde_json::Value::String("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".into());
+		checksum_result("eth_call", &mut data);
+		assert_eq!(data, serde_json::Value::String("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".into()));
+	}
+}
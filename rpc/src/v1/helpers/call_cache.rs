@@ -0,0 +1,177 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Cache of `eth_call` outputs for the current best block.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ethereum_types::{Address, H256};
+use parking_lot::Mutex;
+use types::transaction::SignedTransaction;
+
+/// Identifies a call: the block it was executed on, the sender and the hash of the call transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CallKey {
+	block: H256,
+	sender: Address,
+	transaction: H256,
+}
+
+impl CallKey {
+	/// Creates the key of a (fake-signed) call executed on top of given block.
+	pub fn new(block: H256, call: &SignedTransaction) -> Self {
+		CallKey {
+			block,
+			sender: call.sender(),
+			transaction: call.as_unsigned().hash(None),
+		}
+	}
+}
+
+/// Cache hits and misses since startup.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CallCacheStats {
+	/// Calls answered from the cache.
+	pub hits: usize,
+	/// Calls that had to be executed.
+	pub misses: usize,
+}
+
+struct Entries {
+	head: H256,
+	outputs: HashMap<CallKey, Vec<u8>>,
+}
+
+/// Outputs of successful calls, dropped whenever the best block changes.
+///
+/// Holds at most `capacity` outputs, later calls are executed but not cached until the next block.
+/// A capacity of zero disables the cache.
+pub struct CallCache {
+	capacity: usize,
+	entries: Mutex<Entries>,
+	hits: AtomicUsize,
+	misses: AtomicUsize,
+}
+
+impl CallCache {
+	/// Creates a cache holding up to `capacity` call outputs.
+	pub fn new(capacity: usize) -> Self {
+		CallCache {
+			capacity,
+			entries: Mutex::new(Entries {
+				head: H256::zero(),
+				outputs: HashMap::new(),
+			}),
+			hits: AtomicUsize::new(0),
+			misses: AtomicUsize::new(0),
+		}
+	}
+
+	/// Whether call outputs are cached at all.
+	pub fn is_enabled(&self) -> bool {
+		self.capacity > 0
+	}
+
+	/// Returns the cached output of a call, `head` being the current best block hash.
+	pub fn get(&self, head: &H256, key: &CallKey) -> Option<Vec<u8>> {
+		let mut entries = self.entries.lock();
+		self.check_head(&mut entries, head);
+		match entries.outputs.get(key) {
+			Some(output) => {
+				self.hits.fetch_add(1, Ordering::Relaxed);
+				Some(output.clone())
+			},
+			None => {
+				self.misses.fetch_add(1, Ordering::Relaxed);
+				None
+			},
+		}
+	}
+
+	/// Caches the output of a successful call, `head` being the current best block hash.
+	pub fn insert(&self, head: &H256, key: CallKey, output: Vec<u8>) {
+		let mut entries = self.entries.lock();
+		self.check_head(&mut entries, head);
+		if entries.outputs.len() < self.capacity {
+			entries.outputs.insert(key, output);
+		}
+	}
+
+	/// Returns cache hits and misses since startup.
+	pub fn stats(&self) -> CallCacheStats {
+		CallCacheStats {
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+		}
+	}
+
+	fn check_head(&self, entries: &mut Entries, head: &H256) {
+		if entries.head != *head {
+			let stats = self.stats();
+			debug!(target: "rpc", "New best block, dropping {} cached calls ({} hits, {} misses so far)", entries.outputs.len(), stats.hits, stats.misses);
+			entries.head = *head;
+			entries.outputs.clear();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{Address, H256, U256};
+	use types::transaction::{Action, Transaction};
+
+	use super::{CallCache, CallCacheStats, CallKey};
+
+	fn key(block: H256, data: Vec<u8>) -> CallKey {
+		let call = Transaction {
+			nonce: U256::zero(),
+			action: Action::Call(Address::from_low_u64_be(1)),
+			gas: 100_000.into(),
+			gas_price: U256::zero(),
+			value: U256::zero(),
+			data,
+		}.fake_sign(Address::from_low_u64_be(2));
+		CallKey::new(block, &call)
+	}
+
+	#[test]
+	fn should_cache_calls_until_head_changes() {
+		let cache = CallCache::new(10);
+		let head = H256::from_low_u64_be(1);
+		let call = key(head, vec![1]);
+
+		assert_eq!(cache.get(&head, &call), None);
+		cache.insert(&head, call.clone(), vec![42]);
+		assert_eq!(cache.get(&head, &call), Some(vec![42]));
+		assert_eq!(cache.get(&head, &key(head, vec![2])), None);
+
+		assert_eq!(cache.get(&H256::from_low_u64_be(2), &call), None);
+		assert_eq!(cache.stats(), CallCacheStats { hits: 1, misses: 3 });
+	}
+
+	#[test]
+	fn should_not_exceed_capacity() {
+		let cache = CallCache::new(1);
+		let head = H256::from_low_u64_be(1);
+		cache.insert(&head, key(head, vec![1]), vec![1]);
+		cache.insert(&head, key(head, vec![2]), vec![2]);
+
+		assert_eq!(cache.get(&head, &key(head, vec![1])), Some(vec![1]));
+		assert_eq!(cache.get(&head, &key(head, vec![2])), None);
+		assert!(!CallCache::new(0).is_enabled());
+	}
+}
@@ -38,6 +38,7 @@ pub struct FullDispatcher<C, M> {
 	miner: Arc<M>,
 	nonces: Arc<Mutex<nonce::Reservations>>,
 	gas_price_percentile: usize,
+	gas_price_blocks: usize,
 }
 
 impl<C, M> FullDispatcher<C, M> {
@@ -47,12 +48,14 @@ impl<C, M> FullDispatcher<C, M> {
 		miner: Arc<M>,
 		nonces: Arc<Mutex<nonce::Reservations>>,
 		gas_price_percentile: usize,
+		gas_price_blocks: usize,
 	) -> Self {
 		FullDispatcher {
 			client,
 			miner,
 			nonces,
 			gas_price_percentile,
+			gas_price_blocks,
 		}
 	}
 }
@@ -64,6 +67,7 @@ impl<C, M> Clone for FullDispatcher<C, M> {
 			miner: self.miner.clone(),
 			nonces: self.nonces.clone(),
 			gas_price_percentile: self.gas_price_percentile,
+			gas_price_blocks: self.gas_price_blocks,
 		}
 	}
 }
@@ -88,7 +92,7 @@ impl<C: miner::BlockChainClient, M: MinerService> FullDispatcher<C, M> {
 	}
 }
 
-impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher for FullDispatcher<C, M> {
+impl<C: miner::BlockChainClient + BlockChainClient + 'static, M: MinerService + 'static> Dispatcher for FullDispatcher<C, M> {
 	fn fill_optional_fields(&self, request: TransactionRequest, default_sender: Address, force_nonce: bool)
 		-> BoxFuture<FilledTransactionRequest>
 	{
@@ -106,7 +110,7 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 			to: request.to,
 			nonce,
 			gas_price: request.gas_price.unwrap_or_else(|| {
-				default_gas_price(&*self.client, &*self.miner, self.gas_price_percentile)
+				default_gas_price(&*self.client, &*self.miner, self.gas_price_percentile, self.gas_price_blocks)
 			}),
 			gas: request.gas.unwrap_or_else(|| self.miner.sensible_gas_limit()),
 			value: request.value.unwrap_or_else(|| 0.into()),
@@ -148,4 +152,45 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 	fn dispatch_transaction(&self, signed_transaction: PendingTransaction) -> Result<H256> {
 		Self::dispatch_transaction(&*self.client, &*self.miner, signed_transaction, true)
 	}
+
+	fn sign_and_dispatch_batch(
+		&self,
+		filled: Vec<FilledTransactionRequest>,
+		signer: &Arc<dyn Accounts>,
+		password: SignWith,
+	) -> BoxFuture<Vec<H256>> {
+		let from = match filled.first() {
+			Some(first) => first.from,
+			None => return Box::new(future::ok(Vec::new())),
+		};
+		let chain_id = self.client.signing_chain_id();
+		let state = self.state_nonce(&from);
+		let reserved = self.nonces.lock().reserve_range(from, state, filled.len());
+
+		let client = self.client.clone();
+		let miner = self.miner.clone();
+		let signer = signer.clone();
+		Box::new(reserved
+			.map_err(|_| errors::internal("Nonce reservation failure", ""))
+			.and_then(move |nonce| {
+				let conditions: Vec<_> = filled.iter().map(|filled| filled.condition.clone()).collect();
+				// Dropping the reservation on failure hands the whole range out again.
+				let signed = signer.sign_transactions(filled, chain_id, *nonce.value(), password)?.into_value();
+
+				let mut hashes = Vec::with_capacity(signed.len());
+				for (transaction, condition) in signed.into_iter().zip(conditions) {
+					let pending = PendingTransaction::new(transaction, condition.map(Into::into));
+					match Self::dispatch_transaction(&*client, &*miner, pending, true) {
+						Ok(hash) => hashes.push(hash),
+						Err(e) => {
+							nonce.mark_partially_used(hashes.len());
+							return Err(errors::batch_partially_dispatched(e, &hashes));
+						},
+					}
+				}
+				nonce.mark_used();
+				Ok(hashes)
+			})
+		)
+	}
 }
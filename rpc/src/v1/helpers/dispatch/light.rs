@@ -242,6 +242,33 @@ where
 			.map_err(errors::transaction)
 			.map(|_| hash)
 	}
+
+	fn sign_and_dispatch_batch(
+		&self,
+		filled: Vec<FilledTransactionRequest>,
+		signer: &Arc<dyn Accounts>,
+		password: SignWith,
+	) -> BoxFuture<Vec<H256>> {
+		let nonce = match filled.first() {
+			Some(first) => first.nonce.expect("nonce is always provided; qed"),
+			None => return Box::new(future::ok(Vec::new())),
+		};
+		let chain_id = self.client.signing_chain_id();
+		let conditions: Vec<_> = filled.iter().map(|filled| filled.condition.clone()).collect();
+		let signed = match signer.sign_transactions(filled, chain_id, nonce, password) {
+			Ok(signed) => signed.into_value(),
+			Err(e) => return Box::new(future::err(e)),
+		};
+
+		let mut hashes = Vec::with_capacity(signed.len());
+		for (transaction, condition) in signed.into_iter().zip(conditions) {
+			match self.dispatch_transaction(PendingTransaction::new(transaction, condition.map(Into::into))) {
+				Ok(hash) => hashes.push(hash),
+				Err(e) => return Box::new(future::err(errors::batch_partially_dispatched(e, &hashes))),
+			}
+		}
+		Box::new(future::ok(hashes))
+	}
 }
 
 /// Get a recent gas price corpus.
@@ -43,6 +43,10 @@ mod signing {
 			Err(errors::account("Signing unsupported", "See #9997"))
 		}
 
+		fn sign_transactions(&self, _filled: Vec<FilledTransactionRequest>, _chain_id: Option<u64>, _nonce: U256, _password: SignWith) -> Result<WithToken<Vec<SignedTransaction>>> {
+			Err(errors::account("Signing unsupported", "See #9997"))
+		}
+
 		fn sign_message(&self, _address: Address, _password: SignWith, _hash: SignMessage) -> Result<WithToken<Signature>> {
 			Err(errors::account("Signing unsupported", "See #9997"))
 		}
@@ -51,6 +55,10 @@ mod signing {
 			Err(errors::account("Signing unsupported", "See #9997"))
 		}
 
+		fn export_secret(&self, _address: Address, _password: SignWith) -> Result<H256> {
+			Err(errors::account("Signing unsupported", "See #9997"))
+		}
+
 		fn supports_prospective_signing(&self, _address: &Address, _password: &SignWith) -> bool {
 			false
 		}
@@ -94,6 +102,7 @@ use v1::types::{
 	EthSignRequest as RpcEthSignRequest,
 	EIP191SignRequest as RpcSignRequest,
 	DecryptRequest as RpcDecryptRequest,
+	ExportSecretRequest as RpcExportSecretRequest,
 };
 
 /// Has the capability to dispatch, sign, and decrypt.
@@ -124,6 +133,16 @@ pub trait Dispatcher: Send + Sync + Clone {
 
 	/// "Dispatch" a local transaction.
 	fn dispatch_transaction(&self, signed_transaction: PendingTransaction) -> Result<H256>;
+
+	/// Sign transaction requests of a single sender with consecutive nonces and dispatch them in order.
+	/// Nothing is dispatched unless all of them were signed. Dispatching stops at the first rejected
+	/// transaction, the error then lists the hashes of the transactions dispatched before it.
+	fn sign_and_dispatch_batch(
+		&self,
+		filled: Vec<FilledTransactionRequest>,
+		signer: &Arc<dyn Accounts>,
+		password: SignWith,
+	) -> BoxFuture<Vec<H256>>;
 }
 
 /// Payload to sign
@@ -142,12 +161,19 @@ pub trait Accounts: Send + Sync {
 	/// Sign given filled transaction request for the specified chain_id.
 	fn sign_transaction(&self, filled: FilledTransactionRequest, chain_id: Option<u64>, nonce: U256, password: SignWith) -> Result<WithToken<SignedTransaction>>;
 
+	/// Sign given filled transaction requests of a single sender with consecutive nonces starting at `nonce`,
+	/// unlocking the account only once.
+	fn sign_transactions(&self, filled: Vec<FilledTransactionRequest>, chain_id: Option<u64>, nonce: U256, password: SignWith) -> Result<WithToken<Vec<SignedTransaction>>>;
+
 	/// Sign given message.
 	fn sign_message(&self, address: Address, password: SignWith, hash: SignMessage) -> Result<WithToken<Signature>>;
 
 	/// Decrypt given message.
 	fn decrypt(&self, address: Address, password: SignWith, data: Bytes) -> Result<WithToken<Bytes>>;
 
+	/// Export the raw secret of an account. Only accepts the account password.
+	fn export_secret(&self, address: Address, password: SignWith) -> Result<H256>;
+
 	/// Returns `true` if the accounts can sign multiple times.
 	fn supports_prospective_signing(&self, address: &Address, password: &SignWith) -> bool;
 
@@ -332,6 +358,12 @@ pub fn execute<D: Dispatcher + 'static>(
 				);
 			Box::new(future::done(res))
 		},
+		ConfirmationPayload::ExportSecret(address) => {
+			let res = signer.export_secret(address, pass)
+				.map(ConfirmationResponse::Secret)
+				.map(WithToken::No);
+			Box::new(future::done(res))
+		},
 	}
 }
 
@@ -346,12 +378,12 @@ pub fn eth_data_hash(mut data: Bytes) -> H256 {
 	keccak(message_data)
 }
 
-/// Extract the default gas price from a client and miner.
-pub fn default_gas_price<C, M>(client: &C, miner: &M, percentile: usize) -> U256 where
+/// Extract the default gas price from a client and miner, sampling the last `sample_size` blocks.
+pub fn default_gas_price<C, M>(client: &C, miner: &M, percentile: usize, sample_size: usize) -> U256 where
 	C: BlockChainClient,
 	M: MinerService,
 {
-	client.gas_price_corpus(100).percentile(percentile).cloned().unwrap_or_else(|| miner.sensible_gas_price())
+	client.gas_price_corpus(sample_size).percentile(percentile).cloned().unwrap_or_else(|| miner.sensible_gas_price())
 }
 
 /// Convert RPC confirmation payload to signer confirmation payload.
@@ -377,5 +409,8 @@ pub fn from_rpc<D>(payload: RpcConfirmationPayload, default_account: Address, di
 		RpcConfirmationPayload::EIP191SignMessage(RpcSignRequest { address, data }) => {
 			Box::new(future::ok(ConfirmationPayload::SignMessage(address, data)))
 		},
+		RpcConfirmationPayload::ExportSecret(RpcExportSecretRequest { address }) => {
+			Box::new(future::ok(ConfirmationPayload::ExportSecret(address)))
+		},
 	}
 }
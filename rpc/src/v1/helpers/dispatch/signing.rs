@@ -16,7 +16,7 @@
 
 use std::sync::Arc;
 
-use accounts::AccountProvider;
+use accounts::{AccountProvider, SignError};
 use bytes::Bytes;
 use crypto::DEFAULT_MAC;
 use ethereum_types::{H256, U256, Address};
@@ -42,22 +42,46 @@ impl Signer {
 
 impl super::Accounts for Signer {
 	fn sign_transaction(&self, filled: FilledTransactionRequest, chain_id: Option<u64>, nonce: U256, password: SignWith) -> Result<WithToken<SignedTransaction>> {
-		let t = Transaction {
-			nonce: nonce,
-			action: filled.to.map_or(Action::Create, Action::Call),
-			gas: filled.gas,
-			gas_price: filled.gas_price,
-			value: filled.value,
-			data: filled.data,
-		};
+		let from = filled.from;
+		let t = transaction(filled, nonce);
 
 		let hash = t.hash(chain_id);
-		let signature = signature(&*self.accounts, filled.from, hash, password)?;
+		let signature = signature(&*self.accounts, from, hash, password)?;
+
+		Ok(signature.map(|sig| signed(t, sig, chain_id)))
+	}
+
+	fn sign_transactions(&self, filled: Vec<FilledTransactionRequest>, chain_id: Option<u64>, nonce: U256, password: SignWith) -> Result<WithToken<Vec<SignedTransaction>>> {
+		let from = match filled.first() {
+			Some(first) => first.from,
+			None => return Ok(WithToken::No(Vec::new())),
+		};
+		let transactions: Vec<_> = filled.into_iter()
+			.enumerate()
+			.map(|(i, filled)| transaction(filled, nonce + i))
+			.collect();
+		let hashes: Vec<_> = transactions.iter().map(|t| t.hash(chain_id)).collect();
+
+		let signatures = match password.clone() {
+			SignWith::Nothing => self.accounts.sign_many(from, None, &hashes).map(WithToken::No),
+			SignWith::Password(pass) => self.accounts.sign_many(from, Some(pass), &hashes).map(WithToken::No),
+			SignWith::Token(token) => hashes.iter()
+				.try_fold((Vec::new(), token), |(mut signatures, token), hash| {
+					let (signature, token) = self.accounts.sign_with_token(from, token, *hash)?;
+					signatures.push(signature);
+					Ok::<_, SignError>((signatures, token))
+				})
+				.map(Into::into),
+		}.map_err(|e| match password {
+			SignWith::Nothing => errors::signing(e),
+			_ => errors::password(e),
+		})?;
 
-		Ok(signature.map(|sig| {
-			SignedTransaction::new(t.with_signature(sig, chain_id))
-				.expect("Transaction was signed by AccountsProvider; it never produces invalid signatures; qed")
-		}))
+		Ok(signatures.map(|signatures| transactions.into_iter()
+			.zip(signatures)
+			.map(|(t, sig)| signed(t, sig, chain_id))
+			.collect()
+		))
 	}
 
 	fn sign_message(&self, address: Address, password: SignWith, hash: SignMessage) -> Result<WithToken<Signature>> {
@@ -83,6 +107,16 @@ impl super::Accounts for Signer {
 		})
 	}
 
+	fn export_secret(&self, address: Address, password: SignWith) -> Result<H256> {
+		// an unlocked account or a token is not enough, the user has to enter the password for every export
+		match password {
+			SignWith::Password(pass) => self.accounts.export_secret(&address, &pass)
+				.map(|secret| *secret)
+				.map_err(|e| errors::password(SignError::SStore(e))),
+			_ => Err(errors::unsupported("Exporting a secret requires the account password.", None)),
+		}
+	}
+
 	fn supports_prospective_signing(&self, address: &Address, password: &SignWith) -> bool {
 		// If the account is permanently unlocked we can try to sign
 		// using prospective nonce. This should speed up sending
@@ -102,6 +136,22 @@ impl super::Accounts for Signer {
 	}
 }
 
+fn transaction(filled: FilledTransactionRequest, nonce: U256) -> Transaction {
+	Transaction {
+		nonce,
+		action: filled.to.map_or(Action::Create, Action::Call),
+		gas: filled.gas,
+		gas_price: filled.gas_price,
+		value: filled.value,
+		data: filled.data,
+	}
+}
+
+fn signed(t: Transaction, signature: Signature, chain_id: Option<u64>) -> SignedTransaction {
+	SignedTransaction::new(t.with_signature(signature, chain_id))
+		.expect("Transaction was signed by AccountsProvider; it never produces invalid signatures; qed")
+}
+
 fn signature(accounts: &AccountProvider, address: Address, hash: H256, password: SignWith) -> Result<WithToken<Signature>> {
 	match password.clone() {
 		SignWith::Nothing => accounts.sign(address, None, hash).map(WithToken::No),
@@ -17,9 +17,12 @@
 //! RPC Error codes and error objects
 
 use std::fmt;
+use std::time::Duration;
 
+use ethereum_types::H256;
 use jsonrpc_core::{futures, Result as RpcResult, Error, ErrorCode, Value};
 use rlp::DecoderError;
+use serde_json::Map;
 use types::transaction::Error as TransactionError;
 use ethcore_private_tx::Error as PrivateTransactionError;
 use vm::Error as VMError;
@@ -54,6 +57,7 @@ mod codes {
 	pub const PASSWORD_INVALID: i64 = -32021;
 	pub const ACCOUNT_ERROR: i64 = -32023;
 	pub const PRIVATE_ERROR: i64 = -32024;
+	pub const REQUEST_TIMEOUT: i64 = -32030;
 	pub const REQUEST_REJECTED: i64 = -32040;
 	pub const REQUEST_REJECTED_LIMIT: i64 = -32041;
 	pub const REQUEST_NOT_FOUND: i64 = -32042;
@@ -115,6 +119,22 @@ pub fn request_rejected_limit() -> Error {
 	}
 }
 
+pub fn request_timeout(method: &str, timeout: Duration) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::REQUEST_TIMEOUT),
+		message: "Request took too long, narrow your query and try again.".into(),
+		data: Some(Value::String(format!("{} exceeded its timeout of {:?}", method, timeout))),
+	}
+}
+
+pub fn too_many_results(limit: usize, items_desc: &str) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::REQUEST_REJECTED_LIMIT),
+		message: format!("Too many {} match the request, at most {} can be returned. Use `after` and `count` to page through them.", items_desc, limit),
+		data: Some(Value::Object(vec![("limit".to_owned(), Value::from(limit))].into_iter().collect())),
+	}
+}
+
 pub fn request_rejected_param_limit(limit: u64, items_desc: &str) -> Error {
 	Error {
 		code: ErrorCode::ServerError(codes::REQUEST_REJECTED_LIMIT),
@@ -371,6 +391,22 @@ pub fn fetch<T: fmt::Debug>(error: T) -> Error {
 	}
 }
 
+pub fn private_relay_disabled() -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::UNSUPPORTED_REQUEST),
+		message: "No private transaction relay configured.".into(),
+		data: Some(Value::String("Start the node with --private-tx-relay to enable it.".into())),
+	}
+}
+
+pub fn private_relay_rejected(error: Value) -> Error {
+	Error {
+		code: ErrorCode::ServerError(codes::TRANSACTION_ERROR),
+		message: "Transaction rejected by the private relay.".into(),
+		data: Some(error),
+	}
+}
+
 #[cfg(any(test, feature = "accounts"))]
 pub fn invalid_call_data<T: fmt::Display>(error: T) -> Error {
 	Error {
@@ -480,6 +516,22 @@ pub fn transaction<T: Into<EthcoreError>>(error: T) -> Error {
 	}
 }
 
+/// Error of a batch of transactions which stopped dispatching at a rejected transaction. The data
+/// lists the hashes of the transactions dispatched before it next to the data of the original error.
+pub fn batch_partially_dispatched(error: Error, dispatched: &[H256]) -> Error {
+	let mut data = Map::new();
+	data.insert("dispatched".into(), Value::Array(dispatched.iter().map(|hash| Value::String(format!("{:#x}", hash))).collect()));
+	if let Some(reason) = error.data {
+		data.insert("reason".into(), reason);
+	}
+
+	Error {
+		code: error.code,
+		message: error.message,
+		data: Some(Value::Object(data)),
+	}
+}
+
 pub fn decode<T: Into<EthcoreError>>(error: T) -> Error {
 	match error.into() {
 		EthcoreError::Decoder(ref dec_err) => rlp(dec_err.clone()),
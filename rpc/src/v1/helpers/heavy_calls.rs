@@ -0,0 +1,266 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Execution of expensive RPC methods on a dedicated worker pool.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use jsonrpc_core as core;
+use jsonrpc_core::futures::{future, Future, sync::oneshot};
+use parity_runtime::Runtime;
+use tokio_timer::{self, Timer};
+
+use v1::Metadata;
+use v1::helpers::errors;
+
+/// Methods treated as heavy by default, with their soft timeouts in seconds.
+pub const DEFAULT_HEAVY_CALLS: &str = "eth_getLogs:30,eth_getFilterLogs:30,trace_filter:30,trace_block:30,\
+	trace_replayTransaction:30,trace_replayBlockTransactions:30,debug_replayBlock:30";
+
+/// Number of heavy calls that may wait for a worker, per worker.
+pub const QUEUED_CALLS_PER_THREAD: usize = 32;
+
+/// Expensive methods, executed on a worker pool so that they can't occupy all of the server threads.
+///
+/// Timeouts are soft: a call exceeding its timeout while executing is answered with an error,
+/// but keeps its worker busy until it completes. A call timing out while queued is dropped.
+pub struct HeavyCalls {
+	timeouts: HashMap<String, Duration>,
+	runtime: Runtime,
+	timer: Timer,
+	// calls queued or executing
+	pending: Arc<AtomicUsize>,
+	max_pending: usize,
+}
+
+impl HeavyCalls {
+	/// Creates a pool executing at most `max_concurrent` heavy calls at a time. Up to
+	/// `QUEUED_CALLS_PER_THREAD` more per worker are queued, further ones are rejected.
+	pub fn new(timeouts: HashMap<String, Duration>, max_concurrent: usize) -> Self {
+		let threads = ::std::cmp::max(1, max_concurrent);
+		HeavyCalls {
+			timeouts,
+			runtime: Runtime::with_thread_count(threads),
+			timer: tokio_timer::wheel()
+				.tick_duration(Duration::from_millis(10))
+				.build(),
+			pending: Default::default(),
+			max_pending: threads * (1 + QUEUED_CALLS_PER_THREAD),
+		}
+	}
+
+	/// Parses a comma-separated list of `method:seconds` pairs.
+	pub fn parse_timeouts(s: &str) -> Result<HashMap<String, Duration>, String> {
+		s.split(',')
+			.map(str::trim)
+			.filter(|entry| !entry.is_empty())
+			.map(|entry| {
+				let mut parts = entry.splitn(2, ':');
+				let method = parts.next().unwrap_or_default();
+				let seconds = parts.next()
+					.ok_or_else(|| format!("Missing timeout for heavy RPC method {}", method))?
+					.parse::<u64>()
+					.map_err(|e| format!("Invalid timeout for heavy RPC method {}: {}", method, e))?;
+				Ok((method.to_owned(), Duration::from_secs(seconds)))
+			})
+			.collect()
+	}
+
+	/// Returns the heavy method with the shortest timeout in given request, if any.
+	pub fn heaviest_call(&self, request: &core::Request) -> Option<(&str, Duration)> {
+		let calls = match *request {
+			core::Request::Single(ref call) => ::std::slice::from_ref(call),
+			core::Request::Batch(ref calls) => &calls[..],
+		};
+
+		calls.iter()
+			.filter_map(|call| match *call {
+				core::Call::MethodCall(ref call) => self.timeouts.get_key_value(&call.method),
+				_ => None,
+			})
+			.map(|(method, timeout)| (method.as_str(), *timeout))
+			.min_by_key(|&(_, timeout)| timeout)
+	}
+
+	/// Executes the request on the worker pool, answering with a timeout error for every call in it
+	/// if no response is ready within `timeout`. Answers with a rejection right away if the queue
+	/// is full.
+	pub fn dispatch<M: core::Middleware<Metadata>>(
+		&self,
+		handler: Arc<core::MetaIoHandler<Metadata, M>>,
+		request: core::Request,
+		meta: Metadata,
+		method: &str,
+		timeout: Duration,
+	) -> core::FutureResponse {
+		if self.pending.fetch_add(1, Ordering::SeqCst) >= self.max_pending {
+			self.pending.fetch_sub(1, Ordering::SeqCst);
+			return Box::new(future::ok(error_response(&request, errors::request_rejected_limit())));
+		}
+
+		let timed_out = error_response(&request, errors::request_timeout(method, timeout));
+
+		let (tx, rx) = oneshot::channel();
+		let pending = self.pending.clone();
+		self.runtime.executor().spawn_fn(move || {
+			// timed out while queued, nobody waits for the response anymore
+			if tx.is_canceled() {
+				pending.fetch_sub(1, Ordering::SeqCst);
+				return future::Either::A(future::ok(()));
+			}
+
+			let response = handler.handle_rpc_request(request, meta);
+			future::Either::B(response.then(move |res| {
+				pending.fetch_sub(1, Ordering::SeqCst);
+				let _ = tx.send(res);
+				Ok(())
+			}))
+		});
+
+		let response = rx.then(|res| match res {
+			Ok(res) => res,
+			Err(_) => Err(()),
+		});
+		let timeout = self.timer.sleep(timeout).then(move |_| Ok(timed_out));
+
+		Box::new(response.select(timeout)
+			.map(|(res, _)| res)
+			.map_err(|(err, _)| err)
+		)
+	}
+}
+
+fn error_response(request: &core::Request, error: core::Error) -> Option<core::Response> {
+	let output = |call: &core::Call| match *call {
+		core::Call::MethodCall(ref call) => Some(core::Output::from(Err(error.clone()), call.id.clone(), call.jsonrpc)),
+		_ => None,
+	};
+
+	match *request {
+		core::Request::Single(ref call) => output(call).map(core::Response::Single),
+		core::Request::Batch(ref calls) => Some(core::Response::Batch(calls.iter().filter_map(output).collect())),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use std::thread;
+	use std::time::Duration;
+
+	use jsonrpc_core::{self as core, MetaIoHandler, Value};
+	use jsonrpc_core::futures::{future, Future};
+
+	use v1::Metadata;
+	use super::{HeavyCalls, DEFAULT_HEAVY_CALLS, QUEUED_CALLS_PER_THREAD};
+
+	fn handler(executed: Arc<AtomicUsize>) -> Arc<MetaIoHandler<Metadata>> {
+		let mut handler = MetaIoHandler::default();
+		handler.add_method("eth_getLogs", move |params: core::Params| {
+			executed.fetch_add(1, Ordering::SeqCst);
+			let millis: u64 = params.parse::<(u64,)>()?.0;
+			thread::sleep(Duration::from_millis(millis));
+			Ok(Value::Bool(true))
+		});
+		Arc::new(handler)
+	}
+
+	fn dispatch(heavy: &HeavyCalls, handler: Arc<MetaIoHandler<Metadata>>, request: &str) -> core::FutureResponse {
+		let request: core::Request = ::serde_json::from_str(request).unwrap();
+		let (method, timeout) = heavy.heaviest_call(&request).unwrap();
+		heavy.dispatch(handler, request, Default::default(), method, timeout)
+	}
+
+	fn call(heavy: &HeavyCalls, request: &str) -> String {
+		let response = dispatch(heavy, handler(Default::default()), request).wait().unwrap();
+		::serde_json::to_string(&response.unwrap()).unwrap()
+	}
+
+	#[test]
+	fn should_parse_default_timeouts() {
+		let timeouts = HeavyCalls::parse_timeouts(DEFAULT_HEAVY_CALLS).unwrap();
+		assert_eq!(timeouts["eth_getLogs"], Duration::from_secs(30));
+		assert_eq!(timeouts.len(), 7);
+		assert!(HeavyCalls::parse_timeouts("").unwrap().is_empty());
+		assert!(HeavyCalls::parse_timeouts("eth_getLogs").is_err());
+		assert!(HeavyCalls::parse_timeouts("eth_getLogs:soon").is_err());
+	}
+
+	#[test]
+	fn should_find_heaviest_call() {
+		let heavy = HeavyCalls::new(HeavyCalls::parse_timeouts("eth_getLogs:10,trace_filter:5").unwrap(), 1);
+		let single: core::Request = ::serde_json::from_str(r#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[],"id":1}"#).unwrap();
+		let light: core::Request = ::serde_json::from_str(r#"{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":1}"#).unwrap();
+		let batch: core::Request = ::serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","method":"eth_getLogs","params":[],"id":1},
+			{"jsonrpc":"2.0","method":"trace_filter","params":[],"id":2}
+		]"#).unwrap();
+
+		assert_eq!(heavy.heaviest_call(&single), Some(("eth_getLogs", Duration::from_secs(10))));
+		assert_eq!(heavy.heaviest_call(&light), None);
+		assert_eq!(heavy.heaviest_call(&batch), Some(("trace_filter", Duration::from_secs(5))));
+	}
+
+	#[test]
+	fn should_answer_calls_completing_in_time() {
+		let heavy = HeavyCalls::new(HeavyCalls::parse_timeouts("eth_getLogs:5").unwrap(), 1);
+		let response = call(&heavy, r#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[0],"id":1}"#);
+		assert_eq!(response, r#"{"jsonrpc":"2.0","result":true,"id":1}"#);
+	}
+
+	#[test]
+	fn should_time_out_slow_calls() {
+		let mut timeouts = HeavyCalls::parse_timeouts("").unwrap();
+		timeouts.insert("eth_getLogs".into(), Duration::from_millis(50));
+		let heavy = HeavyCalls::new(timeouts, 1);
+
+		let response = call(&heavy, r#"[{"jsonrpc":"2.0","method":"eth_getLogs","params":[500],"id":1}]"#);
+		assert_eq!(response, r#"[{"jsonrpc":"2.0","error":{"code":-32030,"message":"Request took too long, narrow your query and try again.","data":"eth_getLogs exceeded its timeout of 50ms"},"id":1}]"#);
+	}
+
+	#[test]
+	fn should_drop_calls_timing_out_while_queued() {
+		let mut timeouts = HeavyCalls::parse_timeouts("").unwrap();
+		timeouts.insert("eth_getLogs".into(), Duration::from_millis(50));
+		let heavy = HeavyCalls::new(timeouts, 1);
+		let executed = Arc::new(AtomicUsize::new(0));
+
+		let request = r#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[300],"id":1}"#;
+		let first = dispatch(&heavy, handler(executed.clone()), request);
+		let second = dispatch(&heavy, handler(executed.clone()), request);
+		future::join_all(vec![first, second]).wait().unwrap();
+
+		// the second call is only dequeued once the first one completes
+		thread::sleep(Duration::from_millis(500));
+		assert_eq!(executed.load(Ordering::SeqCst), 1);
+	}
+
+	#[test]
+	fn should_reject_calls_beyond_queue_limit() {
+		let heavy = HeavyCalls::new(HeavyCalls::parse_timeouts("eth_getLogs:5").unwrap(), 1);
+		let request = r#"{"jsonrpc":"2.0","method":"eth_getLogs","params":[300],"id":1}"#;
+		let _queued: Vec<_> = (0..=QUEUED_CALLS_PER_THREAD)
+			.map(|_| dispatch(&heavy, handler(Default::default()), request))
+			.collect();
+
+		let response = dispatch(&heavy, handler(Default::default()), request).wait().unwrap();
+		assert_eq!(::serde_json::to_string(&response.unwrap()).unwrap(), r#"{"jsonrpc":"2.0","error":{"code":-32041,"message":"Request has been rejected because of queue limit."},"id":1}"#);
+	}
+}
@@ -417,6 +417,7 @@ where
 											transaction_log_index: Some(transaction_log_index.into()),
 											log_type: "mined".into(),
 											removed: false,
+											decoded: None,
 										});
 									}
 									block_index += 1;
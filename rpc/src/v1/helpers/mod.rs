@@ -17,7 +17,10 @@
 #[macro_use]
 pub mod errors;
 
+pub mod abi_registry;
+pub mod address_format;
 pub mod block_import;
+pub mod call_cache;
 pub mod deprecated;
 pub mod dispatch;
 #[cfg(any(test, feature = "accounts"))]
@@ -26,26 +29,39 @@ pub mod eip191;
 pub mod engine_signer;
 pub mod external_signer;
 pub mod fake_sign;
+pub mod heavy_calls;
 pub mod ipfs;
 pub mod light_fetch;
+pub mod name_resolution;
 pub mod nonce;
+pub mod private_relay;
 #[cfg(any(test, feature = "accounts"))]
 pub mod secretstore;
+pub mod state_dump;
 
 mod network_settings;
 mod poll_filter;
 mod poll_manager;
 mod requests;
+mod rpc_modules;
 mod subscribers;
 mod subscription_manager;
 mod work;
 mod signature;
 
+use types::transaction::{Condition, PendingTransaction};
+
+pub use self::abi_registry::AbiRegistry;
+pub use self::address_format::AddressFormat;
 pub use self::dispatch::{Dispatcher, FullDispatcher, LightDispatcher};
 pub use self::signature::verify_signature;
 pub use self::network_settings::NetworkSettings;
 pub use self::poll_manager::PollManager;
-pub use self::poll_filter::{PollFilter, SyncPollFilter, limit_logs};
+pub use self::poll_filter::{PollFilter, SyncPollFilter, limit_logs, skip_logs, skip_pending_logs};
+pub use self::heavy_calls::HeavyCalls;
+pub use self::name_resolution::NameResolution;
+pub use self::private_relay::PrivateRelay;
+pub use self::rpc_modules::RpcModules;
 pub use self::requests::{
 	TransactionRequest, FilledTransactionRequest, ConfirmationRequest, ConfirmationPayload, CallRequest,
 };
@@ -56,3 +72,12 @@ pub use self::work::submit_work_detail;
 pub fn to_url(address: &Option<::Host>) -> Option<String> {
 	address.as_ref().map(|host| (**host).to_owned())
 }
+
+/// Whether the transaction's activation condition is not met yet at given best block.
+pub fn is_scheduled(tx: &PendingTransaction, best_block_number: u64, best_block_timestamp: u64) -> bool {
+	match tx.condition {
+		Some(Condition::Number(number)) => number > best_block_number,
+		Some(Condition::Timestamp(timestamp)) => timestamp > best_block_timestamp,
+		None => false,
+	}
+}
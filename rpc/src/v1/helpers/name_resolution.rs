@@ -0,0 +1,239 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Resolution of names given in place of addresses in RPC parameters.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use client_traits::ChainInfo;
+use ethereum_types::{Address, H256};
+use jsonrpc_core::{self as core, Value};
+use parking_lot::Mutex;
+use registrar::RegistrarClient;
+use types::ids::BlockId;
+use v1::helpers::address_format::ADDRESS_PARAMS;
+
+#[cfg(any(test, feature = "accounts"))]
+use accounts::AccountProvider;
+
+/// Maximum number of resolutions cached for a single block.
+const MAX_CACHED_NAMES: usize = 1024;
+
+/// Resolves names to addresses.
+pub trait NameResolver: Send + Sync {
+	/// Returns the address `name` refers to as of given block.
+	fn resolve(&self, name: &str, block: BlockId) -> Option<Address>;
+}
+
+/// Resolves the names of local address book entries. Names shared by several entries aren't resolved.
+#[cfg(any(test, feature = "accounts"))]
+pub struct AddressBook(pub Arc<AccountProvider>);
+
+#[cfg(any(test, feature = "accounts"))]
+impl NameResolver for AddressBook {
+	fn resolve(&self, name: &str, _block: BlockId) -> Option<Address> {
+		let mut matching = self.0.addresses_info().into_iter()
+			.filter(|(_, meta)| meta.name == name)
+			.map(|(address, _)| address);
+		match (matching.next(), matching.next()) {
+			(Some(address), None) => Some(address),
+			_ => None,
+		}
+	}
+}
+
+/// Resolves names through the `A` records of the on-chain registry.
+pub struct Registry<C>(pub Arc<C>);
+
+impl<C: RegistrarClient + 'static> NameResolver for Registry<C> {
+	fn resolve(&self, name: &str, block: BlockId) -> Option<Address> {
+		self.0.get_address(name, block).unwrap_or_else(|e| {
+			debug!(target: "rpc", "Can't resolve {} through the registry: {}", name, e);
+			None
+		})
+	}
+}
+
+struct ResolvedNames {
+	block: H256,
+	names: HashMap<String, Option<Address>>,
+}
+
+/// Replaces names given in place of addresses in the parameters of known methods with the
+/// address the first resolver knowing the name returns. Resolutions are cached until the best block changes.
+pub struct NameResolution {
+	chain: Arc<dyn ChainInfo + Send + Sync>,
+	resolvers: Vec<Box<dyn NameResolver>>,
+	cache: Mutex<ResolvedNames>,
+}
+
+impl NameResolution {
+	/// Creates name resolution trying given resolvers in order, on top of the best block of `chain`.
+	pub fn new(chain: Arc<dyn ChainInfo + Send + Sync>, resolvers: Vec<Box<dyn NameResolver>>) -> Self {
+		NameResolution {
+			chain,
+			resolvers,
+			cache: Mutex::new(ResolvedNames {
+				block: H256::zero(),
+				names: HashMap::new(),
+			}),
+		}
+	}
+
+	/// Returns the address `name` refers to as of the best block.
+	pub fn resolve(&self, name: &str) -> Option<Address> {
+		let best_block = self.chain.chain_info().best_block_hash;
+		{
+			let mut cache = self.cache.lock();
+			if cache.block != best_block {
+				cache.block = best_block;
+				cache.names.clear();
+			}
+			if let Some(resolved) = cache.names.get(name) {
+				return *resolved;
+			}
+		}
+
+		let resolved = self.resolvers.iter()
+			.filter_map(|resolver| resolver.resolve(name, BlockId::Hash(best_block)))
+			.next();
+
+		let mut cache = self.cache.lock();
+		if cache.block == best_block && cache.names.len() < MAX_CACHED_NAMES {
+			cache.names.insert(name.to_owned(), resolved);
+		}
+		resolved
+	}
+
+	/// Replaces names in the address parameters of the calls in given request.
+	/// Names that don't resolve are left as they are.
+	pub fn resolve_request(&self, request: &mut core::Request) {
+		match *request {
+			core::Request::Single(ref mut call) => self.resolve_call(call),
+			core::Request::Batch(ref mut calls) => for call in calls {
+				self.resolve_call(call);
+			},
+		}
+	}
+
+	fn resolve_call(&self, call: &mut core::Call) {
+		let (method, params) = match *call {
+			core::Call::MethodCall(ref mut call) => (&call.method, &mut call.params),
+			core::Call::Notification(ref mut notification) => (&notification.method, &mut notification.params),
+			core::Call::Invalid { .. } => return,
+		};
+		let params = match *params {
+			core::Params::Array(ref mut params) => params,
+			_ => return,
+		};
+
+		for &(_, index, fields) in ADDRESS_PARAMS.iter().filter(|&&(name, _, _)| name == method.as_str()) {
+			let param = match params.get_mut(index) {
+				Some(param) => param,
+				None => continue,
+			};
+			if fields.is_empty() {
+				self.resolve_value(param);
+			} else if let Value::Object(ref mut object) = *param {
+				for field in fields {
+					if let Some(value) = object.get_mut(*field) {
+						self.resolve_value(value);
+					}
+				}
+			}
+		}
+	}
+
+	fn resolve_value(&self, value: &mut Value) {
+		let address = match *value {
+			Value::Array(ref mut values) => return values.iter_mut().for_each(|value| self.resolve_value(value)),
+			Value::String(ref name) if !name.starts_with("0x") => self.resolve(name),
+			_ => None,
+		};
+		if let Some(address) = address {
+			*value = Value::String(format!("{:?}", address));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Arc;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	use ethcore::test_helpers::{EachBlockWith, TestBlockChainClient};
+	use ethereum_types::Address;
+	use jsonrpc_core as core;
+	use serde_json;
+	use types::ids::BlockId;
+
+	use super::{NameResolution, NameResolver};
+
+	#[derive(Default)]
+	struct Names {
+		lookups: Arc<AtomicUsize>,
+	}
+
+	impl NameResolver for Names {
+		fn resolve(&self, name: &str, _block: BlockId) -> Option<Address> {
+			self.lookups.fetch_add(1, Ordering::SeqCst);
+			match name {
+				"alice.eth" => Some(Address::from_low_u64_be(0xa)),
+				_ => None,
+			}
+		}
+	}
+
+	#[test]
+	fn should_resolve_names_in_address_params() {
+		let resolution = NameResolution::new(Arc::new(TestBlockChainClient::new()), vec![Box::new(Names::default())]);
+		let mut request: core::Request = serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["alice.eth","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_call","params":[{"from":"bob.eth","to":"alice.eth","data":"alice.eth"}],"id":2},
+			{"jsonrpc":"2.0","method":"eth_getBlockByHash","params":["alice.eth",false],"id":3},
+			{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"address":["alice.eth","bob.eth"]}],"id":4}
+		]"#).unwrap();
+
+		resolution.resolve_request(&mut request);
+
+		let expected: core::Request = serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x000000000000000000000000000000000000000a","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_call","params":[{"from":"bob.eth","to":"0x000000000000000000000000000000000000000a","data":"alice.eth"}],"id":2},
+			{"jsonrpc":"2.0","method":"eth_getBlockByHash","params":["alice.eth",false],"id":3},
+			{"jsonrpc":"2.0","method":"eth_getLogs","params":[{"address":["0x000000000000000000000000000000000000000a","bob.eth"]}],"id":4}
+		]"#).unwrap();
+		assert_eq!(request, expected);
+	}
+
+	#[test]
+	fn should_cache_resolutions_until_best_block_changes() {
+		let client = Arc::new(TestBlockChainClient::new());
+		let names = Names::default();
+		let lookups = names.lookups.clone();
+		let resolution = NameResolution::new(client.clone(), vec![Box::new(names)]);
+
+		assert_eq!(resolution.resolve("alice.eth"), Some(Address::from_low_u64_be(0xa)));
+		assert_eq!(resolution.resolve("alice.eth"), Some(Address::from_low_u64_be(0xa)));
+		assert_eq!(resolution.resolve("bob.eth"), None);
+		assert_eq!(resolution.resolve("bob.eth"), None);
+		assert_eq!(lookups.load(Ordering::SeqCst), 2);
+
+		client.add_blocks(1, EachBlockWith::Nothing);
+		assert_eq!(resolution.resolve("alice.eth"), Some(Address::from_low_u64_be(0xa)));
+		assert_eq!(lookups.load(Ordering::SeqCst), 3);
+	}
+}
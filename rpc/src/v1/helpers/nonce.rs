@@ -49,6 +49,13 @@ impl Reservations {
 	///
 	/// The reserved nonce cannot be smaller than the minimal nonce.
 	pub fn reserve(&mut self, sender: Address, minimal: U256) -> Reserved {
+		self.reserve_range(sender, minimal, 1)
+	}
+
+	/// Reserve `count` consecutive nonces for particular address.
+	///
+	/// The first reserved nonce cannot be smaller than the minimal nonce.
+	pub fn reserve_range(&mut self, sender: Address, minimal: U256, count: usize) -> Reserved {
 		if self.nonces.len() + 1 > Self::CLEAN_AT {
 			self.nonces.retain(|_, v| !v.is_empty());
 		}
@@ -56,7 +63,7 @@ impl Reservations {
 		let executor = &self.executor;
 		self.nonces.entry(sender)
 			.or_insert_with(move || SenderReservations::new(executor.clone()))
-			.reserve_nonce(minimal)
+			.reserve_nonces(minimal, count)
 	}
 }
 
@@ -89,10 +96,16 @@ impl SenderReservations {
 	/// The caller can use `prospective_nonce` and perform some heavy computation anticipating
 	/// that the `prospective_nonce` will be equal to the one he will get.
 	pub fn reserve_nonce(&mut self, minimal: U256) -> Reserved {
+		self.reserve_nonces(minimal, 1)
+	}
+
+	/// Reserves a range of `count` consecutive prospective nonces, resolved together.
+	/// Nonces reserved afterwards are resolved once the whole range is finished.
+	pub fn reserve_nonces(&mut self, minimal: U256, count: usize) -> Reserved {
 		// Update prospective value
 		let dropped = self.dropped.swap(0, atomic::Ordering::SeqCst);
 		let prospective_value = cmp::max(minimal, self.prospective_value - dropped);
-		self.prospective_value = prospective_value + 1;
+		self.prospective_value = prospective_value + count;
 
 		let (next, rx) = oneshot::channel();
 		let next = Some(next);
@@ -106,6 +119,7 @@ impl SenderReservations {
 				next,
 				next_sent,
 				minimal,
+				count,
 				prospective_value,
 				executor,
 				dropped,
@@ -115,6 +129,7 @@ impl SenderReservations {
 				next,
 				next_sent,
 				minimal,
+				count,
 				prospective_value,
 				executor,
 				dropped,
@@ -138,6 +153,7 @@ pub struct Reserved {
 	next: Option<oneshot::Sender<U256>>,
 	next_sent: Arc<AtomicBool>,
 	minimal: U256,
+	count: usize,
 	prospective_value: U256,
 	executor: Executor,
 	dropped: Arc<AtomicUsize>,
@@ -168,6 +184,7 @@ impl Future for Reserved {
 
 		Ok(Async::Ready(Ready {
 			value,
+			count: self.count,
 			matches_prospective,
 			next: self.next.take(),
 			next_sent: self.next_sent.clone(),
@@ -180,7 +197,7 @@ impl Drop for Reserved {
 	fn drop(&mut self) {
 		if let Some(next) = self.next.take() {
 			let next_sent = self.next_sent.clone();
-			self.dropped.fetch_add(1, atomic::Ordering::SeqCst);
+			self.dropped.fetch_add(self.count, atomic::Ordering::SeqCst);
 			// If Reserved is dropped just pipe previous and next together.
 			let previous = mem::replace(&mut self.previous, Either::B(future::ok(U256::default())));
 			self.executor.spawn(
@@ -203,6 +220,7 @@ impl Drop for Reserved {
 #[derive(Debug)]
 pub struct Ready {
 	value: U256,
+	count: usize,
 	matches_prospective: bool,
 	next: Option<oneshot::Sender<U256>>,
 	next_sent: Arc<AtomicBool>,
@@ -212,7 +230,7 @@ pub struct Ready {
 impl Ready {
 	const RECV_PROOF: &'static str = "Receiver never dropped.";
 
-	/// Returns a value of the nonce.
+	/// Returns a value of the nonce, the first one of a reserved range.
 	pub fn value(&self) -> &U256 {
 		&self.value
 	}
@@ -224,17 +242,25 @@ impl Ready {
 
 	/// Marks this nonce as used.
 	/// Make sure to call that method after this nonce has been consumed.
-	pub fn mark_used(mut self) {
+	pub fn mark_used(self) {
+		let count = self.count;
+		self.mark_partially_used(count);
+	}
+
+	/// Marks only the first `used` nonces of a reserved range as used,
+	/// the remaining ones are handed out to the next reservation.
+	pub fn mark_partially_used(mut self, used: usize) {
 		let next = self.next.take().expect("Nonce can be marked as used only once; qed");
+		self.dropped.fetch_add(self.count.saturating_sub(used), atomic::Ordering::SeqCst);
 		self.next_sent.store(true, atomic::Ordering::SeqCst);
-		next.send(self.value + 1).expect(Self::RECV_PROOF);
+		next.send(self.value + cmp::min(used, self.count)).expect(Self::RECV_PROOF);
 	}
 }
 
 impl Drop for Ready {
 	fn drop(&mut self) {
 		if let Some(next) = self.next.take() {
-			self.dropped.fetch_add(1, atomic::Ordering::SeqCst);
+			self.dropped.fetch_add(self.count, atomic::Ordering::SeqCst);
 			self.next_sent.store(true, atomic::Ordering::SeqCst);
 			next.send(self.value).expect(Self::RECV_PROOF);
 		}
@@ -294,6 +320,34 @@ mod tests {
 		assert!(nonces.is_empty());
 	}
 
+	#[test]
+	fn should_reserve_a_range_of_nonces() {
+		let runtime = Runtime::with_thread_count(1);
+		let mut nonces = SenderReservations::new(runtime.executor());
+
+		let range = nonces.reserve_nonces(5.into(), 3);
+		let n1 = nonces.reserve_nonce(5.into());
+		let n2 = nonces.reserve_nonce(5.into());
+		assert_eq!(n1.prospective_value(), &U256::from(8));
+
+		// Only the first two nonces of the range were used.
+		let r = range.wait().unwrap();
+		assert_eq!(r.value(), &U256::from(5));
+		r.mark_partially_used(2);
+
+		let r = n1.wait().unwrap();
+		assert_eq!(r.value(), &U256::from(7));
+		assert!(!r.matches_prospective());
+		r.mark_used();
+
+		let r = n2.wait().unwrap();
+		assert_eq!(r.value(), &U256::from(8));
+		r.mark_used();
+
+		// The unused nonce is accounted for in the next prospective value.
+		assert_eq!(nonces.reserve_nonce(5.into()).prospective_value(), &U256::from(9));
+	}
+
 	#[test]
 	fn should_return_prospective_nonce() {
 		let runtime = Runtime::with_thread_count(1);
@@ -17,6 +17,7 @@
 //! Helper type with all filter state data.
 
 use std::{
+	cmp,
 	collections::{BTreeSet, HashSet, VecDeque},
 	sync::Arc,
 };
@@ -78,3 +79,21 @@ pub fn limit_logs(mut logs: Vec<Log>, limit: Option<usize>) -> Vec<Log> {
 		_ => logs,
 	}
 }
+
+/// Returns the logs without the last `n`
+pub fn skip_logs(mut logs: Vec<Log>, offset: Option<usize>) -> Vec<Log> {
+	let len = logs.len();
+	logs.truncate(len - cmp::min(len, offset.unwrap_or(0)));
+	logs
+}
+
+/// Skips the last `offset` pending logs, which follow all mined ones. Returns how many of the mined
+/// logs are left to skip.
+pub fn skip_pending_logs(pending: &mut Vec<Log>, offset: Option<usize>) -> Option<usize> {
+	offset.map(|offset| {
+		let skipped = cmp::min(offset, pending.len());
+		let len = pending.len();
+		pending.truncate(len - skipped);
+		offset - skipped
+	})
+}
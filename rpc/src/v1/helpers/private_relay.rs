@@ -0,0 +1,68 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Relay of transactions to an external endpoint instead of the public transaction pool.
+
+use std::collections::VecDeque;
+
+use ethereum_types::H256;
+use parking_lot::Mutex;
+use rustc_hex::ToHex;
+use types::BlockNumber;
+
+/// Number of relayed transactions kept track of.
+const MAX_TRACKED: usize = 1024;
+
+/// HTTP endpoint transactions are relayed to, along with the transactions sent to it.
+pub struct PrivateRelay {
+	endpoint: String,
+	relayed: Mutex<VecDeque<(H256, BlockNumber)>>,
+}
+
+impl PrivateRelay {
+	/// Creates a relay to given endpoint.
+	pub fn new(endpoint: String) -> Self {
+		PrivateRelay {
+			endpoint,
+			relayed: Mutex::new(VecDeque::new()),
+		}
+	}
+
+	/// URL of the relay endpoint.
+	pub fn endpoint(&self) -> &str {
+		&self.endpoint
+	}
+
+	/// JSON-RPC request submitting a raw transaction to the endpoint.
+	pub fn request_body(raw: &[u8]) -> String {
+		format!(r#"{{"jsonrpc":"2.0","method":"eth_sendRawTransaction","params":["0x{}"],"id":1}}"#, raw.to_hex())
+	}
+
+	/// Records a transaction relayed while `best_block` was the best block.
+	/// The oldest record is dropped once `MAX_TRACKED` are kept.
+	pub fn note_relayed(&self, hash: H256, best_block: BlockNumber) {
+		let mut relayed = self.relayed.lock();
+		if relayed.len() == MAX_TRACKED {
+			relayed.pop_front();
+		}
+		relayed.push_back((hash, best_block));
+	}
+
+	/// Relayed transactions, oldest first, with the best block at the time they were relayed.
+	pub fn relayed(&self) -> Vec<(H256, BlockNumber)> {
+		self.relayed.lock().iter().cloned().collect()
+	}
+}
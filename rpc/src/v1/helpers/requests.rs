@@ -121,6 +121,8 @@ pub enum ConfirmationPayload {
 	SignMessage(Address, H256),
 	/// Decrypt request
 	Decrypt(Address, Bytes),
+	/// Export the raw secret of an account
+	ExportSecret(Address),
 }
 
 impl ConfirmationPayload {
@@ -131,6 +133,7 @@ impl ConfirmationPayload {
 			ConfirmationPayload::EthSignMessage(ref address, _) => *address,
 			ConfirmationPayload::SignMessage(ref address, _) => *address,
 			ConfirmationPayload::Decrypt(ref address, _) => *address,
+			ConfirmationPayload::ExportSecret(ref address) => *address,
 		}
 	}
 }
@@ -0,0 +1,78 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime control over the modules and access rules of the HTTP JSON-RPC server.
+
+use parking_lot::RwLock;
+
+type Reload = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Hooks for replacing the RPC modules exposed over HTTP and the origins and hosts
+/// allowed to reach them.
+///
+/// The server registers its reload functions once it is running; until then
+/// every reconfiguration attempt fails.
+#[derive(Default)]
+pub struct RpcModules {
+	reload: RwLock<Option<Reload>>,
+	reload_cors: RwLock<Option<Reload>>,
+	reload_hosts: RwLock<Option<Reload>>,
+}
+
+impl RpcModules {
+	/// Register the function rebuilding the HTTP handler from a module list (`--jsonrpc-apis` syntax).
+	pub fn set_reload<F>(&self, f: F) where
+		F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+	{
+		*self.reload.write() = Some(Box::new(f));
+	}
+
+	/// Register the function replacing the allowed origins (`--jsonrpc-cors` syntax).
+	pub fn set_reload_cors<F>(&self, f: F) where
+		F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+	{
+		*self.reload_cors.write() = Some(Box::new(f));
+	}
+
+	/// Register the function replacing the allowed hosts (`--jsonrpc-hosts` syntax).
+	pub fn set_reload_hosts<F>(&self, f: F) where
+		F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+	{
+		*self.reload_hosts.write() = Some(Box::new(f));
+	}
+
+	/// Expose exactly the given modules from now on.
+	pub fn reload(&self, modules: &str) -> Result<(), String> {
+		call(&self.reload, modules)
+	}
+
+	/// Accept cross-origin requests from the given origins only from now on.
+	pub fn reload_cors(&self, cors: &str) -> Result<(), String> {
+		call(&self.reload_cors, cors)
+	}
+
+	/// Accept requests addressed to the given hosts only from now on.
+	pub fn reload_hosts(&self, hosts: &str) -> Result<(), String> {
+		call(&self.reload_hosts, hosts)
+	}
+}
+
+fn call(reload: &RwLock<Option<Reload>>, value: &str) -> Result<(), String> {
+	match *reload.read() {
+		Some(ref reload) => reload(value),
+		None => Err("HTTP JSON-RPC server is not running".into()),
+	}
+}
@@ -0,0 +1,250 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dumps of the account state at a given block, written to a file in the background.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Component, Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use client_traits::BlockChainClient;
+use rustc_hex::ToHex;
+use types::basic_account::BasicAccount;
+use types::ids::BlockId;
+use types::BlockNumber;
+
+/// Number of accounts read from the state at once.
+const CHUNK_SIZE: u64 = 1000;
+/// Pause after each chunk of accounts, so that a dump doesn't starve block import.
+const CHUNK_PAUSE: Duration = Duration::from_millis(20);
+/// Pause after each chunk of accounts while blocks are queued for import.
+const BUSY_PAUSE: Duration = Duration::from_millis(500);
+
+/// Output format of a state dump.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DumpFormat {
+	/// A JSON object with an entry per account.
+	Json,
+	/// A header line followed by a comma-separated line per account.
+	Csv,
+}
+
+impl FromStr for DumpFormat {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"json" => Ok(DumpFormat::Json),
+			"csv" => Ok(DumpFormat::Csv),
+			other => Err(format!("Unknown state dump format {}, expected json or csv.", other)),
+		}
+	}
+}
+
+/// Writes the accounts of the state at `block` to `out`, keyed by address when fat DB is on and by
+/// address hash otherwise. Returns the number of accounts written.
+pub fn write_state<C, W>(client: &C, block: BlockId, format: DumpFormat, out: &mut W) -> io::Result<usize> where
+	C: BlockChainClient + ?Sized,
+	W: Write,
+{
+	match format {
+		DumpFormat::Json => out.write_all(b"{")?,
+		DumpFormat::Csv => writeln!(out, "key,balance,nonce,code_hash,storage_root")?,
+	}
+
+	let mut after: Option<Vec<u8>> = None;
+	let mut written = 0;
+	loop {
+		let entries = client.list_account_entries(block, after.as_ref().map(Vec::as_slice), CHUNK_SIZE)
+			.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "State of the block is no longer available"))?;
+		let complete = (entries.len() as u64) < CHUNK_SIZE;
+
+		for (key, account) in entries {
+			write_account(out, format, written == 0, &key, &account)?;
+			written += 1;
+			after = Some(key);
+		}
+		if complete {
+			break;
+		}
+
+		trace!(target: "rpc", "Dumped {} accounts", written);
+		thread::sleep(if client.queue_info().is_empty() { CHUNK_PAUSE } else { BUSY_PAUSE });
+	}
+
+	if format == DumpFormat::Json {
+		out.write_all(b"\n}\n")?;
+	}
+	out.flush()?;
+	Ok(written)
+}
+
+fn write_account<W: Write>(out: &mut W, format: DumpFormat, first: bool, key: &[u8], account: &BasicAccount) -> io::Result<()> {
+	match format {
+		DumpFormat::Json => write!(
+			out,
+			"{}\n\t\"0x{}\": {{\"balance\": \"0x{:x}\", \"nonce\": \"0x{:x}\", \"codeHash\": \"0x{:x}\", \"storageRoot\": \"0x{:x}\"}}",
+			if first { "" } else { "," },
+			key.to_hex(),
+			account.balance,
+			account.nonce,
+			account.code_hash,
+			account.storage_root,
+		),
+		DumpFormat::Csv => writeln!(
+			out,
+			"0x{},0x{:x},0x{:x},0x{:x},0x{:x}",
+			key.to_hex(),
+			account.balance,
+			account.nonce,
+			account.code_hash,
+			account.storage_root,
+		),
+	}
+}
+
+/// Keeps the state of a block from being pruned until dropped.
+pub struct StatePin<C: BlockChainClient + ?Sized> {
+	client: Arc<C>,
+	number: BlockNumber,
+}
+
+impl<C: BlockChainClient + ?Sized> StatePin<C> {
+	/// Pins the state of block `number`.
+	pub fn new(client: Arc<C>, number: BlockNumber) -> Self {
+		client.pin_state(number);
+		StatePin { client, number }
+	}
+}
+
+impl<C: BlockChainClient + ?Sized> Drop for StatePin<C> {
+	fn drop(&mut self) {
+		self.client.unpin_state(self.number);
+	}
+}
+
+/// Runs state dumps in the background, one at a time, writing them to files in a single directory.
+pub struct StateDumps {
+	dir: PathBuf,
+	running: Arc<AtomicBool>,
+}
+
+impl StateDumps {
+	/// Creates state dumps written to `dir`. The directory is created on the first dump.
+	pub fn new(dir: PathBuf) -> Self {
+		StateDumps {
+			dir,
+			running: Default::default(),
+		}
+	}
+
+	/// Path of the dump file `name`, `None` unless it's a plain file name.
+	pub fn path(&self, name: &str) -> Option<PathBuf> {
+		let mut components = Path::new(name).components();
+		match (components.next(), components.next()) {
+			(Some(Component::Normal(file)), None) => Some(self.dir.join(file)),
+			_ => None,
+		}
+	}
+
+	/// Starts dumping the state at `block`, pinned by `pin` until the dump completes, to a new file at `path`.
+	/// Fails if another dump is running or the file exists.
+	pub fn start<C>(&self, pin: StatePin<C>, block: BlockId, path: PathBuf, format: DumpFormat) -> Result<(), String> where
+		C: BlockChainClient + 'static,
+	{
+		if self.running.compare_and_swap(false, true, Ordering::SeqCst) {
+			return Err("Another state dump is in progress.".into());
+		}
+
+		let running = self.running.clone();
+		let dump = fs::create_dir_all(&self.dir)
+			.and_then(|_| OpenOptions::new().write(true).create_new(true).open(&path))
+			.map_err(|e| format!("Can't create {}: {}", path.display(), e))
+			.and_then(|file| thread::Builder::new()
+				.name("state-dump".into())
+				.spawn(move || {
+					match write_state(&*pin.client, block, format, &mut BufWriter::new(file)) {
+						Ok(count) => info!(target: "rpc", "Dumped {} accounts to {}", count, path.display()),
+						Err(e) => warn!(target: "rpc", "State dump to {} failed: {}", path.display(), e),
+					}
+					drop(pin);
+					running.store(false, Ordering::SeqCst);
+				})
+				.map_err(|e| format!("Can't start state dump: {}", e))
+			);
+
+		if dump.is_err() {
+			self.running.store(false, Ordering::SeqCst);
+		}
+		dump.map(|_| ())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ethereum_types::{H256, U256};
+	use types::basic_account::BasicAccount;
+
+	use super::{DumpFormat, StateDumps, write_account};
+
+	fn account() -> BasicAccount {
+		BasicAccount {
+			nonce: U256::from(1),
+			balance: U256::from(1000),
+			storage_root: H256::from_low_u64_be(2),
+			code_hash: H256::from_low_u64_be(3),
+			code_version: U256::zero(),
+		}
+	}
+
+	#[test]
+	fn should_format_accounts() {
+		let mut json = Vec::new();
+		write_account(&mut json, DumpFormat::Json, true, &[0xaa, 0x01], &account()).unwrap();
+		write_account(&mut json, DumpFormat::Json, false, &[0xaa, 0x02], &account()).unwrap();
+		assert_eq!(String::from_utf8(json).unwrap(), format!(
+			"\n\t\"0xaa01\": {{\"balance\": \"0x3e8\", \"nonce\": \"0x1\", \"codeHash\": \"0x{:x}\", \"storageRoot\": \"0x{:x}\"}},\
+			\n\t\"0xaa02\": {{\"balance\": \"0x3e8\", \"nonce\": \"0x1\", \"codeHash\": \"0x{:x}\", \"storageRoot\": \"0x{:x}\"}}",
+			H256::from_low_u64_be(3), H256::from_low_u64_be(2), H256::from_low_u64_be(3), H256::from_low_u64_be(2),
+		));
+
+		let mut csv = Vec::new();
+		write_account(&mut csv, DumpFormat::Csv, true, &[0xaa, 0x01], &account()).unwrap();
+		assert_eq!(String::from_utf8(csv).unwrap(), format!("0xaa01,0x3e8,0x1,0x{:x},0x{:x}\n", H256::from_low_u64_be(3), H256::from_low_u64_be(2)));
+	}
+
+	#[test]
+	fn should_only_accept_file_names() {
+		let dumps = StateDumps::new("dumps".into());
+		assert_eq!(dumps.path("state.json"), Some("dumps/state.json".into()));
+		assert_eq!(dumps.path("/tmp/state.json"), None);
+		assert_eq!(dumps.path("../state.json"), None);
+		assert_eq!(dumps.path("dir/state.json"), None);
+		assert_eq!(dumps.path(""), None);
+	}
+
+	#[test]
+	fn should_parse_formats() {
+		assert_eq!("json".parse(), Ok(DumpFormat::Json));
+		assert_eq!("csv".parse(), Ok(DumpFormat::Csv));
+		assert!("xml".parse::<DumpFormat>().is_err());
+	}
+}
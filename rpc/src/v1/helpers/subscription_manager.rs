@@ -27,6 +27,7 @@ use jsonrpc_core::{self as core, MetaIoHandler};
 use jsonrpc_pubsub::SubscriptionId;
 
 use v1::helpers::Subscribers;
+use v1::helpers::address_format::checksum_result;
 use v1::metadata::Metadata;
 
 #[derive(Debug)]
@@ -102,6 +103,7 @@ impl<S: core::Middleware<Metadata>> GenericPollManager<S> {
 
 			let last_result = subscription.last_result.clone();
 			let sender = subscription.sink.clone();
+			let method = subscription.method.clone();
 
 			let result = result.and_then(move |response| {
 				// quick check if the subscription is still valid
@@ -116,7 +118,10 @@ impl<S: core::Middleware<Metadata>> GenericPollManager<S> {
 					*last_result = Some(output.clone());
 
 					let send = match output {
-						core::Output::Success(core::Success { result, .. }) => Ok(result),
+						core::Output::Success(core::Success { mut result, .. }) => {
+							checksum_result(&method, &mut result);
+							Ok(result)
+						},
 						core::Output::Failure(core::Failure { error, .. }) => Err(error),
 					};
 					Either::A(sender.send(send).map(|_| ()).map_err(|_| ()))
@@ -16,19 +16,64 @@
 
 //! Debug APIs RPC implementation
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use client_traits::BlockChainClient;
+use account_state::state::StateInfo;
+use client_traits::{BlockChainClient, StateClient};
+use ethcore::client::Call;
+use ethereum_types::U256;
+use evm::interpreter::DebugSession;
+use machine::executed::Executed;
+use parking_lot::Mutex;
 use types::header::Header;
 use types::transaction::LocalizedTransaction;
 
 use jsonrpc_core::Result;
+use v1::helpers::{errors, fake_sign};
+use v1::impls::traces::to_call_analytics;
 use v1::traits::Debug;
-use v1::types::{Block, Bytes, RichBlock, BlockTransactions, Transaction};
+use v1::types::{
+	Block, BlockNumber, Breakpoints, Bytes, CallRequest, DebugSessionState, ReplayedBlock, RichBlock,
+	BlockTransactions, TraceOptions, Transaction, block_number_to_id,
+};
+
+/// Maximum number of step debugger sessions, each holds a thread.
+const MAX_SESSIONS: usize = 4;
+/// Sessions unused for this long are stopped.
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+/// How long to wait for a debugged call to pause or finish before reporting its state.
+const PAUSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Outcome of a debugged call, set once it finishes.
+type SessionResult = Arc<Mutex<Option<::std::result::Result<Executed, String>>>>;
+
+/// Step debugger session executing a call on its own thread.
+struct Session {
+	debugger: Arc<DebugSession>,
+	result: SessionResult,
+	last_used: Instant,
+}
+
+impl Drop for Session {
+	fn drop(&mut self) {
+		// lets the call run to completion so its thread exits
+		self.debugger.detach();
+	}
+}
+
+#[derive(Default)]
+struct Sessions {
+	next_id: U256,
+	sessions: HashMap<U256, Session>,
+}
 
 /// Debug rpc implementation.
 pub struct DebugClient<C> {
 	client: Arc<C>,
+	sessions: Mutex<Sessions>,
 }
 
 impl<C> DebugClient<C> {
@@ -36,11 +81,43 @@ impl<C> DebugClient<C> {
 	pub fn new(client: Arc<C>) -> Self {
 		Self {
 			client,
+			sessions: Default::default(),
 		}
 	}
+
+	/// Marks a session used and returns its debugger and result slot.
+	fn session(&self, id: U256) -> Result<(Arc<DebugSession>, SessionResult)> {
+		let mut sessions = self.sessions.lock();
+		let session = sessions.sessions.get_mut(&id).ok_or_else(|| errors::invalid_params("Unknown debug session", id))?;
+		session.last_used = Instant::now();
+		Ok((session.debugger.clone(), session.result.clone()))
+	}
+
+	/// Waits for the call of a session to pause or finish and reports its state.
+	fn wait_state(&self, id: U256) -> Result<DebugSessionState> {
+		let (debugger, result) = self.session(id)?;
+		let paused = debugger.wait_paused(PAUSE_TIMEOUT);
+		let result = result.lock();
+		Ok(DebugSessionState {
+			paused: paused.map(Into::into),
+			finished: result.is_some(),
+			output: match *result {
+				Some(Ok(ref executed)) => Some(executed.output.clone().into()),
+				_ => None,
+			},
+			error: match *result {
+				Some(Ok(ref executed)) => executed.exception.as_ref().map(ToString::to_string),
+				Some(Err(ref error)) => Some(error.clone()),
+				None => None,
+			},
+		})
+	}
 }
 
-impl<C: BlockChainClient + 'static> Debug for DebugClient<C> {
+impl<C, S> Debug for DebugClient<C> where
+	S: StateInfo + 'static,
+	C: BlockChainClient + StateClient<State=S> + Call<State=S> + Send + Sync + 'static,
+{
 	fn bad_blocks(&self) -> Result<Vec<RichBlock>> {
 		fn cast<O, T: Copy + Into<O>>(t: &T) -> O {
 			(*t).into()
@@ -90,6 +167,88 @@ impl<C: BlockChainClient + 'static> Debug for DebugClient<C> {
 			}
 		}).collect())
 	}
+
+	fn replay_block(&self, block_number: BlockNumber, flags: TraceOptions) -> Result<ReplayedBlock> {
+		if block_number == BlockNumber::Pending {
+			return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ()));
+		}
+
+		self.client.replay_block(block_number_to_id(block_number), to_call_analytics(flags))
+			.map(Into::into)
+			.map_err(errors::call)
+	}
+
+	fn start_session(&self, request: CallRequest, block: Option<BlockNumber>) -> Result<U256> {
+		let block = block.unwrap_or_default();
+		if block == BlockNumber::Pending {
+			return Err(errors::invalid_params("`BlockNumber::Pending` is not supported", ()));
+		}
+		let id = block_number_to_id(block);
+
+		let signed = fake_sign::sign_call(CallRequest::into(request))?;
+		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?
+			.decode().map_err(errors::decode)?;
+
+		let mut sessions = self.sessions.lock();
+		sessions.sessions.retain(|_, session| session.last_used.elapsed() < SESSION_IDLE_TIMEOUT);
+		if sessions.sessions.len() >= MAX_SESSIONS {
+			return Err(errors::request_rejected_param_limit(MAX_SESSIONS as u64, "debug sessions"));
+		}
+
+		let debugger = Arc::new(DebugSession::paused_at_start());
+		let result = Arc::new(Mutex::new(None));
+		let client = self.client.clone();
+		let (observer, slot) = (debugger.clone(), result.clone());
+		thread::Builder::new()
+			.name("debug-session".into())
+			.spawn(move || {
+				// the state is not `Send`, so it is opened on the session's own thread
+				let executed = match client.state_at(id) {
+					Some(mut state) => client.call_observed(&signed, &mut state, &header, observer.clone())
+						.map_err(|e| e.to_string()),
+					None => Err("Couldn't find the block's state in the chain".into()),
+				};
+				*slot.lock() = Some(executed);
+				// wakes up any waiters
+				observer.detach();
+			})
+			.map_err(|e| errors::internal("Couldn't start debug session thread", e))?;
+
+		let session_id = sessions.next_id;
+		sessions.next_id = session_id + U256::one();
+		sessions.sessions.insert(session_id, Session {
+			debugger,
+			result,
+			last_used: Instant::now(),
+		});
+		Ok(session_id)
+	}
+
+	fn set_breakpoints(&self, id: U256, breakpoints: Breakpoints) -> Result<bool> {
+		let breakpoints = breakpoints.into_evm()
+			.map_err(|opcode| errors::invalid_params("Unknown instruction", opcode))?;
+		let (debugger, _) = self.session(id)?;
+		debugger.set_breakpoints(breakpoints);
+		Ok(true)
+	}
+
+	fn session_state(&self, id: U256) -> Result<DebugSessionState> {
+		self.wait_state(id)
+	}
+
+	fn step(&self, id: U256) -> Result<DebugSessionState> {
+		self.session(id)?.0.step();
+		self.wait_state(id)
+	}
+
+	fn resume(&self, id: U256) -> Result<DebugSessionState> {
+		self.session(id)?.0.resume();
+		self.wait_state(id)
+	}
+
+	fn stop_session(&self, id: U256) -> Result<bool> {
+		Ok(self.sessions.lock().sessions.remove(&id).is_some())
+	}
 }
 
 fn serialize<T: ::serde::Serialize>(t: &T) -> String {
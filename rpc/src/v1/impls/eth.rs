@@ -45,16 +45,17 @@ use types::{
 };
 
 use jsonrpc_core::{BoxFuture, Result};
-use jsonrpc_core::futures::future;
+use jsonrpc_core::futures::{future, Future};
 
-use v1::helpers::{self, errors, limit_logs, fake_sign};
+use v1::helpers::{self, errors, limit_logs, skip_pending_logs, fake_sign, AbiRegistry};
+use v1::helpers::call_cache::{CallCache, CallKey};
 use v1::helpers::deprecated::{self, DeprecationNotice};
 use v1::helpers::dispatch::{FullDispatcher, default_gas_price};
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, Bytes, SyncStatus, SyncInfo,
 	Transaction, CallRequest, Index, Filter, Log, Receipt, Work, EthAccount, StorageProof,
-	block_number_to_id
+	StateOverride, block_number_to_id, into_state_override,
 };
 use v1::metadata::Metadata;
 
@@ -71,6 +72,8 @@ pub struct EthClientOptions {
 	pub send_block_number_in_get_work: bool,
 	/// Gas Price Percentile used as default gas price.
 	pub gas_price_percentile: usize,
+	/// Number of recent blocks sampled for the default gas price.
+	pub gas_price_blocks: usize,
 	/// Return 'null' instead of an error if ancient block sync is still in
 	/// progress and the block information requested could not be found.
 	pub allow_missing_blocks: bool,
@@ -97,6 +100,7 @@ impl Default for EthClientOptions {
 			allow_pending_receipt_query: true,
 			send_block_number_in_get_work: true,
 			gas_price_percentile: 50,
+			gas_price_blocks: 100,
 			allow_missing_blocks: false,
 			allow_experimental_rpcs: false,
 			no_ancient_blocks: false,
@@ -118,7 +122,9 @@ pub struct EthClient<C, SN: ?Sized, S: ?Sized, M, EM> where
 	accounts: Arc<dyn Fn() -> Vec<Address> + Send + Sync>,
 	miner: Arc<M>,
 	external_miner: Arc<EM>,
+	abi_registry: Arc<AbiRegistry>,
 	seed_compute: Mutex<SeedHashCompute>,
+	call_cache: Arc<CallCache>,
 	options: EthClientOptions,
 	deprecation_notice: DeprecationNotice,
 }
@@ -160,10 +166,20 @@ pub fn base_logs<C, M, T: StateInfo + 'static> (client: &C, miner: &M, filter: F
 	C: miner::BlockChainClient + BlockChainClient + StateClient<State=T> + Call<State=T>,
 	M: MinerService<State=T> {
 	let include_pending = filter.to_block == Some(BlockNumber::Pending);
-	let filter: EthcoreFilter = match filter.try_into() {
+	let mut filter: EthcoreFilter = match filter.try_into() {
 		Ok(value) => value,
 		Err(err) => return Box::new(future::err(err)),
 	};
+
+	let pending = if include_pending {
+		let best_block = client.chain_info().best_block_number;
+		let mut pending = pending_logs(&*miner, best_block, &filter);
+		filter.offset = skip_pending_logs(&mut pending, filter.offset);
+		pending
+	} else {
+		Vec::new()
+	};
+
 	let mut logs = match client.logs(filter.clone()) {
 		Ok(logs) => logs
 			.into_iter()
@@ -171,12 +187,7 @@ pub fn base_logs<C, M, T: StateInfo + 'static> (client: &C, miner: &M, filter: F
 			.collect::<Vec<Log>>(),
 		Err(id) => return Box::new(future::err(errors::filter_block_not_found(id))),
 	};
-
-	if include_pending {
-		let best_block = client.chain_info().best_block_number;
-		let pending = pending_logs(&*miner, best_block, &filter);
-		logs.extend(pending);
-	}
+	logs.extend(pending);
 
 	let logs = limit_logs(logs, filter.limit);
 
@@ -199,6 +210,8 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 		accounts: &Arc<dyn Fn() -> Vec<Address> + Send + Sync>,
 		miner: &Arc<M>,
 		em: &Arc<EM>,
+		abi_registry: &Arc<AbiRegistry>,
+		call_cache: &Arc<CallCache>,
 		options: EthClientOptions
 	) -> Self {
 		EthClient {
@@ -208,7 +221,9 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 			miner: miner.clone(),
 			accounts: accounts.clone(),
 			external_miner: em.clone(),
+			abi_registry: abi_registry.clone(),
 			seed_compute: Mutex::new(SeedHashCompute::default()),
+			call_cache: call_cache.clone(),
 			options,
 			deprecation_notice: Default::default(),
 		}
@@ -357,7 +372,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 	fn uncle(&self, id: PendingUncleId) -> Result<Option<RichBlock>> {
 		let client = &self.client;
 
-		let (uncle, parent_difficulty, extra) = match id {
+		let (uncle, extra) = match id {
 			PendingUncleId { id: PendingOrBlock::Pending, position } => {
 				let info = self.client.chain_info();
 
@@ -371,41 +386,26 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 					None => return Ok(None),
 				};
 
-				let difficulty = {
-					let latest_difficulty = self.client.block_total_difficulty(BlockId::Latest).expect("blocks in chain have details; qed");
-					let pending_difficulty = self.miner.pending_block_header(info.best_block_number).map(|header| *header.difficulty());
-
-					if let Some(difficulty) = pending_difficulty {
-						difficulty + latest_difficulty
-					} else {
-						latest_difficulty
-					}
-				};
-
-				let extra = self.client.engine().extra_info(&pending_block.header);
+				let extra = self.client.engine().extra_info(&uncle);
 
-				(uncle, difficulty, extra)
+				(uncle, extra)
 			},
 
 			PendingUncleId { id: PendingOrBlock::Block(block_id), position } => {
-				let uncle_id = UncleId { block: block_id, position };
-
-				let uncle = match client.uncle(uncle_id) {
-					Some(hdr) => match hdr.decode() {
-						Ok(h) => h,
-						Err(e) => return Err(errors::decode(e))
-					},
+				let localized = match client.localized_uncle(UncleId { block: block_id, position }) {
+					Some(localized) => localized,
 					None => { return Ok(None); }
 				};
 
-				let parent_difficulty = match client.block_total_difficulty(BlockId::Hash(*uncle.parent_hash())) {
-					Some(difficulty) => difficulty,
-					None => { return Ok(None); }
+				let uncle = match localized.header.decode() {
+					Ok(h) => h,
+					Err(e) => return Err(errors::decode(e))
 				};
 
+				let uncle_id = UncleId { block: BlockId::Hash(localized.block_hash), position: localized.position };
 				let extra = client.uncle_extra_info(uncle_id).expect(EXTRA_INFO_PROOF);
 
-				(uncle, parent_difficulty, extra)
+				(uncle, extra)
 			}
 		};
 
@@ -429,7 +429,8 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 				logs_bloom: Some(*uncle.log_bloom()),
 				timestamp: uncle.timestamp().into(),
 				difficulty: *uncle.difficulty(),
-				total_difficulty: Some(uncle.difficulty() + parent_difficulty),
+				// Uncles are not part of the canonical chain, so they have no total difficulty.
+				total_difficulty: None,
 				receipts_root: *uncle.receipts_root(),
 				extra_data: uncle.extra_data().clone().into(),
 				seal_fields: uncle.seal().iter().cloned().map(Into::into).collect(),
@@ -471,19 +472,29 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T> EthClient<C, SN, S, M, EM> where
 	/// blocks state&header.
 	fn pending_state_and_header_with_fallback(&self) -> (T, Header) {
 		let best_block_number = self.client.chain_info().best_block_number;
-		let (maybe_state, maybe_header) =
-			self.miner.pending_state(best_block_number).map_or_else(|| (None, None),|s| {
-				(Some(s), self.miner.pending_block_header(best_block_number))
-			});
-
-		match (maybe_state, maybe_header) {
-			(Some(state), Some(header)) => (state, header),
-			_ => {
+		match self.miner.pending_state_and_header(best_block_number) {
+			Some((state, header)) => (state, header),
+			None => {
 				warn!("Falling back to \"Latest\"");
 				self.client.latest_state_and_header()
 			}
 		}
 	}
+
+	/// Get the nonce and balance of `address` in the best pending block, with the local transactions
+	/// still waiting in the queue applied on top.
+	fn pending_account(&self, address: &Address) -> Option<(U256, U256)> {
+		let info = self.client.chain_info();
+		let nonce = self.miner
+			.pending_state(info.best_block_number)
+			.and_then(|s| s.nonce(address).ok())
+			.or_else(|| {
+				warn!("Fallback to `BlockId::Latest`");
+				self.client.nonce(address, BlockId::Latest)
+			})?;
+		let balance = self.client.balance(address, self.get_state(BlockNumber::Pending))?;
+		Some(self.miner.local_pending_account(address, nonce, balance))
+	}
 }
 
 pub fn pending_logs<M>(miner: &M, best_block: EthBlockNumber, filter: &EthcoreFilter) -> Vec<Log> where M: MinerService {
@@ -602,7 +613,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn gas_price(&self) -> BoxFuture<U256> {
-		Box::new(future::ok(default_gas_price(&*self.client, &*self.miner, self.options.gas_price_percentile)))
+		Box::new(future::ok(default_gas_price(&*self.client, &*self.miner, self.options.gas_price_percentile, self.options.gas_price_blocks)))
 	}
 
 	fn accounts(&self) -> Result<Vec<H160>> {
@@ -620,7 +631,11 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		let num = num.unwrap_or_default();
 
 		try_bf!(check_known(&*self.client, num.clone()));
-		let res = match self.client.balance(&address, self.get_state(num)) {
+		let balance = match num {
+			BlockNumber::Pending => self.pending_account(&address).map(|(_, balance)| balance),
+			num => self.client.balance(&address, self.get_state(num)),
+		};
+		let res = match balance {
 			Some(balance) => Ok(balance),
 			None => Err(errors::state_pruned()),
 		};
@@ -690,17 +705,8 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 				Ok(self.miner.next_nonce(&*self.client, &address))
 			}
 			BlockNumber::Pending => {
-				let info = self.client.chain_info();
-				let nonce = self.miner
-					.pending_state(info.best_block_number)
-					.and_then(|s| s.nonce(&address).ok())
-					.or_else(|| {
-						warn!("Fallback to `BlockId::Latest`");
-						self.client.nonce(&address, BlockId::Latest)
-					});
-
-				match nonce {
-					Some(nonce) => Ok(nonce),
+				match self.pending_account(&address) {
+					Some((nonce, _)) => Ok(nonce),
 					None => Err(errors::database("latest nonce missing"))
 				}
 			},
@@ -824,15 +830,20 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn transaction_receipt(&self, hash: H256) -> BoxFuture<Option<Receipt>> {
+		let decode = |mut receipt: Receipt| {
+			self.abi_registry.decode_logs(&mut receipt.logs);
+			receipt
+		};
+
 		if self.options.allow_pending_receipt_query {
 			let best_block = self.client.chain_info().best_block_number;
 			if let Some(receipt) = self.miner.pending_receipt(best_block, &hash) {
-				return Box::new(future::ok(Some(receipt.into())));
+				return Box::new(future::ok(Some(decode(receipt.into()))));
 			}
 		}
 
 		let receipt = self.client.transaction_receipt(TransactionId::Hash(hash));
-		let result = Ok(receipt.map(Into::into))
+		let result = Ok(receipt.map(Into::into).map(decode))
 			.and_then(errors::check_block_gap(&*self.client, self.options));
 		Box::new(future::done(result))
 	}
@@ -870,7 +881,11 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 	}
 
 	fn logs(&self, filter: Filter) -> BoxFuture<Vec<Log>> {
-		base_logs(&*self.client, &*self.miner, filter)
+		let abi_registry = self.abi_registry.clone();
+		Box::new(base_logs(&*self.client, &*self.miner, filter).map(move |mut logs| {
+			abi_registry.decode_logs(&mut logs);
+			logs
+		}))
 	}
 
 	fn work(&self, no_new_work_timeout: Option<u64>) -> Result<Work> {
@@ -959,7 +974,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, request: CallRequest, num: Option<BlockNumber>) -> BoxFuture<Bytes> {
+	fn call(&self, request: CallRequest, num: Option<BlockNumber>, state_override: Option<StateOverride>) -> BoxFuture<Bytes> {
 		let request = CallRequest::into(request);
 		let signed = try_bf!(fake_sign::sign_call(request));
 
@@ -987,7 +1002,19 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 				(state, header)
 			};
 
-		let result = self.client.call(&signed, Default::default(), &mut state, &header);
+		// Pending state and overridden state are not determined by the block hash alone.
+		let cache_key = match num {
+			BlockNumber::Pending => None,
+			_ if state_override.is_some() || !self.call_cache.is_enabled() => None,
+			_ => Some(CallKey::new(header.hash(), &signed)),
+		};
+		let best_block_hash = self.client.chain_info().best_block_hash;
+		if let Some(output) = cache_key.as_ref().and_then(|key| self.call_cache.get(&best_block_hash, key)) {
+			return Box::new(future::ok(output.into()));
+		}
+
+		let state_override = state_override.map(into_state_override);
+		let result = self.client.call(&signed, Default::default(), &mut state, &header, state_override.as_ref());
 
 		Box::new(future::done(result
 			.map_err(errors::call)
@@ -997,7 +1024,12 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> Eth for EthClient<
 					None => Ok(executed)
 				}
 			})
-			.map(|b| b.output.into())
+			.map(|b| {
+				if let Some(key) = cache_key {
+					self.call_cache.insert(&best_block_hash, key, b.output.clone());
+				}
+				b.output.into()
+			})
 		))
 	}
 
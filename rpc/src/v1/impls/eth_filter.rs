@@ -33,7 +33,7 @@ use jsonrpc_core::futures::{future, Future};
 use jsonrpc_core::futures::future::Either;
 use v1::traits::EthFilter;
 use v1::types::{BlockNumber, Index, Filter, FilterChanges, Log};
-use v1::helpers::{errors, SyncPollFilter, PollFilter, PollManager, limit_logs};
+use v1::helpers::{errors, SyncPollFilter, PollFilter, PollManager, limit_logs, skip_pending_logs};
 use v1::impls::eth::pending_logs;
 
 /// Something which provides data that can be filtered over.
@@ -233,6 +233,8 @@ impl<T: Filterable + Send + Sync + 'static> EthFilter for T {
 				let current_number = self.best_block_number();
 
 				let mut filter = filter.clone();
+				// new logs are never skipped
+				filter.offset = None;
 
 				// retrieve reorg logs
 				let (mut reorg, reorg_len) = last_block_hash.map_or_else(|| (Vec::new(), 0), |h| self.removed_logs(h, &filter));
@@ -279,7 +281,7 @@ impl<T: Filterable + Send + Sync + 'static> EthFilter for T {
 	}
 
 	fn filter_logs(&self, index: Index) -> BoxFuture<Vec<Log>> {
-		let (filter, include_pending) = {
+		let (mut filter, include_pending) = {
 			let mut polls = self.polls().lock();
 
 			match polls.poll(&index.value()).and_then(|f| f.modify(|filter| match *filter {
@@ -295,7 +297,9 @@ impl<T: Filterable + Send + Sync + 'static> EthFilter for T {
 		// fetch pending logs.
 		let pending = if include_pending {
 			let best_block = self.best_block_number();
-			self.pending_logs(best_block, &filter)
+			let mut pending = self.pending_logs(best_block, &filter);
+			filter.offset = skip_pending_logs(&mut pending, filter.offset);
+			pending
 		} else {
 			Vec::new()
 		};
@@ -332,7 +332,9 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 			},
 			(pubsub::Kind::Logs, Some(pubsub::Params::Logs(filter))) => {
 				match filter.try_into() {
-					Ok(filter) => {
+					Ok(mut filter) => {
+						// new logs are never skipped
+						filter.offset = None;
 						self.logs_subscribers.write().push(subscriber, filter);
 						return;
 					},
@@ -38,13 +38,14 @@ use types::filter::Filter as EthcoreFilter;
 use types::ids::BlockId;
 
 use v1::impls::eth_filter::Filterable;
-use v1::helpers::{errors, limit_logs, SyncPollFilter, PollManager};
+use v1::helpers::{errors, limit_logs, skip_logs, SyncPollFilter, PollManager};
 use v1::helpers::deprecated::{self, DeprecationNotice};
 use v1::helpers::light_fetch::{self, LightFetch};
 use v1::traits::Eth;
 use v1::types::{
 	RichBlock, Block, BlockTransactions, BlockNumber, LightBlockNumber, Bytes, SyncStatus as RpcSyncStatus,
-	SyncInfo as RpcSyncInfo, Transaction, CallRequest, Index, Filter, Log, Receipt, Work, EthAccount
+	SyncInfo as RpcSyncInfo, Transaction, CallRequest, Index, Filter, Log, Receipt, Work, EthAccount,
+	StateOverride,
 };
 use v1::metadata::Metadata;
 
@@ -400,7 +401,11 @@ where
 		self.send_raw_transaction(raw)
 	}
 
-	fn call(&self, req: CallRequest, num: Option<BlockNumber>) -> BoxFuture<Bytes> {
+	fn call(&self, req: CallRequest, num: Option<BlockNumber>, state_override: Option<StateOverride>) -> BoxFuture<Bytes> {
+		if state_override.is_some() {
+			return Box::new(future::err(errors::light_unimplemented(None)));
+		}
+
 		Box::new(self.fetcher().proved_read_only_execution(req, num, self.transaction_queue.clone()).and_then(|res| {
 			match res {
 				Ok(exec) => Ok(exec.output.into()),
@@ -556,7 +561,8 @@ where
 	}
 
 	fn logs(&self, filter: EthcoreFilter) -> BoxFuture<Vec<Log>> {
-		Box::new(self.fetcher().logs(filter)) as BoxFuture<_>
+		let offset = filter.offset;
+		Box::new(self.fetcher().logs(filter).map(move |logs| skip_logs(logs, offset))) as BoxFuture<_>
 	}
 
 	fn pending_logs(&self, _block_number: u64, _filter: &EthcoreFilter) -> Vec<Log> {
@@ -33,7 +33,7 @@ use ethcore_logger::RotatingLogger;
 use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_core::futures::{future, Future};
 use light::on_demand::OnDemandRequester;
-use v1::helpers::{self, errors, ipfs, NetworkSettings, verify_signature};
+use v1::helpers::{self, errors, ipfs, limit_logs, skip_logs, NetworkSettings, verify_signature};
 use v1::helpers::external_signer::{SignerService, SigningQueue};
 use v1::helpers::dispatch::LightDispatcher;
 use v1::helpers::light_fetch::{LightFetch, light_all_transactions};
@@ -43,10 +43,10 @@ use v1::types::{
 	Bytes, CallRequest,
 	Peers, Transaction, RpcSettings, Histogram,
 	TransactionStats, LocalTransactionStatus,
-	LightBlockNumber, ChainStatus, Receipt,
+	LightBlockNumber, ChainStatus, DbStats, Reorg, Receipt, MinerStats, CallCacheStats,
 	BlockNumber, ConsensusCapability, VersionInfo,
 	OperationsInfo, Header, RichHeader, RecoveredAccount,
-	Log, Filter,
+	Log, Filter, DecodedCall, Schedule, TransactionInclusion,
 };
 use Host;
 use v1::helpers::errors::light_unimplemented;
@@ -118,6 +118,14 @@ where
 		Ok(U256::default())
 	}
 
+	fn miner_stats(&self) -> Result<MinerStats> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn call_cache_stats(&self) -> Result<CallCacheStats> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn extra_data(&self) -> Result<Bytes> {
 		Ok(Bytes::default())
 	}
@@ -208,6 +216,14 @@ where
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn storage_root(&self, _: H160, _: Option<BlockNumber>) -> Result<Option<H256>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn code_hash(&self, _: H160, _: Option<BlockNumber>) -> Result<Option<H256>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key, &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -242,6 +258,16 @@ where
 		)
 	}
 
+	fn scheduled_transactions(&self) -> Result<Vec<Transaction>> {
+		let chain_info = self.light_dispatch.client.chain_info();
+		Ok(
+			light_all_transactions(&self.light_dispatch)
+				.filter(|t| helpers::is_scheduled(t, chain_info.best_block_number, chain_info.best_block_timestamp))
+				.map(Transaction::from_pending)
+				.collect()
+		)
+	}
+
 	fn future_transactions(&self) -> Result<Vec<Transaction>> {
 		let txq = self.light_dispatch.transaction_queue.read();
 		let chain_info = self.light_dispatch.client.chain_info();
@@ -253,6 +279,10 @@ where
 		)
 	}
 
+	fn transaction_status(&self, _hash: H256) -> Result<Option<LocalTransactionStatus>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn pending_transactions_stats(&self) -> Result<BTreeMap<H256, TransactionStats>> {
 		let stats = self.light_dispatch.sync.transactions_stats();
 		Ok(stats.into_iter()
@@ -324,6 +354,14 @@ where
 		})
 	}
 
+	fn db_stats(&self) -> Result<DbStats> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn reorg_history(&self, _count: usize) -> Result<Vec<Reorg>> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn node_kind(&self) -> Result<::v1::types::NodeKind> {
 		use ::v1::types::{NodeKind, Availability, Capability};
 
@@ -396,11 +434,12 @@ where
 	}
 
 	fn logs_no_tx_hash(&self, filter: Filter) -> BoxFuture<Vec<Log>> {
+		let (limit, offset) = (filter.limit, filter.offset);
 		let filter = match filter.try_into() {
 			Ok(value) => value,
 			Err(err) => return Box::new(future::err(err)),
 		};
-		Box::new(self.fetcher().logs_no_tx_hash(filter)) as BoxFuture<_>
+		Box::new(self.fetcher().logs_no_tx_hash(filter).map(move |logs| limit_logs(skip_logs(logs, offset), limit))) as BoxFuture<_>
 	}
 
 	fn verify_signature(&self, is_prefixed: bool, message: Bytes, r: H256, s: H256, v: U64) -> Result<RecoveredAccount> {
@@ -418,4 +457,24 @@ where
 	fn submit_raw_block(&self, _block: Bytes) -> Result<H256> {
 		Err(light_unimplemented(None))
 	}
+
+	fn decode_transaction_input(&self, _address: H160, _data: Bytes) -> Result<Option<DecodedCall>> {
+		Err(light_unimplemented(None))
+	}
+
+	fn check_transaction_included(&self, _raw: Bytes) -> Result<TransactionInclusion> {
+		Err(light_unimplemented(None))
+	}
+
+	fn schedule_at(&self, number: Option<BlockNumber>) -> Result<Schedule> {
+		let client = &self.light_dispatch.client;
+		let number = match number.unwrap_or_default() {
+			BlockNumber::Num(num) => num,
+			BlockNumber::Pending => client.chain_info().best_block_number + 1,
+			number => client.block_header(block_number_to_id(number)).ok_or_else(errors::unknown_block)?.number(),
+		};
+
+		let engine = client.engine();
+		Ok(Schedule::new(number, &engine.schedule(number), &engine.machine().schedule_transitions()))
+	}
 }
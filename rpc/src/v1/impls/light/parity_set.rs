@@ -27,24 +27,26 @@ use light::client::LightChainClient;
 use sync::ManageNetwork;
 
 use jsonrpc_core::{Result, BoxFuture};
-use jsonrpc_core::futures::Future;
-use v1::helpers::errors;
+use jsonrpc_core::futures::{future, Future};
+use v1::helpers::{errors, RpcModules};
 use v1::traits::ParitySet;
-use v1::types::{Bytes, ReleaseInfo, Transaction};
+use v1::types::{BlockNumber, Bytes, RelayedTransaction, ReleaseInfo, Transaction};
 
 /// Parity-specific rpc interface for operations altering the settings.
 pub struct ParitySetClient<F> {
 	client: Arc<dyn LightChainClient>,
 	net: Arc<dyn ManageNetwork>,
+	rpc_modules: Arc<RpcModules>,
 	fetch: F,
 }
 
 impl<F: Fetch> ParitySetClient<F> {
 	/// Creates new `ParitySetClient` with given `Fetch`.
-	pub fn new(client: Arc<dyn LightChainClient>, net: Arc<dyn ManageNetwork>, fetch: F) -> Self {
+	pub fn new(client: Arc<dyn LightChainClient>, net: Arc<dyn ManageNetwork>, rpc_modules: Arc<RpcModules>, fetch: F) -> Self {
 		ParitySetClient {
 			client,
 			net,
+			rpc_modules,
 			fetch,
 		}
 	}
@@ -71,6 +73,10 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 		Err(errors::light_unimplemented(None))
 	}
 
+	fn set_author_rotation(&self, _authors: Vec<H160>) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
 	fn set_engine_signer_secret(&self, _secret: H256) -> Result<bool> {
 		Err(errors::light_unimplemented(None))
 	}
@@ -101,6 +107,20 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 		}
 	}
 
+	fn add_to_blacklist(&self, entry: String) -> Result<bool> {
+		match self.net.add_to_blacklist(entry) {
+			Ok(()) => Ok(true),
+			Err(e) => Err(errors::invalid_params("Blacklist entry", e)),
+		}
+	}
+
+	fn remove_from_blacklist(&self, entry: String) -> Result<bool> {
+		match self.net.remove_from_blacklist(entry) {
+			Ok(()) => Ok(true),
+			Err(e) => Err(errors::invalid_params("Blacklist entry", e)),
+		}
+	}
+
 	fn drop_non_reserved_peers(&self) -> Result<bool> {
 		self.net.deny_unreserved_peers();
 		Ok(true)
@@ -153,4 +173,46 @@ impl<F: Fetch> ParitySet for ParitySetClient<F> {
 	fn remove_transaction(&self, _hash: H256) -> Result<Option<Transaction>> {
 		Err(errors::light_unimplemented(None))
 	}
+
+	fn clear_bad_blocks(&self) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn send_private_transaction(&self, _raw: Bytes) -> BoxFuture<H256> {
+		Box::new(future::err(errors::light_unimplemented(None)))
+	}
+
+	fn relayed_transactions(&self) -> Result<Vec<RelayedTransaction>> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn register_abi(&self, _address: H160, _abi: String) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn unregister_abi(&self, _address: H160) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn dump_state(&self, _path: String, _format: Option<String>, _block: Option<BlockNumber>) -> Result<bool> {
+		Err(errors::light_unimplemented(None))
+	}
+
+	fn set_rpc_modules(&self, modules: Vec<String>) -> Result<bool> {
+		self.rpc_modules.reload(&modules.join(","))
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("modules", e))
+	}
+
+	fn set_rpc_cors(&self, cors: String) -> Result<bool> {
+		self.rpc_modules.reload_cors(&cors)
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("cors", e))
+	}
+
+	fn set_rpc_hosts(&self, hosts: String) -> Result<bool> {
+		self.rpc_modules.reload_hosts(&hosts)
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("hosts", e))
+	}
 }
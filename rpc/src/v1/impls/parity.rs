@@ -16,11 +16,11 @@
 
 //! Parity-specific rpc implementation.
 use std::sync::Arc;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use crypto::DEFAULT_MAC;
 use ethereum_types::{H64, H160, H256, H512, U64, U256};
-use ethcore::client::Call;
+use ethcore::client::{Call, EngineInfo};
 use client_traits::{BlockChainClient, StateClient};
 use ethcore::miner::{self, MinerService, FilterOptions};
 use snapshot::SnapshotService;
@@ -32,15 +32,18 @@ use ethstore::random_phrase;
 use jsonrpc_core::futures::future;
 use jsonrpc_core::{BoxFuture, Result};
 use sync::{SyncProvider, ManageNetwork};
+use rlp::Rlp;
 use types::{
-	ids::BlockId,
+	ids::{BlockId, TransactionId},
+	transaction::{LocalizedTransaction, SignedTransaction},
 	verification::Unverified,
 	snapshot::RestorationStatus,
 };
 use updater::{Service as UpdateService};
 use version::version_data;
 
-use v1::helpers::{self, errors, fake_sign, ipfs, NetworkSettings, verify_signature};
+use v1::helpers::{self, errors, fake_sign, ipfs, AbiRegistry, NetworkSettings, verify_signature};
+use v1::helpers::call_cache::CallCache;
 use v1::helpers::external_signer::{SigningQueue, SignerService};
 use v1::metadata::Metadata;
 use v1::traits::Parity;
@@ -49,8 +52,8 @@ use v1::types::{
 	Peers, Transaction, RpcSettings, Histogram,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
-	OperationsInfo, ChainStatus, Log, Filter,
-	RichHeader, Receipt, RecoveredAccount,
+	OperationsInfo, ChainStatus, DbStats, Reorg, Log, Filter, MinerStats, CallCacheStats,
+	RichHeader, Receipt, RecoveredAccount, DecodedCall, Schedule, TransactionInclusion,
 	block_number_to_id
 };
 use Host;
@@ -67,6 +70,10 @@ pub struct ParityClient<C, M, U> {
 	signer: Option<Arc<SignerService>>,
 	ws_address: Option<Host>,
 	snapshot: Option<Arc<dyn SnapshotService>>,
+	abi_registry: Arc<AbiRegistry>,
+	call_cache: Arc<CallCache>,
+	gas_price_blocks: usize,
+	conflict_search_depth: u64,
 }
 
 impl<C, M, U> ParityClient<C, M, U> where
@@ -84,6 +91,10 @@ impl<C, M, U> ParityClient<C, M, U> where
 		signer: Option<Arc<SignerService>>,
 		ws_address: Option<Host>,
 		snapshot: Option<Arc<dyn SnapshotService>>,
+		abi_registry: Arc<AbiRegistry>,
+		call_cache: Arc<CallCache>,
+		gas_price_blocks: usize,
+		conflict_search_depth: u64,
 	) -> Self {
 		ParityClient {
 			client,
@@ -96,13 +107,37 @@ impl<C, M, U> ParityClient<C, M, U> where
 			signer,
 			ws_address,
 			snapshot,
+			abi_registry,
+			call_cache,
+			gas_price_blocks,
+			conflict_search_depth,
 		}
 	}
+
+	/// Looks for a transaction from `sender` with given nonce in the last `conflict_search_depth` blocks.
+	///
+	/// Transactions are only indexed by hash, so every block body in range is read.
+	fn find_mined_transaction(&self, sender: &H160, nonce: U256) -> Option<LocalizedTransaction> {
+		if self.conflict_search_depth == 0 {
+			return None;
+		}
+		let best_block = self.client.chain_info().best_block_number;
+		for number in (best_block.saturating_sub(self.conflict_search_depth - 1)..=best_block).rev() {
+			let body = self.client.block_body(BlockId::Number(number))?;
+			let index = body.transactions().into_iter().position(|tx| {
+				tx.nonce == nonce && SignedTransaction::new(tx).map_or(false, |tx| tx.sender() == *sender)
+			});
+			if let Some(index) = index {
+				return self.client.transaction(TransactionId::Location(BlockId::Number(number), index));
+			}
+		}
+		None
+	}
 }
 
 impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 	S: StateInfo + 'static,
-	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + 'static,
+	C: miner::BlockChainClient + BlockChainClient + StateClient<State=S> + Call<State=S> + EngineInfo + 'static,
 	M: MinerService<State=S> + 'static,
 	U: UpdateService + 'static,
 {
@@ -116,6 +151,14 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		Ok(self.miner.queue_status().options.minimal_gas_price)
 	}
 
+	fn miner_stats(&self) -> Result<MinerStats> {
+		Ok(self.miner.stats().into())
+	}
+
+	fn call_cache_stats(&self) -> Result<CallCacheStats> {
+		Ok(self.call_cache.stats().into())
+	}
+
 	fn extra_data(&self) -> Result<Bytes> {
 		Ok(Bytes::new(self.miner.authoring_params().extra_data))
 	}
@@ -186,7 +229,7 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 
 	fn gas_price_histogram(&self) -> BoxFuture<Histogram> {
 		Box::new(future::done(self.client
-			.gas_price_corpus(100)
+			.gas_price_corpus(self.gas_price_blocks)
 			.histogram(10)
 			.ok_or_else(errors::not_enough_data)
 			.map(Into::into)
@@ -238,6 +281,32 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 			.map(|a| a.into_iter().map(Into::into).collect()))
 	}
 
+	fn storage_root(&self, address: H160, block_number: Option<BlockNumber>) -> Result<Option<H256>> {
+		let number = match block_number.unwrap_or_default() {
+			BlockNumber::Pending => {
+				warn!("BlockNumber::Pending is unsupported");
+				return Ok(None);
+			},
+
+			num => block_number_to_id(num)
+		};
+
+		Ok(self.client.storage_root(&address, number))
+	}
+
+	fn code_hash(&self, address: H160, block_number: Option<BlockNumber>) -> Result<Option<H256>> {
+		let number = match block_number.unwrap_or_default() {
+			BlockNumber::Pending => {
+				warn!("BlockNumber::Pending is unsupported");
+				return Ok(None);
+			},
+
+			num => block_number_to_id(num)
+		};
+
+		Ok(self.client.code_hash(&address, number))
+	}
+
 	fn encrypt_message(&self, key: H512, phrase: Bytes) -> Result<Bytes> {
 		ecies::encrypt(&key, &DEFAULT_MAC, &phrase.0)
 			.map_err(errors::encryption)
@@ -273,8 +342,49 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		Ok(self.miner.queued_transaction_hashes())
 	}
 
+	fn scheduled_transactions(&self) -> Result<Vec<Transaction>> {
+		let chain_info = self.client.chain_info();
+
+		Ok(self.miner.queued_transactions()
+			.into_iter()
+			.map(|t| t.pending().clone())
+			.filter(|t| helpers::is_scheduled(t, chain_info.best_block_number, chain_info.best_block_timestamp))
+			.map(Transaction::from_pending)
+			.collect()
+		)
+	}
+
 	fn future_transactions(&self) -> Result<Vec<Transaction>> {
-		Err(errors::deprecated("Use `parity_allTransaction` instead."))
+		let mut next_nonces = HashMap::new();
+
+		Ok(self.miner.queued_transactions()
+			.into_iter()
+			.filter(|t| {
+				let sender = t.signed().sender();
+				let next_nonce = *next_nonces.entry(sender)
+					.or_insert_with(|| self.miner.next_nonce(&*self.client, &sender));
+				t.signed().nonce >= next_nonce
+			})
+			.map(|t| Transaction::from_pending(t.pending().clone()))
+			.collect()
+		)
+	}
+
+	fn transaction_status(&self, hash: H256) -> Result<Option<LocalTransactionStatus>> {
+		if let Some(tx) = self.miner.transaction(&hash) {
+			let next_nonce = self.miner.next_nonce(&*self.client, &tx.signed().sender());
+			return Ok(Some(if tx.signed().nonce < next_nonce {
+				LocalTransactionStatus::Pending
+			} else {
+				LocalTransactionStatus::Future
+			}));
+		}
+
+		if let Some(tx) = self.client.transaction(TransactionId::Hash(hash)) {
+			return Ok(Some(LocalTransactionStatus::Mined(Transaction::from_localized(tx))));
+		}
+
+		Ok(self.miner.local_transactions().remove(&hash).map(LocalTransactionStatus::from))
 	}
 
 	fn pending_transactions_stats(&self) -> Result<BTreeMap<H256, TransactionStats>> {
@@ -334,6 +444,21 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		})
 	}
 
+	fn db_stats(&self) -> Result<DbStats> {
+		let pruning_info = self.client.pruning_info();
+
+		Ok(DbStats {
+			pruning: self.client.pruning_mode().into(),
+			earliest_chain: pruning_info.earliest_chain.into(),
+			earliest_state: pruning_info.earliest_state.into(),
+			disk_usage: self.client.disk_usage().map(Into::into),
+		})
+	}
+
+	fn reorg_history(&self, count: usize) -> Result<Vec<Reorg>> {
+		Ok(self.client.reorg_history(count).into_iter().map(Into::into).collect())
+	}
+
 	fn node_kind(&self) -> Result<::v1::types::NodeKind> {
 		use ::v1::types::{NodeKind, Availability, Capability};
 
@@ -484,4 +609,46 @@ impl<C, M, U, S> Parity for ParityClient<C, M, U> where
 		);
 		Ok(result.map_err(errors::cannot_submit_block)?)
 	}
+
+	fn decode_transaction_input(&self, address: H160, data: Bytes) -> Result<Option<DecodedCall>> {
+		Ok(self.abi_registry.decode_input(&address, &data.0))
+	}
+
+	fn check_transaction_included(&self, raw: Bytes) -> Result<TransactionInclusion> {
+		let transaction = Rlp::new(&raw.0).as_val()
+			.map_err(errors::rlp)
+			.and_then(|tx| SignedTransaction::new(tx).map_err(errors::transaction))?;
+		let hash = transaction.hash();
+
+		if let Some(tx) = self.client.transaction(TransactionId::Hash(hash)) {
+			return Ok(TransactionInclusion::Mined(Transaction::from_localized(tx)));
+		}
+		if let Some(tx) = self.miner.transaction(&hash) {
+			return Ok(TransactionInclusion::Pending(Transaction::from_pending(tx.pending().clone())));
+		}
+
+		let sender = transaction.sender();
+		let nonce = transaction.nonce;
+		if self.client.latest_nonce(&sender) > nonce {
+			let mined = self.find_mined_transaction(&sender, nonce).map(Transaction::from_localized);
+			return Ok(TransactionInclusion::ConflictMined(mined));
+		}
+
+		let pending = self.miner.queued_transactions_from_sender(&sender).into_iter()
+			.find(|tx| tx.signed().nonce == nonce);
+		Ok(match pending {
+			Some(tx) => TransactionInclusion::ConflictPending(Transaction::from_pending(tx.pending().clone())),
+			None => TransactionInclusion::NotFound,
+		})
+	}
+
+	fn schedule_at(&self, number: Option<BlockNumber>) -> Result<Schedule> {
+		let number = match number.unwrap_or_default() {
+			BlockNumber::Pending => self.client.chain_info().best_block_number + 1,
+			number => self.client.block_number(block_number_to_id(number)).ok_or_else(errors::unknown_block)?,
+		};
+
+		let engine = self.client.engine();
+		Ok(Schedule::new(number, &engine.schedule(number), &engine.machine().schedule_transitions()))
+	}
 }
@@ -176,6 +176,25 @@ impl ParityAccounts for ParityAccountsClient {
 		Ok(true)
 	}
 
+	fn export_address_book(&self) -> Result<String> {
+		self.deprecation_notice("parity_exportAddressBook");
+		Ok(self.accounts.export_address_book())
+	}
+
+	fn import_address_book(&self, json: String) -> Result<Vec<H160>> {
+		self.deprecation_notice("parity_importAddressBook");
+		let mut addresses = self.accounts.import_address_book(&json)
+			.map_err(|e| errors::account("Could not import address book.", e))?;
+		addresses.sort();
+		Ok(addresses)
+	}
+
+	fn clear_address_book(&self) -> Result<bool> {
+		self.deprecation_notice("parity_clearAddressBook");
+		self.accounts.clear_address_book();
+		Ok(true)
+	}
+
 	fn set_account_name(&self, addr: H160, name: String) -> Result<bool> {
 		self.deprecation_notice("parity_setAccountName");
 		let addr: Address = addr.into();
@@ -16,24 +16,32 @@
 
 /// Parity-specific rpc interface for operations altering the settings.
 use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use client_traits::BlockChainClient;
 use types::client_types::Mode;
+use types::ids::{BlockId, TransactionId};
+use types::transaction::SignedTransaction;
 use ethcore::miner::{self, MinerService};
 use ethereum_types::{H160, H256, U256};
 use crypto::publickey::KeyPair;
 use fetch::{self, Fetch};
 use hash::keccak_buffer;
+use hyper::header::{CONTENT_TYPE, HeaderValue};
+use rlp::Rlp;
+use serde_json;
 use sync::ManageNetwork;
 use updater::{Service as UpdateService};
+use version::with_version;
 
 use jsonrpc_core::{BoxFuture, Result};
-use jsonrpc_core::futures::Future;
-use v1::helpers::errors;
+use jsonrpc_core::futures::{future, Future};
+use v1::helpers::{errors, AbiRegistry, PrivateRelay, RpcModules};
+use v1::helpers::state_dump::{DumpFormat, StateDumps, StatePin};
 use v1::traits::ParitySet;
-use v1::types::{Bytes, ReleaseInfo, Transaction};
+use v1::types::{block_number_to_id, BlockNumber, Bytes, RelayedTransaction, ReleaseInfo, Transaction};
 
 #[cfg(any(test, feature = "accounts"))]
 pub mod accounts {
@@ -88,7 +96,11 @@ pub struct ParitySetClient<C, M, U, F = fetch::Client> {
 	miner: Arc<M>,
 	updater: Arc<U>,
 	net: Arc<dyn ManageNetwork>,
+	rpc_modules: Arc<RpcModules>,
 	fetch: F,
+	private_relay: Option<Arc<PrivateRelay>>,
+	abi_registry: Arc<AbiRegistry>,
+	state_dumps: StateDumps,
 }
 
 impl<C, M, U, F> ParitySetClient<C, M, U, F>
@@ -100,14 +112,22 @@ impl<C, M, U, F> ParitySetClient<C, M, U, F>
 		miner: &Arc<M>,
 		updater: &Arc<U>,
 		net: &Arc<dyn ManageNetwork>,
+		rpc_modules: &Arc<RpcModules>,
 		fetch: F,
+		private_relay: Option<Arc<PrivateRelay>>,
+		abi_registry: &Arc<AbiRegistry>,
+		state_dumps_dir: PathBuf,
 	) -> Self {
 		ParitySetClient {
 			client: client.clone(),
 			miner: miner.clone(),
 			updater: updater.clone(),
 			net: net.clone(),
+			rpc_modules: rpc_modules.clone(),
 			fetch,
+			private_relay,
+			abi_registry: abi_registry.clone(),
+			state_dumps: StateDumps::new(state_dumps_dir),
 		}
 	}
 }
@@ -151,7 +171,7 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 	}
 
 	fn set_extra_data(&self, extra_data: Bytes) -> Result<bool> {
-		self.miner.set_extra_data(extra_data.into_vec());
+		self.miner.set_extra_data(with_version(extra_data.into_vec()));
 		Ok(true)
 	}
 
@@ -160,6 +180,11 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 		Ok(true)
 	}
 
+	fn set_author_rotation(&self, authors: Vec<H160>) -> Result<bool> {
+		self.miner.set_author_rotation(authors).map_err(|e| errors::unsupported(e.to_string(), None))?;
+		Ok(true)
+	}
+
 	fn set_engine_signer_secret(&self, secret: H256) -> Result<bool> {
 		let keypair = KeyPair::from_secret(secret.into()).map_err(|e| errors::account("Invalid secret", e))?;
 		self.miner.set_author(miner::Author::Sealer(engine::signer::from_keypair(keypair)));
@@ -185,6 +210,20 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 		}
 	}
 
+	fn add_to_blacklist(&self, entry: String) -> Result<bool> {
+		match self.net.add_to_blacklist(entry) {
+			Ok(()) => Ok(true),
+			Err(e) => Err(errors::invalid_params("Blacklist entry", e)),
+		}
+	}
+
+	fn remove_from_blacklist(&self, entry: String) -> Result<bool> {
+		match self.net.remove_from_blacklist(entry) {
+			Ok(()) => Ok(true),
+			Err(e) => Err(errors::invalid_params("Blacklist entry", e)),
+		}
+	}
+
 	fn drop_non_reserved_peers(&self) -> Result<bool> {
 		self.net.deny_unreserved_peers();
 		Ok(true)
@@ -246,4 +285,106 @@ impl<C, M, U, F> ParitySet for ParitySetClient<C, M, U, F> where
 			.map(|t| Transaction::from_pending(t.pending().clone()))
 		)
 	}
+
+	fn clear_bad_blocks(&self) -> Result<bool> {
+		self.client.clear_bad_blocks();
+		Ok(true)
+	}
+
+	fn set_rpc_modules(&self, modules: Vec<String>) -> Result<bool> {
+		self.rpc_modules.reload(&modules.join(","))
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("modules", e))
+	}
+
+	fn set_rpc_cors(&self, cors: String) -> Result<bool> {
+		self.rpc_modules.reload_cors(&cors)
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("cors", e))
+	}
+
+	fn set_rpc_hosts(&self, hosts: String) -> Result<bool> {
+		self.rpc_modules.reload_hosts(&hosts)
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("hosts", e))
+	}
+
+	fn send_private_transaction(&self, raw: Bytes) -> BoxFuture<H256> {
+		let relay = match self.private_relay {
+			Some(ref relay) => relay.clone(),
+			None => return Box::new(future::err(errors::private_relay_disabled())),
+		};
+		let hash = try_bf!(Rlp::new(&raw.0).as_val()
+			.map_err(errors::rlp)
+			.and_then(|tx| SignedTransaction::new(tx).map_err(errors::transaction)))
+			.hash();
+		let url = try_bf!(relay.endpoint().parse().map_err(errors::fetch));
+		let request = fetch::Request::post(url)
+			.with_header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+			.with_body(PrivateRelay::request_body(&raw.0));
+		let best_block = self.client.chain_info().best_block_number;
+
+		let future = self.fetch.fetch(request, Default::default()).then(move |result| {
+			let response = result.map_err(errors::fetch)?;
+			if !response.is_success() {
+				return Err(errors::fetch(response.status()));
+			}
+			// relays speaking JSON-RPC report rejected transactions in the response body.
+			let body: Option<serde_json::Value> = serde_json::from_reader(fetch::BodyReader::new(response)).ok();
+			if let Some(error) = body.as_ref().and_then(|body| body.get("error")) {
+				return Err(errors::private_relay_rejected(error.clone()));
+			}
+			relay.note_relayed(hash, best_block);
+			Ok(hash)
+		});
+		Box::new(future)
+	}
+
+	fn relayed_transactions(&self) -> Result<Vec<RelayedTransaction>> {
+		let relay = self.private_relay.as_ref().ok_or_else(errors::private_relay_disabled)?;
+		Ok(relay.relayed().into_iter().map(|(hash, relayed_at)| {
+			let included = self.client.transaction(TransactionId::Hash(hash));
+			RelayedTransaction {
+				hash,
+				relayed_at: relayed_at.into(),
+				block_hash: included.as_ref().map(|tx| tx.block_hash),
+				block_number: included.map(|tx| tx.block_number.into()),
+			}
+		}).collect())
+	}
+
+	fn register_abi(&self, address: H160, abi: String) -> Result<bool> {
+		self.abi_registry.register(address, &abi)
+			.map(|_| true)
+			.map_err(|e| errors::invalid_params("abi", e))
+	}
+
+	fn unregister_abi(&self, address: H160) -> Result<bool> {
+		Ok(self.abi_registry.unregister(&address))
+	}
+
+	fn dump_state(&self, name: String, format: Option<String>, block_number: Option<BlockNumber>) -> Result<bool> {
+		let path = self.state_dumps.path(&name)
+			.ok_or_else(|| errors::invalid_params("name", "Dumps are written to the state dumps directory, expected a file name."))?;
+		let format = match format {
+			Some(format) => format.parse().map_err(|e| errors::invalid_params("format", e))?,
+			None => DumpFormat::Json,
+		};
+		let id = match block_number.unwrap_or_default() {
+			BlockNumber::Pending => return Err(errors::invalid_params("block", "Pending state can't be dumped.")),
+			num => block_number_to_id(num),
+		};
+		// Dump the block by hash, so that the dump isn't affected by blocks imported meanwhile, and keep
+		// its state from being pruned until the dump completes.
+		let hash = self.client.block_hash(id).ok_or_else(errors::unknown_block)?;
+		let number = self.client.block_number(BlockId::Hash(hash)).ok_or_else(errors::unknown_block)?;
+		let pin = StatePin::new(self.client.clone(), number);
+		if self.client.list_account_entries(BlockId::Hash(hash), None, 0).is_none() {
+			return Err(errors::state_pruned());
+		}
+
+		self.state_dumps.start(pin, BlockId::Hash(hash), path, format)
+			.map(|_| true)
+			.map_err(|e| errors::internal("Cannot dump state", e))
+	}
 }
@@ -262,6 +262,34 @@ impl<D: Dispatcher + 'static> Personal for PersonalClient<D> {
 		)
 	}
 
+	fn send_transactions_batch(&self, _meta: Metadata, requests: Vec<TransactionRequest>, password: String) -> BoxFuture<Vec<H256>> {
+		self.deprecation_notice.print("parity_sendTransactionsBatch", deprecated::msgs::ACCOUNTS);
+		let from = match requests.first() {
+			Some(first) => first.from,
+			None => return Box::new(future::ok(Vec::new())),
+		};
+		if requests.iter().any(|request| request.from != from) {
+			return Box::new(future::err(errors::invalid_params("from", "All transactions in a batch must have the same sender.")));
+		}
+		if requests.iter().any(|request| request.nonce.is_some()) {
+			return Box::new(future::err(errors::invalid_params("nonce", "Nonces of batched transactions are assigned by the node.")));
+		}
+
+		let default = match from {
+			Some(account) => account,
+			None => try_bf!(self.accounts.default_account().map_err(|e| errors::account("Cannot find default account.", e))),
+		};
+
+		let dispatcher = self.dispatcher.clone();
+		let accounts = Arc::new(dispatch::Signer::new(self.accounts.clone())) as _;
+		let filled = requests.into_iter().map(|request| dispatcher.fill_optional_fields(request.into(), default, false));
+		Box::new(future::join_all(filled)
+			.and_then(move |filled| {
+				dispatcher.sign_and_dispatch_batch(filled, &accounts, SignWith::Password(password.into()))
+			})
+		)
+	}
+
 	fn sign_and_send_transaction(&self, meta: Metadata, request: TransactionRequest, password: String) -> BoxFuture<H256> {
 		self.deprecation_notice.print("personal_signAndSendTransaction", Some("use personal_sendTransaction instead."));
 		warn!("Using deprecated personal_signAndSendTransaction, use personal_sendTransaction instead.");
@@ -18,7 +18,7 @@
 
 use std::sync::Arc;
 
-use ethereum_types::{U256, H520};
+use ethereum_types::{H160, H520, U256};
 use parity_runtime::Executor;
 use parking_lot::Mutex;
 use rlp::Rlp;
@@ -234,6 +234,9 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 					// TODO [ToDr]: Decrypt can we verify if the answer is correct?
 					Ok(ConfirmationResponse::Decrypt(bytes))
 				},
+				ConfirmationPayload::ExportSecret(_address) => {
+					Err(errors::unsupported("Secret exports can only be confirmed with the account password.", None))
+				},
 			};
 			if let Ok(ref response) = result {
 				self.signer.request_confirmed(sender, Ok(response.clone()));
@@ -244,6 +247,15 @@ impl<D: Dispatcher + 'static> Signer for SignerClient<D> {
 		}).unwrap_or_else(|| Err(errors::invalid_params("Unknown RequestID", id)))
 	}
 
+	fn request_secret_export(&self, meta: Self::Metadata, address: H160) -> Result<U256> {
+		self.deprecation_notice.print("signer_requestSecretExport", deprecated::msgs::ACCOUNTS);
+
+		// nobody waits for the result, the secret is only returned to the caller confirming the request
+		self.signer.add_request(ConfirmationPayload::ExportSecret(address), meta.origin)
+			.map(|(id, _)| id)
+			.map_err(|_| errors::request_rejected_limit())
+	}
+
 	fn reject_request(&self, id: U256) -> Result<bool> {
 		self.deprecation_notice.print("signer_rejectRequest", deprecated::msgs::ACCOUNTS);
 
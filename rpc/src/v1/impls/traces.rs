@@ -16,6 +16,7 @@
 
 //! Traces api implementation.
 
+use std::cmp;
 use std::sync::Arc;
 
 use account_state::state::StateInfo;
@@ -26,6 +27,7 @@ use rlp::Rlp;
 use types::{
 	call_analytics::CallAnalytics,
 	ids::{BlockId, TransactionId, TraceId},
+	trace_filter::Filter,
 	transaction::SignedTransaction,
 };
 
@@ -36,7 +38,7 @@ use v1::helpers::{errors, fake_sign};
 use v1::types::{TraceFilter, LocalizedTrace, BlockNumber, Index, CallRequest, Bytes, TraceResults,
 	TraceResultsWithTransactionHash, TraceOptions, block_number_to_id};
 
-fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
+pub(crate) fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 	CallAnalytics {
 		transaction_tracing: flags.contains(&("trace".to_owned())),
 		vm_tracing: flags.contains(&("vmTrace".to_owned())),
@@ -47,13 +49,15 @@ fn to_call_analytics(flags: TraceOptions) -> CallAnalytics {
 /// Traces api implementation.
 pub struct TracesClient<C> {
 	client: Arc<C>,
+	max_results: usize,
 }
 
 impl<C> TracesClient<C> {
-	/// Creates new Traces client.
-	pub fn new(client: &Arc<C>) -> Self {
+	/// Creates new Traces client, returning at most `max_results` filtered traces at once (0 for no limit).
+	pub fn new(client: &Arc<C>, max_results: usize) -> Self {
 		TracesClient {
 			client: client.clone(),
+			max_results,
 		}
 	}
 }
@@ -65,8 +69,17 @@ impl<C, S> Traces for TracesClient<C> where
 	type Metadata = Metadata;
 
 	fn filter(&self, filter: TraceFilter) -> Result<Option<Vec<LocalizedTrace>>> {
-		Ok(self.client.filter_traces(filter.into())
-			.map(|traces| traces.into_iter().map(LocalizedTrace::from).collect()))
+		let mut filter: Filter = filter.into();
+		if self.max_results > 0 {
+			// Ask for one more trace than allowed to tell whether the limit is exceeded.
+			filter.count = Some(filter.count.map_or(self.max_results + 1, |count| cmp::min(count, self.max_results + 1)));
+		}
+
+		match self.client.filter_traces(filter) {
+			Some(ref traces) if self.max_results > 0 && traces.len() > self.max_results =>
+				Err(errors::too_many_results(self.max_results, "traces")),
+			traces => Ok(traces.map(|traces| traces.into_iter().map(LocalizedTrace::from).collect())),
+		}
 	}
 
 	fn block_traces(&self, block_number: BlockNumber) -> Result<Option<Vec<LocalizedTrace>>> {
@@ -112,7 +125,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
 		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?;
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?, None)
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
@@ -163,7 +176,7 @@ impl<C, S> Traces for TracesClient<C> where
 		let mut state = self.client.state_at(id).ok_or_else(errors::state_pruned)?;
 		let header = self.client.block_header(id).ok_or_else(errors::state_pruned)?;
 
-		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?)
+		self.client.call(&signed, to_call_analytics(flags), &mut state, &header.decode().map_err(errors::decode)?, None)
 			.map(TraceResults::from)
 			.map_err(errors::call)
 	}
@@ -22,9 +22,11 @@ use std::sync::atomic::{self, AtomicUsize};
 use std::time;
 use parity_runtime;
 use jsonrpc_core as core;
-use jsonrpc_core::futures::future::Either;
+use jsonrpc_core::futures::future::{self, Either};
 use order_stat;
 use parking_lot::RwLock;
+use serde_json;
+use v1::helpers::{AddressFormat, NameResolution};
 
 pub use self::parity_runtime::Executor;
 
@@ -183,32 +185,223 @@ pub trait ActivityNotifier: Send + Sync + 'static {
 	fn active(&self);
 }
 
+/// Logging of served RPC requests.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RpcLogging {
+	/// Log method, params size, duration and outcome of every request.
+	pub access: bool,
+	/// Log the full params of requests taking longer than this.
+	pub slow_threshold: Option<time::Duration>,
+}
+
+impl RpcLogging {
+	fn is_enabled(&self) -> bool {
+		self.access || self.slow_threshold.is_some()
+	}
+}
+
+/// Methods and params of a request, as logged.
+struct RequestSummary {
+	methods: String,
+	params: String,
+}
+
+/// Namespaces whose methods only take public chain data, calls and transactions.
+const PUBLIC_NAMESPACES: &[&str] = &["eth_", "net_", "web3_", "trace_", "rpc_"];
+
+/// Methods outside of `PUBLIC_NAMESPACES` known to take no passwords, secrets or recovery phrases.
+const PUBLIC_METHODS: &[&str] = &[
+	"debug_getBadBlocks",
+	"debug_replayBlock",
+	"debug_resume",
+	"debug_sessionState",
+	"debug_setBreakpoints",
+	"debug_startSession",
+	"debug_step",
+	"debug_stopSession",
+	"modules",
+	"parity_acceptNonReservedPeers",
+	"parity_addReservedPeer",
+	"parity_addToBlacklist",
+	"parity_allTransactionHashes",
+	"parity_allTransactions",
+	"parity_call",
+	"parity_callCacheStats",
+	"parity_chain",
+	"parity_chainStatus",
+	"parity_checkTransactionIncluded",
+	"parity_clearBadBlocks",
+	"parity_clearEngineSigner",
+	"parity_codeHash",
+	"parity_consensusCapability",
+	"parity_dbStats",
+	"parity_decodeTransactionInput",
+	"parity_defaultExtraData",
+	"parity_devLogs",
+	"parity_devLogsLevels",
+	"parity_dropNonReservedPeers",
+	"parity_enode",
+	"parity_extraData",
+	"parity_futureTransactions",
+	"parity_gasCeilTarget",
+	"parity_gasFloorTarget",
+	"parity_gasPriceHistogram",
+	"parity_getBlockHeaderByNumber",
+	"parity_getBlockReceipts",
+	"parity_getLogsNoTransactionHash",
+	"parity_getRawBlockByNumber",
+	"parity_listAccounts",
+	"parity_listStorageKeys",
+	"parity_localTransactions",
+	"parity_minGasPrice",
+	"parity_minerStats",
+	"parity_mode",
+	"parity_netChain",
+	"parity_netPeers",
+	"parity_netPort",
+	"parity_nextNonce",
+	"parity_nodeKind",
+	"parity_nodeName",
+	"parity_nodeStatus",
+	"parity_pendingTransactions",
+	"parity_pendingTransactionsStats",
+	"parity_registryAddress",
+	"parity_releasesInfo",
+	"parity_removeFromBlacklist",
+	"parity_removeReservedPeer",
+	"parity_removeTransaction",
+	"parity_reorgHistory",
+	"parity_rpcSettings",
+	"parity_scheduleAt",
+	"parity_scheduledTransactions",
+	"parity_setAuthor",
+	"parity_setAuthorRotation",
+	"parity_setExtraData",
+	"parity_setGasCeilTarget",
+	"parity_setGasFloorTarget",
+	"parity_setMaxTransactionGas",
+	"parity_setMinGasPrice",
+	"parity_setMode",
+	"parity_setRpcCors",
+	"parity_setRpcHosts",
+	"parity_setRpcModules",
+	"parity_setTransactionsLimit",
+	"parity_startNetwork",
+	"parity_stopNetwork",
+	"parity_storageRoot",
+	"parity_submitRawBlock",
+	"parity_submitWorkDetail",
+	"parity_transactionStatus",
+	"parity_transactionsLimit",
+	"parity_unsignedTransactionsCount",
+	"parity_upgradeReady",
+	"parity_verifySignature",
+	"parity_versionInfo",
+	"parity_wsUrl",
+];
+
+/// Whether the params of given method are known to carry no secrets. Params of any other method
+/// are never logged, so that new methods taking secrets are redacted until they are reviewed.
+fn has_public_params(method: &str) -> bool {
+	PUBLIC_NAMESPACES.iter().any(|namespace| method.starts_with(namespace))
+		|| PUBLIC_METHODS.contains(&method)
+}
+
+impl RequestSummary {
+	fn new(request: &core::Request) -> Self {
+		fn params(method: &str, params: &core::Params) -> String {
+			match has_public_params(method) {
+				true => serde_json::to_string(params).unwrap_or_default(),
+				false => "<redacted>".into(),
+			}
+		}
+
+		let calls = match *request {
+			core::Request::Single(ref call) => vec![call],
+			core::Request::Batch(ref calls) => calls.iter().collect(),
+		};
+		let (methods, params): (Vec<_>, Vec<_>) = calls.into_iter().map(|call| match *call {
+			core::Call::MethodCall(ref call) => (call.method.as_str(), params(&call.method, &call.params)),
+			core::Call::Notification(ref notification) => (notification.method.as_str(), params(&notification.method, &notification.params)),
+			core::Call::Invalid { .. } => ("<invalid>", String::new()),
+		}).unzip();
+
+		RequestSummary {
+			methods: methods.join(","),
+			params: params.join(","),
+		}
+	}
+}
+
+fn outcome(response: &Option<core::Response>) -> String {
+	fn output(output: &core::Output) -> String {
+		match *output {
+			core::Output::Success(_) => "ok".into(),
+			core::Output::Failure(ref failure) => format!("error {}", failure.error.code.code()),
+		}
+	}
+
+	match *response {
+		None => "no response".into(),
+		Some(core::Response::Single(ref single)) => output(single),
+		Some(core::Response::Batch(ref outputs)) => outputs.iter().map(output).collect::<Vec<_>>().join(","),
+	}
+}
+
 /// Stats-counting RPC middleware
 pub struct Middleware<T: ActivityNotifier = ClientNotifier> {
 	stats: Arc<RpcStats>,
 	notifier: T,
+	logging: RpcLogging,
+	names: Option<Arc<NameResolution>>,
+	addresses: Option<AddressFormat>,
 }
 
 impl<T: ActivityNotifier> Middleware<T> {
-	/// Create new Middleware with stats counter and activity notifier.
-	pub fn new(stats: Arc<RpcStats>, notifier: T) -> Self {
+	/// Create new Middleware with stats counter, activity notifier and request logging.
+	pub fn new(stats: Arc<RpcStats>, notifier: T, logging: RpcLogging) -> Self {
 		Middleware {
 			stats,
 			notifier,
+			logging,
+			names: None,
+			addresses: None,
 		}
 	}
+
+	/// Resolve names given in place of addresses before requests are handled.
+	pub fn with_name_resolution(mut self, names: Option<Arc<NameResolution>>) -> Self {
+		self.names = names;
+		self
+	}
+
+	/// Accept ICAP addresses in requests and checksum the addresses in responses.
+	pub fn with_address_format(mut self, addresses: AddressFormat) -> Self {
+		self.addresses = Some(addresses);
+		self
+	}
 }
 
 impl<M: core::Metadata, T: ActivityNotifier> core::Middleware<M> for Middleware<T> {
 	type Future = core::FutureResponse;
 	type CallFuture = core::middleware::NoopCallFuture;
 
-	fn on_request<F, X>(&self, request: core::Request, meta: M, process: F) -> Either<Self::Future, X> where
+	fn on_request<F, X>(&self, mut request: core::Request, meta: M, process: F) -> Either<Self::Future, X> where
 		F: FnOnce(core::Request, M) -> X,
 		X: core::futures::Future<Item=Option<core::Response>, Error=()> + Send + 'static,
 	{
 		let start = time::Instant::now();
 
+		let response_format = match self.addresses.map(|addresses| addresses.format_request(&mut request)) {
+			Some(Err(response)) => return Either::A(Box::new(future::ok(response))),
+			Some(Ok(response_format)) => Some(response_format),
+			None => None,
+		};
+
+		if let Some(ref names) = self.names {
+			names.resolve_request(&mut request);
+		}
+
 		self.notifier.active();
 		self.stats.count_request();
 
@@ -217,12 +410,27 @@ impl<M: core::Metadata, T: ActivityNotifier> core::Middleware<M> for Middleware<
 			_ => None,
 		};
 		let stats = self.stats.clone();
+		let logging = self.logging.clone();
+		let summary = if logging.is_enabled() { Some(RequestSummary::new(&request)) } else { None };
 
 		let future = process(request, meta).map(move |res| {
-			let time = start.elapsed().as_micros();
+			let res = match response_format {
+				Some(response_format) => response_format.apply(res),
+				None => res,
+			};
+			let elapsed = start.elapsed();
+			let time = elapsed.as_micros();
 			if time > 10_000 {
 				debug!(target: "rpc", "[{:?}] Took {}ms", id, time / 1_000);
 			}
+			if let Some(summary) = summary {
+				if logging.access {
+					info!(target: "rpc", "{} params: {} bytes, took {}ms: {}", summary.methods, summary.params.len(), elapsed.as_millis(), outcome(&res));
+				}
+				if let Some(threshold) = logging.slow_threshold.filter(|threshold| elapsed > *threshold) {
+					warn!(target: "rpc", "Slow request {} took {}ms (over {}ms), params: {}", summary.methods, elapsed.as_millis(), threshold.as_millis(), summary.params);
+				}
+			}
 			stats.add_roundtrip(time);
 			res
 		});
@@ -246,7 +454,9 @@ impl ActivityNotifier for ClientNotifier {
 #[cfg(test)]
 mod tests {
 
-	use super::{RateCalculator, StatsCalculator, RpcStats};
+	use jsonrpc_core as core;
+	use serde_json;
+	use super::{RateCalculator, StatsCalculator, RpcStats, RequestSummary, outcome};
 
 	#[test]
 	fn should_calculate_rate() {
@@ -303,6 +513,38 @@ mod tests {
 		assert_eq!(stats.approximated_roundtrip(), 125);
 	}
 
+	#[test]
+	fn should_summarize_requests_for_logging() {
+		let request: core::Request = serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","method":"eth_getBalance","params":["0x0000000000000000000000000000000000000001","latest"],"id":1},
+			{"jsonrpc":"2.0","method":"eth_blockNumber","id":2}
+		]"#).unwrap();
+		let summary = RequestSummary::new(&request);
+		assert_eq!(summary.methods, "eth_getBalance,eth_blockNumber");
+		assert_eq!(summary.params, r#"["0x0000000000000000000000000000000000000001","latest"],null"#);
+
+		let response: core::Response = serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","result":"0x0","id":1},
+			{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}
+		]"#).unwrap();
+		assert_eq!(outcome(&Some(response)), "ok,error -32601");
+		assert_eq!(outcome(&None), "no response");
+	}
+
+	#[test]
+	fn should_redact_params_not_known_to_be_public() {
+		let request: core::Request = serde_json::from_str(r#"[
+			{"jsonrpc":"2.0","method":"personal_unlockAccount","params":["0x0000000000000000000000000000000000000001","hunter2",null],"id":1},
+			{"jsonrpc":"2.0","method":"parity_newAccountFromPhrase","params":["phrase","hunter2"],"id":2},
+			{"jsonrpc":"2.0","method":"parity_someFutureMethod","params":["hunter2"],"id":3},
+			{"jsonrpc":"2.0","method":"eth_blockNumber","params":[],"id":4},
+			{"jsonrpc":"2.0","method":"parity_setRpcHosts","params":[["localhost"]],"id":5}
+		]"#).unwrap();
+		let summary = RequestSummary::new(&request);
+		assert_eq!(summary.methods, "personal_unlockAccount,parity_newAccountFromPhrase,parity_someFutureMethod,eth_blockNumber,parity_setRpcHosts");
+		assert_eq!(summary.params, r#"<redacted>,<redacted>,<redacted>,[],[["localhost"]]"#);
+	}
+
 	#[test]
 	fn should_be_sync_and_send() {
 		let stats = RpcStats::default();
@@ -43,11 +43,13 @@ pub mod traits;
 
 pub use self::traits::{Debug, Eth, EthFilter, EthPubSub, EthSigning, Net, Parity, ParityAccountsInfo, ParityAccounts, ParitySet, ParitySetAccounts, ParitySigning, Personal, PubSub, Private, Rpc, SecretStore, Signer, Traces, Web3};
 pub use self::impls::*;
-pub use self::helpers::{NetworkSettings, block_import, dispatch};
+pub use self::helpers::call_cache::CallCache;
+pub use self::helpers::heavy_calls::{HeavyCalls, DEFAULT_HEAVY_CALLS};
+pub use self::helpers::{AbiRegistry, AddressFormat, NameResolution, NetworkSettings, PrivateRelay, RpcModules, block_import, dispatch, name_resolution};
 pub use self::metadata::Metadata;
 pub use self::types::Origin;
 pub use self::types::pubsub::PubSubSyncStatus;
-pub use self::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher};
+pub use self::extractors::{RpcExtractor, WsExtractor, WsStats, WsDispatcher, HttpAccessControl, HttpDispatcher};
 
 /// Signer utilities
 pub mod signer {
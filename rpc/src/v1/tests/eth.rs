@@ -39,6 +39,7 @@ use types::{
 
 use jsonrpc_core::IoHandler;
 use v1::helpers::dispatch::{self, FullDispatcher};
+use v1::helpers::call_cache::CallCache;
 use v1::helpers::nonce;
 use v1::impls::{EthClient, EthClientOptions, SigningUnsafeClient};
 use v1::metadata::Metadata;
@@ -137,20 +138,23 @@ impl EthTester {
 			&accounts,
 			&miner_service,
 			&external_miner,
+			&Default::default(),
+			&Arc::new(CallCache::new(0)),
 			EthClientOptions {
 				pending_nonce_from_queue: false,
 				allow_pending_receipt_query: true,
 				send_block_number_in_get_work: true,
 				gas_price_percentile: 50,
+				gas_price_blocks: 100,
 				allow_experimental_rpcs: true,
 				allow_missing_blocks: false,
-				no_ancient_blocks: false
+				no_ancient_blocks: false,
 			},
 		);
 
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-		let dispatcher = FullDispatcher::new(client.clone(), miner_service.clone(), reservations, 50);
+		let dispatcher = FullDispatcher::new(client.clone(), miner_service.clone(), reservations, 50, 100);
 		let signer = Arc::new(dispatch::Signer::new(account_provider.clone())) as _;
 		let eth_sign = SigningUnsafeClient::new(
 			&signer,
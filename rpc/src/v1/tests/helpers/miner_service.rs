@@ -24,7 +24,7 @@ use client_traits::{Nonce, StateClient, ForceUpdateSealing};
 use engine::{Engine, signer::EngineSigner};
 use ethcore::block::SealedBlock;
 use ethcore::client::{PrepareOpenBlock, EngineInfo};
-use ethcore::miner::{self, MinerService, AuthoringParams, FilterOptions};
+use ethcore::miner::{self, MinerService, MinerStats, AuthoringParams, FilterOptions};
 use ethcore::test_helpers::TestState;
 use ethereum_types::{H256, U256, Address};
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
@@ -47,6 +47,8 @@ pub struct TestMinerService {
 	pub imported_transactions: Mutex<Vec<SignedTransaction>>,
 	/// Pre-existed pending transactions
 	pub pending_transactions: Mutex<HashMap<H256, SignedTransaction>>,
+	/// Pre-existed queued transactions with an activation condition
+	pub conditional_transactions: Mutex<HashMap<H256, PendingTransaction>>,
 	/// Pre-existed local transactions
 	pub local_transactions: Mutex<BTreeMap<H256, LocalTransactionStatus>>,
 	/// Pre-existed pending receipts
@@ -66,6 +68,7 @@ impl Default for TestMinerService {
 		TestMinerService {
 			imported_transactions: Default::default(),
 			pending_transactions: Default::default(),
+			conditional_transactions: Default::default(),
 			local_transactions: Default::default(),
 			pending_receipts: Default::default(),
 			next_nonces: Default::default(),
@@ -74,6 +77,8 @@ impl Default for TestMinerService {
 				author: Address::zero(),
 				gas_range_target: (12345.into(), 54321.into()),
 				extra_data: vec![1, 2, 3, 4],
+				author_rotation: Vec::new(),
+				sealer: false,
 			}),
 			signer: RwLock::new(None),
 		}
@@ -115,6 +120,10 @@ impl MinerService for TestMinerService {
 		None
 	}
 
+	fn pending_state_and_header(&self, _latest_block_number: BlockNumber) -> Option<(Self::State, Header)> {
+		None
+	}
+
 	fn pending_block_header(&self, _latest_block_number: BlockNumber) -> Option<Header> {
 		None
 	}
@@ -129,7 +138,14 @@ impl MinerService for TestMinerService {
 
 	fn set_author<T: Into<Option<miner::Author>>>(&self, author: T) {
 		let author_opt = author.into();
-		self.authoring_params.write().author = author_opt.as_ref().map(miner::Author::address).unwrap_or_default();
+		{
+			let mut params = self.authoring_params.write();
+			params.author = author_opt.as_ref().map(miner::Author::address).unwrap_or_default();
+			params.sealer = match author_opt {
+				Some(miner::Author::Sealer(_)) => true,
+				_ => false,
+			};
+		}
 		match author_opt {
 			Some(miner::Author::Sealer(signer)) => *self.signer.write() = Some(signer),
 			Some(miner::Author::External(_addr)) => (),
@@ -137,6 +153,15 @@ impl MinerService for TestMinerService {
 		}
 	}
 
+	fn set_author_rotation(&self, authors: Vec<Address>) -> Result<(), Error> {
+		let mut params = self.authoring_params.write();
+		if params.sealer && !authors.is_empty() {
+			return Err(Error::Msg("Author rotation is not available while blocks are sealed by an engine signer.".into()));
+		}
+		params.author_rotation = authors;
+		Ok(())
+	}
+
 	fn set_extra_data(&self, extra_data: Bytes) {
 		self.authoring_params.write().extra_data = extra_data;
 	}
@@ -253,9 +278,12 @@ impl MinerService for TestMinerService {
 	}
 
 	fn queued_transactions(&self) -> Vec<Arc<VerifiedTransaction>> {
+		let conditional = self.conditional_transactions.lock().values().cloned().map(|tx| {
+			Arc::new(VerifiedTransaction::from_pending_block_transaction(tx))
+		}).collect::<Vec<_>>();
 		self.pending_transactions.lock().values().cloned().map(|tx| {
 			Arc::new(VerifiedTransaction::from_pending_block_transaction(tx))
-		}).collect()
+		}).chain(conditional).collect()
 	}
 
 	fn queued_transaction_hashes(&self) -> Vec<H256> {
@@ -295,6 +323,14 @@ impl MinerService for TestMinerService {
 		}
 	}
 
+	fn stats(&self) -> MinerStats {
+		MinerStats {
+			low_gas_price: 3,
+			block_gas_limit_reached: 1,
+			..Default::default()
+		}
+	}
+
 	/// Submit `seal` as a valid solution for the header of `pow_hash`.
 	/// Will check the seal, but not actually insert the block into the chain.
 	fn submit_seal(&self, _pow_hash: H256, _seal: Vec<Bytes>) -> Result<SealedBlock, Error> {
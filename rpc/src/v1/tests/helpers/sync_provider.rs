@@ -17,6 +17,7 @@
 //! Test implementation of SyncProvider.
 
 use std::collections::BTreeMap;
+use std::time::Duration;
 use ethereum_types::{H256, H512};
 use parking_lot::RwLock;
 use network::client_version::ClientVersion;
@@ -58,6 +59,9 @@ impl TestSyncProvider {
 				num_snapshot_chunks: 0,
 				snapshot_chunks_done: 0,
 				last_imported_old_block_number: None,
+				bytes_received: 0,
+				bytes_sent: 0,
+				num_blocks_in_flight: 0,
 			}),
 			is_importing: RwLock::new(false)
 		}
@@ -85,10 +89,15 @@ impl SyncProvider for TestSyncProvider {
 				capabilities: vec!["eth/62".to_owned(), "eth/63".to_owned()],
 				remote_address: "127.0.0.1:7777".to_owned(),
 				local_address: "127.0.0.1:8888".to_owned(),
+				ping: Some(Duration::from_millis(45)),
 				eth_info: Some(EthProtocolInfo {
 					version: 62,
 					difficulty: Some(40.into()),
 					head: H256::from_low_u64_be(50),
+					head_number: Some(80),
+					latency: Some(Duration::from_millis(120)),
+					asking: Some("BlockBodies".to_owned()),
+					asking_blocks: 16,
 				}),
 				pip_info: None,
 			},
@@ -98,10 +107,15 @@ impl SyncProvider for TestSyncProvider {
 				capabilities: vec!["eth/63".to_owned(), "eth/64".to_owned()],
 				remote_address: "Handshake".to_owned(),
 				local_address: "127.0.0.1:3333".to_owned(),
+				ping: None,
 				eth_info: Some(EthProtocolInfo {
 					version: 64,
 					difficulty: None,
 					head: H256::from_low_u64_be(60),
+					head_number: None,
+					latency: None,
+					asking: None,
+					asking_blocks: 0,
 				}),
 				pip_info: None,
 			}
@@ -32,7 +32,7 @@ use rlp;
 use rustc_hex::{FromHex, ToHex};
 use sync::SyncState;
 use types::{
-	ids::{BlockId, TransactionId},
+	ids::{BlockId, TransactionId, UncleId},
 	transaction::{Transaction, Action},
 	log_entry::{LocalizedLogEntry, LogEntry},
 	receipt::{LocalizedReceipt, RichReceipt, TransactionOutcome},
@@ -40,7 +40,7 @@ use types::{
 };
 
 use jsonrpc_core::IoHandler;
-use v1::{Eth, EthClient, EthClientOptions, EthFilter, EthFilterClient};
+use v1::{CallCache, Eth, EthClient, EthClientOptions, EthFilter, EthFilterClient};
 use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestSnapshotService};
 use v1::metadata::Metadata;
 
@@ -97,7 +97,7 @@ impl EthTester {
 		let snapshot = snapshot_service();
 		let hashrates = Arc::new(Mutex::new(HashMap::new()));
 		let external_miner = Arc::new(ExternalMiner::new(hashrates.clone()));
-		let eth = EthClient::new(&client, &snapshot, &sync, &opt_ap, &miner, &external_miner, options).to_delegate();
+		let eth = EthClient::new(&client, &snapshot, &sync, &opt_ap, &miner, &external_miner, &Default::default(), &Arc::new(CallCache::new(0)), options).to_delegate();
 		let filter = EthFilterClient::new(client.clone(), miner.clone(), 60).to_delegate();
 
 		let mut io: IoHandler<Metadata> = IoHandler::default();
@@ -215,6 +215,7 @@ fn rpc_eth_logs() {
 		transaction_log_index: 0,
 		transaction_hash: H256::zero(),
 		log_index: 0,
+		removed: false,
 	}, LocalizedLogEntry {
 		block_number: 1,
 		block_hash: H256::zero(),
@@ -227,19 +228,23 @@ fn rpc_eth_logs() {
 		transaction_log_index: 1,
 		transaction_hash: H256::zero(),
 		log_index: 1,
+		removed: false,
 	}]);
 
 	let request1 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{}], "id": 1}"#;
 	let request2 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"limit":1}], "id": 1}"#;
 	let request3 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"limit":0}], "id": 1}"#;
+	let request4 = r#"{"jsonrpc": "2.0", "method": "eth_getLogs", "params": [{"limit":1,"offset":1}], "id": 1}"#;
 
 	let response1 = r#"{"jsonrpc":"2.0","result":[{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x0","removed":false,"topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x0","type":"mined"},{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x1","removed":false,"topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x1","type":"mined"}],"id":1}"#;
 	let response2 = r#"{"jsonrpc":"2.0","result":[{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x1","removed":false,"topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x1","type":"mined"}],"id":1}"#;
 	let response3 = r#"{"jsonrpc":"2.0","result":[],"id":1}"#;
+	let response4 = r#"{"jsonrpc":"2.0","result":[{"address":"0x0000000000000000000000000000000000000000","blockHash":"0x0000000000000000000000000000000000000000000000000000000000000000","blockNumber":"0x1","data":"0x010203","logIndex":"0x0","removed":false,"topics":[],"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0","transactionLogIndex":"0x0","type":"mined"}],"id":1}"#;
 
 	assert_eq!(tester.io.handle_request_sync(request1), Some(response1.to_owned()));
 	assert_eq!(tester.io.handle_request_sync(request2), Some(response2.to_owned()));
 	assert_eq!(tester.io.handle_request_sync(request3), Some(response3.to_owned()));
+	assert_eq!(tester.io.handle_request_sync(request4), Some(response4.to_owned()));
 }
 
 #[test]
@@ -273,6 +278,7 @@ fn rpc_logs_filter() {
 		transaction_log_index: 0,
 		transaction_hash: H256::zero(),
 		log_index: 0,
+		removed: false,
 	}, LocalizedLogEntry {
 		block_number: 1,
 		block_hash: H256::zero(),
@@ -285,6 +291,7 @@ fn rpc_logs_filter() {
 		transaction_log_index: 1,
 		transaction_hash: H256::zero(),
 		log_index: 1,
+		removed: false,
 	}]);
 
 	// Register filters first
@@ -611,6 +618,56 @@ fn rpc_eth_uncle_count_by_block_number() {
 	assert_eq!(EthTester::default().io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_uncle_by_block_number_and_index() {
+	let tester = EthTester::default();
+	tester.client.add_blocks(2, EachBlockWith::Uncle);
+
+	let uncle = tester.client.uncle(UncleId { block: BlockId::Number(1), position: 0 }).unwrap();
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getUncleByBlockNumberAndIndex",
+		"params": ["0x1", "0x0"],
+		"id": 1
+	}"#;
+	let response: serde_json::Value = serde_json::from_str(&tester.io.handle_request_sync(request).unwrap()).unwrap();
+	let result = &response["result"];
+	assert_eq!(result["hash"], format!("0x{:x}", uncle.hash()));
+	assert_eq!(result["parentHash"], format!("0x{:x}", tester.client.block_hash(BlockId::Number(0)).unwrap()));
+	assert_eq!(result["number"], "0x1");
+	assert_eq!(result["difficulty"], "0x1");
+	assert_eq!(result["totalDifficulty"], serde_json::Value::Null);
+	assert_eq!(result["uncles"], serde_json::Value::Array(vec![]));
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getUncleByBlockNumberAndIndex",
+		"params": ["0x1", "0x1"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_eth_uncle_by_block_hash_and_index() {
+	let tester = EthTester::default();
+	tester.client.add_blocks(2, EachBlockWith::Uncle);
+
+	let hash = tester.client.block_hash(BlockId::Number(2)).unwrap();
+	let uncle = tester.client.uncle(UncleId { block: BlockId::Number(2), position: 0 }).unwrap();
+	let request = format!(r#"{{
+		"jsonrpc": "2.0",
+		"method": "eth_getUncleByBlockHashAndIndex",
+		"params": ["0x{:x}", "0x0"],
+		"id": 1
+	}}"#, hash);
+	let response: serde_json::Value = serde_json::from_str(&tester.io.handle_request_sync(&request).unwrap()).unwrap();
+	assert_eq!(response["result"]["hash"], format!("0x{:x}", uncle.hash()));
+	assert_eq!(response["result"]["number"], "0x2");
+	assert_eq!(response["result"]["totalDifficulty"], serde_json::Value::Null);
+}
+
 #[test]
 fn rpc_eth_code() {
 	let tester = EthTester::default();
@@ -956,9 +1013,11 @@ fn rpc_eth_transaction_receipt() {
 			transaction_index: 0,
 			transaction_log_index: 0,
 			log_index: 1,
+			removed: false,
 		}],
 		log_bloom: Bloom::zero(),
 		outcome: TransactionOutcome::StateRoot(H256::zero()),
+		output: None,
 	};
 
 	let hash = H256::from_str("b903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238").unwrap();
@@ -976,6 +1035,39 @@ fn rpc_eth_transaction_receipt() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_eth_transaction_receipt_with_output() {
+	let receipt = LocalizedReceipt {
+		from: H160::from_str("b60e8dd61c5d32be8058bb8eb970870f07233155").unwrap(),
+		to: Some(H160::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()),
+		transaction_hash: H256::zero(),
+		transaction_index: 0,
+		block_hash: H256::from_str("ed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5").unwrap(),
+		block_number: 0x4510c,
+		cumulative_gas_used: U256::from(0x20),
+		gas_used: U256::from(0x10),
+		contract_address: None,
+		logs: vec![],
+		log_bloom: Bloom::zero(),
+		outcome: TransactionOutcome::StatusCode(1),
+		output: Some(vec![0x12, 0x34]),
+	};
+
+	let hash = H256::from_str("b903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238").unwrap();
+	let tester = EthTester::default();
+	tester.client.set_transaction_receipt(TransactionId::Hash(hash), receipt);
+
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "eth_getTransactionReceipt",
+		"params": ["0xb903239f8543d04b5dc1ba6579132b143087c68db1b2168786408fcbce568238"],
+		"id": 1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"blockHash":"0xed76641c68a1c641aee09a94b3b471f4dc0316efe5ac19cf488e2674cf8d05b5","blockNumber":"0x4510c","contractAddress":null,"cumulativeGasUsed":"0x20","from":"0xb60e8dd61c5d32be8058bb8eb970870f07233155","gasUsed":"0x10","logs":[],"logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","output":"0x1234","status":"0x1","to":"0xd46e8dd67c5d32be8058bb8eb970870f07244567","transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000000","transactionIndex":"0x0"},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_eth_transaction_receipt_null() {
 	let tester = EthTester::default();
@@ -114,6 +114,7 @@ fn should_subscribe_to_logs() {
 			transaction_index: 0,
 			log_index: 0,
 			transaction_log_index: 0,
+			removed: false,
 		}
 	]);
 
@@ -28,6 +28,8 @@ impl ManageNetwork for TestManageNetwork {
 	fn deny_unreserved_peers(&self) { }
 	fn remove_reserved_peer(&self, _peer: String) -> Result<(), String> { Ok(()) }
 	fn add_reserved_peer(&self, _peer: String) -> Result<(), String> { Ok(()) }
+	fn add_to_blacklist(&self, _entry: String) -> Result<(), String> { Ok(()) }
+	fn remove_from_blacklist(&self, _entry: String) -> Result<(), String> { Ok(()) }
 	fn start_network(&self) {}
 	fn stop_network(&self) {}
 	fn num_peers_range(&self) -> RangeInclusive<u32> { 25..=50 }
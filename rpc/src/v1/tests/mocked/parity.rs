@@ -23,14 +23,17 @@ use machine::executed::Executed;
 use miner::pool::local_transactions::Status as LocalTransactionStatus;
 use sync::ManageNetwork;
 use types::{
+	disk_usage::DiskUsage,
 	ids::TransactionId,
 	receipt::{LocalizedReceipt, TransactionOutcome},
+	reorg::Reorg,
 };
 
 use jsonrpc_core::IoHandler;
 use v1::{Parity, ParityClient};
 use v1::metadata::Metadata;
-use v1::helpers::NetworkSettings;
+use v1::helpers::{AbiRegistry, NetworkSettings};
+use v1::helpers::call_cache::{CallCache, CallKey};
 use v1::helpers::external_signer::SignerService;
 use v1::tests::helpers::{TestSyncProvider, Config, TestMinerService, TestUpdater};
 use super::manage_network::TestManageNetwork;
@@ -47,6 +50,8 @@ pub struct Dependencies {
 	pub settings: Arc<NetworkSettings>,
 	pub network: Arc<dyn ManageNetwork>,
 	pub ws_address: Option<Host>,
+	pub abi_registry: Arc<AbiRegistry>,
+	pub call_cache: Arc<CallCache>,
 }
 
 impl Dependencies {
@@ -71,6 +76,8 @@ impl Dependencies {
 			}),
 			network: Arc::new(TestManageNetwork),
 			ws_address: Some("127.0.0.1:18546".into()),
+			abi_registry: Default::default(),
+			call_cache: Arc::new(CallCache::new(1)),
 		}
 	}
 
@@ -86,6 +93,10 @@ impl Dependencies {
 			signer,
 			self.ws_address.clone(),
 			None,
+			self.abi_registry.clone(),
+			self.call_cache.clone(),
+			100,
+			256,
 		)
 	}
 
@@ -191,6 +202,41 @@ fn rpc_parity_min_gas_price() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_miner_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_minerStats", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"blockGasLimitReached":"0x1","gasShareExceeded":"0x0","invalid":"0x0","invalidNonce":"0x0","lowGasPrice":"0x3","notAllowed":"0x0","tooManyTransactions":"0x0"},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_call_cache_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let head = H256::from_low_u64_be(1);
+	let call = ::types::transaction::Transaction {
+		value: 0.into(),
+		gas: 21_000.into(),
+		gas_price: 0.into(),
+		action: ::types::transaction::Action::Call(Address::from_low_u64_be(1)),
+		data: vec![1],
+		nonce: 0.into(),
+	}.fake_sign(Address::from_low_u64_be(2));
+	let key = CallKey::new(head, &call);
+	assert_eq!(deps.call_cache.get(&head, &key), None);
+	deps.call_cache.insert(&head, key.clone(), vec![42]);
+	assert_eq!(deps.call_cache.get(&head, &key), Some(vec![42]));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_callCacheStats", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"hits":"0x1","misses":"0x1"},"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_dev_logs() {
 	let deps = Dependencies::new();
@@ -255,7 +301,7 @@ fn rpc_parity_net_peers() {
 	let io = deps.default_client();
 
 	let request = r#"{"jsonrpc": "2.0", "method": "parity_netPeers", "params":[], "id": 1}"#;
-	let response = r#"{"jsonrpc":"2.0","result":{"active":0,"connected":120,"max":50,"peers":[{"caps":["eth/62","eth/63"],"id":"node1","name":{"ParityClient":{"can_handle_large_requests":true,"compiler":"rustc","identity":"1","name":"Parity-Ethereum","os":"linux","semver":"2.4.0"}},"network":{"localAddress":"127.0.0.1:8888","remoteAddress":"127.0.0.1:7777"},"protocols":{"eth":{"difficulty":"0x28","head":"0000000000000000000000000000000000000000000000000000000000000032","version":62},"pip":null}},{"caps":["eth/63","eth/64"],"id":null,"name":{"ParityClient":{"can_handle_large_requests":true,"compiler":"rustc","identity":"2","name":"Parity-Ethereum","os":"linux","semver":"2.4.0"}},"network":{"localAddress":"127.0.0.1:3333","remoteAddress":"Handshake"},"protocols":{"eth":{"difficulty":null,"head":"000000000000000000000000000000000000000000000000000000000000003c","version":64},"pip":null}}]},"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"active":0,"connected":120,"max":50,"peers":[{"caps":["eth/62","eth/63"],"id":"node1","name":{"ParityClient":{"can_handle_large_requests":true,"compiler":"rustc","identity":"1","name":"Parity-Ethereum","os":"linux","semver":"2.4.0"}},"network":{"localAddress":"127.0.0.1:8888","ping":45,"remoteAddress":"127.0.0.1:7777"},"protocols":{"eth":{"asking":"BlockBodies","askingBlocks":16,"difficulty":"0x28","head":"0000000000000000000000000000000000000000000000000000000000000032","headNumber":"0x50","latency":120,"version":62},"pip":null}},{"caps":["eth/63","eth/64"],"id":null,"name":{"ParityClient":{"can_handle_large_requests":true,"compiler":"rustc","identity":"2","name":"Parity-Ethereum","os":"linux","semver":"2.4.0"}},"network":{"localAddress":"127.0.0.1:3333","ping":null,"remoteAddress":"Handshake"},"protocols":{"eth":{"asking":null,"askingBlocks":0,"difficulty":null,"head":"000000000000000000000000000000000000000000000000000000000000003c","headNumber":null,"latency":null,"version":64},"pip":null}}]},"id":1}"#;
 
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
@@ -444,6 +490,98 @@ fn rpc_parity_local_transactions() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_transaction_status() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let tx = ::types::transaction::Transaction {
+		value: 5.into(),
+		gas: 3.into(),
+		gas_price: 2.into(),
+		action: ::types::transaction::Action::Create,
+		data: vec![1, 2, 3],
+		nonce: 0.into(),
+	}.fake_sign(Address::from_low_u64_be(3));
+	let hash = tx.hash();
+	deps.miner.pending_transactions.lock().insert(hash, tx);
+
+	let request = format!(r#"{{"jsonrpc": "2.0", "method": "parity_transactionStatus", "params":["{:?}"], "id": 1}}"#, hash);
+	let future = r#"{"jsonrpc":"2.0","result":{"status":"future"},"id":1}"#;
+	let pending = r#"{"jsonrpc":"2.0","result":{"status":"pending"},"id":1}"#;
+	let unknown = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(&request), Some(future.to_owned()));
+	deps.miner.next_nonces.write().insert(Address::from_low_u64_be(3), 1.into());
+	assert_eq!(io.handle_request_sync(&request), Some(pending.to_owned()));
+	deps.miner.pending_transactions.lock().clear();
+	assert_eq!(io.handle_request_sync(&request), Some(unknown.to_owned()));
+}
+
+#[test]
+fn rpc_parity_scheduled_transactions() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let tx = ::types::transaction::Transaction {
+		value: 5.into(),
+		gas: 3.into(),
+		gas_price: 2.into(),
+		action: ::types::transaction::Action::Create,
+		data: vec![1, 2, 3],
+		nonce: 0.into(),
+	}.fake_sign(Address::from_low_u64_be(3));
+	deps.miner.pending_transactions.lock().insert(tx.hash(), tx);
+
+	// transactions without an activation condition are never scheduled
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_scheduledTransactions", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[],"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_scheduled_transactions_with_condition() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let tx = |nonce: u64, condition| ::types::transaction::PendingTransaction::new(::types::transaction::Transaction {
+		value: 5.into(),
+		gas: 3.into(),
+		gas_price: 2.into(),
+		action: ::types::transaction::Action::Create,
+		data: vec![1, 2, 3],
+		nonce: nonce.into(),
+	}.fake_sign(Address::from_low_u64_be(3)), condition);
+	let scheduled = tx(0, Some(::types::transaction::Condition::Number(100)));
+	let activated = tx(1, Some(::types::transaction::Condition::Number(0)));
+	deps.miner.conditional_transactions.lock().insert(scheduled.hash(), scheduled.clone());
+	deps.miner.conditional_transactions.lock().insert(activated.hash(), activated.clone());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_scheduledTransactions", "params":[], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+
+	assert!(response.contains(&format!("{:?}", scheduled.hash())));
+	assert!(response.contains(r#""condition":{"block":100}"#));
+	assert!(!response.contains(&format!("{:?}", activated.hash())));
+}
+
+#[test]
+fn rpc_parity_storage_root_and_code_hash() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	deps.client.set_code(Address::from_low_u64_be(1), vec![]);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_codeHash", "params":["0x0000000000000000000000000000000000000001"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470","id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_codeHash", "params":["0x0000000000000000000000000000000000000002"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_storageRoot", "params":["0x0000000000000000000000000000000000000001", "latest"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_chain_status() {
 	let deps = Dependencies::new();
@@ -458,6 +596,44 @@ fn rpc_parity_chain_status() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_db_stats() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dbStats", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"diskUsage":null,"earliestChain":"0x1","earliestState":"0x0","pruning":"archive"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	*deps.client.disk_usage.write() = Some(DiskUsage {
+		key_value: 1024,
+		state: 512,
+		blocks: 256,
+		extras: 128,
+		traces: 64,
+		blooms: 16,
+		trace_blooms: 8,
+	});
+	let response = r#"{"jsonrpc":"2.0","result":{"diskUsage":{"blocks":"0x100","blooms":"0x10","extras":"0x80","keyValue":"0x400","state":"0x200","total":"0x418","traceBlooms":"0x8","traces":"0x40"},"earliestChain":"0x1","earliestState":"0x0","pruning":"archive"},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_reorg_history() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	*deps.client.reorgs.write() = vec![
+		Reorg { ancestor: H256::from_low_u64_be(1), retracted: vec![H256::from_low_u64_be(2)], enacted: vec![H256::from_low_u64_be(3)], timestamp: 20 },
+		Reorg { ancestor: H256::from_low_u64_be(4), retracted: vec![], enacted: vec![], timestamp: 10 },
+	];
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_reorgHistory", "params":[1], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"ancestor":"0x0000000000000000000000000000000000000000000000000000000000000001","enacted":["0x0000000000000000000000000000000000000000000000000000000000000003"],"retracted":["0x0000000000000000000000000000000000000000000000000000000000000002"],"timestamp":"0x14"}],"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_node_kind() {
 	let deps = Dependencies::new();
@@ -532,6 +708,7 @@ fn rpc_parity_block_receipts() {
 			logs: vec![],
 			log_bloom: Bloom::from_low_u64_be(1),
 			outcome: TransactionOutcome::Unknown,
+			output: None,
 			to: None,
 			from: Address::from_low_u64_be(9),
 		});
@@ -620,3 +797,74 @@ fn rpc_parity_verify_signature() {
 
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
+
+#[test]
+fn rpc_parity_decode_transaction_input() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_decodeTransactionInput", "params":["0x0000000000000000000000000000000000000001", "0xa9059cbb000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000003e8"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	deps.abi_registry.register(Address::from_low_u64_be(1), r#"[{"type":"function","name":"transfer","constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"outputs":[]}]"#).unwrap();
+	let response = r#"{"jsonrpc":"2.0","result":{"function":"transfer(address,uint256)","params":[{"name":"to","type":"address","value":"0x0000000000000000000000000000000000000002"},{"name":"value","type":"uint256","value":"1000"}]},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_schedule_at() {
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_scheduleAt", "params":["0x0"], "id": 1}"#;
+	let response: ::serde_json::Value = ::serde_json::from_str(&io.handle_request_sync(request).unwrap()).unwrap();
+	let schedule = &response["result"];
+	assert_eq!(schedule["blockNumber"], 0);
+	assert!(schedule["activatedTransitions"].as_array().unwrap().contains(&"eip150Transition".into()));
+	assert_eq!(schedule["haveRevert"], false);
+	assert_eq!(schedule["callGas"], 700);
+	assert_eq!(schedule["txGas"], 21000);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_scheduleAt", "params":["pending"], "id": 1}"#;
+	let response: ::serde_json::Value = ::serde_json::from_str(&io.handle_request_sync(request).unwrap()).unwrap();
+	assert_eq!(response["result"]["blockNumber"], 1);
+}
+
+#[test]
+fn rpc_parity_check_transaction_included() {
+	use rustc_hex::ToHex;
+
+	let deps = Dependencies::new();
+	let io = deps.default_client();
+	let keypair = Random.generate().unwrap();
+	let sign = |value: u64| ::types::transaction::Transaction {
+		value: value.into(),
+		gas: 21_000.into(),
+		gas_price: 1.into(),
+		action: ::types::transaction::Action::Create,
+		data: vec![],
+		nonce: 0.into(),
+	}.sign(keypair.secret(), None);
+	let status = |request: &str| {
+		let response: ::serde_json::Value = ::serde_json::from_str(&io.handle_request_sync(request).unwrap()).unwrap();
+		response["result"]["status"].clone()
+	};
+
+	let transaction = sign(1);
+	let request = format!(
+		r#"{{"jsonrpc": "2.0", "method": "parity_checkTransactionIncluded", "params":["0x{}"], "id": 1}}"#,
+		::rlp::encode(&transaction).to_hex(),
+	);
+	assert_eq!(status(&request), "notFound");
+
+	let conflicting = sign(2);
+	deps.miner.pending_transactions.lock().insert(conflicting.hash(), conflicting);
+	assert_eq!(status(&request), "conflictPending");
+
+	deps.miner.pending_transactions.lock().insert(transaction.hash(), transaction.clone());
+	assert_eq!(status(&request), "pending");
+
+	deps.miner.pending_transactions.lock().clear();
+	deps.client.set_nonce(keypair.address(), 1.into());
+	assert_eq!(status(&request), "conflictMined");
+}
@@ -217,6 +217,30 @@ fn should_be_able_to_remove_address() {
 	assert_eq!(res, Some(response.into()));
 }
 
+#[test]
+fn should_be_able_to_export_and_import_address_book() {
+	let tester = setup();
+	tester.accounts.set_address_name(Address::from_low_u64_be(1), "One".into());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_exportAddressBook", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"{\"0x0000000000000000000000000000000000000001\":{\"name\":\"One\",\"meta\":\"{}\",\"uuid\":null}}","id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.into()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_importAddressBook", "params": ["{\"0x0000000000000000000000000000000000000002\":{\"name\":\"Two\",\"meta\":\"{}\",\"uuid\":null},\"0x0000000000000000000000000000000000000001\":{\"name\":\"Uno\",\"meta\":\"{}\",\"uuid\":null}}"], "id": 2}"#;
+	let response = r#"{"jsonrpc":"2.0","result":["0x0000000000000000000000000000000000000001","0x0000000000000000000000000000000000000002"],"id":2}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.into()));
+	assert_eq!(tester.accounts.addresses_info()[&Address::from_low_u64_be(1)].name, "Uno");
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_importAddressBook", "params": ["[]"], "id": 3}"#;
+	let res = tester.io.handle_request_sync(request).unwrap();
+	assert!(res.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32023,"message":"Could not import address book.""#), "{}", res);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_clearAddressBook", "params": [], "id": 4}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":4}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.into()));
+	assert!(tester.accounts.addresses_info().is_empty());
+}
+
 #[test]
 fn rpc_parity_new_vault() {
 	let tempdir = TempDir::new("").unwrap();
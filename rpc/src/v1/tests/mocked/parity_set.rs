@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::env;
 use std::sync::Arc;
 use std::str::FromStr;
 use rustc_hex::FromHex;
@@ -24,7 +25,8 @@ use ethcore::test_helpers::TestBlockChainClient;
 use sync::ManageNetwork;
 
 use jsonrpc_core::IoHandler;
-use v1::{ParitySet, ParitySetClient};
+use v1::{ParitySet, ParitySetClient, RpcModules};
+use v1::helpers::PrivateRelay;
 use v1::tests::helpers::{TestMinerService, TestUpdater};
 use super::manage_network::TestManageNetwork;
 
@@ -59,7 +61,11 @@ fn parity_set_client(
 		miner,
 		updater,
 		&(net.clone() as Arc<dyn ManageNetwork>),
+		&Arc::new(RpcModules::default()),
 		FakeFetch::new(Some(1)),
+		None,
+		&Default::default(),
+		env::temp_dir(),
 	)
 }
 
@@ -169,6 +175,26 @@ fn rpc_parity_set_extra_data() {
 	assert_eq!(miner.authoring_params().extra_data, "cd1722f3947def4cf144679da39c4c32bdc35681".from_hex().unwrap());
 }
 
+#[test]
+fn rpc_parity_set_extra_data_with_version() {
+	use version::raw_package_info;
+
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	// "pool/{version}"
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setExtraData", "params":["0x706f6f6c2f7b76657273696f6e7d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert_eq!(miner.authoring_params().extra_data, format!("pool/{}", raw_package_info().1).into_bytes());
+}
+
 #[test]
 fn rpc_parity_set_author() {
 	let miner = miner_service();
@@ -185,6 +211,24 @@ fn rpc_parity_set_author() {
 	assert_eq!(miner.authoring_params().author, Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap());
 }
 
+#[test]
+fn rpc_parity_set_author_rotation() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setAuthorRotation", "params":[["0x0000000000000000000000000000000000000001","0x0000000000000000000000000000000000000002"]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	let params = miner.authoring_params();
+	assert_eq!(params.author_for(1), Address::from_low_u64_be(2));
+	assert_eq!(params.author_for(2), Address::from_low_u64_be(1));
+}
+
 #[test]
 fn rpc_parity_set_transactions_limit() {
 	let miner = miner_service();
@@ -200,6 +244,39 @@ fn rpc_parity_set_transactions_limit() {
 	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_parity_blacklist() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_addToBlacklist", "params":["10.0.0.0/8"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_removeFromBlacklist", "params":["10.0.0.0/8"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_clear_bad_blocks() {
+	let miner = miner_service();
+	let client = client_service();
+	let network = network_service();
+	let updater = updater_service();
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client, &miner, &updater, &network).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_clearBadBlocks", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_parity_set_hash_content() {
 	let miner = miner_service();
@@ -268,3 +345,127 @@ fn rpc_parity_set_engine_signer() {
 	let signature = miner.signer.read().as_ref().unwrap().sign(::hash::keccak("x")).unwrap().to_vec();
 	assert_eq!(&format!("{}", signature.pretty()), "6f46069ded2154af6e806706e4f7f6fd310ac45f3c6dccb85f11c0059ee20a09245df0a0008bb84a10882b1298284bc93058e7bc5938ea728e77620061687a6401");
 }
+
+#[test]
+fn rpc_parity_set_rpc_modules() {
+	use parking_lot::Mutex;
+
+	let rpc_modules = Arc::new(RpcModules::default());
+	let mut io = IoHandler::new();
+	io.extend_with(ParitySetClient::new(
+		&client_service(),
+		&miner_service(),
+		&updater_service(),
+		&(network_service() as Arc<dyn ManageNetwork>),
+		&rpc_modules,
+		FakeFetch::new(Some(1)),
+		None,
+		&Default::default(),
+		env::temp_dir(),
+	).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setRpcModules", "params":[["eth", "net"]], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: modules","data":"\"HTTP JSON-RPC server is not running\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let reloaded = Arc::new(Mutex::new(None));
+	let r = reloaded.clone();
+	rpc_modules.set_reload(move |modules| {
+		*r.lock() = Some(modules.to_owned());
+		Ok(())
+	});
+
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert_eq!(reloaded.lock().as_ref().map(String::as_str), Some("eth,net"));
+
+	let r = reloaded.clone();
+	rpc_modules.set_reload_cors(move |cors| {
+		*r.lock() = Some(cors.to_owned());
+		Ok(())
+	});
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setRpcCors", "params":["http://localhost:3000"], "id": 1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	assert_eq!(reloaded.lock().as_ref().map(String::as_str), Some("http://localhost:3000"));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_setRpcHosts", "params":["none"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: hosts","data":"\"HTTP JSON-RPC server is not running\""},"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+}
+
+#[test]
+fn rpc_parity_send_private_transaction() {
+	let raw = "f85f800182520894095e7baea6a6c7c4c2dfeb977efac326af552d870a801ba048b55bfa915ac795c431978d8a6a992b628d557da5ff759b307d495a36649353a0efffd310ac743f371de3b9f7f9cb56c0b28ad43601b4ab949f53faa07bd2c804";
+	let hash = ::hash::keccak(raw.from_hex().unwrap());
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_sendPrivateTransaction", "params":["0x"#.to_owned() + raw + r#""], "id": 1}"#;
+
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client_service(), &miner_service(), &updater_service(), &network_service()).to_delegate());
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32000,"message":"No private transaction relay configured.","data":"Start the node with --private-tx-relay to enable it."},"id":1}"#;
+	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
+
+	let miner = miner_service();
+	let mut io = IoHandler::new();
+	io.extend_with(ParitySetClient::new(
+		&client_service(),
+		&miner,
+		&updater_service(),
+		&(network_service() as Arc<dyn ManageNetwork>),
+		&Arc::new(RpcModules::default()),
+		FakeFetch::new(Some(1)),
+		Some(Arc::new(PrivateRelay::new("http://localhost:8545".into()))),
+		&Default::default(),
+		env::temp_dir(),
+	).to_delegate());
+
+	let response = r#"{"jsonrpc":"2.0","result":"0x"#.to_owned() + &format!("{:x}", hash) + r#"","id":1}"#;
+	assert_eq!(io.handle_request_sync(&request), Some(response));
+	assert!(miner.imported_transactions.lock().is_empty());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_relayedTransactions", "params":[], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":[{"blockHash":null,"blockNumber":null,"hash":"0x"#.to_owned() + &format!("{:x}", hash) + r#"","relayedAt":"0x0"}],"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response));
+}
+
+#[test]
+fn rpc_parity_register_abi() {
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client_service(), &miner_service(), &updater_service(), &network_service()).to_delegate());
+
+	let abi = r#"[{\"type\":\"function\",\"name\":\"transfer\",\"constant\":false,\"inputs\":[{\"name\":\"to\",\"type\":\"address\"},{\"name\":\"value\",\"type\":\"uint256\"}],\"outputs\":[]}]"#;
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_registerAbi", "params":["0x0000000000000000000000000000000000000001", ""#.to_owned() + abi + r#""], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(&request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_unregisterAbi", "params":["0x0000000000000000000000000000000000000001"], "id": 1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+	let response = r#"{"jsonrpc":"2.0","result":false,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request), Some(response.to_owned()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_registerAbi", "params":["0x0000000000000000000000000000000000000001", "{"], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: abi""#));
+}
+
+#[test]
+fn rpc_parity_dump_state() {
+	let mut io = IoHandler::new();
+	io.extend_with(parity_set_client(&client_service(), &miner_service(), &updater_service(), &network_service()).to_delegate());
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dumpState", "params":["/tmp/state.csv", "csv"], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: name""#));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dumpState", "params":["state.xml", "xml"], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: format""#));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dumpState", "params":["state.csv", "csv", "pending"], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: block""#));
+
+	// The test client doesn't keep any state.
+	let request = r#"{"jsonrpc": "2.0", "method": "parity_dumpState", "params":["state.csv", "csv"], "id": 1}"#;
+	let response = io.handle_request_sync(request).unwrap();
+	assert!(response.starts_with(r#"{"jsonrpc":"2.0","error":{"code":-32000,"#));
+}
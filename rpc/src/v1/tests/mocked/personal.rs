@@ -73,7 +73,7 @@ fn setup_with(c: Config) -> PersonalTester {
 	let miner = miner_service();
 	let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50);
+	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50, 100);
 	let personal = PersonalClient::new(&accounts, dispatcher, false, c.allow_experimental_rpcs);
 
 	let mut io = IoHandler::default();
@@ -256,6 +256,77 @@ fn sign_and_send_test(method: &str) {
 	assert_eq!(tester.io.handle_request_sync(request.as_ref()), Some(response));
 }
 
+#[test]
+fn send_transactions_batch() {
+	let tester = setup();
+	let address = tester.accounts.new_account(&"password123".into()).unwrap();
+	let batch = |password: &str| r#"{
+		"jsonrpc": "2.0",
+		"method": "parity_sendTransactionsBatch",
+		"params": [[{
+			"from": ""#.to_owned() + format!("0x{:x}", address).as_ref() + r#"",
+			"to": "0xd46e8dd67c5d32be8058bb8eb970870f07244567",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"value": "0x9184e72a"
+		}, {
+			"from": ""# + format!("0x{:x}", address).as_ref() + r#"",
+			"gas": "0x76c0",
+			"gasPrice": "0x9184e72a000",
+			"data": "0x01"
+		}], ""# + password + r#""],
+		"id": 1
+	}"#;
+
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32021,"message":"Account password is invalid or account does not exist.","data":"SStore(InvalidPassword)"},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&batch("password321")), Some(response.into()));
+	assert!(tester.miner.imported_transactions.lock().is_empty());
+
+	let transactions = vec![
+		Transaction {
+			nonce: U256::zero(),
+			gas_price: U256::from(0x9184e72a000u64),
+			gas: U256::from(0x76c0),
+			action: Action::Call(Address::from_str("d46e8dd67c5d32be8058bb8eb970870f07244567").unwrap()),
+			value: U256::from(0x9184e72au64),
+			data: vec![]
+		},
+		Transaction {
+			nonce: U256::one(),
+			gas_price: U256::from(0x9184e72a000u64),
+			gas: U256::from(0x76c0),
+			action: Action::Create,
+			value: U256::zero(),
+			data: vec![1]
+		},
+	];
+	let hashes: Vec<_> = transactions.into_iter().map(|t| {
+		let signature = tester.accounts.sign(address, Some("password123".into()), t.hash(None)).unwrap();
+		format!("\"0x{:x}\"", t.with_signature(signature, None).hash())
+	}).collect();
+
+	let response = r#"{"jsonrpc":"2.0","result":["#.to_owned() + &hashes.join(",") + r#"],"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(&batch("password123")), Some(response));
+	assert_eq!(tester.miner.imported_transactions.lock().len(), 2);
+}
+
+#[test]
+fn send_transactions_batch_requires_single_sender() {
+	let tester = setup();
+	let request = r#"{
+		"jsonrpc": "2.0",
+		"method": "parity_sendTransactionsBatch",
+		"params": [[
+			{"from": "0x0000000000000000000000000000000000000001"},
+			{"from": "0x0000000000000000000000000000000000000002"}
+		], "password123"],
+		"id": 1
+	}"#;
+
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Couldn't parse parameters: from","data":"\"All transactions in a batch must have the same sender.\""},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.into()));
+}
+
 #[test]
 fn ec_recover() {
 	let tester = setup();
@@ -20,6 +20,7 @@ use ethereum_types::{H520, U256, Address};
 use bytes::ToPretty;
 
 use accounts::AccountProvider;
+use crypto::publickey::{Generator, Random};
 use ethcore::test_helpers::TestBlockChainClient;
 use parity_runtime::Runtime;
 use parking_lot::Mutex;
@@ -66,7 +67,7 @@ fn signer_tester() -> SignerTester {
 	let miner = miner_service();
 	let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 
-	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50);
+	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50, 100);
 	let mut io = IoHandler::default();
 	io.extend_with(SignerClient::new(account_signer, dispatcher, &signer, runtime.executor()).to_delegate());
 
@@ -540,6 +541,47 @@ fn should_confirm_decrypt_with_phrase() {
 	assert_eq!(tester.miner.imported_transactions.lock().len(), 0);
 }
 
+#[test]
+fn should_export_secret_only_after_confirmation_with_password() {
+	// given
+	let tester = signer_tester();
+	let key_pair = Random.generate().unwrap();
+	let address = tester.accounts.insert_account(key_pair.secret().clone(), &"test".into()).unwrap();
+
+	// when
+	let request = r#"{
+		"jsonrpc":"2.0",
+		"method":"signer_requestSecretExport",
+		"params":[""#.to_owned() + &format!("0x{:x}", address) + r#""],
+		"id":1
+	}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x1","id":1}"#;
+
+	// then
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert_eq!(tester.signer.requests().len(), 1);
+	assert!(tester.accounts.secret_exports().is_empty());
+
+	// raw confirmations and wrong passwords leave the request in the queue
+	let request = r#"{"jsonrpc":"2.0","method":"signer_confirmRequestRaw","params":["0x1","0x"],"id":1}"#;
+	assert!(tester.io.handle_request_sync(&request).unwrap().contains("error"));
+	let request = r#"{"jsonrpc":"2.0","method":"signer_confirmRequest","params":["0x1",{},"wrong"],"id":1}"#;
+	assert!(tester.io.handle_request_sync(&request).unwrap().contains("error"));
+	assert_eq!(tester.signer.requests().len(), 1);
+	assert!(tester.accounts.secret_exports().is_empty());
+
+	// when
+	let request = r#"{"jsonrpc":"2.0","method":"signer_confirmRequest","params":["0x1",{},"test"],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"#.to_owned() + &serde_json::to_string(&**key_pair.secret()).unwrap() + r#","id":1}"#;
+
+	// then
+	assert_eq!(tester.io.handle_request_sync(&request), Some(response.to_owned()));
+	assert_eq!(tester.signer.requests().len(), 0);
+	let exports = tester.accounts.secret_exports();
+	assert_eq!(exports.len(), 1);
+	assert_eq!(exports[0].address, address);
+}
+
 #[test]
 fn should_generate_new_token() {
 	// given
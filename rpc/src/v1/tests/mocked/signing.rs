@@ -61,7 +61,7 @@ impl SigningTester {
 		let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
 		let mut io = IoHandler::default();
 
-		let dispatcher = FullDispatcher::new(client.clone(), miner.clone(), reservations, 50);
+		let dispatcher = FullDispatcher::new(client.clone(), miner.clone(), reservations, 50, 100);
 
 		let executor = Executor::new_thread_per_future();
 
@@ -68,7 +68,7 @@ fn io() -> Tester {
 		state_diff: None,
 	}));
 	let miner = Arc::new(TestMinerService::default());
-	let traces = TracesClient::new(&client);
+	let traces = TracesClient::new(&client, 1);
 	let mut io = IoHandler::default();
 	io.extend_with(traces.to_delegate());
 
@@ -89,6 +89,18 @@ fn rpc_trace_filter() {
 	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
 }
 
+#[test]
+fn rpc_trace_filter_too_many_results() {
+	let tester = io();
+	let trace = tester.client.traces.read().as_ref().unwrap()[0].clone();
+	tester.client.traces.write().as_mut().unwrap().push(trace);
+
+	let request = r#"{"jsonrpc":"2.0","method":"trace_filter","params": [{}],"id":1}"#;
+	let response = r#"{"jsonrpc":"2.0","error":{"code":-32041,"message":"Too many traces match the request, at most 1 can be returned. Use `after` and `count` to page through them.","data":{"limit":1}},"id":1}"#;
+
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+}
+
 #[test]
 fn rpc_trace_filter_missing_trace() {
 	let tester = io();
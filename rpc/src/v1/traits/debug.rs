@@ -16,10 +16,13 @@
 
 //! Debug RPC interface.
 
+use ethereum_types::U256;
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 
-use v1::types::RichBlock;
+use v1::types::{
+	BlockNumber, Breakpoints, CallRequest, DebugSessionState, ReplayedBlock, RichBlock, TraceOptions,
+};
 
 /// Debug RPC interface.
 #[rpc(server)]
@@ -27,4 +30,35 @@ pub trait Debug {
 	/// Returns recently seen bad blocks.
 	#[rpc(name = "debug_getBadBlocks")]
 	fn bad_blocks(&self) -> Result<Vec<RichBlock>>;
+
+	/// Re-executes a block on top of its parent's state, returning the result of each
+	/// transaction and whether the resulting state root matches the recorded one.
+	#[rpc(name = "debug_replayBlock")]
+	fn replay_block(&self, _: BlockNumber, _: TraceOptions) -> Result<ReplayedBlock>;
+
+	/// Starts executing a call in a step debugger session, paused before its first
+	/// instruction. Returns the session id.
+	#[rpc(name = "debug_startSession")]
+	fn start_session(&self, _: CallRequest, _: Option<BlockNumber>) -> Result<U256>;
+
+	/// Replaces the breakpoints of a session.
+	#[rpc(name = "debug_setBreakpoints")]
+	fn set_breakpoints(&self, _: U256, _: Breakpoints) -> Result<bool>;
+
+	/// Returns the state of a session, waiting a few seconds for the call to pause or finish.
+	#[rpc(name = "debug_sessionState")]
+	fn session_state(&self, _: U256) -> Result<DebugSessionState>;
+
+	/// Executes the paused instruction and returns the state of the session once paused
+	/// before the next one.
+	#[rpc(name = "debug_step")]
+	fn step(&self, _: U256) -> Result<DebugSessionState>;
+
+	/// Continues to the next breakpoint and returns the state of the session.
+	#[rpc(name = "debug_resume")]
+	fn resume(&self, _: U256) -> Result<DebugSessionState>;
+
+	/// Runs the call to completion without breaking and discards the session.
+	#[rpc(name = "debug_stopSession")]
+	fn stop_session(&self, _: U256) -> Result<bool>;
 }
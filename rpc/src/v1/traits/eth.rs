@@ -19,7 +19,7 @@ use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_derive::rpc;
 use ethereum_types::{H64, H160, H256, U64, U256};
 
-use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, EthAccount};
+use v1::types::{RichBlock, BlockNumber, Bytes, CallRequest, Filter, FilterChanges, Index, EthAccount, StateOverride};
 use v1::types::{Log, Receipt, SyncStatus, Transaction, Work};
 
 /// Eth rpc interface.
@@ -119,8 +119,9 @@ pub trait Eth {
 	fn submit_transaction(&self, _: Bytes) -> Result<H256>;
 
 	/// Call contract, returning the output data.
+	/// Accounts in the optional state override set are modified before the call is executed.
 	#[rpc(name = "eth_call")]
-	fn call(&self, _: CallRequest, _: Option<BlockNumber>) -> BoxFuture<Bytes>;
+	fn call(&self, _: CallRequest, _: Option<BlockNumber>, _: Option<StateOverride>) -> BoxFuture<Bytes>;
 
 	/// Estimate gas needed for execution of given contract.
 	#[rpc(name = "eth_estimateGas")]
@@ -27,8 +27,8 @@ use v1::types::{
 	Peers, Transaction, RpcSettings, Histogram, RecoveredAccount,
 	TransactionStats, LocalTransactionStatus,
 	BlockNumber, ConsensusCapability, VersionInfo,
-	OperationsInfo, ChainStatus, Log, Filter,
-	RichHeader, Receipt,
+	OperationsInfo, ChainStatus, DbStats, Reorg, Log, Filter, MinerStats, CallCacheStats,
+	RichHeader, Receipt, DecodedCall, Schedule, TransactionInclusion,
 };
 
 /// Parity-specific rpc interface.
@@ -57,6 +57,14 @@ pub trait Parity {
 	#[rpc(name = "parity_minGasPrice")]
 	fn min_gas_price(&self) -> Result<U256>;
 
+	/// Returns the number of transactions left out of authored blocks, by reason.
+	#[rpc(name = "parity_minerStats")]
+	fn miner_stats(&self) -> Result<MinerStats>;
+
+	/// Returns `eth_call` cache hits and misses since startup.
+	#[rpc(name = "parity_callCacheStats")]
+	fn call_cache_stats(&self) -> Result<CallCacheStats>;
+
 	/// Returns latest logs
 	#[rpc(name = "parity_devLogs")]
 	fn dev_logs(&self) -> Result<Vec<String>>;
@@ -125,6 +133,16 @@ pub trait Parity {
 		_: Option<BlockNumber>,
 	) -> Result<Option<Vec<H256>>>;
 
+	/// Returns the storage trie root of the given address at the given block,
+	/// or null if the account doesn't exist or the state is not available.
+	#[rpc(name = "parity_storageRoot")]
+	fn storage_root(&self, _: H160, _: Option<BlockNumber>) -> Result<Option<H256>>;
+
+	/// Returns the code hash of the given address at the given block,
+	/// or null if the account doesn't exist or the state is not available.
+	#[rpc(name = "parity_codeHash")]
+	fn code_hash(&self, _: H160, _: Option<BlockNumber>) -> Result<Option<H256>>;
+
 	/// Encrypt some data with a public key under ECIES.
 	/// First parameter is the 512-byte destination public key, second is the message.
 	#[rpc(name = "parity_encryptMessage")]
@@ -144,10 +162,20 @@ pub trait Parity {
 	#[rpc(name = "parity_allTransactionHashes")]
 	fn all_transaction_hashes(&self) -> Result<Vec<H256>>;
 
-	/// Returns all future transactions from transaction queue (deprecated)
+	/// Returns transactions from transaction queue that are blocked by a nonce gap.
 	#[rpc(name = "parity_futureTransactions")]
 	fn future_transactions(&self) -> Result<Vec<Transaction>>;
 
+	/// Returns transactions from transaction queue that wait for their activation condition
+	/// (a minimal block number or timestamp) to be met.
+	#[rpc(name = "parity_scheduledTransactions")]
+	fn scheduled_transactions(&self) -> Result<Vec<Transaction>>;
+
+	/// Returns status of a transaction: pending or future when in the queue, mined when included
+	/// in the chain, or the reason it was dropped for local transactions. `null` if unknown.
+	#[rpc(name = "parity_transactionStatus")]
+	fn transaction_status(&self, _: H256) -> Result<Option<LocalTransactionStatus>>;
+
 	/// Returns propagation statistics on transactions pending in the queue.
 	#[rpc(name = "parity_pendingTransactionsStats")]
 	fn pending_transactions_stats(&self) -> Result<BTreeMap<H256, TransactionStats>>;
@@ -192,6 +220,14 @@ pub trait Parity {
 	#[rpc(name = "parity_chainStatus")]
 	fn chain_status(&self) -> Result<ChainStatus>;
 
+	/// Get the pruning mode, the earliest available block and state, and the size on disk of the databases.
+	#[rpc(name = "parity_dbStats")]
+	fn db_stats(&self) -> Result<DbStats>;
+
+	/// Get up to `count` most recent reorganizations of the canonical chain, newest first.
+	#[rpc(name = "parity_reorgHistory")]
+	fn reorg_history(&self, _: usize) -> Result<Vec<Reorg>>;
+
 	/// Get node kind info.
 	#[rpc(name = "parity_nodeKind")]
 	fn node_kind(&self) -> Result<::v1::types::NodeKind>;
@@ -247,4 +283,20 @@ pub trait Parity {
 	/// Submit raw block to be published to the network
 	#[rpc(name = "parity_submitRawBlock")]
 	fn submit_raw_block(&self, _: Bytes) -> Result<H256>;
+
+	/// Decodes the input of a transaction sent to given address, using the ABI registered for it
+	/// with `parity_registerAbi`. Returns `null` if there is no ABI or no function matches.
+	#[rpc(name = "parity_decodeTransactionInput")]
+	fn decode_transaction_input(&self, _: H160, _: Bytes) -> Result<Option<DecodedCall>>;
+
+	/// Checks whether given raw signed transaction, or another one from the same sender with the
+	/// same nonce, is already mined or pending. Only the number of recent blocks set with
+	/// `--jsonrpc-conflict-search-depth` is searched for a mined conflicting transaction.
+	#[rpc(name = "parity_checkTransactionIncluded")]
+	fn check_transaction_included(&self, _: Bytes) -> Result<TransactionInclusion>;
+
+	/// Returns the EVM gas costs and features in effect at given block, along with the spec
+	/// transitions which activated them. If no parameter is provided defaults to `latest`.
+	#[rpc(name = "parity_scheduleAt")]
+	fn schedule_at(&self, _: Option<BlockNumber>) -> Result<Schedule>;
 }
@@ -79,6 +79,19 @@ pub trait ParityAccounts {
 	#[rpc(name = "parity_removeAddress")]
 	fn remove_address(&self, _: H160) -> Result<bool>;
 
+	/// Returns the addressbook as a JSON string, suitable for `parity_importAddressBook`.
+	#[rpc(name = "parity_exportAddressBook")]
+	fn export_address_book(&self) -> Result<String>;
+
+	/// Merges addressbook entries from a JSON string, replacing existing entries for the same address.
+	/// Returns the imported addresses.
+	#[rpc(name = "parity_importAddressBook")]
+	fn import_address_book(&self, _: String) -> Result<Vec<H160>>;
+
+	/// Permanently deletes all addresses from the addressbook.
+	#[rpc(name = "parity_clearAddressBook")]
+	fn clear_address_book(&self) -> Result<bool>;
+
 	/// Set an account's name.
 	#[rpc(name = "parity_setAccountName")]
 	fn set_account_name(&self, _: H160, _: String) -> Result<bool>;
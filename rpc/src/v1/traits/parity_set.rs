@@ -20,7 +20,7 @@ use ethereum_types::{H160, H256, U256};
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 
-use v1::types::{Bytes, ReleaseInfo, Transaction};
+use v1::types::{BlockNumber, Bytes, RelayedTransaction, ReleaseInfo, Transaction};
 
 /// Parity-specific rpc interface for operations altering the account-related settings.
 #[rpc(server)]
@@ -45,7 +45,7 @@ pub trait ParitySet {
 	#[rpc(name = "parity_setGasCeilTarget")]
 	fn set_gas_ceil_target(&self, _: U256) -> Result<bool>;
 
-	/// Sets new extra data for mined blocks.
+	/// Sets new extra data for mined blocks. Any occurrence of `{version}` is replaced with the client version.
 	#[rpc(name = "parity_setExtraData")]
 	fn set_extra_data(&self, _: Bytes) -> Result<bool>;
 
@@ -53,6 +53,10 @@ pub trait ParitySet {
 	#[rpc(name = "parity_setAuthor")]
 	fn set_author(&self, _: H160) -> Result<bool>;
 
+	/// Sets authors to rotate through for mined blocks, one per block. Empty list disables rotation.
+	#[rpc(name = "parity_setAuthorRotation")]
+	fn set_author_rotation(&self, _: Vec<H160>) -> Result<bool>;
+
 	/// Sets the secret of engine signer account.
 	#[rpc(name = "parity_setEngineSignerSecret")]
 	fn set_engine_signer_secret(&self, _: H256) -> Result<bool>;
@@ -77,6 +81,15 @@ pub trait ParitySet {
 	#[rpc(name = "parity_removeReservedPeer")]
 	fn remove_reserved_peer(&self, _: String) -> Result<bool>;
 
+	/// Blacklist a node id, enode URL, IP address or CIDR range. Matching peers are
+	/// disconnected and never dialed or accepted again. Persisted across restarts.
+	#[rpc(name = "parity_addToBlacklist")]
+	fn add_to_blacklist(&self, _: String) -> Result<bool>;
+
+	/// Remove a blacklist entry.
+	#[rpc(name = "parity_removeFromBlacklist")]
+	fn remove_from_blacklist(&self, _: String) -> Result<bool>;
+
 	/// Drop all non-reserved peers.
 	#[rpc(name = "parity_dropNonReservedPeers")]
 	fn drop_non_reserved_peers(&self) -> Result<bool>;
@@ -125,4 +138,48 @@ pub trait ParitySet {
 	/// Returns `true` when transaction was removed, `false` if it was not found.
 	#[rpc(name = "parity_removeTransaction")]
 	fn remove_transaction(&self, _: H256) -> Result<Option<Transaction>>;
+
+	/// Forgets all blocks previously found to be bad, so that they may be imported again.
+	#[rpc(name = "parity_clearBadBlocks")]
+	fn clear_bad_blocks(&self) -> Result<bool>;
+
+	/// Replace the modules exposed over HTTP JSON-RPC, without restarting the server.
+	/// Accepts the same values as `--jsonrpc-apis`, e.g. `["safe", "-parity_set"]`.
+	#[rpc(name = "parity_setRpcModules")]
+	fn set_rpc_modules(&self, _: Vec<String>) -> Result<bool>;
+
+	/// Replace the origins allowed to make cross-origin requests over HTTP JSON-RPC.
+	/// Accepts the same values as `--jsonrpc-cors`, e.g. `"none"` or `"http://localhost:3000"`.
+	#[rpc(name = "parity_setRpcCors")]
+	fn set_rpc_cors(&self, _: String) -> Result<bool>;
+
+	/// Replace the hosts that HTTP JSON-RPC requests may be addressed to.
+	/// Accepts the same values as `--jsonrpc-hosts`, e.g. `"none"` or `"node.local"`.
+	#[rpc(name = "parity_setRpcHosts")]
+	fn set_rpc_hosts(&self, _: String) -> Result<bool>;
+
+	/// Sends a signed transaction to the configured private relay instead of the local
+	/// transaction queue, so it is never broadcast to peers. Returns the transaction hash.
+	#[rpc(name = "parity_sendPrivateTransaction")]
+	fn send_private_transaction(&self, _: Bytes) -> BoxFuture<H256>;
+
+	/// Lists the transactions sent with `parity_sendPrivateTransaction`, oldest first,
+	/// along with the block they were included in, if any.
+	#[rpc(name = "parity_relayedTransactions")]
+	fn relayed_transactions(&self) -> Result<Vec<RelayedTransaction>>;
+
+	/// Registers the JSON ABI of the contract at given address, replacing any previous one.
+	/// Logs it emits are then decoded under `decoded` in `eth_getLogs` and receipt responses.
+	#[rpc(name = "parity_registerAbi")]
+	fn register_abi(&self, _: H160, _: String) -> Result<bool>;
+
+	/// Removes the ABI registered for given address. Returns `false` if there was none.
+	#[rpc(name = "parity_unregisterAbi")]
+	fn unregister_abi(&self, _: H160) -> Result<bool>;
+
+	/// Starts writing the accounts of the state at given block (latest by default) to a new file of given name
+	/// in the node's state dumps directory, as `json` (default) or `csv`. The dump runs in the background and
+	/// pauses regularly to let blocks import, the state of the block isn't pruned until it completes.
+	#[rpc(name = "parity_dumpState")]
+	fn dump_state(&self, _: String, _: Option<String>, _: Option<BlockNumber>) -> Result<bool>;
 }
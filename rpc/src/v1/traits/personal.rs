@@ -68,6 +68,12 @@ pub trait Personal {
 	#[rpc(meta, name = "personal_sendTransaction")]
 	fn send_transaction(&self, _: Self::Metadata, _: TransactionRequest, _: String) -> BoxFuture<H256>;
 
+	/// Signs transactions from a single account with consecutive nonces and sends them in order,
+	/// unlocking the account once. Nothing is sent if any of them can't be signed. Sending stops at the
+	/// first rejected transaction, the error data then lists the hashes of the transactions sent before it.
+	#[rpc(meta, name = "parity_sendTransactionsBatch")]
+	fn send_transactions_batch(&self, _: Self::Metadata, _: Vec<TransactionRequest>, _: String) -> BoxFuture<Vec<H256>>;
+
 	/// @deprecated alias for `personal_sendTransaction`.
 	#[rpc(meta, name = "personal_signAndSendTransaction")]
 	fn sign_and_send_transaction(&self, _: Self::Metadata, _: TransactionRequest, _: String) -> BoxFuture<H256>;
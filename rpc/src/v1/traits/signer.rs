@@ -16,7 +16,7 @@
 
 //! Parity Signer-related rpc interface.
 
-use ethereum_types::U256;
+use ethereum_types::{H160, U256};
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use jsonrpc_derive::rpc;
@@ -50,6 +50,11 @@ pub trait Signer {
 	#[rpc(name = "signer_confirmRequestRaw")]
 	fn confirm_request_raw(&self, _: U256, _: Bytes) -> Result<ConfirmationResponse>;
 
+	/// Queue a request to export the raw secret of an account. The secret is returned
+	/// when the request is confirmed with the account password.
+	#[rpc(meta, name = "signer_requestSecretExport")]
+	fn request_secret_export(&self, _: Self::Metadata, _: H160) -> Result<U256>;
+
 	/// Reject the confirmation request.
 	#[rpc(name = "signer_rejectRequest")]
 	fn reject_request(&self, _: U256) -> Result<bool>;
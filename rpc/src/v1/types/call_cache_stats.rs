@@ -0,0 +1,39 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! `eth_call` cache statistics.
+
+use ethereum_types::U64;
+use v1::helpers::call_cache::CallCacheStats as CacheStats;
+
+/// `eth_call` cache hits and misses since startup.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallCacheStats {
+	/// Calls answered from the cache.
+	pub hits: U64,
+	/// Calls that had to be executed.
+	pub misses: U64,
+}
+
+impl From<CacheStats> for CallCacheStats {
+	fn from(stats: CacheStats) -> Self {
+		CallCacheStats {
+			hits: stats.hits.into(),
+			misses: stats.misses.into(),
+		}
+	}
+}
@@ -62,6 +62,7 @@ impl fmt::Display for ConfirmationPayload {
 			ConfirmationPayload::EthSignMessage(ref sign) => write!(f, "{}", sign),
 			ConfirmationPayload::EIP191SignMessage(ref sign) => write!(f, "{}", sign),
 			ConfirmationPayload::Decrypt(ref decrypt) => write!(f, "{}", decrypt),
+			ConfirmationPayload::ExportSecret(ref export) => write!(f, "{}", export),
 		}
 	}
 }
@@ -155,6 +156,24 @@ impl fmt::Display for DecryptRequest {
 	}
 }
 
+/// Secret export request
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExportSecretRequest {
+	/// Address
+	pub address: H160,
+}
+
+impl fmt::Display for ExportSecretRequest {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"export the secret of {}",
+			Colour::White.bold().paint(format!("0x{:?}", self.address)),
+		)
+	}
+}
+
 /// Confirmation response for particular payload
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConfirmationResponse {
@@ -166,6 +185,8 @@ pub enum ConfirmationResponse {
 	Signature(H520),
 	/// Decrypted data
 	Decrypt(Bytes),
+	/// Exported secret
+	Secret(H256),
 }
 
 impl Serialize for ConfirmationResponse {
@@ -177,6 +198,7 @@ impl Serialize for ConfirmationResponse {
 			ConfirmationResponse::SignTransaction(ref rlp) => rlp.serialize(serializer),
 			ConfirmationResponse::Signature(ref signature) => signature.serialize(serializer),
 			ConfirmationResponse::Decrypt(ref data) => data.serialize(serializer),
+			ConfirmationResponse::Secret(ref secret) => secret.serialize(serializer),
 		}
 	}
 }
@@ -206,6 +228,8 @@ pub enum ConfirmationPayload {
 	EIP191SignMessage(EIP191SignRequest),
 	/// Decryption
 	Decrypt(DecryptRequest),
+	/// Secret export
+	ExportSecret(ExportSecretRequest),
 }
 
 impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
@@ -225,6 +249,9 @@ impl From<helpers::ConfirmationPayload> for ConfirmationPayload {
 				address,
 				msg: msg.into(),
 			}),
+			helpers::ConfirmationPayload::ExportSecret(address) => ConfirmationPayload::ExportSecret(ExportSecretRequest {
+				address,
+			}),
 		}
 	}
 }
@@ -237,6 +264,7 @@ impl ConfirmationPayload {
 			ConfirmationPayload::EthSignMessage(ref request) => Some(&request.address),
 			ConfirmationPayload::EIP191SignMessage(ref request) => Some(&request.address),
 			ConfirmationPayload::Decrypt(ref request) => Some(&request.address),
+			ConfirmationPayload::ExportSecret(ref request) => Some(&request.address),
 		}
 	}
 }
@@ -392,6 +420,23 @@ mod tests {
 		assert_eq!(res.unwrap(), expected.to_owned());
 	}
 
+	#[test]
+	fn should_serialize_export_secret_confirmation() {
+		// given
+		let request = helpers::ConfirmationRequest {
+			id: 15.into(),
+			payload: helpers::ConfirmationPayload::ExportSecret(Address::from_low_u64_be(10)),
+			origin: Default::default(),
+		};
+
+		// when
+		let res = serde_json::to_string(&ConfirmationRequest::from(request));
+		let expected = r#"{"id":"0xf","payload":{"exportSecret":{"address":"0x000000000000000000000000000000000000000a"}},"origin":"unknown"}"#;
+
+		// then
+		assert_eq!(res.unwrap(), expected.to_owned());
+	}
+
 	#[test]
 	fn should_deserialize_modification() {
 		// given
@@ -0,0 +1,71 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Database statistics.
+
+use ethereum_types::U64;
+use types::disk_usage::DiskUsage as EthDiskUsage;
+
+/// Database statistics.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbStats {
+	/// State pruning algorithm in use.
+	pub pruning: String,
+	/// First block after which the whole chain is available.
+	pub earliest_chain: U64,
+	/// First block for which the state is available.
+	pub earliest_state: U64,
+	/// Size on disk of the databases, if known.
+	pub disk_usage: Option<DiskUsage>,
+}
+
+/// Size on disk of the databases, in bytes.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsage {
+	/// Key-value database holding the state, blocks, extras and traces.
+	pub key_value: U64,
+	/// Estimated share of the key-value database taken by the state.
+	pub state: U64,
+	/// Estimated share of the key-value database taken by block headers and bodies.
+	pub blocks: U64,
+	/// Estimated share of the key-value database taken by block extras.
+	pub extras: U64,
+	/// Estimated share of the key-value database taken by traces.
+	pub traces: U64,
+	/// Header blooms database.
+	pub blooms: U64,
+	/// Trace blooms database.
+	pub trace_blooms: U64,
+	/// Sum of all of the above.
+	pub total: U64,
+}
+
+impl From<EthDiskUsage> for DiskUsage {
+	fn from(usage: EthDiskUsage) -> Self {
+		DiskUsage {
+			key_value: usage.key_value.into(),
+			state: usage.state.into(),
+			blocks: usage.blocks.into(),
+			extras: usage.extras.into(),
+			traces: usage.traces.into(),
+			blooms: usage.blooms.into(),
+			trace_blooms: usage.trace_blooms.into(),
+			total: (usage.key_value + usage.blooms + usage.trace_blooms).into(),
+		}
+	}
+}
@@ -0,0 +1,119 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Step debugger session types.
+
+use ethereum_types::{H160, U256};
+use evm::Instruction;
+use evm::interpreter::{Breakpoints as EvmBreakpoints, PausedStep};
+use v1::types::Bytes;
+
+/// Breakpoints of a debug session.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Breakpoints {
+	/// Program counters to break at.
+	#[serde(default)]
+	pub pcs: Vec<u64>,
+	/// Mnemonics of the instructions to break at, e.g. `SSTORE`.
+	#[serde(default)]
+	pub opcodes: Vec<String>,
+}
+
+impl Breakpoints {
+	/// Converts to EVM breakpoints, failing on the first unknown mnemonic.
+	pub fn into_evm(self) -> Result<EvmBreakpoints, String> {
+		let mut breakpoints = EvmBreakpoints::default();
+		for pc in self.pcs {
+			breakpoints.add_pc(pc as usize);
+		}
+		for opcode in self.opcodes {
+			let instruction = (0..=255u8)
+				.filter_map(Instruction::from_u8)
+				.find(|instruction| instruction.info().name.eq_ignore_ascii_case(&opcode))
+				.ok_or(opcode)?;
+			breakpoints.add_instruction(instruction);
+		}
+		Ok(breakpoints)
+	}
+}
+
+/// Machine state at which a debugged call is paused.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugStep {
+	/// Call depth of the executing frame.
+	pub depth: u64,
+	/// Address of the executing code.
+	pub address: H160,
+	/// Program counter of the next instruction.
+	pub pc: u64,
+	/// Mnemonic of the next instruction.
+	pub op: String,
+	/// Gas left.
+	pub gas: U256,
+	/// Stack, bottom first.
+	pub stack: Vec<U256>,
+	/// Memory.
+	pub memory: Bytes,
+}
+
+impl From<PausedStep> for DebugStep {
+	fn from(step: PausedStep) -> Self {
+		DebugStep {
+			depth: step.depth as u64,
+			address: step.address,
+			pc: step.pc as u64,
+			op: step.instruction.info().name.to_owned(),
+			gas: step.gas,
+			stack: step.stack,
+			memory: step.memory.into(),
+		}
+	}
+}
+
+/// State of a debug session.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugSessionState {
+	/// Where the call is paused, if it is.
+	pub paused: Option<DebugStep>,
+	/// Whether the call has finished.
+	pub finished: bool,
+	/// Output of the finished call.
+	pub output: Option<Bytes>,
+	/// Why the finished call failed, if it did.
+	pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use evm::Instruction;
+	use super::Breakpoints;
+
+	#[test]
+	fn should_convert_breakpoints() {
+		let breakpoints: Breakpoints = serde_json::from_str(r#"{"pcs":[4],"opcodes":["sstore"]}"#).unwrap();
+		let breakpoints = breakpoints.into_evm().unwrap();
+		assert!(breakpoints.matches(4, Instruction::ADD));
+		assert!(breakpoints.matches(0, Instruction::SSTORE));
+		assert!(!breakpoints.matches(0, Instruction::ADD));
+
+		let breakpoints: Breakpoints = serde_json::from_str(r#"{"opcodes":["NOPE"]}"#).unwrap();
+		assert_eq!(breakpoints.into_evm(), Err("NOPE".to_owned()));
+	}
+}
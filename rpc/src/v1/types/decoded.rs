@@ -0,0 +1,47 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Logs and transaction inputs decoded with a registered contract ABI.
+
+/// A decoded event or function parameter.
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct DecodedParam {
+	/// Parameter name, empty if the ABI doesn't name it.
+	pub name: String,
+	/// Solidity type, e.g. `uint256`.
+	#[serde(rename = "type")]
+	pub kind: String,
+	/// Decoded value.
+	pub value: String,
+}
+
+/// A log decoded as an event of the emitting contract.
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct DecodedLog {
+	/// Event signature, e.g. `Transfer(address,address,uint256)`.
+	pub event: String,
+	/// Event parameters, in declaration order.
+	pub params: Vec<DecodedParam>,
+}
+
+/// A transaction input decoded as a call to a function of the called contract.
+#[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
+pub struct DecodedCall {
+	/// Function signature, e.g. `transfer(address,uint256)`.
+	pub function: String,
+	/// Function arguments, in declaration order.
+	pub params: Vec<DecodedParam>,
+}
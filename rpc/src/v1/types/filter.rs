@@ -73,6 +73,9 @@ pub struct Filter {
 	pub topics: Option<Vec<Topic>>,
 	/// Limit
 	pub limit: Option<usize>,
+	/// Number of the most recent matching logs skipped before applying the limit.
+	/// Ignored when polling filter changes and by subscriptions.
+	pub offset: Option<usize>,
 }
 
 impl Filter {
@@ -117,6 +120,7 @@ impl Filter {
 				]
 			},
 			limit: self.limit,
+			offset: self.offset,
 		})
 	}
 }
@@ -177,7 +181,13 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: None,
+			offset: None,
 		});
+
+		let s = r#"{"limit":10,"offset":5}"#;
+		let deserialized: Filter = serde_json::from_str(s).unwrap();
+		assert_eq!(deserialized.limit, Some(10));
+		assert_eq!(deserialized.offset, Some(5));
 	}
 
 	#[test]
@@ -193,6 +203,7 @@ mod tests {
 				VariadicValue::Null,
 			]),
 			limit: None,
+			offset: Some(5),
 		};
 
 		let eth_filter: EthFilter = filter.try_into().unwrap();
@@ -207,6 +218,7 @@ mod tests {
 				None,
 			],
 			limit: None,
+			offset: Some(5),
 		});
 	}
 }
@@ -16,7 +16,7 @@
 
 use ethereum_types::{H160, H256, U256};
 use types::log_entry::{LocalizedLogEntry, LogEntry};
-use v1::types::Bytes;
+use v1::types::{Bytes, DecodedLog};
 
 /// Log
 #[derive(Debug, Serialize, PartialEq, Eq, Hash, Clone)]
@@ -46,6 +46,9 @@ pub struct Log {
 	/// Whether Log Type is Removed (Geth Compatibility Field)
 	#[serde(default)]
 	pub removed: bool,
+	/// Log decoded with the ABI registered for the emitting contract
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub decoded: Option<DecodedLog>,
 }
 
 impl From<LocalizedLogEntry> for Log {
@@ -61,7 +64,8 @@ impl From<LocalizedLogEntry> for Log {
 			log_index: Some(e.log_index.into()),
 			transaction_log_index: Some(e.transaction_log_index.into()),
 			log_type: "mined".to_owned(),
-			removed: false,
+			removed: e.removed,
+			decoded: None,
 		}
 	}
 }
@@ -80,6 +84,7 @@ impl From<LogEntry> for Log {
 			transaction_log_index: None,
 			log_type: "pending".to_owned(),
 			removed: false,
+			decoded: None,
 		}
 	}
 }
@@ -110,6 +115,7 @@ mod tests {
 			log_index: Some(U256::from(1)),
 			log_type: "mined".to_owned(),
 			removed: false,
+			decoded: None,
 		};
 
 		let serialized = serde_json::to_string(&log).unwrap();
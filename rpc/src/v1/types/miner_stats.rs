@@ -0,0 +1,54 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Miner statistics.
+
+use ethcore::miner::MinerStats as EthMinerStats;
+use ethereum_types::U64;
+
+/// Number of transactions left out of authored blocks, by reason.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinerStats {
+	/// Gas price below the block minimum.
+	pub low_gas_price: U64,
+	/// Block transactions limit reached.
+	pub too_many_transactions: U64,
+	/// Using too large a share of the block gas limit.
+	pub gas_share_exceeded: U64,
+	/// Not enough gas left in the block.
+	pub block_gas_limit_reached: U64,
+	/// Nonce not matching the sender's.
+	pub invalid_nonce: U64,
+	/// Sender not allowed to transact.
+	pub not_allowed: U64,
+	/// Failed verification or execution.
+	pub invalid: U64,
+}
+
+impl From<EthMinerStats> for MinerStats {
+	fn from(stats: EthMinerStats) -> Self {
+		MinerStats {
+			low_gas_price: stats.low_gas_price.into(),
+			too_many_transactions: stats.too_many_transactions.into(),
+			gas_share_exceeded: stats.gas_share_exceeded.into(),
+			block_gas_limit_reached: stats.block_gas_limit_reached.into(),
+			invalid_nonce: stats.invalid_nonce.into(),
+			not_allowed: stats.not_allowed.into(),
+			invalid: stats.invalid.into(),
+		}
+	}
+}
@@ -23,21 +23,30 @@ mod account_info;
 mod block;
 mod block_number;
 mod bytes;
+mod call_cache_stats;
 mod call_request;
 mod confirmations;
 mod consensus_status;
+mod db_stats;
+mod debug_session;
+mod decoded;
 mod derivation;
 mod filter;
 mod histogram;
 mod index;
 mod log;
+mod miner_stats;
 mod node_kind;
 mod private_receipt;
 mod private_log;
+mod private_relay;
 mod provenance;
 mod receipt;
+mod reorg;
 mod rpc_settings;
+mod schedule;
 mod secretstore;
+mod state_override;
 mod sync;
 mod trace;
 mod trace_filter;
@@ -54,31 +63,40 @@ pub use self::account_info::{AccountInfo, ExtAccountInfo, EthAccount, StoragePro
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::{BlockNumber, LightBlockNumber, block_number_to_id};
+pub use self::call_cache_stats::CallCacheStats;
 pub use self::call_request::CallRequest;
 pub use self::confirmations::{
 	ConfirmationPayload, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken,
-	TransactionModification, EIP191SignRequest, EthSignRequest, DecryptRequest, Either
+	TransactionModification, EIP191SignRequest, EthSignRequest, DecryptRequest, ExportSecretRequest, Either
 };
 pub use self::consensus_status::*;
+pub use self::db_stats::{DbStats, DiskUsage};
+pub use self::debug_session::{Breakpoints, DebugSessionState, DebugStep};
+pub use self::decoded::{DecodedCall, DecodedLog, DecodedParam};
 pub use self::derivation::{DeriveHash, DeriveHierarchical, Derive};
 pub use self::filter::{Filter, FilterChanges};
 pub use self::histogram::Histogram;
 pub use self::index::Index;
 pub use self::log::Log;
+pub use self::miner_stats::MinerStats;
 pub use self::node_kind::{NodeKind, Availability, Capability};
 pub use self::private_receipt::{PrivateTransactionReceipt, PrivateTransactionReceiptAndTransaction};
 pub use self::private_log::PrivateTransactionLog;
+pub use self::private_relay::RelayedTransaction;
 pub use self::provenance::Origin;
 pub use self::receipt::Receipt;
+pub use self::reorg::Reorg;
 pub use self::rpc_settings::RpcSettings;
+pub use self::schedule::Schedule;
 pub use self::secretstore::EncryptedDocumentKey;
+pub use self::state_override::{AccountOverride, StateOverride, into_state_override};
 pub use self::sync::{
 	SyncStatus, SyncInfo, Peers, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
 	TransactionStats, ChainStatus, EthProtocolInfo, PipProtocolInfo,
 };
-pub use self::trace::{LocalizedTrace, TraceResults, TraceResultsWithTransactionHash};
+pub use self::trace::{LocalizedTrace, ReplayedBlock, TraceResults, TraceResultsWithTransactionHash};
 pub use self::trace_filter::TraceFilter;
-pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
+pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus, TransactionInclusion};
 pub use self::transaction_request::TransactionRequest;
 pub use self::transaction_condition::TransactionCondition;
 pub use self::work::Work;
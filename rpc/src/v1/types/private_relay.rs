@@ -0,0 +1,33 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Transaction sent through the private relay.
+
+use ethereum_types::{H256, U64};
+
+/// A transaction relayed by `parity_sendPrivateTransaction`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelayedTransaction {
+	/// Transaction hash.
+	pub hash: H256,
+	/// Best block number when the transaction was relayed.
+	pub relayed_at: U64,
+	/// Hash of the block including the transaction, once mined.
+	pub block_hash: Option<H256>,
+	/// Number of the block including the transaction, once mined.
+	pub block_number: Option<U64>,
+}
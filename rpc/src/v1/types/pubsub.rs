@@ -19,7 +19,9 @@
 use ethereum_types::H256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Error;
-use serde_json::{Value, from_value};
+use serde::ser::Error as SerError;
+use serde_json::{Value, from_value, to_value};
+use v1::helpers::address_format::checksum_value;
 use v1::types::{RichHeader, Filter, Log};
 
 /// Subscription result.
@@ -48,14 +50,24 @@ impl Serialize for Result {
 		where S: Serializer
 	{
 		match *self {
-			Result::Header(ref header) => header.serialize(serializer),
-			Result::Log(ref log) => log.serialize(serializer),
+			Result::Header(ref header) => serialize_checksummed(header, serializer),
+			Result::Log(ref log) => serialize_checksummed(log, serializer),
 			Result::TransactionHash(ref hash) => hash.serialize(serializer),
 			Result::SyncState(ref sync) => sync.serialize(serializer),
 		}
 	}
 }
 
+/// Serializes with checksummed addresses, like in the responses to calls.
+fn serialize_checksummed<T, S>(value: &T, serializer: S) -> ::std::result::Result<S::Ok, S::Error> where
+	T: Serialize,
+	S: Serializer,
+{
+	let mut value = to_value(value).map_err(S::Error::custom)?;
+	checksum_value(&mut value);
+	value.serialize(serializer)
+}
+
 /// Subscription kind.
 #[derive(Debug, Deserialize, PartialEq, Eq, Hash, Clone)]
 #[serde(deny_unknown_fields)]
@@ -104,7 +116,7 @@ impl<'a> Deserialize<'a> for Params {
 mod tests {
 	use serde_json;
 	use super::{Result, Kind, Params};
-	use v1::types::{RichHeader, Header, Filter};
+	use v1::types::{RichHeader, Header, Filter, Log};
 	use v1::types::filter::VariadicValue;
 
 	#[test]
@@ -132,6 +144,7 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: None,
+			offset: None,
 		}));
 		assert_eq!(logs2, Params::Logs(Filter {
 			from_block: None,
@@ -140,6 +153,7 @@ mod tests {
 			address: None,
 			topics: None,
 			limit: Some(10),
+			offset: None,
 		}));
 		assert_eq!(logs3, Params::Logs(Filter {
 			from_block: None,
@@ -150,6 +164,7 @@ mod tests {
 				VariadicValue::Single("000000000000000000000000a94f5374fce5edbc8e2a8697c15331677e6ebf0b".parse().unwrap()
 			)]),
 			limit: None,
+			offset: None,
 		}));
 	}
 
@@ -180,4 +195,25 @@ mod tests {
 		let expected = r#"{"author":"0x0000000000000000000000000000000000000000","difficulty":"0x0","extraData":"0x","gasLimit":"0x0","gasUsed":"0x0","hash":"0x0000000000000000000000000000000000000000000000000000000000000000","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","miner":"0x0000000000000000000000000000000000000000","number":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","receiptsRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","sealFields":["0x","0x"],"sha3Uncles":"0x0000000000000000000000000000000000000000000000000000000000000000","size":"0x45","stateRoot":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":"0x0","transactionsRoot":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#;
 		assert_eq!(serde_json::to_string(&header).unwrap(), expected);
 	}
+
+	#[test]
+	fn should_checksum_addresses_in_log() {
+		let log = Result::Log(Box::new(Log {
+			address: "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap(),
+			topics: vec![],
+			data: vec![0x5a; 20].into(),
+			block_hash: None,
+			block_number: None,
+			transaction_hash: None,
+			transaction_index: None,
+			log_index: None,
+			transaction_log_index: None,
+			log_type: "mined".into(),
+			removed: false,
+			decoded: None,
+		}));
+		let serialized = serde_json::to_string(&log).unwrap();
+		assert!(serialized.contains(r#""address":"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed""#));
+		assert!(serialized.contains(r#""data":"0x5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a5a""#));
+	}
 }
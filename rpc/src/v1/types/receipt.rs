@@ -15,7 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use ethereum_types::{H160, H256, U64, U256, Bloom as H2048};
-use v1::types::Log;
+use v1::types::{Bytes, Log};
 use types::receipt::{Receipt as EthReceipt, RichReceipt, LocalizedReceipt, TransactionOutcome};
 
 /// Receipt
@@ -52,6 +52,10 @@ pub struct Receipt {
 	// NOTE(niklasad1): Unknown after EIP98 rules, if it's missing then skip serializing it
 	#[serde(skip_serializing_if = "Option::is_none", rename = "status")]
 	pub status_code: Option<U64>,
+	/// Return data, or the revert reason of a failed transaction
+	// NOTE: only recorded if the node stores transaction outputs, skipped otherwise
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub output: Option<Bytes>,
 }
 
 impl Receipt {
@@ -86,6 +90,7 @@ impl From<LocalizedReceipt> for Receipt {
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			output: r.output.map(Into::into),
 		}
 	}
 }
@@ -106,6 +111,7 @@ impl From<RichReceipt> for Receipt {
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			output: None,
 		}
 	}
 }
@@ -126,6 +132,7 @@ impl From<EthReceipt> for Receipt {
 			status_code: Self::outcome_to_status_code(&r.outcome),
 			state_root: Self::outcome_to_state_root(r.outcome),
 			logs_bloom: r.log_bloom,
+			output: None,
 		}
 	}
 }
@@ -165,10 +172,12 @@ mod tests {
 				log_index: Some(1.into()),
 				log_type: "mined".into(),
 				removed: false,
+				decoded: None,
 			}],
 			logs_bloom: Bloom::from_low_u64_be(15),
 			state_root: Some(H256::from_low_u64_be(10)),
 			status_code: Some(1u64.into()),
+			output: None,
 		};
 
 		let serialized = serde_json::to_string(&receipt).unwrap();
@@ -0,0 +1,45 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Chain reorganization record.
+
+use ethereum_types::{H256, U64};
+use types::reorg::Reorg as EthReorg;
+
+/// A reorganization of the canonical chain.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reorg {
+	/// Newest common ancestor of the old and new canonical chains.
+	pub ancestor: H256,
+	/// Blocks removed from the canonical chain.
+	pub retracted: Vec<H256>,
+	/// Blocks added to the canonical chain, ending with the new best block.
+	pub enacted: Vec<H256>,
+	/// Unix timestamp at which the reorganization was imported.
+	pub timestamp: U64,
+}
+
+impl From<EthReorg> for Reorg {
+	fn from(reorg: EthReorg) -> Self {
+		Reorg {
+			ancestor: reorg.ancestor,
+			retracted: reorg.retracted,
+			enacted: reorg.enacted,
+			timestamp: reorg.timestamp.into(),
+		}
+	}
+}
@@ -0,0 +1,192 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! EVM schedule in effect at a block.
+
+use vm;
+
+/// EVM gas costs and features in effect at a block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Schedule {
+	/// Number of the block the schedule applies to.
+	pub block_number: u64,
+	/// Spec transitions changing the schedule activated at or before the block, in activation order.
+	pub activated_transitions: Vec<String>,
+	/// Whether `DELEGATECALL` is available.
+	pub have_delegate_call: bool,
+	/// Whether `CREATE2` is available.
+	pub have_create2: bool,
+	/// Whether `REVERT` is available.
+	pub have_revert: bool,
+	/// Whether `STATICCALL` is available.
+	pub have_static_call: bool,
+	/// Whether `RETURNDATASIZE` and `RETURNDATACOPY` are available.
+	pub have_return_data: bool,
+	/// Whether `SHL`, `SHR` and `SAR` are available.
+	pub have_bitwise_shifting: bool,
+	/// Whether `EXTCODEHASH` is available.
+	pub have_extcodehash: bool,
+	/// Whether `CHAINID` is available.
+	pub have_chain_id: bool,
+	/// Whether `SELFBALANCE` is available.
+	pub have_selfbalance: bool,
+	/// Whether net gas metering of `SSTORE` is enabled.
+	pub eip1283: bool,
+	/// Whether `SSTORE` fails when gas left is below the call stipend.
+	pub eip1706: bool,
+	/// Maximum call depth.
+	pub max_depth: usize,
+	/// Gas prices of the instruction tiers.
+	pub tier_step_gas: Vec<usize>,
+	/// Gas price of `EXP`.
+	pub exp_gas: usize,
+	/// Additional gas for each byte of the `EXP` exponent.
+	pub exp_byte_gas: usize,
+	/// Gas price of `SHA3`.
+	pub sha3_gas: usize,
+	/// Additional gas for each word hashed by `SHA3`.
+	pub sha3_word_gas: usize,
+	/// Gas price of `SLOAD`.
+	pub sload_gas: usize,
+	/// Gas price of `SSTORE` to a dirty slot, if net gas metering is enabled.
+	pub sstore_dirty_gas: Option<usize>,
+	/// Gas price of `SSTORE` setting a zero slot.
+	pub sstore_set_gas: usize,
+	/// Gas price of `SSTORE` changing a non-zero slot.
+	pub sstore_reset_gas: usize,
+	/// Gas refund for `SSTORE` clearing a slot.
+	pub sstore_refund_gas: usize,
+	/// Gas price of `LOG*`.
+	pub log_gas: usize,
+	/// Additional gas for each byte of logged data.
+	pub log_data_gas: usize,
+	/// Additional gas for each log topic.
+	pub log_topic_gas: usize,
+	/// Gas price of `CREATE`.
+	pub create_gas: usize,
+	/// Gas price of `CALL`.
+	pub call_gas: usize,
+	/// Gas given to the callee of a value transfer.
+	pub call_stipend: usize,
+	/// Additional gas for a `CALL` transferring value.
+	pub call_value_transfer_gas: usize,
+	/// Additional gas for a `CALL` creating an account.
+	pub call_new_account_gas: usize,
+	/// Gas refund for `SELFDESTRUCT`.
+	pub suicide_refund_gas: usize,
+	/// Gas price of `SELFDESTRUCT`.
+	pub suicide_gas: usize,
+	/// Additional gas for a `SELFDESTRUCT` creating an account.
+	pub suicide_to_new_account_cost: usize,
+	/// Gas price for each word of memory.
+	pub memory_gas: usize,
+	/// Gas for each byte of deployed code.
+	pub create_data_gas: usize,
+	/// Maximum size of deployed code.
+	pub create_data_limit: usize,
+	/// Intrinsic gas of a transaction.
+	pub tx_gas: usize,
+	/// Intrinsic gas of a contract creation transaction.
+	pub tx_create_gas: usize,
+	/// Gas for each zero byte of transaction data.
+	pub tx_data_zero_gas: usize,
+	/// Gas for each non-zero byte of transaction data.
+	pub tx_data_non_zero_gas: usize,
+	/// Gas for each word copied to memory.
+	pub copy_gas: usize,
+	/// Gas price of `EXTCODESIZE`.
+	pub extcodesize_gas: usize,
+	/// Gas price of `EXTCODECOPY`.
+	pub extcodecopy_base_gas: usize,
+	/// Gas price of `EXTCODEHASH`.
+	pub extcodehash_gas: usize,
+	/// Gas price of `BALANCE`.
+	pub balance_gas: usize,
+	/// Gas price of `BLOCKHASH`.
+	pub blockhash_gas: usize,
+	/// Divisor of the gas left given to a sub-call, if capped.
+	pub sub_gas_cap_divisor: Option<usize>,
+	/// Whether empty accounts are not created.
+	pub no_empty: bool,
+	/// Whether touched empty accounts are removed.
+	pub kill_empty: bool,
+	/// Whether WebAssembly contracts are enabled.
+	pub wasm: bool,
+}
+
+impl Schedule {
+	/// Describes `schedule` in effect at `block_number`, given the schedule transitions of the chain.
+	pub fn new(block_number: u64, schedule: &vm::Schedule, transitions: &[(u64, &str)]) -> Self {
+		Schedule {
+			block_number,
+			activated_transitions: transitions.iter()
+				.take_while(|&&(block, _)| block <= block_number)
+				.map(|&(_, name)| name.to_owned())
+				.collect(),
+			have_delegate_call: schedule.have_delegate_call,
+			have_create2: schedule.have_create2,
+			have_revert: schedule.have_revert,
+			have_static_call: schedule.have_static_call,
+			have_return_data: schedule.have_return_data,
+			have_bitwise_shifting: schedule.have_bitwise_shifting,
+			have_extcodehash: schedule.have_extcodehash,
+			have_chain_id: schedule.have_chain_id,
+			have_selfbalance: schedule.have_selfbalance,
+			eip1283: schedule.eip1283,
+			eip1706: schedule.eip1706,
+			max_depth: schedule.max_depth,
+			tier_step_gas: schedule.tier_step_gas.to_vec(),
+			exp_gas: schedule.exp_gas,
+			exp_byte_gas: schedule.exp_byte_gas,
+			sha3_gas: schedule.sha3_gas,
+			sha3_word_gas: schedule.sha3_word_gas,
+			sload_gas: schedule.sload_gas,
+			sstore_dirty_gas: schedule.sstore_dirty_gas,
+			sstore_set_gas: schedule.sstore_set_gas,
+			sstore_reset_gas: schedule.sstore_reset_gas,
+			sstore_refund_gas: schedule.sstore_refund_gas,
+			log_gas: schedule.log_gas,
+			log_data_gas: schedule.log_data_gas,
+			log_topic_gas: schedule.log_topic_gas,
+			create_gas: schedule.create_gas,
+			call_gas: schedule.call_gas,
+			call_stipend: schedule.call_stipend,
+			call_value_transfer_gas: schedule.call_value_transfer_gas,
+			call_new_account_gas: schedule.call_new_account_gas,
+			suicide_refund_gas: schedule.suicide_refund_gas,
+			suicide_gas: schedule.suicide_gas,
+			suicide_to_new_account_cost: schedule.suicide_to_new_account_cost,
+			memory_gas: schedule.memory_gas,
+			create_data_gas: schedule.create_data_gas,
+			create_data_limit: schedule.create_data_limit,
+			tx_gas: schedule.tx_gas,
+			tx_create_gas: schedule.tx_create_gas,
+			tx_data_zero_gas: schedule.tx_data_zero_gas,
+			tx_data_non_zero_gas: schedule.tx_data_non_zero_gas,
+			copy_gas: schedule.copy_gas,
+			extcodesize_gas: schedule.extcodesize_gas,
+			extcodecopy_base_gas: schedule.extcodecopy_base_gas,
+			extcodehash_gas: schedule.extcodehash_gas,
+			balance_gas: schedule.balance_gas,
+			blockhash_gas: schedule.blockhash_gas,
+			sub_gas_cap_divisor: schedule.sub_gas_cap_divisor,
+			no_empty: schedule.no_empty,
+			kill_empty: schedule.kill_empty,
+			wasm: schedule.wasm.is_some(),
+		}
+	}
+}
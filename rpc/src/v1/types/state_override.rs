@@ -0,0 +1,95 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! State overrides for `eth_call`.
+
+use std::collections::BTreeMap;
+
+use ethereum_types::{H160, H256, U256};
+use types::state_override::{AccountOverride as EthAccountOverride, StateOverride as EthStateOverride};
+use v1::types::Bytes;
+
+/// Values replacing those of an account before executing a call.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+	/// Balance
+	pub balance: Option<U256>,
+	/// Nonce
+	pub nonce: Option<U256>,
+	/// Code
+	pub code: Option<Bytes>,
+	/// Storage slots to set; other slots keep their value
+	pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// Account overrides keyed by address.
+pub type StateOverride = BTreeMap<H160, AccountOverride>;
+
+impl Into<EthAccountOverride> for AccountOverride {
+	fn into(self) -> EthAccountOverride {
+		EthAccountOverride {
+			balance: self.balance,
+			nonce: self.nonce,
+			code: self.code.map(Into::into),
+			storage: self.state_diff.unwrap_or_default(),
+		}
+	}
+}
+
+/// Converts RPC state overrides into their client representation.
+pub fn into_state_override(overrides: StateOverride) -> EthStateOverride {
+	overrides.into_iter().map(|(address, account)| (address, account.into())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+	use serde_json;
+	use ethereum_types::{H160, H256, U256};
+	use super::{AccountOverride, StateOverride};
+
+	#[test]
+	fn state_override_deserialize() {
+		let s = r#"{
+			"0x0000000000000000000000000000000000000001": {
+				"balance": "0x10",
+				"code": "0x6000",
+				"stateDiff": {
+					"0x0000000000000000000000000000000000000000000000000000000000000002": "0x0000000000000000000000000000000000000000000000000000000000000003"
+				}
+			},
+			"0x0000000000000000000000000000000000000004": { "nonce": "0x5" }
+		}"#;
+		let deserialized: StateOverride = serde_json::from_str(s).unwrap();
+
+		let mut state_diff = BTreeMap::new();
+		state_diff.insert(H256::from_low_u64_be(2), H256::from_low_u64_be(3));
+		let mut expected = BTreeMap::new();
+		expected.insert(H160::from_low_u64_be(1), AccountOverride {
+			balance: Some(U256::from(0x10)),
+			nonce: None,
+			code: Some(vec![0x60, 0x00].into()),
+			state_diff: Some(state_diff),
+		});
+		expected.insert(H160::from_low_u64_be(4), AccountOverride {
+			nonce: Some(U256::from(5)),
+			..Default::default()
+		});
+		assert_eq!(deserialized, expected);
+	}
+}
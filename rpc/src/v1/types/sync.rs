@@ -73,6 +73,8 @@ pub struct PeerNetworkInfo {
 	pub remote_address: String,
 	/// Local endpoint address
 	pub local_address: String,
+	/// Average session ping round-trip time in milliseconds
+	pub ping: Option<u64>,
 }
 
 /// Peer protocols information
@@ -86,6 +88,7 @@ pub struct PeerProtocolsInfo {
 
 /// Peer Ethereum protocol information
 #[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EthProtocolInfo {
 	/// Negotiated ethereum protocol version
 	pub version: u32,
@@ -93,6 +96,14 @@ pub struct EthProtocolInfo {
 	pub difficulty: Option<U256>,
 	/// SHA3 of peer best block hash
 	pub head: String,
+	/// Peer best block number if announced
+	pub head_number: Option<U256>,
+	/// Average response time to our requests in milliseconds
+	pub latency: Option<u64>,
+	/// Type of data currently being requested from the peer
+	pub asking: Option<String>,
+	/// Number of blocks currently being requested from the peer
+	pub asking_blocks: usize,
 }
 
 impl From<sync::EthProtocolInfo> for EthProtocolInfo {
@@ -101,6 +112,10 @@ impl From<sync::EthProtocolInfo> for EthProtocolInfo {
 			version: info.version,
 			difficulty: info.difficulty.map(Into::into),
 			head: format!("{:x}", info.head),
+			head_number: info.head_number.map(Into::into),
+			latency: info.latency.map(|latency| latency.as_millis() as u64),
+			asking: info.asking,
+			asking_blocks: info.asking_blocks,
 		}
 	}
 }
@@ -164,6 +179,7 @@ impl From<SyncPeerInfo> for PeerInfo {
 			network: PeerNetworkInfo {
 				remote_address: p.remote_address,
 				local_address: p.local_address,
+				ping: p.ping.map(|ping| ping.as_millis() as u64),
 			},
 			protocols: PeerProtocolsInfo {
 				eth: p.eth_info.map(Into::into),
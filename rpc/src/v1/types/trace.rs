@@ -16,6 +16,7 @@
 
 use std::collections::BTreeMap;
 
+use client_traits::ReplayedBlock as EthReplayedBlock;
 use machine::executed::Executed;
 use trace as et;
 use trace::{FlatTrace, LocalizedTrace as EthLocalizedTrace, trace, TraceError};
@@ -672,6 +673,31 @@ impl From<(H256, Executed)> for TraceResultsWithTransactionHash {
 	}
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Outcome of re-executing a historical block.
+pub struct ReplayedBlock {
+	/// Results of the block's transactions, in block order.
+	pub transactions: Vec<TraceResultsWithTransactionHash>,
+	/// State root obtained by re-executing the block.
+	pub state_root: H256,
+	/// State root recorded in the block header.
+	pub expected_state_root: H256,
+	/// Whether re-execution reproduced the recorded state root.
+	pub state_root_matches: bool,
+}
+
+impl From<EthReplayedBlock> for ReplayedBlock {
+	fn from(block: EthReplayedBlock) -> Self {
+		ReplayedBlock {
+			state_root_matches: block.state_root_matches(),
+			state_root: block.state_root,
+			expected_state_root: block.expected_state_root,
+			transactions: block.transactions.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use serde_json;
@@ -151,6 +151,61 @@ impl Serialize for LocalTransactionStatus {
 	}
 }
 
+/// Whether a transaction, or another one from the same sender with the same nonce, is already
+/// mined or pending.
+#[derive(Debug)]
+pub enum TransactionInclusion {
+	/// Neither the transaction nor a conflicting one is known.
+	NotFound,
+	/// Transaction was mined.
+	Mined(Transaction),
+	/// Transaction is in the queue.
+	Pending(Transaction),
+	/// A conflicting transaction was mined. It is included if it was found in the searched recent blocks.
+	ConflictMined(Option<Transaction>),
+	/// A conflicting transaction is in the queue.
+	ConflictPending(Transaction),
+}
+
+impl Serialize for TransactionInclusion {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where S: Serializer
+	{
+		use self::TransactionInclusion::*;
+
+		let elems = match *self {
+			NotFound => 1,
+			Mined(..) | Pending(..) | ConflictMined(..) | ConflictPending(..) => 2,
+		};
+
+		let status = "status";
+		let transaction = "transaction";
+
+		let mut struc = serializer.serialize_struct("TransactionInclusion", elems)?;
+		match *self {
+			NotFound => struc.serialize_field(status, "notFound")?,
+			Mined(ref tx) => {
+				struc.serialize_field(status, "mined")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			Pending(ref tx) => {
+				struc.serialize_field(status, "pending")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			ConflictMined(ref tx) => {
+				struc.serialize_field(status, "conflictMined")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+			ConflictPending(ref tx) => {
+				struc.serialize_field(status, "conflictPending")?;
+				struc.serialize_field(transaction, tx)?;
+			},
+		}
+
+		struc.end()
+	}
+}
+
 /// Geth-compatible output for eth_signTransaction method
 #[derive(Debug, Default, Clone, PartialEq, Serialize)]
 pub struct RichRawTransaction {
@@ -244,7 +299,7 @@ impl Transaction {
 	/// Convert `PendingTransaction` into RPC Transaction.
 	pub fn from_pending(t: PendingTransaction) -> Transaction {
 		let mut r = Transaction::from_signed(t.transaction);
-		r.condition = r.condition.map(Into::into);
+		r.condition = t.condition.map(Into::into);
 		r
 	}
 }
@@ -275,7 +330,7 @@ impl LocalTransactionStatus {
 
 #[cfg(test)]
 mod tests {
-	use super::{Transaction, LocalTransactionStatus};
+	use super::{Transaction, LocalTransactionStatus, TransactionInclusion};
 	use serde_json;
 
 	#[test]
@@ -331,4 +386,22 @@ mod tests {
 			r#","hash":"0x000000000000000000000000000000000000000000000000000000000000000a","gasPrice":"0x5"}"#
 		);
 	}
+
+	#[test]
+	fn test_transaction_inclusion_serialize() {
+		let tx_ser = serde_json::to_string(&Transaction::default()).unwrap();
+
+		assert_eq!(
+			serde_json::to_string(&TransactionInclusion::NotFound).unwrap(),
+			r#"{"status":"notFound"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&TransactionInclusion::Pending(Transaction::default())).unwrap(),
+			r#"{"status":"pending","transaction":"#.to_owned() + &tx_ser + r#"}"#
+		);
+		assert_eq!(
+			serde_json::to_string(&TransactionInclusion::ConflictMined(None)).unwrap(),
+			r#"{"status":"conflictMined","transaction":null}"#
+		);
+	}
 }
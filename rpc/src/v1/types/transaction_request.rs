@@ -17,6 +17,7 @@
 //! `TransactionRequest` type
 
 use ethereum_types::{H160, U256};
+use types::denominations::format_ether;
 use v1::types::{Bytes, TransactionCondition};
 use v1::helpers;
 use ansi_term::Colour;
@@ -46,21 +47,6 @@ pub struct TransactionRequest {
 	pub condition: Option<TransactionCondition>,
 }
 
-pub fn format_ether(i: U256) -> String {
-	let mut string = format!("{}", i);
-	let idx = string.len() as isize - 18;
-	if idx <= 0 {
-		let mut prefix = String::from("0.");
-		for _ in 0..idx.abs() {
-			prefix.push('0');
-		}
-		string = prefix + &string;
-	} else {
-		string.insert(idx as usize, '.');
-	}
-	String::from(string.trim_end_matches('0').trim_end_matches('.'))
-}
-
 impl fmt::Display for TransactionRequest {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		let eth = self.value.unwrap_or_default();
@@ -318,6 +318,7 @@ impl OperationsClient for OperationsContractClient {
 			address: Some(vec![address]),
 			topics,
 			limit: None,
+			offset: None,
 		};
 
 		client.logs(filter)
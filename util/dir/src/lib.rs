@@ -224,6 +224,16 @@ impl DatabaseDirectories {
 	pub fn network_path(&self) -> PathBuf {
 		self.spec_root_path().join("network")
 	}
+
+	/// Get the path for the file of chain events not yet exported.
+	pub fn event_export_path(&self) -> PathBuf {
+		self.db_root_path().join("event_export")
+	}
+
+	/// Get the path for the directory of state dumps.
+	pub fn state_dumps_path(&self) -> PathBuf {
+		self.db_root_path().join("state_dumps")
+	}
 }
 
 /// Default data path
@@ -35,6 +35,7 @@ use rlp::{encode, decode};
 use crate::{
 	DB_PREFIX_LEN, LATEST_ERA_KEY, error_negatively_reference_hash, error_key_already_exists,
 	JournalDB, new_memory_db,
+	pins::EraPins,
 	util::{DatabaseKey, DatabaseValueView, DatabaseValueRef},
 };
 
@@ -112,6 +113,7 @@ pub struct EarlyMergeDB {
 	refs: Option<Arc<RwLock<HashMap<H256, RefInfo>>>>,
 	latest_era: Option<u64>,
 	column: u32,
+	pins: EraPins,
 }
 
 impl EarlyMergeDB {
@@ -125,6 +127,7 @@ impl EarlyMergeDB {
 			refs,
 			latest_era,
 			column,
+			pins: EraPins::default(),
 		}
 	}
 
@@ -324,6 +327,7 @@ impl JournalDB for EarlyMergeDB {
 			refs: self.refs.clone(),
 			latest_era: self.latest_era.clone(),
 			column: self.column.clone(),
+			pins: self.pins.clone(),
 		})
 	}
 
@@ -337,6 +341,18 @@ impl JournalDB for EarlyMergeDB {
 
 	fn latest_era(&self) -> Option<u64> { self.latest_era }
 
+	fn pin_era(&self, era: u64) {
+		self.pins.pin(era)
+	}
+
+	fn unpin_era(&self, era: u64) {
+		self.pins.unpin(era)
+	}
+
+	fn earliest_pinned_era(&self) -> Option<u64> {
+		self.pins.earliest()
+	}
+
 	fn mem_used(&self) -> usize {
 		let mut ops = new_malloc_size_ops();
 		self.overlay.size_of(&mut ops) + match self.refs {
@@ -414,6 +430,7 @@ impl JournalDB for EarlyMergeDB {
 	}
 
 	fn mark_canonical(&mut self, batch: &mut DBTransaction, end_era: u64, canon_id: &H256) -> io::Result<u32> {
+		self.pins.check_prunable(end_era)?;
 		let mut refs = self.refs.as_ref().unwrap().write();
 
 		// apply old commits' details
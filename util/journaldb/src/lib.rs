@@ -35,6 +35,7 @@ mod refcounteddb;
 mod util;
 mod as_hash_db_impls;
 mod overlaydb;
+mod pins;
 
 /// A `HashDB` which can manage a short-term journal potentially containing many forks of mutually
 /// exclusive actions.
@@ -81,6 +82,16 @@ pub trait JournalDB: HashDB<KeccakHasher, DBValue> {
 	/// Whether this database is pruned.
 	fn is_prunable(&self) -> bool { true }
 
+	/// Keep the state of `era` and later eras from being pruned until `unpin_era` is called,
+	/// e.g. while a snapshot of it is taken. Pins are counted and shared between clones.
+	fn pin_era(&self, _era: u64) {}
+
+	/// Release a pin previously taken with `pin_era`.
+	fn unpin_era(&self, _era: u64) {}
+
+	/// Earliest pinned era, pruning must stop before it.
+	fn earliest_pinned_era(&self) -> Option<u64> { None }
+
 	/// Get backing database.
 	fn backing(&self) -> &Arc<dyn kvdb::KeyValueDB>;
 
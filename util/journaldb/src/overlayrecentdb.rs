@@ -37,6 +37,7 @@ use rlp::{Rlp, RlpStream, encode, decode, DecoderError, Decodable, Encodable};
 use crate::{
 	DB_PREFIX_LEN, LATEST_ERA_KEY, JournalDB, error_negatively_reference_hash,
 	new_memory_db,
+	pins::EraPins,
 	util::DatabaseKey
 };
 
@@ -76,6 +77,7 @@ pub struct OverlayRecentDB {
 	backing: Arc<dyn KeyValueDB>,
 	journal_overlay: Arc<RwLock<JournalOverlay>>,
 	column: u32,
+	pins: EraPins,
 }
 
 struct DatabaseValue {
@@ -148,6 +150,7 @@ impl Clone for OverlayRecentDB {
 			backing: self.backing.clone(),
 			journal_overlay: self.journal_overlay.clone(),
 			column: self.column.clone(),
+			pins: self.pins.clone(),
 		}
 	}
 }
@@ -161,6 +164,7 @@ impl OverlayRecentDB {
 			backing,
 			journal_overlay,
 			column: col,
+			pins: EraPins::default(),
 		}
 	}
 
@@ -279,6 +283,18 @@ impl JournalDB for OverlayRecentDB {
 
 	fn earliest_era(&self) -> Option<u64> { self.journal_overlay.read().earliest_era }
 
+	fn pin_era(&self, era: u64) {
+		self.pins.pin(era)
+	}
+
+	fn unpin_era(&self, era: u64) {
+		self.pins.unpin(era)
+	}
+
+	fn earliest_pinned_era(&self) -> Option<u64> {
+		self.pins.earliest()
+	}
+
 	fn state(&self, key: &H256) -> Option<Bytes> {
 		let key = to_short_key(key);
 		// Hold the read lock for shortest possible amount of time.
@@ -356,6 +372,7 @@ impl JournalDB for OverlayRecentDB {
 
 	fn mark_canonical(&mut self, batch: &mut DBTransaction, end_era: u64, canon_id: &H256) -> io::Result<u32> {
 		trace!(target: "journaldb", "canonical: #{} ({})", end_era, canon_id);
+		self.pins.check_prunable(end_era)?;
 
 		let mut journal_overlay = self.journal_overlay.write();
 		let journal_overlay = &mut *journal_overlay;
@@ -546,6 +563,29 @@ mod tests {
 		assert!(jdb.contains(&x, EMPTY_PREFIX));
 	}
 
+	#[test]
+	fn pinned_era_is_not_pruned() {
+		let mut jdb = new_db();
+		let h = jdb.insert(EMPTY_PREFIX, b"foo");
+		commit_batch(&mut jdb, 0, &keccak(b"0"), None).unwrap();
+		jdb.remove(&h, EMPTY_PREFIX);
+		commit_batch(&mut jdb, 1, &keccak(b"1"), None).unwrap();
+		commit_batch(&mut jdb, 2, &keccak(b"2"), Some((0, keccak(b"0")))).unwrap();
+
+		// pins taken on a clone apply to the original
+		let reader = jdb.boxed_clone();
+		reader.pin_era(1);
+		assert_eq!(jdb.earliest_pinned_era(), Some(1));
+		let mut batch = jdb.backing().transaction();
+		assert!(jdb.mark_canonical(&mut batch, 1, &keccak(b"1")).is_err());
+		assert!(jdb.contains(&h, EMPTY_PREFIX));
+
+		reader.unpin_era(1);
+		commit_batch(&mut jdb, 3, &keccak(b"3"), Some((1, keccak(b"1")))).unwrap();
+		assert!(jdb.can_reconstruct_refs());
+		assert!(!jdb.contains(&h, EMPTY_PREFIX));
+	}
+
 	#[test]
 	fn long_history() {
 		// history is 3
@@ -0,0 +1,95 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Eras pinned by long-running readers of a pruned database.
+
+use std::{
+	collections::BTreeMap,
+	io,
+	sync::Arc,
+};
+
+use parking_lot::Mutex;
+
+/// Reference-counted set of pinned eras, shared by all clones of a database.
+#[derive(Clone, Default)]
+pub struct EraPins(Arc<Mutex<BTreeMap<u64, usize>>>);
+
+impl EraPins {
+	/// Add a reference to `era`.
+	pub fn pin(&self, era: u64) {
+		*self.0.lock().entry(era).or_insert(0) += 1;
+	}
+
+	/// Remove a reference to `era`, previously added with `pin`.
+	pub fn unpin(&self, era: u64) {
+		let mut pins = self.0.lock();
+		let remove = match pins.get_mut(&era) {
+			Some(count) => {
+				*count -= 1;
+				*count == 0
+			}
+			None => false,
+		};
+		if remove {
+			pins.remove(&era);
+		}
+	}
+
+	/// Earliest era with at least one reference.
+	pub fn earliest(&self) -> Option<u64> {
+		self.0.lock().keys().next().cloned()
+	}
+
+	/// Fails if pruning `era` could delete state of a pinned era.
+	pub fn check_prunable(&self, era: u64) -> io::Result<()> {
+		match self.earliest() {
+			Some(pinned) if pinned <= era => Err(io::Error::new(
+				io::ErrorKind::Other,
+				format!("Era {} can't be pruned while era {} is pinned.", era, pinned),
+			)),
+			_ => Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::EraPins;
+
+	#[test]
+	fn pins_are_counted_and_shared() {
+		let pins = EraPins::default();
+		let shared = pins.clone();
+		pins.pin(10);
+		pins.pin(10);
+		shared.pin(12);
+		assert_eq!(shared.earliest(), Some(10));
+		assert!(pins.check_prunable(9).is_ok());
+		assert!(pins.check_prunable(10).is_err());
+
+		pins.unpin(10);
+		assert_eq!(shared.earliest(), Some(10));
+		shared.unpin(10);
+		assert_eq!(pins.earliest(), Some(12));
+		assert!(pins.check_prunable(11).is_ok());
+
+		// unbalanced unpins are ignored
+		pins.unpin(12);
+		pins.unpin(12);
+		assert_eq!(pins.earliest(), None);
+	}
+}
@@ -34,6 +34,7 @@ use rlp::{encode, decode};
 use crate::{
 	overlaydb::OverlayDB,
 	JournalDB, DB_PREFIX_LEN, LATEST_ERA_KEY,
+	pins::EraPins,
 	util::{DatabaseKey, DatabaseValueView, DatabaseValueRef},
 };
 
@@ -64,6 +65,7 @@ pub struct RefCountedDB {
 	inserts: Vec<H256>,
 	removes: Vec<H256>,
 	column: u32,
+	pins: EraPins,
 }
 
 impl RefCountedDB {
@@ -80,6 +82,7 @@ impl RefCountedDB {
 			removes: vec![],
 			latest_era,
 			column,
+			pins: EraPins::default(),
 		}
 	}
 }
@@ -101,6 +104,7 @@ impl JournalDB for RefCountedDB {
 			inserts: self.inserts.clone(),
 			removes: self.removes.clone(),
 			column: self.column.clone(),
+			pins: self.pins.clone(),
 		})
 	}
 
@@ -119,6 +123,18 @@ impl JournalDB for RefCountedDB {
 
 	fn latest_era(&self) -> Option<u64> { self.latest_era }
 
+	fn pin_era(&self, era: u64) {
+		self.pins.pin(era)
+	}
+
+	fn unpin_era(&self, era: u64) {
+		self.pins.unpin(era)
+	}
+
+	fn earliest_pinned_era(&self) -> Option<u64> {
+		self.pins.earliest()
+	}
+
 	fn state(&self, id: &H256) -> Option<Bytes> {
 		self.backing.get_by_prefix(self.column, &id[0..DB_PREFIX_LEN]).map(|b| b.into_vec())
 	}
@@ -164,6 +180,7 @@ impl JournalDB for RefCountedDB {
 	}
 
 	fn mark_canonical(&mut self, batch: &mut DBTransaction, end_era: u64, canon_id: &H256) -> io::Result<u32> {
+		self.pins.check_prunable(end_era)?;
 		// apply old commits' details
 		let mut db_key = DatabaseKey {
 			era: end_era,
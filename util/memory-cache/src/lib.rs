@@ -82,6 +82,12 @@ impl<K: Eq + Hash, V: MallocSizeOf> MemoryLruCache<K, V> {
 		self.inner.get_mut(key)
 	}
 
+	/// Remove all items from the cache.
+	pub fn clear(&mut self) {
+		self.inner.clear();
+		self.cur_size = 0;
+	}
+
 	/// Currently-used size of values in bytes.
 	pub fn current_size(&self) -> usize {
 		self.cur_size
@@ -0,0 +1,275 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Operator-maintained list of nodes and address ranges that are never dialed or accepted.
+
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use ipnetwork::IpNetwork;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use serde_json;
+
+use network::Error;
+
+use crate::ip_utils::SocketAddrExt;
+use crate::node_table::{Node, NodeId};
+
+const BLACKLIST_FILE: &str = "blacklist.json";
+
+/// A single blacklist entry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BlacklistEntry {
+	/// A node id, given either as an `enode://` URL or as plain hex.
+	Node(NodeId),
+	/// An IP address or CIDR range.
+	Network(IpNetwork),
+}
+
+impl FromStr for BlacklistEntry {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.starts_with("enode://") {
+			return Node::from_str(s).map(|n| BlacklistEntry::Node(n.id));
+		}
+		if let Ok(id) = NodeId::from_str(s) {
+			return Ok(BlacklistEntry::Node(id));
+		}
+		if s.contains('/') {
+			return IpNetwork::from_str(s).map(BlacklistEntry::Network).map_err(|_| Error::AddressParse);
+		}
+		let ip = IpAddr::from_str(s)?;
+		let prefix = if ip.is_ipv4() { 32 } else { 128 };
+		IpNetwork::new(ip, prefix).map(BlacklistEntry::Network).map_err(|_| Error::AddressParse)
+	}
+}
+
+impl Display for BlacklistEntry {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match *self {
+			BlacklistEntry::Node(ref id) => write!(f, "{:x}", id),
+			BlacklistEntry::Network(ref net) => write!(f, "{}", net),
+		}
+	}
+}
+
+/// Blacklist backed by a file in the network configuration directory.
+pub struct Blacklist {
+	nodes: HashSet<NodeId>,
+	networks: Vec<IpNetwork>,
+	path: Option<String>,
+}
+
+impl Blacklist {
+	/// Load the blacklist from `path`, if given.
+	pub fn new(path: Option<String>) -> Blacklist {
+		let mut blacklist = Blacklist {
+			nodes: HashSet::new(),
+			networks: Vec::new(),
+			path,
+		};
+		for entry in blacklist.load() {
+			blacklist.insert(entry);
+		}
+		blacklist
+	}
+
+	/// Add an entry and persist the list. Returns `false` if it was already present.
+	pub fn add(&mut self, entry: BlacklistEntry) -> io::Result<bool> {
+		let added = self.insert(entry);
+		if added {
+			self.save()?;
+		}
+		Ok(added)
+	}
+
+	/// Remove an entry and persist the list. Returns `false` if it was not present.
+	pub fn remove(&mut self, entry: &BlacklistEntry) -> io::Result<bool> {
+		let removed = match *entry {
+			BlacklistEntry::Node(ref id) => self.nodes.remove(id),
+			BlacklistEntry::Network(ref net) => {
+				let len = self.networks.len();
+				self.networks.retain(|n| n != net);
+				self.networks.len() != len
+			},
+		};
+		if removed {
+			self.save()?;
+		}
+		Ok(removed)
+	}
+
+	/// All entries, nodes first.
+	pub fn entries(&self) -> Vec<BlacklistEntry> {
+		self.nodes.iter().cloned().map(BlacklistEntry::Node)
+			.chain(self.networks.iter().cloned().map(BlacklistEntry::Network))
+			.collect()
+	}
+
+	/// Whether the node id is blacklisted.
+	pub fn contains_node(&self, id: &NodeId) -> bool {
+		self.nodes.contains(id)
+	}
+
+	/// Whether the address falls into a blacklisted range.
+	pub fn contains_ip(&self, ip: &IpAddr) -> bool {
+		self.networks.iter().any(|net| ip.is_within(net))
+	}
+
+	fn insert(&mut self, entry: BlacklistEntry) -> bool {
+		match entry {
+			BlacklistEntry::Node(id) => self.nodes.insert(id),
+			BlacklistEntry::Network(net) => {
+				if self.networks.contains(&net) {
+					false
+				} else {
+					self.networks.push(net);
+					true
+				}
+			},
+		}
+	}
+
+	fn save(&self) -> io::Result<()> {
+		let mut path = match self.path {
+			Some(ref path) => PathBuf::from(path),
+			None => return Ok(()),
+		};
+		fs::create_dir_all(&path)?;
+		path.push(BLACKLIST_FILE);
+		let file = json::Blacklist {
+			entries: self.entries().iter().map(ToString::to_string).collect(),
+		};
+
+		let f = fs::File::create(&path)?;
+		serde_json::to_writer_pretty(f, &file)?;
+		Ok(())
+	}
+
+	fn load(&self) -> Vec<BlacklistEntry> {
+		let path = match self.path {
+			Some(ref path) => PathBuf::from(path).join(BLACKLIST_FILE),
+			None => return Vec::new(),
+		};
+
+		let file = match fs::File::open(&path) {
+			Ok(file) => file,
+			Err(e) => {
+				debug!(target: "network", "Error opening blacklist file: {:?}", e);
+				return Vec::new();
+			},
+		};
+		let res: Result<json::Blacklist, _> = serde_json::from_reader(file);
+		match res {
+			Ok(file) => {
+				file.entries.iter()
+					.filter_map(|e| match e.parse() {
+						Ok(entry) => Some(entry),
+						Err(err) => {
+							warn!(target: "network", "Ignoring invalid blacklist entry {}: {:?}", e, err);
+							None
+						}
+					})
+					.collect()
+			},
+			Err(e) => {
+				warn!(target: "network", "Error reading blacklist file: {:?}", e);
+				Vec::new()
+			},
+		}
+	}
+}
+
+mod json {
+	use super::*;
+
+	#[derive(Serialize, Deserialize)]
+	pub struct Blacklist {
+		pub entries: Vec<String>,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::net::IpAddr;
+	use std::str::FromStr;
+
+	use tempdir::TempDir;
+
+	use super::*;
+
+	const NODE: &str = "a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c";
+
+	#[test]
+	fn parses_entries() {
+		let id = NodeId::from_str(NODE).unwrap();
+		assert_eq!(BlacklistEntry::from_str(NODE).unwrap(), BlacklistEntry::Node(id));
+		assert_eq!(
+			BlacklistEntry::from_str(&format!("enode://{}@22.99.55.44:7770", NODE)).unwrap(),
+			BlacklistEntry::Node(id),
+		);
+		assert_eq!(
+			BlacklistEntry::from_str("10.0.0.0/8").unwrap(),
+			BlacklistEntry::Network(IpNetwork::from_str("10.0.0.0/8").unwrap()),
+		);
+		assert_eq!(BlacklistEntry::from_str("10.1.2.3").unwrap().to_string(), "10.1.2.3/32");
+		assert_eq!(BlacklistEntry::from_str("::1").unwrap().to_string(), "::1/128");
+		assert!(BlacklistEntry::from_str("10.0.0.0/33").is_err());
+		assert!(BlacklistEntry::from_str("not an entry").is_err());
+	}
+
+	#[test]
+	fn matches_nodes_and_ranges() {
+		let mut blacklist = Blacklist::new(None);
+		let id = NodeId::from_str(NODE).unwrap();
+		assert!(blacklist.add(BlacklistEntry::Node(id)).unwrap());
+		assert!(!blacklist.add(BlacklistEntry::Node(id)).unwrap());
+		assert!(blacklist.add("10.0.0.0/8".parse().unwrap()).unwrap());
+
+		assert!(blacklist.contains_node(&id));
+		assert!(blacklist.contains_ip(&IpAddr::from_str("10.20.30.40").unwrap()));
+		assert!(!blacklist.contains_ip(&IpAddr::from_str("11.0.0.1").unwrap()));
+
+		assert!(blacklist.remove(&"10.0.0.0/8".parse().unwrap()).unwrap());
+		assert!(!blacklist.contains_ip(&IpAddr::from_str("10.20.30.40").unwrap()));
+		assert!(!blacklist.remove(&"10.0.0.0/8".parse().unwrap()).unwrap());
+	}
+
+	#[test]
+	fn persists_entries() {
+		let tempdir = TempDir::new("").unwrap();
+		let path = Some(tempdir.path().to_str().unwrap().to_owned());
+		let id = NodeId::from_str(NODE).unwrap();
+
+		{
+			let mut blacklist = Blacklist::new(path.clone());
+			blacklist.add(BlacklistEntry::Node(id)).unwrap();
+			blacklist.add("192.168.0.0/16".parse().unwrap()).unwrap();
+		}
+
+		let blacklist = Blacklist::new(path);
+		assert!(blacklist.contains_node(&id));
+		assert!(blacklist.contains_ip(&IpAddr::from_str("192.168.1.1").unwrap()));
+		assert_eq!(blacklist.entries().len(), 2);
+	}
+}
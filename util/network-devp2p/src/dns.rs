@@ -0,0 +1,271 @@
+// Copyright 2015-2020 Parity Technologies (UK) Ltd.
+// This file is part of Parity Ethereum.
+
+// Parity Ethereum is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity Ethereum is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Node lists published in DNS.
+//!
+//! A boot node given as `dns://<domain>` stands for the nodes listed in the TXT records of
+//! the domain, each record holding one or more enode urls separated by whitespace. Seed
+//! operators can change the list without shipping a new chain spec or binary.
+//!
+//! The standard library only resolves addresses, so the TXT records are queried over UDP
+//! from the first name server in `/etc/resolv.conf`. Queries block, callers are expected
+//! to make them off the network event loop.
+
+use std::fs;
+use std::io;
+use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::time::Duration;
+
+use rand::random;
+
+/// Prefix of boot node urls referring to a DNS node list.
+pub const NODE_LIST_PREFIX: &str = "dns://";
+
+const RESOLV_CONF: &str = "/etc/resolv.conf";
+const DNS_PORT: u16 = 53;
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+const TYPE_TXT: u16 = 16;
+const TYPE_OPT: u16 = 41;
+const CLASS_IN: u16 = 1;
+/// Response size advertised through EDNS, without it responses are cut at 512 bytes,
+/// which only fits a few enode urls.
+const MAX_RESPONSE_SIZE: u16 = 4096;
+
+/// Returns the domain of a boot node url referring to a DNS node list.
+pub fn node_list_domain(url: &str) -> Option<&str> {
+	if url.starts_with(NODE_LIST_PREFIX) {
+		Some(&url[NODE_LIST_PREFIX.len()..])
+	} else {
+		None
+	}
+}
+
+/// Check if `domain` is a well-formed domain name.
+pub fn is_valid_domain(domain: &str) -> bool {
+	domain.len() <= 253 && domain.trim_end_matches('.').split('.').all(|label| {
+		!label.is_empty() && label.len() <= 63 && label.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+	})
+}
+
+/// Looks up the TXT records of `domain`. A domain that doesn't exist has no records.
+pub fn resolve_txt(domain: &str) -> io::Result<Vec<String>> {
+	let server = name_server()?;
+	let id = random::<u16>();
+	let query = encode_query(id, domain)?;
+
+	let local: SocketAddr = if server.is_ipv4() { ([0u8; 4], 0).into() } else { ([0u16; 8], 0).into() };
+	let socket = UdpSocket::bind(local)?;
+	socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+	socket.connect(server)?;
+	socket.send(&query)?;
+
+	let mut response = vec![0u8; MAX_RESPONSE_SIZE as usize];
+	loop {
+		let len = socket.recv(&mut response)?;
+		// stray answers to other queries are skipped
+		if len >= 2 && response[0..2] == id.to_be_bytes() {
+			return decode_txt_response(&response[..len]);
+		}
+	}
+}
+
+fn name_server() -> io::Result<SocketAddr> {
+	fs::read_to_string(RESOLV_CONF)?
+		.lines()
+		.filter_map(|line| {
+			let mut words = line.split_whitespace();
+			match (words.next(), words.next()) {
+				(Some("nameserver"), Some(address)) => address.parse::<IpAddr>().ok(),
+				_ => None,
+			}
+		})
+		.next()
+		.map(|ip| SocketAddr::new(ip, DNS_PORT))
+		.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no name server configured"))
+}
+
+fn encode_query(id: u16, domain: &str) -> io::Result<Vec<u8>> {
+	if !is_valid_domain(domain) {
+		return Err(invalid_data("invalid domain name"));
+	}
+
+	let mut query = Vec::with_capacity(domain.len() + 29);
+	query.extend_from_slice(&id.to_be_bytes());
+	// recursion desired, one question and the EDNS record
+	query.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 1]);
+	for label in domain.trim_end_matches('.').split('.') {
+		query.push(label.len() as u8);
+		query.extend_from_slice(label.as_bytes());
+	}
+	query.push(0);
+	query.extend_from_slice(&TYPE_TXT.to_be_bytes());
+	query.extend_from_slice(&CLASS_IN.to_be_bytes());
+	// EDNS record: root name, the response size in place of the class, no ttl and no data
+	query.push(0);
+	query.extend_from_slice(&TYPE_OPT.to_be_bytes());
+	query.extend_from_slice(&MAX_RESPONSE_SIZE.to_be_bytes());
+	query.extend_from_slice(&[0, 0, 0, 0, 0, 0]);
+	Ok(query)
+}
+
+fn decode_txt_response(response: &[u8]) -> io::Result<Vec<String>> {
+	let mut reader = Reader { data: response, pos: 0 };
+	reader.skip(2)?;
+	let flags = reader.u16()?;
+	if flags & 0x8000 == 0 {
+		return Err(invalid_data("not a response"));
+	}
+	match flags & 0x000f {
+		0 => {},
+		// no such domain
+		3 => return Ok(Vec::new()),
+		code => return Err(invalid_data(&format!("name server error {}", code))),
+	}
+	let questions = reader.u16()?;
+	let answers = reader.u16()?;
+	// authority and additional records aren't needed
+	reader.skip(4)?;
+
+	for _ in 0..questions {
+		reader.skip_name()?;
+		reader.skip(4)?;
+	}
+
+	let mut records = Vec::new();
+	for _ in 0..answers {
+		reader.skip_name()?;
+		let record_type = reader.u16()?;
+		// class and ttl
+		reader.skip(6)?;
+		let len = reader.u16()? as usize;
+		let data = reader.take(len)?;
+		// e.g. the CNAME records leading to the TXT records
+		if record_type != TYPE_TXT {
+			continue;
+		}
+
+		// a TXT record is a sequence of length-prefixed strings, which are joined
+		let mut text = Vec::with_capacity(len);
+		let mut strings = Reader { data, pos: 0 };
+		while strings.pos < data.len() {
+			let len = strings.u8()? as usize;
+			text.extend_from_slice(strings.take(len)?);
+		}
+		records.push(String::from_utf8_lossy(&text).into_owned());
+	}
+	Ok(records)
+}
+
+fn invalid_data(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+		let end = self.pos + len;
+		if end > self.data.len() {
+			return Err(invalid_data("truncated response"));
+		}
+		let bytes = &self.data[self.pos..end];
+		self.pos = end;
+		Ok(bytes)
+	}
+
+	fn skip(&mut self, len: usize) -> io::Result<()> {
+		self.take(len).map(|_| ())
+	}
+
+	fn u8(&mut self) -> io::Result<u8> {
+		self.take(1).map(|bytes| bytes[0])
+	}
+
+	fn u16(&mut self) -> io::Result<u16> {
+		self.take(2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn skip_name(&mut self) -> io::Result<()> {
+		loop {
+			match self.u8()? {
+				0 => return Ok(()),
+				// a pointer to the rest of the name elsewhere in the message ends it
+				len if len & 0xc0 == 0xc0 => return self.skip(1),
+				len if len & 0xc0 == 0 => self.skip(len as usize)?,
+				_ => return Err(invalid_data("unsupported label type")),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn validates_domains() {
+		assert!(is_valid_domain("seeds.example.org"));
+		assert!(is_valid_domain("seeds.example.org."));
+		assert!(is_valid_domain("_nodes.example-1.org"));
+		assert!(!is_valid_domain(""));
+		assert!(!is_valid_domain("seeds..example.org"));
+		assert!(!is_valid_domain("seeds.example.org/path"));
+		assert!(!is_valid_domain(&"a".repeat(64)));
+		assert_eq!(node_list_domain("dns://seeds.example.org"), Some("seeds.example.org"));
+		assert_eq!(node_list_domain("enode://00@seeds.example.org:30303"), None);
+	}
+
+	#[test]
+	fn encodes_txt_query() {
+		let query = encode_query(0x1234, "a.bc").unwrap();
+		assert_eq!(query, vec![
+			0x12, 0x34, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 1,
+			1, b'a', 2, b'b', b'c', 0, 0, 16, 0, 1,
+			0, 0, 41, 0x10, 0x00, 0, 0, 0, 0, 0, 0,
+		]);
+		assert!(encode_query(0, "a b").is_err());
+	}
+
+	#[test]
+	fn decodes_txt_response() {
+		let response = vec![
+			// id, flags, one question, two answers, no authority or additional records
+			0x12, 0x34, 0x81, 0x80, 0, 1, 0, 2, 0, 0, 0, 0,
+			// question a.bc TXT IN
+			1, b'a', 2, b'b', b'c', 0, 0, 16, 0, 1,
+			// CNAME answer pointing at the question name, skipped
+			0xc0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 2, 0xc0, 12,
+			// TXT answer of two strings
+			0xc0, 12, 0, 16, 0, 1, 0, 0, 0, 60, 0, 7, 2, b'a', b'b', 3, b' ', b'c', b'd',
+		];
+		assert_eq!(decode_txt_response(&response).unwrap(), vec!["ab cd".to_owned()]);
+
+		// truncated record data
+		assert!(decode_txt_response(&response[..response.len() - 1]).is_err());
+	}
+
+	#[test]
+	fn decodes_missing_domain_as_empty() {
+		let response = vec![0x12, 0x34, 0x81, 0x83, 0, 0, 0, 0, 0, 0, 0, 0];
+		assert!(decode_txt_response(&response).unwrap().is_empty());
+
+		let response = vec![0x12, 0x34, 0x81, 0x82, 0, 0, 0, 0, 0, 0, 0, 0];
+		assert!(decode_txt_response(&response).is_err());
+	}
+}
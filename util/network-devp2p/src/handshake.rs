@@ -72,6 +72,8 @@ pub struct Handshake {
 	pub auth_cipher: Bytes,
 	/// A copy of received encrypted ack packet
 	pub ack_cipher: Bytes,
+	/// Whether we sent an EIP-8 auth packet
+	eip8_auth: bool,
 }
 
 const V4_AUTH_PACKET_SIZE: usize = 307;
@@ -96,15 +98,19 @@ impl Handshake {
 			remote_version: PROTOCOL_VERSION,
 			auth_cipher: Bytes::new(),
 			ack_cipher: Bytes::new(),
+			eip8_auth: false,
 		})
 	}
 
-	/// Start a handshake
-	pub fn start<Message>(&mut self, io: &IoContext<Message>, host: &HostInfo, originated: bool) -> Result<(), Error> where Message: Send + Clone+ Sync + 'static {
+	/// Start a handshake. Outgoing handshakes send an EIP-8 auth packet unless `legacy_auth` is set,
+	/// for peers that didn't answer one before.
+	pub fn start<Message>(&mut self, io: &IoContext<Message>, host: &HostInfo, originated: bool, legacy_auth: bool) -> Result<(), Error> where Message: Send + Clone+ Sync + 'static {
 		self.originated = originated;
 		io.register_timer(self.connection.token, HANDSHAKE_TIMEOUT).ok();
-		if originated {
+		if originated && legacy_auth {
 			self.write_auth(io, host.secret(), host.id())?;
+		} else if originated {
+			self.write_auth_eip8(io, host.secret(), host.id())?;
 		}
 		else {
 			self.state = HandshakeState::ReadingAuth;
@@ -118,6 +124,11 @@ impl Handshake {
 		self.state == HandshakeState::StartSession
 	}
 
+	/// Check if we sent an EIP-8 auth packet and no ack has arrived yet.
+	pub fn eip8_auth_unanswered(&self) -> bool {
+		self.eip8_auth && self.state == HandshakeState::ReadingAck
+	}
+
 	/// Readable IO handler. Drives the state change.
 	pub fn readable<Message>(&mut self, io: &IoContext<Message>, host: &HostInfo) -> Result<(), Error> where Message: Send + Clone + Sync + 'static {
 		while let Some(data) = self.connection.readable()? {
@@ -276,6 +287,36 @@ impl Handshake {
 		Ok(())
 	}
 
+	/// Sends EIP8 auth message
+	fn write_auth_eip8<Message>(&mut self, io: &IoContext<Message>, secret: &Secret, public: &Public) -> Result<(), Error> where Message: Send + Clone + Sync + 'static {
+		trace!(target: "network", "Sending EIP8 handshake auth to {:?}", self.connection.remote_addr_str());
+		// S(ecdhe-random, ecdh-shared-secret^nonce)
+		let shared = *ecdh::agree(secret, &self.id)?;
+		let signature = H520::from_slice(&*sign(self.ecdhe.secret(), &(shared ^ self.nonce))?);
+
+		let mut rlp = RlpStream::new_list(4);
+		rlp.append(&signature);
+		rlp.append(public);
+		rlp.append(&self.nonce);
+		rlp.append(&PROTOCOL_VERSION);
+
+		let pad_array = [0u8; 200];
+		let pad = &pad_array[0 .. 100 + random::<usize>() % 100];
+		rlp.append_raw(pad, 0);
+
+		let encoded = rlp.drain();
+		let len = (encoded.len() + ECIES_OVERHEAD) as u16;
+		let prefix = [ (len >> 8) as u8, (len & 0xff) as u8 ];
+		let message = ecies::encrypt(&self.id, &prefix, &encoded)?;
+		self.auth_cipher = prefix.to_vec();
+		self.auth_cipher.extend_from_slice(&message);
+		self.connection.send(io, self.auth_cipher.clone());
+		self.connection.expect(V4_ACK_PACKET_SIZE);
+		self.state = HandshakeState::ReadingAck;
+		self.eip8_auth = true;
+		Ok(())
+	}
+
 	/// Sends ack message
 	fn write_ack<Message>(&mut self, io: &IoContext<Message>) -> Result<(), Error> where Message: Send + Clone + Sync + 'static {
 		trace!(target: "network", "Sending handshake ack to {:?}", self.connection.remote_addr_str());
@@ -442,6 +483,48 @@ mod test {
 		assert_eq!(ack.len(), total);
 	}
 
+	#[test]
+	fn test_handshake_write_auth_eip8() {
+		let initiator_keys = Random.generate().unwrap();
+		let responder_keys = Random.generate().unwrap();
+
+		let mut initiator = create_handshake(Some(responder_keys.public()));
+		initiator.write_auth_eip8(&test_io(), initiator_keys.secret(), initiator_keys.public()).unwrap();
+		assert_eq!(initiator.state, super::HandshakeState::ReadingAck);
+
+		let auth = initiator.auth_cipher.clone();
+		let total = (((auth[0] as u16) << 8 | (auth[1] as u16)) as usize) + 2;
+		assert_eq!(auth.len(), total);
+
+		let mut responder = create_handshake(None);
+		responder.read_auth(&test_io(), responder_keys.secret(), &auth[0..super::V4_AUTH_PACKET_SIZE]).unwrap();
+		assert_eq!(responder.state, super::HandshakeState::ReadingAuthEip8);
+		responder.read_auth_eip8(&test_io(), responder_keys.secret(), &auth[super::V4_AUTH_PACKET_SIZE..]).unwrap();
+		assert_eq!(responder.state, super::HandshakeState::StartSession);
+		assert_eq!(&responder.id, initiator_keys.public());
+		assert_eq!(&responder.remote_ephemeral, initiator.ecdhe.public());
+		assert_eq!(responder.remote_version, super::PROTOCOL_VERSION);
+		assert!(initiator.eip8_auth_unanswered());
+	}
+
+	#[test]
+	fn test_handshake_write_auth_legacy() {
+		let initiator_keys = Random.generate().unwrap();
+		let responder_keys = Random.generate().unwrap();
+
+		let mut initiator = create_handshake(Some(responder_keys.public()));
+		initiator.write_auth(&test_io(), initiator_keys.secret(), initiator_keys.public()).unwrap();
+		assert_eq!(initiator.state, super::HandshakeState::ReadingAck);
+		assert_eq!(initiator.auth_cipher.len(), super::V4_AUTH_PACKET_SIZE);
+		assert!(!initiator.eip8_auth_unanswered());
+
+		let mut responder = create_handshake(None);
+		responder.read_auth(&test_io(), responder_keys.secret(), &initiator.auth_cipher).unwrap();
+		assert_eq!(responder.state, super::HandshakeState::StartSession);
+		assert_eq!(&responder.id, initiator_keys.public());
+		assert_eq!(&responder.remote_ephemeral, initiator.ecdhe.public());
+	}
+
 	#[test]
 	fn test_handshake_ack_plain() {
 		let remote = H512::from_str("fda1cff674c90c9a197539fe3dfb53086ace64f83ed7c6eabec741f7f381cc803e52ab2cd55d5569bce4347107a310dfd5f88a010cd2ffd1005ca406f1842877").unwrap();
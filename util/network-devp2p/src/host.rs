@@ -24,7 +24,8 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use ethereum_types::H256;
 use keccak_hash::keccak;
@@ -34,6 +35,9 @@ use mio::{
 	Token,
 	udp::UdpSocket
 };
+use net2::TcpBuilder;
+#[cfg(unix)]
+use net2::unix::UnixTcpBuilderExt;
 use parity_path::restrict_permissions_owner;
 use parking_lot::{Mutex, RwLock};
 use rlp::{Encodable, RlpStream};
@@ -47,9 +51,11 @@ use network::{
 };
 
 use crate::{
+	blacklist::{Blacklist, BlacklistEntry},
 	connection::PAYLOAD_SOFT_LIMIT,
 	discovery::{Discovery, MAX_DATAGRAM_SIZE, NodeEntry, TableUpdates},
-	ip_utils::{map_external_address, select_public_address},
+	dns,
+	ip_utils::{map_external_address, select_public_address, SocketAddrExt},
 	node_table::*,
 	PROTOCOL_VERSION,
 	session::{Session, SessionData}
@@ -70,6 +76,7 @@ const DISCOVERY_REFRESH: TimerToken = SYS_TIMER + 4;
 const FAST_DISCOVERY_REFRESH: TimerToken = SYS_TIMER + 5;
 const DISCOVERY_ROUND: TimerToken = SYS_TIMER + 6;
 const NODE_TABLE: TimerToken = SYS_TIMER + 7;
+const BOOT_NODES_RESOLVE: TimerToken = SYS_TIMER + 8;
 const FIRST_SESSION: StreamToken = 0;
 const LAST_SESSION: StreamToken = FIRST_SESSION + MAX_SESSIONS - 1;
 const USER_TIMER: TimerToken = LAST_SESSION + 256;
@@ -86,6 +93,13 @@ const FAST_DISCOVERY_REFRESH_TIMEOUT: Duration = Duration::from_secs(10);
 const DISCOVERY_ROUND_TIMEOUT: Duration = Duration::from_millis(300);
 // for NODE_TABLE TimerToken
 const NODE_TABLE_TIMEOUT: Duration = Duration::from_secs(300);
+// for BOOT_NODES_RESOLVE TimerToken
+const BOOT_NODES_RESOLVE_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Number of EIP-8 auth packets a node has to leave unanswered before it is sent legacy ones.
+const LEGACY_AUTH_FAILURES: u32 = 3;
+/// Time after the last unanswered EIP-8 auth packet for which a node is sent legacy ones.
+const LEGACY_AUTH_EXPIRY: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Debug, PartialEq, Eq)]
 /// Protocol info
@@ -259,9 +273,38 @@ struct ProtocolTimer {
 	pub token: TimerToken, // Handler level token
 }
 
+/// EIP-8 auth packets a node left unanswered.
+struct UnansweredAuth {
+	count: u32,
+	last: Instant,
+}
+
+impl UnansweredAuth {
+	fn new() -> Self {
+		UnansweredAuth { count: 0, last: Instant::now() }
+	}
+
+	/// Note another unanswered packet, counting afresh if the earlier ones have expired.
+	fn note(&mut self) {
+		if self.expired() {
+			self.count = 0;
+		}
+		self.count += 1;
+		self.last = Instant::now();
+	}
+
+	fn expired(&self) -> bool {
+		self.last.elapsed() >= LEGACY_AUTH_EXPIRY
+	}
+
+	fn use_legacy(&self) -> bool {
+		self.count >= LEGACY_AUTH_FAILURES && !self.expired()
+	}
+}
+
 /// Root IO handler. Manages protocol handlers, IO timers and network connections.
 ///
-/// NOTE: must keep the lock in order of: reserved_nodes (rwlock) -> session (mutex, from sessions)
+/// NOTE: must keep the lock in order of: reserved_nodes (rwlock) -> session (mutex, from sessions) -> blacklist (rwlock)
 pub struct Host {
 	pub info: RwLock<HostInfo>,
 	udp_socket: Mutex<Option<UdpSocket>>,
@@ -273,6 +316,12 @@ pub struct Host {
 	timers: RwLock<HashMap<TimerToken, ProtocolTimer>>,
 	timer_counter: RwLock<usize>,
 	reserved_nodes: RwLock<HashSet<NodeId>>,
+	/// Nodes that dropped the connection after our EIP-8 auth packet, sent a legacy one once
+	/// this happened often enough.
+	legacy_auth_nodes: RwLock<HashMap<NodeId, UnansweredAuth>>,
+	blacklist: RwLock<Blacklist>,
+	/// Set while a worker thread resolves boot nodes.
+	resolving_boot_nodes: Arc<AtomicBool>,
 	stopping: AtomicBool,
 	filter: Option<Arc<dyn ConnectionFilter>>,
 }
@@ -288,11 +337,13 @@ impl Host {
 		let keys = if let Some(ref secret) = config.use_secret {
 			KeyPair::from_secret(secret.clone())?
 		} else {
-			config.config_path.clone().and_then(|ref p| load_key(Path::new(&p)))
+			config.config_path.clone().and_then(|ref p| load_node_key(Path::new(&p)))
 				.map_or_else(|| {
 				let key = Random.generate().expect("Error generating random key pair");
 				if let Some(path) = config.config_path.clone() {
-					save_key(Path::new(&path), key.secret());
+					if let Err(e) = save_node_key(Path::new(&path), key.secret()) {
+						warn!("Error saving key file: {:?}", e);
+					}
 				}
 				key
 			},
@@ -300,7 +351,7 @@ impl Host {
 		};
 		let path = config.net_config_path.clone();
 		// Setup the server socket
-		let tcp_listener = TcpListener::bind(&listen_address)?;
+		let tcp_listener = bind_tcp_listener(&listen_address, config.reuse_port)?;
 		listen_address = SocketAddr::new(listen_address.ip(), tcp_listener.local_addr()?.port());
 		debug!(target: "network", "Listening at {:?}", listen_address);
 		let udp_port = config.udp_port.unwrap_or_else(|| listen_address.port());
@@ -324,17 +375,21 @@ impl Host {
 			udp_socket: Mutex::new(None),
 			tcp_listener: Mutex::new(tcp_listener),
 			sessions: Arc::new(RwLock::new(Slab::new_starting_at(FIRST_SESSION, MAX_SESSIONS))),
-			nodes: RwLock::new(NodeTable::new(path)),
+			nodes: RwLock::new(NodeTable::new(path.clone())),
 			handlers: RwLock::new(HashMap::new()),
 			timers: RwLock::new(HashMap::new()),
 			timer_counter: RwLock::new(USER_TIMER),
 			reserved_nodes: RwLock::new(HashSet::new()),
+			legacy_auth_nodes: RwLock::new(HashMap::new()),
+			blacklist: RwLock::new(Blacklist::new(path)),
+			resolving_boot_nodes: Arc::new(AtomicBool::new(false)),
 			stopping: AtomicBool::new(false),
 			filter,
 		};
 
-		for n in boot_nodes {
-			host.add_node(&n);
+		// DNS node lists are looked up once the host is started
+		for n in boot_nodes.iter().filter(|n| dns::node_list_domain(n).is_none()) {
+			host.add_node(n);
 		}
 
 		for n in reserved_nodes {
@@ -359,6 +414,57 @@ impl Host {
 		}
 	}
 
+	/// Resolves boot nodes given by host name or DNS node list again, so that seed nodes can
+	/// move or change without a new chain spec or binary. Name lookups block, so they are made
+	/// on a worker thread, which reports the nodes back with `BootNodesResolved`.
+	fn resolve_boot_nodes(&self, io: &IoContext<NetworkIoMessage>) {
+		let boot_nodes: Vec<String> = self.info.read().config.boot_nodes.iter()
+			.filter(|n| is_host_name_url(n))
+			.cloned()
+			.collect();
+
+		if boot_nodes.is_empty() || self.resolving_boot_nodes.swap(true, AtomicOrdering::AcqRel) {
+			return;
+		}
+
+		let resolving = self.resolving_boot_nodes.clone();
+		let channel = io.channel();
+		let worker = thread::Builder::new().name("boot-nodes".into()).spawn(move || {
+			let nodes = boot_nodes.iter()
+				.flat_map(|url| resolve_boot_node(url))
+				.map(|node| node.to_string())
+				.collect();
+			resolving.store(false, AtomicOrdering::Release);
+			channel.send(NetworkIoMessage::BootNodesResolved(nodes))
+				.unwrap_or_else(|e| debug!(target: "network", "Error sending resolved boot nodes: {:?}", e));
+		});
+
+		if let Err(e) = worker {
+			warn!(target: "network", "Error starting boot node resolution: {}", e);
+			self.resolving_boot_nodes.store(false, AtomicOrdering::Release);
+		}
+	}
+
+	/// Adds the boot nodes resolved by `resolve_boot_nodes` that are new or have moved.
+	fn add_resolved_boot_nodes(&self, urls: &[String]) {
+		for url in urls {
+			// resolved nodes carry IP addresses, parsing them doesn't query DNS
+			let n = match Node::from_str(url) {
+				Ok(n) => n,
+				Err(_) => continue,
+			};
+			let changed = self.nodes.read().get(&n.id).map_or(true, |node| node.endpoint.address != n.endpoint.address);
+			if changed {
+				debug!(target: "network", "Boot node resolved to {}", url);
+				let entry = NodeEntry { endpoint: n.endpoint.clone(), id: n.id };
+				self.nodes.write().add_node(n);
+				if let Some(ref mut discovery) = *self.discovery.lock() {
+					discovery.add_node(entry);
+				}
+			}
+		}
+	}
+
 	pub fn add_reserved_node(&self, id: &str) -> Result<(), Error> {
 		let n = Node::from_str(id)?;
 
@@ -410,6 +516,42 @@ impl Host {
 		Ok(())
 	}
 
+	/// Blacklist a node id or an IP range and drop any matching sessions.
+	pub fn add_to_blacklist(&self, entry: &str, io: &IoContext<NetworkIoMessage>) -> Result<(), Error> {
+		let entry = BlacklistEntry::from_str(entry)?;
+		self.blacklist.write().add(entry.clone())?;
+
+		let mut to_kill = Vec::new();
+		for e in self.sessions.read().iter() {
+			let mut s = e.lock();
+			let matches = match entry {
+				BlacklistEntry::Node(ref id) => s.id() == Some(id),
+				BlacklistEntry::Network(ref net) => s.remote_addr().map_or(false, |a| a.ip().is_within(net)),
+			};
+			if matches {
+				s.disconnect(io, DisconnectReason::DisconnectRequested);
+				to_kill.push(s.token());
+			}
+		}
+		for p in to_kill {
+			trace!(target: "network", "Disconnecting blacklisted peer: {}", p);
+			self.kill_connection(p, io, false);
+		}
+
+		Ok(())
+	}
+
+	pub fn remove_from_blacklist(&self, entry: &str) -> Result<(), Error> {
+		let entry = BlacklistEntry::from_str(entry)?;
+		self.blacklist.write().remove(&entry)?;
+
+		Ok(())
+	}
+
+	pub fn blacklist(&self) -> Vec<String> {
+		self.blacklist.read().entries().iter().map(ToString::to_string).collect()
+	}
+
 	pub fn external_url(&self) -> Option<String> {
 		let info = self.info.read();
 		info.public_endpoint.as_ref().map(|e| format!("{}", Node::new(*info.id(), e.clone())))
@@ -503,6 +645,11 @@ impl Host {
 			io.register_timer(DISCOVERY_ROUND, DISCOVERY_ROUND_TIMEOUT)?;
 		}
 		io.register_timer(NODE_TABLE, NODE_TABLE_TIMEOUT)?;
+		if self.info.read().config.boot_nodes.iter().any(|n| is_host_name_url(n)) {
+			io.register_timer(BOOT_NODES_RESOLVE, BOOT_NODES_RESOLVE_TIMEOUT)?;
+			// DNS node lists haven't been looked up yet
+			self.resolve_boot_nodes(io);
+		}
 		io.register_stream(TCP_ACCEPT)?;
 		Ok(())
 	}
@@ -563,19 +710,25 @@ impl Host {
 	}
 
 	fn connect_peers(&self, io: &IoContext<NetworkIoMessage>) {
-		let (min_peers, mut pin, max_handshakes, allow_ips, self_id) = {
+		let (min_peers, max_egress, mut pin, max_handshakes, allow_ips, self_id) = {
 			let info = self.info.read();
 			if info.capabilities.is_empty() {
 				return;
 			}
 			let config = &info.config;
 
-			(config.min_peers, config.non_reserved_mode == NonReservedPeerMode::Deny, config.max_handshakes as usize, config.ip_filter.clone(), *info.id())
+			(config.min_peers, config.max_outbound_peers, config.non_reserved_mode == NonReservedPeerMode::Deny, config.max_handshakes as usize, config.ip_filter.clone(), *info.id())
 		};
 
 		let (handshake_count, egress_count, ingress_count) = self.session_count();
 		let reserved_nodes = self.reserved_nodes.read();
-		if egress_count + ingress_count >= min_peers as usize + reserved_nodes.len() {
+		// with an outbound quota we keep dialing until it's used up, otherwise until we have min_peers
+		let max_egress = max_egress.map(|max_egress| max_egress as usize + reserved_nodes.len());
+		let enough_peers = match max_egress {
+			Some(max_egress) => egress_count >= max_egress,
+			None => egress_count + ingress_count >= min_peers as usize + reserved_nodes.len(),
+		};
+		if enough_peers {
 			// check if all pinned nodes are connected.
 			if reserved_nodes.iter().all(|n| self.have_session(n) && self.connecting_to(n)) {
 				return;
@@ -599,13 +752,18 @@ impl Host {
 		});
 
 		let max_handshakes_per_round = max_handshakes / 2;
+		let egress_left = match (pin, max_egress) {
+			(false, Some(max_egress)) => max_egress.saturating_sub(egress_count),
+			_ => usize::max_value(),
+		};
 		let mut started: usize = 0;
 		for id in nodes.filter(|id|
 				!self.have_session(id) &&
 				!self.connecting_to(id) &&
 				*id != self_id &&
+				!self.blacklist.read().contains_node(id) &&
 				self.filter.as_ref().map_or(true, |f| f.connection_allowed(&self_id, &id, ConnectionDirection::Outbound))
-			).take(min(max_handshakes_per_round, max_handshakes - handshake_count)) {
+			).take(min(min(max_handshakes_per_round, max_handshakes - handshake_count), egress_left)) {
 			self.connect_peer(&id, io);
 			started += 1;
 		}
@@ -632,6 +790,10 @@ impl Host {
 					return;
 				}
 			};
+			if self.blacklist.read().contains_ip(&address.ip()) {
+				trace!(target: "network", "{}: Aborted connect. Address {:?} is blacklisted", id, address);
+				return;
+			}
 			match TcpStream::connect(&address) {
 				Ok(socket) => {
 					trace!(target: "network", "{}: Connecting to {:?}", id, address);
@@ -652,11 +814,12 @@ impl Host {
 
 	fn create_connection(&self, socket: TcpStream, id: Option<&NodeId>, io: &IoContext<NetworkIoMessage>) -> Result<(), Error> {
 		let nonce = self.info.write().next_nonce();
+		let legacy_auth = id.map_or(false, |id| self.legacy_auth_nodes.read().get(id).map_or(false, UnansweredAuth::use_legacy));
 		let mut sessions = self.sessions.write();
 
 		let token = sessions.insert_with_opt(|token| {
 			trace!(target: "network", "{}: Initiating session {:?}", token, id);
-			match Session::new(io, socket, token, id, &nonce, &self.info.read()) {
+			match Session::new(io, socket, token, id, &nonce, &self.info.read(), legacy_auth) {
 				Ok(s) => Some(Arc::new(Mutex::new(s))),
 				Err(e) => {
 					debug!(target: "network", "Session create error: {:?}", e);
@@ -678,7 +841,13 @@ impl Host {
 		trace!(target: "network", "Accepting incoming connection");
 		loop {
 			let socket = match self.tcp_listener.lock().accept() {
-				Ok((sock, _addr)) => sock,
+				Ok((sock, addr)) => {
+					if self.blacklist.read().contains_ip(&addr.ip()) {
+						trace!(target: "network", "Rejecting incoming connection from blacklisted address {:?}", addr);
+						continue;
+					}
+					sock
+				},
 				Err(e) => {
 					if e.kind() != io::ErrorKind::WouldBlock {
 						debug!(target: "network", "Error accepting connection: {:?}", e);
@@ -745,7 +914,7 @@ impl Host {
 							let (_, egress_count, ingress_count) = self.session_count();
 							let reserved_nodes = self.reserved_nodes.read();
 							let mut s = session.lock();
-							let (min_peers, mut max_peers, reserved_only, self_id) = {
+							let (min_peers, mut max_peers, max_egress, max_ingress, reserved_only, self_id) = {
 								let info = self.info.read();
 								let mut max_peers = info.config.max_peers;
 								for cap in &s.info.capabilities {
@@ -754,7 +923,14 @@ impl Host {
 										break;
 									}
 								}
-								(info.config.min_peers as usize, max_peers as usize, info.config.non_reserved_mode == NonReservedPeerMode::Deny, *info.id())
+								(
+									info.config.min_peers as usize,
+									max_peers as usize,
+									info.config.max_outbound_peers,
+									info.config.max_inbound_peers,
+									info.config.non_reserved_mode == NonReservedPeerMode::Deny,
+									*info.id(),
+								)
 							};
 
 							max_peers = max(max_peers, min_peers);
@@ -762,11 +938,13 @@ impl Host {
 							let id = *s.id().expect("Ready session always has id");
 
 							// Check for the session limit.
+							// Unless configured otherwise:
 							// Outgoing connections are allowed as long as their count is <= min_peers
 							// Incoming connections are allowed to take all of the max_peers reserve, or at most half of the slots.
-							let max_ingress = max(max_peers - min_peers, min_peers / 2);
+							let max_egress = max_egress.map_or(min_peers, |n| n as usize);
+							let max_ingress = max_ingress.map_or_else(|| max(max_peers - min_peers, min_peers / 2), |n| n as usize);
 							if reserved_only ||
-								(s.info.originated && egress_count > min_peers) ||
+								(s.info.originated && egress_count > max_egress) ||
 								(!s.info.originated && ingress_count > max_ingress) {
 								if !reserved_nodes.contains(&id) {
 									// only proceed if the connecting peer is reserved.
@@ -777,6 +955,13 @@ impl Host {
 								}
 							}
 
+							if self.blacklist.read().contains_node(&id) {
+								trace!(target: "network", "Disconnecting blacklisted peer {:?}", id);
+								s.disconnect(io, DisconnectReason::UnexpectedIdentity);
+								kill = true;
+								break;
+							}
+
 							if !self.filter.as_ref().map_or(true, |f| f.connection_allowed(&self_id, &id, ConnectionDirection::Inbound)) {
 								trace!(target: "network", "Inbound connection not allowed for {:?}", id);
 								s.disconnect(io, DisconnectReason::UnexpectedIdentity);
@@ -931,6 +1116,7 @@ impl Host {
 	fn kill_connection(&self, token: StreamToken, io: &IoContext<NetworkIoMessage>, remote: bool) {
 		let mut to_disconnect: Vec<ProtocolId> = Vec::new();
 		let mut failure_id = None;
+		let mut legacy_auth_id = None;
 		let mut deregister = false;
 		let mut expired_session = None;
 		if let FIRST_SESSION ..= LAST_SESSION = token {
@@ -946,6 +1132,9 @@ impl Host {
 							}
 						}
 					}
+					if s.eip8_auth_unanswered() {
+						legacy_auth_id = s.id().cloned();
+					}
 					s.set_expired();
 					failure_id = s.id().cloned();
 				}
@@ -957,6 +1146,14 @@ impl Host {
 				self.nodes.write().note_failure(&id);
 			}
 		}
+		if let Some(id) = legacy_auth_id {
+			let mut legacy_auth_nodes = self.legacy_auth_nodes.write();
+			let unanswered = legacy_auth_nodes.entry(id).or_insert_with(UnansweredAuth::new);
+			unanswered.note();
+			if unanswered.use_legacy() {
+				debug!(target: "network", "{}: EIP-8 auth not answered {} times, using legacy auth", id, unanswered.count);
+			}
+		}
 		for p in to_disconnect {
 			let reserved = self.reserved_nodes.read();
 			if let Some(h) = self.handlers.read().get(&p) {
@@ -984,6 +1181,7 @@ impl Host {
 		for i in to_remove {
 			trace!(target: "network", "Removed from node table: {}", i);
 		}
+		self.legacy_auth_nodes.write().retain(|id, unanswered| !node_changes.removed.contains(id) && !unanswered.expired());
 		let reserved_nodes = self.reserved_nodes.read();
 		self.nodes.write().update(node_changes, &*reserved_nodes);
 	}
@@ -1075,6 +1273,7 @@ impl IoHandler<NetworkIoMessage> for Host {
 				nodes.clear_useless();
 				nodes.save();
 			},
+			BOOT_NODES_RESOLVE => self.resolve_boot_nodes(io),
 			_ => match self.timers.read().get(&token).cloned() {
 				Some(timer) => match self.handlers.read().get(&timer.protocol).cloned() {
 					None => { warn!(target: "network", "No handler found for protocol: {:?}", timer.protocol) },
@@ -1151,6 +1350,7 @@ impl IoHandler<NetworkIoMessage> for Host {
 			},
 			NetworkIoMessage::InitPublicInterface =>
 				self.init_public_interface(io).unwrap_or_else(|e| warn!("Error initializing public interface: {:?}", e)),
+			NetworkIoMessage::BootNodesResolved(ref nodes) => self.add_resolved_boot_nodes(nodes),
 			_ => {}	// ignore others.
 		}
 	}
@@ -1218,30 +1418,63 @@ impl IoHandler<NetworkIoMessage> for Host {
 	}
 }
 
-fn save_key(path: &Path, key: &Secret) {
-	let mut path_buf = PathBuf::from(path);
-	if let Err(e) = fs::create_dir_all(path_buf.as_path()) {
-		warn!("Error creating key directory: {:?}", e);
-		return;
+/// Resolves a boot node url, or the nodes listed in a DNS node list, to nodes with IP addresses.
+fn resolve_boot_node(url: &str) -> Vec<Node> {
+	let domain = match dns::node_list_domain(url) {
+		Some(domain) => domain,
+		None => return match Node::from_str(url) {
+			Ok(node) => vec![node],
+			Err(e) => {
+				debug!(target: "network", "Could not resolve boot node {}: {:?}", url, e);
+				Vec::new()
+			}
+		},
 	};
-	path_buf.push("key");
-	let path = path_buf.as_path();
-	let mut file = match fs::File::create(&path) {
-		Ok(file) => file,
+
+	match dns::resolve_txt(domain) {
+		Ok(records) => records.iter()
+			.flat_map(|record| record.split_whitespace())
+			.filter_map(|enode| Node::from_str(enode)
+				.map_err(|e| debug!(target: "network", "Invalid node {} listed by {}: {:?}", enode, url, e))
+				.ok())
+			.collect(),
 		Err(e) => {
-			warn!("Error creating key file: {:?}", e);
-			return;
+			debug!(target: "network", "Could not look up node list {}: {}", url, e);
+			Vec::new()
 		}
+	}
+}
+
+/// Bind the listening socket, sharing the port with other sockets if `reuse_port` is set.
+fn bind_tcp_listener(address: &SocketAddr, reuse_port: bool) -> io::Result<TcpListener> {
+	if !reuse_port {
+		return TcpListener::bind(address);
+	}
+	let builder = match *address {
+		SocketAddr::V4(_) => TcpBuilder::new_v4()?,
+		SocketAddr::V6(_) => TcpBuilder::new_v6()?,
 	};
+	builder.reuse_address(true)?;
+	#[cfg(unix)]
+	builder.reuse_port(true)?;
+	TcpListener::from_std(builder.bind(address)?.listen(1024)?)
+}
+
+/// Store the node key in the `key` file of the given network directory, creating it if needed.
+pub fn save_node_key(path: &Path, key: &Secret) -> io::Result<()> {
+	let mut path_buf = PathBuf::from(path);
+	fs::create_dir_all(path_buf.as_path())?;
+	path_buf.push("key");
+	let path = path_buf.as_path();
+	let mut file = fs::File::create(&path)?;
 	if let Err(e) = restrict_permissions_owner(path, true, false) {
 		warn!(target: "network", "Failed to modify permissions of the file ({})", e);
 	}
-	if let Err(e) = file.write(&key.to_hex().into_bytes()) {
-		warn!("Error writing key file: {:?}", e);
-	}
+	file.write_all(&key.to_hex().into_bytes())
 }
 
-fn load_key(path: &Path) -> Option<Secret> {
+/// Load the node key from the `key` file of the given network directory.
+pub fn load_node_key(path: &Path) -> Option<Secret> {
 	let mut path_buf = PathBuf::from(path);
 	path_buf.push("key");
 	let mut file = match fs::File::open(path_buf.as_path()) {
@@ -1274,11 +1507,20 @@ fn key_save_load() {
 
 	let tempdir = TempDir::new("").unwrap();
 	let key = H256::random().into();
-	save_key(tempdir.path(), &key);
-	let r = load_key(tempdir.path());
+	save_node_key(tempdir.path(), &key).unwrap();
+	let r = load_node_key(tempdir.path());
 	assert_eq!(key, r.unwrap());
 }
 
+#[test]
+#[cfg(unix)]
+fn binds_shared_port() {
+	let address = SocketAddr::from_str("127.0.0.1:0").unwrap();
+	let first = bind_tcp_listener(&address, true).unwrap();
+	let address = first.local_addr().unwrap();
+	assert!(bind_tcp_listener(&address, true).is_ok());
+}
+
 #[test]
 fn host_client_url() {
 	let mut config = NetworkConfiguration::new_local();
@@ -1287,3 +1529,21 @@ fn host_client_url() {
 	let host: Host = Host::new(config, None).unwrap();
 	assert!(host.local_url().starts_with("enode://101b3ef5a4ea7a1c7928e24c4c75fd053c235d7b80c22ae5c03d145d0ac7396e2a4ffff9adee3133a7b05044a5cee08115fd65145e5165d646bde371010d803c@"));
 }
+
+#[test]
+fn legacy_auth_needs_repeated_failures() {
+	let mut unanswered = UnansweredAuth::new();
+	for _ in 1..LEGACY_AUTH_FAILURES {
+		unanswered.note();
+		assert!(!unanswered.use_legacy());
+	}
+	unanswered.note();
+	assert!(unanswered.use_legacy());
+
+	// an expired mark starts counting afresh
+	unanswered.last = Instant::now() - LEGACY_AUTH_EXPIRY;
+	assert!(!unanswered.use_legacy());
+	unanswered.note();
+	assert_eq!(unanswered.count, 1);
+	assert!(!unanswered.use_legacy());
+}
@@ -61,15 +61,17 @@
 #![allow(deprecated)]
 
 pub use ethcore_io::TimerToken;
-pub use host::NetworkContext;
+pub use host::{NetworkContext, load_node_key, save_node_key};
 pub use node_table::{MAX_NODES_IN_TABLE, NodeId, validate_node_url};
 pub use service::NetworkService;
 
+mod blacklist;
 mod host;
 mod connection;
 mod handshake;
 mod session;
 mod discovery;
+mod dns;
 mod service;
 mod node_table;
 mod ip_utils;
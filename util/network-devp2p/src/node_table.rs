@@ -35,6 +35,7 @@ use network::{AllowIP, Error, IpFilter};
 
 use crate::{
 	discovery::{NodeEntry, TableUpdates},
+	dns,
 	ip_utils::*,
 };
 
@@ -533,14 +534,31 @@ impl Drop for NodeTable {
 	}
 }
 
-/// Check if node url is valid
+/// Check if node url is valid. DNS node lists (`dns://<domain>`) are only checked for a
+/// well-formed domain, their nodes are looked up later.
 pub fn validate_node_url(url: &str) -> Option<Error> {
+	if let Some(domain) = dns::node_list_domain(url) {
+		return if dns::is_valid_domain(domain) { None } else { Some(Error::AddressParse) };
+	}
 	match Node::from_str(url) {
 		Ok(_) => None,
 		Err(e) => Some(e)
 	}
 }
 
+/// Check if node url refers to its host by name rather than by IP address, or is a DNS
+/// node list, in which case the nodes may change and the name has to be resolved again.
+pub fn is_host_name_url(url: &str) -> bool {
+	if dns::node_list_domain(url).is_some() {
+		return true;
+	}
+	let endpoint = match url.find('@') {
+		Some(at) if url.starts_with("enode://") => &url[at + 1..],
+		_ => url,
+	};
+	endpoint.parse::<SocketAddr>().is_err()
+}
+
 mod json {
 	use super::*;
 
@@ -656,6 +674,17 @@ mod tests {
 		assert!(endpoint.is_ok())
 	}
 
+	#[test]
+	fn detects_host_name_urls() {
+		let id = "a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c";
+		assert!(!is_host_name_url(&format!("enode://{}@22.99.55.44:7770", id)));
+		assert!(!is_host_name_url(&format!("enode://{}@[::1]:7770", id)));
+		assert!(!is_host_name_url("22.99.55.44:7770"));
+		assert!(is_host_name_url(&format!("enode://{}@boot.example.com:7770", id)));
+		assert!(is_host_name_url("localhost:7770"));
+		assert!(is_host_name_url("dns://seeds.example.org"));
+	}
+
 	#[test]
 	fn node_parse() {
 		assert!(validate_node_url("enode://a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c@22.99.55.44:7770").is_none());
@@ -683,6 +712,12 @@ mod tests {
 		assert_matches!(node.unwrap_err(), Error::AddressParse);
 	}
 
+	#[test]
+	fn validates_dns_node_lists() {
+		assert!(validate_node_url("dns://seeds.example.org").is_none());
+		assert_matches!(validate_node_url("dns://seeds example"), Some(Error::AddressParse));
+	}
+
 	#[test]
 	fn table_last_contact_order() {
 		let node1 = Node::from_str("enode://a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c@22.99.55.44:7770").unwrap();
@@ -14,8 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
+use std::io;
 use std::net::SocketAddr;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use ansi_term::Colour;
@@ -29,6 +31,7 @@ use network::{
 
 };
 
+use crate::blacklist::{Blacklist, BlacklistEntry};
 use crate::host::Host;
 
 struct HostHandler {
@@ -174,6 +177,51 @@ impl NetworkService {
 		}
 	}
 
+	/// Blacklist a node id or IP range, disconnecting matching peers.
+	///
+	/// While the network is stopped the entry is written straight to the blacklist file,
+	/// which the host loads when it starts.
+	pub fn add_to_blacklist(&self, entry: &str) -> Result<(), Error> {
+		let host = self.host.read();
+		if let Some(ref host) = *host {
+			let io_ctxt = IoContext::new(self.io_service.channel(), 0);
+			host.add_to_blacklist(entry, &io_ctxt)
+		} else {
+			let entry = BlacklistEntry::from_str(entry)?;
+			self.stored_blacklist()?.add(entry)?;
+			Ok(())
+		}
+	}
+
+	/// Remove a blacklist entry.
+	pub fn remove_from_blacklist(&self, entry: &str) -> Result<(), Error> {
+		let host = self.host.read();
+		if let Some(ref host) = *host {
+			host.remove_from_blacklist(entry)
+		} else {
+			let entry = BlacklistEntry::from_str(entry)?;
+			self.stored_blacklist()?.remove(&entry)?;
+			Ok(())
+		}
+	}
+
+	/// Get the blacklist entries.
+	pub fn blacklist(&self) -> Vec<String> {
+		match *self.host.read() {
+			Some(ref host) => host.blacklist(),
+			None => Blacklist::new(self.config.net_config_path.clone()).entries().iter().map(ToString::to_string).collect(),
+		}
+	}
+
+	/// Blacklist stored in the network configuration directory, for changes made while
+	/// the host isn't running. Fails if there is no directory to store it in.
+	fn stored_blacklist(&self) -> Result<Blacklist, Error> {
+		match self.config.net_config_path {
+			Some(ref path) => Ok(Blacklist::new(Some(path.clone()))),
+			None => Err(io::Error::new(io::ErrorKind::NotFound, "No network configuration directory to store the blacklist in").into()),
+		}
+	}
+
 	/// Set the non-reserved peer mode.
 	pub fn set_non_reserved_mode(&self, mode: NonReservedPeerMode) {
 		let host = self.host.read();
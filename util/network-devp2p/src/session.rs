@@ -15,7 +15,7 @@
 // along with Parity Ethereum.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::{io, str};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::SocketAddr;
 use std::time::{Duration, Instant};
 
@@ -42,6 +42,8 @@ use crate::{
 // Timeout must be less than (interval - 1).
 const PING_TIMEOUT: Duration = Duration::from_secs(60);
 const PING_INTERVAL: Duration = Duration::from_secs(120);
+// Number of recent ping round-trip times the reported average is taken over.
+const PING_SAMPLES: usize = 8;
 const MIN_PROTOCOL_VERSION: u32 = 4;
 const MIN_COMPRESSION_PROTOCOL_VERSION: u32 = 5;
 
@@ -65,6 +67,7 @@ pub struct Session {
 	expired: bool,
 	ping_time: Instant,
 	pong_time: Option<Instant>,
+	ping_samples: VecDeque<Duration>,
 	state: State,
 	// Protocol states -- accumulates pending packets until signaled as ready.
 	protocol_states: HashMap<ProtocolId, ProtocolState>,
@@ -106,13 +109,14 @@ const PACKET_LAST: u8 = 0x7f;
 impl Session {
 	/// Create a new session out of completed handshake. This clones the handshake connection object
 	/// and leaves the handshake in limbo to be de-registered from the event loop.
+	/// Outgoing connections start with a legacy auth packet if `legacy_auth` is set.
 	pub fn new<Message>(io: &IoContext<Message>, socket: TcpStream, token: StreamToken, id: Option<&NodeId>,
-		nonce: &H256, host: &HostInfo) -> Result<Session, Error>
+		nonce: &H256, host: &HostInfo, legacy_auth: bool) -> Result<Session, Error>
 		where Message: Send + Clone + Sync + 'static {
 		let originated = id.is_some();
 		let mut handshake = Handshake::new(token, id, socket, nonce).expect("Can't create handshake");
 		let local_addr = handshake.connection.local_addr_str();
-		handshake.start(io, host, originated)?;
+		handshake.start(io, host, originated, legacy_auth)?;
 		Ok(Session {
 			state: State::Handshake(handshake),
 			had_hello: false,
@@ -123,12 +127,14 @@ impl Session {
 				capabilities: Vec::new(),
 				peer_capabilities: Vec::new(),
 				ping: None,
+				ping_average: None,
 				originated,
 				remote_address: "Handshake".to_owned(),
 				local_address: local_addr,
 			},
 			ping_time: Instant::now(),
 			pong_time: None,
+			ping_samples: VecDeque::with_capacity(PING_SAMPLES),
 			expired: false,
 			protocol_states: HashMap::new(),
 			compression: false,
@@ -175,6 +181,14 @@ impl Session {
 		self.expired
 	}
 
+	/// Check if we started the handshake with an EIP-8 auth packet that hasn't been answered.
+	pub fn eip8_auth_unanswered(&self) -> bool {
+		match self.state {
+			State::Handshake(ref h) => h.eip8_auth_unanswered(),
+			_ => false,
+		}
+	}
+
 	/// Check if this session is over and there is nothing to be sent.
 	pub fn done(&self) -> bool {
 		self.expired() && !self.connection().is_sending()
@@ -371,7 +385,7 @@ impl Session {
 			PACKET_PONG => {
 				let time = Instant::now();
 				self.pong_time = Some(time);
-				self.info.ping = Some(time.duration_since(self.ping_time));
+				self.note_ping(time.duration_since(self.ping_time));
 				Ok(SessionData::Continue)
 			},
 			PACKET_GET_PEERS => Ok(SessionData::None), //TODO;
@@ -492,6 +506,17 @@ impl Session {
 		Ok(())
 	}
 
+	/// Records a ping round-trip time and updates the rolling average over the recent ones.
+	fn note_ping(&mut self, rtt: Duration) {
+		if self.ping_samples.len() == PING_SAMPLES {
+			self.ping_samples.pop_front();
+		}
+		self.ping_samples.push_back(rtt);
+		let total: Duration = self.ping_samples.iter().sum();
+		self.info.ping = Some(rtt);
+		self.info.ping_average = Some(total / self.ping_samples.len() as u32);
+	}
+
 	fn send_pong<Message>(&mut self, io: &IoContext<Message>) -> Result<(), Error> where Message: Send + Sync + Clone {
 		self.send_packet(io, None, PACKET_PONG, &EMPTY_LIST_RLP)
 	}
@@ -27,6 +27,7 @@ use parking_lot::Mutex;
 use network::{PeerId, NetworkContext, NetworkProtocolHandler, NetworkConfiguration};
 use ethcore_network_devp2p::NetworkService;
 use parity_crypto::publickey::{Generator, Random};
+use tempdir::TempDir;
 use ethcore_io::TimerToken;
 
 pub struct TestProtocol {
@@ -111,6 +112,26 @@ fn net_start_stop() {
 	service.start().unwrap();
 }
 
+#[test]
+fn net_blacklist_while_stopped() {
+	let tempdir = TempDir::new("").unwrap();
+	let mut config = NetworkConfiguration::new_local();
+	config.net_config_path = Some(tempdir.path().to_str().unwrap().to_owned());
+	let service = NetworkService::new(config, None).unwrap();
+	service.add_to_blacklist("10.0.0.0/8").unwrap();
+	assert_eq!(service.blacklist(), vec!["10.0.0.0/8".to_owned()]);
+
+	// the host picks up entries stored while it was stopped
+	service.start().unwrap();
+	assert_eq!(service.blacklist(), vec!["10.0.0.0/8".to_owned()]);
+	service.stop();
+	service.remove_from_blacklist("10.0.0.0/8").unwrap();
+	assert!(service.blacklist().is_empty());
+
+	let service = NetworkService::new(NetworkConfiguration::new_local(), None).unwrap();
+	assert!(service.add_to_blacklist("10.0.0.0/8").is_err());
+}
+
 #[test]
 fn net_disconnect() {
 	let key1 = Random.generate().unwrap();
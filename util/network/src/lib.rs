@@ -97,6 +97,8 @@ pub enum NetworkIoMessage {
 	DisablePeer(PeerId),
 	/// Network has been started with the host as the given enode.
 	NetworkStarted(String),
+	/// Boot nodes given by host name or DNS node list have been resolved to the given enodes.
+	BootNodesResolved(Vec<String>),
 }
 
 /// Shared session information
@@ -114,6 +116,8 @@ pub struct SessionInfo {
 	pub peer_capabilities: Vec<PeerCapabilityInfo>,
 	/// Peer ping delay
 	pub ping: Option<Duration>,
+	/// Average of the recent peer ping delays
+	pub ping_average: Option<Duration>,
 	/// True if this session was originated by us.
 	pub originated: bool,
 	/// Remote endpoint address of the session
@@ -210,6 +214,12 @@ pub struct NetworkConfiguration {
 	pub min_peers: u32,
 	/// Maximum allowed number of peers
 	pub max_peers: u32,
+	/// Maximum number of peers we connect to. `min_peers` if none
+	pub max_outbound_peers: Option<u32>,
+	/// Maximum number of peers connecting to us. Derived from `min_peers` and `max_peers` if none
+	pub max_inbound_peers: Option<u32>,
+	/// Allow other sockets to listen on the same TCP port (e.g. several nodes behind a load balancer)
+	pub reuse_port: bool,
 	/// Maximum handshakes
 	pub max_handshakes: u32,
 	/// Reserved protocols. Peers with <key> protocol get additional <value> connection slots.
@@ -246,6 +256,9 @@ impl NetworkConfiguration {
 			use_secret: None,
 			min_peers: 25,
 			max_peers: 50,
+			max_outbound_peers: None,
+			max_inbound_peers: None,
+			reuse_port: false,
 			max_handshakes: 64,
 			reserved_protocols: HashMap::new(),
 			ip_filter: IpFilter::default(),
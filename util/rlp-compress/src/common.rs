@@ -20,6 +20,14 @@ lazy_static! {
 	pub static ref BLOCKS_SWAPPER: Swapper<'static> = Swapper::new(COMMON_RLPS, INVALID_RLPS);
 }
 
+lazy_static! {
+	/// RLP of an empty log bloom, carried by every receipt without logs.
+	static ref EMPTY_BLOOM_RLP: Vec<u8> = ::rlp::encode(&vec![0u8; 256]);
+
+	/// Swapper for block receipts compression.
+	pub static ref RECEIPTS_SWAPPER: Swapper<'static> = Swapper::new(&[&EMPTY_BLOOM_RLP[..]], INVALID_RLPS);
+}
+
 static EMPTY_RLPS: &'static [&'static [u8]] = &[
 	// RLP of KECCAK_NULL_RLP
 	&[160, 86, 232, 31, 23, 27, 204, 85, 166, 255, 131, 69, 230, 146, 192, 248, 110, 91, 72, 224, 27, 153, 108, 173, 192, 1, 98, 47, 181, 227, 99, 180, 33],
@@ -17,7 +17,7 @@ use std::cmp;
 use std::collections::HashMap;
 use elastic_array::ElasticArray1024;
 use rlp::{Rlp, RlpStream};
-use common::{SNAPSHOT_SWAPPER, BLOCKS_SWAPPER};
+use common::{SNAPSHOT_SWAPPER, BLOCKS_SWAPPER, RECEIPTS_SWAPPER};
 
 pub fn snapshot_swapper() -> &'static Swapper<'static> {
 	&SNAPSHOT_SWAPPER as &Swapper
@@ -27,6 +27,10 @@ pub fn blocks_swapper() -> &'static Swapper<'static> {
 	&BLOCKS_SWAPPER as &Swapper
 }
 
+pub fn receipts_swapper() -> &'static Swapper<'static> {
+	&RECEIPTS_SWAPPER as &Swapper
+}
+
 /// A trait used to compress rlp.
 pub trait Compressor {
 	/// Get compressed version of given rlp.
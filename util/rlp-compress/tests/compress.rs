@@ -16,7 +16,7 @@
 
 extern crate rlp_compress;
 
-use rlp_compress::{compress, decompress, Swapper, snapshot_swapper, blocks_swapper, Compressor, Decompressor};
+use rlp_compress::{compress, decompress, Swapper, snapshot_swapper, blocks_swapper, receipts_swapper, Compressor, Decompressor};
 
 #[test]
 fn invalid_rlp_swapper() {
@@ -37,6 +37,19 @@ fn simple_compression() {
 	assert_eq!(decompressed.to_vec(), basic_account_rlp);
 }
 
+#[test]
+fn receipt_compression() {
+	// [status, gas used, empty bloom, no logs]
+	let mut receipt_rlp = vec![249, 1, 8, 1, 130, 82, 8, 185, 1, 0];
+	receipt_rlp.extend_from_slice(&[0u8; 256]);
+	receipt_rlp.push(192);
+
+	let compressed = compress(&receipt_rlp, receipts_swapper());
+	assert_eq!(compressed.to_vec(), vec![199, 1, 130, 82, 8, 129, 0, 192]);
+	let decompressed = decompress(&compressed, receipts_swapper());
+	assert_eq!(decompressed.to_vec(), receipt_rlp);
+}
+
 #[test]
 fn nested_list_rlp() {
 	let nested_basic_account_rlp = vec![228, 4, 226, 2, 160, 86, 232, 31, 23, 27, 204, 85, 166, 255, 131, 69, 230, 146, 192, 248, 110, 91, 72, 224, 27, 153, 108, 173, 192, 1, 98, 47, 181, 227, 99, 180, 33];
@@ -69,6 +69,15 @@ pub fn version_data() -> Bytes {
 	s.out()
 }
 
+/// Replaces every `{version}` in block extra data with the version number of this software.
+/// Extra data that isn't valid UTF-8 is returned unchanged.
+pub fn with_version(extra_data: Bytes) -> Bytes {
+	match String::from_utf8(extra_data) {
+		Ok(text) => text.replace("{version}", env!("CARGO_PKG_VERSION")).into_bytes(),
+		Err(e) => e.into_bytes(),
+	}
+}
+
 /// Provide raw information on the package.
 pub fn raw_package_info() -> (&'static str, &'static str, &'static str) {
 	(THIS_TRACK, env!["CARGO_PKG_VERSION"], env!["VERGEN_SHA"])